@@ -0,0 +1,365 @@
+//! Product structure: the assembly tree of a STEP file.
+//!
+//! Assemblies are described by `NEXT_ASSEMBLY_USAGE_OCCURRENCE` entities,
+//! each placing one `PRODUCT_DEFINITION` inside another. The placement, when
+//! present, is attached through
+//!
+//! ```text
+//! CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#rel, #shape)
+//!   #shape = PRODUCT_DEFINITION_SHAPE(_, _, #occurrence)
+//!   #rel   = (... REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#t) ...)
+//!   #t     = ITEM_DEFINED_TRANSFORMATION(_, _, #from, #to)
+//! ```
+//!
+//! This is a read-only walk over the parsed entities.
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use crate::geometry::Axis2Placement3d;
+use crate::parser::{StepFile, StepValue};
+
+/// A row-major 4x4 affine transform.
+pub type Matrix4 = [[f64; 4]; 4];
+
+const IDENTITY: Matrix4 = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// A component (part or sub-assembly) in the assembly tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssemblyNode {
+    /// ID of the component's `PRODUCT_DEFINITION`.
+    pub product_definition: u64,
+    /// Name of the component's `PRODUCT`, or its ID string when unnamed.
+    pub name: String,
+    /// Components placed inside this one; empty for a part.
+    pub children: Vec<AssemblyChild>,
+}
+
+/// One placement of a component inside its parent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssemblyChild {
+    /// ID of the `NEXT_ASSEMBLY_USAGE_OCCURRENCE`.
+    pub occurrence: u64,
+    /// Name of the occurrence, e.g. `Left wheel`; may be empty.
+    pub name: String,
+    /// Placement of the component in the parent's coordinates. Identity when
+    /// the file gives none.
+    pub transform: Matrix4,
+    /// The placed component. A sub-assembly used several times is shared
+    /// between its occurrences rather than copied.
+    pub node: Rc<AssemblyNode>,
+}
+
+impl StepFile {
+    /// The product structure as a tree of named components with their
+    /// relative transforms.
+    ///
+    /// Returns `None` if the file has no `NEXT_ASSEMBLY_USAGE_OCCURRENCE`,
+    /// i.e. it holds a single part. If several top-level assemblies are
+    /// present, the one with the lowest `PRODUCT_DEFINITION` ID is returned.
+    /// Occurrences that would make a component contain itself are skipped.
+    pub fn assembly_tree(&self) -> Option<AssemblyNode> {
+        // Occurrences by parent, in ID order: (occurrence, name, child)
+        let mut usages: HashMap<u64, Vec<(u64, String, u64)>> = HashMap::new();
+        let mut children = HashSet::new();
+        let mut occurrences = self.entities_of_type("NEXT_ASSEMBLY_USAGE_OCCURRENCE");
+        occurrences.sort_by_key(|e| e.id);
+        for occurrence in occurrences {
+            let (Some(parent), Some(child)) = (
+                occurrence.args.get(3).and_then(StepValue::as_entity_ref),
+                occurrence.args.get(4).and_then(StepValue::as_entity_ref),
+            ) else {
+                continue;
+            };
+            let name = string_arg(&occurrence.args, 1).to_string();
+            usages
+                .entry(parent)
+                .or_default()
+                .push((occurrence.id, name, child));
+            children.insert(child);
+        }
+
+        let root = usages.keys().filter(|id| !children.contains(id)).min()?;
+        let walk = AssemblyWalk {
+            file: self,
+            usages: &usages,
+            transforms: self.occurrence_transforms(),
+        };
+        let mut built = HashMap::new();
+        let root = walk.node(*root, &mut built, &mut HashSet::new());
+        Some(Rc::unwrap_or_clone(root))
+    }
+
+    /// Transforms attached to occurrences, keyed by occurrence ID.
+    fn occurrence_transforms(&self) -> HashMap<u64, Matrix4> {
+        let mut transforms = HashMap::new();
+        for cdsr in self.entities_of_type("CONTEXT_DEPENDENT_SHAPE_REPRESENTATION") {
+            let entity_arg = |i: usize| {
+                cdsr.args
+                    .get(i)
+                    .and_then(StepValue::as_entity_ref)
+                    .and_then(|id| self.get(id))
+            };
+            let (Some(relationship), Some(shape)) = (entity_arg(0), entity_arg(1)) else {
+                continue;
+            };
+            let Some(occurrence) = shape.args.get(2).and_then(StepValue::as_entity_ref) else {
+                continue;
+            };
+            if let Some(transform) = self.find_item_transformation(&relationship.args) {
+                transforms.insert(occurrence, transform);
+            }
+        }
+        transforms
+    }
+
+    /// The first `ITEM_DEFINED_TRANSFORMATION` referenced from `args`,
+    /// looking inside the parts of complex entities.
+    fn find_item_transformation(&self, args: &[StepValue]) -> Option<Matrix4> {
+        args.iter().find_map(|arg| match arg {
+            StepValue::EntityRef(id) => {
+                let entity = self.get(*id)?;
+                if entity.type_name != "ITEM_DEFINED_TRANSFORMATION" {
+                    return None;
+                }
+                let placement = |i: usize| {
+                    let id = entity.args.get(i)?.as_entity_ref()?;
+                    self.get(id)?.as_axis2_placement_3d(self)
+                };
+                let (from, to) = (placement(2)?, placement(3)?);
+                Some(multiply(
+                    &placement_matrix(&to),
+                    &invert_rigid(&placement_matrix(&from)),
+                ))
+            }
+            StepValue::Typed { args, .. } | StepValue::List(args) => {
+                self.find_item_transformation(args)
+            }
+            _ => None,
+        })
+    }
+
+    /// Name of the `PRODUCT` behind a `PRODUCT_DEFINITION`, falling back to
+    /// its ID string.
+    fn product_name(&self, product_definition: u64) -> String {
+        let product = self
+            .get(product_definition)
+            .and_then(|pd| self.get(pd.args.get(2)?.as_entity_ref()?))
+            .and_then(|formation| self.get(formation.args.get(2)?.as_entity_ref()?));
+        match product {
+            Some(product) => match string_arg(&product.args, 1) {
+                "" => string_arg(&product.args, 0).to_string(),
+                name => name.to_string(),
+            },
+            None => String::new(),
+        }
+    }
+}
+
+struct AssemblyWalk<'a> {
+    file: &'a StepFile,
+    usages: &'a HashMap<u64, Vec<(u64, String, u64)>>,
+    transforms: HashMap<u64, Matrix4>,
+}
+
+impl AssemblyWalk<'_> {
+    /// Build the node for a product definition, reusing nodes already built.
+    /// `open` holds the definitions on the current path, to break cycles.
+    fn node(
+        &self,
+        id: u64,
+        built: &mut HashMap<u64, Rc<AssemblyNode>>,
+        open: &mut HashSet<u64>,
+    ) -> Rc<AssemblyNode> {
+        if let Some(node) = built.get(&id) {
+            return node.clone();
+        }
+        open.insert(id);
+        let mut children = Vec::new();
+        for (occurrence, name, child) in self.usages.get(&id).into_iter().flatten() {
+            if open.contains(child) {
+                continue;
+            }
+            children.push(AssemblyChild {
+                occurrence: *occurrence,
+                name: name.clone(),
+                transform: self.transforms.get(occurrence).copied().unwrap_or(IDENTITY),
+                node: self.node(*child, built, open),
+            });
+        }
+        open.remove(&id);
+
+        let node = Rc::new(AssemblyNode {
+            product_definition: id,
+            name: self.file.product_name(id),
+            children,
+        });
+        built.insert(id, node.clone());
+        node
+    }
+}
+
+fn string_arg(args: &[StepValue], i: usize) -> &str {
+    args.get(i)
+        .and_then(StepValue::as_string)
+        .unwrap_or_default()
+}
+
+/// The local-to-parent matrix of a placement, defaulting its axes as
+/// Part 42 does and making them orthonormal.
+fn placement_matrix(placement: &Axis2Placement3d) -> Matrix4 {
+    let normalize = |v: [f64; 3]| {
+        let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+        if len > 0.0 {
+            v.map(|c| c / len)
+        } else {
+            v
+        }
+    };
+    let dot = |a: [f64; 3], b: [f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+    let cross = |a: [f64; 3], b: [f64; 3]| {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    };
+
+    let z = normalize(placement.axis.unwrap_or([0.0, 0.0, 1.0]));
+    let r = placement.ref_direction.unwrap_or([1.0, 0.0, 0.0]);
+    let d = dot(r, z);
+    let x = normalize([r[0] - d * z[0], r[1] - d * z[1], r[2] - d * z[2]]);
+    let y = cross(z, x);
+    let p = placement.location;
+    [
+        [x[0], y[0], z[0], p[0]],
+        [x[1], y[1], z[1], p[1]],
+        [x[2], y[2], z[2], p[2]],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+/// Inverse of a rotation-plus-translation matrix.
+fn invert_rigid(m: &Matrix4) -> Matrix4 {
+    let mut inv = IDENTITY;
+    for i in 0..3 {
+        for j in 0..3 {
+            inv[i][j] = m[j][i];
+        }
+        inv[i][3] = -(0..3).map(|k| m[k][i] * m[k][3]).sum::<f64>();
+    }
+    inv
+}
+
+fn multiply(a: &Matrix4, b: &Matrix4) -> Matrix4 {
+    let mut out = [[0.0; 4]; 4];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            *cell = (0..4).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    /// A robot with two instances of an arm sub-assembly, each holding a link.
+    const ASSEMBLY: &str = r#"
+ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1 = APPLICATION_CONTEXT('automotive design');
+#2 = PRODUCT_CONTEXT('', #1, 'mechanical');
+#3 = PRODUCT_DEFINITION_CONTEXT('part definition', #1, 'design');
+#10 = PRODUCT('robot', 'Robot', '', (#2));
+#11 = PRODUCT_DEFINITION_FORMATION('', '', #10);
+#12 = PRODUCT_DEFINITION('design', '', #11, #3);
+#20 = PRODUCT('arm', 'Arm', '', (#2));
+#21 = PRODUCT_DEFINITION_FORMATION('', '', #20);
+#22 = PRODUCT_DEFINITION('design', '', #21, #3);
+#30 = PRODUCT('link', '', '', (#2));
+#31 = PRODUCT_DEFINITION_FORMATION('', '', #30);
+#32 = PRODUCT_DEFINITION('design', '', #31, #3);
+#40 = CARTESIAN_POINT('', (0.0, 0.0, 0.0));
+#41 = DIRECTION('', (0.0, 0.0, 1.0));
+#42 = DIRECTION('', (1.0, 0.0, 0.0));
+#43 = AXIS2_PLACEMENT_3D('', #40, #41, #42);
+#44 = CARTESIAN_POINT('', (100.0, 0.0, 0.0));
+#45 = DIRECTION('', (0.0, 1.0, 0.0));
+#46 = AXIS2_PLACEMENT_3D('', #44, #41, #45);
+#47 = CARTESIAN_POINT('', (0.0, 0.0, 50.0));
+#48 = AXIS2_PLACEMENT_3D('', #47, $, $);
+#49 = SHAPE_REPRESENTATION('', (#43), #3);
+#50 = NEXT_ASSEMBLY_USAGE_OCCURRENCE('arm-1', 'Left arm', '', #12, #22, $);
+#51 = NEXT_ASSEMBLY_USAGE_OCCURRENCE('arm-2', 'Right arm', '', #12, #22, $);
+#52 = NEXT_ASSEMBLY_USAGE_OCCURRENCE('link-1', 'Link', '', #22, #32, $);
+#60 = PRODUCT_DEFINITION_SHAPE('', '', #51);
+#61 = ITEM_DEFINED_TRANSFORMATION('', '', #43, #46);
+#62 = (REPRESENTATION_RELATIONSHIP('', '', #49, #49) REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#61) SHAPE_REPRESENTATION_RELATIONSHIP());
+#63 = CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#62, #60);
+#64 = PRODUCT_DEFINITION_SHAPE('', '', #52);
+#65 = ITEM_DEFINED_TRANSFORMATION('', '', #43, #48);
+#66 = (REPRESENTATION_RELATIONSHIP('', '', #49, #49) REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#65) SHAPE_REPRESENTATION_RELATIONSHIP());
+#67 = CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#66, #64);
+ENDSEC;
+END-ISO-10303-21;
+"#;
+
+    #[test]
+    fn test_assembly_tree() {
+        let file = parse(ASSEMBLY.as_bytes()).unwrap();
+        let root = file.assembly_tree().unwrap();
+        assert_eq!(root.name, "Robot");
+        assert_eq!(root.product_definition, 12);
+
+        let names: Vec<_> = root.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Left arm", "Right arm"]);
+        let (left, right) = (&root.children[0], &root.children[1]);
+        assert_eq!(left.node.name, "Arm");
+        assert!(Rc::ptr_eq(&left.node, &right.node));
+
+        // The left arm has no placement; the right one is turned 90° about
+        // Z and moved 100 along X
+        assert_eq!(left.transform, IDENTITY);
+        let expected = [
+            [0.0, -1.0, 0.0, 100.0],
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        for (row, expected_row) in right.transform.iter().zip(expected) {
+            for (a, b) in row.iter().zip(expected_row) {
+                assert!((a - b).abs() < 1e-12, "{:?}", right.transform);
+            }
+        }
+
+        // Unnamed products fall back to their ID
+        let link = &left.node.children[0];
+        assert_eq!(link.node.name, "link");
+        assert!(link.node.children.is_empty());
+        assert_eq!(link.transform[2][3], 50.0);
+    }
+
+    #[test]
+    fn test_single_part_has_no_tree() {
+        let data = r#"
+ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1 = PRODUCT('bolt', 'Bolt', '', ());
+ENDSEC;
+END-ISO-10303-21;
+"#;
+        assert!(parse(data.as_bytes()).unwrap().assembly_tree().is_none());
+    }
+}
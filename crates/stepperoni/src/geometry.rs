@@ -0,0 +1,120 @@
+//! Typed accessors for common STEP geometry entities.
+//!
+//! These read the raw [`StepEntity`] arguments of well-known geometry types
+//! without building a full semantic model. Each accessor returns `None` when
+//! the entity type or argument layout doesn't match.
+
+use crate::parser::{StepEntity, StepFile, StepValue};
+
+/// A resolved `AXIS2_PLACEMENT_3D`: a location with optional axis and
+/// reference directions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Axis2Placement3d {
+    /// Origin of the placement.
+    pub location: [f64; 3],
+    /// Z axis direction, if given.
+    pub axis: Option<[f64; 3]>,
+    /// X axis direction, if given.
+    pub ref_direction: Option<[f64; 3]>,
+}
+
+impl StepEntity {
+    /// Coordinates of a `CARTESIAN_POINT('name', (x, y, z))`.
+    pub fn as_cartesian_point(&self) -> Option<[f64; 3]> {
+        self.triple_arg("CARTESIAN_POINT")
+    }
+
+    /// Ratios of a `DIRECTION('name', (x, y, z))`.
+    pub fn as_direction(&self) -> Option<[f64; 3]> {
+        self.triple_arg("DIRECTION")
+    }
+
+    /// An `AXIS2_PLACEMENT_3D('name', #location, #axis, #ref_direction)`,
+    /// with its referenced point and directions resolved through `file`.
+    ///
+    /// The axis and reference direction may be `$`, in which case they are
+    /// `None`. A dangling or mistyped reference yields `None` overall.
+    pub fn as_axis2_placement_3d(&self, file: &StepFile) -> Option<Axis2Placement3d> {
+        let [_, location, axis, ref_direction] = self.args_of("AXIS2_PLACEMENT_3D")? else {
+            return None;
+        };
+        let optional_direction = |value: &StepValue| match value {
+            StepValue::Null => Some(None),
+            value => file.get(value.as_entity_ref()?)?.as_direction().map(Some),
+        };
+        Some(Axis2Placement3d {
+            location: file.get(location.as_entity_ref()?)?.as_cartesian_point()?,
+            axis: optional_direction(axis)?,
+            ref_direction: optional_direction(ref_direction)?,
+        })
+    }
+
+    fn args_of(&self, type_name: &str) -> Option<&[StepValue]> {
+        (self.type_name == type_name).then_some(self.args.as_slice())
+    }
+
+    fn triple_arg(&self, type_name: &str) -> Option<[f64; 3]> {
+        let [_, coords] = self.args_of(type_name)? else {
+            return None;
+        };
+        let [x, y, z] = coords.as_list()? else {
+            return None;
+        };
+        Some([x.as_real()?, y.as_real()?, z.as_real()?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+
+    const INPUT: &str = r#"
+ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1 = CARTESIAN_POINT('origin', (1.0, 2.5, -3.0));
+#2 = DIRECTION('z', (0.0, 0.0, 1.0));
+#3 = DIRECTION('x', (1.0, 0.0, 0.0));
+#4 = AXIS2_PLACEMENT_3D('', #1, #2, #3);
+#5 = AXIS2_PLACEMENT_3D('', #1, $, $);
+#6 = CARTESIAN_POINT('2d', (1.0, 2.0));
+#7 = AXIS2_PLACEMENT_3D('', #2, #2, #3);
+ENDSEC;
+END-ISO-10303-21;
+"#;
+
+    #[test]
+    fn test_point_and_direction() {
+        let file = parse(INPUT.as_bytes()).unwrap();
+
+        assert_eq!(
+            file.get(1).unwrap().as_cartesian_point(),
+            Some([1.0, 2.5, -3.0])
+        );
+        assert_eq!(file.get(2).unwrap().as_direction(), Some([0.0, 0.0, 1.0]));
+
+        // Wrong type or arity
+        assert_eq!(file.get(1).unwrap().as_direction(), None);
+        assert_eq!(file.get(2).unwrap().as_cartesian_point(), None);
+        assert_eq!(file.get(6).unwrap().as_cartesian_point(), None);
+    }
+
+    #[test]
+    fn test_axis2_placement_3d() {
+        let file = parse(INPUT.as_bytes()).unwrap();
+
+        let placement = file.get(4).unwrap().as_axis2_placement_3d(&file).unwrap();
+        assert_eq!(placement.location, [1.0, 2.5, -3.0]);
+        assert_eq!(placement.axis, Some([0.0, 0.0, 1.0]));
+        assert_eq!(placement.ref_direction, Some([1.0, 0.0, 0.0]));
+
+        let defaults = file.get(5).unwrap().as_axis2_placement_3d(&file).unwrap();
+        assert_eq!(defaults.axis, None);
+        assert_eq!(defaults.ref_direction, None);
+
+        // Location referencing a direction
+        assert!(file.get(7).unwrap().as_axis2_placement_3d(&file).is_none());
+        assert!(file.get(1).unwrap().as_axis2_placement_3d(&file).is_none());
+    }
+}
@@ -9,6 +9,7 @@
 //! let step_data = br#"ISO-10303-21;
 //! HEADER;
 //! FILE_DESCRIPTION(('Example'), '2;1');
+//! FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));
 //! ENDSEC;
 //! DATA;
 //! #1 = CARTESIAN_POINT('origin', (0.0, 0.0, 0.0));
@@ -31,15 +32,23 @@
 //! // Find all entities of a type
 //! let directions = file.entities_of_type("DIRECTION");
 //! assert_eq!(directions.len(), 1);
+//!
+//! // Read header metadata
+//! assert_eq!(file.header().description, ["Example"]);
+//! assert_eq!(file.header().schema, ["AUTOMOTIVE_DESIGN"]);
 //! ```
 
+mod assembly;
 mod error;
+mod geometry;
 mod lexer;
 mod parser;
 
+pub use assembly::{AssemblyChild, AssemblyNode, Matrix4};
 pub use error::StepError;
+pub use geometry::Axis2Placement3d;
 pub use lexer::{Lexer, Position, SpannedToken, Token};
-pub use parser::{Parser, StepEntity, StepFile, StepValue};
+pub use parser::{Parser, StepEntity, StepFile, StepHeader, StepValue};
 
 /// Parse a STEP file from bytes.
 ///
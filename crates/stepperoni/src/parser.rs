@@ -62,6 +62,46 @@ impl StepValue {
         }
     }
 
+    /// Try to get as a number, unwrapping measure values.
+    ///
+    /// Like [`Self::as_real`], but also accepts a typed measure such as
+    /// `LENGTH_MEASURE(2.5)` or `POSITIVE_PLANE_ANGLE_MEASURE(90)`, as found
+    /// in tolerances and unit definitions. The value is returned as written;
+    /// no unit conversion is applied.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            StepValue::Typed { type_name, args } if type_name.ends_with("_MEASURE") => {
+                match args.as_slice() {
+                    [value] => value.as_f64(),
+                    _ => None,
+                }
+            }
+            _ => self.as_real(),
+        }
+    }
+
+    /// Try to get as an integer, also accepting reals with no fractional
+    /// part (e.g. `3.0`).
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            StepValue::Integer(v) => Some(*v),
+            StepValue::Real(v) if v.fract() == 0.0 && v.abs() < i64::MAX as f64 => Some(*v as i64),
+            _ => None,
+        }
+    }
+
+    /// Try to get as a boolean or logical: `.T.`/`.TRUE.` or
+    /// `.F.`/`.FALSE.`.
+    ///
+    /// The logical `.U.` (unknown) gives `None`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.as_enum()? {
+            "T" | "TRUE" => Some(true),
+            "F" | "FALSE" => Some(false),
+            _ => None,
+        }
+    }
+
     /// Try to get as a string.
     pub fn as_string(&self) -> Option<&str> {
         match self {
@@ -108,16 +148,104 @@ pub struct StepEntity {
     pub args: Vec<StepValue>,
 }
 
+/// File metadata from the standard HEADER section entities.
+///
+/// Fields missing from the file (or given as `$`) are left empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StepHeader {
+    /// Informal description of the contents (`FILE_DESCRIPTION`).
+    pub description: Vec<String>,
+    /// Conformance level, e.g. `2;1` (`FILE_DESCRIPTION`).
+    pub implementation_level: String,
+    /// File name (`FILE_NAME`).
+    pub name: String,
+    /// Creation time, ISO 8601 (`FILE_NAME`).
+    pub time_stamp: String,
+    /// Author names and addresses (`FILE_NAME`).
+    pub author: Vec<String>,
+    /// Organizations of the authors (`FILE_NAME`).
+    pub organization: Vec<String>,
+    /// System that wrote the file (`FILE_NAME`).
+    pub preprocessor_version: String,
+    /// System the data came from (`FILE_NAME`).
+    pub originating_system: String,
+    /// Person who authorized sending the file (`FILE_NAME`).
+    pub authorization: String,
+    /// Application protocol schemas, e.g. `AUTOMOTIVE_DESIGN` (`FILE_SCHEMA`).
+    pub schema: Vec<String>,
+}
+
+impl StepHeader {
+    /// Collect the metadata from parsed header section entities.
+    pub fn from_entities(entities: &[StepEntity]) -> Self {
+        let string = |v: Option<&StepValue>| {
+            v.and_then(StepValue::as_string)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let strings = |v: Option<&StepValue>| -> Vec<String> {
+            v.and_then(StepValue::as_list)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|s| s.as_string().map(str::to_string))
+                .collect()
+        };
+
+        let mut header = StepHeader::default();
+        for entity in entities {
+            let arg = |i: usize| entity.args.get(i);
+            match entity.type_name.as_str() {
+                "FILE_DESCRIPTION" => {
+                    header.description = strings(arg(0));
+                    header.implementation_level = string(arg(1));
+                }
+                "FILE_NAME" => {
+                    header.name = string(arg(0));
+                    header.time_stamp = string(arg(1));
+                    header.author = strings(arg(2));
+                    header.organization = strings(arg(3));
+                    header.preprocessor_version = string(arg(4));
+                    header.originating_system = string(arg(5));
+                    header.authorization = string(arg(6));
+                }
+                "FILE_SCHEMA" => header.schema = strings(arg(0)),
+                _ => {}
+            }
+        }
+        header
+    }
+}
+
 /// The complete parsed content of a STEP file.
 #[derive(Debug, Clone)]
 pub struct StepFile {
     /// Header section contents.
-    pub header: Vec<StepEntity>,
+    header_entities: Vec<StepEntity>,
     /// Data section entities, indexed by ID.
     pub entities: HashMap<u64, StepEntity>,
+    /// Metadata collected from `header_entities`.
+    metadata: StepHeader,
 }
 
 impl StepFile {
+    /// File metadata from the header section: description, author,
+    /// originating system, schema and so on.
+    pub fn header(&self) -> &StepHeader {
+        &self.metadata
+    }
+
+    /// Raw header section entities, in file order.
+    pub fn header_entities(&self) -> &[StepEntity] {
+        &self.header_entities
+    }
+
+    /// Replace the header section entities, re-reading the metadata
+    /// returned by [`StepFile::header`] from them.
+    pub fn set_header_entities(&mut self, entities: Vec<StepEntity>) {
+        self.metadata = StepHeader::from_entities(&entities);
+        self.header_entities = entities;
+    }
+
     /// Get an entity by ID.
     pub fn get(&self, id: u64) -> Option<&StepEntity> {
         self.entities.get(&id)
@@ -185,7 +313,12 @@ impl Parser {
             }
         }
 
-        Ok(StepFile { header, entities })
+        let metadata = StepHeader::from_entities(&header);
+        Ok(StepFile {
+            header_entities: header,
+            entities,
+            metadata,
+        })
     }
 
     fn parse_section_entities(&mut self) -> Result<Vec<StepEntity>, StepError> {
@@ -441,7 +574,7 @@ ENDSEC;
 END-ISO-10303-21;
 "#;
         let file = Parser::parse(input.as_bytes()).unwrap();
-        assert_eq!(file.header.len(), 1);
+        assert_eq!(file.header_entities().len(), 1);
         assert_eq!(file.entities.len(), 2);
 
         let p1 = file.get(1).unwrap();
@@ -454,6 +587,42 @@ END-ISO-10303-21;
         assert_eq!(coords[0].as_real(), Some(0.0));
     }
 
+    #[test]
+    fn test_header_metadata() {
+        let input = r#"
+ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('Example', 'second line'), '2;1');
+FILE_NAME('part.stp', '2024-01-01T12:00:00', ('Ada'), ('ACME'), 'vcad', 'CAD 1.0', $);
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));
+ENDSEC;
+DATA;
+ENDSEC;
+END-ISO-10303-21;
+"#;
+        let file = Parser::parse(input.as_bytes()).unwrap();
+        let header = file.header();
+        assert_eq!(header.description, ["Example", "second line"]);
+        assert_eq!(header.implementation_level, "2;1");
+        assert_eq!(header.name, "part.stp");
+        assert_eq!(header.time_stamp, "2024-01-01T12:00:00");
+        assert_eq!(header.author, ["Ada"]);
+        assert_eq!(header.organization, ["ACME"]);
+        assert_eq!(header.originating_system, "CAD 1.0");
+        assert_eq!(header.authorization, "");
+        assert_eq!(
+            header.schema,
+            ["AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }"]
+        );
+
+        let mut empty =
+            Parser::parse(b"ISO-10303-21;\nHEADER;\nENDSEC;\nDATA;\nENDSEC;\n").unwrap();
+        assert_eq!(*empty.header(), StepHeader::default());
+
+        empty.set_header_entities(file.header_entities().to_vec());
+        assert_eq!(empty.header(), file.header());
+    }
+
     #[test]
     fn test_parse_nested_list() {
         let input = r#"
@@ -546,4 +715,31 @@ END-ISO-10303-21;
         let point = file.get(2).unwrap();
         assert_eq!(point.type_name, "CARTESIAN_POINT");
     }
+
+    #[test]
+    fn test_numeric_coercion() {
+        assert_eq!(StepValue::Integer(0).as_f64(), Some(0.0));
+        assert_eq!(StepValue::Real(0.0).as_f64(), Some(0.0));
+        let measure = StepValue::Typed {
+            type_name: "LENGTH_MEASURE".into(),
+            args: vec![StepValue::Real(2.5)],
+        };
+        assert_eq!(measure.as_f64(), Some(2.5));
+        assert_eq!(measure.as_real(), None);
+        assert_eq!(StepValue::String("0".into()).as_f64(), None);
+
+        assert_eq!(StepValue::Real(3.0).as_i64(), Some(3));
+        assert_eq!(StepValue::Real(3.5).as_i64(), None);
+        assert_eq!(StepValue::Real(3.0).as_integer(), None);
+        assert_eq!(StepValue::Integer(-7).as_i64(), Some(-7));
+    }
+
+    #[test]
+    fn test_as_bool() {
+        assert_eq!(StepValue::Enum("T".into()).as_bool(), Some(true));
+        assert_eq!(StepValue::Enum("F".into()).as_bool(), Some(false));
+        assert_eq!(StepValue::Enum("TRUE".into()).as_bool(), Some(true));
+        assert_eq!(StepValue::Enum("U".into()).as_bool(), None);
+        assert_eq!(StepValue::Integer(1).as_bool(), None);
+    }
 }
@@ -2,26 +2,98 @@
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Margin, Position, Rect},
+    Terminal,
+};
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     io::{self, Stdout},
     path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, OnceLock,
+    },
     time::Duration,
 };
-use vcad_ir::{CsgOp, Document, Node, NodeId, SceneEntry, Vec3};
+use vcad_ir::{
+    BoxOrigin, CsgOp, Document, MassProperties, MaterialUsage, Node, NodeId, SceneEntry,
+    TextAlignment, Vec3, VolumeProperties,
+};
 
-use crate::render::{Camera, RenderBuffer, Triangle};
+use crate::render::{
+    Camera, Projection, RenderBuffer, RenderConfig, Triangle, Vec3 as RenderVec3, ViewPreset,
+};
 use crate::ui;
 
 /// Mesh data from evaluation.
 pub struct EvaluatedMesh {
+    /// Scene root the mesh was evaluated from.
+    pub root: NodeId,
     pub vertices: Vec<f32>,
     pub indices: Vec<u32>,
+    /// `(key, value)` tags from the root's subtree, outermost first.
+    pub tags: Vec<(String, String)>,
+}
+
+impl EvaluatedMesh {
+    /// Debug tint from a `color` tag in `#rrggbb` form, if any.
+    pub fn tag_color(&self) -> Option<[u8; 3]> {
+        let (_, value) = self.tags.iter().find(|(key, _)| key == "color")?;
+        let hex = value.strip_prefix('#')?;
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        Some([channel(0)?, channel(2)?, channel(4)?])
+    }
+}
+
+/// Most document states kept in the history.
+const MAX_HISTORY: usize = 100;
+
+/// Camera orbit per viewport pixel dragged, in degrees.
+const ORBIT_DEGREES_PER_PIXEL: f32 = 1.0;
+
+/// Camera distance factor per scroll wheel step towards the scene.
+const SCROLL_ZOOM: f32 = 0.9;
+
+/// Viewport pixels per terminal cell (braille dots).
+const CELL_PIXELS: (f32, f32) = (2.0, 4.0);
+
+/// A left-button press that started in the viewport.
+struct Drag {
+    /// Cell the mouse was last seen at.
+    last: (u16, u16),
+    /// Whether the mouse moved since the press, making it an orbit rather
+    /// than a click.
+    moved: bool,
+}
+
+/// A document state in the undo history.
+struct Snapshot {
+    document: Document,
+    /// Status message of the action that produced this state.
+    label: String,
+}
+
+/// One row of the history panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// Position to pass to [`App::jump_to`].
+    pub index: usize,
+    /// Status message of the action that produced this state.
+    pub label: String,
+    /// Whether this is the state currently shown.
+    pub current: bool,
 }
 
 /// Application state.
@@ -32,6 +104,10 @@ pub struct App {
     pub selected: HashSet<NodeId>,
     /// Camera for 3D viewport.
     pub camera: Camera,
+    /// Viewport rendering quality.
+    pub render_config: RenderConfig,
+    /// Width over height of the rendered viewport, used to fit the view.
+    pub viewport_aspect: f32,
     /// Whether the app is running.
     pub running: bool,
     /// Command input buffer.
@@ -42,10 +118,17 @@ pub struct App {
     pub status: String,
     /// Cached evaluated meshes.
     pub meshes: Vec<EvaluatedMesh>,
-    /// Undo stack.
-    undo_stack: Vec<Document>,
-    /// Redo stack.
-    redo_stack: Vec<Document>,
+    /// Warnings from the last evaluation, shown on the status line.
+    pub warnings: Vec<String>,
+    /// Whether the history panel is shown.
+    pub show_history: bool,
+    /// Left-button press in the viewport, while the button is held.
+    drag: Option<Drag>,
+    /// Document states, oldest first. Entries after `history_index` can be
+    /// redone.
+    history: Vec<Snapshot>,
+    /// Index of the current state in `history`.
+    history_index: usize,
     /// Next node ID.
     next_node_id: NodeId,
     /// File path if opened from file.
@@ -57,24 +140,36 @@ impl App {
     pub fn new(file_path: Option<PathBuf>) -> Result<Self> {
         let document = if let Some(ref path) = file_path {
             let json = std::fs::read_to_string(path)?;
-            Document::from_json(&json)?
+            Document::from_json_migrated(&json)?
         } else {
             Document::new()
         };
 
         let next_node_id = document.nodes.keys().copied().max().unwrap_or(0) + 1;
+        let initial = Snapshot {
+            document: document.clone(),
+            label: match &file_path {
+                Some(path) => format!("Opened {}", path.display()),
+                None => "New document".to_string(),
+            },
+        };
 
         let mut app = Self {
             document,
             selected: HashSet::new(),
             camera: Camera::default(),
+            render_config: RenderConfig::default(),
+            viewport_aspect: 2.0,
             running: true,
             command_input: String::new(),
             command_mode: false,
             status: "Ready".to_string(),
             meshes: Vec::new(),
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
+            warnings: Vec::new(),
+            show_history: false,
+            drag: None,
+            history: vec![initial],
+            history_index: 0,
             next_node_id,
             file_path,
         };
@@ -92,22 +187,38 @@ impl App {
         id
     }
 
-    /// Push current state to undo stack.
-    fn push_undo(&mut self) {
-        self.undo_stack.push(self.document.clone());
-        self.redo_stack.clear();
-        // Limit undo stack size
-        if self.undo_stack.len() > 100 {
-            self.undo_stack.remove(0);
+    /// Evaluate an edited document and record it as a new history state.
+    ///
+    /// If evaluation fails the edit is rolled back to the current history
+    /// state, so history only ever holds documents that evaluated.
+    fn evaluate_and_record(&mut self, label: String) -> Result<()> {
+        if let Err(err) = self.evaluate() {
+            self.restore(self.history_index)?;
+            return Err(err);
         }
+        self.record(label);
+        Ok(())
+    }
+
+    /// Record the current document as a new history state, discarding any
+    /// states that could have been redone.
+    fn record(&mut self, label: String) {
+        self.history.truncate(self.history_index + 1);
+        self.history.push(Snapshot {
+            document: self.document.clone(),
+            label: label.clone(),
+        });
+        if self.history.len() > MAX_HISTORY + 1 {
+            self.history.remove(0);
+        }
+        self.history_index = self.history.len() - 1;
+        self.status = label;
     }
 
     /// Undo the last action.
     pub fn undo(&mut self) -> Result<()> {
-        if let Some(prev) = self.undo_stack.pop() {
-            self.redo_stack.push(self.document.clone());
-            self.document = prev;
-            self.evaluate()?;
+        if self.history_index > 0 {
+            self.restore(self.history_index - 1)?;
             self.status = "Undo".to_string();
         }
         Ok(())
@@ -115,18 +226,50 @@ impl App {
 
     /// Redo the last undone action.
     pub fn redo(&mut self) -> Result<()> {
-        if let Some(next) = self.redo_stack.pop() {
-            self.undo_stack.push(self.document.clone());
-            self.document = next;
-            self.evaluate()?;
+        if self.history_index + 1 < self.history.len() {
+            self.restore(self.history_index + 1)?;
             self.status = "Redo".to_string();
         }
         Ok(())
     }
 
+    /// Recorded document states, oldest first.
+    pub fn history_entries(&self) -> Vec<HistoryEntry> {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(index, snapshot)| HistoryEntry {
+                index,
+                label: snapshot.label.clone(),
+                current: index == self.history_index,
+            })
+            .collect()
+    }
+
+    /// Jump directly to a history state.
+    ///
+    /// States before `index` become undoable and states after it redoable,
+    /// as if undo or redo had been pressed repeatedly.
+    pub fn jump_to(&mut self, index: usize) -> Result<()> {
+        if index >= self.history.len() {
+            anyhow::bail!("No history entry {}", index);
+        }
+        self.restore(index)?;
+        self.status = format!("Jumped to: {}", self.history[index].label);
+        Ok(())
+    }
+
+    /// Make history state `index` the current document.
+    fn restore(&mut self, index: usize) -> Result<()> {
+        self.document = self.history[index].document.clone();
+        self.history_index = index;
+        self.selected
+            .retain(|id| self.document.roots.iter().any(|e| e.root == *id));
+        self.evaluate()
+    }
+
     /// Add a cube primitive.
     pub fn add_cube(&mut self, size: f64) -> Result<NodeId> {
-        self.push_undo();
         let id = self.alloc_node_id();
         self.document.nodes.insert(
             id,
@@ -135,7 +278,9 @@ impl App {
                 name: Some(format!("Cube {}", id)),
                 op: CsgOp::Cube {
                     size: Vec3::new(size, size, size),
+                    origin: BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
         self.document.roots.push(SceneEntry {
@@ -143,14 +288,12 @@ impl App {
             material: "default".to_string(),
             visible: None,
         });
-        self.evaluate()?;
-        self.status = format!("Added cube {}", id);
+        self.evaluate_and_record(format!("Added cube {}", id))?;
         Ok(id)
     }
 
     /// Add a cylinder primitive.
     pub fn add_cylinder(&mut self, radius: f64, height: f64) -> Result<NodeId> {
-        self.push_undo();
         let id = self.alloc_node_id();
         self.document.nodes.insert(
             id,
@@ -162,6 +305,7 @@ impl App {
                     height,
                     segments: 32,
                 },
+                attributes: None,
             },
         );
         self.document.roots.push(SceneEntry {
@@ -169,14 +313,12 @@ impl App {
             material: "default".to_string(),
             visible: None,
         });
-        self.evaluate()?;
-        self.status = format!("Added cylinder {}", id);
+        self.evaluate_and_record(format!("Added cylinder {}", id))?;
         Ok(id)
     }
 
     /// Add a sphere primitive.
     pub fn add_sphere(&mut self, radius: f64) -> Result<NodeId> {
-        self.push_undo();
         let id = self.alloc_node_id();
         self.document.nodes.insert(
             id,
@@ -187,6 +329,7 @@ impl App {
                     radius,
                     segments: 32,
                 },
+                attributes: None,
             },
         );
         self.document.roots.push(SceneEntry {
@@ -194,8 +337,7 @@ impl App {
             material: "default".to_string(),
             visible: None,
         });
-        self.evaluate()?;
-        self.status = format!("Added sphere {}", id);
+        self.evaluate_and_record(format!("Added sphere {}", id))?;
         Ok(id)
     }
 
@@ -204,23 +346,21 @@ impl App {
         if self.selected.is_empty() {
             return Ok(());
         }
-        self.push_undo();
 
         // Remove from roots
         self.document
             .roots
             .retain(|e| !self.selected.contains(&e.root));
 
-        // Remove nodes (only if they're not referenced by remaining roots)
-        // For simplicity, just remove the root nodes for now
+        // Remove the root nodes; the graphs below them are reclaimed by
+        // `Document::gc` on save
         for &id in &self.selected {
             self.document.nodes.remove(&id);
         }
 
         let count = self.selected.len();
         self.selected.clear();
-        self.evaluate()?;
-        self.status = format!("Deleted {} part(s)", count);
+        self.evaluate_and_record(format!("Deleted {} part(s)", count))?;
         Ok(())
     }
 
@@ -229,7 +369,6 @@ impl App {
         if self.selected.is_empty() {
             return Ok(());
         }
-        self.push_undo();
 
         for &selected_id in &self.selected.clone() {
             // Find the root entry for this selection
@@ -256,6 +395,7 @@ impl App {
                             child: old_root,
                             offset: Vec3::new(dx, dy, dz),
                         },
+                        attributes: None,
                     },
                 );
 
@@ -268,14 +408,16 @@ impl App {
             }
         }
 
-        self.evaluate()?;
-        self.status = format!("Translated by ({}, {}, {})", dx, dy, dz);
+        self.evaluate_and_record(format!("Translated by ({}, {}, {})", dx, dy, dz))?;
         Ok(())
     }
 
     /// Save the document to file.
+    ///
+    /// Nodes no longer reachable from the scene are dropped first.
     pub fn save(&mut self) -> Result<()> {
         if let Some(ref path) = self.file_path {
+            self.document.gc();
             let json = self.document.to_json()?;
             std::fs::write(path, json)?;
             self.status = format!("Saved to {}", path.display());
@@ -286,7 +428,10 @@ impl App {
     }
 
     /// Save the document to a new file.
+    ///
+    /// Nodes no longer reachable from the scene are dropped first.
     pub fn save_as(&mut self, path: PathBuf) -> Result<()> {
+        self.document.gc();
         let json = self.document.to_json()?;
         std::fs::write(&path, json)?;
         self.file_path = Some(path.clone());
@@ -310,18 +455,176 @@ impl App {
         Ok(())
     }
 
+    /// Axis-aligned bounds of the evaluated meshes as `(min, max)`, or `None`
+    /// if nothing is visible.
+    pub fn mesh_bounds(&self) -> Option<([f32; 3], [f32; 3])> {
+        let mut points = self
+            .meshes
+            .iter()
+            .flat_map(|mesh| mesh.vertices.chunks_exact(3));
+        let first = points.next()?;
+        let mut min = [first[0], first[1], first[2]];
+        let mut max = min;
+        for p in points {
+            for i in 0..3 {
+                min[i] = min[i].min(p[i]);
+                max[i] = max[i].max(p[i]);
+            }
+        }
+        Some((min, max))
+    }
+
+    /// Point the camera at the whole scene.
+    pub fn fit_view(&mut self) {
+        match self.mesh_bounds() {
+            Some((min, max)) => {
+                self.camera.fit_bounds(
+                    RenderVec3::new(min[0], min[1], min[2]),
+                    RenderVec3::new(max[0], max[1], max[2]),
+                    self.viewport_aspect,
+                );
+                self.status = "Fit view to scene".to_string();
+            }
+            None => {
+                self.status = "Nothing to fit".to_string();
+            }
+        }
+    }
+
+    /// Switch to a standard view.
+    pub fn set_view(&mut self, view: ViewPreset) {
+        self.camera.set_view(view);
+        self.status = format!("{:?} view", view);
+    }
+
+    /// Toggle between perspective and orthographic projection.
+    pub fn toggle_projection(&mut self) {
+        let projection = match self.camera.projection {
+            Projection::Perspective => Projection::Orthographic,
+            Projection::Orthographic => Projection::Perspective,
+        };
+        self.camera.set_projection(projection);
+        self.status = format!("{:?} projection", projection);
+    }
+
+    /// Nearest part under pixel `(x, y)` of a `width` x `height` viewport.
+    pub fn pick_part(&self, x: f32, y: f32, width: f32, height: f32) -> Option<NodeId> {
+        let (origin, dir) = self.camera.pick_ray(x, y, width, height);
+        let mut nearest: Option<(f32, NodeId)> = None;
+        for mesh in &self.meshes {
+            let vertex = |i: u32| {
+                let i = i as usize * 3;
+                let v = mesh.vertices.get(i..i + 3)?;
+                Some(RenderVec3::new(v[0], v[1], v[2]))
+            };
+            for tri in mesh.indices.chunks_exact(3) {
+                let (Some(v0), Some(v1), Some(v2)) =
+                    (vertex(tri[0]), vertex(tri[1]), vertex(tri[2]))
+                else {
+                    continue;
+                };
+                if let Some(t) = ray_triangle(origin, dir, v0, v1, v2) {
+                    if nearest.is_none_or(|(best, _)| t < best) {
+                        nearest = Some((t, mesh.root));
+                    }
+                }
+            }
+        }
+        nearest.map(|(_, id)| id)
+    }
+
+    /// Select the part under a viewport pixel, or clear the selection if
+    /// there is none.
+    pub fn select_at(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.selected.clear();
+        match self.pick_part(x, y, width, height) {
+            Some(id) => {
+                self.selected.insert(id);
+                self.status = format!("Selected node {}", id);
+            }
+            None => self.status = "Selection cleared".to_string(),
+        }
+    }
+
+    /// Handle a mouse event over the UI laid out as `areas`.
+    ///
+    /// Left-dragging in the viewport orbits the camera, the wheel zooms, and
+    /// clicking a part in the viewport or the parts tree selects it. The tree
+    /// is taken to be scrolled to `focused_index`, as drawn. Returns the tree
+    /// index of a part clicked in the tree, so the caller can move the
+    /// keyboard focus there.
+    pub fn handle_mouse(
+        &mut self,
+        mouse: MouseEvent,
+        areas: &ui::ScreenLayout,
+        focused_index: usize,
+    ) -> Option<usize> {
+        let cell = Position::new(mouse.column, mouse.row);
+        let viewport = areas.viewport.inner(Margin::new(1, 1));
+        let tree = areas.tree.inner(Margin::new(1, 1));
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if viewport.contains(cell) => {
+                self.drag = Some(Drag {
+                    last: (mouse.column, mouse.row),
+                    moved: false,
+                });
+            }
+            MouseEventKind::Down(MouseButton::Left) if tree.contains(cell) => {
+                let index = ui::tree_scroll_offset(focused_index, tree.height)
+                    + (mouse.row - tree.y) as usize;
+                let parts = self.get_parts();
+                let (id, _) = parts.get(index)?;
+                self.selected.clear();
+                self.selected.insert(*id);
+                return Some(index);
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some(drag) = &mut self.drag {
+                    let dx = (mouse.column as f32 - drag.last.0 as f32) * CELL_PIXELS.0;
+                    let dy = (mouse.row as f32 - drag.last.1 as f32) * CELL_PIXELS.1;
+                    drag.last = (mouse.column, mouse.row);
+                    drag.moved |= dx != 0.0 || dy != 0.0;
+                    // Grab-and-turn: dragging right swings the model right
+                    self.camera.rotate_horizontal(-dx * ORBIT_DEGREES_PER_PIXEL);
+                    self.camera.rotate_vertical(dy * ORBIT_DEGREES_PER_PIXEL);
+                }
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                if let Some(drag) = self.drag.take() {
+                    if !drag.moved && viewport.contains(cell) {
+                        let (x, y, width, height) = viewport_pixel(viewport, cell);
+                        self.select_at(x, y, width, height);
+                    }
+                }
+            }
+            MouseEventKind::ScrollUp if viewport.contains(cell) => {
+                self.camera.zoom(SCROLL_ZOOM);
+            }
+            MouseEventKind::ScrollDown if viewport.contains(cell) => {
+                self.camera.zoom(1.0 / SCROLL_ZOOM);
+            }
+            _ => {}
+        }
+        None
+    }
+
     /// Evaluate the document to get meshes.
+    ///
+    /// Warnings are kept in [`App::warnings`] rather than printed, which
+    /// would garble the terminal UI.
     pub fn evaluate(&mut self) -> Result<()> {
-        self.meshes = evaluate_document(&self.document)?;
+        (self.meshes, self.warnings) = evaluate_document_with_warnings(&self.document)?;
         Ok(())
     }
 
     /// Get triangles for rendering.
     pub fn get_triangles(&self) -> Vec<Triangle> {
         let mut triangles = Vec::new();
-        let color = [180u8, 180, 190];
+        let default_color = [180u8, 180, 190];
 
         for mesh in &self.meshes {
+            let color = mesh.tag_color().unwrap_or(default_color);
             for tri in mesh.indices.chunks(3) {
                 if tri.len() < 3 {
                     continue;
@@ -436,12 +739,52 @@ impl App {
                     self.status = "Usage: export <path.stl>".to_string();
                 }
             }
+            "history" | "hist" => {
+                if let Some(index) = parts.get(1) {
+                    let index = index
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("Usage: history [index]"))?;
+                    self.jump_to(index)?;
+                } else {
+                    self.show_history = !self.show_history;
+                }
+            }
+            "supersample" | "aa" => match parts.get(1).and_then(|s| s.parse().ok()) {
+                Some(factor @ (1 | 2 | 4)) => {
+                    self.render_config.supersample = factor;
+                    self.status = format!("Supersampling {}x", factor);
+                }
+                _ => {
+                    self.status = "Usage: supersample <1|2|4>".to_string();
+                }
+            },
+            "view" => match parts.get(1).map(|s| s.to_lowercase()).as_deref() {
+                Some("front") => self.set_view(ViewPreset::Front),
+                Some("top") => self.set_view(ViewPreset::Top),
+                Some("right") => self.set_view(ViewPreset::Right),
+                Some("iso") => self.set_view(ViewPreset::Iso),
+                _ => {
+                    self.status = "Usage: view <front|top|right|iso>".to_string();
+                }
+            },
+            "ortho" => {
+                self.camera.set_projection(Projection::Orthographic);
+                self.status = "Orthographic projection".to_string();
+            }
+            "persp" | "perspective" => {
+                self.camera.set_projection(Projection::Perspective);
+                self.status = "Perspective projection".to_string();
+            }
+            "fit" => {
+                self.fit_view();
+            }
             "quit" | "q" => {
                 self.running = false;
             }
             "help" | "?" => {
-                self.status = "Commands: cube, cylinder, sphere, delete, move, save, export, quit"
-                    .to_string();
+                self.status =
+                    "Commands: cube, cylinder, sphere, delete, move, history, supersample, view, ortho, persp, fit, save, export, quit"
+                        .to_string();
             }
             _ => {
                 self.status = format!("Unknown command: {}", parts[0]);
@@ -452,27 +795,314 @@ impl App {
     }
 }
 
-/// Evaluate a document to meshes.
+/// Deepest node nesting evaluated before giving up, well within the stack
+/// of the thread evaluating it.
+const MAX_EVAL_DEPTH: usize = 1000;
+
+/// Solids of the shared nodes evaluated so far, see [`evaluate_node`].
+///
+/// Only nodes referenced more than once, by other nodes or by scene roots,
+/// get a slot: any other node is evaluated once anyway, and keeping its
+/// solid would only hold a copy of it. Slots are filled through a shared
+/// reference, so one cache can serve roots evaluated in parallel.
+struct SolidCache {
+    solids: HashMap<NodeId, OnceLock<Option<vcad_kernel::Solid>>>,
+    /// Number of nodes evaluated (not found in the cache).
+    evaluated: AtomicUsize,
+    /// Problems evaluation worked around, such as an unknown font.
+    warnings: Mutex<Vec<String>>,
+}
+
+impl SolidCache {
+    /// An empty cache with a slot for each node `doc` references twice or
+    /// more.
+    fn new(doc: &Document) -> Self {
+        let mut uses: HashMap<NodeId, usize> = HashMap::new();
+        let children = doc.nodes.keys().flat_map(|&id| doc.children_of(id));
+        for id in children.chain(doc.roots.iter().map(|entry| entry.root)) {
+            *uses.entry(id).or_default() += 1;
+        }
+        Self {
+            solids: uses
+                .into_iter()
+                .filter(|&(_, n)| n > 1)
+                .map(|(id, _)| (id, OnceLock::new()))
+                .collect(),
+            evaluated: AtomicUsize::new(0),
+            warnings: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Record a warning raised while evaluating.
+    fn warn(&self, message: String) {
+        self.warnings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(message);
+    }
+
+    /// The warnings raised so far, in the order they were raised.
+    fn into_warnings(self) -> Vec<String> {
+        self.warnings
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Print evaluation warnings for command-line use.
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+}
+
+/// Evaluate a document to meshes, printing any warnings to stderr.
+///
+/// Nodes shared between scene roots are evaluated once. Fails if the
+/// document has a cycle.
+#[cfg(any(test, not(feature = "rayon")))]
 pub fn evaluate_document(doc: &Document) -> Result<Vec<EvaluatedMesh>> {
+    let (meshes, warnings) = evaluate_document_with_warnings(doc)?;
+    print_warnings(&warnings);
+    Ok(meshes)
+}
+
+/// Evaluate a document to meshes, returning the warnings raised along the
+/// way instead of printing them.
+pub fn evaluate_document_with_warnings(
+    doc: &Document,
+) -> Result<(Vec<EvaluatedMesh>, Vec<String>)> {
+    let resolved = doc.resolved()?;
+    let doc: &Document = &resolved;
+    doc.topological_order()?;
+    let cache = SolidCache::new(doc);
     let mut meshes = Vec::new();
 
     for entry in &doc.roots {
-        if let Some(solid) = evaluate_node(doc, entry.root)? {
+        meshes.extend(evaluate_root(doc, entry, &cache)?);
+    }
+
+    Ok((meshes, cache.into_warnings()))
+}
+
+/// Evaluate a document to meshes, meshing the scene roots in parallel.
+///
+/// Roots only read the document, so each is evaluated on its own thread.
+/// They share one cache, so a node used by several roots is usually
+/// evaluated once; two threads reaching it at the same time may both
+/// evaluate it. The meshes come out in [`Document::roots`] order, as with
+/// [`evaluate_document_with_warnings`].
+#[cfg(feature = "rayon")]
+pub fn evaluate_document_parallel(doc: &Document) -> Result<Vec<EvaluatedMesh>> {
+    use rayon::prelude::*;
+
+    let resolved = doc.resolved()?;
+    let doc: &Document = &resolved;
+    doc.topological_order()?;
+    let cache = SolidCache::new(doc);
+    let meshes = doc
+        .roots
+        .par_iter()
+        .map(|entry| evaluate_root(doc, entry, &cache))
+        .collect::<Result<Vec<_>>>()?;
+    print_warnings(&cache.into_warnings());
+    Ok(meshes.into_iter().flatten().collect())
+}
+
+/// Evaluate the bodies of each scene root, keyed by root node.
+///
+/// Most roots give one body; a split keeping both halves gives two. Roots
+/// without geometry are left out. Nodes shared between roots are
+/// evaluated once, as in [`evaluate_document_with_warnings`], and
+/// warnings printed to stderr.
+/// Fails if the document has a cycle.
+pub fn evaluate_solids(doc: &Document) -> Result<HashMap<NodeId, Vec<vcad_kernel::Solid>>> {
+    let resolved = doc.resolved()?;
+    let doc: &Document = &resolved;
+    doc.topological_order()?;
+    let cache = SolidCache::new(doc);
+    let mut solids = HashMap::new();
+    for entry in &doc.roots {
+        if solids.contains_key(&entry.root) {
+            continue;
+        }
+        let bodies = evaluate_bodies(doc, entry.root, &cache, 0)?;
+        if !bodies.is_empty() {
+            solids.insert(entry.root, bodies);
+        }
+    }
+    print_warnings(&cache.into_warnings());
+    Ok(solids)
+}
+
+/// Combined mass properties of a document's scene roots, from their
+/// solids as given by [`evaluate_solids`].
+///
+/// Each root is integrated from its tessellated meshes, then weighted by
+/// its material's density; see [`Document::mass_properties`].
+pub fn mass_properties(
+    doc: &Document,
+    solids: &HashMap<NodeId, Vec<vcad_kernel::Solid>>,
+) -> MassProperties {
+    doc.mass_properties(|entry| {
+        solids
+            .get(&entry.root)
+            .map(|bodies| volume_properties(bodies))
+    })
+}
+
+/// Unit-density properties of a root's bodies taken together.
+fn volume_properties(bodies: &[vcad_kernel::Solid]) -> VolumeProperties {
+    // At unit density a body's mass is its volume, so the bodies combine
+    // as parts of one mass.
+    let combined = MassProperties::combine(bodies.iter().map(|solid| {
+        let [x, y, z] = solid.center_of_mass();
+        MassProperties {
+            mass: solid.volume(),
+            center_of_mass: Vec3::new(x, y, z),
+            inertia: solid.inertia_tensor(),
+        }
+    }));
+    VolumeProperties {
+        volume: combined.mass,
+        centroid: combined.center_of_mass,
+        inertia: combined.inertia,
+    }
+}
+
+/// Volume and mass of each material used by a document's scene roots,
+/// from their solids as given by [`evaluate_solids`].
+///
+/// Volumes are taken from the tessellated meshes; see
+/// [`Document::material_usage`].
+pub fn material_usage(
+    doc: &Document,
+    solids: &HashMap<NodeId, Vec<vcad_kernel::Solid>>,
+) -> HashMap<String, MaterialUsage> {
+    doc.material_usage(|entry| {
+        solids
+            .get(&entry.root)
+            .map(|bodies| bodies.iter().map(|solid| solid.volume()).sum())
+    })
+}
+
+/// Evaluate and mesh one scene root, one mesh per body.
+fn evaluate_root(
+    doc: &Document,
+    entry: &SceneEntry,
+    cache: &SolidCache,
+) -> Result<Vec<EvaluatedMesh>> {
+    let bodies = evaluate_bodies(doc, entry.root, cache, 0)?;
+    let tags: Vec<(String, String)> = doc
+        .subtree_tags(entry.root)
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    Ok(bodies
+        .iter()
+        .map(|solid| {
             let mesh = solid.to_mesh(32);
-            meshes.push(EvaluatedMesh {
+            EvaluatedMesh {
+                root: entry.root,
                 vertices: mesh.vertices,
                 indices: mesh.indices,
-            });
-        }
+                tags: tags.clone(),
+            }
+        })
+        .collect())
+}
+
+/// Evaluate a node to the separate bodies it produces.
+///
+/// A split keeping both halves gives its non-empty halves as two bodies,
+/// also through transforms and tags above it. Any other node gives the
+/// single solid from [`evaluate_node`], if any.
+fn evaluate_bodies(
+    doc: &Document,
+    node_id: NodeId,
+    cache: &SolidCache,
+    depth: usize,
+) -> Result<Vec<vcad_kernel::Solid>> {
+    if depth > MAX_EVAL_DEPTH {
+        anyhow::bail!(
+            "Node {} is nested more than {} levels deep",
+            node_id,
+            MAX_EVAL_DEPTH
+        );
     }
+    let node = doc
+        .nodes
+        .get(&node_id)
+        .ok_or_else(|| anyhow::anyhow!("Node {} not found", node_id))?;
 
-    Ok(meshes)
+    match &node.op {
+        CsgOp::SplitByPlane {
+            child,
+            plane_origin,
+            plane_normal,
+            keep: vcad_ir::SplitKeep::Both,
+        } => {
+            let Some(solid) = evaluate_node(doc, *child, cache, depth + 1)? else {
+                return Ok(Vec::new());
+            };
+            let (positive, negative) = solid.split_by_plane(
+                vcad_kernel::vcad_kernel_math::Point3::new(
+                    plane_origin.x,
+                    plane_origin.y,
+                    plane_origin.z,
+                ),
+                vcad_kernel::vcad_kernel_math::Vec3::new(
+                    plane_normal.x,
+                    plane_normal.y,
+                    plane_normal.z,
+                ),
+            );
+            Ok([positive, negative]
+                .into_iter()
+                .filter(|half| !half.is_empty())
+                .collect())
+        }
+        CsgOp::Translate { .. } | CsgOp::Rotate { .. } | CsgOp::Scale { .. } => {
+            let (base, transform) = doc.fold_transforms(node_id);
+            let transform = vcad_kernel::vcad_kernel_math::Transform::from_rows(&transform.matrix);
+            Ok(evaluate_bodies(doc, base, cache, depth + 1)?
+                .iter()
+                .map(|solid| solid.transform(&transform))
+                .collect())
+        }
+        CsgOp::Tag { child, .. } => evaluate_bodies(doc, *child, cache, depth + 1),
+        _ => Ok(evaluate_node(doc, node_id, cache, depth)?
+            .into_iter()
+            .collect()),
+    }
 }
 
 /// Recursively evaluate a node to a Solid.
-fn evaluate_node(doc: &Document, node_id: NodeId) -> Result<Option<vcad_kernel::Solid>> {
+///
+/// Shared nodes already in `cache` are not evaluated again, so a subtree
+/// shared by several parents costs one evaluation. `depth` is the node's nesting
+/// below the node evaluation started from. The caller checks the document
+/// for cycles first, e.g. with [`Document::topological_order`].
+fn evaluate_node(
+    doc: &Document,
+    node_id: NodeId,
+    cache: &SolidCache,
+    depth: usize,
+) -> Result<Option<vcad_kernel::Solid>> {
     use vcad_kernel::Solid;
 
+    let slot = cache.solids.get(&node_id);
+    if let Some(solid) = slot.and_then(OnceLock::get) {
+        return Ok(solid.clone());
+    }
+    if depth > MAX_EVAL_DEPTH {
+        anyhow::bail!(
+            "Node {} is nested more than {} levels deep",
+            node_id,
+            MAX_EVAL_DEPTH
+        );
+    }
+
     let node = doc
         .nodes
         .get(&node_id)
@@ -480,7 +1110,9 @@ fn evaluate_node(doc: &Document, node_id: NodeId) -> Result<Option<vcad_kernel::
 
     let solid = match &node.op {
         CsgOp::Empty => Some(Solid::empty()),
-        CsgOp::Cube { size } => Some(Solid::cube(size.x, size.y, size.z)),
+        CsgOp::Cube { size, origin } => {
+            Some(Solid::cube_at(size.x, size.y, size.z, (*origin).into()))
+        }
         CsgOp::Cylinder {
             radius,
             height,
@@ -494,8 +1126,8 @@ fn evaluate_node(doc: &Document, node_id: NodeId) -> Result<Option<vcad_kernel::
             segments,
         } => Some(Solid::cone(*radius_bottom, *radius_top, *height, *segments)),
         CsgOp::Union { left, right } => {
-            let l = evaluate_node(doc, *left)?;
-            let r = evaluate_node(doc, *right)?;
+            let l = evaluate_node(doc, *left, cache, depth + 1)?;
+            let r = evaluate_node(doc, *right, cache, depth + 1)?;
             match (l, r) {
                 (Some(l), Some(r)) => Some(l.union(&r)),
                 (Some(l), None) => Some(l),
@@ -504,8 +1136,8 @@ fn evaluate_node(doc: &Document, node_id: NodeId) -> Result<Option<vcad_kernel::
             }
         }
         CsgOp::Difference { left, right } => {
-            let l = evaluate_node(doc, *left)?;
-            let r = evaluate_node(doc, *right)?;
+            let l = evaluate_node(doc, *left, cache, depth + 1)?;
+            let r = evaluate_node(doc, *right, cache, depth + 1)?;
             match (l, r) {
                 (Some(l), Some(r)) => Some(l.difference(&r)),
                 (Some(l), None) => Some(l),
@@ -513,36 +1145,156 @@ fn evaluate_node(doc: &Document, node_id: NodeId) -> Result<Option<vcad_kernel::
             }
         }
         CsgOp::Intersection { left, right } => {
-            let l = evaluate_node(doc, *left)?;
-            let r = evaluate_node(doc, *right)?;
+            let l = evaluate_node(doc, *left, cache, depth + 1)?;
+            let r = evaluate_node(doc, *right, cache, depth + 1)?;
             match (l, r) {
                 (Some(l), Some(r)) => Some(l.intersection(&r)),
                 _ => None,
             }
         }
-        CsgOp::Translate { child, offset } => {
-            let c = evaluate_node(doc, *child)?;
-            c.map(|s| s.translate(offset.x, offset.y, offset.z))
-        }
-        CsgOp::Rotate { child, angles } => {
-            let c = evaluate_node(doc, *child)?;
-            c.map(|s| s.rotate(angles.x, angles.y, angles.z))
-        }
-        CsgOp::Scale { child, factor } => {
-            let c = evaluate_node(doc, *child)?;
-            c.map(|s| s.scale(factor.x, factor.y, factor.z))
+        CsgOp::Translate { .. } | CsgOp::Rotate { .. } | CsgOp::Scale { .. } => {
+            // Fold the whole chain of transforms so the child is copied once.
+            let (base, transform) = doc.fold_transforms(node_id);
+            let c = evaluate_node(doc, base, cache, depth + 1)?;
+            let transform = vcad_kernel::vcad_kernel_math::Transform::from_rows(&transform.matrix);
+            c.map(|s| s.transform(&transform))
         }
         CsgOp::Sketch2D { .. } => {
             // Sketches need extrusion to become solids
             None
         }
-        CsgOp::Extrude { .. } => {
-            // TODO: Implement sketch extrusion
-            None
+        CsgOp::Extrude {
+            sketch,
+            direction,
+            twist_angle,
+            scale_end,
+        } => {
+            let direction =
+                vcad_kernel::vcad_kernel_math::Vec3::new(direction.x, direction.y, direction.z);
+            let mut solid: Option<Solid> = None;
+            for profile in sketch_profiles(doc, *sketch, cache, depth + 1)? {
+                let part = match (twist_angle, scale_end) {
+                    (None, None) => Solid::extrude(profile, direction),
+                    _ => Solid::extrude_with_options(
+                        profile,
+                        direction,
+                        twist_angle.unwrap_or(0.0),
+                        scale_end.unwrap_or(1.0),
+                    ),
+                }
+                .map_err(|e| anyhow::anyhow!("Extrude failed: {}", e))?;
+                solid = Some(match solid {
+                    Some(solid) => solid.merge_disjoint(&part),
+                    None => part,
+                });
+            }
+            solid
         }
-        CsgOp::Revolve { .. } => {
-            // TODO: Implement sketch revolve
-            None
+        CsgOp::Revolve {
+            sketch,
+            axis_origin,
+            axis_dir,
+            angle_deg,
+        } => {
+            use vcad_kernel::vcad_kernel_math::{Point3, Vec3};
+
+            let profile = sketch_profile(doc, *sketch, cache, depth + 1)?;
+            let solid = Solid::revolve(
+                profile,
+                Point3::new(axis_origin.x, axis_origin.y, axis_origin.z),
+                Vec3::new(axis_dir.x, axis_dir.y, axis_dir.z),
+                *angle_deg,
+            );
+            Some(solid.map_err(|e| anyhow::anyhow!("Revolve failed: {}", e))?)
+        }
+        CsgOp::Loft {
+            profiles,
+            mode,
+            closed,
+        } => {
+            let profiles = profiles
+                .iter()
+                .map(|&id| sketch_profile(doc, id, cache, depth + 1))
+                .collect::<Result<Vec<_>>>()?;
+            let options = vcad_kernel::vcad_kernel_sweep::LoftOptions {
+                mode: match mode {
+                    vcad_ir::LoftMode::Ruled => vcad_kernel::vcad_kernel_sweep::LoftMode::Ruled,
+                    vcad_ir::LoftMode::Smooth => vcad_kernel::vcad_kernel_sweep::LoftMode::Smooth,
+                },
+                closed: *closed,
+                ..Default::default()
+            };
+            Some(
+                Solid::loft(&profiles, options)
+                    .map_err(|e| anyhow::anyhow!("Loft failed: {}", e))?,
+            )
+        }
+        CsgOp::Sweep {
+            profile,
+            path,
+            options,
+        } => {
+            use vcad_kernel::vcad_kernel_math::Point3;
+            use vcad_kernel::vcad_kernel_sweep::{
+                DegeneratePolicy, Helix, PolylinePath, SweepOptions, SweepWarning,
+            };
+
+            let profile = sketch_profile(doc, *profile, cache, depth + 1)?;
+            let defaults = SweepOptions::default();
+            let options = SweepOptions {
+                twist_angle: options.twist_angle.unwrap_or(defaults.twist_angle),
+                scale_start: options.scale_start.unwrap_or(defaults.scale_start),
+                scale_end: options.scale_end.unwrap_or(defaults.scale_end),
+                path_segments: options.path_segments.unwrap_or(defaults.path_segments),
+                arc_segments: options.arc_segments.unwrap_or(defaults.arc_segments),
+                orientation_angle: options.orientation.unwrap_or(defaults.orientation_angle),
+                on_degenerate: match options.on_degenerate.unwrap_or_default() {
+                    vcad_ir::DegeneratePolicy::Error => DegeneratePolicy::Error,
+                    vcad_ir::DegeneratePolicy::Skip => DegeneratePolicy::Skip,
+                    vcad_ir::DegeneratePolicy::Interpolate => DegeneratePolicy::Interpolate,
+                },
+                ..defaults
+            };
+            let point = |v: &vcad_ir::Vec3| Point3::new(v.x, v.y, v.z);
+            let swept = match path {
+                vcad_ir::SweepPath::Line { start, end } => {
+                    let line = vcad_kernel::vcad_kernel_geom::Line3d::from_points(
+                        point(start),
+                        point(end),
+                    );
+                    Solid::sweep_with_warnings(profile, &line, options)
+                }
+                vcad_ir::SweepPath::Helix {
+                    radius,
+                    pitch,
+                    height,
+                    turns,
+                } => Solid::sweep_with_warnings(
+                    profile,
+                    &Helix::new(*radius, *pitch, *height, *turns),
+                    options,
+                ),
+                vcad_ir::SweepPath::Polyline { points } => Solid::sweep_with_warnings(
+                    profile,
+                    &PolylinePath::new(points.iter().map(point).collect()),
+                    options,
+                ),
+            };
+            let (solid, warnings) = swept.map_err(|e| anyhow::anyhow!("Sweep failed: {}", e))?;
+            for warning in warnings {
+                cache.warn(match warning {
+                    SweepWarning::SkippedStation(t) => {
+                        format!(
+                            "Sweep node {}: skipped degenerate station at t={}",
+                            node_id, t
+                        )
+                    }
+                    SweepWarning::InterpolatedFrame(t) => {
+                        format!("Sweep node {}: interpolated frame at t={}", node_id, t)
+                    }
+                });
+            }
+            Some(solid)
         }
         CsgOp::LinearPattern {
             child,
@@ -550,7 +1302,7 @@ fn evaluate_node(doc: &Document, node_id: NodeId) -> Result<Option<vcad_kernel::
             count,
             spacing,
         } => {
-            let c = evaluate_node(doc, *child)?;
+            let c = evaluate_node(doc, *child, cache, depth + 1)?;
             c.map(|s| {
                 s.linear_pattern(
                     vcad_kernel::vcad_kernel_math::Vec3::new(direction.x, direction.y, direction.z),
@@ -566,7 +1318,7 @@ fn evaluate_node(doc: &Document, node_id: NodeId) -> Result<Option<vcad_kernel::
             count,
             angle_deg,
         } => {
-            let c = evaluate_node(doc, *child)?;
+            let c = evaluate_node(doc, *child, cache, depth + 1)?;
             c.map(|s| {
                 s.circular_pattern(
                     vcad_kernel::vcad_kernel_math::Point3::new(
@@ -581,23 +1333,56 @@ fn evaluate_node(doc: &Document, node_id: NodeId) -> Result<Option<vcad_kernel::
             })
         }
         CsgOp::Shell { child, thickness } => {
-            let c = evaluate_node(doc, *child)?;
+            let c = evaluate_node(doc, *child, cache, depth + 1)?;
             c.map(|s| s.shell(*thickness))
         }
         CsgOp::Fillet { child, radius } => {
-            let c = evaluate_node(doc, *child)?;
+            let c = evaluate_node(doc, *child, cache, depth + 1)?;
             c.map(|s| s.fillet(*radius))
         }
         CsgOp::Chamfer { child, distance } => {
-            let c = evaluate_node(doc, *child)?;
+            let c = evaluate_node(doc, *child, cache, depth + 1)?;
             c.map(|s| s.chamfer(*distance))
         }
+        CsgOp::SplitByPlane {
+            child,
+            plane_origin,
+            plane_normal,
+            keep,
+        } => {
+            if *keep == vcad_ir::SplitKeep::Both {
+                anyhow::bail!(
+                    "Node {} keeps both halves of a split, which are two bodies; \
+                     it can only be used as a scene root",
+                    node_id
+                );
+            }
+            let c = evaluate_node(doc, *child, cache, depth + 1)?;
+            c.map(|s| {
+                let (positive, negative) = s.split_by_plane(
+                    vcad_kernel::vcad_kernel_math::Point3::new(
+                        plane_origin.x,
+                        plane_origin.y,
+                        plane_origin.z,
+                    ),
+                    vcad_kernel::vcad_kernel_math::Vec3::new(
+                        plane_normal.x,
+                        plane_normal.y,
+                        plane_normal.z,
+                    ),
+                );
+                match keep {
+                    vcad_ir::SplitKeep::Positive => positive,
+                    vcad_ir::SplitKeep::Negative | vcad_ir::SplitKeep::Both => negative,
+                }
+            })
+        }
         CsgOp::StepImport { path } => {
             // Import geometry from STEP file
             match Solid::from_step(path) {
                 Ok(solid) => Some(solid),
                 Err(e) => {
-                    eprintln!("Failed to import STEP file '{}': {}", path, e);
+                    cache.warn(format!("Failed to import STEP file '{}': {}", path, e));
                     None
                 }
             }
@@ -606,11 +1391,249 @@ fn evaluate_node(doc: &Document, node_id: NodeId) -> Result<Option<vcad_kernel::
             // Text needs extrusion to become solid
             None
         }
+        CsgOp::Text {
+            content,
+            font,
+            height,
+            depth,
+            plane_origin,
+            x_dir,
+            y_dir,
+            alignment,
+        } => {
+            use vcad_kernel::vcad_kernel_math::{Point3, Vec3};
+            use vcad_kernel::vcad_kernel_text::FontRegistry;
+
+            let builtin = FontRegistry::builtin_sans();
+            if *font != builtin.name {
+                cache.warn(format!(
+                    "Unknown font '{}', falling back to '{}'",
+                    font, builtin.name
+                ));
+            }
+            let solid = Solid::text(
+                content,
+                builtin,
+                *height,
+                *depth,
+                Point3::new(plane_origin.x, plane_origin.y, plane_origin.z),
+                Vec3::new(x_dir.x, x_dir.y, x_dir.z),
+                Vec3::new(y_dir.x, y_dir.y, y_dir.z),
+                text_alignment(*alignment),
+            )
+            .map_err(|e| anyhow::anyhow!("Text node {}: {}", node_id, e))?;
+            Some(solid)
+        }
+        CsgOp::ProjectToSketch { .. } => {
+            // Projections produce sketches, which need extrusion to become
+            // solids; see `sketch_profile`
+            None
+        }
+        CsgOp::SvgProfile { .. } => {
+            // SVG paths produce sketches, which need extrusion to become
+            // solids; see `sketch_profiles`
+            None
+        }
+        CsgOp::Tag { child, .. } => {
+            // Tags carry metadata only; see `evaluate_document`
+            evaluate_node(doc, *child, cache, depth + 1)?
+        }
     };
 
+    cache.evaluated.fetch_add(1, Ordering::Relaxed);
+    if let Some(slot) = slot {
+        // Another thread may have filled it meanwhile; either solid will do
+        let _ = slot.set(solid.clone());
+    }
     Ok(solid)
 }
 
+/// The kernel's counterpart of an IR text alignment.
+fn text_alignment(alignment: TextAlignment) -> vcad_kernel::vcad_kernel_text::TextAlignment {
+    use vcad_kernel::vcad_kernel_text::TextAlignment as KernelTextAlignment;
+    match alignment {
+        TextAlignment::Left => KernelTextAlignment::Left,
+        TextAlignment::Center => KernelTextAlignment::Center,
+        TextAlignment::Right => KernelTextAlignment::Right,
+    }
+}
+
+/// Convert a sketch node to a single kernel profile, see
+/// [`sketch_profiles`].
+fn sketch_profile(
+    doc: &Document,
+    node_id: NodeId,
+    cache: &SolidCache,
+    depth: usize,
+) -> Result<vcad_kernel::vcad_kernel_sketch::SketchProfile> {
+    let mut profiles = sketch_profiles(doc, node_id, cache, depth)?;
+    if profiles.len() != 1 {
+        anyhow::bail!(
+            "Sketch {} has {} separate outlines, expected one",
+            node_id,
+            profiles.len()
+        );
+    }
+    Ok(profiles.remove(0))
+}
+
+/// Convert a sketch node to kernel profiles, one per separate outline.
+///
+/// A [`CsgOp::Sketch2D`] is one profile. A [`CsgOp::ProjectToSketch`] node
+/// evaluates its solid through `cache`, like [`evaluate_node`], and must
+/// project to a single outline. A [`CsgOp::SvgProfile`] gives a profile for
+/// each outer loop of the path, with the holes inside it.
+fn sketch_profiles(
+    doc: &Document,
+    node_id: NodeId,
+    cache: &SolidCache,
+    depth: usize,
+) -> Result<Vec<vcad_kernel::vcad_kernel_sketch::SketchProfile>> {
+    use vcad_kernel::vcad_kernel_math::{Point3, Vec3};
+
+    let node = doc
+        .nodes
+        .get(&node_id)
+        .ok_or_else(|| anyhow::anyhow!("Node {} not found", node_id))?;
+    match &node.op {
+        CsgOp::Sketch2D {
+            origin,
+            x_dir,
+            y_dir,
+            segments,
+            holes,
+        } => {
+            let profile = kernel_profile([origin, x_dir, y_dir], segments, holes)
+                .map_err(|e| anyhow::anyhow!("Invalid sketch {}: {}", node_id, e))?;
+            Ok(vec![profile])
+        }
+        CsgOp::ProjectToSketch {
+            solid,
+            plane_origin,
+            plane_normal,
+        } => {
+            let solid = evaluate_node(doc, *solid, cache, depth + 1)?
+                .ok_or_else(|| anyhow::anyhow!("Projection {} has no solid to project", node_id))?;
+            let profile = solid
+                .project_to_sketch(
+                    Point3::new(plane_origin.x, plane_origin.y, plane_origin.z),
+                    Vec3::new(plane_normal.x, plane_normal.y, plane_normal.z),
+                )
+                .ok_or_else(|| anyhow::anyhow!("Projection {} is not a single outline", node_id))?;
+            Ok(vec![profile])
+        }
+        CsgOp::SvgProfile {
+            path_data,
+            scale,
+            plane_origin,
+            x_dir,
+            y_dir,
+        } => vcad_kernel_svg::parse_svg_outlines(path_data, *scale)
+            .map_err(|e| anyhow::anyhow!("SVG profile {}: {}", node_id, e))?
+            .iter()
+            .map(|outline| {
+                kernel_profile(
+                    [plane_origin, x_dir, y_dir],
+                    &outline.segments,
+                    &outline.holes,
+                )
+                .map_err(|e| anyhow::anyhow!("Invalid SVG profile {}: {}", node_id, e))
+            })
+            .collect(),
+        _ => anyhow::bail!("Node {} is not a sketch", node_id),
+    }
+}
+
+/// Build a kernel profile from IR segments on the plane given by its
+/// origin, x and y directions.
+fn kernel_profile(
+    [origin, x_dir, y_dir]: [&vcad_ir::Vec3; 3],
+    segments: &[vcad_ir::SketchSegment2D],
+    holes: &[Vec<vcad_ir::SketchSegment2D>],
+) -> std::result::Result<
+    vcad_kernel::vcad_kernel_sketch::SketchProfile,
+    vcad_kernel::vcad_kernel_sketch::SketchError,
+> {
+    use vcad_kernel::vcad_kernel_math::{Point2, Point3, Vec3};
+    use vcad_kernel::vcad_kernel_sketch::{SketchProfile, SketchSegment};
+
+    let point = |v: &vcad_ir::Vec2| Point2::new(v.x, v.y);
+    let convert = |seg: &vcad_ir::SketchSegment2D| match seg {
+        vcad_ir::SketchSegment2D::Line { start, end } => SketchSegment::Line {
+            start: point(start),
+            end: point(end),
+        },
+        vcad_ir::SketchSegment2D::Arc {
+            start,
+            end,
+            center,
+            ccw,
+        } => SketchSegment::Arc {
+            start: point(start),
+            end: point(end),
+            center: point(center),
+            ccw: *ccw,
+        },
+    };
+
+    SketchProfile::new(
+        Point3::new(origin.x, origin.y, origin.z),
+        Vec3::new(x_dir.x, x_dir.y, x_dir.z),
+        Vec3::new(y_dir.x, y_dir.y, y_dir.z),
+        segments.iter().map(convert).collect(),
+    )
+    .and_then(|profile| {
+        profile.with_holes(
+            holes
+                .iter()
+                .map(|hole| hole.iter().map(convert).collect())
+                .collect(),
+        )
+    })
+}
+
+/// Center of `cell` in viewport pixels, with the viewport's pixel size, as
+/// `(x, y, width, height)`.
+fn viewport_pixel(viewport: Rect, cell: Position) -> (f32, f32, f32, f32) {
+    let (cell_w, cell_h) = CELL_PIXELS;
+    (
+        (cell.x - viewport.x) as f32 * cell_w + cell_w / 2.0,
+        (cell.y - viewport.y) as f32 * cell_h + cell_h / 2.0,
+        viewport.width as f32 * cell_w,
+        viewport.height as f32 * cell_h,
+    )
+}
+
+/// Distance along a ray to its hit on a triangle (Möller–Trumbore), if any.
+fn ray_triangle(
+    origin: RenderVec3,
+    dir: RenderVec3,
+    v0: RenderVec3,
+    v1: RenderVec3,
+    v2: RenderVec3,
+) -> Option<f32> {
+    let edge1 = v1.sub(v0);
+    let edge2 = v2.sub(v0);
+    let p = dir.cross(edge2);
+    let det = edge1.dot(p);
+    if det.abs() < 1e-8 {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    let s = origin.sub(v0);
+    let u = s.dot(p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(edge1);
+    let v = dir.dot(q) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = edge2.dot(q) * inv_det;
+    (t > 0.0).then_some(t)
+}
+
 /// Run the TUI application.
 pub fn run_tui(file: Option<PathBuf>) -> Result<()> {
     // Setup terminal
@@ -645,16 +1668,20 @@ fn run_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) ->
     while app.running {
         // Get terminal size
         let size = terminal.size()?;
+        let areas = ui::layout(Rect::new(0, 0, size.width, size.height), app.show_history);
 
         // Update render buffer size based on viewport
-        let viewport_width = (size.width.saturating_sub(28)) as u32 * 2; // 2 pixels per char width
-        let viewport_height = (size.height.saturating_sub(6)) as u32 * 4; // 4 pixels per char height (braille)
+        let viewport = areas.viewport.inner(Margin::new(1, 1));
+        let viewport_width = viewport.width as u32 * 2; // 2 pixels per char width
+        let viewport_height = viewport.height as u32 * 4; // 4 pixels per char height (braille)
         if render_buffer.width != viewport_width || render_buffer.height != viewport_height {
             render_buffer = RenderBuffer::new(viewport_width.max(40), viewport_height.max(20));
         }
+        app.viewport_aspect = render_buffer.width as f32 / render_buffer.height as f32;
 
         // Render 3D scene to buffer
         let triangles = app.get_triangles();
+        render_buffer.supersample = app.render_config.supersample;
         crate::render::render_scene(&mut render_buffer, &triangles, &app.camera);
 
         // Draw UI
@@ -664,7 +1691,12 @@ fn run_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) ->
 
         // Handle input
         if event::poll(Duration::from_millis(16))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                if let Some(index) = app.handle_mouse(mouse, &areas, focused_part_index) {
+                    focused_part_index = index;
+                }
+            } else if let Event::Key(key) = event {
                 if app.command_mode {
                     // Command input mode
                     match key.code {
@@ -744,6 +1776,22 @@ fn run_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) ->
                         KeyCode::Char('-') => {
                             app.camera.zoom(1.25);
                         }
+                        // Standard views
+                        KeyCode::Char('v') => {
+                            app.set_view(ViewPreset::Front);
+                        }
+                        KeyCode::Char('t') => {
+                            app.set_view(ViewPreset::Top);
+                        }
+                        KeyCode::Char('i') => {
+                            app.set_view(ViewPreset::Iso);
+                        }
+                        KeyCode::Char('o') => {
+                            app.toggle_projection();
+                        }
+                        KeyCode::Char('f') => {
+                            app.fit_view();
+                        }
                         // Part selection
                         KeyCode::Tab => {
                             let parts = app.get_parts();
@@ -789,3 +1837,684 @@ fn run_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) ->
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc_with_root(ops: Vec<CsgOp>) -> Document {
+        let mut doc = Document::new();
+        let root = ops.len() as NodeId - 1;
+        for (id, op) in ops.into_iter().enumerate() {
+            let id = id as NodeId;
+            doc.nodes.insert(
+                id,
+                Node {
+                    id,
+                    name: None,
+                    op,
+                    attributes: None,
+                },
+            );
+        }
+        doc.roots.push(SceneEntry {
+            root,
+            material: "default".to_string(),
+            visible: None,
+        });
+        doc
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_evaluation_matches_sequential() {
+        // Ten parts, each a primitive turned and moved along x
+        let mut doc = Document::new();
+        let mut add = |id: NodeId, op: CsgOp| {
+            doc.nodes.insert(
+                id,
+                Node {
+                    id,
+                    name: None,
+                    op,
+                    attributes: None,
+                },
+            );
+        };
+        for i in 0..10 {
+            let base = 10 * i;
+            let r = 1.0 + 0.1 * i as f64;
+            let primitive = match i % 3 {
+                0 => CsgOp::Cube {
+                    size: Vec3::new(r, 2.0 * r, 3.0 * r),
+                    origin: BoxOrigin::Corner,
+                },
+                1 => CsgOp::Cylinder {
+                    radius: r,
+                    height: 5.0,
+                    segments: 16,
+                },
+                _ => CsgOp::Sphere {
+                    radius: r,
+                    segments: 12,
+                },
+            };
+            add(base, primitive);
+            add(
+                base + 1,
+                CsgOp::Rotate {
+                    child: base,
+                    angles: Vec3::new(10.0 * i as f64, 0.0, 45.0),
+                },
+            );
+            add(
+                base + 2,
+                CsgOp::Translate {
+                    child: base + 1,
+                    offset: Vec3::new(12.0 * i as f64, 0.0, 0.0),
+                },
+            );
+        }
+        for i in 0..10 {
+            doc.roots.push(SceneEntry {
+                root: 10 * i + 2,
+                material: "default".to_string(),
+                visible: None,
+            });
+        }
+
+        let sequential = evaluate_document(&doc).unwrap();
+        let parallel = evaluate_document_parallel(&doc).unwrap();
+        assert_eq!(sequential.len(), 10);
+        assert_eq!(parallel.len(), sequential.len());
+        for (a, b) in sequential.iter().zip(&parallel) {
+            assert_eq!(a.vertices, b.vertices);
+            assert_eq!(a.indices, b.indices);
+            assert_eq!(a.tags, b.tags);
+        }
+    }
+
+    #[test]
+    fn test_jump_to_history_entry() {
+        let mut app = App::new(None).unwrap();
+        app.add_cube(10.0).unwrap();
+        app.add_cylinder(5.0, 10.0).unwrap();
+        app.add_sphere(5.0).unwrap();
+        assert_eq!(app.get_parts().len(), 3);
+
+        let entries = app.history_entries();
+        assert_eq!(entries.len(), 4);
+        assert!(entries[3].current);
+        let cube = entries
+            .iter()
+            .position(|e| e.label.starts_with("Added cube"))
+            .unwrap();
+
+        app.jump_to(cube).unwrap();
+        assert_eq!(app.get_parts().len(), 1);
+        assert!(app.history_entries()[cube].current);
+
+        // Later states stay redoable until a new action replaces them.
+        app.redo().unwrap();
+        assert_eq!(app.get_parts().len(), 2);
+        app.jump_to(cube).unwrap();
+        app.add_sphere(2.0).unwrap();
+        assert_eq!(app.history_entries().len(), cube + 2);
+        assert!(app.jump_to(cube + 2).is_err());
+    }
+
+    #[test]
+    fn test_failed_edit_is_not_recorded() {
+        let mut app = App::new(None).unwrap();
+        let cube = app.add_cube(10.0).unwrap();
+
+        // Break the document behind history's back so the next edit fails.
+        app.document.nodes.remove(&cube);
+        app.selected.insert(cube);
+        assert!(app.translate_selected(1.0, 0.0, 0.0).is_err());
+
+        assert_eq!(app.history_entries().len(), 2);
+        assert!(app.document.nodes.contains_key(&cube));
+        assert_eq!(app.get_parts().len(), 1);
+        assert_eq!(app.meshes.len(), 1);
+    }
+
+    #[test]
+    fn test_tag_is_transparent() {
+        let cylinder = CsgOp::Cylinder {
+            radius: 3.0,
+            height: 20.0,
+            segments: 16,
+        };
+        let offset = Vec3::new(5.0, 0.0, -2.0);
+        let plain = doc_with_root(vec![
+            cylinder.clone(),
+            CsgOp::Translate { child: 0, offset },
+        ]);
+        let tagged = doc_with_root(vec![
+            cylinder,
+            CsgOp::Tag {
+                child: 0,
+                key: "color".to_string(),
+                value: "#ff8000".to_string(),
+            },
+            CsgOp::Translate { child: 1, offset },
+        ]);
+
+        let plain = evaluate_document(&plain).unwrap();
+        let tagged = evaluate_document(&tagged).unwrap();
+
+        assert_eq!(plain.len(), 1);
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(plain[0].vertices, tagged[0].vertices);
+        assert_eq!(plain[0].indices, tagged[0].indices);
+        assert_eq!(plain[0].tag_color(), None);
+        assert_eq!(tagged[0].tag_color(), Some([255, 128, 0]));
+    }
+
+    #[test]
+    fn test_extrude_projection() {
+        // Project a cylinder along its axis and extrude the outline
+        let doc = doc_with_root(vec![
+            CsgOp::Cylinder {
+                radius: 5.0,
+                height: 10.0,
+                segments: 32,
+            },
+            CsgOp::ProjectToSketch {
+                solid: 0,
+                plane_origin: Vec3::new(0.0, 0.0, 0.0),
+                plane_normal: Vec3::new(0.0, 0.0, 1.0),
+            },
+            CsgOp::Extrude {
+                sketch: 1,
+                direction: Vec3::new(0.0, 0.0, 2.0),
+                twist_angle: None,
+                scale_end: None,
+            },
+        ]);
+        let solid = evaluate_node(&doc, 2, &SolidCache::new(&doc), 0)
+            .unwrap()
+            .unwrap();
+        let disc = std::f64::consts::PI * 25.0 * 2.0;
+        assert!((solid.volume() - disc).abs() < 0.05 * disc);
+        let (min, max) = solid.bounding_box();
+        assert!(min[2].abs() < 1e-9 && (max[2] - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_extrude_svg_profile() {
+        // A 10 mm square with a 4 mm hole, and a separate 2 mm square
+        let svg = CsgOp::SvgProfile {
+            path_data: "M0 0 H10 V10 H0 Z M3 3 H7 V7 H3 Z M20 0 h2 v2 h-2 z".to_string(),
+            scale: 1.0,
+            plane_origin: Vec3::new(0.0, 0.0, 0.0),
+            x_dir: Vec3::new(1.0, 0.0, 0.0),
+            y_dir: Vec3::new(0.0, 1.0, 0.0),
+        };
+        let doc = doc_with_root(vec![
+            svg,
+            CsgOp::Extrude {
+                sketch: 0,
+                direction: Vec3::new(0.0, 0.0, 3.0),
+                twist_angle: None,
+                scale_end: None,
+            },
+        ]);
+        let solid = evaluate_node(&doc, 1, &SolidCache::new(&doc), 0)
+            .unwrap()
+            .unwrap();
+        assert!((solid.volume() - (100.0 - 16.0 + 4.0) * 3.0).abs() < 1e-6);
+        // SVG's y axis points down
+        let (min, max) = solid.bounding_box();
+        assert!((min[1] + 10.0).abs() < 1e-9 && max[1].abs() < 1e-9);
+        assert!((max[0] - 22.0).abs() < 1e-9);
+
+        // A sweep needs a single outline
+        let mut sweep = doc.clone();
+        sweep.nodes.get_mut(&1).unwrap().op = CsgOp::Sweep {
+            profile: 0,
+            path: vcad_ir::SweepPath::Line {
+                start: Vec3::new(0.0, 0.0, 0.0),
+                end: Vec3::new(0.0, 0.0, 3.0),
+            },
+            options: Default::default(),
+        };
+        let err = evaluate_document(&sweep).err().unwrap();
+        assert!(err.to_string().contains("2 separate outlines"), "{err}");
+    }
+
+    #[test]
+    fn test_rotate_matches_kernel() {
+        let size = Vec3::new(10.0, 20.0, 30.0);
+        let angles = Vec3::new(30.0, 40.0, 50.0);
+        let doc = doc_with_root(vec![
+            CsgOp::Cube {
+                size,
+                origin: BoxOrigin::Corner,
+            },
+            CsgOp::Rotate { child: 0, angles },
+        ]);
+        let meshes = evaluate_document(&doc).unwrap();
+
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+        for v in meshes[0].vertices.chunks_exact(3) {
+            for k in 0..3 {
+                min[k] = min[k].min(v[k] as f64);
+                max[k] = max[k].max(v[k] as f64);
+            }
+        }
+        let (kernel_min, kernel_max) = vcad_kernel::Solid::cube(size.x, size.y, size.z)
+            .rotate(angles.x, angles.y, angles.z)
+            .bounding_box();
+        for k in 0..3 {
+            assert!(
+                (min[k] - kernel_min[k]).abs() < 1e-4,
+                "{min:?} {kernel_min:?}"
+            );
+            assert!(
+                (max[k] - kernel_max[k]).abs() < 1e-4,
+                "{max:?} {kernel_max:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluate_loft_and_sweep() {
+        let square = |z: f64| CsgOp::Sketch2D {
+            origin: vcad_ir::Vec3::new(0.0, 0.0, z),
+            x_dir: vcad_ir::Vec3::new(1.0, 0.0, 0.0),
+            y_dir: vcad_ir::Vec3::new(0.0, 1.0, 0.0),
+            segments: [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]
+                .iter()
+                .zip([(4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)])
+                .map(|(&(x0, y0), (x1, y1))| vcad_ir::SketchSegment2D::Line {
+                    start: vcad_ir::Vec2::new(x0, y0),
+                    end: vcad_ir::Vec2::new(x1, y1),
+                })
+                .collect(),
+            holes: Vec::new(),
+        };
+
+        let loft = doc_with_root(vec![
+            square(0.0),
+            square(10.0),
+            CsgOp::Loft {
+                profiles: vec![0, 1],
+                mode: vcad_ir::LoftMode::Ruled,
+                closed: false,
+            },
+        ]);
+        assert_eq!(evaluate_document(&loft).unwrap().len(), 1);
+
+        let sweep = doc_with_root(vec![
+            square(0.0),
+            CsgOp::Sweep {
+                profile: 0,
+                path: vcad_ir::SweepPath::Line {
+                    start: vcad_ir::Vec3::new(0.0, 0.0, 0.0),
+                    end: vcad_ir::Vec3::new(0.0, 0.0, 10.0),
+                },
+                options: Default::default(),
+            },
+        ]);
+        assert_eq!(evaluate_document(&sweep).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_mass_properties_weighted_by_density() {
+        // Two 10 mm cubes 20 mm apart; the right one is three times as dense
+        let mut doc = doc_with_root(vec![
+            CsgOp::Cube {
+                size: Vec3::new(10.0, 10.0, 10.0),
+                origin: BoxOrigin::Corner,
+            },
+            CsgOp::Translate {
+                child: 0,
+                offset: Vec3::new(20.0, 0.0, 0.0),
+            },
+        ]);
+        doc.roots[0].material = "heavy".to_string();
+        doc.roots.push(SceneEntry {
+            root: 0,
+            material: "light".to_string(),
+            visible: None,
+        });
+        doc.roots.push(SceneEntry {
+            root: 0,
+            material: "no_density".to_string(),
+            visible: None,
+        });
+        let steel = vcad_ir::MaterialDef::preset("steel").unwrap();
+        for (name, density) in [
+            ("heavy", Some(3000.0)),
+            ("light", Some(1000.0)),
+            ("no_density", None),
+        ] {
+            let material = vcad_ir::MaterialDef {
+                name: name.to_string(),
+                density,
+                ..steel.clone()
+            };
+            doc.materials.insert(name.to_string(), material);
+        }
+
+        let props = mass_properties(&doc, &evaluate_solids(&doc).unwrap());
+        // 1000 mm³ at 3000 and at 1000 kg/m³
+        assert!((props.mass - 4e-3).abs() < 1e-9);
+        // (3 × 25 + 1 × 5) / 4
+        assert!((props.center_of_mass.x - 20.0).abs() < 1e-6);
+        assert!((props.center_of_mass.y - 5.0).abs() < 1e-6);
+        // About the x axis the cubes act as one 10 mm cube; the x-offsets
+        // add to the other axes
+        let cube = 4e-3 * 200.0 / 12.0;
+        assert!((props.inertia[0][0] - cube).abs() < 1e-6);
+        assert!(props.inertia[1][1] > cube);
+    }
+
+    #[test]
+    fn test_material_usage_of_cube() {
+        let mut doc = doc_with_root(vec![CsgOp::Cube {
+            size: Vec3::new(20.0, 10.0, 5.0),
+            origin: BoxOrigin::Corner,
+        }]);
+        doc.roots[0].material = "pla".to_string();
+        doc.add_material_preset("pla");
+
+        let usage = material_usage(&doc, &evaluate_solids(&doc).unwrap());
+        let pla = usage["pla"];
+        assert!((pla.volume - 1000.0).abs() < 1e-6);
+        // density × volume, in kg/m³ × m³
+        assert!((pla.mass.unwrap() - 1240.0 * 1000.0 * 1e-9).abs() < 1e-12);
+        let estimate = pla.print_estimate(0.2, 0.2);
+        assert!(estimate.extruded_volume < pla.volume);
+        assert!(estimate.time > 0.0);
+    }
+
+    #[test]
+    fn test_evaluate_split_by_plane() {
+        let split = |keep| {
+            let doc = doc_with_root(vec![
+                CsgOp::Cube {
+                    size: Vec3::new(10.0, 10.0, 10.0),
+                    origin: BoxOrigin::Corner,
+                },
+                CsgOp::SplitByPlane {
+                    child: 0,
+                    plane_origin: Vec3::new(0.0, 0.0, 4.0),
+                    plane_normal: Vec3::new(0.0, 0.0, 1.0),
+                    keep,
+                },
+            ]);
+            evaluate_document(&doc)
+                .unwrap()
+                .iter()
+                .map(|mesh| {
+                    let z: Vec<f32> = mesh.vertices.chunks(3).map(|v| v[2]).collect();
+                    let min = z.iter().cloned().fold(f32::INFINITY, f32::min);
+                    let max = z.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+                    (min, max)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let ranges = split(vcad_ir::SplitKeep::Positive);
+        assert_eq!(ranges.len(), 1);
+        assert!((ranges[0].0 - 4.0).abs() < 1e-4 && (ranges[0].1 - 10.0).abs() < 1e-4);
+        let ranges = split(vcad_ir::SplitKeep::Negative);
+        assert_eq!(ranges.len(), 1);
+        assert!(ranges[0].0.abs() < 1e-4 && (ranges[0].1 - 4.0).abs() < 1e-4);
+        // Both halves come out as separate bodies of the one root
+        let ranges = split(vcad_ir::SplitKeep::Both);
+        assert_eq!(ranges.len(), 2);
+        assert!((ranges[0].0 - 4.0).abs() < 1e-4 && (ranges[0].1 - 10.0).abs() < 1e-4);
+        assert!(ranges[1].0.abs() < 1e-4 && (ranges[1].1 - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_split_both_needs_scene_root() {
+        let ops = |above| {
+            doc_with_root(vec![
+                CsgOp::Cube {
+                    size: Vec3::new(10.0, 10.0, 10.0),
+                    origin: BoxOrigin::Corner,
+                },
+                CsgOp::SplitByPlane {
+                    child: 0,
+                    plane_origin: Vec3::new(0.0, 0.0, 4.0),
+                    plane_normal: Vec3::new(0.0, 0.0, 1.0),
+                    keep: vcad_ir::SplitKeep::Both,
+                },
+                above,
+            ])
+        };
+
+        // Transforms apply to each half
+        let doc = ops(CsgOp::Translate {
+            child: 1,
+            offset: Vec3::new(0.0, 0.0, 5.0),
+        });
+        let solids = evaluate_solids(&doc).unwrap();
+        let bodies = &solids[&doc.roots[0].root];
+        assert_eq!(bodies.len(), 2);
+        let volume: f64 = bodies.iter().map(|s| s.volume()).sum();
+        assert!((volume - 1000.0).abs() < 1e-3);
+
+        // Other operations need a single solid
+        let doc = ops(CsgOp::Shell {
+            child: 1,
+            thickness: 1.0,
+        });
+        assert!(evaluate_document(&doc).is_err());
+    }
+
+    #[test]
+    fn test_fit_view_targets_scene() {
+        let mut app = App::new(None).unwrap();
+        app.fit_view();
+        assert_eq!(app.status, "Nothing to fit");
+
+        app.add_cube(40.0).unwrap();
+        let (min, max) = app.mesh_bounds().unwrap();
+        app.fit_view();
+        for i in 0..3 {
+            let center = (min[i] + max[i]) / 2.0;
+            let target = [
+                app.camera.target.x,
+                app.camera.target.y,
+                app.camera.target.z,
+            ][i];
+            assert!((target - center).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_text() {
+        let text = |font: &str| CsgOp::Text {
+            content: "AB".to_string(),
+            font: font.to_string(),
+            height: 10.0,
+            depth: 2.0,
+            plane_origin: Vec3::new(0.0, 0.0, 0.0),
+            x_dir: Vec3::new(1.0, 0.0, 0.0),
+            y_dir: Vec3::new(0.0, 1.0, 0.0),
+            alignment: TextAlignment::Center,
+        };
+
+        let doc = doc_with_root(vec![text("sans-serif")]);
+        let solid = evaluate_node(&doc, 0, &SolidCache::new(&doc), 0)
+            .unwrap()
+            .unwrap();
+        assert!(solid.volume() > 0.0);
+        let (min, max) = solid.bounding_box();
+        assert!(min[0] < 0.0 && max[0] > 0.0);
+        assert!((max[2] - 2.0).abs() < 1e-9);
+
+        // Unknown fonts fall back to the built-in one, with a warning
+        let doc = doc_with_root(vec![text("Comic Sans")]);
+        let cache = SolidCache::new(&doc);
+        let fallback = evaluate_node(&doc, 0, &cache, 0).unwrap().unwrap();
+        assert!((fallback.volume() - solid.volume()).abs() < 1e-9);
+        assert_eq!(
+            cache.into_warnings(),
+            ["Unknown font 'Comic Sans', falling back to 'sans-serif'"]
+        );
+
+        // The TUI shows the warning instead of printing it
+        let mut app = App::new(None).unwrap();
+        app.document = doc;
+        app.evaluate().unwrap();
+        assert_eq!(app.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_shared_subtree_evaluated_once() {
+        // A cube used by both sides of a union
+        let translate = |x: f64| CsgOp::Translate {
+            child: 0,
+            offset: Vec3::new(x, 0.0, 0.0),
+        };
+        let doc = doc_with_root(vec![
+            CsgOp::Cube {
+                size: Vec3::new(1.0, 1.0, 1.0),
+                origin: BoxOrigin::Corner,
+            },
+            translate(0.0),
+            translate(3.0),
+            CsgOp::Union { left: 1, right: 2 },
+        ]);
+
+        let cache = SolidCache::new(&doc);
+        let solid = evaluate_node(&doc, 3, &cache, 0).unwrap().unwrap();
+        assert!((solid.volume() - 2.0).abs() < 1e-9);
+        assert_eq!(cache.evaluated.load(Ordering::Relaxed), 4);
+
+        // Only the cube is kept; evaluating again redoes the three nodes
+        // above it
+        assert_eq!(cache.solids.keys().collect::<Vec<_>>(), [&0]);
+        evaluate_node(&doc, 3, &cache, 0).unwrap();
+        assert_eq!(cache.evaluated.load(Ordering::Relaxed), 7);
+    }
+
+    #[test]
+    fn test_cyclic_document_errors() {
+        let doc = doc_with_root(vec![
+            CsgOp::Translate {
+                child: 1,
+                offset: Vec3::new(1.0, 0.0, 0.0),
+            },
+            CsgOp::Union { left: 0, right: 0 },
+        ]);
+        let err = evaluate_document(&doc).err().unwrap();
+        assert!(err.to_string().contains("cycle"), "{err}");
+        assert!(evaluate_solids(&doc).is_err());
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_mouse_drag_orbits_camera() {
+        let mut app = App::new(None).unwrap();
+        let areas = ui::layout(Rect::new(0, 0, 120, 40), false);
+        let (x, y) = (areas.viewport.x + 30, areas.viewport.y + 10);
+        let azimuth = app.camera.azimuth();
+        let elevation = app.camera.elevation();
+
+        app.handle_mouse(
+            mouse(MouseEventKind::Down(MouseButton::Left), x, y),
+            &areas,
+            0,
+        );
+        app.handle_mouse(
+            mouse(MouseEventKind::Drag(MouseButton::Left), x + 5, y),
+            &areas,
+            0,
+        );
+        app.handle_mouse(
+            mouse(MouseEventKind::Drag(MouseButton::Left), x + 10, y),
+            &areas,
+            0,
+        );
+        app.handle_mouse(
+            mouse(MouseEventKind::Up(MouseButton::Left), x + 10, y),
+            &areas,
+            0,
+        );
+
+        // Ten cells of two pixels each
+        let expected = -10.0 * CELL_PIXELS.0 * ORBIT_DEGREES_PER_PIXEL;
+        assert!((app.camera.azimuth() - azimuth - expected).abs() < 1e-4);
+        assert_eq!(app.camera.elevation(), elevation);
+
+        // Dragging outside a press does nothing
+        app.handle_mouse(
+            mouse(MouseEventKind::Drag(MouseButton::Left), x, y),
+            &areas,
+            0,
+        );
+        assert!((app.camera.azimuth() - azimuth - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_mouse_click_selects_part() {
+        let mut app = App::new(None).unwrap();
+        let id = app.add_cube(20.0).unwrap();
+        app.fit_view();
+        let areas = ui::layout(Rect::new(0, 0, 120, 40), false);
+        let viewport = areas.viewport.inner(Margin::new(1, 1));
+        let center = (
+            viewport.x + viewport.width / 2,
+            viewport.y + viewport.height / 2,
+        );
+
+        let click = |app: &mut App, (x, y): (u16, u16)| {
+            app.handle_mouse(
+                mouse(MouseEventKind::Down(MouseButton::Left), x, y),
+                &areas,
+                0,
+            );
+            app.handle_mouse(
+                mouse(MouseEventKind::Up(MouseButton::Left), x, y),
+                &areas,
+                0,
+            )
+        };
+        click(&mut app, center);
+        assert!(app.selected.contains(&id));
+
+        // The corner of the viewport misses the cube
+        click(&mut app, (viewport.x, viewport.y));
+        assert!(app.selected.is_empty());
+
+        // First row of the parts tree
+        let tree = areas.tree.inner(Margin::new(1, 1));
+        assert_eq!(click(&mut app, (tree.x + 2, tree.y)), None);
+        assert!(app.selected.contains(&id));
+    }
+
+    #[test]
+    fn test_tree_click_accounts_for_scroll() {
+        let mut app = App::new(None).unwrap();
+        let ids: Vec<NodeId> = (0..13).map(|_| app.add_cube(1.0).unwrap()).collect();
+        let areas = ui::layout(Rect::new(0, 0, 120, 20), false);
+        let tree = areas.tree.inner(Margin::new(1, 1));
+        assert!((tree.height as usize) < ids.len());
+
+        // Focusing the last part scrolls it onto the bottom row
+        let focused = ids.len() - 1;
+        let offset = ui::tree_scroll_offset(focused, tree.height);
+        assert_eq!(offset + tree.height as usize - 1, focused);
+
+        let down = mouse(MouseEventKind::Down(MouseButton::Left), tree.x + 2, tree.y);
+        assert_eq!(app.handle_mouse(down, &areas, focused), Some(offset));
+        assert!(app.selected.contains(&ids[offset]));
+    }
+}
@@ -30,7 +30,7 @@ enum Commands {
     Export {
         /// Input .vcad file
         input: PathBuf,
-        /// Output file (format determined by extension: .stl, .glb, .step, .stp, .urdf)
+        /// Output file (format determined by extension: .stl, .obj, .glb, .step, .stp, .urdf)
         output: PathBuf,
     },
     /// Import a STEP file to .vcad format
@@ -93,10 +93,15 @@ fn export_file(input: &PathBuf, output: &PathBuf) -> Result<()> {
     use std::fs;
 
     let json = fs::read_to_string(input)?;
-    let doc = vcad_ir::Document::from_json(&json)?;
+    let doc = vcad_ir::Document::from_json_migrated(&json)?;
+    let resolved = doc.resolved()?;
+    let doc: &vcad_ir::Document = &resolved;
 
     // Evaluate document to get meshes
-    let meshes = crate::app::evaluate_document(&doc)?;
+    #[cfg(feature = "rayon")]
+    let meshes = crate::app::evaluate_document_parallel(doc)?;
+    #[cfg(not(feature = "rayon"))]
+    let meshes = crate::app::evaluate_document(doc)?;
 
     let ext = output.extension().and_then(|e| e.to_str()).unwrap_or("");
     match ext.to_lowercase().as_str() {
@@ -111,18 +116,23 @@ fn export_file(input: &PathBuf, output: &PathBuf) -> Result<()> {
                     combined_idxs.push(idx + base_idx);
                 }
             }
+            warn_if_not_watertight(&combined_verts, &combined_idxs);
             let stl_bytes = export_stl_bytes(&combined_verts, &combined_idxs)?;
             fs::write(output, stl_bytes)?;
             println!("Exported STL to {}", output.display());
         }
+        "obj" => {
+            fs::write(output, obj_text(doc, &meshes))?;
+            println!("Exported OBJ to {}", output.display());
+        }
         "glb" => {
             println!("GLB export not yet implemented in CLI");
         }
         "step" | "stp" => {
-            export_step(&doc, output)?;
+            export_step(doc, output)?;
         }
         "urdf" => {
-            export_urdf(&doc, output)?;
+            export_urdf(doc, output)?;
         }
         _ => {
             anyhow::bail!("Unknown output format: {}", ext);
@@ -132,6 +142,51 @@ fn export_file(input: &PathBuf, output: &PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Warn when the exported mesh has open or inconsistently wound edges,
+/// which slicers tend to reject or repair unpredictably.
+fn warn_if_not_watertight(vertices: &[f32], indices: &[u32]) {
+    use vcad_kernel::vcad_kernel_tessellate::TriangleMesh;
+
+    let mesh = TriangleMesh {
+        vertices: vertices.to_vec(),
+        indices: indices.to_vec(),
+        normals: Vec::new(),
+    };
+    if !mesh.is_watertight() {
+        eprintln!(
+            "Warning: mesh is not watertight ({} boundary edges); it may not print correctly",
+            mesh.boundary_edges().len()
+        );
+    }
+}
+
+/// One OBJ file with an object per scene root, named after the root node.
+fn obj_text(doc: &vcad_ir::Document, meshes: &[crate::app::EvaluatedMesh]) -> String {
+    use vcad_kernel::vcad_kernel_tessellate::{objects_to_obj, TriangleMesh};
+
+    let parts: Vec<(String, TriangleMesh)> = meshes
+        .iter()
+        .map(|mesh| {
+            let name = doc
+                .nodes
+                .get(&mesh.root)
+                .and_then(|n| n.name.clone())
+                .unwrap_or_else(|| format!("Node {}", mesh.root));
+            let mesh = TriangleMesh {
+                vertices: mesh.vertices.clone(),
+                indices: mesh.indices.clone(),
+                normals: Vec::new(),
+            };
+            (name, mesh)
+        })
+        .collect();
+    let objects: Vec<(&str, &TriangleMesh)> = parts
+        .iter()
+        .map(|(name, mesh)| (name.as_str(), mesh))
+        .collect();
+    objects_to_obj(&objects, true)
+}
+
 fn export_stl_bytes(vertices: &[f32], indices: &[u32]) -> Result<Vec<u8>> {
     let num_triangles = indices.len() / 3;
     let mut data = Vec::with_capacity(84 + num_triangles * 50);
@@ -202,7 +257,9 @@ fn export_step(doc: &vcad_ir::Document, output: &PathBuf) -> Result<()> {
 
     // Try to create a solid from the IR
     let solid = match &root_node.op {
-        vcad_ir::CsgOp::Cube { size } => Solid::cube(size.x, size.y, size.z),
+        vcad_ir::CsgOp::Cube { size, origin } => {
+            Solid::cube_at(size.x, size.y, size.z, (*origin).into())
+        }
         vcad_ir::CsgOp::Cylinder {
             radius,
             height,
@@ -283,6 +340,7 @@ fn import_step(input: &PathBuf, output: &PathBuf, name: Option<String>) -> Resul
                 op: vcad_ir::CsgOp::StepImport {
                     path: input.to_string_lossy().into_owned(),
                 },
+                attributes: None,
             },
         );
         doc.roots.push(vcad_ir::SceneEntry {
@@ -317,6 +375,21 @@ fn show_info(file: &PathBuf) -> Result<()> {
     println!("  Materials: {}", doc.materials.len());
     println!("  Scene entries: {}", doc.roots.len());
 
+    match doc.statistics() {
+        Ok(stats) => {
+            println!("  Primitives: {}", stats.primitives);
+            println!("  Shared nodes: {}", stats.shared_nodes);
+            println!("  Max depth: {}", stats.max_depth);
+            println!("\nOperations:");
+            for (op, count) in &stats.op_counts {
+                println!("  {}: {}", op, count);
+            }
+        }
+        Err(e) => {
+            println!("  Invalid node graph: {}", e);
+        }
+    }
+
     if !doc.roots.is_empty() {
         println!("\nScene:");
         for (i, entry) in doc.roots.iter().enumerate() {
@@ -329,18 +402,59 @@ fn show_info(file: &PathBuf) -> Result<()> {
         }
     }
 
-    // Evaluate and show mesh stats
-    match crate::app::evaluate_document(&doc) {
-        Ok(meshes) => {
-            let total_tris: usize = meshes.iter().map(|m| m.indices.len() / 3).sum();
-            let total_verts: usize = meshes.iter().map(|m| m.vertices.len() / 3).sum();
-            println!("\nMesh stats:");
-            println!("  Total triangles: {}", total_tris);
-            println!("  Total vertices: {}", total_verts);
-        }
+    // Evaluate once for the mesh stats, mass properties and material usage
+    let solids = match crate::app::evaluate_solids(&doc) {
+        Ok(solids) => solids,
         Err(e) => {
             println!("\nFailed to evaluate: {}", e);
+            return Ok(());
+        }
+    };
+    let meshes: Vec<_> = doc
+        .roots
+        .iter()
+        .filter_map(|entry| solids.get(&entry.root))
+        .flatten()
+        .map(|solid| solid.to_mesh(32))
+        .collect();
+    let total_tris: usize = meshes.iter().map(|m| m.indices.len() / 3).sum();
+    let total_verts: usize = meshes.iter().map(|m| m.vertices.len() / 3).sum();
+    println!("\nMesh stats:");
+    println!("  Total triangles: {}", total_tris);
+    println!("  Total vertices: {}", total_verts);
+
+    let mass = crate::app::mass_properties(&doc, &solids);
+    if mass.mass > 0.0 {
+        let com = mass.center_of_mass;
+        println!("\nMass properties:");
+        println!("  Mass: {:.4} kg", mass.mass);
+        println!(
+            "  Center of mass: ({:.3}, {:.3}, {:.3}) mm",
+            com.x, com.y, com.z
+        );
+    }
+
+    let mut usage: Vec<_> = crate::app::material_usage(&doc, &solids)
+        .into_iter()
+        .collect();
+    usage.sort_by(|a, b| a.0.cmp(&b.0));
+    println!("\nMaterial usage:");
+    for (material, amount) in &usage {
+        match amount.mass {
+            Some(mass) => println!(
+                "  {}: {:.2} cm³, {:.1} g",
+                material,
+                amount.volume / 1000.0,
+                mass * 1000.0
+            ),
+            None => println!("  {}: {:.2} cm³", material, amount.volume / 1000.0),
         }
+        let print = amount.print_estimate(0.2, 0.2);
+        println!(
+            "    FDM print at 20% infill, 0.2 mm layers: {:.2} m filament, {:.1} h",
+            print.filament_length / 1000.0,
+            print.time / 3600.0
+        );
     }
 
     Ok(())
@@ -383,3 +497,60 @@ fn export_urdf(doc: &vcad_ir::Document, output: &PathBuf) -> Result<()> {
     );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vcad_kernel::vcad_kernel_tessellate::TriangleMesh;
+    use vcad_kernel::Solid;
+
+    fn bounds(vertices: &[f32]) -> ([f32; 3], [f32; 3]) {
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for v in vertices.chunks_exact(3) {
+            for c in 0..3 {
+                min[c] = min[c].min(v[c]);
+                max[c] = max[c].max(v[c]);
+            }
+        }
+        (min, max)
+    }
+
+    #[test]
+    fn test_stl_export_roundtrip() {
+        let solid = Solid::cylinder(5.0, 12.0, 24).translate(1.0, 2.0, 3.0);
+        let mesh = solid.to_mesh(24);
+        let bytes = export_stl_bytes(&mesh.vertices, &mesh.indices).unwrap();
+
+        let imported = TriangleMesh::from_stl_bytes(&bytes).unwrap();
+        assert_eq!(imported.num_triangles(), mesh.num_triangles());
+        assert!(imported.num_vertices() <= mesh.num_vertices());
+        assert_eq!(bounds(&imported.vertices), bounds(&mesh.vertices));
+
+        let reloaded = Solid::from_stl_bytes(&bytes).unwrap();
+        assert!((reloaded.volume() - solid.volume()).abs() < 1e-3 * solid.volume());
+    }
+
+    #[test]
+    fn test_obj_export_groups_parts() {
+        let mut doc =
+            vcad_ir::Document::from_json_migrated(include_str!("../../../examples/plate.vcad"))
+                .unwrap();
+        // A second part: the hole cylinder on its own
+        doc.roots.push(vcad_ir::SceneEntry {
+            root: 3,
+            material: "aluminum".to_string(),
+            visible: None,
+        });
+        let meshes = crate::app::evaluate_document(&doc).unwrap();
+        let text = obj_text(&doc, &meshes);
+
+        assert!(text.contains("o Plate_with_Hole\ng Plate_with_Hole\n"));
+        assert!(text.contains("g Hole_Positioned\n"));
+        let imported = TriangleMesh::from_obj_str(&text).unwrap();
+        let triangles: usize = meshes.iter().map(|m| m.indices.len() / 3).sum();
+        let vertices: usize = meshes.iter().map(|m| m.vertices.len() / 3).sum();
+        assert_eq!(imported.num_triangles(), triangles);
+        assert_eq!(imported.num_vertices(), vertices);
+    }
+}
@@ -45,6 +45,10 @@ impl Vec3 {
     pub fn sub(self, other: Self) -> Self {
         Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
     }
+
+    pub fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
 }
 
 /// A triangle with vertex positions and color.
@@ -56,6 +60,30 @@ pub struct Triangle {
     pub color: [u8; 3],
 }
 
+/// How the camera projects the scene onto the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Projection {
+    /// Perspective projection with the camera's field of view.
+    #[default]
+    Perspective,
+    /// Parallel projection. The visible height matches what the perspective
+    /// camera sees at the target, so toggling keeps the model the same size.
+    Orthographic,
+}
+
+/// Standard viewing directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewPreset {
+    /// Looking down -Z.
+    Front,
+    /// Looking down -Y.
+    Top,
+    /// Looking down -X.
+    Right,
+    /// Isometric view from the (+X, +Y, +Z) octant.
+    Iso,
+}
+
 /// Camera for 3D viewing.
 #[derive(Debug, Clone)]
 pub struct Camera {
@@ -67,6 +95,10 @@ pub struct Camera {
     pub up: Vec3,
     /// Field of view in degrees.
     pub fov: f32,
+    /// Direction towards the key light in world space (need not be unit length).
+    pub light_dir: Vec3,
+    /// Projection mode.
+    pub projection: Projection,
     /// Distance from target (for orbit controls).
     distance: f32,
     /// Horizontal angle in degrees.
@@ -95,6 +127,8 @@ impl Default for Camera {
             target: Vec3::new(0.0, 0.0, 0.0),
             up: Vec3::new(0.0, 1.0, 0.0),
             fov: 60.0,
+            light_dir: Vec3::new(0.5, 0.8, 0.3),
+            projection: Projection::default(),
             distance,
             azimuth,
             elevation,
@@ -121,6 +155,101 @@ impl Camera {
         self.update_position();
     }
 
+    /// Horizontal orbit angle in degrees.
+    pub fn azimuth(&self) -> f32 {
+        self.azimuth
+    }
+
+    /// Vertical orbit angle in degrees.
+    pub fn elevation(&self) -> f32 {
+        self.elevation
+    }
+
+    /// World-space ray through pixel `(x, y)` of a `width` x `height`
+    /// viewport, as `(origin, unit direction)`.
+    ///
+    /// Pixel coordinates grow right and down, as in [`RenderBuffer`].
+    pub fn pick_ray(&self, x: f32, y: f32, width: f32, height: f32) -> (Vec3, Vec3) {
+        let ndc_x = 2.0 * x / width - 1.0;
+        let ndc_y = 1.0 - 2.0 * y / height;
+        let aspect = width / height;
+        let tan_half_fov = (self.fov / 2.0).to_radians().tan();
+
+        // Same basis as `Mat4::look_at`
+        let back = self.position.sub(self.target).normalize();
+        let right = self.up.cross(back).normalize();
+        let up = back.cross(right);
+        let across = right
+            .scale(ndc_x * aspect * tan_half_fov)
+            .add(up.scale(ndc_y * tan_half_fov));
+
+        match self.projection {
+            Projection::Perspective => (self.position, across.sub(back).normalize()),
+            Projection::Orthographic => (
+                self.position.add(across.scale(self.distance)),
+                back.scale(-1.0),
+            ),
+        }
+    }
+
+    /// Switch between perspective and orthographic projection.
+    pub fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+    }
+
+    /// Orbit to one of the standard views, keeping target and distance.
+    pub fn set_view(&mut self, view: ViewPreset) {
+        let (azimuth, elevation) = match view {
+            ViewPreset::Front => (0.0, 0.0),
+            ViewPreset::Top => (0.0, 89.0),
+            ViewPreset::Right => (90.0, 0.0),
+            // atan(1/sqrt(2)): the elevation of the (1, 1, 1) diagonal
+            ViewPreset::Iso => (45.0, 35.264),
+        };
+        self.azimuth = azimuth;
+        self.elevation = elevation;
+        self.update_position();
+    }
+
+    /// Aim at the center of an axis-aligned box and back off until its
+    /// bounding sphere fills the view, keeping the current viewing direction.
+    ///
+    /// `aspect` is the viewport's width over height; the sphere is fitted to
+    /// the narrower of the horizontal and vertical fields of view.
+    pub fn fit_bounds(&mut self, min: Vec3, max: Vec3, aspect: f32) {
+        let center = Vec3::new(
+            (min.x + max.x) / 2.0,
+            (min.y + max.y) / 2.0,
+            (min.z + max.z) / 2.0,
+        );
+        let half = max.sub(center);
+        let radius = half.dot(half).sqrt().max(1e-3);
+        let half_vertical = (self.fov / 2.0).to_radians();
+        let half_horizontal = (half_vertical.tan() * aspect).atan();
+        let half_fov = half_vertical.min(half_horizontal);
+
+        self.target = center;
+        // Small margin so the silhouette doesn't touch the viewport edge
+        self.distance = radius * 1.1 / half_fov.sin();
+        self.update_position();
+    }
+
+    /// Combined view and projection matrix for a viewport aspect ratio.
+    fn view_projection(&self, aspect: f32) -> Mat4 {
+        let view = Mat4::look_at(self.position, self.target, self.up);
+        let fov = self.fov * PI / 180.0;
+        // Fitting large models can push the camera past the default far plane
+        let far = (self.distance * 2.0).max(1000.0);
+        let proj = match self.projection {
+            Projection::Perspective => Mat4::perspective(fov, aspect, 0.1, far),
+            Projection::Orthographic => {
+                let half_height = self.distance * (fov / 2.0).tan();
+                Mat4::orthographic(half_height * aspect, half_height, 0.1, far)
+            }
+        };
+        proj.multiply(&view)
+    }
+
     fn update_position(&mut self) {
         let az_rad = self.azimuth.to_radians();
         let el_rad = self.elevation.to_radians();
@@ -139,6 +268,13 @@ pub struct RenderBuffer {
     pub height: u32,
     pub pixels: Vec<u8>,
     pub depth: Vec<f32>,
+    /// Fraction of each pixel covered by geometry (0.0..=1.0). Only 0 or 1
+    /// unless the scene was supersampled.
+    pub coverage: Vec<f32>,
+    /// Samples per pixel along each axis: scenes are rasterized at this
+    /// multiple of the buffer resolution and box-filtered down, which
+    /// anti-aliases edges. 1 (the default) renders directly.
+    pub supersample: u32,
 }
 
 impl RenderBuffer {
@@ -149,6 +285,8 @@ impl RenderBuffer {
             height,
             pixels: vec![0; size * 4],
             depth: vec![f32::INFINITY; size],
+            coverage: vec![0.0; size],
+            supersample: 1,
         }
     }
 
@@ -160,6 +298,7 @@ impl RenderBuffer {
             self.pixels[i * 4 + 2] = b;
             self.pixels[i * 4 + 3] = 255;
             self.depth[i] = f32::INFINITY;
+            self.coverage[i] = 0.0;
         }
     }
 
@@ -174,8 +313,54 @@ impl RenderBuffer {
             self.pixels[idx * 4 + 1] = g;
             self.pixels[idx * 4 + 2] = b;
             self.pixels[idx * 4 + 3] = 255;
+            self.coverage[idx] = 1.0;
         }
     }
+
+    /// Box-filter this buffer into `target`, which must be `factor` times
+    /// smaller in each dimension. Colors and coverage are averaged over each
+    /// `factor`×`factor` block; depth keeps the nearest sample.
+    fn downsample_into(&self, target: &mut RenderBuffer, factor: u32) {
+        let samples = (factor * factor) as f32;
+        for y in 0..target.height {
+            for x in 0..target.width {
+                let mut color = [0.0f32; 3];
+                let mut coverage = 0.0;
+                let mut depth = f32::INFINITY;
+                for sy in 0..factor {
+                    for sx in 0..factor {
+                        let idx = ((y * factor + sy) * self.width + x * factor + sx) as usize;
+                        for (c, &p) in color.iter_mut().zip(&self.pixels[idx * 4..idx * 4 + 3]) {
+                            *c += p as f32;
+                        }
+                        coverage += self.coverage[idx];
+                        depth = depth.min(self.depth[idx]);
+                    }
+                }
+
+                let idx = (y * target.width + x) as usize;
+                for (p, c) in target.pixels[idx * 4..idx * 4 + 3].iter_mut().zip(color) {
+                    *p = (c / samples).round() as u8;
+                }
+                target.pixels[idx * 4 + 3] = 255;
+                target.coverage[idx] = coverage / samples;
+                target.depth[idx] = depth;
+            }
+        }
+    }
+}
+
+/// Rendering quality settings, applied to each frame's [`RenderBuffer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderConfig {
+    /// Supersampling factor; see [`RenderBuffer::supersample`].
+    pub supersample: u32,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self { supersample: 1 }
+    }
 }
 
 /// 4x4 matrix for transformations.
@@ -237,12 +422,40 @@ impl Mat4 {
         }
     }
 
+    /// Symmetric orthographic projection of the box
+    /// `[-half_width, half_width] x [-half_height, half_height] x [-near, -far]`.
+    fn orthographic(half_width: f32, half_height: f32, near: f32, far: f32) -> Self {
+        let nf = 1.0 / (near - far);
+
+        Self {
+            data: [
+                1.0 / half_width,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                1.0 / half_height,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                2.0 * nf,
+                0.0,
+                0.0,
+                0.0,
+                (far + near) * nf,
+                1.0,
+            ],
+        }
+    }
+
+    /// Matrix product `self * other` (column-major storage).
     fn multiply(&self, other: &Mat4) -> Mat4 {
         let mut result = [0.0f32; 16];
-        for i in 0..4 {
-            for j in 0..4 {
+        for col in 0..4 {
+            for row in 0..4 {
                 for k in 0..4 {
-                    result[i * 4 + j] += self.data[i * 4 + k] * other.data[k * 4 + j];
+                    result[col * 4 + row] += self.data[k * 4 + row] * other.data[col * 4 + k];
                 }
             }
         }
@@ -258,12 +471,31 @@ impl Mat4 {
     }
 }
 
+/// Ambient light contribution (fraction of the base color).
+const AMBIENT: f32 = 0.3;
+/// Diffuse (Lambert) light contribution at normal incidence.
+const DIFFUSE: f32 = 0.7;
+
 fn edge_function(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> f32 {
     (c.0 - a.0) * (b.1 - a.1) - (c.1 - a.1) * (b.0 - a.0)
 }
 
-/// Render triangles to the buffer.
+/// Render triangles to the buffer, supersampling per
+/// [`RenderBuffer::supersample`].
 pub fn render_scene(buffer: &mut RenderBuffer, triangles: &[Triangle], camera: &Camera) {
+    let factor = buffer.supersample.max(1);
+    // The empty-scene grid is drawn with single-pixel lines that filtering
+    // would only wash out.
+    if factor == 1 || triangles.is_empty() {
+        rasterize(buffer, triangles, camera);
+        return;
+    }
+    let mut hires = RenderBuffer::new(buffer.width * factor, buffer.height * factor);
+    rasterize(&mut hires, triangles, camera);
+    hires.downsample_into(buffer, factor);
+}
+
+fn rasterize(buffer: &mut RenderBuffer, triangles: &[Triangle], camera: &Camera) {
     buffer.clear(30, 30, 35);
 
     if triangles.is_empty() {
@@ -273,12 +505,9 @@ pub fn render_scene(buffer: &mut RenderBuffer, triangles: &[Triangle], camera: &
     }
 
     let aspect = buffer.width as f32 / buffer.height as f32;
-    let view = Mat4::look_at(camera.position, camera.target, camera.up);
-    let proj = Mat4::perspective(camera.fov * PI / 180.0, aspect, 0.1, 1000.0);
-    let mvp = proj.multiply(&view);
+    let mvp = camera.view_projection(aspect);
 
-    // Light direction (from top-right-front)
-    let light_dir = Vec3::new(0.5, 0.8, 0.3).normalize();
+    let light_dir = camera.light_dir.normalize();
 
     for tri in triangles {
         let v0 = Vec3::new(tri.v0[0], tri.v0[1], tri.v0[2]);
@@ -321,9 +550,7 @@ pub fn render_scene(buffer: &mut RenderBuffer, triangles: &[Triangle], camera: &
             normal
         };
         let ndotl = shading_normal.dot(light_dir).max(0.0);
-        let ambient = 0.3;
-        let diffuse = 0.7;
-        let intensity = ambient + diffuse * ndotl;
+        let intensity = AMBIENT + DIFFUSE * ndotl;
 
         let lit_r = ((tri.color[0] as f32) * intensity).min(255.0) as u8;
         let lit_g = ((tri.color[1] as f32) * intensity).min(255.0) as u8;
@@ -453,6 +680,60 @@ mod tests {
         assert!((camera.distance - initial_dist).abs() < 0.1);
     }
 
+    #[test]
+    fn test_fit_bounds_frames_box() {
+        let min = Vec3::new(-40.0, 5.0, 100.0);
+        let max = Vec3::new(60.0, 25.0, 180.0);
+        let corners: Vec<Vec3> = (0..8)
+            .map(|i| {
+                Vec3::new(
+                    if i & 1 == 0 { min.x } else { max.x },
+                    if i & 2 == 0 { min.y } else { max.y },
+                    if i & 4 == 0 { min.z } else { max.z },
+                )
+            })
+            .collect();
+
+        for projection in [Projection::Perspective, Projection::Orthographic] {
+            for view in [ViewPreset::Front, ViewPreset::Top, ViewPreset::Iso] {
+                // Wide and tall viewports
+                for aspect in [1.5, 0.4] {
+                    let mut camera = Camera::default();
+                    camera.set_projection(projection);
+                    camera.set_view(view);
+                    camera.fit_bounds(min, max, aspect);
+
+                    let mvp = camera.view_projection(aspect);
+                    for &corner in &corners {
+                        let (x, y, z, w) = mvp.transform_point(corner);
+                        assert!(w > 0.0, "{projection:?} {view:?}: corner behind camera");
+                        for c in [x, y, z] {
+                            assert!(
+                                (-1.0..=1.0).contains(&c),
+                                "{projection:?} {view:?} {aspect}: corner outside viewport ({x}, {y}, {z})"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_orthographic_keeps_size_at_target() {
+        let mut camera = Camera::default();
+        camera.set_view(ViewPreset::Front);
+        // In the plane through the target facing the camera
+        let edge = Vec3::new(0.0, 10.0, 0.0);
+        let (_, perspective_y, _, _) = camera.view_projection(1.0).transform_point(edge);
+        camera.set_projection(Projection::Orthographic);
+        let (_, ortho_y, _, _) = camera.view_projection(1.0).transform_point(edge);
+        assert!(
+            (perspective_y - ortho_y).abs() < 1e-4,
+            "{perspective_y} vs {ortho_y}"
+        );
+    }
+
     #[test]
     fn test_render_buffer() {
         let buffer = RenderBuffer::new(100, 50);
@@ -492,7 +773,97 @@ mod tests {
         // This should not panic
         render_scene(&mut buffer, &triangles, &camera);
 
+        // The triangle straddles the look-at target, so the center is covered.
+        assert!(buffer.depth[(50 * buffer.width + 50) as usize].is_finite());
+
         // Buffer should be modified (at least cleared)
         assert!(buffer.pixels.iter().any(|&p| p > 0));
     }
+
+    #[test]
+    fn test_supersampling_partial_coverage() {
+        let triangles = vec![Triangle {
+            v0: [-10.0, -10.0, 0.0],
+            v1: [10.0, -10.0, 0.0],
+            v2: [0.0, 10.0, 0.0],
+            color: [180, 180, 190],
+        }];
+        let camera = Camera::default();
+        let is_partial = |c: &f32| *c > 0.0 && *c < 1.0;
+
+        // A hard edge at 1x: every pixel is either covered or not.
+        let mut hard = RenderBuffer::new(100, 100);
+        render_scene(&mut hard, &triangles, &camera);
+        assert!(!hard.coverage.iter().any(is_partial));
+
+        // At 2x the slanted edges pick up intermediate coverage and colors.
+        let mut smooth = RenderBuffer::new(100, 100);
+        smooth.supersample = 2;
+        render_scene(&mut smooth, &triangles, &camera);
+        let partial = smooth.coverage.iter().position(is_partial).unwrap();
+        let full = smooth.coverage.iter().position(|&c| c == 1.0).unwrap();
+        let (background, lit) = (30, smooth.pixels[full * 4]);
+        let blended = smooth.pixels[partial * 4];
+        assert!(
+            blended > background.min(lit) && blended < background.max(lit),
+            "blended = {blended}, lit = {lit}"
+        );
+        assert!(smooth.depth[partial].is_finite());
+    }
+
+    #[test]
+    fn test_render_lambert_shading() {
+        let mut buffer = RenderBuffer::new(64, 64);
+        let mut camera = Camera {
+            light_dir: Vec3::new(0.0, 1.0, 1.0),
+            ..Camera::default()
+        };
+
+        // Triangle in the XZ plane (normal +Y) large enough to cover the center pixel.
+        let triangles = vec![Triangle {
+            v0: [-50.0, 0.0, -50.0],
+            v1: [0.0, 0.0, 80.0],
+            v2: [80.0, 0.0, -20.0],
+            color: [200, 200, 200],
+        }];
+        render_scene(&mut buffer, &triangles, &camera);
+
+        let ndotl = Vec3::new(0.0, 1.0, 0.0).dot(camera.light_dir.normalize());
+        let expected = (200.0 * (AMBIENT + DIFFUSE * ndotl)) as u8;
+
+        let idx = ((32 * buffer.width + 32) * 4) as usize;
+        assert!(buffer.depth[(32 * buffer.width + 32) as usize].is_finite());
+        assert_eq!(buffer.pixels[idx], expected);
+        assert_eq!(buffer.pixels[idx + 1], expected);
+        assert_eq!(buffer.pixels[idx + 2], expected);
+
+        // Lighting from below the face only leaves the ambient term.
+        camera.light_dir = Vec3::new(0.0, -1.0, 0.0);
+        render_scene(&mut buffer, &triangles, &camera);
+        assert_eq!(buffer.pixels[idx], (200.0 * AMBIENT) as u8);
+    }
+
+    #[test]
+    fn test_pick_ray_hits_projected_point() {
+        let point = Vec3::new(12.0, -7.0, 4.0);
+        let (width, height) = (160.0, 90.0);
+        for projection in [Projection::Perspective, Projection::Orthographic] {
+            let mut camera = Camera::default();
+            camera.set_projection(projection);
+            let (x, y, _, _) = camera
+                .view_projection(width / height)
+                .transform_point(point);
+            let (origin, dir) = camera.pick_ray(
+                (x + 1.0) * 0.5 * width,
+                (1.0 - y) * 0.5 * height,
+                width,
+                height,
+            );
+
+            // Distance from the point to the ray
+            let to_point = point.sub(origin);
+            let off = to_point.sub(dir.scale(to_point.dot(dir)));
+            assert!(off.dot(off).sqrt() < 1e-3, "{projection:?}");
+        }
+    }
 }
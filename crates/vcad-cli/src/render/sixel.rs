@@ -6,7 +6,10 @@ use super::RenderBuffer;
 
 /// Convert a render buffer to braille character art.
 ///
-/// Each braille character represents a 2x4 pixel grid.
+/// Each braille character represents a 2x4 pixel grid. A dot is raised for
+/// pixels at least half covered by geometry; uncovered pixels (such as the
+/// empty-scene grid) fall back to a brightness threshold.
+///
 /// Returns (width_chars, height_chars, string).
 pub fn buffer_to_braille(buffer: &RenderBuffer) -> (u16, u16, String) {
     let char_width = (buffer.width / 2) as u16;
@@ -47,8 +50,14 @@ pub fn buffer_to_braille(buffer: &RenderBuffer) -> (u16, u16, String) {
                         count += 1;
 
                         // Threshold for "on" pixel
+                        let coverage = buffer.coverage[(y * buffer.width + x) as usize];
                         let brightness = (r + g + b) / 3;
-                        if brightness > 50 {
+                        let on = if coverage > 0.0 {
+                            coverage >= 0.5
+                        } else {
+                            brightness > 50
+                        };
+                        if on {
                             let bit = match (dx, dy) {
                                 (0, 0) => 0,
                                 (0, 1) => 1,
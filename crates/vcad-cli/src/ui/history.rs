@@ -0,0 +1,52 @@
+//! Undo history widget.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Width of the history panel in columns.
+pub const HISTORY_WIDTH: u16 = 28;
+
+/// Draw the history panel, newest state first.
+pub fn draw_history(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default().borders(Borders::ALL).title(" History ");
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let items: Vec<ListItem> = app
+        .history_entries()
+        .into_iter()
+        .rev()
+        .map(|entry| {
+            let (marker, style) = if entry.current {
+                (
+                    "> ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("  ", Style::default().fg(Color::White))
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::raw(marker),
+                Span::styled(
+                    format!("{:>2} ", entry.index),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(entry.label, style),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items);
+    f.render_widget(list, inner);
+}
@@ -1,11 +1,13 @@
 //! TUI widgets and layout.
 
 mod command;
+mod history;
 mod status;
 mod tree;
 mod viewport;
 
 pub use command::*;
+pub use history::*;
 pub use status::*;
 pub use tree::*;
 pub use viewport::*;
@@ -21,10 +23,25 @@ use ratatui::{
 use crate::app::App;
 use crate::render::RenderBuffer;
 
-/// Draw the full UI.
-pub fn draw(f: &mut Frame, app: &App, render_buffer: &RenderBuffer, focused_part_index: usize) {
-    let size = f.area();
+/// Screen areas of the UI panels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScreenLayout {
+    /// Title bar with the key help.
+    pub header: Rect,
+    /// Parts tree, including its border.
+    pub tree: Rect,
+    /// 3D viewport, including its border.
+    pub viewport: Rect,
+    /// History panel; zero-width when hidden.
+    pub history: Rect,
+    /// Command input line.
+    pub command: Rect,
+    /// Status bar.
+    pub status: Rect,
+}
 
+/// Split the terminal area into panels.
+pub fn layout(size: Rect, show_history: bool) -> ScreenLayout {
     // Main vertical layout
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -36,29 +53,49 @@ pub fn draw(f: &mut Frame, app: &App, render_buffer: &RenderBuffer, focused_part
         ])
         .split(size);
 
-    // Header
-    draw_header(f, chunks[0]);
-
-    // Main content (sidebar + viewport)
+    // Main content (sidebar + viewport + optional history panel)
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
             Constraint::Length(24), // Sidebar
             Constraint::Min(20),    // Viewport
+            Constraint::Length(if show_history { HISTORY_WIDTH } else { 0 }),
         ])
         .split(chunks[1]);
 
+    ScreenLayout {
+        header: chunks[0],
+        tree: main_chunks[0],
+        viewport: main_chunks[1],
+        history: main_chunks[2],
+        command: chunks[2],
+        status: chunks[3],
+    }
+}
+
+/// Draw the full UI.
+pub fn draw(f: &mut Frame, app: &App, render_buffer: &RenderBuffer, focused_part_index: usize) {
+    let areas = layout(f.area(), app.show_history);
+
+    // Header
+    draw_header(f, areas.header);
+
     // Sidebar (parts tree)
-    draw_tree(f, main_chunks[0], app, focused_part_index);
+    draw_tree(f, areas.tree, app, focused_part_index);
 
     // 3D Viewport
-    draw_viewport(f, main_chunks[1], render_buffer);
+    draw_viewport(f, areas.viewport, render_buffer);
+
+    // History panel
+    if app.show_history {
+        draw_history(f, areas.history, app);
+    }
 
     // Command input
-    draw_command(f, chunks[2], app);
+    draw_command(f, areas.command, app);
 
     // Status bar
-    draw_status(f, chunks[3], app);
+    draw_status(f, areas.status, app);
 }
 
 fn draw_header(f: &mut Frame, area: Rect) {
@@ -83,6 +120,12 @@ fn draw_header(f: &mut Frame, area: Rect) {
         Span::raw(":rotate "),
         Span::styled("+/-", Style::default().fg(Color::Yellow)),
         Span::raw(":zoom "),
+        Span::styled("v/t/i", Style::default().fg(Color::Yellow)),
+        Span::raw(":view "),
+        Span::styled("o", Style::default().fg(Color::Yellow)),
+        Span::raw(":ortho "),
+        Span::styled("f", Style::default().fg(Color::Yellow)),
+        Span::raw(":fit "),
         Span::styled("x", Style::default().fg(Color::Yellow)),
         Span::raw(":del "),
         Span::styled("u", Style::default().fg(Color::Yellow)),
@@ -15,7 +15,7 @@ pub fn draw_status(f: &mut Frame, area: Rect, app: &App) {
     let parts = app.get_parts();
     let tri_count: usize = app.meshes.iter().map(|m| m.indices.len() / 3).sum();
 
-    let status_line = Line::from(vec![
+    let mut spans = vec![
         Span::styled(" ", Style::default()),
         Span::styled(&app.status, Style::default().fg(Color::Green)),
         Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
@@ -29,6 +29,15 @@ pub fn draw_status(f: &mut Frame, area: Rect, app: &App) {
             Style::default().fg(Color::Cyan),
         ),
         Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!(
+                "View: az {:.0}° el {:.0}°",
+                app.camera.azimuth(),
+                app.camera.elevation()
+            ),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
         Span::styled(
             format!("Selected: {}", app.selected.len()),
             Style::default().fg(if app.selected.is_empty() {
@@ -37,7 +46,15 @@ pub fn draw_status(f: &mut Frame, area: Rect, app: &App) {
                 Color::Yellow
             }),
         ),
-    ]);
+    ];
+    if !app.warnings.is_empty() {
+        spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(
+            format!("Warning: {}", app.warnings.join("; ")),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    let status_line = Line::from(spans);
 
     let paragraph = Paragraph::new(status_line).style(Style::default().bg(Color::Rgb(30, 30, 35)));
     f.render_widget(paragraph, area);
@@ -10,6 +10,12 @@ use ratatui::{
 
 use crate::app::App;
 
+/// Index of the first part shown in a tree with `rows` visible rows,
+/// scrolled just far enough to keep the focused part in view.
+pub fn tree_scroll_offset(focused_index: usize, rows: u16) -> usize {
+    (focused_index + 1).saturating_sub(rows.max(1) as usize)
+}
+
 /// Draw the parts/feature tree.
 pub fn draw_tree(f: &mut Frame, area: Rect, app: &App, focused_index: usize) {
     let block = Block::default().borders(Borders::ALL).title(" Parts ");
@@ -26,9 +32,11 @@ pub fn draw_tree(f: &mut Frame, area: Rect, app: &App, focused_index: usize) {
         return;
     }
 
+    let offset = tree_scroll_offset(focused_index, inner.height);
     let items: Vec<ListItem> = parts
         .iter()
         .enumerate()
+        .skip(offset)
         .map(|(i, (id, name))| {
             let is_selected = app.selected.contains(id);
             let is_focused = i == focused_index;
@@ -19,7 +19,7 @@
 //!
 //! ## Geometry (line number = node ID, optional quoted name at end)
 //! ```text
-//! C sx sy sz ["name"]           # Cube
+//! C sx sy sz [center] ["name"]  # Cube (min corner at origin unless center)
 //! Y r h ["name"]                # Cylinder
 //! S r ["name"]                  # Sphere
 //! K rb rt h ["name"]            # Cone
@@ -34,6 +34,24 @@
 //! SH n thickness ["name"]       # Shell
 //! FI n radius ["name"]          # Fillet
 //! CH n distance ["name"]        # Chamfer
+//! TG n "key" "value" ["name"]   # Tag (metadata, geometrically a no-op)
+//! SP n ox oy oz nx ny nz keep ["name"]  # Split by plane (keep: positive, negative or both)
+//! TX "text" "font" height depth ox oy oz  xx xy xz  yx yy yz [align] ["name"]  # 3D text (align: left, center or right)
+//! ```
+//!
+//! ## Labels
+//!
+//! Any geometry line may be labeled with `@name:` right after its opcode,
+//! and the label used as `@name` wherever a node ID is expected: in later
+//! geometry lines, `ROOT` and `PDEF`. Labels must be defined before they are
+//! used and are resolved to the node's numeric ID, which stays valid
+//! alongside them.
+//! ```text
+//! C @base: 50 30 5 "Base Plate"
+//! Y @hole: 5 10
+//! T @moved: @hole 25 15 0
+//! D @base 2
+//! ROOT 3 default
 //! ```
 //!
 //! ## Sketch (block)
@@ -41,9 +59,25 @@
 //! SK ox oy oz  xx xy xz  yx yy yz ["name"]
 //! L x1 y1 x2 y2                 # Line segment
 //! A x1 y1 x2 y2 cx cy ccw       # Arc (ccw: 0 or 1)
+//! HOLE                          # Inner loop: L/A lines up to its own END
 //! END
 //! E sk dx dy dz ["name"]        # Extrude
 //! V sk ox oy oz ax ay az angle ["name"]  # Revolve
+//! LF mode sk1 sk2 ... [closed] ["name"]  # Loft (mode: ruled or smooth)
+//! SW sk L sx sy sz ex ey ez ["name"]      # Sweep along a line
+//! SW sk H radius pitch height turns ["name"]  # Sweep along a helix
+//! SW sk P x1 y1 z1 x2 y2 z2 ... ["name"]  # Sweep along a polyline
+//!                               # Any path may be followed by options:
+//!                               # twist=rad scale_start=s scale_end=s segments=n
+//!                               # arc_segments=n orientation=rad
+//!                               # degenerate=error|skip|interpolate
+//! PJ n ox oy oz nx ny nz ["name"]        # Project solid silhouette to sketch
+//! SVG "d" scale ox oy oz  xx xy xz  yx yy yz ["name"]  # SVG path profile
+//! ```
+//!
+//! ## Imports
+//! ```text
+//! STP "path" ["name"]           # STEP file import
 //! ```
 //!
 //! ## Scene roots
@@ -53,7 +87,8 @@
 //!
 //! ## Assembly
 //! ```text
-//! PDEF id "name" rootNodeId [material]
+//! PDEF id "name" rootNodeId [material|_] [collisionRootNodeId]
+//! PMASS partDefId mass cx cy cz ixx ixy ixz iyy iyz izz
 //! INST id partDefId "name" tx ty tz rx ry rz sx sy sz [material]
 //! JFIX id parentInst childInst px py pz cx cy cz
 //! JREV id parentInst childInst px py pz cx cy cz ax ay az [min max]
@@ -98,10 +133,11 @@
 //! ```
 
 use crate::{
-    AmbientOcclusion, Background, Bloom, CameraPreset, CsgOp, Document, Environment,
-    EnvironmentPreset, Instance, Joint, JointKind, Light, LightKind, MaterialDef, Node, PartDef,
-    PostProcessing, SceneEntry, SceneSettings, SketchSegment2D, ToneMapping, Transform3D, Vec2,
-    Vec3, Vignette,
+    AmbientOcclusion, Background, Bloom, BoxOrigin, CameraPreset, CsgOp, CycleError,
+    DegeneratePolicy, Document, Environment, EnvironmentPreset, Instance, Joint, JointKind, Light,
+    LightKind, LoftMode, MassProperties, MaterialDef, Node, PartDef, PostProcessing, SceneEntry,
+    SceneSettings, SketchSegment2D, SplitKeep, SweepOptions, SweepPath, TextAlignment, ToneMapping,
+    Transform3D, Vec2, Vec3, Vignette,
 };
 use std::collections::HashMap;
 use std::fmt::{self, Write as FmtWrite};
@@ -126,6 +162,15 @@ impl fmt::Display for CompactParseError {
 
 impl std::error::Error for CompactParseError {}
 
+impl From<CycleError> for CompactParseError {
+    fn from(err: CycleError) -> Self {
+        CompactParseError {
+            line: 0,
+            message: err.to_string(),
+        }
+    }
+}
+
 /// Convert a Document to compact IR format.
 ///
 /// The document must have a simple DAG structure where node IDs can be
@@ -171,22 +216,8 @@ pub fn to_compact(doc: &Document) -> Result<String, CompactParseError> {
     if !doc.nodes.is_empty() {
         writeln!(output, "# Geometry").unwrap();
 
-        // Find all root nodes (nodes not referenced by any other node)
-        let referenced: std::collections::HashSet<u64> = doc
-            .nodes
-            .values()
-            .flat_map(|n| get_children(&n.op))
-            .collect();
-
-        let roots: Vec<u64> = doc
-            .nodes
-            .keys()
-            .filter(|id| !referenced.contains(id))
-            .copied()
-            .collect();
-
         // Topological sort: dependencies before dependents
-        let sorted = topological_sort(doc, &roots)?;
+        let sorted = doc.topological_order()?;
 
         // Create ID mapping: original NodeId -> line number
         let id_map: HashMap<u64, usize> =
@@ -223,18 +254,7 @@ pub fn to_compact(doc: &Document) -> Result<String, CompactParseError> {
             writeln!(output, "# Parts").unwrap();
 
             // Get id_map for node references
-            let referenced: std::collections::HashSet<u64> = doc
-                .nodes
-                .values()
-                .flat_map(|n| get_children(&n.op))
-                .collect();
-            let roots: Vec<u64> = doc
-                .nodes
-                .keys()
-                .filter(|id| !referenced.contains(id))
-                .copied()
-                .collect();
-            let sorted = topological_sort(doc, &roots)?;
+            let sorted = doc.topological_order()?;
             let id_map: HashMap<u64, usize> =
                 sorted.iter().enumerate().map(|(i, &id)| (id, i)).collect();
 
@@ -254,10 +274,41 @@ pub fn to_compact(doc: &Document) -> Result<String, CompactParseError> {
                     mapped_root
                 )
                 .unwrap();
-                if let Some(ref mat) = pdef.default_material {
-                    write!(output, " {}", escape_id(mat)).unwrap();
+                match (&pdef.default_material, pdef.collision_root) {
+                    (Some(mat), _) => write!(output, " {}", escape_id(mat)).unwrap(),
+                    (None, Some(_)) => write!(output, " _").unwrap(),
+                    (None, None) => {}
+                }
+                if let Some(collision_root) = pdef.collision_root {
+                    let mapped = id_map
+                        .get(&collision_root)
+                        .ok_or_else(|| CompactParseError {
+                            line: 0,
+                            message: format!("unknown part def collision root {}", collision_root),
+                        })?;
+                    write!(output, " {}", mapped).unwrap();
                 }
                 writeln!(output).unwrap();
+                if let Some(props) = &pdef.mass_properties {
+                    let c = &props.center_of_mass;
+                    let i = &props.inertia;
+                    writeln!(
+                        output,
+                        "PMASS {} {} {} {} {} {} {} {} {} {} {}",
+                        escape_id(&pdef.id),
+                        props.mass,
+                        c.x,
+                        c.y,
+                        c.z,
+                        i[0][0],
+                        i[0][1],
+                        i[0][2],
+                        i[1][1],
+                        i[1][2],
+                        i[2][2]
+                    )
+                    .unwrap();
+                }
             }
             writeln!(output).unwrap();
         }
@@ -701,6 +752,7 @@ pub fn from_compact(s: &str) -> Result<Document, CompactParseError> {
     // Track the first geometry line number for node ID mapping
     let mut geometry_line_offset: Option<usize> = None;
     let mut geometry_node_count = 0;
+    let mut labels: HashMap<String, u64> = HashMap::new();
 
     while let Some(line) = lines.next() {
         let trimmed = line.trim();
@@ -728,14 +780,33 @@ pub fn from_compact(s: &str) -> Result<Document, CompactParseError> {
 
             // Scene root
             "ROOT" => {
+                let mut parts = parts.clone();
+                let root = resolve_label(parts.get(1).copied(), &labels, current_line)?;
+                if let Some(root) = &root {
+                    parts[1] = root.as_str();
+                }
                 parse_root(&mut doc, &parts, current_line)?;
             }
 
             // Part definition
             "PDEF" => {
+                let mut parts = parts.clone();
+                let root = resolve_label(parts.get(3).copied(), &labels, current_line)?;
+                if let Some(root) = &root {
+                    parts[3] = root.as_str();
+                }
+                let collision = resolve_label(parts.get(5).copied(), &labels, current_line)?;
+                if let Some(collision) = &collision {
+                    parts[5] = collision.as_str();
+                }
                 parse_part_def(&mut doc, &parts, current_line)?;
             }
 
+            // Explicit mass properties of a part definition
+            "PMASS" => {
+                parse_part_mass(&mut doc, &parts, current_line)?;
+            }
+
             // Instance
             "INST" => {
                 parse_instance(&mut doc, &parts, current_line)?;
@@ -802,8 +873,39 @@ pub fn from_compact(s: &str) -> Result<Document, CompactParseError> {
                 }
 
                 let node_id = geometry_node_count as u64;
-                let (op, name) =
-                    parse_geometry_line(trimmed, current_line, &mut lines, &mut current_line)?;
+                let line_num = current_line;
+
+                // Strip a label definition and substitute label references
+                let mut parts = parts;
+                let label = match parts.get(1) {
+                    Some(p) if p.starts_with('@') && p.ends_with(':') => {
+                        let part = parts.remove(1);
+                        Some(label_name(&part[..part.len() - 1], line_num)?)
+                    }
+                    _ => None,
+                };
+                let mut resolved = Vec::with_capacity(parts.len());
+                for part in &parts {
+                    resolved.push(
+                        resolve_label(Some(part), &labels, line_num)?
+                            .unwrap_or_else(|| part.to_string()),
+                    );
+                }
+                let (op, name) = parse_geometry_line(
+                    &resolved.join(" "),
+                    line_num,
+                    &mut lines,
+                    &mut current_line,
+                )?;
+
+                if let Some(label) = label {
+                    if labels.insert(label.to_string(), node_id).is_some() {
+                        return Err(CompactParseError {
+                            line: line_num,
+                            message: format!("duplicate label: @{}", label),
+                        });
+                    }
+                }
 
                 doc.nodes.insert(
                     node_id,
@@ -811,6 +913,7 @@ pub fn from_compact(s: &str) -> Result<Document, CompactParseError> {
                         id: node_id,
                         name,
                         op,
+                        attributes: None,
                     },
                 );
 
@@ -848,6 +951,9 @@ pub fn from_compact(s: &str) -> Result<Document, CompactParseError> {
                     roughness: 0.5,
                     density: None,
                     friction: None,
+                    opacity: None,
+                    emissive: None,
+                    base_color_texture: None,
                 },
             );
         }
@@ -867,6 +973,39 @@ pub fn from_compact(s: &str) -> Result<Document, CompactParseError> {
     Ok(doc)
 }
 
+/// The name of a `@name` label, which may not be empty or contain `:`.
+fn label_name(part: &str, line: usize) -> Result<&str, CompactParseError> {
+    match part.strip_prefix('@') {
+        Some(name) if !name.is_empty() && !name.contains(':') => Ok(name),
+        _ => Err(CompactParseError {
+            line,
+            message: format!("invalid label: {}", part),
+        }),
+    }
+}
+
+/// Resolve a `@name` reference to its node ID.
+///
+/// Returns `None` for anything that isn't a label reference, to be parsed
+/// as it is.
+fn resolve_label(
+    part: Option<&str>,
+    labels: &HashMap<String, u64>,
+    line: usize,
+) -> Result<Option<String>, CompactParseError> {
+    let Some(part) = part.filter(|p| p.starts_with('@')) else {
+        return Ok(None);
+    };
+    let name = label_name(part, line)?;
+    labels
+        .get(name)
+        .map(|id| Some(id.to_string()))
+        .ok_or_else(|| CompactParseError {
+            line,
+            message: format!("unknown label: {}", part),
+        })
+}
+
 /// Split a line by whitespace, but keep quoted strings together.
 fn split_line_respecting_quotes(line: &str) -> Vec<&str> {
     let mut parts = Vec::new();
@@ -953,6 +1092,9 @@ fn parse_material(
             roughness,
             density,
             friction,
+            opacity: None,
+            emissive: None,
+            base_color_texture: None,
         },
     );
 
@@ -1001,7 +1143,11 @@ fn parse_part_def(
     let id = parse_string_arg(parts[1]);
     let name = parse_string_arg(parts[2]);
     let root = parse_u64(parts[3], line)?;
-    let default_material = parts.get(4).map(|s| parse_string_arg(s));
+    let default_material = parts
+        .get(4)
+        .filter(|s| **s != "_")
+        .map(|s| parse_string_arg(s));
+    let collision_root = parts.get(5).map(|s| parse_u64(s, line)).transpose()?;
 
     let part_defs = doc.part_defs.get_or_insert_with(HashMap::new);
     part_defs.insert(
@@ -1010,6 +1156,8 @@ fn parse_part_def(
             id,
             name: Some(name),
             root,
+            collision_root,
+            mass_properties: None,
             default_material,
         },
     );
@@ -1017,6 +1165,41 @@ fn parse_part_def(
     Ok(())
 }
 
+/// Parse the explicit mass properties of an earlier part definition.
+fn parse_part_mass(
+    doc: &mut Document,
+    parts: &[&str],
+    line: usize,
+) -> Result<(), CompactParseError> {
+    if parts.len() != 12 {
+        return Err(CompactParseError {
+            line,
+            message: format!("PMASS requires 11 args, got {}", parts.len() - 1),
+        });
+    }
+
+    let id = parse_string_arg(parts[1]);
+    let v = |i: usize| parse_f64(parts[i], line);
+    let (ixx, ixy, ixz, iyy, iyz, izz) = (v(6)?, v(7)?, v(8)?, v(9)?, v(10)?, v(11)?);
+    let props = MassProperties {
+        mass: v(2)?,
+        center_of_mass: Vec3::new(v(3)?, v(4)?, v(5)?),
+        inertia: [[ixx, ixy, ixz], [ixy, iyy, iyz], [ixz, iyz, izz]],
+    };
+
+    let pdef = doc
+        .part_defs
+        .as_mut()
+        .and_then(|defs| defs.get_mut(&id))
+        .ok_or_else(|| CompactParseError {
+            line,
+            message: format!("PMASS for unknown part def {id}"),
+        })?;
+    pdef.mass_properties = Some(props);
+
+    Ok(())
+}
+
 /// Parse an instance.
 fn parse_instance(
     doc: &mut Document,
@@ -1698,8 +1881,15 @@ where
 
     let opcode = parts[0];
 
-    // Check for trailing quoted name
-    let (args, name) = extract_trailing_name(&parts);
+    // Check for trailing quoted name. A tag's value and an import's path are
+    // themselves quoted, so those only have a name when they have one more
+    // part than their arguments.
+    let (args, name) =
+        if (opcode == "TG" && parts.len() == 4) || (opcode == "STP" && parts.len() == 2) {
+            (parts.clone(), None)
+        } else {
+            extract_trailing_name(&parts)
+        };
 
     // Now parse based on opcode
     let op = parse_geometry_opcode(opcode, &args, line_num, lines, current_line)?;
@@ -1732,18 +1922,29 @@ where
 {
     match opcode {
         "C" => {
-            if parts.len() != 4 {
+            if parts.len() != 4 && parts.len() != 5 {
                 return Err(CompactParseError {
                     line: line_num,
                     message: format!("C requires 3 args, got {}", parts.len() - 1),
                 });
             }
+            let origin = match parts.get(4) {
+                None => BoxOrigin::Corner,
+                Some(&"center") => BoxOrigin::Center,
+                Some(other) => {
+                    return Err(CompactParseError {
+                        line: line_num,
+                        message: format!("unknown cube origin: {}", other),
+                    })
+                }
+            };
             Ok(CsgOp::Cube {
                 size: Vec3::new(
                     parse_f64(parts[1], line_num)?,
                     parse_f64(parts[2], line_num)?,
                     parse_f64(parts[3], line_num)?,
                 ),
+                origin,
             })
         }
 
@@ -1961,6 +2162,158 @@ where
             })
         }
 
+        "TG" => {
+            if parts.len() != 4 {
+                return Err(CompactParseError {
+                    line: line_num,
+                    message: format!("TG requires 3 args, got {}", parts.len() - 1),
+                });
+            }
+            Ok(CsgOp::Tag {
+                child: parse_u64(parts[1], line_num)?,
+                key: parse_string_arg(parts[2]),
+                value: parse_string_arg(parts[3]),
+            })
+        }
+
+        "SP" => {
+            if parts.len() != 9 {
+                return Err(CompactParseError {
+                    line: line_num,
+                    message: format!("SP requires 8 args, got {}", parts.len() - 1),
+                });
+            }
+            let keep = match parts[8] {
+                "positive" => SplitKeep::Positive,
+                "negative" => SplitKeep::Negative,
+                "both" => SplitKeep::Both,
+                other => {
+                    return Err(CompactParseError {
+                        line: line_num,
+                        message: format!("unknown split side: {}", other),
+                    });
+                }
+            };
+            Ok(CsgOp::SplitByPlane {
+                child: parse_u64(parts[1], line_num)?,
+                plane_origin: Vec3::new(
+                    parse_f64(parts[2], line_num)?,
+                    parse_f64(parts[3], line_num)?,
+                    parse_f64(parts[4], line_num)?,
+                ),
+                plane_normal: Vec3::new(
+                    parse_f64(parts[5], line_num)?,
+                    parse_f64(parts[6], line_num)?,
+                    parse_f64(parts[7], line_num)?,
+                ),
+                keep,
+            })
+        }
+
+        "SVG" => {
+            if parts.len() != 12 {
+                return Err(CompactParseError {
+                    line: line_num,
+                    message: format!("SVG requires 11 args, got {}", parts.len() - 1),
+                });
+            }
+            Ok(CsgOp::SvgProfile {
+                path_data: parse_string_arg(parts[1]),
+                scale: parse_f64(parts[2], line_num)?,
+                plane_origin: Vec3::new(
+                    parse_f64(parts[3], line_num)?,
+                    parse_f64(parts[4], line_num)?,
+                    parse_f64(parts[5], line_num)?,
+                ),
+                x_dir: Vec3::new(
+                    parse_f64(parts[6], line_num)?,
+                    parse_f64(parts[7], line_num)?,
+                    parse_f64(parts[8], line_num)?,
+                ),
+                y_dir: Vec3::new(
+                    parse_f64(parts[9], line_num)?,
+                    parse_f64(parts[10], line_num)?,
+                    parse_f64(parts[11], line_num)?,
+                ),
+            })
+        }
+
+        "TX" => {
+            if parts.len() != 14 && parts.len() != 15 {
+                return Err(CompactParseError {
+                    line: line_num,
+                    message: format!("TX requires 13 args, got {}", parts.len() - 1),
+                });
+            }
+            let alignment = match parts.get(14) {
+                None | Some(&"left") => TextAlignment::Left,
+                Some(&"center") => TextAlignment::Center,
+                Some(&"right") => TextAlignment::Right,
+                Some(other) => {
+                    return Err(CompactParseError {
+                        line: line_num,
+                        message: format!("unknown text alignment: {}", other),
+                    })
+                }
+            };
+            Ok(CsgOp::Text {
+                content: parse_string_arg(parts[1]),
+                font: parse_string_arg(parts[2]),
+                height: parse_f64(parts[3], line_num)?,
+                depth: parse_f64(parts[4], line_num)?,
+                plane_origin: Vec3::new(
+                    parse_f64(parts[5], line_num)?,
+                    parse_f64(parts[6], line_num)?,
+                    parse_f64(parts[7], line_num)?,
+                ),
+                x_dir: Vec3::new(
+                    parse_f64(parts[8], line_num)?,
+                    parse_f64(parts[9], line_num)?,
+                    parse_f64(parts[10], line_num)?,
+                ),
+                y_dir: Vec3::new(
+                    parse_f64(parts[11], line_num)?,
+                    parse_f64(parts[12], line_num)?,
+                    parse_f64(parts[13], line_num)?,
+                ),
+                alignment,
+            })
+        }
+
+        "STP" => {
+            if parts.len() != 2 {
+                return Err(CompactParseError {
+                    line: line_num,
+                    message: format!("STP requires 1 arg, got {}", parts.len() - 1),
+                });
+            }
+            Ok(CsgOp::StepImport {
+                path: parse_string_arg(parts[1]),
+            })
+        }
+
+        "PJ" => {
+            if parts.len() != 8 {
+                return Err(CompactParseError {
+                    line: line_num,
+                    message: format!("PJ requires 7 args, got {}", parts.len() - 1),
+                });
+            }
+            Ok(CsgOp::ProjectToSketch {
+                solid: parse_u64(parts[1], line_num)?,
+                plane_origin: Vec3::new(
+                    parse_f64(parts[2], line_num)?,
+                    parse_f64(parts[3], line_num)?,
+                    parse_f64(parts[4], line_num)?,
+                ),
+                plane_normal: Vec3::new(
+                    parse_f64(parts[5], line_num)?,
+                    parse_f64(parts[6], line_num)?,
+                    parse_f64(parts[7], line_num)?,
+                ),
+            })
+        }
+
         "SK" => {
             if parts.len() != 10 {
                 return Err(CompactParseError {
@@ -1986,6 +2339,9 @@ where
             );
 
             let mut segments = Vec::new();
+            let mut holes = Vec::new();
+            // Segments of the HOLE sub-block being parsed, if any
+            let mut hole: Option<Vec<SketchSegment2D>> = None;
 
             // Parse sketch segments until END
             loop {
@@ -1997,7 +2353,23 @@ where
 
                 let seg_trimmed = seg_line.trim();
                 if seg_trimmed == "END" {
-                    break;
+                    match hole.take() {
+                        Some(segments) => {
+                            holes.push(segments);
+                            continue;
+                        }
+                        None => break,
+                    }
+                }
+                if seg_trimmed == "HOLE" {
+                    if hole.is_some() {
+                        return Err(CompactParseError {
+                            line: *current_line,
+                            message: "HOLE blocks cannot be nested".to_string(),
+                        });
+                    }
+                    hole = Some(Vec::new());
+                    continue;
                 }
 
                 let seg_parts: Vec<&str> = seg_trimmed.split_whitespace().collect();
@@ -2005,6 +2377,7 @@ where
                     continue; // Skip empty lines in sketch
                 }
 
+                let segments = hole.as_mut().unwrap_or(&mut segments);
                 match seg_parts[0] {
                     "L" => {
                         if seg_parts.len() != 5 {
@@ -2061,6 +2434,7 @@ where
                 x_dir,
                 y_dir,
                 segments,
+                holes,
             })
         }
 
@@ -2106,6 +2480,125 @@ where
             })
         }
 
+        "LF" => {
+            if parts.len() < 4 {
+                return Err(CompactParseError {
+                    line: line_num,
+                    message: format!(
+                        "LF requires a mode and at least 2 profiles, got {} args",
+                        parts.len() - 1
+                    ),
+                });
+            }
+            let mode = match parts[1] {
+                "ruled" => LoftMode::Ruled,
+                "smooth" => LoftMode::Smooth,
+                other => {
+                    return Err(CompactParseError {
+                        line: line_num,
+                        message: format!("unknown loft mode: {}", other),
+                    });
+                }
+            };
+            let closed = parts.last() == Some(&"closed");
+            let profiles = &parts[2..parts.len() - usize::from(closed)];
+            if profiles.len() < 2 {
+                return Err(CompactParseError {
+                    line: line_num,
+                    message: format!("LF requires at least 2 profiles, got {}", profiles.len()),
+                });
+            }
+            Ok(CsgOp::Loft {
+                profiles: profiles
+                    .iter()
+                    .map(|p| parse_u64(p, line_num))
+                    .collect::<Result<_, _>>()?,
+                mode,
+                closed,
+            })
+        }
+
+        "SW" => {
+            if parts.len() < 3 {
+                return Err(CompactParseError {
+                    line: line_num,
+                    message: format!(
+                        "SW requires a profile and a path, got {} args",
+                        parts.len() - 1
+                    ),
+                });
+            }
+            // The path's values, then any `key=value` options
+            let split = parts[3..]
+                .iter()
+                .position(|p| p.contains('='))
+                .map_or(parts.len(), |i| i + 3);
+            let values = parts[3..split]
+                .iter()
+                .map(|p| parse_f64(p, line_num))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut options = SweepOptions::default();
+            for part in &parts[split..] {
+                let invalid = || CompactParseError {
+                    line: line_num,
+                    message: format!("invalid SW option: {}", part),
+                };
+                let (key, value) = part.split_once('=').ok_or_else(invalid)?;
+                match key {
+                    "twist" => options.twist_angle = Some(parse_f64(value, line_num)?),
+                    "scale_start" => options.scale_start = Some(parse_f64(value, line_num)?),
+                    "scale_end" => options.scale_end = Some(parse_f64(value, line_num)?),
+                    "segments" => {
+                        options.path_segments = Some(value.parse().map_err(|_| invalid())?)
+                    }
+                    "arc_segments" => {
+                        options.arc_segments = Some(value.parse().map_err(|_| invalid())?)
+                    }
+                    "orientation" => options.orientation = Some(parse_f64(value, line_num)?),
+                    "degenerate" => {
+                        options.on_degenerate = Some(match value {
+                            "error" => DegeneratePolicy::Error,
+                            "skip" => DegeneratePolicy::Skip,
+                            "interpolate" => DegeneratePolicy::Interpolate,
+                            _ => return Err(invalid()),
+                        })
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            let path = match (parts[2], values.as_slice()) {
+                ("L", &[sx, sy, sz, ex, ey, ez]) => SweepPath::Line {
+                    start: Vec3::new(sx, sy, sz),
+                    end: Vec3::new(ex, ey, ez),
+                },
+                ("H", &[radius, pitch, height, turns]) => SweepPath::Helix {
+                    radius,
+                    pitch,
+                    height,
+                    turns,
+                },
+                ("P", coords) if coords.len() >= 6 && coords.len() % 3 == 0 => {
+                    SweepPath::Polyline {
+                        points: coords
+                            .chunks_exact(3)
+                            .map(|c| Vec3::new(c[0], c[1], c[2]))
+                            .collect(),
+                    }
+                }
+                (kind, _) => {
+                    return Err(CompactParseError {
+                        line: line_num,
+                        message: format!("invalid SW path {} with {} values", kind, values.len()),
+                    });
+                }
+            };
+            Ok(CsgOp::Sweep {
+                profile: parse_u64(parts[1], line_num)?,
+                path,
+                options,
+            })
+        }
+
         _ => Err(CompactParseError {
             line: line_num,
             message: format!("unknown opcode: {}", opcode),
@@ -2114,7 +2607,7 @@ where
 }
 
 /// Get child node IDs from an operation.
-fn get_children(op: &CsgOp) -> Vec<u64> {
+pub(crate) fn get_children(op: &CsgOp) -> Vec<u64> {
     match op {
         CsgOp::Union { left, right }
         | CsgOp::Difference { left, right }
@@ -2126,65 +2619,30 @@ fn get_children(op: &CsgOp) -> Vec<u64> {
         | CsgOp::CircularPattern { child, .. }
         | CsgOp::Shell { child, .. }
         | CsgOp::Fillet { child, .. }
-        | CsgOp::Chamfer { child, .. } => vec![*child],
+        | CsgOp::Chamfer { child, .. }
+        | CsgOp::Tag { child, .. }
+        | CsgOp::SplitByPlane { child, .. } => vec![*child],
         CsgOp::Extrude { sketch, .. } | CsgOp::Revolve { sketch, .. } => vec![*sketch],
-        _ => vec![],
-    }
-}
-
-/// Topological sort of nodes.
-fn topological_sort(doc: &Document, roots: &[u64]) -> Result<Vec<u64>, CompactParseError> {
-    let mut result = Vec::new();
-    let mut visited = std::collections::HashSet::new();
-    let mut temp_visited = std::collections::HashSet::new();
-
-    fn visit(
-        node_id: u64,
-        doc: &Document,
-        visited: &mut std::collections::HashSet<u64>,
-        temp_visited: &mut std::collections::HashSet<u64>,
-        result: &mut Vec<u64>,
-    ) -> Result<(), CompactParseError> {
-        if visited.contains(&node_id) {
-            return Ok(());
-        }
-        if temp_visited.contains(&node_id) {
-            return Err(CompactParseError {
-                line: 0,
-                message: format!("cycle detected at node {}", node_id),
-            });
-        }
-
-        temp_visited.insert(node_id);
-
-        if let Some(node) = doc.nodes.get(&node_id) {
-            for child_id in get_children(&node.op) {
-                visit(child_id, doc, visited, temp_visited, result)?;
-            }
-        }
-
-        temp_visited.remove(&node_id);
-        visited.insert(node_id);
-        result.push(node_id);
-        Ok(())
+        CsgOp::Loft { profiles, .. } => profiles.clone(),
+        CsgOp::Sweep { profile, .. } => vec![*profile],
+        CsgOp::ProjectToSketch { solid, .. } => vec![*solid],
+        CsgOp::Cube { .. }
+        | CsgOp::Cylinder { .. }
+        | CsgOp::Sphere { .. }
+        | CsgOp::Cone { .. }
+        | CsgOp::Empty
+        | CsgOp::Sketch2D { .. }
+        | CsgOp::Text2D { .. }
+        | CsgOp::Text { .. }
+        | CsgOp::SvgProfile { .. }
+        | CsgOp::StepImport { .. } => vec![],
     }
-
-    for &root_id in roots {
-        visit(root_id, doc, &mut visited, &mut temp_visited, &mut result)?;
-    }
-
-    // Also visit any orphan nodes
-    let all_ids: Vec<u64> = doc.nodes.keys().copied().collect();
-    for id in all_ids {
-        if !visited.contains(&id) {
-            visit(id, doc, &mut visited, &mut temp_visited, &mut result)?;
-        }
-    }
-
-    Ok(result)
 }
 
 /// Format a CsgOp as a compact IR line with optional name suffix.
+///
+/// The match lists every variant, so a new op cannot be added without
+/// deciding how it is written here.
 fn format_op(
     op: &CsgOp,
     id_map: &HashMap<u64, usize>,
@@ -2195,9 +2653,13 @@ fn format_op(
         .unwrap_or_default();
 
     match op {
-        CsgOp::Cube { size } => Ok(format!(
-            "C {} {} {}{}",
-            size.x, size.y, size.z, name_suffix
+        CsgOp::Cube { size, origin } => Ok(format!(
+            "C {} {} {}{}{}",
+            size.x,
+            size.y,
+            size.z,
+            if origin.is_corner() { "" } else { " center" },
+            name_suffix
         )),
 
         CsgOp::Cylinder {
@@ -2358,6 +2820,7 @@ fn format_op(
             x_dir,
             y_dir,
             segments,
+            holes,
         } => {
             let mut lines = vec![format!(
                 "SK {} {} {}  {} {} {}  {} {} {}{}",
@@ -2373,29 +2836,11 @@ fn format_op(
                 name_suffix
             )];
 
-            for seg in segments {
-                match seg {
-                    SketchSegment2D::Line { start, end } => {
-                        lines.push(format!("L {} {} {} {}", start.x, start.y, end.x, end.y));
-                    }
-                    SketchSegment2D::Arc {
-                        start,
-                        end,
-                        center,
-                        ccw,
-                    } => {
-                        lines.push(format!(
-                            "A {} {} {} {} {} {} {}",
-                            start.x,
-                            start.y,
-                            end.x,
-                            end.y,
-                            center.x,
-                            center.y,
-                            if *ccw { 1 } else { 0 }
-                        ));
-                    }
-                }
+            lines.extend(segments.iter().map(format_sketch_segment));
+            for hole in holes {
+                lines.push("HOLE".to_string());
+                lines.extend(hole.iter().map(format_sketch_segment));
+                lines.push("END".to_string());
             }
 
             lines.push("END".to_string());
@@ -2438,17 +2883,257 @@ fn format_op(
             ))
         }
 
-        CsgOp::StepImport { .. } => Err(CompactParseError {
-            line: 0,
-            message: "STEP import not supported in compact format".to_string(),
-        }),
-
-        CsgOp::Text2D { .. } => Err(CompactParseError {
-            line: 0,
-            message: "Text2D not supported in compact format".to_string(),
-        }),
-    }
-}
+        CsgOp::Loft {
+            profiles,
+            mode,
+            closed,
+        } => {
+            let mut line = String::from("LF ");
+            line.push_str(match mode {
+                LoftMode::Ruled => "ruled",
+                LoftMode::Smooth => "smooth",
+            });
+            for profile in profiles {
+                let p = id_map.get(profile).ok_or_else(|| CompactParseError {
+                    line: 0,
+                    message: format!("unknown node {}", profile),
+                })?;
+                write!(line, " {}", p).unwrap();
+            }
+            if *closed {
+                line.push_str(" closed");
+            }
+            Ok(format!("{}{}", line, name_suffix))
+        }
+
+        CsgOp::Sweep {
+            profile,
+            path,
+            options,
+        } => {
+            let p = id_map.get(profile).ok_or_else(|| CompactParseError {
+                line: 0,
+                message: format!("unknown node {}", profile),
+            })?;
+            let mut path = match path {
+                SweepPath::Line { start, end } => format!(
+                    "L {} {} {} {} {} {}",
+                    start.x, start.y, start.z, end.x, end.y, end.z
+                ),
+                SweepPath::Helix {
+                    radius,
+                    pitch,
+                    height,
+                    turns,
+                } => format!("H {} {} {} {}", radius, pitch, height, turns),
+                SweepPath::Polyline { points } => {
+                    let mut path = String::from("P");
+                    for pt in points {
+                        write!(path, " {} {} {}", pt.x, pt.y, pt.z).unwrap();
+                    }
+                    path
+                }
+            };
+            let SweepOptions {
+                twist_angle,
+                scale_start,
+                scale_end,
+                path_segments,
+                arc_segments,
+                orientation,
+                on_degenerate,
+            } = options;
+            if let Some(twist) = twist_angle {
+                write!(path, " twist={}", twist).unwrap();
+            }
+            if let Some(scale) = scale_start {
+                write!(path, " scale_start={}", scale).unwrap();
+            }
+            if let Some(scale) = scale_end {
+                write!(path, " scale_end={}", scale).unwrap();
+            }
+            if let Some(segments) = path_segments {
+                write!(path, " segments={}", segments).unwrap();
+            }
+            if let Some(segments) = arc_segments {
+                write!(path, " arc_segments={}", segments).unwrap();
+            }
+            if let Some(angle) = orientation {
+                write!(path, " orientation={}", angle).unwrap();
+            }
+            if let Some(policy) = on_degenerate {
+                let policy = match policy {
+                    DegeneratePolicy::Error => "error",
+                    DegeneratePolicy::Skip => "skip",
+                    DegeneratePolicy::Interpolate => "interpolate",
+                };
+                write!(path, " degenerate={}", policy).unwrap();
+            }
+            Ok(format!("SW {} {}{}", p, path, name_suffix))
+        }
+
+        CsgOp::ProjectToSketch {
+            solid,
+            plane_origin,
+            plane_normal,
+        } => {
+            let s = id_map.get(solid).ok_or_else(|| CompactParseError {
+                line: 0,
+                message: format!("unknown node {}", solid),
+            })?;
+            Ok(format!(
+                "PJ {} {} {} {} {} {} {}{}",
+                s,
+                plane_origin.x,
+                plane_origin.y,
+                plane_origin.z,
+                plane_normal.x,
+                plane_normal.y,
+                plane_normal.z,
+                name_suffix
+            ))
+        }
+
+        CsgOp::SvgProfile {
+            path_data,
+            scale,
+            plane_origin,
+            x_dir,
+            y_dir,
+        } => Ok(format!(
+            "SVG {} {} {} {} {} {} {} {} {} {} {}{}",
+            format_quoted_string(path_data),
+            scale,
+            plane_origin.x,
+            plane_origin.y,
+            plane_origin.z,
+            x_dir.x,
+            x_dir.y,
+            x_dir.z,
+            y_dir.x,
+            y_dir.y,
+            y_dir.z,
+            name_suffix
+        )),
+
+        CsgOp::Tag { child, key, value } => {
+            let c = id_map.get(child).ok_or_else(|| CompactParseError {
+                line: 0,
+                message: format!("unknown node {}", child),
+            })?;
+            Ok(format!(
+                "TG {} {} {}{}",
+                c,
+                format_quoted_string(key),
+                format_quoted_string(value),
+                name_suffix
+            ))
+        }
+
+        CsgOp::SplitByPlane {
+            child,
+            plane_origin,
+            plane_normal,
+            keep,
+        } => {
+            let c = id_map.get(child).ok_or_else(|| CompactParseError {
+                line: 0,
+                message: format!("unknown node {}", child),
+            })?;
+            Ok(format!(
+                "SP {} {} {} {} {} {} {} {}{}",
+                c,
+                plane_origin.x,
+                plane_origin.y,
+                plane_origin.z,
+                plane_normal.x,
+                plane_normal.y,
+                plane_normal.z,
+                match keep {
+                    SplitKeep::Positive => "positive",
+                    SplitKeep::Negative => "negative",
+                    SplitKeep::Both => "both",
+                },
+                name_suffix
+            ))
+        }
+
+        CsgOp::StepImport { path } if path.contains(['\n', '\r']) => Err(CompactParseError {
+            line: 0,
+            message: "multi-line STEP import path not supported in compact format".to_string(),
+        }),
+
+        CsgOp::StepImport { path } => {
+            Ok(format!("STP {}{}", format_quoted_string(path), name_suffix))
+        }
+
+        CsgOp::Text2D { .. } => Err(CompactParseError {
+            line: 0,
+            message: "Text2D not supported in compact format".to_string(),
+        }),
+
+        CsgOp::Text { content, .. } if content.contains(['\n', '\r']) => Err(CompactParseError {
+            line: 0,
+            message: "multi-line text not supported in compact format".to_string(),
+        }),
+
+        CsgOp::Text {
+            content,
+            font,
+            height,
+            depth,
+            plane_origin,
+            x_dir,
+            y_dir,
+            alignment,
+        } => Ok(format!(
+            "TX {} {} {} {} {} {} {} {} {} {} {} {} {}{}{}",
+            format_quoted_string(content),
+            format_quoted_string(font),
+            height,
+            depth,
+            plane_origin.x,
+            plane_origin.y,
+            plane_origin.z,
+            x_dir.x,
+            x_dir.y,
+            x_dir.z,
+            y_dir.x,
+            y_dir.y,
+            y_dir.z,
+            match alignment {
+                TextAlignment::Left => "",
+                TextAlignment::Center => " center",
+                TextAlignment::Right => " right",
+            },
+            name_suffix
+        )),
+    }
+}
+
+/// Format one line of a sketch block.
+fn format_sketch_segment(seg: &SketchSegment2D) -> String {
+    match seg {
+        SketchSegment2D::Line { start, end } => {
+            format!("L {} {} {} {}", start.x, start.y, end.x, end.y)
+        }
+        SketchSegment2D::Arc {
+            start,
+            end,
+            center,
+            ccw,
+        } => format!(
+            "A {} {} {} {} {} {} {}",
+            start.x,
+            start.y,
+            end.x,
+            end.y,
+            center.x,
+            center.y,
+            if *ccw { 1 } else { 0 }
+        ),
+    }
+}
 
 
 fn parse_f64(s: &str, line: usize) -> Result<f64, CompactParseError> {
@@ -2484,7 +3169,7 @@ mod tests {
         assert_eq!(doc.nodes.len(), 1);
         let node = &doc.nodes[&0];
         match &node.op {
-            CsgOp::Cube { size } => {
+            CsgOp::Cube { size, .. } => {
                 assert_eq!(size.x, 50.0);
                 assert_eq!(size.y, 30.0);
                 assert_eq!(size.z, 5.0);
@@ -2502,7 +3187,7 @@ mod tests {
 
         // Node 0: Cube
         match &doc.nodes[&0].op {
-            CsgOp::Cube { size } => {
+            CsgOp::Cube { size, .. } => {
                 assert_eq!(size.x, 50.0);
                 assert_eq!(size.y, 30.0);
                 assert_eq!(size.z, 5.0);
@@ -2555,6 +3240,9 @@ mod tests {
                 roughness: 0.5,
                 density: None,
                 friction: None,
+                opacity: None,
+                emissive: None,
+                base_color_texture: None,
             },
         );
         doc.nodes.insert(
@@ -2564,7 +3252,9 @@ mod tests {
                 name: None,
                 op: CsgOp::Cube {
                     size: Vec3::new(10.0, 20.0, 30.0),
+                    origin: BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
         doc.roots.push(SceneEntry {
@@ -2580,7 +3270,7 @@ mod tests {
 
         let restored = from_compact(&compact).unwrap();
         match &restored.nodes[&0].op {
-            CsgOp::Cube { size } => {
+            CsgOp::Cube { size, .. } => {
                 assert_eq!(size.x, 10.0);
                 assert_eq!(size.y, 20.0);
                 assert_eq!(size.z, 30.0);
@@ -2601,7 +3291,9 @@ mod tests {
                 name: None,
                 op: CsgOp::Cube {
                     size: Vec3::new(50.0, 30.0, 5.0),
+                    origin: BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
 
@@ -2616,6 +3308,7 @@ mod tests {
                     height: 10.0,
                     segments: 0,
                 },
+                attributes: None,
             },
         );
 
@@ -2629,6 +3322,7 @@ mod tests {
                     child: 1,
                     offset: Vec3::new(25.0, 15.0, 0.0),
                 },
+                attributes: None,
             },
         );
 
@@ -2639,6 +3333,7 @@ mod tests {
                 id: 3,
                 name: None,
                 op: CsgOp::Difference { left: 0, right: 2 },
+                attributes: None,
             },
         );
 
@@ -2656,6 +3351,95 @@ mod tests {
         assert!(compact.contains("D 0 2"));
     }
 
+    #[test]
+    fn test_cube_origin() {
+        let doc = from_compact("C 10 20 30\nC 10 20 30 center \"box\"").unwrap();
+        assert!(matches!(
+            doc.nodes[&0].op,
+            CsgOp::Cube {
+                origin: BoxOrigin::Corner,
+                ..
+            }
+        ));
+        assert!(matches!(
+            doc.nodes[&1].op,
+            CsgOp::Cube {
+                origin: BoxOrigin::Center,
+                ..
+            }
+        ));
+        assert_eq!(doc.nodes[&1].name.as_deref(), Some("box"));
+
+        let compact = to_compact(&doc).unwrap();
+        assert!(compact.contains("C 10 20 30 center \"box\""));
+        assert_eq!(from_compact(&compact).unwrap().nodes, doc.nodes);
+
+        assert!(from_compact("C 10 20 30 middle").is_err());
+    }
+
+    #[test]
+    fn test_text() {
+        let compact = r#"TX "AB \"x\"" "sans-serif" 10 1.5 0 0 5 1 0 0 0 1 0 center "Label""#;
+        let doc = from_compact(compact).unwrap();
+        let node = &doc.nodes[&0];
+        assert_eq!(node.name.as_deref(), Some("Label"));
+        match &node.op {
+            CsgOp::Text {
+                content,
+                font,
+                depth,
+                plane_origin,
+                alignment,
+                ..
+            } => {
+                assert_eq!(content, "AB \"x\"");
+                assert_eq!(font, "sans-serif");
+                assert_eq!(*depth, 1.5);
+                assert_eq!(plane_origin.z, 5.0);
+                assert_eq!(*alignment, TextAlignment::Center);
+            }
+            _ => panic!("expected Text"),
+        }
+
+        let roundtrip = from_compact(&to_compact(&doc).unwrap()).unwrap();
+        assert_eq!(roundtrip.nodes, doc.nodes);
+
+        let left = from_compact(r#"TX "A" "sans-serif" 10 1 0 0 0 1 0 0 0 1 0"#).unwrap();
+        assert!(matches!(
+            left.nodes[&0].op,
+            CsgOp::Text {
+                alignment: TextAlignment::Left,
+                ..
+            }
+        ));
+        assert!(from_compact(r#"TX "A" "sans-serif" 10 1 0 0 0 1 0 0 0 1 0 top"#).is_err());
+    }
+
+    #[test]
+    fn test_labels() {
+        let numeric = "C 10 10 10\nY 2 20\nT 1 5 5 -5\nD 0 2\nU 0 3 \"both\"\nROOT 4 default";
+        let labeled = r#"C @block: 10 10 10
+Y @pin: 2 20
+# Comments and unlabeled lines mix freely with labels
+T @pin 5 5 -5
+D @cut: @block 2
+U @block @cut "both"
+ROOT 4 default"#;
+        let a = from_compact(numeric).unwrap();
+        let b = from_compact(labeled).unwrap();
+        assert_eq!(a.nodes, b.nodes);
+        assert_eq!(a.roots, b.roots);
+
+        let doc = from_compact("C @a: 1 1 1\nPDEF p \"P\" @a\nROOT @a default").unwrap();
+        assert_eq!(doc.roots[0].root, 0);
+        assert_eq!(doc.part_defs.unwrap()["p"].root, 0);
+
+        assert!(from_compact("U @a @b").is_err());
+        assert!(from_compact("C @a: 1 1 1\nC @a: 2 2 2").is_err());
+        assert!(from_compact("C @: 1 1 1").is_err());
+        assert!(from_compact("C @a: 1 1 1\nT @b: @b 0 0 0").is_err());
+    }
+
     #[test]
     fn test_all_primitives() {
         let compact = "C 10 20 30\nY 5 15\nS 8\nK 5 2 20";
@@ -2664,7 +3448,7 @@ mod tests {
         assert_eq!(doc.nodes.len(), 4);
 
         match &doc.nodes[&0].op {
-            CsgOp::Cube { size } => assert_eq!(*size, Vec3::new(10.0, 20.0, 30.0)),
+            CsgOp::Cube { size, .. } => assert_eq!(*size, Vec3::new(10.0, 20.0, 30.0)),
             _ => panic!("expected Cube"),
         }
 
@@ -2814,6 +3598,130 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_project_to_sketch() {
+        let compact = "Y 5 10\nPJ 0 0 0 0 0 0 1\nE 1 0 0 3";
+        let doc = from_compact(compact).unwrap();
+
+        match &doc.nodes[&1].op {
+            CsgOp::ProjectToSketch {
+                solid,
+                plane_origin,
+                plane_normal,
+            } => {
+                assert_eq!(*solid, 0);
+                assert_eq!(*plane_origin, Vec3::new(0.0, 0.0, 0.0));
+                assert_eq!(*plane_normal, Vec3::new(0.0, 0.0, 1.0));
+            }
+            _ => panic!("expected ProjectToSketch"),
+        }
+
+        let mut doc = doc;
+        doc.roots.push(SceneEntry {
+            root: 2,
+            material: "default".to_string(),
+            visible: None,
+        });
+        assert!(to_compact(&doc).unwrap().contains("PJ 0 0 0 0 0 0 1"));
+    }
+
+    #[test]
+    fn test_tag() {
+        let compact = "C 10 10 10\nTG 0 \"color\" \"#ff0000\"\nTG 1 debug \"a \\\"b\\\"\" \"marked\"";
+        let doc = from_compact(compact).unwrap();
+
+        match &doc.nodes[&1].op {
+            CsgOp::Tag { child, key, value } => {
+                assert_eq!(*child, 0);
+                assert_eq!(key, "color");
+                assert_eq!(value, "#ff0000");
+            }
+            _ => panic!("expected Tag"),
+        }
+        assert_eq!(doc.nodes[&1].name, None);
+        match &doc.nodes[&2].op {
+            CsgOp::Tag { key, value, .. } => {
+                assert_eq!(key, "debug");
+                assert_eq!(value, "a \"b\"");
+            }
+            _ => panic!("expected Tag"),
+        }
+        assert_eq!(doc.nodes[&2].name.as_deref(), Some("marked"));
+
+        let mut doc = doc;
+        doc.roots.push(SceneEntry {
+            root: 2,
+            material: "default".to_string(),
+            visible: None,
+        });
+        let restored = from_compact(&to_compact(&doc).unwrap()).unwrap();
+        assert_eq!(restored.nodes[&1].op, doc.nodes[&1].op);
+        assert_eq!(restored.nodes[&2].op, doc.nodes[&2].op);
+        assert_eq!(restored.nodes[&2].name.as_deref(), Some("marked"));
+    }
+
+    #[test]
+    fn test_split_by_plane() {
+        let compact = "C 10 10 10\nSP 0 5 5 5 1 0 0 negative \"left\"\nSP 0 0 0 5 0 0 1 both";
+        let doc = from_compact(compact).unwrap();
+
+        match &doc.nodes[&1].op {
+            CsgOp::SplitByPlane {
+                child,
+                plane_origin,
+                plane_normal,
+                keep,
+            } => {
+                assert_eq!(*child, 0);
+                assert_eq!(*plane_origin, Vec3::new(5.0, 5.0, 5.0));
+                assert_eq!(*plane_normal, Vec3::new(1.0, 0.0, 0.0));
+                assert_eq!(*keep, SplitKeep::Negative);
+            }
+            _ => panic!("expected SplitByPlane"),
+        }
+        assert_eq!(doc.nodes[&1].name.as_deref(), Some("left"));
+        assert!(from_compact("C 1 1 1\nSP 0 0 0 0 0 0 1 top").is_err());
+
+        let mut doc = doc;
+        for root in [1, 2] {
+            doc.roots.push(SceneEntry {
+                root,
+                material: "default".to_string(),
+                visible: None,
+            });
+        }
+        let restored = from_compact(&to_compact(&doc).unwrap()).unwrap();
+        assert_eq!(restored.nodes[&1].op, doc.nodes[&1].op);
+        assert_eq!(restored.nodes[&2].op, doc.nodes[&2].op);
+        assert_eq!(restored.nodes[&1].name.as_deref(), Some("left"));
+    }
+
+    #[test]
+    fn test_svg_profile() {
+        let compact = "SVG \"M0 0 L10 0 L10 10 L0 10 Z\" 0.5 0 0 0 1 0 0 0 1 0 \"logo\"\nE 0 0 0 2";
+        let doc = from_compact(compact).unwrap();
+
+        match &doc.nodes[&0].op {
+            CsgOp::SvgProfile {
+                path_data, scale, ..
+            } => {
+                assert_eq!(path_data, "M0 0 L10 0 L10 10 L0 10 Z");
+                assert_eq!(*scale, 0.5);
+            }
+            _ => panic!("expected SvgProfile"),
+        }
+        assert_eq!(doc.nodes[&0].name.as_deref(), Some("logo"));
+
+        let mut doc = doc;
+        doc.roots.push(SceneEntry {
+            root: 1,
+            material: "default".to_string(),
+            visible: None,
+        });
+        let restored = from_compact(&to_compact(&doc).unwrap()).unwrap();
+        assert_eq!(restored.nodes[&0].op, doc.nodes[&0].op);
+    }
+
     #[test]
     fn test_sketch_extrude() {
         let compact = "SK 0 0 0  1 0 0  0 1 0\nL 0 0 10 0\nL 10 0 10 5\nL 10 5 0 5\nL 0 5 0 0\nEND\nE 0 0 0 20";
@@ -2826,11 +3734,13 @@ mod tests {
                 x_dir,
                 y_dir,
                 segments,
+                holes,
             } => {
                 assert_eq!(*origin, Vec3::new(0.0, 0.0, 0.0));
                 assert_eq!(*x_dir, Vec3::new(1.0, 0.0, 0.0));
                 assert_eq!(*y_dir, Vec3::new(0.0, 1.0, 0.0));
                 assert_eq!(segments.len(), 4);
+                assert!(holes.is_empty());
             }
             _ => panic!("expected Sketch2D"),
         }
@@ -2868,6 +3778,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_loft_and_sweep_roundtrip() {
+        let sketch = "SK 0 0 0  1 0 0  0 1 0\nL 0 0 1 0\nL 1 0 1 1\nL 1 1 0 1\nL 0 1 0 0\nEND";
+        let compact = format!(
+            "{sketch}\n{sketch}\nLF ruled 0 1 \"Loft\"\nSW 0 H 10 2 20 10\nSW 1 P 0 0 0 0 0 5 5 0 5"
+        );
+        let doc = from_compact(&compact).unwrap();
+
+        match &doc.nodes[&2].op {
+            CsgOp::Loft { profiles, mode, .. } => {
+                assert_eq!(profiles, &vec![0, 1]);
+                assert_eq!(*mode, LoftMode::Ruled);
+            }
+            _ => panic!("expected Loft"),
+        }
+        assert_eq!(doc.nodes[&2].name.as_deref(), Some("Loft"));
+        match &doc.nodes[&3].op {
+            CsgOp::Sweep { profile, path, .. } => {
+                assert_eq!(*profile, 0);
+                assert_eq!(
+                    *path,
+                    SweepPath::Helix {
+                        radius: 10.0,
+                        pitch: 2.0,
+                        height: 20.0,
+                        turns: 10.0,
+                    }
+                );
+            }
+            _ => panic!("expected Sweep"),
+        }
+        match &doc.nodes[&4].op {
+            CsgOp::Sweep {
+                path: SweepPath::Polyline { points },
+                ..
+            } => assert_eq!(points.len(), 3),
+            _ => panic!("expected polyline Sweep"),
+        }
+
+        let restored = from_compact(&to_compact(&doc).unwrap()).unwrap();
+        for id in 2..=4 {
+            assert_eq!(restored.nodes[&id].op, doc.nodes[&id].op);
+        }
+
+        assert!(from_compact("SW 0 H 1 2").is_err());
+        assert!(from_compact(&format!("{sketch}\nSW 0 H 10 2 20 10 degenerate=drop")).is_err());
+        assert!(from_compact("LF spline 0 1").is_err());
+    }
+
     #[test]
     fn test_sketch_with_arc() {
         let compact = "SK 0 0 0  1 0 0  0 1 0\nL 0 0 10 0\nA 10 0 10 10 10 5 1\nL 10 10 0 10\nL 0 10 0 0\nEND";
@@ -2895,6 +3854,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sketch_with_hole() {
+        let compact = "SK 0 0 0  1 0 0  0 1 0\nL 0 0 10 0\nL 10 0 10 10\nL 10 10 0 10\nL 0 10 0 0\nHOLE\nL 3 3 3 7\nL 3 7 7 7\nL 7 7 7 3\nL 7 3 3 3\nEND\nEND\nE 0 0 0 5";
+        let doc = from_compact(compact).unwrap();
+
+        match &doc.nodes[&0].op {
+            CsgOp::Sketch2D {
+                segments, holes, ..
+            } => {
+                assert_eq!(segments.len(), 4);
+                assert_eq!(holes.len(), 1);
+                assert_eq!(holes[0].len(), 4);
+            }
+            _ => panic!("expected Sketch2D"),
+        }
+        assert!(matches!(doc.nodes[&1].op, CsgOp::Extrude { sketch: 0, .. }));
+
+        let restored = from_compact(&to_compact(&doc).unwrap()).unwrap();
+        assert_eq!(restored.nodes[&0].op, doc.nodes[&0].op);
+
+        let nested = "SK 0 0 0  1 0 0  0 1 0\nL 0 0 1 0\nHOLE\nHOLE\nEND\nEND\nEND";
+        assert!(from_compact(nested).is_err());
+    }
+
     #[test]
     fn test_comments_and_empty_lines() {
         let compact = "# This is a comment\nC 10 10 10\n\n# Another comment\nY 5 10";
@@ -2956,7 +3939,7 @@ mod tests {
         let doc = from_compact(compact).unwrap();
 
         match &doc.nodes[&0].op {
-            CsgOp::Cube { size } => {
+            CsgOp::Cube { size, .. } => {
                 assert_eq!(size.x, 10.5);
                 assert_eq!(size.y, 20.25);
                 assert_eq!(size.z, 30.125);
@@ -3058,6 +4041,48 @@ PDEF base "Base Part" 0 aluminum"#;
         assert_eq!(pdef.name, Some("Base Part".to_string()));
         assert_eq!(pdef.root, 0);
         assert_eq!(pdef.default_material, Some("aluminum".to_string()));
+        assert_eq!(pdef.collision_root, None);
+    }
+
+    #[test]
+    fn test_part_def_collision_root_roundtrip() {
+        let compact = r#"C 50 30 5
+C 10 10 10
+PDEF base "Base" 0 _ 1
+PDEF arm "Arm" 1 steel 0"#;
+
+        let doc = from_compact(compact).unwrap();
+        let part_defs = doc.part_defs.as_ref().unwrap();
+        assert_eq!(part_defs["base"].default_material, None);
+        assert_eq!(part_defs["base"].collision_root, Some(1));
+        assert_eq!(part_defs["arm"].default_material.as_deref(), Some("steel"));
+        assert_eq!(part_defs["arm"].collision_root, Some(0));
+
+        let restored = from_compact(&to_compact(&doc).unwrap()).unwrap();
+        assert_eq!(restored.part_defs, doc.part_defs);
+    }
+
+    #[test]
+    fn test_part_mass_roundtrip() {
+        let compact = r#"C 50 30 5
+PDEF base "Base Part" 0
+PMASS base 1.5 25 15 2.5 400 -1 0 900 0 1200"#;
+
+        let doc = from_compact(compact).unwrap();
+        let props = doc.part_defs.as_ref().unwrap()["base"]
+            .mass_properties
+            .expect("mass properties");
+        assert_eq!(props.mass, 1.5);
+        assert_eq!(props.center_of_mass, Vec3::new(25.0, 15.0, 2.5));
+        assert_eq!(
+            props.inertia,
+            [[400.0, -1.0, 0.0], [-1.0, 900.0, 0.0], [0.0, 0.0, 1200.0]]
+        );
+
+        let restored = from_compact(&to_compact(&doc).unwrap()).unwrap();
+        assert_eq!(restored.part_defs, doc.part_defs);
+
+        assert!(from_compact("C 1 1 1\nPMASS base 1 0 0 0 1 0 0 1 0 1").is_err());
     }
 
     #[test]
@@ -3320,6 +4345,9 @@ CAM cam2 0 100 0 0 0 0"#;
                 roughness: 0.3,
                 density: Some(2700.0),
                 friction: Some(0.6),
+                opacity: None,
+                emissive: None,
+                base_color_texture: None,
             },
         );
 
@@ -3331,7 +4359,9 @@ CAM cam2 0 100 0 0 0 0"#;
                 name: Some("Cube".to_string()),
                 op: CsgOp::Cube {
                     size: Vec3::new(10.0, 10.0, 10.0),
+                    origin: BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
 
@@ -3350,6 +4380,8 @@ CAM cam2 0 100 0 0 0 0"#;
                 id: "part1".to_string(),
                 name: Some("Part 1".to_string()),
                 root: 0,
+                collision_root: None,
+                mass_properties: None,
                 default_material: Some("aluminum".to_string()),
             },
         );
@@ -3455,4 +4487,256 @@ CAM cam2 0 100 0 0 0 0"#;
         ));
         assert!(matches!(scene.background, Some(Background::Solid { .. })));
     }
+
+    /// One sample of every op, using only what the compact format can hold.
+    fn sample_ops() -> Vec<CsgOp> {
+        let v = |x, y, z| Vec3::new(x, y, z);
+        let line = |x1, y1, x2, y2| SketchSegment2D::Line {
+            start: Vec2::new(x1, y1),
+            end: Vec2::new(x2, y2),
+        };
+        vec![
+            CsgOp::Cube {
+                size: v(1.0, 2.0, 3.0),
+                origin: BoxOrigin::Center,
+            },
+            CsgOp::Cylinder {
+                radius: 2.0,
+                height: 5.0,
+                segments: 0,
+            },
+            CsgOp::Sphere {
+                radius: 3.5,
+                segments: 0,
+            },
+            CsgOp::Cone {
+                radius_bottom: 4.0,
+                radius_top: 1.0,
+                height: 6.0,
+                segments: 0,
+            },
+            CsgOp::Empty,
+            CsgOp::Union { left: 0, right: 1 },
+            CsgOp::Difference { left: 0, right: 1 },
+            CsgOp::Intersection { left: 0, right: 1 },
+            CsgOp::Translate {
+                child: 0,
+                offset: v(1.0, -2.0, 0.5),
+            },
+            CsgOp::Rotate {
+                child: 0,
+                angles: v(0.0, 45.0, 90.0),
+            },
+            CsgOp::Scale {
+                child: 0,
+                factor: v(2.0, 2.0, 0.5),
+            },
+            CsgOp::Sketch2D {
+                origin: v(0.0, 0.0, 1.0),
+                x_dir: v(1.0, 0.0, 0.0),
+                y_dir: v(0.0, 1.0, 0.0),
+                segments: vec![
+                    line(0.0, 0.0, 4.0, 0.0),
+                    SketchSegment2D::Arc {
+                        start: Vec2::new(4.0, 0.0),
+                        end: Vec2::new(0.0, 4.0),
+                        center: Vec2::new(0.0, 0.0),
+                        ccw: true,
+                    },
+                    line(0.0, 4.0, 0.0, 0.0),
+                ],
+                holes: vec![vec![
+                    line(1.0, 1.0, 2.0, 1.0),
+                    line(2.0, 1.0, 1.0, 2.0),
+                    line(1.0, 2.0, 1.0, 1.0),
+                ]],
+            },
+            CsgOp::Extrude {
+                sketch: 0,
+                direction: v(0.0, 0.0, 10.0),
+                twist_angle: None,
+                scale_end: None,
+            },
+            CsgOp::Revolve {
+                sketch: 0,
+                axis_origin: v(0.0, 0.0, 0.0),
+                axis_dir: v(0.0, 1.0, 0.0),
+                angle_deg: 270.0,
+            },
+            CsgOp::Loft {
+                profiles: vec![0, 1],
+                mode: LoftMode::Smooth,
+                closed: true,
+            },
+            CsgOp::Sweep {
+                profile: 0,
+                path: SweepPath::Helix {
+                    radius: 5.0,
+                    pitch: 2.0,
+                    height: 10.0,
+                    turns: 5.0,
+                },
+                options: SweepOptions::default(),
+            },
+            CsgOp::Sweep {
+                profile: 0,
+                path: SweepPath::Polyline {
+                    points: vec![v(0.0, 0.0, 0.0), v(0.0, 0.0, 10.0), v(5.0, 0.0, 15.0)],
+                },
+                options: SweepOptions {
+                    twist_angle: Some(0.5),
+                    scale_start: Some(1.0),
+                    scale_end: Some(0.25),
+                    path_segments: Some(48),
+                    arc_segments: Some(12),
+                    orientation: Some(-0.75),
+                    on_degenerate: Some(DegeneratePolicy::Skip),
+                },
+            },
+            CsgOp::LinearPattern {
+                child: 0,
+                direction: v(1.0, 0.0, 0.0),
+                count: 4,
+                spacing: 12.5,
+            },
+            CsgOp::CircularPattern {
+                child: 0,
+                axis_origin: v(1.0, 2.0, 3.0),
+                axis_dir: v(0.0, 0.0, 1.0),
+                count: 6,
+                angle_deg: 360.0,
+            },
+            CsgOp::Shell {
+                child: 0,
+                thickness: 0.8,
+            },
+            CsgOp::Fillet {
+                child: 0,
+                radius: 1.5,
+            },
+            CsgOp::Chamfer {
+                child: 0,
+                distance: 0.5,
+            },
+            CsgOp::Text2D {
+                origin: v(0.0, 0.0, 0.0),
+                x_dir: v(1.0, 0.0, 0.0),
+                y_dir: v(0.0, 1.0, 0.0),
+                text: "vcad".to_string(),
+                font: "sans-serif".to_string(),
+                height: 5.0,
+                letter_spacing: None,
+                line_spacing: None,
+                alignment: TextAlignment::Left,
+            },
+            CsgOp::Text {
+                content: "Serial 42".to_string(),
+                font: "monospace".to_string(),
+                height: 4.0,
+                depth: 0.5,
+                plane_origin: v(1.0, 1.0, 5.0),
+                x_dir: v(1.0, 0.0, 0.0),
+                y_dir: v(0.0, 1.0, 0.0),
+                alignment: TextAlignment::Right,
+            },
+            CsgOp::ProjectToSketch {
+                solid: 0,
+                plane_origin: v(0.0, 0.0, 0.0),
+                plane_normal: v(0.0, 0.0, 1.0),
+            },
+            CsgOp::SvgProfile {
+                path_data: "M0 0 L10 0 L10 10 Z".to_string(),
+                scale: 0.5,
+                plane_origin: v(0.0, 0.0, 2.0),
+                x_dir: v(1.0, 0.0, 0.0),
+                y_dir: v(0.0, 1.0, 0.0),
+            },
+            CsgOp::Tag {
+                child: 0,
+                key: "color".to_string(),
+                value: "#ff0000".to_string(),
+            },
+            CsgOp::SplitByPlane {
+                child: 0,
+                plane_origin: v(0.0, 0.0, 1.0),
+                plane_normal: v(0.0, 0.0, -1.0),
+                keep: SplitKeep::Both,
+            },
+            CsgOp::StepImport {
+                path: "parts/bracket v2.step".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_every_op_roundtrips() {
+        for op in sample_ops() {
+            // Cube leaves for the op's children, then the op itself, named
+            let children = get_children(&op);
+            let mut doc = Document::new();
+            for &id in &children {
+                doc.nodes.insert(
+                    id,
+                    Node {
+                        id,
+                        name: None,
+                        op: CsgOp::Cube {
+                            size: Vec3::new(1.0, 1.0, 1.0),
+                            origin: BoxOrigin::Corner,
+                        },
+                        attributes: None,
+                    },
+                );
+            }
+            let id = children.len() as u64;
+            doc.nodes.insert(
+                id,
+                Node {
+                    id,
+                    name: Some("sample".to_string()),
+                    op: op.clone(),
+                    attributes: None,
+                },
+            );
+
+            let expected = match &op {
+                // Text2D has no opcode; it must be refused, not dropped
+                CsgOp::Text2D { .. } => {
+                    let err = to_compact(&doc).unwrap_err();
+                    assert!(err.message.contains("Text2D"), "{}", err);
+                    continue;
+                }
+                // Empty is written as a zero-size cube
+                CsgOp::Empty => CsgOp::Cube {
+                    size: Vec3::new(0.0, 0.0, 0.0),
+                    origin: BoxOrigin::Corner,
+                },
+                other => other.clone(),
+            };
+
+            let compact = to_compact(&doc)
+                .unwrap_or_else(|e| panic!("{} failed to serialize: {}", op.type_name(), e));
+            let restored = from_compact(&compact).unwrap_or_else(|e| {
+                panic!("{} failed to parse: {}\n{}", op.type_name(), e, compact)
+            });
+            let node = &restored.nodes[&id];
+            assert_eq!(node.op, expected, "{}", op.type_name());
+            assert_eq!(node.name.as_deref(), Some("sample"), "{}", op.type_name());
+        }
+    }
+
+    #[test]
+    fn test_step_import() {
+        let doc = from_compact("STP \"bracket.step\"\nSTP \"base.stp\" \"Base\"").unwrap();
+        assert!(matches!(
+            &doc.nodes[&0].op,
+            CsgOp::StepImport { path } if path == "bracket.step"
+        ));
+        assert_eq!(doc.nodes[&0].name, None);
+        assert!(matches!(
+            &doc.nodes[&1].op,
+            CsgOp::StepImport { path } if path == "base.stp"
+        ));
+        assert_eq!(doc.nodes[&1].name, Some("Base".to_string()));
+    }
 }
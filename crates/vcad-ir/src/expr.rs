@@ -0,0 +1,427 @@
+//! Parameter expressions for parametric documents.
+//!
+//! A document can declare named [`Document::parameters`] and drive numeric
+//! fields of its nodes with arithmetic [`Document::expressions`] over them,
+//! such as `"width / 4 - 2"`. [`Document::resolve_parameters`] evaluates the
+//! expressions and writes the results into the ops, producing a plain
+//! document that the rest of the pipeline can consume unchanged. Evaluators
+//! call [`Document::resolved`] first, so that every front end sees the same
+//! geometry.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::{CsgOp, Document, NodeId};
+
+/// Error returned when parameter expressions cannot be resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprError {
+    /// The expression text is malformed.
+    Parse {
+        /// The expression being parsed.
+        input: String,
+        /// What went wrong.
+        message: String,
+    },
+    /// An expression refers to a parameter that isn't defined.
+    UnknownParameter(String),
+    /// Expressions are attached to a node that doesn't exist.
+    UnknownNode(NodeId),
+    /// An expression targets a field that is missing or can't hold its value.
+    InvalidField {
+        /// Node owning the field.
+        node: NodeId,
+        /// Path of the field within the op.
+        path: String,
+        /// Why the value couldn't be written.
+        reason: String,
+    },
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprError::Parse { input, message } => {
+                write!(f, "invalid expression '{}': {}", input, message)
+            }
+            ExprError::UnknownParameter(name) => write!(f, "unknown parameter '{}'", name),
+            ExprError::UnknownNode(id) => write!(f, "expressions for unknown node {}", id),
+            ExprError::InvalidField { node, path, reason } => {
+                write!(f, "cannot set '{}' on node {}: {}", path, node, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// Binary arithmetic operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    /// `+`
+    Add,
+    /// `-` or `−`
+    Sub,
+    /// `*` or `×`
+    Mul,
+    /// `/` or `÷`
+    Div,
+}
+
+/// A parsed arithmetic expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// A numeric literal.
+    Number(f64),
+    /// A reference to a named parameter.
+    Param(String),
+    /// Negation.
+    Neg(Box<Expr>),
+    /// A binary operation.
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Parse an expression of numbers, parameter names, parentheses, unary
+    /// minus, and `+ - * /` (also `− × ÷`) with the usual precedence.
+    pub fn parse(input: &str) -> Result<Expr, ExprError> {
+        let mut parser = ExprParser {
+            input,
+            chars: input.char_indices().peekable(),
+        };
+        let expr = parser.sum()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(expr),
+            Some((_, c)) => Err(parser.error(format!("unexpected '{}'", c))),
+        }
+    }
+
+    /// Evaluate against parameter values.
+    pub fn eval(&self, parameters: &HashMap<String, f64>) -> Result<f64, ExprError> {
+        Ok(match self {
+            Expr::Number(value) => *value,
+            Expr::Param(name) => *parameters
+                .get(name)
+                .ok_or_else(|| ExprError::UnknownParameter(name.clone()))?,
+            Expr::Neg(inner) => -inner.eval(parameters)?,
+            Expr::Binary(op, lhs, rhs) => {
+                let (a, b) = (lhs.eval(parameters)?, rhs.eval(parameters)?);
+                match op {
+                    BinaryOp::Add => a + b,
+                    BinaryOp::Sub => a - b,
+                    BinaryOp::Mul => a * b,
+                    BinaryOp::Div => a / b,
+                }
+            }
+        })
+    }
+}
+
+/// Recursive-descent parser over the expression grammar.
+struct ExprParser<'a> {
+    input: &'a str,
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+}
+
+impl ExprParser<'_> {
+    fn error(&self, message: String) -> ExprError {
+        ExprError::Parse {
+            input: self.input.to_string(),
+            message,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.peek().map(|&(_, c)| c)
+    }
+
+    /// sum := product (('+' | '-') product)*
+    fn sum(&mut self) -> Result<Expr, ExprError> {
+        let mut lhs = self.product()?;
+        loop {
+            let op = match self.peek() {
+                Some('+') => BinaryOp::Add,
+                Some('-' | '−') => BinaryOp::Sub,
+                _ => return Ok(lhs),
+            };
+            self.chars.next();
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.product()?));
+        }
+    }
+
+    /// product := unary (('*' | '/') unary)*
+    fn product(&mut self) -> Result<Expr, ExprError> {
+        let mut lhs = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Some('*' | '×') => BinaryOp::Mul,
+                Some('/' | '÷') => BinaryOp::Div,
+                _ => return Ok(lhs),
+            };
+            self.chars.next();
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+    }
+
+    /// unary := ('-' | '+') unary | atom
+    fn unary(&mut self) -> Result<Expr, ExprError> {
+        match self.peek() {
+            Some('-' | '−') => {
+                self.chars.next();
+                Ok(Expr::Neg(Box::new(self.unary()?)))
+            }
+            Some('+') => {
+                self.chars.next();
+                self.unary()
+            }
+            _ => self.atom(),
+        }
+    }
+
+    /// atom := number | name | '(' sum ')'
+    fn atom(&mut self) -> Result<Expr, ExprError> {
+        let Some(c) = self.peek() else {
+            return Err(self.error("unexpected end of expression".to_string()));
+        };
+        if c == '(' {
+            self.chars.next();
+            let inner = self.sum()?;
+            return match self.peek() {
+                Some(')') => {
+                    self.chars.next();
+                    Ok(inner)
+                }
+                _ => Err(self.error("expected ')'".to_string())),
+            };
+        }
+
+        let is_number = c.is_ascii_digit() || c == '.';
+        let is_name = c.is_alphabetic() || c == '_';
+        if !is_number && !is_name {
+            return Err(self.error(format!("unexpected '{}'", c)));
+        }
+        let mut token = String::new();
+        while let Some((_, c)) = self.chars.next_if(|&(_, c)| {
+            if is_number {
+                c.is_ascii_digit() || c == '.'
+            } else {
+                c.is_alphanumeric() || c == '_'
+            }
+        }) {
+            token.push(c);
+        }
+        if is_number {
+            token
+                .parse()
+                .map(Expr::Number)
+                .map_err(|_| self.error(format!("invalid number '{}'", token)))
+        } else {
+            Ok(Expr::Param(token))
+        }
+    }
+}
+
+impl Document {
+    /// Evaluate all [`expressions`](Document::expressions) against
+    /// [`parameters`](Document::parameters) and return a copy with the
+    /// results written into the nodes' ops and no expressions left.
+    ///
+    /// Field paths name the op's serialized fields, separated by dots, with
+    /// list positions as numbers: `"size.x"` on a `Cube`, `"radius"` on a
+    /// `Cylinder`, or `"segments.0.end.y"` on a `Sketch2D`. Integer fields
+    /// such as `segments` are rounded.
+    pub fn resolve_parameters(&self) -> Result<Document, ExprError> {
+        let mut doc = self.clone();
+        for (&node_id, fields) in &self.expressions {
+            let node = doc
+                .nodes
+                .get_mut(&node_id)
+                .ok_or(ExprError::UnknownNode(node_id))?;
+            let invalid = |path: &str, reason: String| ExprError::InvalidField {
+                node: node_id,
+                path: path.to_string(),
+                reason,
+            };
+
+            let mut op = serde_json::to_value(&node.op).map_err(|e| invalid("", e.to_string()))?;
+            for (path, text) in fields {
+                let value = Expr::parse(text)?.eval(&self.parameters)?;
+                if !value.is_finite() {
+                    return Err(invalid(path, format!("'{}' is not finite", text)));
+                }
+                let slot = path
+                    .split('.')
+                    .try_fold(&mut op, |v, key| match v {
+                        Value::Array(items) => {
+                            key.parse().ok().and_then(|i: usize| items.get_mut(i))
+                        }
+                        _ => v.get_mut(key),
+                    })
+                    .ok_or_else(|| invalid(path, "no such field".to_string()))?;
+                *slot = match &*slot {
+                    Value::Number(n) if n.is_u64() && value >= 0.0 => {
+                        Value::from(value.round() as u64)
+                    }
+                    Value::Number(n) if n.is_u64() || n.is_i64() => {
+                        Value::from(value.round() as i64)
+                    }
+                    Value::Number(_) => Value::from(value),
+                    _ => return Err(invalid(path, "not a numeric field".to_string())),
+                };
+            }
+            node.op =
+                serde_json::from_value::<CsgOp>(op).map_err(|e| invalid("", e.to_string()))?;
+        }
+        doc.expressions.clear();
+        Ok(doc)
+    }
+
+    /// The document evaluators should work on: `self` if it has no
+    /// expressions, otherwise the result of
+    /// [`resolve_parameters`](Document::resolve_parameters).
+    pub fn resolved(&self) -> Result<Cow<'_, Document>, ExprError> {
+        if self.expressions.is_empty() {
+            Ok(Cow::Borrowed(self))
+        } else {
+            self.resolve_parameters().map(Cow::Owned)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoxOrigin, Node, SceneEntry, Vec3};
+
+    fn params(pairs: &[(&str, f64)]) -> HashMap<String, f64> {
+        pairs.iter().map(|&(k, v)| (k.to_string(), v)).collect()
+    }
+
+    #[test]
+    fn parse_and_eval() {
+        let p = params(&[("width", 100.0), ("wall_2", 3.0)]);
+        let eval = |s: &str| Expr::parse(s).unwrap().eval(&p).unwrap();
+        assert_eq!(eval("1 + 2 * 3"), 7.0);
+        assert_eq!(eval("(1 + 2) * 3"), 9.0);
+        assert_eq!(eval("width / 4 - wall_2"), 22.0);
+        assert_eq!(eval("-width × 2 ÷ 8 − 1"), -26.0);
+        assert_eq!(eval("10 - 4 - 3"), 3.0);
+        assert_eq!(eval(" .5 "), 0.5);
+
+        assert!(matches!(
+            Expr::parse("width +"),
+            Err(ExprError::Parse { .. })
+        ));
+        assert!(matches!(Expr::parse("(1"), Err(ExprError::Parse { .. })));
+        assert!(matches!(Expr::parse("2 $ 3"), Err(ExprError::Parse { .. })));
+        assert_eq!(
+            Expr::parse("depth").unwrap().eval(&p),
+            Err(ExprError::UnknownParameter("depth".to_string()))
+        );
+    }
+
+    #[test]
+    fn resolve_cube_from_width() {
+        let mut doc = Document::new();
+        doc.nodes.insert(
+            1,
+            Node {
+                id: 1,
+                name: None,
+                op: CsgOp::Cube {
+                    size: Vec3::new(1.0, 1.0, 5.0),
+                    origin: BoxOrigin::Corner,
+                },
+                attributes: None,
+            },
+        );
+        doc.nodes.insert(
+            2,
+            Node {
+                id: 2,
+                name: None,
+                op: CsgOp::Cylinder {
+                    radius: 1.0,
+                    height: 10.0,
+                    segments: 32,
+                },
+                attributes: None,
+            },
+        );
+        doc.roots.push(SceneEntry {
+            root: 1,
+            material: "default".to_string(),
+            visible: None,
+        });
+        doc.parameters = params(&[("width", 40.0), ("res", 47.6)]);
+        doc.expressions.insert(
+            1,
+            [("size.x", "width"), ("size.y", "width / 2")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+        doc.expressions.insert(
+            2,
+            [("radius", "width / 8"), ("segments", "res")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+
+        // Expressions survive a JSON round trip
+        let doc = Document::from_json(&doc.to_json().unwrap()).unwrap();
+        let resolved = doc.resolve_parameters().unwrap();
+        assert!(resolved.expressions.is_empty());
+        assert_eq!(*doc.resolved().unwrap(), resolved);
+        assert_eq!(
+            resolved.nodes[&1].op,
+            CsgOp::Cube {
+                size: Vec3::new(40.0, 20.0, 5.0),
+                origin: BoxOrigin::Corner,
+            }
+        );
+        assert_eq!(
+            resolved.nodes[&2].op,
+            CsgOp::Cylinder {
+                radius: 5.0,
+                height: 10.0,
+                segments: 48,
+            }
+        );
+
+        let mut changed = doc.clone();
+        changed.parameters.insert("width".to_string(), 60.0);
+        let resolved = changed.resolve_parameters().unwrap();
+        assert!(matches!(resolved.nodes[&1].op, CsgOp::Cube { size, .. } if size.x == 60.0));
+
+        let mut bad = doc.clone();
+        bad.expressions
+            .get_mut(&1)
+            .unwrap()
+            .insert("size.w".to_string(), "1".to_string());
+        assert!(matches!(
+            bad.resolve_parameters(),
+            Err(ExprError::InvalidField { node: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn plain_documents_unchanged() {
+        let doc = Document::new();
+        let json = doc.to_json().unwrap();
+        assert!(!json.contains("parameters"));
+        assert!(!json.contains("expressions"));
+        assert_eq!(doc.resolve_parameters().unwrap(), doc);
+        assert!(matches!(doc.resolved(), Ok(Cow::Borrowed(_))));
+    }
+}
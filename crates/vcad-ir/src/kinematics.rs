@@ -0,0 +1,337 @@
+//! Forward kinematics for assembly documents.
+//!
+//! Walks the joint tree from the ground instance and computes a world
+//! [`Transform3D`] for every instance, given each joint's current `state`.
+//! This mirrors `solveForwardKinematics` in the TypeScript engine.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use crate::{Document, Joint, JointKind, Transform3D, Vec3};
+
+/// Error returned when an assembly cannot be posed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KinematicsError {
+    /// The joint graph contains a cycle through the given instance.
+    Cycle(String),
+}
+
+impl fmt::Display for KinematicsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KinematicsError::Cycle(id) => {
+                write!(f, "joint graph contains a cycle through instance '{}'", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for KinematicsError {}
+
+type Mat3 = [[f64; 3]; 3];
+
+/// Compute the world transform of every instance from the current joint states.
+///
+/// Traversal starts at the ground instance (and any other instance that is
+/// not the child of a joint), which keep their own transform. Each child is
+/// placed by its joint: the child anchor is brought onto the parent anchor,
+/// then rotated about (revolute) or offset along (slider) the joint axis by
+/// the joint `state`, and finally composed with the instance's local
+/// transform and its parent's world transform.
+///
+/// Instances that are unreachable from a root are omitted.
+///
+/// # Errors
+///
+/// Returns [`KinematicsError::Cycle`] if the joints form a cycle.
+pub fn pose_instances(doc: &Document) -> Result<HashMap<String, Transform3D>, KinematicsError> {
+    let mut results = HashMap::new();
+
+    let instances = match &doc.instances {
+        Some(instances) if !instances.is_empty() => instances,
+        _ => return Ok(results),
+    };
+    let joints: &[Joint] = doc.joints.as_deref().unwrap_or(&[]);
+
+    // child instance → joint placing it
+    let mut joint_of_child: HashMap<&str, &Joint> = HashMap::new();
+    for joint in joints {
+        joint_of_child.insert(joint.child_instance_id.as_str(), joint);
+    }
+
+    // Reject cycles: following parents from any child must reach a root.
+    for joint in joints {
+        let start = joint.child_instance_id.as_str();
+        let mut seen = HashSet::new();
+        let mut current = Some(start);
+        while let Some(id) = current {
+            if !seen.insert(id) {
+                return Err(KinematicsError::Cycle(id.to_string()));
+            }
+            current = joint_of_child
+                .get(id)
+                .and_then(|j| j.parent_instance_id.as_deref());
+        }
+    }
+
+    let mut children_of: HashMap<Option<&str>, Vec<&str>> = HashMap::new();
+    for joint in joints {
+        children_of
+            .entry(joint.parent_instance_id.as_deref())
+            .or_default()
+            .push(joint.child_instance_id.as_str());
+    }
+
+    let instance_by_id: HashMap<&str, _> = instances.iter().map(|i| (i.id.as_str(), i)).collect();
+
+    // Roots: the ground instance first, then any other unjointed instance.
+    let mut roots: Vec<&str> = Vec::new();
+    if let Some(ground) = doc.ground_instance_id.as_deref() {
+        if instance_by_id.contains_key(ground) && !joint_of_child.contains_key(ground) {
+            roots.push(ground);
+        }
+    }
+    for instance in instances {
+        let id = instance.id.as_str();
+        if !joint_of_child.contains_key(id) && !roots.contains(&id) {
+            roots.push(id);
+        }
+    }
+
+    for &root in &roots {
+        let local = instance_by_id[root].transform.unwrap_or_default();
+        results.insert(root.to_string(), local);
+    }
+
+    let mut queue: VecDeque<Option<&str>> = VecDeque::new();
+    queue.push_back(None);
+    queue.extend(roots.iter().map(|&r| Some(r)));
+
+    while let Some(parent) = queue.pop_front() {
+        let Some(children) = children_of.get(&parent) else {
+            continue;
+        };
+        for &child in children {
+            if results.contains_key(child) {
+                continue;
+            }
+            let Some(instance) = instance_by_id.get(child) else {
+                continue;
+            };
+            let joint = joint_of_child[child];
+
+            let parent_world = parent
+                .and_then(|p| results.get(p).copied())
+                .unwrap_or_default();
+            let local = instance.transform.unwrap_or_default();
+            let jointed = compose(&joint_transform(joint), &local);
+            results.insert(child.to_string(), compose(&parent_world, &jointed));
+            queue.push_back(Some(child));
+        }
+    }
+
+    Ok(results)
+}
+
+/// Transform placing a joint's child relative to its parent at the current state.
+fn joint_transform(joint: &Joint) -> Transform3D {
+    let rot = match &joint.kind {
+        JointKind::Fixed | JointKind::Slider { .. } => IDENTITY,
+        JointKind::Revolute { axis, .. } | JointKind::Cylindrical { axis } => {
+            axis_angle(normalize(*axis), joint.state)
+        }
+        // Single-valued state: interpret as rotation about Z.
+        JointKind::Ball => axis_angle(Vec3::new(0.0, 0.0, 1.0), joint.state),
+    };
+
+    let rotated_child = mat_vec(&rot, joint.child_anchor);
+    let mut translation = sub(joint.parent_anchor, rotated_child);
+    if let JointKind::Slider { axis, .. } = &joint.kind {
+        translation = add(translation, scale(normalize(*axis), joint.state));
+    }
+
+    Transform3D {
+        translation,
+        rotation: matrix_to_euler(&rot),
+        scale: Vec3::new(1.0, 1.0, 1.0),
+    }
+}
+
+/// Compose two transforms: apply `inner` first, then `outer`.
+fn compose(outer: &Transform3D, inner: &Transform3D) -> Transform3D {
+    let outer_rot = euler_to_matrix(outer.rotation);
+    let inner_rot = euler_to_matrix(inner.rotation);
+
+    let scaled_inner = Vec3::new(
+        outer.scale.x * inner.translation.x,
+        outer.scale.y * inner.translation.y,
+        outer.scale.z * inner.translation.z,
+    );
+
+    Transform3D {
+        translation: add(outer.translation, mat_vec(&outer_rot, scaled_inner)),
+        rotation: matrix_to_euler(&mat_mul(&outer_rot, &inner_rot)),
+        scale: Vec3::new(
+            outer.scale.x * inner.scale.x,
+            outer.scale.y * inner.scale.y,
+            outer.scale.z * inner.scale.z,
+        ),
+    }
+}
+
+const IDENTITY: Mat3 = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+/// Rotation matrix from Euler angles in degrees (applied X, then Y, then Z).
+fn euler_to_matrix(angles: Vec3) -> Mat3 {
+    let (sx, cx) = angles.x.to_radians().sin_cos();
+    let (sy, cy) = angles.y.to_radians().sin_cos();
+    let (sz, cz) = angles.z.to_radians().sin_cos();
+    [
+        [cy * cz, sx * sy * cz - cx * sz, cx * sy * cz + sx * sz],
+        [cy * sz, sx * sy * sz + cx * cz, cx * sy * sz - sx * cz],
+        [-sy, sx * cy, cx * cy],
+    ]
+}
+
+/// Euler angles in degrees (XYZ order) from a rotation matrix.
+fn matrix_to_euler(m: &Mat3) -> Vec3 {
+    let sy = -m[2][0];
+    let cy = (m[0][0] * m[0][0] + m[1][0] * m[1][0]).sqrt();
+    if cy > 1e-6 {
+        Vec3::new(
+            m[2][1].atan2(m[2][2]).to_degrees(),
+            sy.atan2(cy).to_degrees(),
+            m[1][0].atan2(m[0][0]).to_degrees(),
+        )
+    } else {
+        // Gimbal lock
+        Vec3::new(
+            (-m[1][2]).atan2(m[1][1]).to_degrees(),
+            sy.atan2(cy).to_degrees(),
+            0.0,
+        )
+    }
+}
+
+/// Rotation matrix about a unit axis by `angle_deg` degrees (Rodrigues).
+fn axis_angle(axis: Vec3, angle_deg: f64) -> Mat3 {
+    let (s, c) = angle_deg.to_radians().sin_cos();
+    let t = 1.0 - c;
+    let (x, y, z) = (axis.x, axis.y, axis.z);
+    [
+        [t * x * x + c, t * x * y - s * z, t * x * z + s * y],
+        [t * x * y + s * z, t * y * y + c, t * y * z - s * x],
+        [t * x * z - s * y, t * y * z + s * x, t * z * z + c],
+    ]
+}
+
+fn mat_vec(m: &Mat3, v: Vec3) -> Vec3 {
+    Vec3::new(
+        m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+        m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+        m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+    )
+}
+
+fn mat_mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    let mut r = [[0.0; 3]; 3];
+    for (i, row) in r.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            *cell = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+        }
+    }
+    r
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    Vec3::new(a.x + b.x, a.y + b.y, a.z + b.z)
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    Vec3::new(a.x - b.x, a.y - b.y, a.z - b.z)
+}
+
+fn scale(v: Vec3, s: f64) -> Vec3 {
+    Vec3::new(v.x * s, v.y * s, v.z * s)
+}
+
+fn normalize(v: Vec3) -> Vec3 {
+    let len = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
+    if len < 1e-10 {
+        Vec3::new(0.0, 0.0, 1.0)
+    } else {
+        scale(v, 1.0 / len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Instance;
+
+    fn instance(id: &str) -> Instance {
+        Instance {
+            id: id.to_string(),
+            part_def_id: "part".to_string(),
+            name: None,
+            transform: None,
+            material: None,
+        }
+    }
+
+    fn revolute(id: &str, parent: Option<&str>, child: &str, anchor: Vec3, state: f64) -> Joint {
+        Joint {
+            id: id.to_string(),
+            name: None,
+            parent_instance_id: parent.map(str::to_string),
+            child_instance_id: child.to_string(),
+            parent_anchor: anchor,
+            child_anchor: Vec3::new(0.0, 0.0, 0.0),
+            kind: JointKind::Revolute {
+                axis: Vec3::new(0.0, 0.0, 1.0),
+                limits: None,
+            },
+            state,
+        }
+    }
+
+    #[test]
+    fn slider_offsets_along_axis() {
+        let mut doc = Document::new();
+        doc.instances = Some(vec![instance("base"), instance("carriage")]);
+        doc.ground_instance_id = Some("base".to_string());
+        doc.joints = Some(vec![Joint {
+            kind: JointKind::Slider {
+                axis: Vec3::new(2.0, 0.0, 0.0),
+                limits: None,
+            },
+            ..revolute(
+                "j",
+                Some("base"),
+                "carriage",
+                Vec3::new(0.0, 0.0, 5.0),
+                30.0,
+            )
+        }]);
+
+        let poses = pose_instances(&doc).unwrap();
+        let t = poses["carriage"].translation;
+        assert!((t.x - 30.0).abs() < 1e-9);
+        assert!((t.z - 5.0).abs() < 1e-9);
+        assert_eq!(poses["base"], Transform3D::identity());
+    }
+
+    #[test]
+    fn cyclic_joints_are_rejected() {
+        let mut doc = Document::new();
+        doc.instances = Some(vec![instance("a"), instance("b")]);
+        doc.joints = Some(vec![
+            revolute("j1", Some("a"), "b", Vec3::new(0.0, 0.0, 0.0), 0.0),
+            revolute("j2", Some("b"), "a", Vec3::new(0.0, 0.0, 0.0), 0.0),
+        ]);
+        assert!(matches!(
+            pose_instances(&doc),
+            Err(KinematicsError::Cycle(_))
+        ));
+    }
+}
@@ -7,9 +7,29 @@
 //! Evaluation (meshing) is handled separately by the engine.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 
 pub mod compact;
+pub mod expr;
+pub mod kinematics;
+pub mod mass;
+pub mod migrate;
+pub mod partial;
+pub mod patch;
+pub mod stats;
+pub mod transform;
+pub mod usage;
+
+pub use expr::{Expr, ExprError};
+pub use kinematics::{pose_instances, KinematicsError};
+pub use mass::{MassProperties, VolumeProperties};
+pub use migrate::{MigrationError, CURRENT_VERSION};
+pub use partial::DocumentHeader;
+pub use patch::DocumentPatch;
+pub use stats::DocumentStats;
+pub use transform::Transform;
+pub use usage::{MaterialUsage, PrintEstimate};
 
 // ============================================================================
 // Assembly types (for kinematics)
@@ -135,6 +155,23 @@ pub struct PartDef {
     pub name: Option<String>,
     /// Root node of the geometry DAG.
     pub root: NodeId,
+    /// Root node of simplified collision geometry, when it differs from
+    /// `root`. Consumers fall back to `root` when absent.
+    #[serde(
+        rename = "collisionRoot",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub collision_root: Option<NodeId>,
+    /// Mass properties given explicitly, as imported from a URDF
+    /// `<inertial>`. Consumers prefer these over properties derived from
+    /// the material density.
+    #[serde(
+        rename = "massProperties",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub mass_properties: Option<MassProperties>,
     /// Default material key.
     #[serde(rename = "defaultMaterial", skip_serializing_if = "Option::is_none")]
     pub default_material: Option<String>,
@@ -161,6 +198,15 @@ impl Vec2 {
     pub fn new(x: f64, y: f64) -> Self {
         Self { x, y }
     }
+
+    /// Round onto a grid with `scale` steps per unit, so that points closer
+    /// than about `1 / scale` get the same key.
+    pub fn quantize(&self, scale: f64) -> [i64; 2] {
+        [
+            (self.x * scale).round() as i64,
+            (self.y * scale).round() as i64,
+        ]
+    }
 }
 
 /// 3D vector with f64 components (conventionally millimeters).
@@ -179,6 +225,31 @@ impl Vec3 {
     pub fn new(x: f64, y: f64, z: f64) -> Self {
         Self { x, y, z }
     }
+
+    /// Round onto a grid with `scale` steps per unit, so that points closer
+    /// than about `1 / scale` get the same key.
+    pub fn quantize(&self, scale: f64) -> [i64; 3] {
+        [
+            (self.x * scale).round() as i64,
+            (self.y * scale).round() as i64,
+            (self.z * scale).round() as i64,
+        ]
+    }
+}
+
+/// A [`Vec3`] snapped to a grid, for use as a map key.
+///
+/// `Vec3` compares exactly, so coordinates with floating-point residuals
+/// (e.g. from the constraint solver) never dedupe. Quantizing first makes
+/// nearly coincident points hash and compare equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QuantizedVec3(pub [i64; 3]);
+
+impl QuantizedVec3 {
+    /// Quantize `v` with `scale` grid steps per unit.
+    pub fn new(v: Vec3, scale: f64) -> Self {
+        Self(v.quantize(scale))
+    }
 }
 
 /// Text alignment options for 2D text geometry.
@@ -206,6 +277,9 @@ pub enum SketchSegment2D {
         end: Vec2,
     },
     /// A circular arc from start to end around a center.
+    ///
+    /// An arc whose start and end coincide is a full circle. The start must
+    /// not lie on the center.
     Arc {
         /// Start point in 2D sketch coordinates.
         start: Vec2,
@@ -218,6 +292,202 @@ pub enum SketchSegment2D {
     },
 }
 
+impl SketchSegment2D {
+    /// Length of the segment.
+    ///
+    /// An arc whose start and end coincide is a full circle.
+    pub fn length(&self) -> f64 {
+        match self {
+            SketchSegment2D::Line { start, end } => (end.x - start.x).hypot(end.y - start.y),
+            SketchSegment2D::Arc { .. } => {
+                let (_, sweep, radius) = self.arc_params();
+                radius * sweep.abs()
+            }
+        }
+    }
+
+    /// Point at parameter `t`, where 0 is the start and 1 the end.
+    ///
+    /// Arcs are parameterized by angle, so equal steps in `t` are equal
+    /// steps in arc length.
+    pub fn point_at(&self, t: f64) -> Vec2 {
+        match self {
+            SketchSegment2D::Line { start, end } => Vec2::new(
+                start.x + (end.x - start.x) * t,
+                start.y + (end.y - start.y) * t,
+            ),
+            SketchSegment2D::Arc { center, .. } => {
+                let (start_angle, sweep, radius) = self.arc_params();
+                let angle = start_angle + sweep * t;
+                Vec2::new(
+                    center.x + radius * angle.cos(),
+                    center.y + radius * angle.sin(),
+                )
+            }
+        }
+    }
+
+    /// Unit tangent in the direction of travel at parameter `t`.
+    ///
+    /// Returns a zero vector for degenerate segments.
+    pub fn tangent_at(&self, t: f64) -> Vec2 {
+        let (dx, dy) = match self {
+            SketchSegment2D::Line { start, end } => (end.x - start.x, end.y - start.y),
+            SketchSegment2D::Arc { .. } => {
+                let (start_angle, sweep, _) = self.arc_params();
+                let angle = start_angle + sweep * t;
+                let dir = sweep.signum();
+                (-angle.sin() * dir, angle.cos() * dir)
+            }
+        };
+        let len = dx.hypot(dy);
+        if len < 1e-12 {
+            Vec2::new(0.0, 0.0)
+        } else {
+            Vec2::new(dx / len, dy / len)
+        }
+    }
+
+    /// Start angle, signed sweep (positive counter-clockwise) and radius of
+    /// an arc. Lines yield zero sweep.
+    fn arc_params(&self) -> (f64, f64, f64) {
+        let SketchSegment2D::Arc {
+            start,
+            end,
+            center,
+            ccw,
+        } = self
+        else {
+            return (0.0, 0.0, 0.0);
+        };
+        let radius = (start.x - center.x).hypot(start.y - center.y);
+        let a0 = (start.y - center.y).atan2(start.x - center.x);
+        let a1 = (end.y - center.y).atan2(end.x - center.x);
+        let tau = std::f64::consts::TAU;
+        let mut sweep = if *ccw {
+            (a1 - a0).rem_euclid(tau)
+        } else {
+            (a0 - a1).rem_euclid(tau)
+        };
+        // Coincident endpoints mean a full circle, not an empty arc.
+        if sweep < 1e-12 || tau - sweep < 1e-12 {
+            sweep = tau;
+        }
+        (a0, if *ccw { sweep } else { -sweep }, radius)
+    }
+}
+
+/// Interpolation between the profiles of a [`CsgOp::Loft`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LoftMode {
+    /// Connect adjacent profiles with ruled (planar) faces (default).
+    #[default]
+    Ruled,
+    /// Connect profiles with smooth surfaces.
+    Smooth,
+}
+
+/// Where a [`CsgOp::Cube`] sits relative to the origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BoxOrigin {
+    /// Min corner at the origin, extending along +X, +Y and +Z (default).
+    #[default]
+    Corner,
+    /// Centered on the origin.
+    Center,
+}
+
+impl BoxOrigin {
+    /// Whether this is the default [`BoxOrigin::Corner`].
+    pub fn is_corner(&self) -> bool {
+        *self == BoxOrigin::Corner
+    }
+}
+
+/// Which side of the plane a [`CsgOp::SplitByPlane`] keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitKeep {
+    /// The part on the side the plane normal points to.
+    Positive,
+    /// The part behind the plane.
+    Negative,
+    /// Both parts, kept as separate bodies (default).
+    #[default]
+    Both,
+}
+
+/// The path a [`CsgOp::Sweep`] profile travels along.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SweepPath {
+    /// Straight line between two points.
+    Line {
+        /// Start point.
+        start: Vec3,
+        /// End point.
+        end: Vec3,
+    },
+    /// Helix around the Z axis, starting at the origin.
+    Helix {
+        /// Helix radius.
+        radius: f64,
+        /// Height per turn.
+        pitch: f64,
+        /// Total height.
+        height: f64,
+        /// Number of turns.
+        turns: f64,
+    },
+    /// Connected straight segments through the given points.
+    Polyline {
+        /// Path vertices, at least two.
+        points: Vec<Vec3>,
+    },
+}
+
+/// What a [`CsgOp::Sweep`] does at a path station where the profile frame
+/// is undefined, e.g. at a cusp of the path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DegeneratePolicy {
+    /// Fail the evaluation (default).
+    #[default]
+    Error,
+    /// Drop the station, joining its neighbors directly.
+    Skip,
+    /// Keep the station with a frame interpolated from its neighbors.
+    Interpolate,
+}
+
+/// Options for a [`CsgOp::Sweep`]. Unset fields use the kernel defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct SweepOptions {
+    /// Total twist along the path in radians.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub twist_angle: Option<f64>,
+    /// Profile scale at the start of the path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale_start: Option<f64>,
+    /// Profile scale at the end of the path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale_end: Option<f64>,
+    /// Number of segments along the path (0 = auto).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_segments: Option<u32>,
+    /// Segments per arc in the profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arc_segments: Option<u32>,
+    /// Initial profile rotation around the path tangent in radians.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<f64>,
+    /// Handling of path stations with an undefined frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_degenerate: Option<DegeneratePolicy>,
+}
+
 /// CSG operation — the core building block of the IR DAG.
 ///
 /// Each variant is either a leaf primitive or a combining/transform operation
@@ -225,12 +495,19 @@ pub enum SketchSegment2D {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum CsgOp {
-    /// Axis-aligned box centered at origin.
+    /// Axis-aligned box with its min corner at the origin, or centered on
+    /// it with [`BoxOrigin::Center`].
+    ///
+    /// Documents without an `origin` are corner-aligned, which is how every
+    /// evaluator has always built them.
     Cube {
         /// Size along each axis.
         size: Vec3,
+        /// How the box is placed relative to the origin.
+        #[serde(default, skip_serializing_if = "BoxOrigin::is_corner")]
+        origin: BoxOrigin,
     },
-    /// Cylinder along the Z axis, centered at origin.
+    /// Cylinder along the Z axis, with its base centered at the origin.
     Cylinder {
         /// Radius of the cylinder.
         radius: f64,
@@ -246,7 +523,7 @@ pub enum CsgOp {
         /// Number of circular segments (0 = auto).
         segments: u32,
     },
-    /// Cone along the Z axis, centered at origin.
+    /// Cone along the Z axis, with its base centered at the origin.
     Cone {
         /// Bottom radius.
         radius_bottom: f64,
@@ -314,6 +591,10 @@ pub enum CsgOp {
         y_dir: Vec3,
         /// The segments forming the closed profile.
         segments: Vec<SketchSegment2D>,
+        /// Closed inner loops cut out of the profile, such as the bore of a
+        /// washer. Each must lie inside `segments` and not touch the others.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        holes: Vec<Vec<SketchSegment2D>>,
     },
     /// Extrude a sketch profile along a direction vector.
     Extrude {
@@ -339,6 +620,27 @@ pub enum CsgOp {
         /// Revolution angle in degrees (360 for full revolution).
         angle_deg: f64,
     },
+    /// Loft through a sequence of sketch profiles.
+    Loft {
+        /// The sketch nodes to loft through, in order (at least two).
+        profiles: Vec<NodeId>,
+        /// Interpolation between adjacent profiles.
+        #[serde(default)]
+        mode: LoftMode,
+        /// Connect the last profile back to the first, making a tube.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        closed: bool,
+    },
+    /// Sweep a sketch profile along a path.
+    Sweep {
+        /// The sketch node to sweep.
+        profile: NodeId,
+        /// The path to sweep along.
+        path: SweepPath,
+        /// Twist, scaling and sampling options.
+        #[serde(default)]
+        options: SweepOptions,
+    },
     /// Linear pattern — repeat geometry along a direction.
     LinearPattern {
         /// Child node to pattern.
@@ -411,6 +713,89 @@ pub enum CsgOp {
         #[serde(default)]
         alignment: TextAlignment,
     },
+    /// 3D text: glyph outlines extruded into a solid, e.g. for engraved
+    /// or embossed labels.
+    ///
+    /// The text is laid out as for [`CsgOp::Text2D`], with its baseline
+    /// starting at `plane_origin`, and extruded by `depth` along
+    /// `x_dir × y_dir`.
+    Text {
+        /// The text string to render.
+        content: String,
+        /// Font name. Unknown fonts fall back to the built-in sans-serif.
+        font: String,
+        /// Text height in mm.
+        height: f64,
+        /// Extrusion depth in mm.
+        depth: f64,
+        /// Origin of the text plane in 3D.
+        plane_origin: Vec3,
+        /// X direction of the text plane (text flows along this axis).
+        x_dir: Vec3,
+        /// Y direction of the text plane (text height along this axis).
+        y_dir: Vec3,
+        /// Text alignment.
+        #[serde(default)]
+        alignment: TextAlignment,
+    },
+    /// Sketch from the silhouette of a solid projected onto a plane.
+    ///
+    /// Produces closed profiles like [`CsgOp::Sketch2D`], for referencing
+    /// existing geometry when sketching. The plane's local axes follow the
+    /// same convention as a planar section through `plane_origin`.
+    ProjectToSketch {
+        /// The solid node to project.
+        solid: NodeId,
+        /// A point on the sketch plane.
+        plane_origin: Vec3,
+        /// Normal of the sketch plane (the projection direction).
+        plane_normal: Vec3,
+    },
+    /// Sketch profile from SVG path data (the `d` attribute of a `<path>`).
+    ///
+    /// Each subpath becomes a closed loop; loops nested inside an odd number
+    /// of others are holes. Parsed by the `vcad-kernel-svg` crate.
+    SvgProfile {
+        /// SVG path data, e.g. `"M0 0 L10 0 L10 10 Z"`.
+        path_data: String,
+        /// Millimeters per SVG user unit.
+        scale: f64,
+        /// Origin of the sketch plane in 3D.
+        plane_origin: Vec3,
+        /// Unit vector along the local X axis.
+        x_dir: Vec3,
+        /// Unit vector along the local Y axis (SVG's Y is flipped onto it).
+        y_dir: Vec3,
+    },
+    /// Key/value metadata attached to a subtree, e.g. for tinting it in a
+    /// viewer while debugging.
+    ///
+    /// Geometrically transparent: evaluates to its child unchanged. Unlike
+    /// materials, tags do not affect the scene or exports.
+    Tag {
+        /// The tagged node.
+        child: NodeId,
+        /// Tag key (e.g. `"color"`).
+        key: String,
+        /// Tag value (e.g. `"#ff0000"`).
+        value: String,
+    },
+    /// Cut a solid by a plane.
+    ///
+    /// Each part is closed by a planar cap where the plane cuts through the
+    /// solid. With [`SplitKeep::Both`] the two halves are kept as separate
+    /// bodies in one result rather than fused back together.
+    SplitByPlane {
+        /// The solid node to split.
+        child: NodeId,
+        /// A point on the cutting plane.
+        plane_origin: Vec3,
+        /// Normal of the cutting plane, pointing to the positive side.
+        plane_normal: Vec3,
+        /// Which part(s) to keep.
+        #[serde(default)]
+        keep: SplitKeep,
+    },
     /// Imported geometry from a STEP file.
     #[serde(rename = "step_import")]
     StepImport {
@@ -419,6 +804,50 @@ pub enum CsgOp {
     },
 }
 
+impl CsgOp {
+    /// The variant name, as written in the `type` field of the JSON form.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            CsgOp::Cube { .. } => "Cube",
+            CsgOp::Cylinder { .. } => "Cylinder",
+            CsgOp::Sphere { .. } => "Sphere",
+            CsgOp::Cone { .. } => "Cone",
+            CsgOp::Empty => "Empty",
+            CsgOp::Union { .. } => "Union",
+            CsgOp::Difference { .. } => "Difference",
+            CsgOp::Intersection { .. } => "Intersection",
+            CsgOp::Translate { .. } => "Translate",
+            CsgOp::Rotate { .. } => "Rotate",
+            CsgOp::Scale { .. } => "Scale",
+            CsgOp::Sketch2D { .. } => "Sketch2D",
+            CsgOp::Extrude { .. } => "Extrude",
+            CsgOp::Revolve { .. } => "Revolve",
+            CsgOp::Loft { .. } => "Loft",
+            CsgOp::Sweep { .. } => "Sweep",
+            CsgOp::LinearPattern { .. } => "LinearPattern",
+            CsgOp::CircularPattern { .. } => "CircularPattern",
+            CsgOp::Shell { .. } => "Shell",
+            CsgOp::Fillet { .. } => "Fillet",
+            CsgOp::Chamfer { .. } => "Chamfer",
+            CsgOp::Text2D { .. } => "Text2D",
+            CsgOp::Text { .. } => "Text",
+            CsgOp::ProjectToSketch { .. } => "ProjectToSketch",
+            CsgOp::SvgProfile { .. } => "SvgProfile",
+            CsgOp::Tag { .. } => "Tag",
+            CsgOp::SplitByPlane { .. } => "SplitByPlane",
+            CsgOp::StepImport { .. } => "step_import",
+        }
+    }
+
+    /// Whether this is a solid primitive: a cube, cylinder, sphere or cone.
+    pub fn is_primitive(&self) -> bool {
+        matches!(
+            self,
+            CsgOp::Cube { .. } | CsgOp::Cylinder { .. } | CsgOp::Sphere { .. } | CsgOp::Cone { .. }
+        )
+    }
+}
+
 /// A node in the IR graph.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Node {
@@ -428,6 +857,11 @@ pub struct Node {
     pub name: Option<String>,
     /// The operation this node represents.
     pub op: CsgOp,
+    /// Free-form metadata for tools built on vcad, such as a source line or
+    /// lock state. Ignored by evaluation and not carried by the compact
+    /// format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<HashMap<String, String>>,
 }
 
 /// PBR material definition.
@@ -445,6 +879,57 @@ pub struct MaterialDef {
     pub density: Option<f64>,
     /// Static friction coefficient (for physics simulation).
     pub friction: Option<f64>,
+    /// Opacity in 0.0..1.0 (absent means fully opaque).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opacity: Option<f64>,
+    /// Emissive color as `[r, g, b]` in 0.0..1.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emissive: Option<[f64; 3]>,
+    /// Path or URI of a base color texture image.
+    #[serde(
+        rename = "baseColorTexture",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub base_color_texture: Option<String>,
+}
+
+impl MaterialDef {
+    /// Effective opacity, defaulting to 1.0 when unset.
+    pub fn opacity(&self) -> f64 {
+        self.opacity.unwrap_or(1.0)
+    }
+
+    /// A built-in material by common name, e.g. `"steel"` or `"ABS"`.
+    ///
+    /// Lookup ignores case and surrounding whitespace, and accepts the
+    /// British spelling "aluminium". The returned material is named with the
+    /// canonical key. Available presets: aluminum, steel, brass, copper,
+    /// titanium, abs and pla. Friction is a typical dry static coefficient.
+    pub fn preset(name: &str) -> Option<MaterialDef> {
+        let key = name.trim().to_ascii_lowercase();
+        let (name, color, metallic, roughness, density, friction) = match key.as_str() {
+            "aluminum" | "aluminium" => ("aluminum", [0.8, 0.8, 0.85], 0.9, 0.3, 2700.0, 0.61),
+            "steel" => ("steel", [0.7, 0.7, 0.72], 0.95, 0.25, 7850.0, 0.74),
+            "brass" => ("brass", [0.85, 0.65, 0.3], 0.9, 0.35, 8500.0, 0.51),
+            "copper" => ("copper", [0.95, 0.5, 0.35], 0.95, 0.25, 8960.0, 0.53),
+            "titanium" => ("titanium", [0.6, 0.6, 0.65], 0.85, 0.4, 4500.0, 0.36),
+            "abs" => ("abs", [0.95, 0.95, 0.93], 0.0, 0.5, 1050.0, 0.5),
+            "pla" => ("pla", [0.85, 0.85, 0.8], 0.0, 0.45, 1240.0, 0.4),
+            _ => return None,
+        };
+        Some(MaterialDef {
+            name: name.to_string(),
+            color,
+            metallic,
+            roughness,
+            density: Some(density),
+            friction: Some(friction),
+            opacity: None,
+            emissive: None,
+            base_color_texture: None,
+        })
+    }
 }
 
 /// An entry in the scene — a root node with an assigned material.
@@ -711,12 +1196,26 @@ pub struct SceneSettings {
     pub camera_presets: Option<Vec<CameraPreset>>,
 }
 
+/// Reverse references of a document's nodes, from [`Document::parent_map`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParentMap {
+    parents: HashMap<NodeId, Vec<NodeId>>,
+}
+
+impl ParentMap {
+    /// The nodes that reference `id`, in ascending id order without
+    /// duplicates.
+    pub fn parents_of(&self, id: NodeId) -> &[NodeId] {
+        self.parents.get(&id).map_or(&[], Vec::as_slice)
+    }
+}
+
 /// A vcad document — the `.vcad` file format.
 ///
 /// Contains the full IR DAG, material definitions, and scene assembly.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Document {
-    /// Format version string (e.g. "0.1").
+    /// Format version string (see [`CURRENT_VERSION`]).
     pub version: String,
     /// All nodes in the graph, keyed by [`NodeId`].
     pub nodes: HashMap<NodeId, Node>,
@@ -745,12 +1244,21 @@ pub struct Document {
     /// The instance that is fixed in world space (ground).
     #[serde(rename = "groundInstanceId", skip_serializing_if = "Option::is_none")]
     pub ground_instance_id: Option<String>,
+
+    // Parametric fields (optional, see [`Document::resolve_parameters`])
+    /// Named values that expressions can refer to.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameters: HashMap<String, f64>,
+    /// Expressions driving numeric fields of nodes: node → field path →
+    /// expression text, e.g. `{"3": {"size.x": "width / 2"}}`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub expressions: HashMap<NodeId, BTreeMap<String, String>>,
 }
 
 impl Default for Document {
     fn default() -> Self {
         Self {
-            version: "0.1".to_string(),
+            version: CURRENT_VERSION.to_string(),
             nodes: HashMap::new(),
             materials: HashMap::new(),
             part_materials: HashMap::new(),
@@ -760,10 +1268,27 @@ impl Default for Document {
             instances: None,
             joints: None,
             ground_instance_id: None,
+            parameters: HashMap::new(),
+            expressions: HashMap::new(),
         }
     }
 }
 
+/// Error returned when the node graph is not acyclic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleError {
+    /// A node that (transitively) references itself.
+    pub node: NodeId,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle detected at node {}", self.node)
+    }
+}
+
+impl std::error::Error for CycleError {}
+
 impl Document {
     /// Create a new empty document.
     pub fn new() -> Self {
@@ -776,15 +1301,254 @@ impl Document {
     }
 
     /// Deserialize from JSON string.
+    ///
+    /// The document is returned as written; use
+    /// [`Document::from_json_migrated`] to upgrade older versions. For very
+    /// large documents, see [`Document::load_header`] and
+    /// [`Document::load_subtree`].
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(json)
     }
+
+    /// Add the built-in material `name` (see [`MaterialDef::preset`]) to
+    /// [`Document::materials`] under its canonical key.
+    ///
+    /// An existing material with that key is left untouched, so adding a
+    /// preset twice is harmless. Returns the stored material, or `None` if
+    /// there is no such preset.
+    pub fn add_material_preset(&mut self, name: &str) -> Option<&MaterialDef> {
+        let preset = MaterialDef::preset(name)?;
+        Some(self.materials.entry(preset.name.clone()).or_insert(preset))
+    }
+
+    /// All nodes ordered so that children come before their parents.
+    ///
+    /// Each node appears once, even when shared by several parents. Traversal
+    /// starts from nodes no other node references, in ascending id order,
+    /// visiting children left to right. References to missing nodes are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CycleError`] if a node is its own (transitive) descendant.
+    pub fn topological_order(&self) -> Result<Vec<NodeId>, CycleError> {
+        let referenced: HashSet<NodeId> = self
+            .nodes
+            .values()
+            .flat_map(|n| compact::get_children(&n.op))
+            .collect();
+        let mut ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+        // Unreferenced nodes first; the second pass only picks up nodes that
+        // are reachable solely through a cycle, and so reports it.
+        let starts = ids
+            .iter()
+            .filter(|id| !referenced.contains(id))
+            .chain(&ids)
+            .copied();
+        self.postorder(starts)
+    }
+
+    /// Like [`Document::topological_order`], but only including nodes
+    /// reachable from the scene [`roots`](Document::roots), in root order.
+    pub fn evaluation_order(&self) -> Result<Vec<NodeId>, CycleError> {
+        self.postorder(self.roots.iter().map(|entry| entry.root))
+    }
+
+    /// Depth-first post-order over the subgraphs below `starts`.
+    fn postorder(
+        &self,
+        starts: impl IntoIterator<Item = NodeId>,
+    ) -> Result<Vec<NodeId>, CycleError> {
+        // Children are popped from the back, so reverse them to visit the
+        // left-most child first.
+        let children = |id: NodeId| -> Vec<NodeId> {
+            let mut children = compact::get_children(&self.nodes[&id].op);
+            children.reverse();
+            children
+        };
+
+        let mut order = Vec::new();
+        let mut done = HashSet::new();
+        let mut on_path = HashSet::new();
+        for start in starts {
+            if done.contains(&start) || !self.nodes.contains_key(&start) {
+                continue;
+            }
+            on_path.insert(start);
+            let mut stack = vec![(start, children(start))];
+            while let Some((id, pending)) = stack.last_mut() {
+                if let Some(child) = pending.pop() {
+                    if done.contains(&child) || !self.nodes.contains_key(&child) {
+                        continue;
+                    }
+                    if !on_path.insert(child) {
+                        return Err(CycleError { node: child });
+                    }
+                    stack.push((child, children(child)));
+                } else {
+                    let id = *id;
+                    stack.pop();
+                    on_path.remove(&id);
+                    done.insert(id);
+                    order.push(id);
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// The `(key, value)` pairs of all [`CsgOp::Tag`] nodes in the subtree
+    /// rooted at `node_id`, outermost first.
+    ///
+    /// Nodes shared by several branches are visited once.
+    pub fn subtree_tags(&self, node_id: NodeId) -> Vec<(&str, &str)> {
+        let mut tags = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![node_id];
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let Some(node) = self.nodes.get(&id) else {
+                continue;
+            };
+            if let CsgOp::Tag { key, value, .. } = &node.op {
+                tags.push((key.as_str(), value.as_str()));
+            }
+            // Reverse so the left-most child is visited first.
+            stack.extend(compact::get_children(&node.op).into_iter().rev());
+        }
+        tags
+    }
+
+    // ------------------------------------------------------------------
+    // Lookup
+    // ------------------------------------------------------------------
+
+    /// The node named `name`, or `None` if there is none.
+    ///
+    /// Names need not be unique; if several nodes share one, the one with
+    /// the lowest id is returned. See [`Document::nodes_by_name`].
+    pub fn node_by_name(&self, name: &str) -> Option<&Node> {
+        self.nodes_by_name(name).into_iter().next()
+    }
+
+    /// All nodes named `name`, in ascending id order.
+    pub fn nodes_by_name(&self, name: &str) -> Vec<&Node> {
+        let mut nodes: Vec<&Node> = self
+            .nodes
+            .values()
+            .filter(|n| n.name.as_deref() == Some(name))
+            .collect();
+        nodes.sort_by_key(|n| n.id);
+        nodes
+    }
+
+    /// The first scene entry whose root node is named `name`.
+    pub fn root_named(&self, name: &str) -> Option<&SceneEntry> {
+        self.roots.iter().find(|entry| {
+            self.nodes
+                .get(&entry.root)
+                .is_some_and(|n| n.name.as_deref() == Some(name))
+        })
+    }
+
+    /// The nodes `id` references, left to right. Empty for leaves and
+    /// missing nodes.
+    pub fn children_of(&self, id: NodeId) -> Vec<NodeId> {
+        self.nodes
+            .get(&id)
+            .map(|n| compact::get_children(&n.op))
+            .unwrap_or_default()
+    }
+
+    /// The nodes that reference `id`, in ascending id order.
+    ///
+    /// This scans the whole graph; for repeated queries build the index
+    /// once with [`Document::parent_map`].
+    pub fn parents_of(&self, id: NodeId) -> Vec<NodeId> {
+        let mut parents: Vec<NodeId> = self
+            .nodes
+            .values()
+            .filter(|node| compact::get_children(&node.op).contains(&id))
+            .map(|node| node.id)
+            .collect();
+        parents.sort_unstable();
+        parents
+    }
+
+    /// Reverse references of every node, built in one pass over the graph.
+    ///
+    /// The map is a snapshot; rebuild it after editing [`Document::nodes`].
+    pub fn parent_map(&self) -> ParentMap {
+        let mut parents: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for node in self.nodes.values() {
+            for child in compact::get_children(&node.op) {
+                parents.entry(child).or_default().push(node.id);
+            }
+        }
+        for list in parents.values_mut() {
+            list.sort_unstable();
+            list.dedup();
+        }
+        ParentMap { parents }
+    }
+
+    // ------------------------------------------------------------------
+    // Cleanup
+    // ------------------------------------------------------------------
+
+    /// Remove nodes that no scene root or part definition reaches, along
+    /// with their expressions.
+    ///
+    /// Edits leave such nodes behind: removing a scene entry drops the
+    /// entry but not the graph below it. Instances refer to part
+    /// definitions rather than nodes, so they keep nothing alive by
+    /// themselves.
+    ///
+    /// Returns the number of nodes removed.
+    pub fn gc(&mut self) -> usize {
+        let mut stack: Vec<NodeId> = self.roots.iter().map(|entry| entry.root).collect();
+        for def in self.part_defs.iter().flat_map(|defs| defs.values()) {
+            stack.push(def.root);
+            stack.extend(def.collision_root);
+        }
+
+        let mut reachable = HashSet::new();
+        while let Some(id) = stack.pop() {
+            if reachable.insert(id) {
+                stack.extend(self.children_of(id));
+            }
+        }
+
+        let before = self.nodes.len();
+        self.nodes.retain(|id, _| reachable.contains(id));
+        self.expressions.retain(|id, _| reachable.contains(id));
+        before - self.nodes.len()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn quantize_merges_nearby_points() {
+        let a = Vec3::new(1.0, -2.5, 3.25);
+        let b = Vec3::new(1.0 + 1e-9, -2.5 - 1e-9, 3.25);
+        assert_ne!(a, b);
+        assert_eq!(QuantizedVec3::new(a, 1e6), QuantizedVec3::new(b, 1e6));
+        assert_ne!(
+            QuantizedVec3::new(a, 1e6),
+            QuantizedVec3::new(Vec3::new(1.0 + 1e-5, -2.5, 3.25), 1e6)
+        );
+        assert_eq!(
+            Vec2::new(0.5, 0.5).quantize(1e6),
+            Vec2::new(0.5 + 1e-9, 0.5).quantize(1e6)
+        );
+    }
+
     #[test]
     fn roundtrip_document() {
         let mut doc = Document::new();
@@ -798,7 +1562,9 @@ mod tests {
                 name: Some("box".to_string()),
                 op: CsgOp::Cube {
                     size: Vec3::new(10.0, 20.0, 30.0),
+                    origin: BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
 
@@ -814,6 +1580,7 @@ mod tests {
                     height: 40.0,
                     segments: 0,
                 },
+                attributes: None,
             },
         );
 
@@ -828,6 +1595,7 @@ mod tests {
                     left: cube_id,
                     right: cyl_id,
                 },
+                attributes: None,
             },
         );
 
@@ -841,6 +1609,9 @@ mod tests {
                 roughness: 0.4,
                 density: Some(2700.0),
                 friction: Some(0.6),
+                opacity: None,
+                emissive: None,
+                base_color_texture: None,
             },
         );
 
@@ -874,6 +1645,7 @@ mod tests {
                     radius: 5.0,
                     segments: 0,
                 },
+                attributes: None,
             },
         );
 
@@ -884,7 +1656,9 @@ mod tests {
                 name: None,
                 op: CsgOp::Cube {
                     size: Vec3::new(8.0, 8.0, 8.0),
+                    origin: BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
 
@@ -894,6 +1668,7 @@ mod tests {
                 id: 3,
                 name: Some("rounded_cube".to_string()),
                 op: CsgOp::Intersection { left: 1, right: 2 },
+                attributes: None,
             },
         );
 
@@ -911,17 +1686,66 @@ mod tests {
     #[test]
     fn empty_document() {
         let doc = Document::new();
-        assert_eq!(doc.version, "0.1");
+        assert_eq!(doc.version, CURRENT_VERSION);
         assert!(doc.nodes.is_empty());
         assert!(doc.materials.is_empty());
         assert!(doc.part_materials.is_empty());
         assert!(doc.roots.is_empty());
     }
 
+    #[test]
+    fn tag_json_roundtrip_and_subtree_tags() {
+        let mut doc = Document::new();
+        let ops = [
+            CsgOp::Cube {
+                size: Vec3::new(1.0, 1.0, 1.0),
+                origin: BoxOrigin::Corner,
+            },
+            CsgOp::Tag {
+                child: 0,
+                key: "color".to_string(),
+                value: "#ff0000".to_string(),
+            },
+            CsgOp::Sphere {
+                radius: 1.0,
+                segments: 0,
+            },
+            CsgOp::Union { left: 1, right: 2 },
+            CsgOp::Tag {
+                child: 3,
+                key: "step".to_string(),
+                value: "fuse".to_string(),
+            },
+        ];
+        for (id, op) in ops.into_iter().enumerate() {
+            let id = id as NodeId;
+            doc.nodes.insert(
+                id,
+                Node {
+                    id,
+                    name: None,
+                    op,
+                    attributes: None,
+                },
+            );
+        }
+
+        let restored = Document::from_json(&doc.to_json().unwrap()).unwrap();
+        assert_eq!(restored.nodes[&1].op, doc.nodes[&1].op);
+
+        assert_eq!(
+            doc.subtree_tags(4),
+            vec![("step", "fuse"), ("color", "#ff0000")]
+        );
+        assert_eq!(doc.subtree_tags(1), vec![("color", "#ff0000")]);
+        assert!(doc.subtree_tags(2).is_empty());
+    }
+
     #[test]
     fn serde_tagged_enum() {
         let op = CsgOp::Cube {
             size: Vec3::new(1.0, 2.0, 3.0),
+            origin: BoxOrigin::Corner,
         };
         let json = serde_json::to_string(&op).unwrap();
         assert!(json.contains(r#""type":"Cube""#));
@@ -930,6 +1754,92 @@ mod tests {
         assert_eq!(op, restored);
     }
 
+    #[test]
+    fn cube_origin_defaults_to_corner() {
+        // Documents written before the origin flag keep their placement
+        let legacy = r#"{"type":"Cube","size":{"x":1.0,"y":2.0,"z":3.0}}"#;
+        let op: CsgOp = serde_json::from_str(legacy).unwrap();
+        assert!(matches!(
+            op,
+            CsgOp::Cube {
+                origin: BoxOrigin::Corner,
+                ..
+            }
+        ));
+        assert!(!serde_json::to_string(&op).unwrap().contains("origin"));
+
+        let centered = CsgOp::Cube {
+            size: Vec3::new(1.0, 2.0, 3.0),
+            origin: BoxOrigin::Center,
+        };
+        let json = serde_json::to_string(&centered).unwrap();
+        assert!(json.contains(r#""origin":"center""#));
+        assert_eq!(serde_json::from_str::<CsgOp>(&json).unwrap(), centered);
+    }
+
+    #[test]
+    fn text_json_roundtrip() {
+        let mut doc = Document::new();
+        let op = CsgOp::Text {
+            content: "AB".to_string(),
+            font: "sans-serif".to_string(),
+            height: 10.0,
+            depth: 1.5,
+            plane_origin: Vec3::new(0.0, 0.0, 5.0),
+            x_dir: Vec3::new(1.0, 0.0, 0.0),
+            y_dir: Vec3::new(0.0, 1.0, 0.0),
+            alignment: TextAlignment::Center,
+        };
+        doc.nodes.insert(
+            0,
+            Node {
+                id: 0,
+                name: Some("Label".to_string()),
+                op,
+                attributes: None,
+            },
+        );
+
+        let json = doc.to_json().unwrap();
+        assert!(json.contains(r#""type": "Text""#));
+        let restored = Document::from_json(&json).unwrap();
+        assert_eq!(restored.nodes, doc.nodes);
+    }
+
+    #[test]
+    fn node_attributes_roundtrip() {
+        let mut doc = compact::from_compact("C 10 10 10 \"Block\"\nROOT 0 default").unwrap();
+        assert_eq!(doc.nodes[&0].attributes, None);
+        let json = doc.to_json().unwrap();
+        assert!(!json.contains("attributes"));
+
+        let attributes = HashMap::from([
+            ("source_line".to_string(), "42".to_string()),
+            ("locked".to_string(), "true".to_string()),
+        ]);
+        doc.nodes.get_mut(&0).unwrap().attributes = Some(attributes.clone());
+        let restored = Document::from_json(&doc.to_json().unwrap()).unwrap();
+        assert_eq!(restored.nodes[&0].attributes, Some(attributes));
+
+        // Compact has no syntax for attributes, so they are dropped
+        let compacted = compact::from_compact(&compact::to_compact(&doc).unwrap()).unwrap();
+        assert_eq!(compacted.nodes[&0].attributes, None);
+        assert_eq!(compacted.nodes[&0].op, doc.nodes[&0].op);
+
+        // Documents written before the field existed still load
+        let legacy = r#"{
+            "version": "0.1",
+            "nodes": {
+                "1": { "id": 1, "name": "box", "op": { "type": "Sphere", "radius": 2.0, "segments": 0 } }
+            },
+            "materials": {},
+            "part_materials": {},
+            "roots": []
+        }"#;
+        let doc = Document::from_json(legacy).unwrap();
+        assert_eq!(doc.nodes[&1].attributes, None);
+    }
+
     #[test]
     fn sketch_operations() {
         let mut doc = Document::new();
@@ -963,7 +1873,9 @@ mod tests {
                             end: Vec2::new(0.0, 0.0),
                         },
                     ],
+                    holes: Vec::new(),
                 },
+                attributes: None,
             },
         );
 
@@ -980,6 +1892,7 @@ mod tests {
                     twist_angle: None,
                     scale_end: None,
                 },
+                attributes: None,
             },
         );
 
@@ -1018,6 +1931,61 @@ mod tests {
         assert_eq!(op, restored);
     }
 
+    #[test]
+    fn loft_operation() {
+        let op = CsgOp::Loft {
+            profiles: vec![1, 2],
+            mode: LoftMode::Ruled,
+            closed: false,
+        };
+        let json = serde_json::to_string(&op).unwrap();
+        assert!(json.contains(r#""type":"Loft""#));
+        assert!(json.contains(r#""mode":"ruled""#));
+        let restored: CsgOp = serde_json::from_str(&json).unwrap();
+        assert_eq!(op, restored);
+
+        // Mode defaults to ruled
+        let minimal: CsgOp = serde_json::from_str(r#"{"type":"Loft","profiles":[1,2]}"#).unwrap();
+        assert_eq!(minimal, op);
+        assert!(!json.contains("closed"));
+    }
+
+    #[test]
+    fn sweep_operation() {
+        let op = CsgOp::Sweep {
+            profile: 1,
+            path: SweepPath::Helix {
+                radius: 10.0,
+                pitch: 2.0,
+                height: 20.0,
+                turns: 10.0,
+            },
+            options: SweepOptions {
+                twist_angle: Some(0.5),
+                orientation: Some(0.25),
+                on_degenerate: Some(DegeneratePolicy::Interpolate),
+                ..Default::default()
+            },
+        };
+        let json = serde_json::to_string(&op).unwrap();
+        assert!(json.contains(r#""type":"Sweep""#));
+        assert!(json.contains(r#""type":"Helix""#));
+        assert!(!json.contains("scale_end"));
+        assert!(json.contains(
+            r#""options":{"twist_angle":0.5,"orientation":0.25,"on_degenerate":"interpolate"}"#
+        ));
+        let restored: CsgOp = serde_json::from_str(&json).unwrap();
+        assert_eq!(op, restored);
+
+        assert_eq!(compact::get_children(&op), vec![1]);
+        let loft = CsgOp::Loft {
+            profiles: vec![3, 4, 5],
+            mode: LoftMode::Smooth,
+            closed: true,
+        };
+        assert_eq!(compact::get_children(&loft), vec![3, 4, 5]);
+    }
+
     #[test]
     fn assembly_document_roundtrip() {
         let mut doc = Document::new();
@@ -1031,7 +1999,9 @@ mod tests {
                 name: Some("base_cube".to_string()),
                 op: CsgOp::Cube {
                     size: Vec3::new(10.0, 10.0, 10.0),
+                    origin: BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
 
@@ -1047,6 +2017,7 @@ mod tests {
                     height: 20.0,
                     segments: 0,
                 },
+                attributes: None,
             },
         );
 
@@ -1058,6 +2029,8 @@ mod tests {
                 id: "base".to_string(),
                 name: Some("Base Plate".to_string()),
                 root: cube_id,
+                collision_root: None,
+                mass_properties: None,
                 default_material: Some("aluminum".to_string()),
             },
         );
@@ -1067,6 +2040,8 @@ mod tests {
                 id: "arm".to_string(),
                 name: Some("Arm".to_string()),
                 root: cyl_id,
+                collision_root: None,
+                mass_properties: None,
                 default_material: None,
             },
         );
@@ -1218,4 +2193,226 @@ mod tests {
         assert!(!json.contains(r#""joints""#));
         assert!(!json.contains(r#""groundInstanceId""#));
     }
+
+    #[test]
+    fn material_opacity_roundtrip() {
+        let glass = MaterialDef {
+            name: "smoked_glass".to_string(),
+            color: [0.2, 0.2, 0.25],
+            metallic: 0.0,
+            roughness: 0.05,
+            density: None,
+            friction: None,
+            opacity: Some(0.5),
+            emissive: Some([0.0, 0.1, 0.0]),
+            base_color_texture: Some("textures/glass.png".to_string()),
+        };
+        let json = serde_json::to_string(&glass).unwrap();
+        assert!(json.contains(r#""baseColorTexture""#));
+        let restored: MaterialDef = serde_json::from_str(&json).unwrap();
+        assert_eq!(glass, restored);
+        assert_eq!(restored.opacity(), 0.5);
+
+        // Files written before these fields existed still load as opaque.
+        let legacy = r#"{"name":"abs","color":[1,1,1],"metallic":0,"roughness":0.6,"density":1050,"friction":null}"#;
+        let abs: MaterialDef = serde_json::from_str(legacy).unwrap();
+        assert_eq!(abs.opacity, None);
+        assert_eq!(abs.opacity(), 1.0);
+        assert_eq!(abs.emissive, None);
+        assert_eq!(abs.base_color_texture, None);
+    }
+
+    #[test]
+    fn sketch_arc_length_and_sampling() {
+        let quarter = SketchSegment2D::Arc {
+            start: Vec2::new(10.0, 0.0),
+            end: Vec2::new(0.0, 10.0),
+            center: Vec2::new(0.0, 0.0),
+            ccw: true,
+        };
+        assert!((quarter.length() - 15.708).abs() < 1e-3);
+        let mid = quarter.point_at(0.5);
+        let d = 10.0 * std::f64::consts::FRAC_1_SQRT_2;
+        assert!((mid.x - d).abs() < 1e-9 && (mid.y - d).abs() < 1e-9);
+        let tangent = quarter.tangent_at(0.0);
+        assert!(tangent.x.abs() < 1e-9 && (tangent.y - 1.0).abs() < 1e-9);
+
+        // The clockwise arc between the same points takes the long way round.
+        let long_way = SketchSegment2D::Arc {
+            start: Vec2::new(10.0, 0.0),
+            end: Vec2::new(0.0, 10.0),
+            center: Vec2::new(0.0, 0.0),
+            ccw: false,
+        };
+        assert!((long_way.length() - 15.0 * std::f64::consts::PI).abs() < 1e-9);
+        let mid = long_way.point_at(0.5);
+        assert!((mid.x + d).abs() < 1e-9 && (mid.y + d).abs() < 1e-9);
+        assert!((long_way.tangent_at(0.0).y + 1.0).abs() < 1e-9);
+
+        let full = SketchSegment2D::Arc {
+            start: Vec2::new(5.0, 0.0),
+            end: Vec2::new(5.0, 0.0),
+            center: Vec2::new(0.0, 0.0),
+            ccw: true,
+        };
+        assert!((full.length() - 10.0 * std::f64::consts::PI).abs() < 1e-9);
+        assert!((full.point_at(0.5).x + 5.0).abs() < 1e-9);
+
+        let line = SketchSegment2D::Line {
+            start: Vec2::new(0.0, 0.0),
+            end: Vec2::new(3.0, 4.0),
+        };
+        assert_eq!(line.length(), 5.0);
+        assert_eq!(line.point_at(0.5), Vec2::new(1.5, 2.0));
+        assert_eq!(line.tangent_at(0.3), Vec2::new(0.6, 0.8));
+    }
+
+    #[test]
+    fn material_presets() {
+        let steel = MaterialDef::preset("steel").unwrap();
+        assert!((steel.density.unwrap() - 7850.0).abs() < 1e-9);
+        assert_eq!(MaterialDef::preset(" Aluminium ").unwrap().name, "aluminum");
+        assert!(MaterialDef::preset("unobtainium").is_none());
+
+        let mut doc = Document::new();
+        assert!(doc.add_material_preset("steel").is_some());
+        assert!(doc.add_material_preset("STEEL").is_some());
+        assert!(doc.add_material_preset("unobtainium").is_none());
+        assert_eq!(doc.materials.len(), 1);
+        assert_eq!(doc.materials["steel"], steel);
+    }
+
+    fn graph(edges: &[(NodeId, NodeId, NodeId)], leaves: &[NodeId]) -> Document {
+        let mut doc = Document::new();
+        let cube = |id| Node {
+            id,
+            name: None,
+            op: CsgOp::Cube {
+                size: Vec3::new(1.0, 1.0, 1.0),
+                origin: BoxOrigin::Corner,
+            },
+            attributes: None,
+        };
+        for &id in leaves {
+            doc.nodes.insert(id, cube(id));
+        }
+        for &(id, left, right) in edges {
+            doc.nodes.insert(
+                id,
+                Node {
+                    id,
+                    name: None,
+                    op: CsgOp::Union { left, right },
+                    attributes: None,
+                },
+            );
+        }
+        doc
+    }
+
+    #[test]
+    fn topological_order_diamond() {
+        // 4 = 2 ∪ 3, both of which use the shared leaf 1; 5 is unrelated.
+        let mut doc = graph(&[(4, 2, 3), (2, 1, 5), (3, 1, 1)], &[1, 5, 6]);
+        doc.roots.push(SceneEntry {
+            root: 3,
+            material: "default".to_string(),
+            visible: None,
+        });
+
+        let order = doc.topological_order().unwrap();
+        assert_eq!(order, vec![1, 5, 2, 3, 4, 6]);
+
+        // Only the subgraph under the scene root.
+        assert_eq!(doc.evaluation_order().unwrap(), vec![1, 3]);
+    }
+
+    #[test]
+    fn topological_order_cycle() {
+        // 1 -> 2 -> 3 -> 1, reachable from the top node 4.
+        let doc = graph(&[(4, 1, 5), (1, 2, 5), (2, 3, 5), (3, 1, 5)], &[5]);
+        let err = doc.topological_order().unwrap_err();
+        assert!([1, 2, 3].contains(&err.node));
+
+        // A cycle with no entry point is still found.
+        let doc = graph(&[(1, 2, 2), (2, 1, 1)], &[]);
+        assert!(doc.topological_order().is_err());
+        assert!(compact::to_compact(&doc)
+            .unwrap_err()
+            .message
+            .contains("cycle"));
+    }
+
+    #[test]
+    fn lookup_by_name_and_references() {
+        let doc = compact::from_compact(
+            "M default 0.8 0.8 0.8 0 0.5\n\
+             C 50 30 5 \"Base Plate\"\n\
+             Y 5 10 \"Hole\"\n\
+             T 1 25 15 0\n\
+             D 0 2 \"Plate with Hole\"\n\
+             C 1 1 1 \"Hole\"\n\
+             ROOT 3 default",
+        )
+        .unwrap();
+
+        assert_eq!(doc.node_by_name("Base Plate").unwrap().id, 0);
+        assert_eq!(doc.node_by_name("Hole").unwrap().id, 1);
+        assert!(doc.node_by_name("Missing").is_none());
+        let holes: Vec<NodeId> = doc.nodes_by_name("Hole").iter().map(|n| n.id).collect();
+        assert_eq!(holes, vec![1, 4]);
+        assert_eq!(doc.root_named("Plate with Hole").unwrap().root, 3);
+        assert!(doc.root_named("Base Plate").is_none());
+
+        assert_eq!(doc.children_of(3), vec![0, 2]);
+        assert!(doc.children_of(1).is_empty());
+        assert_eq!(doc.parents_of(1), vec![2]);
+        assert_eq!(doc.parents_of(2), vec![3]);
+        assert!(doc.parents_of(3).is_empty());
+
+        let parents = doc.parent_map();
+        for id in 0..5 {
+            assert_eq!(parents.parents_of(id), doc.parents_of(id));
+        }
+    }
+
+    #[test]
+    fn gc_removes_unreachable_nodes() {
+        let mut doc = compact::from_compact(
+            "C 10 10 10 \"Block\"\n\
+             T 0 5 0 0\n\
+             R 1 0 0 45\n\
+             S 4 \"Ball\"\n\
+             T 3 0 20 0\n\
+             Y 1 5 \"Pin\"\n\
+             ROOT 2 default\n\
+             ROOT 4 default",
+        )
+        .unwrap();
+        doc.part_defs = Some(HashMap::from([(
+            "pin".to_string(),
+            PartDef {
+                id: "pin".to_string(),
+                name: None,
+                root: 5,
+                collision_root: None,
+                mass_properties: None,
+                default_material: None,
+            },
+        )]));
+        doc.expressions
+            .insert(1, BTreeMap::from([("offset.x".into(), "5".into())]));
+        assert_eq!(doc.gc(), 0);
+
+        // Deleting the block's part leaves its transforms behind
+        let kept: Vec<Node> = [3, 4, 5].map(|id| doc.nodes[&id].clone()).into();
+        doc.roots.retain(|entry| entry.root != 2);
+        assert_eq!(doc.gc(), 3);
+        let mut ids: Vec<NodeId> = doc.nodes.keys().copied().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![3, 4, 5]);
+        assert_eq!(kept, [3, 4, 5].map(|id| doc.nodes[&id].clone()));
+        assert!(doc.expressions.is_empty());
+        assert_eq!(doc.gc(), 0);
+    }
 }
@@ -0,0 +1,179 @@
+//! Mass properties of multi-material documents.
+//!
+//! The IR can't evaluate geometry, so [`Document::mass_properties`] takes
+//! each part's unit-density [`VolumeProperties`] from the caller — usually
+//! integrated over the evaluated mesh — and weights them by the density of
+//! the part's material.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Document, SceneEntry, Vec3};
+
+/// Cubic millimeters to cubic meters, as material densities are in kg/m³.
+pub(crate) const MM3_TO_M3: f64 = 1e-9;
+
+/// Volume, centroid and inertia of a part at unit density.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VolumeProperties {
+    /// Volume in mm³.
+    pub volume: f64,
+    /// Centroid of the volume.
+    pub centroid: Vec3,
+    /// Inertia tensor about the centroid for unit density, in mm⁵.
+    pub inertia: [[f64; 3]; 3],
+}
+
+/// Mass, center of mass and inertia of one or more parts, from
+/// [`Document::mass_properties`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MassProperties {
+    /// Total mass in kg.
+    pub mass: f64,
+    /// Center of mass in mm. The origin when there is no mass.
+    pub center_of_mass: Vec3,
+    /// Inertia tensor about the center of mass, in kg·mm².
+    pub inertia: [[f64; 3]; 3],
+}
+
+impl MassProperties {
+    /// Mass properties of a part of uniform `density` (kg/m³).
+    pub fn from_volume(props: &VolumeProperties, density: f64) -> Self {
+        let scale = density * MM3_TO_M3;
+        Self {
+            mass: props.volume * scale,
+            center_of_mass: props.centroid,
+            inertia: props.inertia.map(|row| row.map(|v| v * scale)),
+        }
+    }
+
+    /// Combine the mass properties of several parts into those of the whole.
+    ///
+    /// Each part's inertia is moved to the combined center of mass with the
+    /// parallel-axis theorem before summing.
+    pub fn combine(parts: impl IntoIterator<Item = MassProperties>) -> Self {
+        let parts: Vec<_> = parts.into_iter().collect();
+        let mass: f64 = parts.iter().map(|p| p.mass).sum();
+        if mass <= 0.0 {
+            return Self {
+                mass: 0.0,
+                center_of_mass: Vec3::new(0.0, 0.0, 0.0),
+                inertia: [[0.0; 3]; 3],
+            };
+        }
+
+        let weighted = |f: fn(&Vec3) -> f64| {
+            parts
+                .iter()
+                .map(|p| p.mass * f(&p.center_of_mass))
+                .sum::<f64>()
+                / mass
+        };
+        let com = Vec3::new(weighted(|c| c.x), weighted(|c| c.y), weighted(|c| c.z));
+
+        let mut inertia = [[0.0; 3]; 3];
+        for part in &parts {
+            let c = &part.center_of_mass;
+            let d = [c.x - com.x, c.y - com.y, c.z - com.z];
+            let d2 = d[0] * d[0] + d[1] * d[1] + d[2] * d[2];
+            for i in 0..3 {
+                for j in 0..3 {
+                    let shift = if i == j {
+                        d2 - d[i] * d[j]
+                    } else {
+                        -d[i] * d[j]
+                    };
+                    inertia[i][j] += part.inertia[i][j] + part.mass * shift;
+                }
+            }
+        }
+
+        Self {
+            mass,
+            center_of_mass: com,
+            inertia,
+        }
+    }
+}
+
+impl Document {
+    /// Combined mass properties of the scene roots.
+    ///
+    /// `measure` gives the unit-density properties of a root's geometry, or
+    /// `None` if it has none. Each root is weighted by the density of its
+    /// material; roots whose material is missing or has no density are
+    /// skipped without being measured.
+    pub fn mass_properties(
+        &self,
+        mut measure: impl FnMut(&SceneEntry) -> Option<VolumeProperties>,
+    ) -> MassProperties {
+        let parts = self.roots.iter().filter_map(|entry| {
+            let density = self.materials.get(&entry.material)?.density?;
+            Some(MassProperties::from_volume(&measure(entry)?, density))
+        });
+        MassProperties::combine(parts.collect::<Vec<_>>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A solid box of the given size, cornered at `min`.
+    fn block(min: [f64; 3], size: [f64; 3]) -> VolumeProperties {
+        let [a, b, c] = size;
+        let volume = a * b * c;
+        let i = |p: f64, q: f64| volume * (p * p + q * q) / 12.0;
+        VolumeProperties {
+            volume,
+            centroid: Vec3::new(min[0] + a / 2.0, min[1] + b / 2.0, min[2] + c / 2.0),
+            inertia: [
+                [i(b, c), 0.0, 0.0],
+                [0.0, i(a, c), 0.0],
+                [0.0, 0.0, i(a, b)],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_combine_matches_whole() {
+        // Two halves of a 20×10×10 box give the inertia of the whole box
+        let halves = [
+            block([0.0; 3], [10.0; 3]),
+            block([10.0, 0.0, 0.0], [10.0; 3]),
+        ];
+        let combined =
+            MassProperties::combine(halves.iter().map(|h| MassProperties::from_volume(h, 1e9)));
+        let whole = MassProperties::from_volume(&block([0.0; 3], [20.0, 10.0, 10.0]), 1e9);
+
+        assert!((combined.mass - whole.mass).abs() < 1e-9);
+        assert!((combined.center_of_mass.x - 10.0).abs() < 1e-9);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((combined.inertia[i][j] - whole.inertia[i][j]).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_document_skips_parts_without_density() {
+        let mut doc = Document::new();
+        doc.add_material_preset("steel");
+        for (root, material) in [(0, "steel"), (1, "unknown")] {
+            doc.roots.push(SceneEntry {
+                root,
+                material: material.to_string(),
+                visible: None,
+            });
+        }
+        let mut measured = Vec::new();
+        let props = doc.mass_properties(|entry| {
+            measured.push(entry.root);
+            Some(block([0.0; 3], [10.0; 3]))
+        });
+        assert_eq!(measured, [0]);
+        // 1000 mm³ of steel at 7850 kg/m³
+        assert!((props.mass - 7.85e-3).abs() < 1e-12);
+
+        assert_eq!(Document::new().mass_properties(|_| None).mass, 0.0);
+    }
+}
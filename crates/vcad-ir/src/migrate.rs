@@ -0,0 +1,364 @@
+//! Schema versioning and document migration.
+//!
+//! Every `.vcad` file records the schema version it was written with.
+//! [`Document::migrate`] upgrades an older document in place by applying
+//! each migration step in order until it reaches [`CURRENT_VERSION`].
+//!
+//! # Versions
+//!
+//! - `0.1`: initial format.
+//! - `0.2`: sweeps and lofts use the shape the Rust IR reads, and identity
+//!   extrusion modifiers are omitted. Version 0.1 TypeScript writers stored
+//!   a sweep's profile as `sketch` with its options as flat fields, and a
+//!   loft's profiles as `sketches`. They also emitted `twist_angle: 0` and
+//!   `scale_end: 1` on plain extrusions, so tooling that checks for the
+//!   fields' presence treated them as twisted/tapered extrusions.
+//!
+//! Migrations run on the raw JSON, since older shapes need not parse as a
+//! current [`Document`]. Each step rewrites one node at a time, so the
+//! partial loaders in [`crate::partial`] can upgrade just the nodes they read.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::Document;
+
+/// The schema version written by this crate.
+pub const CURRENT_VERSION: &str = "0.2";
+
+/// Error returned when a document cannot be brought to [`CURRENT_VERSION`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    /// The document's version is not one this crate knows how to upgrade.
+    UnknownVersion(String),
+    /// The document was written by a newer version of vcad.
+    NewerVersion(String),
+    /// The JSON could not be parsed as a document.
+    Parse(String),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::UnknownVersion(v) => write!(f, "unknown document version '{}'", v),
+            MigrationError::NewerVersion(v) => write!(
+                f,
+                "document version '{}' is newer than supported version '{}'",
+                v, CURRENT_VERSION
+            ),
+            MigrationError::Parse(msg) => write!(f, "invalid document JSON: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// A single upgrade step from one version to the next, applied to the JSON
+/// of each node.
+struct Migration {
+    from: &'static str,
+    to: &'static str,
+    upgrade_node: fn(&mut Value),
+}
+
+/// All migration steps, oldest first. Each step's `to` is the next step's `from`.
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: "0.1",
+    to: "0.2",
+    upgrade_node: upgrade_0_1_op,
+}];
+
+/// Sweep options that 0.1 TypeScript writers stored directly on the op.
+const LEGACY_SWEEP_OPTIONS: [&str; 6] = [
+    "twist_angle",
+    "scale_start",
+    "scale_end",
+    "path_segments",
+    "arc_segments",
+    "orientation",
+];
+
+/// 0.1 → 0.2: bring a legacy sweep or loft to the current shape, or clear
+/// `twist_angle: 0` and `scale_end: 1` on an extrusion.
+fn upgrade_0_1_op(node: &mut Value) {
+    let Some(op) = node.get_mut("op").and_then(Value::as_object_mut) else {
+        return;
+    };
+    match op.get("type").and_then(Value::as_str) {
+        Some("Extrude") => {
+            if op.get("twist_angle").and_then(Value::as_f64) == Some(0.0) {
+                op.remove("twist_angle");
+            }
+            if op.get("scale_end").and_then(Value::as_f64) == Some(1.0) {
+                op.remove("scale_end");
+            }
+        }
+        Some("Sweep") => {
+            rename_field(op, "sketch", "profile");
+            let mut options = match op.remove("options") {
+                Some(Value::Object(options)) => options,
+                _ => Map::new(),
+            };
+            for key in LEGACY_SWEEP_OPTIONS {
+                match op.remove(key) {
+                    None | Some(Value::Null) => {}
+                    Some(value) => {
+                        options.entry(key).or_insert(value);
+                    }
+                }
+            }
+            if !options.is_empty() {
+                op.insert("options".to_string(), Value::Object(options));
+            }
+        }
+        Some("Loft") => rename_field(op, "sketches", "profiles"),
+        _ => {}
+    }
+}
+
+/// Move `op[from]` to `op[to]`, unless `to` is already set.
+fn rename_field(op: &mut Map<String, Value>, from: &str, to: &str) {
+    if op.contains_key(to) {
+        return;
+    }
+    if let Some(value) = op.remove(from) {
+        op.insert(to.to_string(), value);
+    }
+}
+
+/// Fill top-level fields that 0.1 writers sometimes left out.
+///
+/// This runs on raw JSON because the fields are required by [`Document`]'s
+/// deserializer.
+fn fill_legacy_fields(value: &mut serde_json::Value) {
+    let Some(obj) = value.as_object_mut() else {
+        return;
+    };
+    let version = obj
+        .entry("version")
+        .or_insert_with(|| serde_json::Value::from("0.1"));
+    if version.as_str() != Some("0.1") {
+        return;
+    }
+    for key in ["nodes", "materials", "part_materials"] {
+        obj.entry(key)
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+    }
+    obj.entry("roots")
+        .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+}
+
+/// Compare dotted numeric versions (`"0.10"` > `"0.2"`).
+fn version_key(version: &str) -> Option<Vec<u32>> {
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// The migration steps that bring a document at `version` to
+/// [`CURRENT_VERSION`]; empty if it is already current.
+fn migration_path(version: &str) -> Result<&'static [Migration], MigrationError> {
+    if version == CURRENT_VERSION {
+        return Ok(&[]);
+    }
+    let start = MIGRATIONS
+        .iter()
+        .position(|m| m.from == version)
+        .ok_or_else(
+            || match (version_key(version), version_key(CURRENT_VERSION)) {
+                (Some(v), Some(current)) if v > current => {
+                    MigrationError::NewerVersion(version.to_string())
+                }
+                _ => MigrationError::UnknownVersion(version.to_string()),
+            },
+        )?;
+    Ok(&MIGRATIONS[start..])
+}
+
+/// Check that a document at `version` can be brought to [`CURRENT_VERSION`].
+pub(crate) fn check_version(version: &str) -> Result<(), MigrationError> {
+    migration_path(version).map(|_| ())
+}
+
+/// Upgrade the JSON of a single node from a document at `version` to
+/// [`CURRENT_VERSION`].
+pub(crate) fn migrate_node(node: &mut Value, version: &str) -> Result<(), MigrationError> {
+    for migration in migration_path(version)? {
+        (migration.upgrade_node)(node);
+    }
+    Ok(())
+}
+
+/// Apply every migration step from the JSON document's `version` on,
+/// leaving `version` at [`CURRENT_VERSION`].
+fn migrate_value(value: &mut Value) -> Result<(), MigrationError> {
+    let version = match value.get("version") {
+        Some(Value::String(version)) => version.clone(),
+        _ => return Err(MigrationError::Parse("missing document version".into())),
+    };
+    for migration in migration_path(&version)? {
+        if let Some(nodes) = value.get_mut("nodes").and_then(Value::as_object_mut) {
+            nodes.values_mut().for_each(migration.upgrade_node);
+        }
+        value["version"] = Value::from(migration.to);
+    }
+    Ok(())
+}
+
+impl Document {
+    /// Upgrade this document to [`CURRENT_VERSION`] in place.
+    ///
+    /// Migration steps are applied in order starting from the document's
+    /// `version`. A document that is already current is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MigrationError::NewerVersion`] if the document is newer than
+    /// this crate, or [`MigrationError::UnknownVersion`] if its version has
+    /// no migration path. The document is unchanged on error.
+    pub fn migrate(&mut self) -> Result<(), MigrationError> {
+        if self.version == CURRENT_VERSION {
+            return Ok(());
+        }
+        let parse_error = |e: serde_json::Error| MigrationError::Parse(e.to_string());
+        let mut value = serde_json::to_value(&*self).map_err(parse_error)?;
+        migrate_value(&mut value)?;
+        *self = serde_json::from_value(value).map_err(parse_error)?;
+        Ok(())
+    }
+
+    /// Deserialize from a JSON string and upgrade to [`CURRENT_VERSION`].
+    ///
+    /// Unlike [`Document::from_json`], this also accepts 0.1 files that omit
+    /// top-level collections (treated as empty) or the `version` field
+    /// (treated as `"0.1"`).
+    pub fn from_json_migrated(json: &str) -> Result<Self, MigrationError> {
+        let mut value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| MigrationError::Parse(e.to_string()))?;
+        fill_legacy_fields(&mut value);
+        migrate_value(&mut value)?;
+        serde_json::from_value(value).map_err(|e| MigrationError::Parse(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CsgOp, LoftMode, SweepOptions};
+
+    const LEGACY_DOC: &str = r#"{
+        "version": "0.1",
+        "nodes": {
+            "1": {
+                "id": 1,
+                "name": null,
+                "op": {
+                    "type": "Sketch2D",
+                    "origin": {"x": 0, "y": 0, "z": 0},
+                    "x_dir": {"x": 1, "y": 0, "z": 0},
+                    "y_dir": {"x": 0, "y": 1, "z": 0},
+                    "segments": []
+                }
+            },
+            "2": {
+                "id": 2,
+                "name": "plate",
+                "op": {
+                    "type": "Extrude",
+                    "sketch": 1,
+                    "direction": {"x": 0, "y": 0, "z": 5},
+                    "twist_angle": 0.0,
+                    "scale_end": 1.0
+                }
+            }
+        },
+        "roots": [{"root": 2, "material": "default"}]
+    }"#;
+
+    #[test]
+    fn legacy_document_upgrades_cleanly() {
+        let doc = Document::from_json_migrated(LEGACY_DOC).unwrap();
+        assert_eq!(doc.version, CURRENT_VERSION);
+        assert!(doc.materials.is_empty());
+        match &doc.nodes[&2].op {
+            CsgOp::Extrude {
+                twist_angle,
+                scale_end,
+                ..
+            } => {
+                assert_eq!(*twist_angle, None);
+                assert_eq!(*scale_end, None);
+            }
+            other => panic!("expected Extrude, got {:?}", other),
+        }
+
+        // Already current: nothing to do.
+        let mut again = doc.clone();
+        again.migrate().unwrap();
+        assert_eq!(again, doc);
+    }
+
+    #[test]
+    fn legacy_sweep_and_loft_are_reshaped() {
+        let json = r#"{
+            "version": "0.1",
+            "nodes": {
+                "1": {"id": 1, "name": null, "op": {"type": "Sweep", "sketch": 0,
+                    "path": {"type": "Line", "start": {"x": 0, "y": 0, "z": 0},
+                             "end": {"x": 0, "y": 0, "z": 10}},
+                    "twist_angle": 0.5, "arc_segments": 12}},
+                "2": {"id": 2, "name": null,
+                    "op": {"type": "Loft", "sketches": [0, 3], "closed": true}},
+                "4": {"id": 4, "name": null, "op": {"type": "Sweep", "profile": 0,
+                    "path": {"type": "Helix", "radius": 5, "pitch": 2, "height": 10, "turns": 5}}}
+            }
+        }"#;
+        let doc = Document::from_json_migrated(json).unwrap();
+        assert_eq!(doc.version, CURRENT_VERSION);
+        match &doc.nodes[&1].op {
+            CsgOp::Sweep {
+                profile, options, ..
+            } => {
+                assert_eq!(*profile, 0);
+                assert_eq!(
+                    *options,
+                    SweepOptions {
+                        twist_angle: Some(0.5),
+                        arc_segments: Some(12),
+                        ..Default::default()
+                    }
+                );
+            }
+            other => panic!("expected Sweep, got {:?}", other),
+        }
+        assert_eq!(
+            doc.nodes[&2].op,
+            CsgOp::Loft {
+                profiles: vec![0, 3],
+                mode: LoftMode::Ruled,
+                closed: true,
+            }
+        );
+        // Already in the current shape: left alone
+        assert!(matches!(
+            &doc.nodes[&4].op,
+            CsgOp::Sweep { options, .. } if *options == SweepOptions::default()
+        ));
+    }
+
+    #[test]
+    fn unsupported_versions_are_rejected() {
+        let mut doc = Document::new();
+        doc.version = "9.0".to_string();
+        assert_eq!(
+            doc.migrate(),
+            Err(MigrationError::NewerVersion("9.0".to_string()))
+        );
+
+        doc.version = "beta".to_string();
+        assert_eq!(
+            doc.migrate(),
+            Err(MigrationError::UnknownVersion("beta".to_string()))
+        );
+        assert_eq!(doc.version, "beta");
+    }
+}
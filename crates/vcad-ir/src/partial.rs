@@ -0,0 +1,272 @@
+//! Partial loading of large documents.
+//!
+//! [`Document::from_json`] builds every node of the graph. Generated
+//! documents can hold hundreds of thousands of nodes, most of which a caller
+//! listing the scene or rendering one part never looks at.
+//! [`Document::load_header`] reads only the scene-level fields, and
+//! [`Document::load_subtree`] builds just the nodes one root depends on. Both
+//! skip over the rest of the node map without deserializing it, and both
+//! upgrade older documents to [`CURRENT_VERSION`] like
+//! [`Document::from_json_migrated`].
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::migrate::{check_version, migrate_node};
+use crate::{
+    compact, Document, MaterialDef, MigrationError, Node, NodeId, SceneEntry, SceneSettings,
+    CURRENT_VERSION,
+};
+
+/// The scene-level fields of a [`Document`], without the node graph.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentHeader {
+    /// Format version string.
+    pub version: String,
+    /// Material definitions, keyed by name.
+    pub materials: HashMap<String, MaterialDef>,
+    /// Per-part material assignments (part name → material name).
+    pub part_materials: HashMap<String, String>,
+    /// Scene entries.
+    pub roots: Vec<SceneEntry>,
+}
+
+/// A document whose nodes are left as unparsed JSON slices.
+#[derive(Deserialize)]
+struct RawDocument<'a> {
+    version: String,
+    #[serde(borrow)]
+    nodes: HashMap<NodeId, &'a RawValue>,
+    materials: HashMap<String, MaterialDef>,
+    part_materials: HashMap<String, String>,
+    roots: Vec<SceneEntry>,
+    #[serde(default)]
+    scene: Option<SceneSettings>,
+    #[serde(default)]
+    parameters: HashMap<String, f64>,
+    #[serde(default)]
+    expressions: HashMap<NodeId, BTreeMap<String, String>>,
+}
+
+impl Document {
+    /// Read the version, materials and scene roots of a JSON document.
+    ///
+    /// The node map is skipped over without being deserialized, so this is
+    /// cheap even for very large documents. The returned version is
+    /// [`CURRENT_VERSION`] once the document's own version is known to
+    /// migrate.
+    pub fn load_header(json: &str) -> Result<DocumentHeader, MigrationError> {
+        let mut header: DocumentHeader = serde_json::from_str(json).map_err(parse_error)?;
+        check_version(&header.version)?;
+        header.version = CURRENT_VERSION.to_string();
+        Ok(header)
+    }
+
+    /// Load the part of a JSON document that `root` depends on.
+    ///
+    /// Only nodes reachable from `root` are deserialized; the rest of the
+    /// node map is kept as raw text until it is dropped. Scene entries and
+    /// expressions that refer to nodes outside the subtree are dropped, as
+    /// are the assembly fields. A missing `root` gives a document without
+    /// nodes. Nodes from an older document are migrated one by one as they
+    /// are read.
+    pub fn load_subtree(json: &str, root: NodeId) -> Result<Document, MigrationError> {
+        let raw: RawDocument = serde_json::from_str(json).map_err(parse_error)?;
+        check_version(&raw.version)?;
+
+        let mut nodes = HashMap::new();
+        let mut stack = vec![root];
+        let mut seen = HashSet::new();
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            let Some(text) = raw.nodes.get(&id) else {
+                continue;
+            };
+            let node: Node = if raw.version == CURRENT_VERSION {
+                serde_json::from_str(text.get()).map_err(parse_error)?
+            } else {
+                let mut value = serde_json::from_str(text.get()).map_err(parse_error)?;
+                migrate_node(&mut value, &raw.version)?;
+                serde_json::from_value(value).map_err(parse_error)?
+            };
+            stack.extend(compact::get_children(&node.op));
+            nodes.insert(id, node);
+        }
+
+        let mut expressions = raw.expressions;
+        expressions.retain(|id, _| nodes.contains_key(id));
+        let roots = raw
+            .roots
+            .into_iter()
+            .filter(|entry| nodes.contains_key(&entry.root))
+            .collect();
+
+        Ok(Document {
+            version: CURRENT_VERSION.to_string(),
+            nodes,
+            materials: raw.materials,
+            part_materials: raw.part_materials,
+            roots,
+            scene: raw.scene,
+            parameters: raw.parameters,
+            expressions,
+            ..Document::default()
+        })
+    }
+}
+
+fn parse_error(e: serde_json::Error) -> MigrationError {
+    MigrationError::Parse(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoxOrigin, CsgOp, SweepOptions, Vec3};
+
+    /// Many independent parts: a cube per root, each translated once.
+    fn big_document(parts: u64) -> Document {
+        let mut doc = Document::new();
+        doc.add_material_preset("steel");
+        for i in 0..parts {
+            let cube = 2 * i;
+            doc.nodes.insert(
+                cube,
+                Node {
+                    id: cube,
+                    name: None,
+                    op: CsgOp::Cube {
+                        size: Vec3::new(1.0, 1.0, 1.0),
+                        origin: BoxOrigin::Corner,
+                    },
+                    attributes: None,
+                },
+            );
+            doc.nodes.insert(
+                cube + 1,
+                Node {
+                    id: cube + 1,
+                    name: Some(format!("part{i}")),
+                    op: CsgOp::Translate {
+                        child: cube,
+                        offset: Vec3::new(i as f64, 0.0, 0.0),
+                    },
+                    attributes: None,
+                },
+            );
+            doc.roots.push(SceneEntry {
+                root: cube + 1,
+                material: "steel".to_string(),
+                visible: None,
+            });
+        }
+        doc
+    }
+
+    #[test]
+    fn test_load_header() {
+        let doc = big_document(5000);
+        let json = doc.to_json().unwrap();
+
+        let header = Document::load_header(&json).unwrap();
+        assert_eq!(header.version, doc.version);
+        assert_eq!(header.materials, doc.materials);
+        assert_eq!(header.part_materials, doc.part_materials);
+        assert_eq!(header.roots, doc.roots);
+
+        // Nodes are never deserialized: a node map that isn't valid IR still
+        // yields the header
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["nodes"] = serde_json::json!({ "0": { "not": "a node" } });
+        let header = Document::load_header(&value.to_string()).unwrap();
+        assert_eq!(header.roots.len(), 5000);
+    }
+
+    #[test]
+    fn test_load_subtree() {
+        let doc = big_document(100);
+        let mut json: serde_json::Value = serde_json::to_value(&doc).unwrap();
+        // Unreachable nodes are not parsed
+        json["nodes"]["10"] = serde_json::json!({ "not": "a node" });
+        let json = json.to_string();
+
+        let part = Document::load_subtree(&json, 7).unwrap();
+        let mut ids: Vec<_> = part.nodes.keys().copied().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, [6, 7]);
+        assert_eq!(part.nodes[&7], doc.nodes[&7]);
+        assert_eq!(part.roots.len(), 1);
+        assert_eq!(part.roots[0].root, 7);
+        assert_eq!(part.materials, doc.materials);
+
+        assert!(Document::load_subtree(&json, 10).is_err());
+        assert!(Document::load_subtree(&json, 1000)
+            .unwrap()
+            .nodes
+            .is_empty());
+    }
+
+    #[test]
+    fn test_load_legacy_subtree() {
+        let json = r#"{
+            "version": "0.1",
+            "nodes": {
+                "0": {"id": 0, "name": null, "op": {"type": "Sketch2D",
+                    "origin": {"x": 0, "y": 0, "z": 0},
+                    "x_dir": {"x": 1, "y": 0, "z": 0},
+                    "y_dir": {"x": 0, "y": 1, "z": 0},
+                    "segments": []}},
+                "1": {"id": 1, "name": null, "op": {"type": "Sweep", "sketch": 0,
+                    "path": {"type": "Line", "start": {"x": 0, "y": 0, "z": 0},
+                             "end": {"x": 0, "y": 0, "z": 10}},
+                    "twist_angle": 0.5}},
+                "2": {"id": 2, "name": null,
+                    "op": {"type": "Loft", "sketches": [0, 0], "closed": false}}
+            },
+            "materials": {},
+            "part_materials": {},
+            "roots": [{"root": 1, "material": "default"}]
+        }"#;
+
+        let part = Document::load_subtree(json, 1).unwrap();
+        assert_eq!(part.version, CURRENT_VERSION);
+        assert_eq!(part, {
+            let mut full = Document::from_json_migrated(json).unwrap();
+            full.nodes.remove(&2);
+            full
+        });
+        match &part.nodes[&1].op {
+            CsgOp::Sweep {
+                profile, options, ..
+            } => {
+                assert_eq!(*profile, 0);
+                assert_eq!(
+                    *options,
+                    SweepOptions {
+                        twist_angle: Some(0.5),
+                        ..Default::default()
+                    }
+                );
+            }
+            other => panic!("expected Sweep, got {:?}", other),
+        }
+        assert!(matches!(
+            Document::load_subtree(json, 2).unwrap().nodes[&2].op,
+            CsgOp::Loft { .. }
+        ));
+        assert_eq!(
+            Document::load_header(json).unwrap().version,
+            CURRENT_VERSION
+        );
+
+        let newer = json.replace(r#""version": "0.1""#, r#""version": "9.0""#);
+        assert_eq!(
+            Document::load_subtree(&newer, 1),
+            Err(MigrationError::NewerVersion("9.0".to_string()))
+        );
+        assert!(Document::load_header(&newer).is_err());
+    }
+}
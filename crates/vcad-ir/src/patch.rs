@@ -0,0 +1,585 @@
+//! Incremental document edits.
+//!
+//! [`Document::diff`] computes a list of [`DocumentPatch`] operations that
+//! turn one document into another, and [`Document::apply_patch`] replays
+//! them. Patches are small and serializable, so an edit such as "change node
+//! 2's radius" can be sent without re-sending the whole document.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    CsgOp, Document, Instance, Joint, MaterialDef, Node, NodeId, PartDef, SceneEntry, SceneSettings,
+};
+
+/// A single edit to a [`Document`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DocumentPatch {
+    /// Replace the operation of an existing node.
+    SetOp {
+        /// Node to modify.
+        id: NodeId,
+        /// New operation.
+        op: CsgOp,
+    },
+    /// Replace the name of an existing node.
+    SetName {
+        /// Node to modify.
+        id: NodeId,
+        /// New name (`None` clears it).
+        name: Option<String>,
+    },
+    /// Replace the attributes of an existing node.
+    SetAttributes {
+        /// Node to modify.
+        id: NodeId,
+        /// New attributes (`None` clears them).
+        attributes: Option<HashMap<String, String>>,
+    },
+    /// Replace the expressions driving a node's fields.
+    SetExpressions {
+        /// Node whose fields the expressions drive.
+        id: NodeId,
+        /// Field path → expression text (`None` removes them).
+        expressions: Option<BTreeMap<String, String>>,
+    },
+    /// Set (or with `None`, remove) a document parameter.
+    SetParameter {
+        /// Parameter name.
+        name: String,
+        /// New value.
+        value: Option<f64>,
+    },
+    /// Insert a new node (replacing any node with the same id).
+    AddNode {
+        /// The node to insert.
+        node: Node,
+    },
+    /// Remove a node.
+    RemoveNode {
+        /// Node to remove.
+        id: NodeId,
+    },
+    /// Insert or replace a material definition.
+    SetMaterial {
+        /// Material key.
+        name: String,
+        /// New definition.
+        material: MaterialDef,
+    },
+    /// Remove a material definition.
+    RemoveMaterial {
+        /// Material key.
+        name: String,
+    },
+    /// Assign (or with `None`, unassign) a part's material.
+    SetPartMaterial {
+        /// Part name.
+        part: String,
+        /// Material name.
+        material: Option<String>,
+    },
+    /// Replace the scene entry list.
+    SetRoots {
+        /// New scene entries.
+        roots: Vec<SceneEntry>,
+    },
+    /// Replace the scene rendering settings.
+    SetScene {
+        /// New settings.
+        scene: Option<SceneSettings>,
+    },
+    /// Replace the assembly data (part definitions, instances, joints, ground).
+    SetAssembly {
+        /// Part definitions.
+        #[serde(rename = "partDefs")]
+        part_defs: Option<HashMap<String, PartDef>>,
+        /// Instances of part definitions.
+        instances: Option<Vec<Instance>>,
+        /// Joints connecting instances.
+        joints: Option<Vec<Joint>>,
+        /// Grounded instance.
+        #[serde(rename = "groundInstanceId")]
+        ground_instance_id: Option<String>,
+    },
+    /// Replace the format version string.
+    SetVersion {
+        /// New version.
+        version: String,
+    },
+}
+
+impl Document {
+    /// Compute the patches that turn `self` into `other`.
+    ///
+    /// Nodes are matched by id: changed operations (by [`CsgOp`] equality),
+    /// names and attributes become [`DocumentPatch::SetOp`],
+    /// [`DocumentPatch::SetName`] and [`DocumentPatch::SetAttributes`], and
+    /// nodes present in only one document become additions or removals.
+    /// Parameters and per-node expressions are diffed the same way. Patches
+    /// are ordered by node id (and material/part/parameter name) so the
+    /// output is deterministic. Identical documents yield an empty list.
+    pub fn diff(&self, other: &Document) -> Vec<DocumentPatch> {
+        let mut patches = Vec::new();
+
+        if self.version != other.version {
+            patches.push(DocumentPatch::SetVersion {
+                version: other.version.clone(),
+            });
+        }
+
+        // Nodes
+        let mut removed: Vec<NodeId> = self
+            .nodes
+            .keys()
+            .filter(|id| !other.nodes.contains_key(id))
+            .copied()
+            .collect();
+        removed.sort_unstable();
+        patches.extend(
+            removed
+                .into_iter()
+                .map(|id| DocumentPatch::RemoveNode { id }),
+        );
+
+        let mut ids: Vec<NodeId> = other.nodes.keys().copied().collect();
+        ids.sort_unstable();
+        for id in ids {
+            let new = &other.nodes[&id];
+            match self.nodes.get(&id) {
+                None => patches.push(DocumentPatch::AddNode { node: new.clone() }),
+                Some(old) if old.id != new.id => {
+                    patches.push(DocumentPatch::AddNode { node: new.clone() })
+                }
+                Some(old) => {
+                    if old.op != new.op {
+                        patches.push(DocumentPatch::SetOp {
+                            id,
+                            op: new.op.clone(),
+                        });
+                    }
+                    if old.name != new.name {
+                        patches.push(DocumentPatch::SetName {
+                            id,
+                            name: new.name.clone(),
+                        });
+                    }
+                    if old.attributes != new.attributes {
+                        patches.push(DocumentPatch::SetAttributes {
+                            id,
+                            attributes: new.attributes.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        // Expressions, which may exist for ids without a node
+        let mut ids: Vec<NodeId> = self
+            .expressions
+            .keys()
+            .chain(other.expressions.keys())
+            .copied()
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        for id in ids {
+            let new = other.expressions.get(&id);
+            if self.expressions.get(&id) != new {
+                patches.push(DocumentPatch::SetExpressions {
+                    id,
+                    expressions: new.cloned(),
+                });
+            }
+        }
+
+        // Parameters
+        let mut names: Vec<&String> = self
+            .parameters
+            .keys()
+            .chain(other.parameters.keys())
+            .collect();
+        names.sort();
+        names.dedup();
+        for name in names {
+            let new = other.parameters.get(name);
+            if self.parameters.get(name) != new {
+                patches.push(DocumentPatch::SetParameter {
+                    name: name.clone(),
+                    value: new.copied(),
+                });
+            }
+        }
+
+        // Materials
+        let mut removed: Vec<&String> = self
+            .materials
+            .keys()
+            .filter(|name| !other.materials.contains_key(*name))
+            .collect();
+        removed.sort();
+        patches.extend(
+            removed
+                .into_iter()
+                .map(|name| DocumentPatch::RemoveMaterial { name: name.clone() }),
+        );
+        let mut names: Vec<&String> = other.materials.keys().collect();
+        names.sort();
+        for name in names {
+            let material = &other.materials[name];
+            if self.materials.get(name) != Some(material) {
+                patches.push(DocumentPatch::SetMaterial {
+                    name: name.clone(),
+                    material: material.clone(),
+                });
+            }
+        }
+
+        // Part material assignments
+        let mut parts: Vec<&String> = self
+            .part_materials
+            .keys()
+            .chain(other.part_materials.keys())
+            .collect();
+        parts.sort();
+        parts.dedup();
+        for part in parts {
+            let new = other.part_materials.get(part);
+            if self.part_materials.get(part) != new {
+                patches.push(DocumentPatch::SetPartMaterial {
+                    part: part.clone(),
+                    material: new.cloned(),
+                });
+            }
+        }
+
+        if self.roots != other.roots {
+            patches.push(DocumentPatch::SetRoots {
+                roots: other.roots.clone(),
+            });
+        }
+
+        if self.scene != other.scene {
+            patches.push(DocumentPatch::SetScene {
+                scene: other.scene.clone(),
+            });
+        }
+
+        if self.part_defs != other.part_defs
+            || self.instances != other.instances
+            || self.joints != other.joints
+            || self.ground_instance_id != other.ground_instance_id
+        {
+            patches.push(DocumentPatch::SetAssembly {
+                part_defs: other.part_defs.clone(),
+                instances: other.instances.clone(),
+                joints: other.joints.clone(),
+                ground_instance_id: other.ground_instance_id.clone(),
+            });
+        }
+
+        patches
+    }
+
+    /// Apply patches in order.
+    ///
+    /// [`DocumentPatch::SetOp`] and [`DocumentPatch::SetName`] on a node that
+    /// does not exist are ignored, as is removing something that is absent.
+    pub fn apply_patch(&mut self, patches: &[DocumentPatch]) {
+        for patch in patches {
+            match patch {
+                DocumentPatch::SetOp { id, op } => {
+                    if let Some(node) = self.nodes.get_mut(id) {
+                        node.op = op.clone();
+                    }
+                }
+                DocumentPatch::SetName { id, name } => {
+                    if let Some(node) = self.nodes.get_mut(id) {
+                        node.name = name.clone();
+                    }
+                }
+                DocumentPatch::SetAttributes { id, attributes } => {
+                    if let Some(node) = self.nodes.get_mut(id) {
+                        node.attributes = attributes.clone();
+                    }
+                }
+                DocumentPatch::SetExpressions { id, expressions } => match expressions {
+                    Some(expressions) => {
+                        self.expressions.insert(*id, expressions.clone());
+                    }
+                    None => {
+                        self.expressions.remove(id);
+                    }
+                },
+                DocumentPatch::SetParameter { name, value } => match value {
+                    Some(value) => {
+                        self.parameters.insert(name.clone(), *value);
+                    }
+                    None => {
+                        self.parameters.remove(name);
+                    }
+                },
+                DocumentPatch::AddNode { node } => {
+                    self.nodes.insert(node.id, node.clone());
+                }
+                DocumentPatch::RemoveNode { id } => {
+                    self.nodes.remove(id);
+                }
+                DocumentPatch::SetMaterial { name, material } => {
+                    self.materials.insert(name.clone(), material.clone());
+                }
+                DocumentPatch::RemoveMaterial { name } => {
+                    self.materials.remove(name);
+                }
+                DocumentPatch::SetPartMaterial { part, material } => match material {
+                    Some(material) => {
+                        self.part_materials.insert(part.clone(), material.clone());
+                    }
+                    None => {
+                        self.part_materials.remove(part);
+                    }
+                },
+                DocumentPatch::SetRoots { roots } => {
+                    self.roots = roots.clone();
+                }
+                DocumentPatch::SetScene { scene } => {
+                    self.scene = scene.clone();
+                }
+                DocumentPatch::SetAssembly {
+                    part_defs,
+                    instances,
+                    joints,
+                    ground_instance_id,
+                } => {
+                    self.part_defs = part_defs.clone();
+                    self.instances = instances.clone();
+                    self.joints = joints.clone();
+                    self.ground_instance_id = ground_instance_id.clone();
+                }
+                DocumentPatch::SetVersion { version } => {
+                    self.version = version.clone();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoxOrigin, Vec3};
+
+    fn node(id: NodeId, op: CsgOp) -> Node {
+        Node {
+            id,
+            name: None,
+            op,
+            attributes: None,
+        }
+    }
+
+    fn bracket() -> Document {
+        let mut doc = Document::new();
+        doc.nodes.insert(
+            1,
+            node(
+                1,
+                CsgOp::Cube {
+                    size: Vec3::new(20.0, 20.0, 5.0),
+                    origin: BoxOrigin::Corner,
+                },
+            ),
+        );
+        doc.nodes.insert(
+            2,
+            node(
+                2,
+                CsgOp::Cylinder {
+                    radius: 3.0,
+                    height: 10.0,
+                    segments: 0,
+                },
+            ),
+        );
+        doc.nodes
+            .insert(3, node(3, CsgOp::Difference { left: 1, right: 2 }));
+        doc.roots.push(SceneEntry {
+            root: 3,
+            material: "default".to_string(),
+            visible: None,
+        });
+        doc
+    }
+
+    #[test]
+    fn unchanged_document_has_empty_diff() {
+        let doc = bracket();
+        assert!(doc.diff(&doc.clone()).is_empty());
+    }
+
+    #[test]
+    fn diff_then_apply_reconstructs_target() {
+        let base = bracket();
+        let mut target = base.clone();
+        target.nodes.get_mut(&2).unwrap().op = CsgOp::Cylinder {
+            radius: 6.0,
+            height: 10.0,
+            segments: 0,
+        };
+        target.nodes.get_mut(&1).unwrap().name = Some("plate".to_string());
+        target.nodes.insert(
+            4,
+            node(
+                4,
+                CsgOp::Translate {
+                    child: 3,
+                    offset: Vec3::new(0.0, 0.0, 10.0),
+                },
+            ),
+        );
+        target.roots[0].root = 4;
+        target
+            .part_materials
+            .insert("plate".to_string(), "steel".to_string());
+
+        let patches = base.diff(&target);
+        assert!(patches.contains(&DocumentPatch::SetOp {
+            id: 2,
+            op: target.nodes[&2].op.clone(),
+        }));
+
+        let mut patched = base.clone();
+        patched.apply_patch(&patches);
+        assert_eq!(patched, target);
+
+        // And back again, which exercises removal.
+        let mut reverted = target.clone();
+        reverted.apply_patch(&target.diff(&base));
+        assert_eq!(reverted, base);
+    }
+
+    /// A document with each field filled in at random from a small pool,
+    /// so that two of them often share some entries and differ in others.
+    fn random_document(next: &mut impl FnMut(u64) -> u64) -> Document {
+        let mut doc = Document::new();
+        if next(2) == 0 {
+            doc.version = "0.1".to_string();
+        }
+        for id in 0..6 {
+            if next(3) == 0 {
+                continue;
+            }
+            let op = match next(2) {
+                0 => CsgOp::Sphere {
+                    radius: next(3) as f64 + 1.0,
+                    segments: 0,
+                },
+                _ => CsgOp::Cube {
+                    size: Vec3::new(next(3) as f64 + 1.0, 2.0, 3.0),
+                    origin: BoxOrigin::Corner,
+                },
+            };
+            let mut n = node(id, op);
+            n.name = (next(2) == 0).then(|| format!("part{}", next(2)));
+            n.attributes = (next(2) == 0).then(|| {
+                (0..next(3))
+                    .map(|k| (format!("key{k}"), format!("value{}", next(2))))
+                    .collect()
+            });
+            doc.nodes.insert(id, n);
+        }
+        for id in 0..6 {
+            if next(3) == 0 {
+                let fields = (0..next(3))
+                    .map(|k| (format!("size.x{k}"), format!("w * {}", next(3))))
+                    .collect();
+                doc.expressions.insert(id, fields);
+            }
+        }
+        for name in ["w", "h", "t"] {
+            if next(2) == 0 {
+                doc.parameters.insert(name.to_string(), next(4) as f64);
+            }
+        }
+        for part in ["part0", "part1"] {
+            if next(2) == 0 {
+                let material = ["steel", "pla"][next(2) as usize];
+                doc.part_materials
+                    .insert(part.to_string(), material.to_string());
+            }
+        }
+        if next(2) == 0 {
+            doc.add_material_preset("pla");
+        }
+        for _ in 0..next(3) {
+            doc.roots.push(SceneEntry {
+                root: next(6),
+                material: "default".to_string(),
+                visible: None,
+            });
+        }
+        if next(3) == 0 {
+            doc.ground_instance_id = Some("base".to_string());
+        }
+        doc
+    }
+
+    #[test]
+    fn diff_then_apply_reconstructs_random_documents() {
+        // Deterministic xorshift so failures are reproducible
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = |n: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % n
+        };
+        for _ in 0..500 {
+            let a = random_document(&mut next);
+            let b = random_document(&mut next);
+            let mut patched = a.clone();
+            patched.apply_patch(&a.diff(&b));
+            assert_eq!(patched, b);
+            assert!(b.diff(&b).is_empty());
+        }
+    }
+
+    #[test]
+    fn diff_covers_attributes_parameters_and_expressions() {
+        let base = bracket();
+        let mut target = base.clone();
+        target.nodes.get_mut(&1).unwrap().attributes =
+            Some(HashMap::from([("locked".to_string(), "true".to_string())]));
+        target.parameters.insert("radius".to_string(), 6.0);
+        target.expressions.insert(
+            2,
+            BTreeMap::from([("radius".to_string(), "radius".to_string())]),
+        );
+
+        let patches = base.diff(&target);
+        assert_eq!(patches.len(), 3);
+        let mut patched = base.clone();
+        patched.apply_patch(&patches);
+        assert_eq!(patched, target);
+
+        let mut reverted = target.clone();
+        reverted.apply_patch(&target.diff(&base));
+        assert_eq!(reverted, base);
+    }
+
+    #[test]
+    fn patch_serde_roundtrip() {
+        let patch = DocumentPatch::SetOp {
+            id: 2,
+            op: CsgOp::Sphere {
+                radius: 6.0,
+                segments: 0,
+            },
+        };
+        let json = serde_json::to_string(&patch).unwrap();
+        assert!(json.contains(r#""type":"SetOp""#));
+        let restored: DocumentPatch = serde_json::from_str(&json).unwrap();
+        assert_eq!(patch, restored);
+    }
+}
@@ -0,0 +1,148 @@
+//! Summary statistics over a document's node graph.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{compact, CycleError, Document, NodeId};
+
+/// Counts and shape of a [`Document`]'s node graph, from
+/// [`Document::statistics`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DocumentStats {
+    /// Number of nodes per operation type, keyed by [`CsgOp::type_name`].
+    ///
+    /// [`CsgOp::type_name`]: crate::CsgOp::type_name
+    pub op_counts: BTreeMap<String, usize>,
+    /// Depth of the DAG below each scene root, in [`Document::roots`] order.
+    ///
+    /// A leaf node has depth 1; a root referring to a missing node has
+    /// depth 0.
+    pub root_depths: Vec<usize>,
+    /// The largest of `root_depths`.
+    pub max_depth: usize,
+    /// Nodes referenced more than once, whether by several parents or twice
+    /// by the same one.
+    pub shared_nodes: usize,
+    /// Number of solid primitive nodes (see [`CsgOp::is_primitive`]).
+    ///
+    /// [`CsgOp::is_primitive`]: crate::CsgOp::is_primitive
+    pub primitives: usize,
+}
+
+impl Document {
+    /// Gather [`DocumentStats`] over all nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CycleError`] if the node graph has a cycle.
+    pub fn statistics(&self) -> Result<DocumentStats, CycleError> {
+        let mut stats = DocumentStats::default();
+        let mut references: HashMap<NodeId, usize> = HashMap::new();
+        for node in self.nodes.values() {
+            *stats
+                .op_counts
+                .entry(node.op.type_name().to_string())
+                .or_default() += 1;
+            if node.op.is_primitive() {
+                stats.primitives += 1;
+            }
+            for child in compact::get_children(&node.op) {
+                *references.entry(child).or_default() += 1;
+            }
+        }
+        stats.shared_nodes = references
+            .iter()
+            .filter(|&(id, &count)| count > 1 && self.nodes.contains_key(id))
+            .count();
+
+        // Children come first in topological order, so each node's depth
+        // follows from its children's
+        let mut depths: HashMap<NodeId, usize> = HashMap::new();
+        for id in self.topological_order()? {
+            let depth = compact::get_children(&self.nodes[&id].op)
+                .iter()
+                .filter_map(|child| depths.get(child))
+                .max()
+                .map_or(1, |d| d + 1);
+            depths.insert(id, depth);
+        }
+        stats.root_depths = self
+            .roots
+            .iter()
+            .map(|entry| depths.get(&entry.root).copied().unwrap_or(0))
+            .collect();
+        stats.max_depth = stats.root_depths.iter().copied().max().unwrap_or(0);
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoxOrigin, CsgOp, Node, SceneEntry, Vec3};
+
+    fn add(doc: &mut Document, id: NodeId, op: CsgOp) {
+        doc.nodes.insert(
+            id,
+            Node {
+                id,
+                name: None,
+                op,
+                attributes: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_statistics_shared_child() {
+        // Two translated copies of one cube, unioned
+        let mut doc = Document::new();
+        let size = Vec3::new(1.0, 1.0, 1.0);
+        let origin = BoxOrigin::Corner;
+        add(&mut doc, 1, CsgOp::Cube { size, origin });
+        for (id, x) in [(2, 0.0), (3, 5.0)] {
+            let offset = Vec3::new(x, 0.0, 0.0);
+            add(&mut doc, id, CsgOp::Translate { child: 1, offset });
+        }
+        add(&mut doc, 4, CsgOp::Union { left: 2, right: 3 });
+        add(
+            &mut doc,
+            5,
+            CsgOp::Sphere {
+                radius: 1.0,
+                segments: 0,
+            },
+        );
+        for root in [4, 5] {
+            doc.roots.push(SceneEntry {
+                root,
+                material: "default".to_string(),
+                visible: None,
+            });
+        }
+
+        let stats = doc.statistics().unwrap();
+        let counts: Vec<_> = stats
+            .op_counts
+            .iter()
+            .map(|(name, &count)| (name.as_str(), count))
+            .collect();
+        assert_eq!(
+            counts,
+            [("Cube", 1), ("Sphere", 1), ("Translate", 2), ("Union", 1)]
+        );
+        assert_eq!(stats.shared_nodes, 1);
+        assert_eq!(stats.primitives, 2);
+        assert_eq!(stats.root_depths, [3, 1]);
+        assert_eq!(stats.max_depth, 3);
+    }
+
+    #[test]
+    fn test_statistics_cycle() {
+        let mut doc = Document::new();
+        let offset = Vec3::new(0.0, 0.0, 0.0);
+        add(&mut doc, 1, CsgOp::Translate { child: 2, offset });
+        add(&mut doc, 2, CsgOp::Translate { child: 1, offset });
+        assert!(doc.statistics().is_err());
+    }
+}
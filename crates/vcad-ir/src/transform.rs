@@ -0,0 +1,281 @@
+//! Affine transform matrices for IR evaluation.
+//!
+//! [`Transform`] is the matrix form of the [`CsgOp::Translate`],
+//! [`CsgOp::Rotate`], and [`CsgOp::Scale`] nodes. Evaluators use
+//! [`Document::fold_transforms`] to collapse a chain of transform nodes into
+//! a single matrix so the child geometry is copied once instead of once per
+//! node.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CsgOp, Document, NodeId, Vec3};
+
+/// A 3D affine transform stored as a row-major 4×4 matrix.
+///
+/// Points are treated as column vectors, so `matrix[i][3]` holds the
+/// translation and the bottom row is always `[0, 0, 0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Transform {
+    /// Row-major matrix elements.
+    pub matrix: [[f64; 4]; 4],
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Transform {
+    /// The identity transform.
+    pub fn identity() -> Self {
+        Self {
+            matrix: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// Translation by `offset`.
+    pub fn from_translation(offset: Vec3) -> Self {
+        let mut t = Self::identity();
+        t.matrix[0][3] = offset.x;
+        t.matrix[1][3] = offset.y;
+        t.matrix[2][3] = offset.z;
+        t
+    }
+
+    /// Rotation by Euler angles in degrees, applied as X, then Y, then Z
+    /// about the body's own (rotated) axes.
+    ///
+    /// This is the convention of [`CsgOp::Rotate`] and of the kernel's
+    /// `Solid::rotate`: the matrix is `Rx · Ry · Rz`.
+    pub fn from_euler_deg(angles: Vec3) -> Self {
+        let (sx, cx) = angles.x.to_radians().sin_cos();
+        let (sy, cy) = angles.y.to_radians().sin_cos();
+        let (sz, cz) = angles.z.to_radians().sin_cos();
+        Self {
+            matrix: [
+                [cy * cz, -cy * sz, sy, 0.0],
+                [
+                    cx * sz + sx * sy * cz,
+                    cx * cz - sx * sy * sz,
+                    -sx * cy,
+                    0.0,
+                ],
+                [sx * sz - cx * sy * cz, sx * cz + cx * sy * sz, cx * cy, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// Non-uniform scale about the origin.
+    pub fn from_scale(factor: Vec3) -> Self {
+        let mut t = Self::identity();
+        t.matrix[0][0] = factor.x;
+        t.matrix[1][1] = factor.y;
+        t.matrix[2][2] = factor.z;
+        t
+    }
+
+    /// Compose two transforms: apply `inner` first, then `self`.
+    pub fn compose(&self, inner: &Transform) -> Self {
+        let mut matrix = [[0.0; 4]; 4];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..4).map(|k| self.matrix[i][k] * inner.matrix[k][j]).sum();
+            }
+        }
+        Self { matrix }
+    }
+
+    /// Transform a point (translation applies).
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        let m = &self.matrix;
+        Vec3::new(
+            m[0][0] * p.x + m[0][1] * p.y + m[0][2] * p.z + m[0][3],
+            m[1][0] * p.x + m[1][1] * p.y + m[1][2] * p.z + m[1][3],
+            m[2][0] * p.x + m[2][1] * p.y + m[2][2] * p.z + m[2][3],
+        )
+    }
+
+    /// Transform a direction vector (translation is ignored).
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        let m = &self.matrix;
+        Vec3::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        )
+    }
+
+    /// Determinant of the linear (upper-left 3×3) part.
+    ///
+    /// Negative for transforms that mirror geometry.
+    pub fn determinant(&self) -> f64 {
+        let m = &self.matrix;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// The inverse transform, or `None` if the transform is singular
+    /// (e.g. a zero scale factor).
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let m = &self.matrix;
+        let inv_det = 1.0 / det;
+        // Adjugate of the 3×3 part.
+        let mut r = [[0.0; 3]; 3];
+        for (i, row) in r.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                let (a0, a1) = ((j + 1) % 3, (j + 2) % 3);
+                let (b0, b1) = ((i + 1) % 3, (i + 2) % 3);
+                *cell = (m[a0][b0] * m[a1][b1] - m[a0][b1] * m[a1][b0]) * inv_det;
+            }
+        }
+        let t = [m[0][3], m[1][3], m[2][3]];
+        let mut matrix = [[0.0; 4]; 4];
+        for i in 0..3 {
+            matrix[i][..3].copy_from_slice(&r[i]);
+            matrix[i][3] = -(r[i][0] * t[0] + r[i][1] * t[1] + r[i][2] * t[2]);
+        }
+        matrix[3][3] = 1.0;
+        Some(Self { matrix })
+    }
+}
+
+impl CsgOp {
+    /// The child and matrix of a transform node, or `None` for other ops.
+    pub fn as_transform(&self) -> Option<(NodeId, Transform)> {
+        match self {
+            CsgOp::Translate { child, offset } => {
+                Some((*child, Transform::from_translation(*offset)))
+            }
+            CsgOp::Rotate { child, angles } => Some((*child, Transform::from_euler_deg(*angles))),
+            CsgOp::Scale { child, factor } => Some((*child, Transform::from_scale(*factor))),
+            _ => None,
+        }
+    }
+}
+
+impl Document {
+    /// Collapse the chain of transform nodes starting at `node_id`.
+    ///
+    /// Returns the first non-transform node in the chain and the combined
+    /// transform to apply to it. A node that is not a transform yields
+    /// itself and the identity. Missing children end the chain, leaving the
+    /// caller to report them.
+    pub fn fold_transforms(&self, node_id: NodeId) -> (NodeId, Transform) {
+        let mut current = node_id;
+        let mut combined = Transform::identity();
+        // Bounded by the node count so a malformed cyclic chain terminates.
+        for _ in 0..=self.nodes.len() {
+            let Some((child, t)) = self.nodes.get(&current).and_then(|n| n.op.as_transform())
+            else {
+                break;
+            };
+            combined = combined.compose(&t);
+            current = child;
+        }
+        (current, combined)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoxOrigin, Node};
+
+    fn assert_close(a: Vec3, b: Vec3) {
+        let d = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt();
+        assert!(d < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn compose_and_inverse_roundtrip() {
+        let t = Transform::from_translation(Vec3::new(5.0, -2.0, 3.0))
+            .compose(&Transform::from_euler_deg(Vec3::new(30.0, 45.0, 60.0)))
+            .compose(&Transform::from_scale(Vec3::new(2.0, 0.5, -1.0)));
+        let inv = t.inverse().unwrap();
+        let p = Vec3::new(1.0, 2.0, 3.0);
+
+        assert_close(inv.transform_point(t.transform_point(p)), p);
+        assert_close(t.compose(&inv).transform_point(p), p);
+        assert!(t.determinant() < 0.0);
+
+        assert!(Transform::from_scale(Vec3::new(1.0, 0.0, 1.0))
+            .inverse()
+            .is_none());
+    }
+
+    #[test]
+    fn rotate_then_translate_matches_manual() {
+        // Rotate 90° about X, then 90° about the turned Z, then translate
+        // by (10, 0, 0). As a product that's Rx · Rz: Z first, then X.
+        let rotate = Transform::from_euler_deg(Vec3::new(90.0, 0.0, 90.0));
+        let translate = Transform::from_translation(Vec3::new(10.0, 0.0, 0.0));
+        let t = translate.compose(&rotate);
+
+        // (0, 1, 0) → Z: (-1, 0, 0) → X: (-1, 0, 0) → +10x.
+        assert_close(
+            t.transform_point(Vec3::new(0.0, 1.0, 0.0)),
+            Vec3::new(9.0, 0.0, 0.0),
+        );
+        // (1, 0, 0) → Z: (0, 1, 0) → X: (0, 0, 1) → +10x.
+        assert_close(
+            t.transform_point(Vec3::new(1.0, 0.0, 0.0)),
+            Vec3::new(10.0, 0.0, 1.0),
+        );
+        // Vectors ignore the translation.
+        assert_close(
+            t.transform_vector(Vec3::new(1.0, 0.0, 0.0)),
+            Vec3::new(0.0, 0.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn fold_transform_chain() {
+        let mut doc = Document::new();
+        let ops = [
+            CsgOp::Cube {
+                size: Vec3::new(1.0, 1.0, 1.0),
+                origin: BoxOrigin::Corner,
+            },
+            CsgOp::Scale {
+                child: 0,
+                factor: Vec3::new(2.0, 2.0, 2.0),
+            },
+            CsgOp::Translate {
+                child: 1,
+                offset: Vec3::new(0.0, 0.0, 5.0),
+            },
+        ];
+        for (id, op) in ops.into_iter().enumerate() {
+            let id = id as NodeId;
+            doc.nodes.insert(
+                id,
+                Node {
+                    id,
+                    name: None,
+                    op,
+                    attributes: None,
+                },
+            );
+        }
+
+        let (base, t) = doc.fold_transforms(2);
+        assert_eq!(base, 0);
+        assert_close(
+            t.transform_point(Vec3::new(1.0, 1.0, 1.0)),
+            Vec3::new(2.0, 2.0, 7.0),
+        );
+        assert_eq!(doc.fold_transforms(0), (0, Transform::identity()));
+    }
+}
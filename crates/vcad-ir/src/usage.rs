@@ -0,0 +1,142 @@
+//! Material usage and FDM print estimates.
+//!
+//! Like [`Document::mass_properties`], [`Document::material_usage`] takes
+//! each part's volume from the caller, since the IR can't evaluate
+//! geometry.
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mass::MM3_TO_M3;
+use crate::{Document, SceneEntry};
+
+/// Share of a part's volume printed solid as walls and top/bottom skins,
+/// whatever the infill.
+const SHELL_FRACTION: f64 = 0.25;
+
+/// Filament diameter in mm.
+const FILAMENT_DIAMETER: f64 = 1.75;
+
+/// Extrusion line width in mm.
+const LINE_WIDTH: f64 = 0.4;
+
+/// Average print speed in mm/s, travel and acceleration included.
+const PRINT_SPEED: f64 = 50.0;
+
+/// How much of one material the scene uses, from
+/// [`Document::material_usage`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MaterialUsage {
+    /// Total volume in mm³.
+    pub volume: f64,
+    /// The material's density in kg/m³, if known.
+    pub density: Option<f64>,
+    /// Total mass in kg, if the density is known.
+    pub mass: Option<f64>,
+}
+
+/// Rough cost of printing a material's parts on an FDM printer, from
+/// [`MaterialUsage::print_estimate`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PrintEstimate {
+    /// Plastic extruded, in mm³.
+    pub extruded_volume: f64,
+    /// Length of 1.75 mm filament used, in mm.
+    pub filament_length: f64,
+    /// Mass of filament used in kg, if the density is known.
+    pub filament_mass: Option<f64>,
+    /// Print time in seconds.
+    pub time: f64,
+}
+
+impl MaterialUsage {
+    /// Estimate the filament and time needed to print this volume.
+    ///
+    /// `infill` is the infill density in 0.0..=1.0 and `layer_height` is
+    /// in mm. The model is simple: a quarter of the volume goes to walls
+    /// and skins printed solid, the rest is filled at `infill`, and the
+    /// plastic is laid down in 0.4 mm wide lines at an average 50 mm/s.
+    /// Supports, brims, and per-layer travel are not counted.
+    pub fn print_estimate(&self, infill: f64, layer_height: f64) -> PrintEstimate {
+        let fill = SHELL_FRACTION + (1.0 - SHELL_FRACTION) * infill.clamp(0.0, 1.0);
+        let extruded_volume = self.volume * fill;
+        let filament_area = PI * FILAMENT_DIAMETER * FILAMENT_DIAMETER / 4.0;
+        PrintEstimate {
+            extruded_volume,
+            filament_length: extruded_volume / filament_area,
+            filament_mass: self.density.map(|d| extruded_volume * d * MM3_TO_M3),
+            time: extruded_volume / (layer_height * LINE_WIDTH * PRINT_SPEED),
+        }
+    }
+}
+
+impl Document {
+    /// Total volume and mass of each material used by the scene roots,
+    /// keyed by material name.
+    ///
+    /// `volume` gives the volume of a root's geometry in mm³, or `None` if
+    /// it has none; such roots don't count. Roots whose material isn't
+    /// defined in [`Document::materials`], or has no density, are counted
+    /// without a mass.
+    pub fn material_usage(
+        &self,
+        mut volume: impl FnMut(&SceneEntry) -> Option<f64>,
+    ) -> HashMap<String, MaterialUsage> {
+        let mut usage: HashMap<String, MaterialUsage> = HashMap::new();
+        for entry in &self.roots {
+            let Some(volume) = volume(entry) else {
+                continue;
+            };
+            let density = self.materials.get(&entry.material).and_then(|m| m.density);
+            let total = usage
+                .entry(entry.material.clone())
+                .or_insert(MaterialUsage {
+                    volume: 0.0,
+                    density,
+                    mass: density.map(|_| 0.0),
+                });
+            total.volume += volume;
+            total.mass = density.map(|d| total.volume * d * MM3_TO_M3);
+        }
+        usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_per_material() {
+        let mut doc = Document::new();
+        doc.add_material_preset("pla");
+        for (root, material) in [(0, "pla"), (1, "pla"), (2, "unknown"), (3, "pla")] {
+            doc.roots.push(SceneEntry {
+                root,
+                material: material.to_string(),
+                visible: None,
+            });
+        }
+        // Roots 0 and 1 are 10 mm cubes, root 3 has no geometry
+        let usage = doc.material_usage(|entry| (entry.root < 3).then_some(1000.0));
+        assert_eq!(usage.len(), 2);
+
+        let pla = usage["pla"];
+        assert_eq!(pla.volume, 2000.0);
+        // 2000 mm³ at 1240 kg/m³
+        assert!((pla.mass.unwrap() - 2.48e-3).abs() < 1e-12);
+        assert_eq!(usage["unknown"].mass, None);
+
+        let solid = pla.print_estimate(1.0, 0.2);
+        assert!((solid.extruded_volume - 2000.0).abs() < 1e-9);
+        assert!((solid.filament_mass.unwrap() - 2.48e-3).abs() < 1e-12);
+        assert!((solid.filament_length - 831.5).abs() < 0.1);
+        // 2000 mm³ at 0.2 × 0.4 × 50 mm³/s
+        assert!((solid.time - 500.0).abs() < 1e-9);
+
+        let sparse = pla.print_estimate(0.2, 0.2);
+        assert!((sparse.extruded_volume - 800.0).abs() < 1e-9);
+    }
+}
@@ -1,6 +1,7 @@
 //! Public API types and entry point for boolean operations.
 
 use vcad_kernel_primitives::BRepSolid;
+use vcad_kernel_shell::mesh_to_brep;
 use vcad_kernel_tessellate::{tessellate_brep, TriangleMesh};
 
 use crate::bbox;
@@ -15,6 +16,8 @@ pub enum BooleanOp {
     Difference,
     /// Intersection: keep only the overlapping region.
     Intersection,
+    /// Symmetric difference: keep what is in exactly one of the solids.
+    Xor,
 }
 
 /// Result of a boolean operation.
@@ -47,11 +50,21 @@ impl BooleanResult {
     }
 
     /// Convert to BRepSolid, consuming self.
-    /// Returns None if the result is mesh-only.
-    pub fn into_brep(self) -> Option<BRepSolid> {
+    ///
+    /// Mesh-only results are converted with one planar face per triangle, so
+    /// an empty result becomes an empty (but valid) solid.
+    pub fn into_brep(self) -> BRepSolid {
         match self {
-            BooleanResult::BRep(brep) => Some(*brep),
-            BooleanResult::Mesh(_) => None,
+            BooleanResult::BRep(brep) => *brep,
+            BooleanResult::Mesh(mesh) => mesh_to_brep(&mesh),
+        }
+    }
+
+    /// Check if the result contains no geometry.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            BooleanResult::Mesh(mesh) => mesh.indices.is_empty(),
+            BooleanResult::BRep(brep) => brep.topology.faces.is_empty(),
         }
     }
 }
@@ -74,8 +87,8 @@ pub fn boolean_op(
     segments: u32,
 ) -> BooleanResult {
     // Check if solids overlap at all
-    let aabb_a = bbox::solid_aabb(solid_a);
-    let aabb_b = bbox::solid_aabb(solid_b);
+    let aabb_a = bbox::cached_solid_aabb(solid_a);
+    let aabb_b = bbox::cached_solid_aabb(solid_b);
 
     if !aabb_a.overlaps(&aabb_b) {
         // No overlap — shortcut
@@ -9,14 +9,14 @@ use vcad_kernel_topo::FaceId;
 
 /// Axis-aligned bounding box in 3D.
 #[derive(Debug, Clone, Copy)]
-pub struct Aabb3 {
+pub struct Aabb {
     /// Minimum corner.
     pub min: Point3,
     /// Maximum corner.
     pub max: Point3,
 }
 
-impl Aabb3 {
+impl Aabb {
     /// Create an AABB from min and max corners.
     pub fn new(min: Point3, max: Point3) -> Self {
         Self { min, max }
@@ -41,7 +41,7 @@ impl Aabb3 {
     }
 
     /// Test if two AABBs overlap (touching counts as overlap).
-    pub fn overlaps(&self, other: &Aabb3) -> bool {
+    pub fn overlaps(&self, other: &Aabb) -> bool {
         self.min.x <= other.max.x
             && self.max.x >= other.min.x
             && self.min.y <= other.max.y
@@ -59,6 +59,67 @@ impl Aabb3 {
         self.max.y += tol;
         self.max.z += tol;
     }
+
+    /// A copy of this AABB grown by `margin` in all directions.
+    ///
+    /// A negative margin shrinks the box.
+    pub fn grow(&self, margin: f64) -> Aabb {
+        let mut aabb = *self;
+        aabb.expand(margin);
+        aabb
+    }
+
+    /// Whether this AABB contains no points (min > max on some axis).
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    /// Test if a point lies inside the AABB (points on the boundary count).
+    pub fn contains_point(&self, p: &Point3) -> bool {
+        p.x >= self.min.x
+            && p.x <= self.max.x
+            && p.y >= self.min.y
+            && p.y <= self.max.y
+            && p.z >= self.min.z
+            && p.z <= self.max.z
+    }
+
+    /// The overlapping region of two AABBs, or `None` if they are disjoint.
+    ///
+    /// Touching boxes intersect in a degenerate (zero-volume) box.
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        Some(Aabb::new(
+            Point3::new(
+                self.min.x.max(other.min.x),
+                self.min.y.max(other.min.y),
+                self.min.z.max(other.min.z),
+            ),
+            Point3::new(
+                self.max.x.min(other.max.x),
+                self.max.y.min(other.max.y),
+                self.max.z.min(other.max.z),
+            ),
+        ))
+    }
+
+    /// The smallest AABB enclosing both boxes.
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        let mut aabb = *self;
+        aabb.include_point(&other.min);
+        aabb.include_point(&other.max);
+        aabb
+    }
+
+    /// Volume of the box, or 0 if it is empty.
+    pub fn volume(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        (self.max.x - self.min.x) * (self.max.y - self.min.y) * (self.max.z - self.min.z)
+    }
 }
 
 /// Compute the AABB for a face from its boundary vertex positions.
@@ -67,10 +128,10 @@ impl Aabb3 {
 /// this is conservative — the actual surface may extend beyond the vertices,
 /// but vertex positions bound the trim loop endpoints. We add a small
 /// tolerance to account for curvature.
-pub fn face_aabb(brep: &BRepSolid, face_id: FaceId) -> Aabb3 {
+pub fn face_aabb(brep: &BRepSolid, face_id: FaceId) -> Aabb {
     let topo = &brep.topology;
     let face = &topo.faces[face_id];
-    let mut aabb = Aabb3::empty();
+    let mut aabb = Aabb::empty();
 
     // Include all vertices from outer loop
     for he_id in topo.loop_half_edges(face.outer_loop) {
@@ -121,7 +182,7 @@ pub fn face_aabb(brep: &BRepSolid, face_id: FaceId) -> Aabb3 {
                         if radius > 1e-6 {
                             // Expand the AABB to cover the full circle
                             // The circle is centered at plane.origin
-                            aabb = Aabb3::empty();
+                            aabb = Aabb::empty();
                             // Include corners of a bounding square around the circle
                             let x_dir = *plane.x_dir.as_ref();
                             let y_dir = *plane.y_dir.as_ref();
@@ -180,7 +241,7 @@ pub fn face_aabb(brep: &BRepSolid, face_id: FaceId) -> Aabb3 {
                     let bottom_center = center + v_min * axis;
                     let top_center = center + v_max * axis;
 
-                    aabb = Aabb3::empty();
+                    aabb = Aabb::empty();
                     aabb.include_point(&(bottom_center + vcad_kernel_math::Vec3::new(r, r, 0.0)));
                     aabb.include_point(&(bottom_center + vcad_kernel_math::Vec3::new(r, -r, 0.0)));
                     aabb.include_point(&(bottom_center + vcad_kernel_math::Vec3::new(-r, r, 0.0)));
@@ -253,7 +314,7 @@ pub fn face_aabb(brep: &BRepSolid, face_id: FaceId) -> Aabb3 {
                 let extent_y = outer * (1.0 - ay * ay).sqrt() + r_minor * ay.abs();
                 let extent_z = outer * (1.0 - az * az).sqrt() + r_minor * az.abs();
 
-                aabb = Aabb3::empty();
+                aabb = Aabb::empty();
                 aabb.include_point(&Point3::new(
                     center.x - extent_x,
                     center.y - extent_y,
@@ -291,8 +352,8 @@ pub fn face_aabb(brep: &BRepSolid, face_id: FaceId) -> Aabb3 {
 ///
 /// Uses the union of all face AABBs, which properly accounts for curved surfaces
 /// (cylinders, cones, spheres) that may extend beyond their boundary vertices.
-pub fn solid_aabb(brep: &BRepSolid) -> Aabb3 {
-    let mut aabb = Aabb3::empty();
+pub fn solid_aabb(brep: &BRepSolid) -> Aabb {
+    let mut aabb = Aabb::empty();
     for (face_id, _) in &brep.topology.faces {
         let face_box = face_aabb(brep, face_id);
         aabb.include_point(&face_box.min);
@@ -301,20 +362,31 @@ pub fn solid_aabb(brep: &BRepSolid) -> Aabb3 {
     aabb
 }
 
+/// Like [`solid_aabb`], but cached on the solid after the first call.
+///
+/// See [`BRepSolid::bounds_or_insert_with`] for when the cache is reset.
+pub fn cached_solid_aabb(brep: &BRepSolid) -> Aabb {
+    let (min, max) = brep.bounds_or_insert_with(|b| {
+        let aabb = solid_aabb(b);
+        (aabb.min, aabb.max)
+    });
+    Aabb::new(min, max)
+}
+
 /// Find candidate face pairs between two solids whose AABBs overlap.
 ///
 /// Returns `(face_from_a, face_from_b)` pairs. Only these pairs need
 /// surface-surface intersection tests.
 pub fn find_candidate_face_pairs(a: &BRepSolid, b: &BRepSolid) -> Vec<(FaceId, FaceId)> {
     // First check if the overall solids overlap at all
-    let aabb_a = solid_aabb(a);
-    let aabb_b = solid_aabb(b);
+    let aabb_a = cached_solid_aabb(a);
+    let aabb_b = cached_solid_aabb(b);
     if !aabb_a.overlaps(&aabb_b) {
         return Vec::new();
     }
 
     // Precompute face AABBs for solid B
-    let b_faces: Vec<(FaceId, Aabb3)> = b
+    let b_faces: Vec<(FaceId, Aabb)> = b
         .topology
         .faces
         .iter()
@@ -343,22 +415,89 @@ mod tests {
 
     #[test]
     fn test_aabb_overlap() {
-        let a = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(10.0, 10.0, 10.0));
-        let b = Aabb3::new(Point3::new(5.0, 5.0, 5.0), Point3::new(15.0, 15.0, 15.0));
+        let a = Aabb::new(Point3::new(0.0, 0.0, 0.0), Point3::new(10.0, 10.0, 10.0));
+        let b = Aabb::new(Point3::new(5.0, 5.0, 5.0), Point3::new(15.0, 15.0, 15.0));
         assert!(a.overlaps(&b));
         assert!(b.overlaps(&a));
 
-        let c = Aabb3::new(Point3::new(20.0, 20.0, 20.0), Point3::new(30.0, 30.0, 30.0));
+        let c = Aabb::new(Point3::new(20.0, 20.0, 20.0), Point3::new(30.0, 30.0, 30.0));
         assert!(!a.overlaps(&c));
     }
 
     #[test]
     fn test_aabb_touching() {
-        let a = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(10.0, 10.0, 10.0));
-        let b = Aabb3::new(Point3::new(10.0, 0.0, 0.0), Point3::new(20.0, 10.0, 10.0));
+        let a = Aabb::new(Point3::new(0.0, 0.0, 0.0), Point3::new(10.0, 10.0, 10.0));
+        let b = Aabb::new(Point3::new(10.0, 0.0, 0.0), Point3::new(20.0, 10.0, 10.0));
         assert!(a.overlaps(&b)); // touching counts
     }
 
+    #[test]
+    fn test_aabb_touching_intersection_is_degenerate() {
+        let a = Aabb::new(Point3::new(0.0, 0.0, 0.0), Point3::new(10.0, 10.0, 10.0));
+        let b = Aabb::new(Point3::new(10.0, 0.0, 0.0), Point3::new(20.0, 10.0, 10.0));
+        let i = a.intersection(&b).unwrap();
+        assert_eq!(i.min.x, 10.0);
+        assert_eq!(i.max.x, 10.0);
+        assert_eq!(i.volume(), 0.0);
+
+        // A hair apart: no overlap, no intersection.
+        let c = b.grow(-1e-9);
+        assert!(!a.overlaps(&c));
+        assert!(a.intersection(&c).is_none());
+    }
+
+    #[test]
+    fn test_aabb_contains_point_boundary() {
+        let a = Aabb::new(Point3::new(0.0, 0.0, 0.0), Point3::new(10.0, 10.0, 10.0));
+        assert!(a.contains_point(&Point3::new(5.0, 5.0, 5.0)));
+        assert!(a.contains_point(&Point3::new(10.0, 0.0, 10.0)));
+        assert!(!a.contains_point(&Point3::new(10.0 + 1e-9, 5.0, 5.0)));
+        assert!(!a.contains_point(&Point3::new(5.0, -1e-9, 5.0)));
+        assert!(!Aabb::empty().contains_point(&Point3::origin()));
+    }
+
+    #[test]
+    fn test_aabb_union_volume_grow() {
+        let a = Aabb::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0));
+        let b = Aabb::new(Point3::new(4.0, -1.0, 0.0), Point3::new(5.0, 0.0, 1.0));
+        let u = a.union(&b);
+        assert_eq!(u.min, Point3::new(0.0, -1.0, 0.0));
+        assert_eq!(u.max, Point3::new(5.0, 2.0, 3.0));
+        assert_eq!(a.volume(), 6.0);
+        assert_eq!(Aabb::empty().volume(), 0.0);
+        assert_eq!(Aabb::empty().union(&a).volume(), 6.0);
+        assert!((a.grow(1.0).volume() - 3.0 * 4.0 * 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cached_aabb_after_translate() {
+        let mut cube = make_cube(10.0, 10.0, 10.0);
+        assert!(cube.cached_bounds().is_none());
+        let before = cached_solid_aabb(&cube);
+        assert!(cube.cached_bounds().is_some());
+
+        // A translated clone starts with an empty cache.
+        let mut moved = cube.clone();
+        assert!(moved.cached_bounds().is_none());
+        for (_, v) in &mut moved.topology.vertices {
+            v.point.x += 100.0;
+        }
+        let fresh = solid_aabb(&moved);
+        let cached = cached_solid_aabb(&moved);
+        assert_eq!((cached.min, cached.max), (fresh.min, fresh.max));
+        assert!((cached.min.x - 100.0).abs() < 1e-10);
+
+        // In-place edits require an explicit invalidation.
+        for (_, v) in &mut cube.topology.vertices {
+            v.point.z -= 5.0;
+        }
+        cube.invalidate_bounds();
+        let cached = cached_solid_aabb(&cube);
+        let fresh = solid_aabb(&cube);
+        assert_eq!((cached.min, cached.max), (fresh.min, fresh.max));
+        assert!((cached.min.z - (before.min.z - 5.0)).abs() < 1e-10);
+    }
+
     #[test]
     fn test_non_overlapping_cubes_no_pairs() {
         // Two cubes far apart — no candidate pairs
@@ -5,13 +5,14 @@
 //! operation then selects which sub-faces to keep.
 
 use vcad_kernel_geom::SurfaceKind;
-use vcad_kernel_math::Point3;
+use vcad_kernel_math::{Point2, Point3, Vec3};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_tessellate::{tessellate_brep, TriangleMesh};
 use vcad_kernel_topo::FaceId;
 
-use crate::point_in_mesh;
+use crate::point_in_mesh_robust;
 use crate::split::point_to_segment_dist_2d;
+use crate::trim::point_in_polygon;
 use crate::BooleanOp;
 
 /// Classification of a face relative to another solid.
@@ -25,6 +26,34 @@ pub enum FaceClassification {
     OnSame,
     /// Face is on the boundary, normals oppose.
     OnOpposite,
+    /// Face samples disagree: the face straddles the other solid's boundary
+    /// and must be split (via SSI) before it can be classified.
+    ///
+    /// Only produced by [`classify_all_faces_opts`] with more than one
+    /// sample per face. [`select_faces`] keeps no such faces.
+    NeedsSubdivision,
+}
+
+/// Options for [`classify_all_faces_opts`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassifyOptions {
+    /// Interior points tested per face. One (the default) tests a single
+    /// [`face_sample_point`], which is fastest but trusts that the face lies
+    /// entirely on one side of the other solid.
+    pub samples_per_face: usize,
+    /// Fraction of samples, in `(0.5, 1.0]`, that must agree for a face to be
+    /// classified `Inside` or `Outside`. Otherwise the face is marked
+    /// [`FaceClassification::NeedsSubdivision`].
+    pub vote_threshold: f64,
+}
+
+impl Default for ClassifyOptions {
+    fn default() -> Self {
+        Self {
+            samples_per_face: 1,
+            vote_threshold: 0.9,
+        }
+    }
 }
 
 /// Compute a sample point in the interior of a face.
@@ -359,9 +388,39 @@ pub fn classify_face(
     other_mesh: &TriangleMesh,
 ) -> FaceClassification {
     let sample = face_sample_point(brep, face_id);
+    if sample_is_inside(sample, outward_normal(brep, face_id), other_mesh) {
+        FaceClassification::Inside
+    } else {
+        FaceClassification::Outside
+    }
+}
+
+/// Classify a face, telling apart faces that lie on the other solid's boundary.
+///
+/// Tests points just behind and just in front of the face: if exactly one of
+/// them is inside the other solid, the face lies on its boundary and is
+/// [`FaceClassification::OnSame`] or [`FaceClassification::OnOpposite`]
+/// depending on which side the other solid is on. Takes twice the
+/// point-in-solid tests of [`classify_face`].
+pub fn classify_face_two_sided(
+    brep: &BRepSolid,
+    face_id: FaceId,
+    other_mesh: &TriangleMesh,
+) -> FaceClassification {
+    let sample = face_sample_point(brep, face_id);
+    let normal = outward_normal(brep, face_id);
+    let behind = sample_is_inside(sample, normal, other_mesh);
+    let in_front = sample_is_inside(sample, -normal, other_mesh);
+    match (behind, in_front) {
+        (true, true) => FaceClassification::Inside,
+        (false, false) => FaceClassification::Outside,
+        (true, false) => FaceClassification::OnSame,
+        (false, true) => FaceClassification::OnOpposite,
+    }
+}
 
-    // Offset the sample point slightly along the face normal
-    // to avoid landing exactly on the boundary
+/// Outward normal of a planar-ish face, from its loop winding.
+fn outward_normal(brep: &BRepSolid, face_id: FaceId) -> Vec3 {
     let face = &brep.topology.faces[face_id];
     let surface = &brep.geometry.surfaces[face.surface_index];
 
@@ -375,7 +434,7 @@ pub fn classify_face(
         .map(|he_id| brep.topology.vertices[brep.topology.half_edges[he_id].origin].point)
         .collect();
 
-    let oriented_normal = if outer_verts.len() >= 3 {
+    if outer_verts.len() >= 3 {
         let e1 = outer_verts[1] - outer_verts[0];
         let e2 = outer_verts[2] - outer_verts[0];
         let n = e1.cross(&e2);
@@ -398,19 +457,19 @@ pub fn classify_face(
             vcad_kernel_topo::Orientation::Forward => normal,
             vcad_kernel_topo::Orientation::Reversed => -normal,
         }
-    };
+    }
+}
 
+/// Whether a point on a face lies inside the other solid.
+fn sample_is_inside(sample: Point3, oriented_normal: Vec3, other_mesh: &TriangleMesh) -> bool {
     // Test the sample point offset slightly inward (negative normal)
     let eps = 1e-4;
     let inward_point = sample - eps * oriented_normal;
 
-    let is_inside = point_in_mesh(&inward_point, other_mesh);
-
-    if is_inside {
-        FaceClassification::Inside
-    } else {
-        FaceClassification::Outside
-    }
+    // The sample sits just inside this face, so for coincident or touching
+    // solids it is close to the other mesh's faces and edges, where a single
+    // ray is most likely to graze an edge.
+    point_in_mesh_robust(&inward_point, other_mesh)
 }
 
 /// Classify all faces of a solid relative to another solid.
@@ -430,10 +489,138 @@ pub fn classify_all_faces(
         .collect()
 }
 
+/// Classify all faces of a solid with [`classify_face_two_sided`].
+pub fn classify_all_faces_two_sided(
+    brep: &BRepSolid,
+    other: &BRepSolid,
+    segments: u32,
+) -> Vec<(FaceId, FaceClassification)> {
+    let other_mesh = tessellate_brep(other, segments);
+    brep.topology
+        .faces
+        .iter()
+        .map(|(face_id, _)| (face_id, classify_face_two_sided(brep, face_id, &other_mesh)))
+        .collect()
+}
+
+/// Classify all faces of a solid, voting over several samples per face.
+///
+/// Planar faces are sampled on a barycentric grid over a fan triangulation
+/// of their outer loop, skipping points outside the loop or inside holes.
+/// Faces whose samples disagree beyond `options.vote_threshold` are marked
+/// [`FaceClassification::NeedsSubdivision`] rather than classified
+/// wholesale. Curved faces, and all faces when `samples_per_face` is one,
+/// use the single-sample [`classify_face`].
+pub fn classify_all_faces_opts(
+    brep: &BRepSolid,
+    other: &BRepSolid,
+    segments: u32,
+    options: ClassifyOptions,
+) -> Vec<(FaceId, FaceClassification)> {
+    let other_mesh = tessellate_brep(other, segments);
+    brep.topology
+        .faces
+        .iter()
+        .map(|(face_id, _)| {
+            let samples = if options.samples_per_face > 1 {
+                face_sample_grid(brep, face_id, options.samples_per_face)
+            } else {
+                Vec::new()
+            };
+            if samples.is_empty() {
+                return (face_id, classify_face(brep, face_id, &other_mesh));
+            }
+
+            let normal = outward_normal(brep, face_id);
+            let inside = samples
+                .iter()
+                .filter(|&&p| sample_is_inside(p, normal, &other_mesh))
+                .count();
+            let inside_frac = inside as f64 / samples.len() as f64;
+            let class = if inside_frac >= options.vote_threshold {
+                FaceClassification::Inside
+            } else if 1.0 - inside_frac >= options.vote_threshold {
+                FaceClassification::Outside
+            } else {
+                FaceClassification::NeedsSubdivision
+            };
+            (face_id, class)
+        })
+        .collect()
+}
+
+/// About `count` points spread over the interior of a planar face.
+///
+/// Returns no points for curved or degenerate faces.
+fn face_sample_grid(brep: &BRepSolid, face_id: FaceId, count: usize) -> Vec<Point3> {
+    let topo = &brep.topology;
+    let face = &topo.faces[face_id];
+    let surface = &brep.geometry.surfaces[face.surface_index];
+    if surface.surface_type() != SurfaceKind::Plane {
+        return Vec::new();
+    }
+
+    let loop_points = |loop_id| -> Vec<Point3> {
+        topo.loop_half_edges(loop_id)
+            .map(|he_id| topo.vertices[topo.half_edges[he_id].origin].point)
+            .collect()
+    };
+    let outer = loop_points(face.outer_loop);
+    if outer.len() < 3 {
+        return Vec::new();
+    }
+
+    // 2D frame in the face plane
+    let origin = outer[0];
+    let normal = outward_normal(brep, face_id);
+    let u_axis = (outer[1] - origin).normalize();
+    let v_axis = normal.cross(&u_axis);
+    let to_2d = |p: &Point3| {
+        let d = p - origin;
+        Point2::new(d.dot(&u_axis), d.dot(&v_axis))
+    };
+    let outer_2d: Vec<Point2> = outer.iter().map(to_2d).collect();
+    let holes_2d: Vec<Vec<Point2>> = face
+        .inner_loops
+        .iter()
+        .map(|&l| loop_points(l).iter().map(to_2d).collect())
+        .collect();
+
+    // Subdivide each fan triangle n times per side and take the centroids of
+    // its upward-pointing sub-triangles: n(n+1)/2 interior points.
+    let n_tris = outer.len() - 2;
+    let per_tri = count.div_ceil(n_tris);
+    let n = (1..).find(|n| n * (n + 1) / 2 >= per_tri).unwrap_or(1);
+
+    let mut points = Vec::new();
+    for k in 1..=n_tris {
+        let (a, b, c) = (outer[0], outer[k], outer[k + 1]);
+        for i in 0..n {
+            for j in 0..n - i {
+                let s = (i as f64 + 1.0 / 3.0) / n as f64;
+                let t = (j as f64 + 1.0 / 3.0) / n as f64;
+                let p = a + (b - a) * s + (c - a) * t;
+                let p2 = to_2d(&p);
+                if point_in_polygon(&p2, &outer_2d)
+                    && !holes_2d.iter().any(|h| point_in_polygon(&p2, h))
+                {
+                    points.push(p);
+                }
+            }
+        }
+    }
+    points
+}
+
 /// Select which faces to keep from each solid based on the boolean operation.
 ///
 /// Returns `(faces_from_a, faces_from_b, reverse_b)`.
 /// `reverse_b` indicates that B's kept faces should have their orientation flipped.
+///
+/// For [`BooleanOp::Xor`] only the faces of each solid outside the other are
+/// kept: the outer boundary of the result. The faces of each solid inside the
+/// other also bound it, flipped, which a single `reverse_b` can't express;
+/// the pipeline sews XOR results from the classifications directly.
 pub fn select_faces(
     op: BooleanOp,
     classes_a: &[(FaceId, FaceClassification)],
@@ -454,6 +641,7 @@ pub fn select_faces(
             BooleanOp::Intersection => {
                 matches!(c, FaceClassification::Inside | FaceClassification::OnSame)
             }
+            BooleanOp::Xor => matches!(c, FaceClassification::Outside),
         })
         .map(|(f, _)| *f)
         .collect();
@@ -464,6 +652,7 @@ pub fn select_faces(
             BooleanOp::Union => matches!(c, FaceClassification::Outside),
             BooleanOp::Difference => matches!(c, FaceClassification::Inside),
             BooleanOp::Intersection => matches!(c, FaceClassification::Inside),
+            BooleanOp::Xor => matches!(c, FaceClassification::Outside),
         })
         .map(|(f, _)| *f)
         .collect();
@@ -526,6 +715,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_classify_straddling_face_needs_subdivision() {
+        // B covers the half of A with x > 5, overhanging it in y and z so no
+        // faces are coincident.
+        let a = make_cube(10.0, 10.0, 10.0);
+        let mut b = make_cube(15.0, 20.0, 20.0);
+        for (_, v) in &mut b.topology.vertices {
+            v.point.x += 5.0;
+            v.point.y -= 5.0;
+            v.point.z -= 5.0;
+        }
+        let options = ClassifyOptions {
+            samples_per_face: 16,
+            vote_threshold: 0.9,
+        };
+
+        let classes = classify_all_faces_opts(&a, &b, 32, options);
+        assert_eq!(classes.len(), 6);
+        for (face_id, class) in &classes {
+            let p = face_sample_point(&a, *face_id);
+            let expected = if p.x.abs() < 1e-9 {
+                FaceClassification::Outside
+            } else if (p.x - 10.0).abs() < 1e-9 {
+                FaceClassification::Inside
+            } else {
+                // The four faces spanning x = 0..10 cross B's boundary at x = 5.
+                FaceClassification::NeedsSubdivision
+            };
+            assert_eq!(*class, expected, "face sampled at {p:?}");
+        }
+
+        // The single-sample default classifies every face wholesale.
+        let classes = classify_all_faces_opts(&a, &b, 32, ClassifyOptions::default());
+        assert!(classes
+            .iter()
+            .all(|(_, c)| *c != FaceClassification::NeedsSubdivision));
+    }
+
     #[test]
     fn test_select_union() {
         let classes_a = vec![
@@ -2,7 +2,8 @@
 
 //! CSG boolean operations on B-rep solids for the vcad kernel.
 //!
-//! Implements union, difference, and intersection of B-rep solids.
+//! Implements union, difference, intersection, and symmetric difference of
+//! B-rep solids.
 //!
 //! The boolean pipeline has 4 stages:
 //! 1. **AABB filter** — broadphase to find candidate face pairs
@@ -27,7 +28,8 @@ pub mod trim;
 
 // Re-export public API
 pub use api::{boolean_op, BooleanOp, BooleanResult};
-pub use mesh::point_in_mesh;
+pub use bbox::Aabb;
+pub use mesh::{point_in_mesh, point_in_mesh_robust};
 
 #[cfg(test)]
 mod tests {
@@ -163,6 +165,44 @@ mod tests {
         assert!(mesh.num_triangles() > 0);
     }
 
+    #[test]
+    fn test_disjoint_intersection_into_brep() {
+        let a = make_cube(10.0, 10.0, 10.0);
+        let mut b = make_cube(10.0, 10.0, 10.0);
+        for (_, v) in &mut b.topology.vertices {
+            v.point.x += 20.0;
+        }
+        let result = boolean_op(&a, &b, BooleanOp::Intersection, 32);
+        assert!(matches!(result, BooleanResult::Mesh(_)));
+        assert!(result.is_empty());
+
+        let empty = result.into_brep();
+        assert!(empty.topology.faces.is_empty());
+        assert_eq!(empty.topology.solids.len(), 1);
+
+        // The empty solid can be chained into further booleans.
+        let union = boolean_op(&empty, &a, BooleanOp::Union, 32);
+        assert!(!union.is_empty());
+        assert_eq!(union.into_brep().topology.faces.len(), 6);
+    }
+
+    #[test]
+    fn test_xor() {
+        let a = make_cube(10.0, 10.0, 10.0);
+        let result = boolean_op(&a, &make_cube(10.0, 10.0, 10.0), BooleanOp::Xor, 32);
+        assert!(result.is_empty());
+
+        // B overlaps A's far corner: the shared 5×5×5 block is left out
+        let mut b = make_cube(10.0, 10.0, 10.0);
+        translate_brep(&mut b, 5.0, 5.0, 5.0);
+        let result = boolean_op(&a, &b, BooleanOp::Xor, 32);
+        assert!(!result.is_empty());
+        let mesh = result.to_mesh(32);
+        assert!(!point_in_mesh(&Point3::new(7.5, 7.5, 7.5), &mesh));
+        assert!(point_in_mesh(&Point3::new(2.5, 2.5, 2.5), &mesh));
+        assert!(point_in_mesh(&Point3::new(12.5, 12.5, 12.5), &mesh));
+    }
+
     /// Test boolean difference with a hole completely inside a plate.
     #[test]
     fn test_plate_with_hole() {
@@ -1019,13 +1059,13 @@ mod tests {
         translate_brep(&mut cyl3, 20.0, 30.0, -5.0);
 
         // First difference
-        let temp1 = boolean_op(&cube, &cyl1, BooleanOp::Difference, 32)
-            .into_brep()
-            .expect("Expected BRep result");
+        let result1 = boolean_op(&cube, &cyl1, BooleanOp::Difference, 32);
+        assert!(result1.as_brep().is_some(), "Expected BRep result");
+        let temp1 = result1.into_brep();
         // Second difference
-        let temp2 = boolean_op(&temp1, &cyl2, BooleanOp::Difference, 32)
-            .into_brep()
-            .expect("Expected BRep result");
+        let result2 = boolean_op(&temp1, &cyl2, BooleanOp::Difference, 32);
+        assert!(result2.as_brep().is_some(), "Expected BRep result");
+        let temp2 = result2.into_brep();
         // Third difference
         let result = boolean_op(&temp2, &cyl3, BooleanOp::Difference, 32);
 
@@ -3,6 +3,21 @@
 use vcad_kernel_math::Point3;
 use vcad_kernel_tessellate::TriangleMesh;
 
+/// Slightly tilted default ray direction, chosen to avoid hitting axis-aligned
+/// edges and vertices exactly in the common case.
+const DEFAULT_RAY: [f64; 3] = [1.0, 1e-7, 1.3e-7];
+
+/// Widely spread ray directions for [`point_in_mesh_robust`].
+const VOTE_RAYS: [[f64; 3]; 3] = [
+    DEFAULT_RAY,
+    [0.2718, 0.9138, 0.1414],
+    [-0.3162, 0.2236, 0.9219],
+];
+
+/// Maximum number of extra rays cast by [`point_in_mesh_robust`] when the
+/// first votes disagree.
+const MAX_RETRIES: u32 = 8;
+
 /// Test if a point is inside a closed triangle mesh using ray casting with exact predicates.
 ///
 /// Uses Shewchuk's exact orient3d predicate to robustly handle boundary cases where
@@ -10,17 +25,65 @@ use vcad_kernel_tessellate::TriangleMesh;
 /// to avoid edge/vertex hits in the common case, with exact predicates as fallback.
 ///
 /// Casts a ray along a tilted direction. Odd crossing count = inside, even = outside.
+/// A ray that grazes an edge or vertex shared by several triangles can miscount;
+/// see [`point_in_mesh_robust`] for a slower voting variant.
 pub fn point_in_mesh(point: &Point3, mesh: &TriangleMesh) -> bool {
+    ray_parity(point, mesh, DEFAULT_RAY)
+}
+
+/// Test if a point is inside a closed triangle mesh by majority vote over
+/// several rays.
+///
+/// Casts three rays in widely spread directions. If they disagree, one of them
+/// grazed an edge or vertex, so up to eight more rays in pseudo-random
+/// directions are cast until the vote is decisive (a majority of at least
+/// two). About three times slower than [`point_in_mesh`].
+pub fn point_in_mesh_robust(point: &Point3, mesh: &TriangleMesh) -> bool {
+    let mut inside = 0u32;
+    let mut outside = 0u32;
+    for dir in VOTE_RAYS {
+        if ray_parity(point, mesh, dir) {
+            inside += 1;
+        } else {
+            outside += 1;
+        }
+    }
+
+    // Deterministic xorshift so results are reproducible.
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+    };
+    let mut retries = 0;
+    while inside != 0 && outside != 0 && inside.abs_diff(outside) < 2 && retries < MAX_RETRIES {
+        retries += 1;
+        let dir = [next(), next(), next()];
+        if dir.iter().map(|c| c * c).sum::<f64>() < 1e-6 {
+            continue;
+        }
+        if ray_parity(point, mesh, dir) {
+            inside += 1;
+        } else {
+            outside += 1;
+        }
+    }
+
+    inside > outside
+}
+
+/// Parity test along a single ray from `point` in direction `ray_dir`.
+///
+/// Points lying on a triangle count as inside.
+fn ray_parity(point: &Point3, mesh: &TriangleMesh, ray_dir: [f64; 3]) -> bool {
     use vcad_kernel_math::predicates::{orient3d, Sign};
 
     let verts = &mesh.vertices;
     let indices = &mesh.indices;
     let mut crossings = 0u32;
 
-    // Slightly tilted ray direction to avoid hitting edges/vertices exactly
-    // The exact predicates handle remaining boundary cases robustly
-    let ray_dir = [1.0f64, 1e-7, 1.3e-7];
-
     for tri in indices.chunks(3) {
         let i0 = tri[0] as usize * 3;
         let i1 = tri[1] as usize * 3;
@@ -153,3 +216,59 @@ fn point_in_triangle_coplanar(p: &Point3, v0: &Point3, v1: &Point3, v2: &Point3)
 
     all_non_neg || all_non_pos
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 10mm cube whose +X face is a fan of four triangles around its
+    /// center vertex (10, 5, 5).
+    fn fanned_cube() -> TriangleMesh {
+        #[rustfmt::skip]
+        let vertices = vec![
+            0.0, 0.0, 0.0,    10.0, 0.0, 0.0,   10.0, 10.0, 0.0,   0.0, 10.0, 0.0,
+            0.0, 0.0, 10.0,   10.0, 0.0, 10.0,  10.0, 10.0, 10.0,  0.0, 10.0, 10.0,
+            10.0, 5.0, 5.0,
+        ];
+        #[rustfmt::skip]
+        let indices = vec![
+            0, 2, 1, 0, 3, 2, // -Z
+            4, 5, 6, 4, 6, 7, // +Z
+            0, 1, 5, 0, 5, 4, // -Y
+            3, 7, 6, 3, 6, 2, // +Y
+            0, 4, 7, 0, 7, 3, // -X
+            8, 1, 2, 8, 2, 6, 8, 6, 5, 8, 5, 1, // +X fan
+        ];
+        TriangleMesh {
+            normals: vec![0.0; vertices.len()],
+            vertices,
+            indices,
+        }
+    }
+
+    #[test]
+    fn test_robust_handles_ray_through_shared_edge() {
+        let mesh = fanned_cube();
+        // Inside point whose default ray passes through the midpoint of the
+        // fan edge (10, 5, 5)–(10, 10, 0), shared by two triangles.
+        let k = 0.5;
+        let p = Point3::new(
+            10.0 - k * DEFAULT_RAY[0],
+            7.5 - k * DEFAULT_RAY[1],
+            2.5 - k * DEFAULT_RAY[2],
+        );
+        assert!(
+            !point_in_mesh(&p, &mesh),
+            "single ray double-counts the edge"
+        );
+        assert!(point_in_mesh_robust(&p, &mesh));
+    }
+
+    #[test]
+    fn test_robust_agrees_on_clear_points() {
+        let mesh = fanned_cube();
+        assert!(point_in_mesh_robust(&Point3::new(3.0, 4.0, 6.0), &mesh));
+        assert!(!point_in_mesh_robust(&Point3::new(13.0, 4.0, 6.0), &mesh));
+        assert!(!point_in_mesh_robust(&Point3::new(5.0, -1.0, 5.0), &mesh));
+    }
+}
@@ -35,8 +35,8 @@ pub(crate) fn non_overlapping_boolean(
     _segments: u32,
 ) -> BooleanResult {
     match op {
-        BooleanOp::Union => {
-            // Union of non-overlapping = both solids combined
+        BooleanOp::Union | BooleanOp::Xor => {
+            // Union (or XOR) of non-overlapping = both solids combined
             let faces_a: Vec<_> = solid_a.topology.faces.keys().collect();
             let faces_b: Vec<_> = solid_b.topology.faces.keys().collect();
             let result = sew::sew_faces(solid_a, &faces_a, solid_b, &faces_b, false, 1e-6);
@@ -308,8 +308,9 @@ pub(crate) fn brep_boolean(
     let mut a = solid_a.clone();
     let mut b = solid_b.clone();
 
-    // 1. Find candidate face pairs via AABB filtering
-    let pairs = bbox::find_candidate_face_pairs(&a, &b);
+    // 1. Find candidate face pairs via AABB filtering. The clones share face
+    // IDs with the inputs, which keep the solid AABBs cached by `boolean_op`.
+    let pairs = bbox::find_candidate_face_pairs(solid_a, solid_b);
     debug_bool!("\n--- Stage 1: AABB filtering ---");
     debug_bool!("Candidate face pairs: {}", pairs.len());
 
@@ -469,8 +470,14 @@ pub(crate) fn brep_boolean(
     debug_bool!("Solid A has {} faces after splits", a.topology.faces.len());
     debug_bool!("Solid B has {} faces after splits", b.topology.faces.len());
 
-    let classes_a = classify::classify_all_faces(&a, &b, segments);
-    let classes_b = classify::classify_all_faces(&b, &a, segments);
+    // XOR drops faces shared by both solids, so it needs them told apart
+    let classify_all = if op == BooleanOp::Xor {
+        classify::classify_all_faces_two_sided
+    } else {
+        classify::classify_all_faces
+    };
+    let classes_a = classify_all(&a, &b, segments);
+    let classes_b = classify_all(&b, &a, segments);
 
     debug_bool!("\nClassification of A faces:");
     for (fid, _class) in &classes_a {
@@ -501,6 +508,9 @@ pub(crate) fn brep_boolean(
     }
 
     // 4. Select and sew
+    if op == BooleanOp::Xor {
+        return BooleanResult::BRep(Box::new(sew_xor(&a, &classes_a, &b, &classes_b)));
+    }
     let (keep_a, keep_b, reverse_b) = classify::select_faces(op, &classes_a, &classes_b);
 
     debug_bool!("\n--- Stage 4: Selection (op={:?}) ---", op);
@@ -541,3 +551,45 @@ pub(crate) fn brep_boolean(
 
     BooleanResult::BRep(Box::new(result))
 }
+
+/// Sew the result of an XOR from the two-sided classifications of both
+/// solids' faces.
+///
+/// A ⊕ B = (A − B) ∪ (B − A): each part is bounded by its own faces outside
+/// the other solid and by the other solid's faces inside it, flipped. Faces
+/// on the boundary of both solids bound neither part and are dropped.
+fn sew_xor(
+    a: &BRepSolid,
+    classes_a: &[(FaceId, classify::FaceClassification)],
+    b: &BRepSolid,
+    classes_b: &[(FaceId, classify::FaceClassification)],
+) -> BRepSolid {
+    use classify::FaceClassification::{Inside, Outside};
+
+    let pick = |classes: &[(FaceId, classify::FaceClassification)], want| -> Vec<FaceId> {
+        classes
+            .iter()
+            .filter(|(_, c)| *c == want)
+            .map(|(f, _)| *f)
+            .collect()
+    };
+    let a_minus_b = sew::sew_faces(
+        a,
+        &pick(classes_a, Outside),
+        b,
+        &pick(classes_b, Inside),
+        true,
+        1e-6,
+    );
+    let b_minus_a = sew::sew_faces(
+        b,
+        &pick(classes_b, Outside),
+        a,
+        &pick(classes_a, Inside),
+        true,
+        1e-6,
+    );
+    let faces_a: Vec<_> = a_minus_b.topology.faces.keys().collect();
+    let faces_b: Vec<_> = b_minus_a.topology.faces.keys().collect();
+    sew::sew_faces(&a_minus_b, &faces_a, &b_minus_a, &faces_b, false, 1e-6)
+}
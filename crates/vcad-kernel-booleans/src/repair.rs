@@ -8,7 +8,7 @@
 
 use std::collections::HashMap;
 
-use vcad_kernel_math::Point3;
+use vcad_kernel_math::{Point3, Quantize};
 use vcad_kernel_topo::{HalfEdgeId, Topology};
 
 /// Repair common topology issues in-place.
@@ -227,11 +227,8 @@ impl VertexKey {
         } else {
             1.0e6
         };
-        Self {
-            x: (p.x * scale).round() as i64,
-            y: (p.y * scale).round() as i64,
-            z: (p.z * scale).round() as i64,
-        }
+        let [x, y, z] = p.quantize(scale);
+        Self { x, y, z }
     }
 }
 
@@ -43,7 +43,7 @@
 //! ```
 
 use vcad_kernel_geom::{GeometryStore, SurfaceKind};
-use vcad_kernel_math::{Point3, Vec3};
+use vcad_kernel_math::{Point3, Quantize, Vec3};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_topo::{FaceId, Orientation, ShellType, Topology};
 
@@ -211,21 +211,13 @@ pub fn sew_faces(
     if all_faces.is_empty() {
         let shell = topo.add_shell(Vec::new(), ShellType::Outer);
         let solid = topo.add_solid(shell);
-        return BRepSolid {
-            topology: topo,
-            geometry: geom,
-            solid_id: solid,
-        };
+        return BRepSolid::new(topo, geom, solid);
     }
 
     let shell = topo.add_shell(all_faces, ShellType::Outer);
     let solid = topo.add_solid(shell);
 
-    BRepSolid {
-        topology: topo,
-        geometry: geom,
-        solid_id: solid,
-    }
+    BRepSolid::new(topo, geom, solid)
 }
 
 /// Copy selected faces from a source BRep into the target topology/geometry.
@@ -411,11 +403,8 @@ impl VertexPosKey {
     fn from_point(p: &Point3) -> Self {
         // Quantize to ~1e-8 resolution
         let scale = 1e8;
-        Self {
-            x: (p.x * scale).round() as i64,
-            y: (p.y * scale).round() as i64,
-            z: (p.z * scale).round() as i64,
-        }
+        let [x, y, z] = p.quantize(scale);
+        Self { x, y, z }
     }
 }
 
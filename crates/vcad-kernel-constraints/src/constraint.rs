@@ -103,6 +103,24 @@ pub enum Constraint {
         at_point: EntityRef,
     },
 
+    /// Two segments (lines or arcs) meet at a shared endpoint with the same
+    /// tangent direction (G1 continuity).
+    ///
+    /// The end of each segment nearest the point is used, so the segments
+    /// may run in either direction. Unlike [`Constraint::Tangent`], the
+    /// chain can't double back on itself at the joint.
+    ///
+    /// Error: signed angle between the tangent arriving along one segment
+    /// and the tangent leaving along the other, in `[-π, π]`
+    G1 {
+        /// First line or arc entity.
+        segment_a: EntityId,
+        /// Second line or arc entity.
+        segment_b: EntityId,
+        /// Point where the segments join.
+        at_point: EntityRef,
+    },
+
     /// Two lines have equal length.
     ///
     /// Error: `|line_a| - |line_b|`
@@ -187,6 +205,63 @@ pub enum Constraint {
         axis: EntityId,
     },
 
+    /// Two points are symmetric about a center point (180° rotation).
+    ///
+    /// Error: `[a.x + b.x - 2 * c.x, a.y + b.y - 2 * c.y]`
+    PointSymmetric {
+        /// First point.
+        point_a: EntityRef,
+        /// Second point.
+        point_b: EntityRef,
+        /// Center of symmetry.
+        center: EntityRef,
+    },
+
+    /// The radius of an arc or circle equals the length of a line.
+    ///
+    /// Error: `r - |line|`
+    RadiusEqualsLength {
+        /// Circle/arc entity.
+        circle: EntityId,
+        /// Line entity.
+        line: EntityId,
+    },
+
+    /// The distance between two points equals the distance between two
+    /// others.
+    ///
+    /// Error: `|a - b| - |c - d|`
+    EqualDistance {
+        /// First point of the first pair.
+        point_a: EntityRef,
+        /// Second point of the first pair.
+        point_b: EntityRef,
+        /// First point of the second pair.
+        point_c: EntityRef,
+        /// Second point of the second pair.
+        point_d: EntityRef,
+    },
+
+    /// A point stays at fixed coordinates in the frame of two others.
+    ///
+    /// With `u = axis - origin` and `v` = `u` rotated by +90°, the point
+    /// is held at `origin + along * u + across * v`, so it follows the
+    /// frame as it translates and rotates, and can't mirror across it.
+    ///
+    /// Error: `[p.x - t.x, p.y - t.y]` for that target `t`
+    FramePoint {
+        /// The constrained point.
+        point: EntityRef,
+        /// Origin of the frame.
+        origin: EntityRef,
+        /// Point the frame's first axis runs to.
+        axis: EntityRef,
+        /// Coordinate along `axis - origin`, in units of its length.
+        along: f64,
+        /// Coordinate across `axis - origin`, in units of its length.
+        across: f64,
+    },
+
     // =========================================================================
     // Dimensional constraints (explicit values)
     // =========================================================================
@@ -275,6 +350,35 @@ pub enum Constraint {
         /// Target diameter.
         diameter: f64,
     },
+
+    /// Swept angle of an arc equals a value.
+    ///
+    /// The sweep is measured from start to end around the center in the
+    /// arc's direction, in `[0, 2π)`.
+    ///
+    /// Error: `sweep - angle_rad`, wrapped to `[-π, π]`
+    ArcAngle {
+        /// Arc entity.
+        arc: EntityId,
+        /// Target swept angle in radians.
+        angle_rad: f64,
+    },
+
+    // =========================================================================
+    // Soft constraints (preferences)
+    // =========================================================================
+    /// A point prefers to lie on a square grid through the sketch origin.
+    ///
+    /// Soft: the sketch solver weights it far below other constraints, and
+    /// it does not count towards the degrees of freedom.
+    ///
+    /// Error: `[p.x - s * round(p.x / s), p.y - s * round(p.y / s)]`
+    GridAligned {
+        /// Point to align.
+        point: EntityRef,
+        /// Grid spacing.
+        spacing: f64,
+    },
 }
 
 impl Constraint {
@@ -289,9 +393,38 @@ impl Constraint {
             Constraint::Concentric { .. } => 2,
             Constraint::Midpoint { .. } => 2,
             Constraint::Symmetric { .. } => 2,
+            Constraint::PointSymmetric { .. } => 2,
+            Constraint::FramePoint { .. } => 2,
+            Constraint::GridAligned { .. } => 2,
             _ => 1,
         }
     }
+
+    /// Whether this is a soft constraint: a preference that yields to the
+    /// others when they conflict.
+    pub fn is_soft(&self) -> bool {
+        matches!(self, Constraint::GridAligned { .. })
+    }
+
+    /// Returns a mutable reference to the target value of a dimensional
+    /// constraint, or `None` for geometric constraints.
+    ///
+    /// Angles are in radians, as stored.
+    pub fn dimension_mut(&mut self) -> Option<&mut f64> {
+        match self {
+            Constraint::Distance { distance, .. }
+            | Constraint::PointLineDistance { distance, .. } => Some(distance),
+            Constraint::Angle { angle_rad, .. } | Constraint::ArcAngle { angle_rad, .. } => {
+                Some(angle_rad)
+            }
+            Constraint::Radius { radius, .. } => Some(radius),
+            Constraint::Length { length, .. } => Some(length),
+            Constraint::HorizontalDistance { x, .. } => Some(x),
+            Constraint::VerticalDistance { y, .. } => Some(y),
+            Constraint::Diameter { diameter, .. } => Some(diameter),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -318,4 +451,19 @@ mod tests {
         };
         assert_eq!(fixed.num_residuals(), 2);
     }
+
+    #[test]
+    fn test_dimension_mut() {
+        let mut length = Constraint::Length {
+            line: EntityId::default(),
+            length: 4.0,
+        };
+        *length.dimension_mut().unwrap() = 6.0;
+        assert!(matches!(length, Constraint::Length { length, .. } if length == 6.0));
+
+        let mut horizontal = Constraint::Horizontal {
+            line: EntityId::default(),
+        };
+        assert!(horizontal.dimension_mut().is_none());
+    }
 }
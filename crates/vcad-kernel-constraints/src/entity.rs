@@ -31,6 +31,8 @@ pub struct SketchLine {
     pub start: EntityId,
     /// Entity ID of the end point.
     pub end: EntityId,
+    /// Construction geometry is solved but excluded from exported profiles.
+    pub is_construction: bool,
 }
 
 /// A circular arc entity.
@@ -47,6 +49,8 @@ pub struct SketchArc {
     pub center: EntityId,
     /// If true, arc goes counter-clockwise from start to end.
     pub ccw: bool,
+    /// Construction geometry is solved but excluded from exported profiles.
+    pub is_construction: bool,
 }
 
 /// A circle entity.
@@ -58,6 +62,8 @@ pub struct SketchCircle {
     pub center: EntityId,
     /// Index of the radius in the parameter vector.
     pub param_radius: usize,
+    /// Construction geometry is solved but excluded from exported profiles.
+    pub is_construction: bool,
 }
 
 /// A sketch entity (point, line, arc, or circle).
@@ -94,6 +100,18 @@ impl SketchEntity {
         matches!(self, SketchEntity::Circle(_))
     }
 
+    /// Check if this entity is construction (reference) geometry.
+    ///
+    /// Points are never construction geometry.
+    pub fn is_construction(&self) -> bool {
+        match self {
+            SketchEntity::Point(_) => false,
+            SketchEntity::Line(l) => l.is_construction,
+            SketchEntity::Arc(a) => a.is_construction,
+            SketchEntity::Circle(c) => c.is_construction,
+        }
+    }
+
     /// Get the point data if this is a point entity.
     pub fn as_point(&self) -> Option<&SketchPoint> {
         match self {
@@ -143,6 +161,7 @@ mod tests {
         let line = SketchEntity::Line(SketchLine {
             start: EntityId::default(),
             end: EntityId::default(),
+            is_construction: false,
         });
         assert!(line.is_line());
         assert!(!line.is_point());
@@ -6,6 +6,7 @@
 use crate::entity::{EntityId, SketchEntity};
 use crate::sketch::Sketch2D;
 use thiserror::Error;
+use vcad_kernel_math::hull::convex_hull_2d;
 use vcad_kernel_math::Point2;
 use vcad_kernel_sketch::{SketchProfile, SketchSegment};
 
@@ -31,6 +32,11 @@ pub enum ExportError {
     /// Failed to order segments into a closed loop.
     #[error("could not order segments into a closed loop")]
     CannotOrderSegments,
+
+    /// The sketch's points span no area, so their hull has fewer than three
+    /// vertices.
+    #[error("points span no area: convex hull has {0} vertices")]
+    DegenerateHull(usize),
 }
 
 /// A segment with its start and end point coordinates (for sorting).
@@ -44,8 +50,9 @@ struct OrderedSegment {
 impl Sketch2D {
     /// Export the sketch to a `SketchProfile`.
     ///
-    /// This collects all line and arc entities, orders them into a closed loop,
-    /// and creates a `SketchProfile` that can be used with extrude/revolve.
+    /// This collects all line and arc entities that are not construction
+    /// geometry, orders them into a closed loop, and creates a `SketchProfile`
+    /// that can be used with extrude/revolve.
     ///
     /// # Errors
     ///
@@ -58,6 +65,9 @@ impl Sketch2D {
         let mut ordered_segments = Vec::new();
 
         for (_id, entity) in &self.entities {
+            if entity.is_construction() {
+                continue;
+            }
             match entity {
                 SketchEntity::Line(line) => {
                     let start = self.get_point_2d(line.start)?;
@@ -110,6 +120,47 @@ impl Sketch2D {
         })
     }
 
+    /// Export the convex hull of all the sketch's points as a `SketchProfile`.
+    ///
+    /// Every point entity counts, construction geometry and arc centers
+    /// included; circles contribute only their centers. The profile is a
+    /// counter-clockwise loop of lines, handy for bounding outlines.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExportError::DegenerateHull`] if the points are all
+    /// coincident or collinear.
+    pub fn hull_profile(&self) -> Result<SketchProfile, ExportError> {
+        let points: Vec<Point2> = self
+            .entities
+            .values()
+            .filter_map(|entity| match entity {
+                SketchEntity::Point(p) => Some(Point2::new(
+                    self.parameters[p.param_x],
+                    self.parameters[p.param_y],
+                )),
+                _ => None,
+            })
+            .collect();
+        let hull = convex_hull_2d(&points);
+        if hull.len() < 3 {
+            return Err(ExportError::DegenerateHull(hull.len()));
+        }
+
+        let segments = hull
+            .iter()
+            .zip(hull.iter().cycle().skip(1))
+            .map(|(&start, &end)| SketchSegment::Line { start, end })
+            .collect();
+        SketchProfile::new(
+            self.origin,
+            *self.x_dir.as_ref(),
+            *self.y_dir.as_ref(),
+            segments,
+        )
+        .map_err(|_| ExportError::DegenerateHull(hull.len()))
+    }
+
     /// Get a point's 2D coordinates.
     fn get_point_2d(&self, id: EntityId) -> Result<Point2, ExportError> {
         let entity = self
@@ -277,6 +328,71 @@ mod tests {
         assert!((verts[1].y - 0.0).abs() < 1e-5);
     }
 
+    #[test]
+    fn test_export_skips_construction_geometry() {
+        let mut sketch = Sketch2D::new();
+
+        let p0 = sketch.add_point(0.0, 0.0);
+        let p1 = sketch.add_point(10.0, 0.0);
+        let p2 = sketch.add_point(9.0, 12.0); // Intentionally off
+        let p3 = sketch.add_point(0.0, 10.0);
+
+        let l0 = sketch.add_line(p0, p1);
+        let l1 = sketch.add_line(p1, p2);
+        let _l2 = sketch.add_line(p2, p3);
+        let l3 = sketch.add_line(p3, p0);
+
+        // The diagonal's length pins the free corner.
+        let diagonal = sketch.add_construction_line(p0, p2);
+
+        sketch.constrain_fixed(EntityRef::Point(p0), 0.0, 0.0);
+        sketch.constrain_horizontal(l0);
+        sketch.constrain_vertical(l1);
+        sketch.constrain_vertical(l3);
+        sketch.constrain_length(l0, 10.0);
+        sketch.constrain_length(l3, 10.0);
+        sketch.constrain_length(diagonal, 200.0_f64.sqrt());
+
+        let result = sketch.solve_default();
+        assert!(result.converged);
+        let (x2, y2) = sketch.get_point(p2).unwrap();
+        assert!((x2 - 10.0).abs() < 1e-5);
+        assert!((y2 - 10.0).abs() < 1e-5);
+
+        let profile = sketch.to_profile().unwrap();
+        assert_eq!(profile.segments.len(), 4);
+
+        // Turning the diagonal into real geometry breaks the loop.
+        sketch.set_construction(diagonal, false);
+        assert!(sketch.to_profile().is_err());
+    }
+
+    #[test]
+    fn test_hull_profile() {
+        let mut sketch = Sketch2D::new();
+        sketch.add_circle_by_coords(3.0, 2.0, 1.5);
+        let a = sketch.add_point(0.0, 0.0);
+        let b = sketch.add_point(8.0, 0.0);
+        sketch.add_line(a, b);
+        sketch.add_point(8.0, 5.0);
+        sketch.add_point(0.0, 5.0);
+        sketch.add_point(4.0, 5.0);
+
+        let profile = sketch.hull_profile().unwrap();
+        assert_eq!(profile.segments.len(), 4);
+        assert!(profile.is_ccw());
+        assert!((profile.signed_area() - 40.0).abs() < 1e-9);
+
+        let mut collinear = Sketch2D::new();
+        collinear.add_point(0.0, 0.0);
+        collinear.add_point(1.0, 1.0);
+        collinear.add_point(2.0, 2.0);
+        assert!(matches!(
+            collinear.hull_profile(),
+            Err(ExportError::DegenerateHull(2))
+        ));
+    }
+
     #[test]
     fn test_export_no_segments() {
         let mut sketch = Sketch2D::new();
@@ -0,0 +1,321 @@
+//! Constraint inference from roughly drawn geometry.
+//!
+//! Imported polylines and freehand sketches come out nearly closed and
+//! nearly square. [`Sketch2D::infer_constraints`] adds the constraints that
+//! make them exactly so, without locking anything down twice.
+
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::constraint::{Constraint, EntityRef};
+use crate::entity::EntityId;
+use crate::sketch::Sketch2D;
+
+/// Tolerances for [`Sketch2D::infer_constraints`].
+#[derive(Debug, Clone)]
+pub struct InferOptions {
+    /// Points closer than this are made coincident, and lines whose lengths
+    /// differ by less than this are made equal.
+    pub distance_tolerance: f64,
+    /// Lines within this angle of an axis, or of being parallel or
+    /// perpendicular to each other, are snapped to it (degrees).
+    pub angle_tolerance_deg: f64,
+}
+
+impl Default for InferOptions {
+    fn default() -> Self {
+        Self {
+            distance_tolerance: 0.1,
+            angle_tolerance_deg: 2.0,
+        }
+    }
+}
+
+/// What a direction class of lines is aligned with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Horizontal,
+    Vertical,
+    /// The first line found along this direction.
+    Along(EntityId),
+}
+
+impl Sketch2D {
+    /// Add the constraints that rough geometry nearly satisfies.
+    ///
+    /// Looks for, in order:
+    /// - points closer than the distance tolerance: `Coincident`
+    /// - lines near an axis: `Horizontal` / `Vertical`
+    /// - other lines nearly parallel or perpendicular to an earlier line:
+    ///   `Parallel` / `Perpendicular`
+    /// - lines of nearly the same length and different direction:
+    ///   `EqualLength`
+    ///
+    /// Each new constraint ties an entity to the first match found, so
+    /// nothing is constrained twice: three close points give two coincident
+    /// constraints, not three. Lines that already carry a direction
+    /// constraint and points already made coincident are left alone. Equal
+    /// lengths are not inferred between parallel lines, since these
+    /// usually follow from the other constraints already (opposite sides of
+    /// a rectangle).
+    ///
+    /// The constraints are appended to [`Self::constraints`] and returned
+    /// so they can be reviewed. Nothing is solved.
+    pub fn infer_constraints(&mut self, opts: InferOptions) -> Vec<Constraint> {
+        let mut added = self.infer_coincident(opts.distance_tolerance);
+        let directions = self.infer_directions(opts.angle_tolerance_deg.to_radians(), &mut added);
+        self.infer_equal_lengths(opts.distance_tolerance, &directions, &mut added);
+        self.constraints.extend(added.iter().cloned());
+        added
+    }
+
+    /// Coincident constraints between close points not yet joined.
+    fn infer_coincident(&self, tolerance: f64) -> Vec<Constraint> {
+        let points: Vec<(EntityId, (f64, f64))> = self
+            .entities
+            .iter()
+            .filter(|(_, e)| e.is_point())
+            .filter_map(|(id, _)| Some((id, self.get_point(id)?)))
+            .collect();
+        let index: HashMap<EntityId, usize> = points
+            .iter()
+            .enumerate()
+            .map(|(i, &(id, _))| (id, i))
+            .collect();
+
+        // Union-find over points, seeded with the existing coincidences
+        let mut parent: Vec<usize> = (0..points.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+        for c in &self.constraints {
+            if let Constraint::Coincident { point_a, point_b } = *c {
+                let a = self.point_entity(point_a).and_then(|id| index.get(&id));
+                let b = self.point_entity(point_b).and_then(|id| index.get(&id));
+                if let (Some(&a), Some(&b)) = (a, b) {
+                    let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+                    parent[rb] = ra;
+                }
+            }
+        }
+
+        let mut added = Vec::new();
+        for i in 0..points.len() {
+            let (id_i, (xi, yi)) = points[i];
+            for (j, &(id_j, (xj, yj))) in points.iter().enumerate().take(i) {
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                if ri != rj && (xi - xj).hypot(yi - yj) < tolerance {
+                    parent[ri] = rj;
+                    added.push(Constraint::Coincident {
+                        point_a: EntityRef::Point(id_j),
+                        point_b: EntityRef::Point(id_i),
+                    });
+                }
+            }
+        }
+        added
+    }
+
+    /// Horizontal, vertical, parallel, and perpendicular constraints.
+    ///
+    /// Returns the direction class each unconstrained line was put in.
+    fn infer_directions(
+        &self,
+        tolerance: f64,
+        added: &mut Vec<Constraint>,
+    ) -> HashMap<EntityId, usize> {
+        let constrained: HashSet<EntityId> = self
+            .constraints
+            .iter()
+            .flat_map(|c| match *c {
+                Constraint::Horizontal { line } | Constraint::Vertical { line } => {
+                    vec![line]
+                }
+                Constraint::Parallel { line_a, line_b }
+                | Constraint::Perpendicular { line_a, line_b }
+                | Constraint::Angle { line_a, line_b, .. } => vec![line_a, line_b],
+                _ => Vec::new(),
+            })
+            .collect();
+
+        // Angle in [0, π) between two directions, folded to [0, π/2]
+        let between = |a: f64, b: f64| {
+            let d = (a - b).rem_euclid(PI);
+            d.min(PI - d)
+        };
+
+        let mut classes = vec![
+            (0.0, Direction::Horizontal),
+            (FRAC_PI_2, Direction::Vertical),
+        ];
+        let mut class_of = HashMap::new();
+        for line in self.line_ids() {
+            if constrained.contains(&line) {
+                continue;
+            }
+            let Some(((x1, y1), (x2, y2))) = self.get_line_endpoints(line) else {
+                continue;
+            };
+            if x1 == x2 && y1 == y2 {
+                continue;
+            }
+            let angle = (y2 - y1).atan2(x2 - x1).rem_euclid(PI);
+
+            if let Some(k) = classes
+                .iter()
+                .position(|&(a, _)| between(angle, a) < tolerance)
+            {
+                added.push(match classes[k].1 {
+                    Direction::Horizontal => Constraint::Horizontal { line },
+                    Direction::Vertical => Constraint::Vertical { line },
+                    Direction::Along(reference) => Constraint::Parallel {
+                        line_a: reference,
+                        line_b: line,
+                    },
+                });
+                class_of.insert(line, k);
+                continue;
+            }
+
+            // Anything perpendicular to an axis would have matched the other
+            let normal = classes.iter().find_map(|&(a, d)| match d {
+                Direction::Along(reference) if (FRAC_PI_2 - between(angle, a)) < tolerance => {
+                    Some(reference)
+                }
+                _ => None,
+            });
+            if let Some(reference) = normal {
+                added.push(Constraint::Perpendicular {
+                    line_a: reference,
+                    line_b: line,
+                });
+            }
+            class_of.insert(line, classes.len());
+            classes.push((angle, Direction::Along(line)));
+        }
+        class_of
+    }
+
+    /// Equal-length constraints between lines of different direction.
+    fn infer_equal_lengths(
+        &self,
+        tolerance: f64,
+        directions: &HashMap<EntityId, usize>,
+        added: &mut Vec<Constraint>,
+    ) {
+        // (length, first line, direction classes in the group)
+        let mut groups: Vec<(f64, EntityId, Vec<usize>)> = Vec::new();
+        for line in self.line_ids() {
+            let Some(&class) = directions.get(&line) else {
+                continue;
+            };
+            let Some(length) = self.get_line_length(line) else {
+                continue;
+            };
+            match groups
+                .iter_mut()
+                .find(|(l, _, _)| (l - length).abs() < tolerance)
+            {
+                Some((_, reference, classes)) => {
+                    if !classes.contains(&class) {
+                        classes.push(class);
+                        added.push(Constraint::EqualLength {
+                            line_a: *reference,
+                            line_b: line,
+                        });
+                    }
+                }
+                None => groups.push((length, line, vec![class])),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_rough_rectangle() {
+        let mut sketch = Sketch2D::new();
+        let p0 = sketch.add_point(0.0, 0.0);
+        let p1 = sketch.add_point(10.2, 0.1);
+        let p2 = sketch.add_point(10.1, 5.1);
+        let p3 = sketch.add_point(0.1, 4.9);
+        let l0 = sketch.add_line(p0, p1);
+        let l1 = sketch.add_line(p1, p2);
+        let l2 = sketch.add_line(p2, p3);
+        let l3 = sketch.add_line(p3, p0);
+
+        let added = sketch.infer_constraints(InferOptions::default());
+        let horizontal: Vec<EntityId> = added
+            .iter()
+            .filter_map(|c| match *c {
+                Constraint::Horizontal { line } => Some(line),
+                _ => None,
+            })
+            .collect();
+        let vertical: Vec<EntityId> = added
+            .iter()
+            .filter_map(|c| match *c {
+                Constraint::Vertical { line } => Some(line),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(horizontal, vec![l0, l2]);
+        assert_eq!(vertical, vec![l1, l3]);
+        assert_eq!(added.len(), 4, "{added:?}");
+
+        // Inferring again finds nothing new
+        assert!(sketch.infer_constraints(InferOptions::default()).is_empty());
+
+        sketch.constrain_fixed(EntityRef::Point(p0), 0.0, 0.0);
+        sketch.constrain_length(l0, 10.0);
+        sketch.constrain_length(l1, 5.0);
+        assert!(sketch.is_fully_constrained());
+        assert!(sketch.solve_default().converged);
+
+        let (x2, y2) = sketch.get_point(p2).unwrap();
+        let (x3, y3) = sketch.get_point(p3).unwrap();
+        assert!((x2 - 10.0).abs() < 1e-6 && (y2 - 5.0).abs() < 1e-6);
+        assert!(x3.abs() < 1e-6 && (y3 - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_infer_joins_loose_segments() {
+        // A square drawn as four separate, slightly gapped segments, with a
+        // tilted corner beside it, one side the same length
+        let mut sketch = Sketch2D::new();
+        let (l0, _, _) = sketch.add_line_by_coords(0.0, 0.0, 10.0, 0.05);
+        let (l1, _, _) = sketch.add_line_by_coords(10.02, 0.0, 10.0, 10.0);
+        let (l2, _, _) = sketch.add_line_by_coords(10.0, 10.03, 0.0, 10.0);
+        let (l3, _, _) = sketch.add_line_by_coords(0.0, 10.0, 0.0, 0.01);
+        let (t0, _, _) = sketch.add_line_by_coords(20.0, 0.0, 28.0, 6.0);
+        let (t1, _, _) = sketch.add_line_by_coords(28.0, 6.0, 25.0, 10.02);
+
+        let added = sketch.infer_constraints(InferOptions::default());
+        let coincident = added
+            .iter()
+            .filter(|c| matches!(c, Constraint::Coincident { .. }))
+            .count();
+        assert_eq!(coincident, 5);
+        assert!(matches!(
+            added[5..],
+            [
+                Constraint::Horizontal { line: h0 },
+                Constraint::Vertical { line: v0 },
+                Constraint::Horizontal { line: h1 },
+                Constraint::Vertical { line: v1 },
+                Constraint::Perpendicular { line_a, line_b },
+                Constraint::EqualLength { line_a: e0, line_b: e1 },
+                Constraint::EqualLength { line_a: e2, line_b: e3 },
+            ] if h0 == l0 && v0 == l1 && h1 == l2 && v1 == l3
+                && line_a == t0 && line_b == t1
+                && e0 == l0 && e1 == l1 && e2 == l0 && e3 == t0
+        ));
+    }
+}
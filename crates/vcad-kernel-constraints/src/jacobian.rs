@@ -135,7 +135,11 @@ mod tests {
             param_x: 2,
             param_y: 3,
         }));
-        let line = entities.insert(SketchEntity::Line(SketchLine { start: p1, end: p2 }));
+        let line = entities.insert(SketchEntity::Line(SketchLine {
+            start: p1,
+            end: p2,
+            is_construction: false,
+        }));
 
         // p1 at (0, 0), p2 at (10, 5)
         let params = vec![0.0, 0.0, 10.0, 5.0];
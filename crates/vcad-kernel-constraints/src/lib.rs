@@ -89,15 +89,18 @@
 mod constraint;
 mod entity;
 mod export;
+mod infer;
 mod jacobian;
 mod residual;
 mod sketch;
 mod solver;
+mod svg;
 
 pub use constraint::{Constraint, EntityRef};
 pub use entity::{EntityId, SketchArc, SketchCircle, SketchEntity, SketchLine, SketchPoint};
 pub use export::ExportError;
-pub use sketch::Sketch2D;
+pub use infer::InferOptions;
+pub use sketch::{BindingError, Sketch2D};
 pub use solver::{SolveResult, SolveStatus, SolverConfig};
 
 #[cfg(test)]
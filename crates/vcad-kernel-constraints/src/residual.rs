@@ -113,6 +113,26 @@ pub fn compute_constraint_residuals(
             vec![dot]
         }
 
+        Constraint::G1 {
+            segment_a,
+            segment_b,
+            at_point,
+        } => {
+            let at = get_point_coords(*at_point, params, entities);
+            let (Some((ax, ay)), Some((bx, by))) = (
+                outgoing_tangent(*segment_a, at, params, entities),
+                outgoing_tangent(*segment_b, at, params, entities),
+            ) else {
+                return vec![0.0];
+            };
+            // Arriving along A means travelling against its outgoing tangent
+            let (ax, ay) = (-ax, -ay);
+            if ax.hypot(ay) < 1e-15 || bx.hypot(by) < 1e-15 {
+                return vec![0.0];
+            }
+            vec![(ax * by - ay * bx).atan2(ax * bx + ay * by)]
+        }
+
         Constraint::EqualLength { line_a, line_b } => {
             let (s1x, s1y, e1x, e1y) = get_line_coords(*line_a, params, entities);
             let (s2x, s2y, e2x, e2y) = get_line_coords(*line_b, params, entities);
@@ -121,6 +141,35 @@ pub fn compute_constraint_residuals(
             vec![len1 - len2]
         }
 
+        Constraint::EqualDistance {
+            point_a,
+            point_b,
+            point_c,
+            point_d,
+        } => {
+            let (ax, ay) = get_point_coords(*point_a, params, entities);
+            let (bx, by) = get_point_coords(*point_b, params, entities);
+            let (cx, cy) = get_point_coords(*point_c, params, entities);
+            let (dx, dy) = get_point_coords(*point_d, params, entities);
+            vec![(bx - ax).hypot(by - ay) - (dx - cx).hypot(dy - cy)]
+        }
+
+        Constraint::FramePoint {
+            point,
+            origin,
+            axis,
+            along,
+            across,
+        } => {
+            let (px, py) = get_point_coords(*point, params, entities);
+            let (ox, oy) = get_point_coords(*origin, params, entities);
+            let (ax, ay) = get_point_coords(*axis, params, entities);
+            let (ux, uy) = (ax - ox, ay - oy);
+            let tx = ox + along * ux - across * uy;
+            let ty = oy + along * uy + across * ux;
+            vec![px - tx, py - ty]
+        }
+
         Constraint::EqualRadius { circle_a, circle_b } => {
             let r1 = get_radius(*circle_a, params, entities);
             let r2 = get_radius(*circle_b, params, entities);
@@ -200,6 +249,24 @@ pub fn compute_constraint_residuals(
             vec![dist_to_axis, perp]
         }
 
+        Constraint::PointSymmetric {
+            point_a,
+            point_b,
+            center,
+        } => {
+            let (ax, ay) = get_point_coords(*point_a, params, entities);
+            let (bx, by) = get_point_coords(*point_b, params, entities);
+            let (cx, cy) = get_point_coords(*center, params, entities);
+            vec![ax + bx - 2.0 * cx, ay + by - 2.0 * cy]
+        }
+
+        Constraint::RadiusEqualsLength { circle, line } => {
+            let r = get_radius(*circle, params, entities);
+            let (sx, sy, ex, ey) = get_line_coords(*line, params, entities);
+            let len = ((ex - sx).powi(2) + (ey - sy).powi(2)).sqrt();
+            vec![r - len]
+        }
+
         Constraint::Distance {
             point_a,
             point_b,
@@ -283,11 +350,47 @@ pub fn compute_constraint_residuals(
             let radius = get_radius(*circle, params, entities);
             vec![2.0 * radius - diameter]
         }
+
+        Constraint::GridAligned { point, spacing } => {
+            if *spacing <= 0.0 {
+                return vec![0.0, 0.0];
+            }
+            let (px, py) = get_point_coords(*point, params, entities);
+            let off_grid = |v: f64| v - spacing * (v / spacing).round();
+            vec![off_grid(px), off_grid(py)]
+        }
+
+        Constraint::ArcAngle { arc, angle_rad } => {
+            let Some(SketchEntity::Arc(a)) = entities.get(*arc) else {
+                return vec![0.0];
+            };
+            let (cx, cy) = get_point_coords(EntityRef::Point(a.center), params, entities);
+            let (sx, sy) = get_point_coords(EntityRef::Point(a.start), params, entities);
+            let (ex, ey) = get_point_coords(EntityRef::Point(a.end), params, entities);
+            let (d1x, d1y) = (sx - cx, sy - cy);
+            let (d2x, d2y) = (ex - cx, ey - cy);
+            if d1x.hypot(d1y) < 1e-15 || d2x.hypot(d2y) < 1e-15 {
+                return vec![0.0];
+            }
+            // Signed angle from start to end, then measured in the arc's direction
+            let signed = (d1x * d2y - d1y * d2x).atan2(d1x * d2x + d1y * d2y);
+            let sweep = if a.ccw { signed } else { -signed };
+            let sweep = sweep.rem_euclid(2.0 * std::f64::consts::PI);
+            // Normalize angle difference to [-π, π]
+            let mut diff = sweep - angle_rad;
+            while diff > std::f64::consts::PI {
+                diff -= 2.0 * std::f64::consts::PI;
+            }
+            while diff < -std::f64::consts::PI {
+                diff += 2.0 * std::f64::consts::PI;
+            }
+            vec![diff]
+        }
     }
 }
 
 /// Get (x, y) coordinates for a point reference.
-fn get_point_coords(
+pub(crate) fn get_point_coords(
     point_ref: EntityRef,
     params: &[f64],
     entities: &SlotMap<EntityId, SketchEntity>,
@@ -332,6 +435,49 @@ fn get_point_coords(
     }
 }
 
+/// Direction leaving a line or arc from its endpoint nearest `at`, into
+/// the segment. Not normalized.
+fn outgoing_tangent(
+    segment: EntityId,
+    at: (f64, f64),
+    params: &[f64],
+    entities: &SlotMap<EntityId, SketchEntity>,
+) -> Option<(f64, f64)> {
+    let dist = |(x, y): (f64, f64)| (x - at.0).hypot(y - at.1);
+    match entities.get(segment)? {
+        SketchEntity::Line(l) => {
+            let s = get_point_coords(EntityRef::Point(l.start), params, entities);
+            let e = get_point_coords(EntityRef::Point(l.end), params, entities);
+            if dist(s) <= dist(e) {
+                Some((e.0 - s.0, e.1 - s.1))
+            } else {
+                Some((s.0 - e.0, s.1 - e.1))
+            }
+        }
+        SketchEntity::Arc(a) => {
+            let (cx, cy) = get_point_coords(EntityRef::Point(a.center), params, entities);
+            let s = get_point_coords(EntityRef::Point(a.start), params, entities);
+            let e = get_point_coords(EntityRef::Point(a.end), params, entities);
+            // The radius vector turned a quarter in the arc's direction
+            let travel = |(px, py): (f64, f64)| {
+                let (rx, ry) = (px - cx, py - cy);
+                if a.ccw {
+                    (-ry, rx)
+                } else {
+                    (ry, -rx)
+                }
+            };
+            if dist(s) <= dist(e) {
+                Some(travel(s))
+            } else {
+                let (tx, ty) = travel(e);
+                Some((-tx, -ty))
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Get (start_x, start_y, end_x, end_y) for a line entity.
 fn get_line_coords(
     line_id: EntityId,
@@ -424,7 +570,11 @@ mod tests {
             param_x: 2,
             param_y: 3,
         }));
-        let line = entities.insert(SketchEntity::Line(SketchLine { start: p1, end: p2 }));
+        let line = entities.insert(SketchEntity::Line(SketchLine {
+            start: p1,
+            end: p2,
+            is_construction: false,
+        }));
         // p1 at (0, 0), p2 at (10, 5) - diagonal line
         let params = vec![0.0, 0.0, 10.0, 5.0];
 
@@ -434,6 +584,47 @@ mod tests {
         assert!((res[0] - 5.0).abs() < 1e-12); // end.y - start.y
     }
 
+    #[test]
+    fn test_g1_residual() {
+        let mut entities = SlotMap::with_key();
+        let mut point = |i: usize| {
+            entities.insert(SketchEntity::Point(SketchPoint {
+                param_x: 2 * i,
+                param_y: 2 * i + 1,
+            }))
+        };
+        let (p0, p1, p2) = (point(0), point(1), point(2));
+        let line = |entities: &mut SlotMap<_, _>, start, end| {
+            entities.insert(SketchEntity::Line(SketchLine {
+                start,
+                end,
+                is_construction: false,
+            }))
+        };
+        let a = line(&mut entities, p0, p1);
+        let b = line(&mut entities, p2, p1);
+        let g1 = Constraint::G1 {
+            segment_a: a,
+            segment_b: b,
+            at_point: EntityRef::Point(p1),
+        };
+
+        // Straight through, with the second line drawn backwards
+        let params = vec![0.0, 0.0, 10.0, 0.0, 20.0, 0.0];
+        let res = compute_constraint_residuals(&g1, &params, &entities);
+        assert!(res[0].abs() < 1e-12);
+
+        // A right-angle turn
+        let params = vec![0.0, 0.0, 10.0, 0.0, 10.0, 5.0];
+        let res = compute_constraint_residuals(&g1, &params, &entities);
+        assert!((res[0] - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+
+        // Doubling back is a half turn, not tangent
+        let params = vec![0.0, 0.0, 10.0, 0.0, 5.0, 0.0];
+        let res = compute_constraint_residuals(&g1, &params, &entities);
+        assert!((res[0].abs() - std::f64::consts::PI).abs() < 1e-12);
+    }
+
     #[test]
     fn test_distance_residual() {
         let (entities, params) = setup_two_points();
@@ -449,6 +640,26 @@ mod tests {
         assert!((res[0] - 2.0).abs() < 1e-12);
     }
 
+    #[test]
+    fn test_point_symmetric_residual() {
+        let (mut entities, params) = setup_two_points();
+        let ids: Vec<_> = entities.keys().collect();
+        let center = entities.insert(SketchEntity::Point(SketchPoint {
+            param_x: 4,
+            param_y: 5,
+        }));
+        let mut params = params;
+        params.extend([5.0, 1.0]);
+        let constraint = Constraint::PointSymmetric {
+            point_a: EntityRef::Point(ids[0]),
+            point_b: EntityRef::Point(ids[1]),
+            center: EntityRef::Point(center),
+        };
+        let res = compute_constraint_residuals(&constraint, &params, &entities);
+        // (0,0) + (10,0) - 2 * (5,1)
+        assert_eq!(res, vec![0.0, -2.0]);
+    }
+
     #[test]
     fn test_fixed_residual() {
         let (entities, params) = setup_two_points();
@@ -4,10 +4,35 @@
 
 use crate::constraint::{Constraint, EntityRef};
 use crate::entity::{EntityId, SketchArc, SketchCircle, SketchEntity, SketchLine, SketchPoint};
-use crate::solver::{solve, SolveResult, SolverConfig};
+use crate::residual::get_point_coords;
+use crate::solver::{solve, solve_weighted, SolveResult, SolverConfig};
 use slotmap::SlotMap;
+use std::collections::HashMap;
+use thiserror::Error;
 use vcad_kernel_math::{Dir3, Point3, Vec3};
 
+/// Weight of the temporary pin on a dragged point in [`Sketch2D::solve_from`].
+const DRAG_WEIGHT: f64 = 1e3;
+
+/// Errors from binding constraint dimensions to variables.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BindingError {
+    /// No constraint at this index of [`Sketch2D::constraints`].
+    #[error("no constraint at index {0}")]
+    NoConstraint(usize),
+
+    /// The constraint at this index is geometric and has no dimension.
+    #[error("constraint {0} has no dimension to bind")]
+    NotDimensional(usize),
+}
+
+/// Weight of soft constraints such as [`Constraint::GridAligned`].
+const SOFT_WEIGHT: f64 = 1e-3;
+
+/// Points of a rigid group closer than this are made coincident rather than
+/// held at a (degenerate) zero distance.
+const RIGID_COINCIDENT_TOLERANCE: f64 = 1e-9;
+
 /// A 2D sketch with entities and constraints.
 ///
 /// The sketch exists in a local coordinate system defined by an origin point
@@ -26,6 +51,10 @@ pub struct Sketch2D {
     pub constraints: Vec<Constraint>,
     /// The parameter vector (X, Y coordinates of points, radii of circles).
     pub parameters: Vec<f64>,
+    /// Named dimension values, see [`Sketch2D::bind_dimension`].
+    variables: HashMap<String, f64>,
+    /// Indices into `constraints` whose target follows a variable.
+    bindings: Vec<(usize, String)>,
 }
 
 impl Default for Sketch2D {
@@ -44,6 +73,8 @@ impl Sketch2D {
             entities: SlotMap::with_key(),
             constraints: Vec::new(),
             parameters: Vec::new(),
+            variables: HashMap::new(),
+            bindings: Vec::new(),
         }
     }
 
@@ -62,6 +93,8 @@ impl Sketch2D {
             entities: SlotMap::with_key(),
             constraints: Vec::new(),
             parameters: Vec::new(),
+            variables: HashMap::new(),
+            bindings: Vec::new(),
         }
     }
 
@@ -85,8 +118,23 @@ impl Sketch2D {
     ///
     /// Returns the entity ID of the new line.
     pub fn add_line(&mut self, start: EntityId, end: EntityId) -> EntityId {
-        self.entities
-            .insert(SketchEntity::Line(SketchLine { start, end }))
+        self.entities.insert(SketchEntity::Line(SketchLine {
+            start,
+            end,
+            is_construction: false,
+        }))
+    }
+
+    /// Add a construction line between two existing point entities.
+    ///
+    /// Construction lines (centerlines, symmetry axes) take part in solving
+    /// but are skipped by [`Sketch2D::to_profile`].
+    pub fn add_construction_line(&mut self, start: EntityId, end: EntityId) -> EntityId {
+        self.entities.insert(SketchEntity::Line(SketchLine {
+            start,
+            end,
+            is_construction: true,
+        }))
     }
 
     /// Add a line by creating two new points at the given coordinates.
@@ -120,6 +168,7 @@ impl Sketch2D {
             end,
             center,
             ccw,
+            is_construction: false,
         }))
     }
 
@@ -132,9 +181,22 @@ impl Sketch2D {
         self.entities.insert(SketchEntity::Circle(SketchCircle {
             center,
             param_radius,
+            is_construction: false,
         }))
     }
 
+    /// Mark a line, arc, or circle as construction geometry (or back).
+    ///
+    /// Has no effect on points or unknown IDs.
+    pub fn set_construction(&mut self, id: EntityId, is_construction: bool) {
+        match self.entities.get_mut(id) {
+            Some(SketchEntity::Line(l)) => l.is_construction = is_construction,
+            Some(SketchEntity::Arc(a)) => a.is_construction = is_construction,
+            Some(SketchEntity::Circle(c)) => c.is_construction = is_construction,
+            _ => {}
+        }
+    }
+
     /// Add a circle by creating a new center point.
     ///
     /// Returns (circle_id, center_point_id).
@@ -207,11 +269,39 @@ impl Sketch2D {
         self.add_constraint(Constraint::Radius { circle, radius });
     }
 
+    /// Constrain two lines or arcs to join smoothly (G1) at a shared point.
+    ///
+    /// Any combination of lines and arcs works, e.g. the joints of a slot.
+    pub fn constrain_g1(&mut self, seg_a: EntityId, seg_b: EntityId, shared_point: EntityRef) {
+        self.add_constraint(Constraint::G1 {
+            segment_a: seg_a,
+            segment_b: seg_b,
+            at_point: shared_point,
+        });
+    }
+
     /// Constrain two lines to have equal length.
     pub fn constrain_equal_length(&mut self, line_a: EntityId, line_b: EntityId) {
         self.add_constraint(Constraint::EqualLength { line_a, line_b });
     }
 
+    /// Constrain the distance from `point_a` to `point_b` to equal the
+    /// distance from `point_c` to `point_d`.
+    pub fn constrain_equal_distance(
+        &mut self,
+        point_a: EntityRef,
+        point_b: EntityRef,
+        point_c: EntityRef,
+        point_d: EntityRef,
+    ) {
+        self.add_constraint(Constraint::EqualDistance {
+            point_a,
+            point_b,
+            point_c,
+            point_d,
+        });
+    }
+
     /// Constrain the angle between two lines.
     pub fn constrain_angle(&mut self, line_a: EntityId, line_b: EntityId, angle_deg: f64) {
         self.add_constraint(Constraint::Angle {
@@ -221,6 +311,198 @@ impl Sketch2D {
         });
     }
 
+    /// Constrain two points to be symmetric about a center point.
+    pub fn constrain_point_symmetric(
+        &mut self,
+        point_a: EntityRef,
+        point_b: EntityRef,
+        center: EntityRef,
+    ) {
+        self.add_constraint(Constraint::PointSymmetric {
+            point_a,
+            point_b,
+            center,
+        });
+    }
+
+    /// Constrain the swept angle of an arc, in radians.
+    pub fn constrain_arc_angle(&mut self, arc: EntityId, angle_rad: f64) {
+        self.add_constraint(Constraint::ArcAngle { arc, angle_rad });
+    }
+
+    /// Constrain the radius of an arc or circle to equal a line's length.
+    pub fn constrain_radius_equals_length(&mut self, circle: EntityId, line: EntityId) {
+        self.add_constraint(Constraint::RadiusEqualsLength { circle, line });
+    }
+
+    /// Softly pull a point onto a grid with the given spacing.
+    ///
+    /// The point lands on the grid when the other constraints allow it, and
+    /// stays where they put it otherwise.
+    pub fn constrain_grid_aligned(&mut self, point: EntityRef, spacing: f64) {
+        self.add_constraint(Constraint::GridAligned { point, spacing });
+    }
+
+    /// Make a group of points, lines, arcs, and circles move as one rigid
+    /// body: the solver can still translate and rotate it, but not distort
+    /// it.
+    ///
+    /// The group is held in the frame of two anchors, the first point and
+    /// the point farthest from it: the anchors keep their distance, and
+    /// every other point keeps its coordinates in their frame. That takes
+    /// 2n - 3 residuals for n points (and removes as many degrees of
+    /// freedom), works when points are collinear with the anchors, and
+    /// doesn't let a point flip to its mirror image. Points lying on an
+    /// anchor are made coincident with it instead, and circles keep their
+    /// current radius.
+    pub fn constrain_rigid(&mut self, entities: &[EntityId]) {
+        let mut points: Vec<EntityId> = Vec::new();
+        let mut circles = Vec::new();
+        for &id in entities {
+            let ids = match self.entities.get(id) {
+                Some(SketchEntity::Point(_)) => vec![id],
+                Some(SketchEntity::Line(l)) => vec![l.start, l.end],
+                Some(SketchEntity::Arc(a)) => vec![a.start, a.end, a.center],
+                Some(SketchEntity::Circle(c)) => {
+                    circles.push(id);
+                    vec![c.center]
+                }
+                None => Vec::new(),
+            };
+            for id in ids {
+                if !points.contains(&id) {
+                    points.push(id);
+                }
+            }
+        }
+
+        if let Some(&first) = points.first() {
+            let farthest = points
+                .iter()
+                .copied()
+                .max_by(|&p, &q| {
+                    self.point_distance(first, p)
+                        .total_cmp(&self.point_distance(first, q))
+                })
+                .unwrap_or(first);
+            if farthest != first {
+                self.hold_distance(first, farthest);
+            }
+            for &p in &points {
+                if p == first || p == farthest {
+                    continue;
+                }
+                let on_anchor = [first, farthest]
+                    .into_iter()
+                    .find(|&a| self.point_distance(a, p) < RIGID_COINCIDENT_TOLERANCE);
+                match on_anchor {
+                    Some(a) => self.hold_distance(a, p),
+                    None => self.hold_in_frame(p, first, farthest),
+                }
+            }
+        }
+
+        for circle in circles {
+            if let Some(r) = self.get_radius(circle) {
+                self.constrain_radius(circle, r);
+            }
+        }
+    }
+
+    /// Hold two points at their current distance, or together if they
+    /// coincide.
+    fn hold_distance(&mut self, a: EntityId, b: EntityId) {
+        let (point_a, point_b) = (EntityRef::Point(a), EntityRef::Point(b));
+        let distance = self.point_distance(a, b);
+        if distance < RIGID_COINCIDENT_TOLERANCE {
+            self.constrain_coincident(point_a, point_b);
+        } else {
+            self.constrain_distance(point_a, point_b, distance);
+        }
+    }
+
+    /// Hold a point at its current coordinates in the frame running from
+    /// `origin` to `axis`.
+    fn hold_in_frame(&mut self, point: EntityId, origin: EntityId, axis: EntityId) {
+        let (Some((px, py)), Some((ox, oy)), Some((ax, ay))) = (
+            self.get_point(point),
+            self.get_point(origin),
+            self.get_point(axis),
+        ) else {
+            return;
+        };
+        let (ux, uy) = (ax - ox, ay - oy);
+        let (dx, dy) = (px - ox, py - oy);
+        let len2 = ux * ux + uy * uy;
+        self.add_constraint(Constraint::FramePoint {
+            point: EntityRef::Point(point),
+            origin: EntityRef::Point(origin),
+            axis: EntityRef::Point(axis),
+            along: (ux * dx + uy * dy) / len2,
+            across: (ux * dy - uy * dx) / len2,
+        });
+    }
+
+    /// Current distance between two point entities (0 if either is missing).
+    fn point_distance(&self, a: EntityId, b: EntityId) -> f64 {
+        match (self.get_point(a), self.get_point(b)) {
+            (Some((ax, ay)), Some((bx, by))) => (bx - ax).hypot(by - ay),
+            _ => 0.0,
+        }
+    }
+
+    // =========================================================================
+    // Variables
+    // =========================================================================
+
+    /// Drive the target of the dimensional constraint at `index` in
+    /// [`Self::constraints`] from the named variable.
+    ///
+    /// If the variable is not defined yet it takes the constraint's current
+    /// target; otherwise the constraint takes the variable's value. Several
+    /// constraints bound to one variable stay equal, and
+    /// [`Self::set_variable`] updates all of them. Binds nothing if `index`
+    /// is out of range or the constraint has no dimension.
+    pub fn bind_dimension(&mut self, index: usize, variable: &str) -> Result<(), BindingError> {
+        let target = dimension_at(&mut self.constraints, index)?;
+        match self.variables.get(variable) {
+            Some(&value) => *target = value,
+            None => {
+                self.variables.insert(variable.to_string(), *target);
+            }
+        }
+        self.bindings.retain(|(i, _)| *i != index);
+        self.bindings.push((index, variable.to_string()));
+        Ok(())
+    }
+
+    /// Set a variable and update every constraint bound to it.
+    ///
+    /// The new targets take effect on the next solve. Fails, changing
+    /// nothing, if a bound constraint has since been removed or replaced by
+    /// one without a dimension.
+    pub fn set_variable(&mut self, variable: &str, value: f64) -> Result<(), BindingError> {
+        let bound: Vec<usize> = self
+            .bindings
+            .iter()
+            .filter(|(_, name)| name == variable)
+            .map(|&(index, _)| index)
+            .collect();
+        for &index in &bound {
+            dimension_at(&mut self.constraints, index)?;
+        }
+        for &index in &bound {
+            *dimension_at(&mut self.constraints, index)? = value;
+        }
+        self.variables.insert(variable.to_string(), value);
+        Ok(())
+    }
+
+    /// Get the current value of a variable.
+    pub fn variable(&self, variable: &str) -> Option<f64> {
+        self.variables.get(variable).copied()
+    }
+
     // =========================================================================
     // Solving
     // =========================================================================
@@ -228,14 +510,36 @@ impl Sketch2D {
     /// Solve the constraint system.
     ///
     /// This adjusts the parameters to satisfy all constraints using
-    /// Levenberg-Marquardt optimization.
+    /// Levenberg-Marquardt optimization. Soft constraints are weighted far
+    /// below the others. If they conflict with them, the hard constraints
+    /// alone are then solved exactly, starting from that compromise, and
+    /// the result is converged once they are met.
     pub fn solve(&mut self, config: &SolverConfig) -> SolveResult {
-        solve(
+        if !self.constraints.iter().any(Constraint::is_soft) {
+            return solve(
+                &self.constraints,
+                &mut self.parameters,
+                &self.entities,
+                config,
+            );
+        }
+        let result = solve_weighted(
             &self.constraints,
+            &self.weights(),
             &mut self.parameters,
             &self.entities,
             config,
-        )
+        );
+        if result.converged {
+            return result;
+        }
+        let hard: Vec<Constraint> = self
+            .constraints
+            .iter()
+            .filter(|c| !c.is_soft())
+            .cloned()
+            .collect();
+        solve(&hard, &mut self.parameters, &self.entities, config)
     }
 
     /// Solve with default configuration.
@@ -243,6 +547,110 @@ impl Sketch2D {
         self.solve(&SolverConfig::default())
     }
 
+    /// Re-solve after an interactive edit, warm-starting from the current
+    /// parameters.
+    ///
+    /// If `dragged` is given, that point is pinned where it currently is by a
+    /// temporary, heavily weighted fixed constraint, so the rest of the
+    /// sketch follows it. Set the point's parameters to the cursor position
+    /// before calling. Small moves from a solved state converge in a few
+    /// iterations. The pin is not kept in [`Self::constraints`].
+    pub fn solve_from(&mut self, config: &SolverConfig, dragged: Option<EntityRef>) -> SolveResult {
+        let Some(point) = dragged else {
+            return self.solve(config);
+        };
+        let (x, y) = get_point_coords(point, &self.parameters, &self.entities);
+        let mut constraints = self.constraints.clone();
+        constraints.push(Constraint::Fixed { point, x, y });
+        let mut weights = self.weights();
+        weights.push(DRAG_WEIGHT);
+        solve_weighted(
+            &constraints,
+            &weights,
+            &mut self.parameters,
+            &self.entities,
+            config,
+        )
+    }
+
+    /// Round every point not pinned by a fixed constraint to the nearest
+    /// multiple of `spacing`, then re-solve to restore the constraints.
+    ///
+    /// Use after solving, to clean up coordinates that drifted off round
+    /// values. Pinned points are put exactly where their constraint says.
+    /// Points whose snapped position conflicts with the constraints are
+    /// moved back off the grid as little as needed. Does nothing but
+    /// re-solve if `spacing` is not positive.
+    pub fn snap_to_grid(&mut self, spacing: f64) -> SolveResult {
+        if spacing > 0.0 {
+            let mut pinned = HashMap::new();
+            for c in &self.constraints {
+                if let Constraint::Fixed { point, x, y } = *c {
+                    if let Some(id) = self.point_entity(point) {
+                        pinned.insert(id, (x, y));
+                    }
+                }
+            }
+            for (id, entity) in &self.entities {
+                let SketchEntity::Point(p) = entity else {
+                    continue;
+                };
+                let (x, y) = match pinned.get(&id) {
+                    Some(&target) => target,
+                    None => {
+                        let snap = |v: f64| spacing * (v / spacing).round();
+                        (
+                            snap(self.parameters[p.param_x]),
+                            snap(self.parameters[p.param_y]),
+                        )
+                    }
+                };
+                self.parameters[p.param_x] = x;
+                self.parameters[p.param_y] = y;
+            }
+        }
+        self.solve_default()
+    }
+
+    /// The point entity an [`EntityRef`] resolves to.
+    pub(crate) fn point_entity(&self, point: EntityRef) -> Option<EntityId> {
+        let entity = |id| self.entities.get(id);
+        match point {
+            EntityRef::Point(id) => entity(id)?.as_point().map(|_| id),
+            EntityRef::LineStart(id) => entity(id)?.as_line().map(|l| l.start),
+            EntityRef::LineEnd(id) => entity(id)?.as_line().map(|l| l.end),
+            EntityRef::Center(id) => match entity(id)? {
+                SketchEntity::Circle(c) => Some(c.center),
+                SketchEntity::Arc(a) => Some(a.center),
+                _ => None,
+            },
+            EntityRef::ArcStart(id) => entity(id)?.as_arc().map(|a| a.start),
+            EntityRef::ArcEnd(id) => entity(id)?.as_arc().map(|a| a.end),
+        }
+    }
+
+    /// Solver weight of each constraint: low for soft ones, 1 otherwise.
+    fn weights(&self) -> Vec<f64> {
+        self.constraints
+            .iter()
+            .map(|c| if c.is_soft() { SOFT_WEIGHT } else { 1.0 })
+            .collect()
+    }
+
+    /// Copy the current parameter vector, e.g. before previewing a drag.
+    pub fn snapshot_parameters(&self) -> Vec<f64> {
+        self.parameters.clone()
+    }
+
+    /// Restore parameters saved by [`Self::snapshot_parameters`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if entities were added since the snapshot was taken.
+    pub fn restore_parameters(&mut self, snapshot: &[f64]) {
+        self.parameters.copy_from_slice(snapshot);
+    }
+
     // =========================================================================
     // Querying
     // =========================================================================
@@ -297,6 +705,8 @@ impl Sketch2D {
     ///
     /// DOF = (number of parameters) - (number of constraint equations)
     ///
+    /// Soft constraints are not counted.
+    ///
     /// A fully constrained sketch has DOF = 0.
     /// An over-constrained sketch has DOF < 0.
     /// An under-constrained sketch has DOF > 0.
@@ -305,6 +715,7 @@ impl Sketch2D {
         let num_constraints: i32 = self
             .constraints
             .iter()
+            .filter(|c| !c.is_soft())
             .map(|c| c.num_residuals() as i32)
             .sum();
         num_params - num_constraints
@@ -347,6 +758,15 @@ impl Sketch2D {
     }
 }
 
+/// The target of the dimensional constraint at `index`.
+fn dimension_at(constraints: &mut [Constraint], index: usize) -> Result<&mut f64, BindingError> {
+    constraints
+        .get_mut(index)
+        .ok_or(BindingError::NoConstraint(index))?
+        .dimension_mut()
+        .ok_or(BindingError::NotDimensional(index))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -441,6 +861,323 @@ mod tests {
         assert!((y3 - 5.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_solve_from_drag_warm_start() {
+        let mut sketch = Sketch2D::new();
+        let p0 = sketch.add_point(0.0, 0.0);
+        let p1 = sketch.add_point(12.0, 1.0);
+        let p2 = sketch.add_point(11.0, 8.0);
+        let p3 = sketch.add_point(1.0, 7.0);
+        let l0 = sketch.add_line(p0, p1);
+        let l1 = sketch.add_line(p1, p2);
+        let l2 = sketch.add_line(p2, p3);
+        let l3 = sketch.add_line(p3, p0);
+        sketch.constrain_fixed(EntityRef::Point(p0), 0.0, 0.0);
+        sketch.constrain_horizontal(l0);
+        sketch.constrain_horizontal(l2);
+        sketch.constrain_vertical(l1);
+        sketch.constrain_vertical(l3);
+        sketch.constrain_length(l0, 10.0);
+
+        let cold = sketch.solve_default();
+        assert!(cold.converged);
+        let solved = sketch.snapshot_parameters();
+
+        // Drag the top-right corner up; the height is free to follow.
+        let (x2, y2) = sketch.get_point(p2).unwrap();
+        set_point(&mut sketch, p2, x2, y2 + 1.0);
+        let warm = sketch.solve_from(&SolverConfig::default(), Some(EntityRef::Point(p2)));
+        assert!(warm.converged);
+        assert!(
+            warm.iterations < cold.iterations,
+            "warm {} vs cold {}",
+            warm.iterations,
+            cold.iterations
+        );
+        let (_, y3) = sketch.get_point(p3).unwrap();
+        assert!((sketch.get_point(p2).unwrap().1 - (y2 + 1.0)).abs() < 1e-6);
+        assert!((y3 - (y2 + 1.0)).abs() < 1e-6);
+        assert_eq!(sketch.num_constraints(), 6);
+
+        sketch.restore_parameters(&solved);
+        assert_eq!(sketch.get_point(p2).unwrap(), (x2, y2));
+    }
+
+    fn set_point(sketch: &mut Sketch2D, id: EntityId, x: f64, y: f64) {
+        if let Some(SketchEntity::Point(p)) = sketch.entities.get(id) {
+            let (ix, iy) = (p.param_x, p.param_y);
+            sketch.parameters[ix] = x;
+            sketch.parameters[iy] = y;
+        }
+    }
+
+    #[test]
+    fn test_solve_point_symmetric() {
+        let mut sketch = Sketch2D::new();
+        let center = sketch.add_point(4.0, 6.0);
+        let a = sketch.add_point(1.0, 2.0);
+        let b = sketch.add_point(0.0, 0.0);
+
+        sketch.constrain_fixed(EntityRef::Point(center), 5.0, 5.0);
+        sketch.constrain_fixed(EntityRef::Point(a), 1.0, 2.0);
+        sketch.constrain_point_symmetric(
+            EntityRef::Point(a),
+            EntityRef::Point(b),
+            EntityRef::Point(center),
+        );
+        assert!(sketch.is_fully_constrained());
+
+        let result = sketch.solve_default();
+        assert!(result.converged, "Solver should converge");
+
+        let (bx, by) = sketch.get_point(b).unwrap();
+        assert!((bx - 9.0).abs() < 1e-6);
+        assert!((by - 8.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_solve_arc_angle() {
+        let mut sketch = Sketch2D::new();
+        let center = sketch.add_point(0.0, 0.0);
+        let start = sketch.add_point(10.0, 0.0);
+        let end = sketch.add_point(3.0, 8.0);
+        let arc = sketch.add_arc(start, end, center, true);
+
+        sketch.constrain_fixed(EntityRef::Point(center), 0.0, 0.0);
+        sketch.constrain_fixed(EntityRef::Point(start), 10.0, 0.0);
+        sketch.add_constraint(Constraint::PointOnCircle {
+            point: EntityRef::ArcEnd(arc),
+            circle: arc,
+        });
+        sketch.constrain_arc_angle(arc, std::f64::consts::FRAC_PI_2);
+        assert!(sketch.is_fully_constrained());
+
+        let result = sketch.solve_default();
+        assert!(result.converged, "Solver should converge");
+
+        let (ex, ey) = sketch.get_point(end).unwrap();
+        assert!(ex.abs() < 1e-6);
+        assert!((ey - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_solve_g1_slot() {
+        // A slot: top line, right arc, bottom line, left arc, drawn roughly
+        let mut sketch = Sketch2D::new();
+        let a = sketch.add_point(-5.0, 5.0);
+        let b = sketch.add_point(5.4, 5.2);
+        let d = sketch.add_point(5.2, -4.6);
+        let e = sketch.add_point(-5.3, -5.1);
+        let right_center = sketch.add_point(6.1, -0.3);
+        let left_center = sketch.add_point(-4.6, 0.4);
+        let top = sketch.add_line(a, b);
+        let right = sketch.add_arc(b, d, right_center, false);
+        let bottom = sketch.add_line(d, e);
+        let left = sketch.add_arc(e, a, left_center, false);
+
+        sketch.constrain_fixed(EntityRef::Point(a), -5.0, 5.0);
+        sketch.constrain_horizontal(top);
+        sketch.constrain_length(top, 10.0);
+        sketch.constrain_radius(right, 5.0);
+        sketch.add_constraint(Constraint::EqualRadius {
+            circle_a: right,
+            circle_b: left,
+        });
+        for arc in [right, left] {
+            sketch.add_constraint(Constraint::PointOnCircle {
+                point: EntityRef::ArcEnd(arc),
+                circle: arc,
+            });
+        }
+        sketch.constrain_g1(top, right, EntityRef::Point(b));
+        sketch.constrain_g1(right, bottom, EntityRef::Point(d));
+        sketch.constrain_g1(bottom, left, EntityRef::Point(e));
+        sketch.constrain_g1(left, top, EntityRef::Point(a));
+
+        let result = sketch.solve_default();
+        assert!(result.converged, "Solver should converge");
+
+        let expected = [
+            (b, (5.0, 5.0)),
+            (d, (5.0, -5.0)),
+            (e, (-5.0, -5.0)),
+            (right_center, (5.0, 0.0)),
+            (left_center, (-5.0, 0.0)),
+        ];
+        for (point, (x, y)) in expected {
+            let (px, py) = sketch.get_point(point).unwrap();
+            assert!((px - x).abs() < 1e-6 && (py - y).abs() < 1e-6);
+        }
+
+        // Nudging a joint off is undone by the next solve
+        let bx = sketch.entities[b].as_point().unwrap().param_x;
+        sketch.parameters[bx] += 0.5;
+        assert!(sketch.solve_default().converged);
+        let (px, py) = sketch.get_point(b).unwrap();
+        assert!((px - 5.0).abs() < 1e-6 && (py - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_solve_radius_equals_length() {
+        let mut sketch = Sketch2D::new();
+        let (circle, center) = sketch.add_circle_by_coords(0.0, 0.0, 2.0);
+        let (line, start, end) = sketch.add_line_by_coords(0.0, 5.0, 7.0, 5.0);
+
+        sketch.constrain_fixed(EntityRef::Point(center), 0.0, 0.0);
+        sketch.constrain_fixed(EntityRef::Point(start), 0.0, 5.0);
+        sketch.constrain_fixed(EntityRef::Point(end), 7.0, 5.0);
+        sketch.constrain_radius_equals_length(circle, line);
+
+        let result = sketch.solve_default();
+        assert!(result.converged, "Solver should converge");
+        assert!((sketch.get_radius(circle).unwrap() - 7.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_solve_equal_distance() {
+        let mut sketch = Sketch2D::new();
+        let (_, a, b) = sketch.add_line_by_coords(0.0, 0.0, 4.0, 0.0);
+        let (_, c, d) = sketch.add_line_by_coords(0.0, 5.0, 9.0, 5.0);
+
+        sketch.constrain_fixed(EntityRef::Point(a), 0.0, 0.0);
+        sketch.constrain_fixed(EntityRef::Point(c), 0.0, 5.0);
+        sketch.add_constraint(Constraint::VerticalDistance {
+            point: EntityRef::Point(b),
+            y: 0.0,
+        });
+        sketch.add_constraint(Constraint::VerticalDistance {
+            point: EntityRef::Point(d),
+            y: 5.0,
+        });
+        sketch.constrain_distance(EntityRef::Point(a), EntityRef::Point(b), 6.0);
+        sketch.constrain_equal_distance(
+            EntityRef::Point(a),
+            EntityRef::Point(b),
+            EntityRef::Point(c),
+            EntityRef::Point(d),
+        );
+        assert!(sketch.is_fully_constrained());
+
+        let result = sketch.solve_default();
+        assert!(result.converged, "Solver should converge");
+        let (bx, _) = sketch.get_point(b).unwrap();
+        let (dx, _) = sketch.get_point(d).unwrap();
+        assert!((bx - 6.0).abs() < 1e-6);
+        assert!((dx - 6.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bound_dimensions_follow_variable() {
+        let mut sketch = Sketch2D::new();
+        let (l0, a, _) = sketch.add_line_by_coords(0.0, 0.0, 4.0, 0.0);
+        let (l1, c, _) = sketch.add_line_by_coords(0.0, 5.0, 9.0, 5.0);
+        sketch.constrain_fixed(EntityRef::Point(a), 0.0, 0.0);
+        sketch.constrain_fixed(EntityRef::Point(c), 0.0, 5.0);
+        sketch.constrain_horizontal(l0);
+        sketch.constrain_horizontal(l1);
+        sketch.constrain_length(l0, 3.0);
+        sketch.bind_dimension(4, "width").unwrap();
+        sketch.constrain_length(l1, 8.0);
+        sketch.bind_dimension(5, "width").unwrap();
+        assert_eq!(
+            sketch.bind_dimension(2, "width"),
+            Err(BindingError::NotDimensional(2))
+        );
+        assert_eq!(
+            sketch.bind_dimension(9, "width"),
+            Err(BindingError::NoConstraint(9))
+        );
+        assert_eq!(sketch.variable("width"), Some(3.0));
+
+        sketch.set_variable("width", 7.5).unwrap();
+        assert!(sketch.solve_default().converged);
+        assert!((sketch.get_line_length(l0).unwrap() - 7.5).abs() < 1e-6);
+        assert!((sketch.get_line_length(l1).unwrap() - 7.5).abs() < 1e-6);
+
+        // A bound constraint removed behind the binding's back is reported
+        sketch.constraints.truncate(5);
+        assert_eq!(
+            sketch.set_variable("width", 2.0),
+            Err(BindingError::NoConstraint(5))
+        );
+        assert_eq!(sketch.variable("width"), Some(7.5));
+    }
+
+    /// Axis-aligned rectangle fixed at the origin, `width` by `height`.
+    fn rectangle(width: f64, height: f64) -> (Sketch2D, [EntityId; 4]) {
+        let mut sketch = Sketch2D::new();
+        let p = [
+            sketch.add_point(0.0, 0.0),
+            sketch.add_point(width + 1.3, 0.4),
+            sketch.add_point(width - 0.7, height + 0.6),
+            sketch.add_point(0.2, height - 0.9),
+        ];
+        let lines: Vec<_> = (0..4)
+            .map(|i| sketch.add_line(p[i], p[(i + 1) % 4]))
+            .collect();
+        sketch.constrain_fixed(EntityRef::Point(p[0]), 0.0, 0.0);
+        sketch.constrain_horizontal(lines[0]);
+        sketch.constrain_vertical(lines[1]);
+        sketch.constrain_horizontal(lines[2]);
+        sketch.constrain_vertical(lines[3]);
+        sketch.constrain_length(lines[0], width);
+        sketch.constrain_length(lines[1], height);
+        (sketch, p)
+    }
+
+    #[test]
+    fn test_snap_to_grid() {
+        let (mut sketch, p) = rectangle(10.0, 5.5);
+        // A loose solve leaves the corners slightly off
+        let loose = SolverConfig {
+            tolerance: 1e-3,
+            ..Default::default()
+        };
+        assert!(sketch.solve(&loose).converged);
+        let (x, _) = sketch.get_point(p[1]).unwrap();
+        assert!(x != 10.0 && (x - 10.0).abs() < 1e-3);
+
+        assert!(sketch.snap_to_grid(0.5).converged);
+        assert_eq!(sketch.get_point(p[1]), Some((10.0, 0.0)));
+        assert_eq!(sketch.get_point(p[2]), Some((10.0, 5.5)));
+        assert_eq!(sketch.get_point(p[3]), Some((0.0, 5.5)));
+
+        // A dimension off the grid wins over the snap
+        let (mut sketch, p) = rectangle(10.2, 5.0);
+        sketch.solve_default();
+        assert!(sketch.snap_to_grid(0.5).converged);
+        let (x, y) = sketch.get_point(p[2]).unwrap();
+        assert!((x - 10.2).abs() < 1e-6 && (y - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_grid_aligned_is_soft() {
+        let mut sketch = Sketch2D::new();
+        let a = sketch.add_point(0.0, 0.0);
+        let b = sketch.add_point(3.3, 0.1);
+        sketch.constrain_fixed(EntityRef::Point(a), 0.0, 0.0);
+        sketch.constrain_distance(EntityRef::Point(a), EntityRef::Point(b), 3.0);
+        sketch.constrain_grid_aligned(EntityRef::Point(b), 1.0);
+        assert_eq!(sketch.degrees_of_freedom(), 1);
+
+        // Satisfiable: b settles on (3, 0)
+        assert!(sketch.solve_default().converged);
+        let (x, y) = sketch.get_point(b).unwrap();
+        assert!((x - 3.0).abs() < 1e-6 && y.abs() < 1e-6);
+
+        // Conflicting: the distance holds, the grid preference gives way
+        sketch.constraints[1] = Constraint::Distance {
+            point_a: EntityRef::Point(a),
+            point_b: EntityRef::Point(b),
+            distance: 3.4,
+        };
+        assert!(sketch.solve_default().converged);
+        let (x, y) = sketch.get_point(b).unwrap();
+        assert!((x.hypot(y) - 3.4).abs() < 1e-6);
+        // and b stays as close to the grid as the distance lets it
+        assert!((x - 3.4).abs() < 1e-3 && y.abs() < 1e-3);
+    }
+
     #[test]
     fn test_line_length() {
         let mut sketch = Sketch2D::new();
@@ -456,4 +1193,83 @@ mod tests {
         assert!((p.y - 5.0).abs() < 1e-12);
         assert!((p.z - 3.0).abs() < 1e-12);
     }
+
+    #[test]
+    fn test_rigid_group_rotates_undistorted() {
+        let mut sketch = Sketch2D::new();
+        let p0 = sketch.add_point(0.0, 0.0);
+        let p1 = sketch.add_point(4.0, 0.0);
+        let p2 = sketch.add_point(0.0, 3.0);
+        let l0 = sketch.add_line(p0, p1);
+        let l1 = sketch.add_line(p1, p2);
+        let l2 = sketch.add_line(p2, p0);
+
+        sketch.constrain_rigid(&[l0, l1, l2]);
+        // A distance between the anchors and a frame point for the third
+        // corner: only the placement is left
+        assert_eq!(sketch.num_constraints(), 2);
+        assert_eq!(sketch.degrees_of_freedom(), 3);
+
+        // Pin one corner somewhere else and turn the base upright
+        sketch.constrain_fixed(EntityRef::Point(p0), 1.0, 1.0);
+        sketch.constrain_vertical(l0);
+        assert!(sketch.is_fully_constrained());
+        assert!(sketch.solve_default().converged);
+
+        let (x1, y1) = sketch.get_point(p1).unwrap();
+        assert!((x1 - 1.0).abs() < 1e-6 && ((y1 - 1.0).abs() - 4.0).abs() < 1e-6);
+        for (line, length) in [(l0, 4.0), (l1, 5.0), (l2, 3.0)] {
+            assert!((sketch.get_line_length(line).unwrap() - length).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_rigid_group_with_collinear_center() {
+        // A semicircle's center lies on the line through its ends
+        let mut sketch = Sketch2D::new();
+        let start = sketch.add_point(0.0, 0.0);
+        let end = sketch.add_point(4.0, 0.0);
+        let center = sketch.add_point(2.0, 0.0);
+        let arc = sketch.add_arc(start, end, center, true);
+        let chord = sketch.add_construction_line(start, end);
+
+        sketch.constrain_rigid(&[arc]);
+        assert_eq!(sketch.degrees_of_freedom(), 3);
+
+        sketch.constrain_fixed(EntityRef::Point(start), 1.0, 1.0);
+        sketch.constrain_vertical(chord);
+        assert!(sketch.is_fully_constrained());
+        assert!(sketch.solve_default().converged);
+
+        let (x0, y0) = sketch.get_point(start).unwrap();
+        let (x1, y1) = sketch.get_point(end).unwrap();
+        let (cx, cy) = sketch.get_point(center).unwrap();
+        assert!((x1 - 1.0).abs() < 1e-6 && ((y1 - 1.0).abs() - 4.0).abs() < 1e-6);
+        assert!((cx - (x0 + x1) / 2.0).abs() < 1e-6);
+        assert!((cy - (y0 + y1) / 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rigid_group_does_not_mirror() {
+        let mut sketch = Sketch2D::new();
+        let p0 = sketch.add_point(0.0, 0.0);
+        let p1 = sketch.add_point(4.0, 0.0);
+        let p2 = sketch.add_point(1.0, 3.0);
+        let l0 = sketch.add_line(p0, p1);
+        let l1 = sketch.add_line(p1, p2);
+        let l2 = sketch.add_line(p2, p0);
+        sketch.constrain_rigid(&[l0, l1, l2]);
+        sketch.constrain_fixed(EntityRef::Point(p0), 0.0, 0.0);
+        sketch.constrain_horizontal(l0);
+
+        // Start the apex at its mirror image across the base, which keeps
+        // every distance of the triangle; the last parameter is its y
+        let mut parameters = sketch.snapshot_parameters();
+        *parameters.last_mut().unwrap() = -3.0;
+        sketch.restore_parameters(&parameters);
+
+        assert!(sketch.solve_default().converged);
+        let (x2, y2) = sketch.get_point(p2).unwrap();
+        assert!((x2 - 1.0).abs() < 1e-6 && (y2 - 3.0).abs() < 1e-6);
+    }
 }
@@ -9,6 +9,9 @@ use crate::jacobian::{compute_all_residuals, compute_jacobian, residual_norm_squ
 use nalgebra::{DMatrix, DVector};
 use slotmap::SlotMap;
 
+/// Most times a step is halved by the line search before it is rejected.
+const LINE_SEARCH_HALVINGS: usize = 10;
+
 /// Configuration for the Levenberg-Marquardt solver.
 #[derive(Debug, Clone)]
 pub struct SolverConfig {
@@ -26,6 +29,20 @@ pub struct SolverConfig {
     pub min_lambda: f64,
     /// Maximum damping factor.
     pub max_lambda: f64,
+    /// Shorten a step that would increase the residual, halving it up to
+    /// 10 times, before rejecting it and raising λ.
+    pub line_search: bool,
+    /// Stop after this many consecutive iterations without a step that
+    /// lowers the residual (0 to never stop early). The result is
+    /// [`SolveStatus::SingularMatrix`] if the hard constraints conflict,
+    /// and [`SolveStatus::Diverged`] otherwise.
+    ///
+    /// Each failed iteration raises λ by `lambda_increase`, so the default
+    /// of 8 gives up once λ has grown by 10⁸: the step is then a tiny
+    /// gradient step, and if none of the last eight (each already shortened
+    /// by the line search) helped, more iterations won't either. It also
+    /// stops well before the default `max_lambda` would.
+    pub divergence_window: usize,
 }
 
 impl Default for SolverConfig {
@@ -38,6 +55,8 @@ impl Default for SolverConfig {
             lambda_decrease: 0.1,
             min_lambda: 1e-12,
             max_lambda: 1e12,
+            line_search: true,
+            divergence_window: 8,
         }
     }
 }
@@ -70,8 +89,14 @@ pub enum SolveStatus {
     NoConstraints,
     /// No parameters to optimize.
     NoParameters,
-    /// Singular matrix encountered.
+    /// Singular matrix encountered, or the solve stalled with a
+    /// rank-deficient Jacobian: some constraints are redundant and
+    /// conflict, so their residuals can't all reach zero.
     SingularMatrix,
+    /// Every step tried increased the residual for
+    /// [`SolverConfig::divergence_window`] iterations in a row, though the
+    /// constraints don't conflict.
+    Diverged,
 }
 
 /// Run the Levenberg-Marquardt solver.
@@ -92,6 +117,23 @@ pub fn solve(
     entities: &SlotMap<EntityId, SketchEntity>,
     config: &SolverConfig,
 ) -> SolveResult {
+    solve_weighted(constraints, &[], params, entities, config)
+}
+
+/// Run the solver with each constraint's residuals scaled by a weight.
+///
+/// `weights[i]` applies to `constraints[i]`; constraints past the end of
+/// `weights` get weight 1. A heavily weighted constraint wins when the
+/// system cannot be satisfied exactly.
+pub(crate) fn solve_weighted(
+    constraints: &[Constraint],
+    weights: &[f64],
+    params: &mut [f64],
+    entities: &SlotMap<EntityId, SketchEntity>,
+    config: &SolverConfig,
+) -> SolveResult {
+    let system = WeightedSystem::new(constraints, weights, entities);
+
     if constraints.is_empty() {
         return SolveResult {
             parameters: params.to_vec(),
@@ -113,7 +155,8 @@ pub fn solve(
     }
 
     let mut lambda = config.initial_lambda;
-    let mut current_norm_sq = residual_norm_squared(constraints, params, entities);
+    let mut current_norm_sq = system.norm_sq(params);
+    let mut failed_steps = 0;
 
     for iteration in 0..config.max_iterations {
         // Check convergence
@@ -128,8 +171,8 @@ pub fn solve(
         }
 
         // Compute Jacobian and residuals
-        let j = compute_jacobian(constraints, params, entities);
-        let r = DVector::from_vec(compute_all_residuals(constraints, params, entities));
+        let j = system.jacobian(params);
+        let r = DVector::from_vec(system.residuals(params));
 
         // Compute J'J and J'r
         let jt = j.transpose();
@@ -137,7 +180,34 @@ pub fn solve(
         let jtr = &jt * &r;
 
         // Try to take a step with current lambda
-        let step_result = try_step(params, &jtj, &jtr, lambda, constraints, entities);
+        let step_result = try_step(
+            params,
+            current_norm_sq,
+            &jtj,
+            &jtr,
+            lambda,
+            config.line_search,
+            &system,
+        );
+        if matches!(step_result, StepResult::Accepted { .. }) {
+            failed_steps = 0;
+        } else {
+            failed_steps += 1;
+            if config.divergence_window > 0 && failed_steps >= config.divergence_window {
+                let status = if is_rank_deficient(&system.hard_rows(&j)) {
+                    SolveStatus::SingularMatrix
+                } else {
+                    SolveStatus::Diverged
+                };
+                return SolveResult {
+                    parameters: params.to_vec(),
+                    residual_norm: current_norm_sq.sqrt(),
+                    iterations: iteration + 1,
+                    converged: false,
+                    status,
+                };
+            }
+        }
 
         match step_result {
             StepResult::Accepted {
@@ -188,6 +258,69 @@ pub fn solve(
     }
 }
 
+/// Constraints with per-residual weights applied to residuals and Jacobian rows.
+struct WeightedSystem<'a> {
+    constraints: &'a [Constraint],
+    entities: &'a SlotMap<EntityId, SketchEntity>,
+    row_weights: Vec<f64>,
+    /// Rows of the residual vector that belong to hard constraints.
+    hard_rows: Vec<usize>,
+}
+
+impl<'a> WeightedSystem<'a> {
+    fn new(
+        constraints: &'a [Constraint],
+        weights: &[f64],
+        entities: &'a SlotMap<EntityId, SketchEntity>,
+    ) -> Self {
+        let row_weights = constraints
+            .iter()
+            .enumerate()
+            .flat_map(|(i, c)| {
+                std::iter::repeat_n(weights.get(i).copied().unwrap_or(1.0), c.num_residuals())
+            })
+            .collect();
+        let hard_rows = constraints
+            .iter()
+            .flat_map(|c| std::iter::repeat_n(!c.is_soft(), c.num_residuals()))
+            .enumerate()
+            .filter_map(|(row, hard)| hard.then_some(row))
+            .collect();
+        Self {
+            constraints,
+            entities,
+            row_weights,
+            hard_rows,
+        }
+    }
+
+    /// The rows of a Jacobian that belong to hard constraints. Soft ones
+    /// are meant to conflict with them, so don't make the system singular.
+    fn hard_rows(&self, j: &DMatrix<f64>) -> DMatrix<f64> {
+        j.select_rows(&self.hard_rows)
+    }
+
+    fn residuals(&self, params: &[f64]) -> Vec<f64> {
+        let mut r = compute_all_residuals(self.constraints, params, self.entities);
+        for (ri, w) in r.iter_mut().zip(&self.row_weights) {
+            *ri *= w;
+        }
+        r
+    }
+
+    fn norm_sq(&self, params: &[f64]) -> f64 {
+        self.residuals(params).iter().map(|r| r * r).sum()
+    }
+
+    fn jacobian(&self, params: &[f64]) -> DMatrix<f64> {
+        let mut j = compute_jacobian(self.constraints, params, self.entities);
+        for (i, w) in self.row_weights.iter().enumerate().take(j.nrows()) {
+            j.row_mut(i).scale_mut(*w);
+        }
+        j
+    }
+}
+
 enum StepResult {
     Accepted {
         new_params: Vec<f64>,
@@ -197,14 +330,31 @@ enum StepResult {
     SingularMatrix,
 }
 
-/// Try taking a step with the given damping factor.
+/// Whether the residuals of `j` depend on each other: fewer independent
+/// rows than rows, up to rounding.
+fn is_rank_deficient(j: &DMatrix<f64>) -> bool {
+    if j.nrows() == 0 {
+        return false;
+    }
+    let singular = j.clone().svd(false, false).singular_values;
+    let largest = singular.max();
+    let tolerance = largest * j.nrows().max(j.ncols()) as f64 * f64::EPSILON;
+    singular.iter().filter(|&&s| s > tolerance).count() < j.nrows()
+}
+
+/// Try taking a step with the given damping factor, from parameters whose
+/// squared residual norm is `norm_sq`.
+///
+/// With `line_search`, a step that doesn't lower the residual is halved
+/// until it does, at most [`LINE_SEARCH_HALVINGS`] times.
 fn try_step(
     params: &[f64],
+    norm_sq: f64,
     jtj: &DMatrix<f64>,
     jtr: &DVector<f64>,
     lambda: f64,
-    constraints: &[Constraint],
-    entities: &SlotMap<EntityId, SketchEntity>,
+    line_search: bool,
+    system: &WeightedSystem,
 ) -> StepResult {
     let n = jtj.nrows();
 
@@ -221,26 +371,26 @@ fn try_step(
         None => return StepResult::SingularMatrix,
     };
 
-    // Compute new parameters
-    let new_params: Vec<f64> = params
-        .iter()
-        .enumerate()
-        .map(|(i, &p)| p + delta[i])
-        .collect();
-
-    // Evaluate new residual norm
-    let new_norm_sq = residual_norm_squared(constraints, &new_params, entities);
-    let old_norm_sq = residual_norm_squared(constraints, params, entities);
-
-    // Accept if the new norm is smaller
-    if new_norm_sq < old_norm_sq {
-        StepResult::Accepted {
-            new_params,
-            new_norm_sq,
+    let halvings = if line_search { LINE_SEARCH_HALVINGS } else { 0 };
+    let mut scale = 1.0;
+    for _ in 0..=halvings {
+        let new_params: Vec<f64> = params
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| p + scale * delta[i])
+            .collect();
+
+        // Accept if the new norm is smaller
+        let new_norm_sq = system.norm_sq(&new_params);
+        if new_norm_sq < norm_sq {
+            return StepResult::Accepted {
+                new_params,
+                new_norm_sq,
+            };
         }
-    } else {
-        StepResult::Rejected
+        scale /= 2.0;
     }
+    StepResult::Rejected
 }
 
 #[cfg(test)]
@@ -299,7 +449,11 @@ mod tests {
             param_x: 2,
             param_y: 3,
         }));
-        let line = entities.insert(SketchEntity::Line(SketchLine { start: p1, end: p2 }));
+        let line = entities.insert(SketchEntity::Line(SketchLine {
+            start: p1,
+            end: p2,
+            is_construction: false,
+        }));
 
         // Start with diagonal line from (0, 0) to (10, 5)
         let mut params = vec![0.0, 0.0, 10.0, 5.0];
@@ -406,8 +560,16 @@ mod tests {
             param_x: 6,
             param_y: 7,
         }));
-        let line1 = entities.insert(SketchEntity::Line(SketchLine { start: p1, end: p2 }));
-        let line2 = entities.insert(SketchEntity::Line(SketchLine { start: p3, end: p4 }));
+        let line1 = entities.insert(SketchEntity::Line(SketchLine {
+            start: p1,
+            end: p2,
+            is_construction: false,
+        }));
+        let line2 = entities.insert(SketchEntity::Line(SketchLine {
+            start: p3,
+            end: p4,
+            is_construction: false,
+        }));
 
         // Line1: (0,0) to (10,0) - horizontal
         // Line2: (5,0) to (5,10) - vertical (already perpendicular)
@@ -442,6 +604,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_conflicting_distances_are_singular() {
+        let mut entities = SlotMap::with_key();
+        let p1 = entities.insert(SketchEntity::Point(SketchPoint {
+            param_x: 0,
+            param_y: 1,
+        }));
+        let p2 = entities.insert(SketchEntity::Point(SketchPoint {
+            param_x: 2,
+            param_y: 3,
+        }));
+        let distance = |distance| Constraint::Distance {
+            point_a: EntityRef::Point(p1),
+            point_b: EntityRef::Point(p2),
+            distance,
+        };
+        let constraints = vec![
+            Constraint::Fixed {
+                point: EntityRef::Point(p1),
+                x: 0.0,
+                y: 0.0,
+            },
+            distance(5.0),
+            distance(10.0),
+        ];
+
+        for line_search in [true, false] {
+            let mut params = vec![0.0, 0.0, 3.0, 4.0];
+            let config = SolverConfig {
+                line_search,
+                ..Default::default()
+            };
+            let result = solve(&constraints, &mut params, &entities, &config);
+            assert!(!result.converged);
+            assert_eq!(result.status, SolveStatus::SingularMatrix);
+            assert!(result.iterations < config.max_iterations);
+            // Settled halfway between the two distances
+            let dist = params[2].hypot(params[3]);
+            assert!((dist - 7.5).abs() < 1e-6, "{dist}");
+        }
+    }
+
     #[test]
     fn test_no_constraints() {
         let entities: SlotMap<EntityId, SketchEntity> = SlotMap::with_key();
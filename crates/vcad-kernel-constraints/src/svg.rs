@@ -0,0 +1,232 @@
+//! SVG rendering of a sketch for debugging the solver.
+//!
+//! Geometry is drawn at the current parameter values, with small glyphs
+//! marking geometric constraints and the remaining degrees of freedom in
+//! the top-left corner.
+
+use std::f64::consts::TAU;
+use std::fmt::Write;
+
+use crate::constraint::Constraint;
+use crate::entity::{EntityId, SketchEntity};
+use crate::sketch::Sketch2D;
+
+impl Sketch2D {
+    /// Render the sketch as a standalone SVG document.
+    ///
+    /// Points are drawn as small circles and lines, arcs, and circles as
+    /// paths (dashed for construction geometry). Horizontal, vertical,
+    /// perpendicular, parallel, and equal-length constraints are marked with
+    /// a glyph next to the constrained line. The Y axis points up, as in the
+    /// sketch.
+    pub fn to_svg(&self) -> String {
+        let (min, max) = self.svg_bounds();
+        let extent = (max.0 - min.0).max(max.1 - min.1).max(1.0);
+        let margin = extent * 0.1;
+        let (x0, y0) = (min.0 - margin, -max.1 - margin);
+        let (w, h) = (max.0 - min.0 + 2.0 * margin, max.1 - min.1 + 2.0 * margin);
+        let stroke = extent * 0.005;
+        let font = extent * 0.04;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{x0} {y0} {w} {h}">"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<g fill="none" stroke="black" stroke-width="{stroke}">"#
+        );
+        for (_, entity) in &self.entities {
+            let Some(d) = self.svg_path(entity) else {
+                continue;
+            };
+            let class = match entity {
+                SketchEntity::Line(_) => "line",
+                SketchEntity::Arc(_) => "arc",
+                _ => "circle",
+            };
+            let dash = if entity.is_construction() {
+                format!(r#" stroke-dasharray="{}""#, stroke * 4.0)
+            } else {
+                String::new()
+            };
+            let _ = writeln!(svg, r#"<path class="{class}" d="{d}"{dash}/>"#);
+        }
+        let _ = writeln!(svg, "</g>");
+
+        let radius = extent * 0.01;
+        for id in self.point_ids() {
+            if let Some((x, y)) = self.get_point(id) {
+                let _ = writeln!(
+                    svg,
+                    r#"<circle class="point" cx="{x}" cy="{}" r="{radius}"/>"#,
+                    -y
+                );
+            }
+        }
+
+        for (glyph, line) in self.constraints.iter().flat_map(constraint_glyphs) {
+            let Some(((x1, y1), (x2, y2))) = self.get_line_endpoints(line) else {
+                continue;
+            };
+            let (mx, my) = ((x1 + x2) / 2.0, (y1 + y2) / 2.0);
+            let _ = writeln!(
+                svg,
+                r#"<text class="constraint" x="{}" y="{}" font-size="{font}">{glyph}</text>"#,
+                mx + font * 0.3,
+                -my - font * 0.3
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<text class="dof" x="{}" y="{}" font-size="{font}">DOF: {}</text>"#,
+            x0 + font * 0.5,
+            y0 + font * 1.5,
+            self.degrees_of_freedom()
+        );
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Bounding box of points and circles as ((min_x, min_y), (max_x, max_y)).
+    fn svg_bounds(&self) -> ((f64, f64), (f64, f64)) {
+        let mut min = (f64::INFINITY, f64::INFINITY);
+        let mut max = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        let mut include = |x: f64, y: f64, r: f64| {
+            min = (min.0.min(x - r), min.1.min(y - r));
+            max = (max.0.max(x + r), max.1.max(y + r));
+        };
+        for (id, entity) in &self.entities {
+            match entity {
+                SketchEntity::Point(_) => {
+                    if let Some((x, y)) = self.get_point(id) {
+                        include(x, y, 0.0);
+                    }
+                }
+                SketchEntity::Circle(c) => {
+                    if let (Some((x, y)), Some(r)) = (self.get_point(c.center), self.get_radius(id))
+                    {
+                        include(x, y, r.abs());
+                    }
+                }
+                _ => {}
+            }
+        }
+        if min.0 > max.0 {
+            ((0.0, 0.0), (0.0, 0.0))
+        } else {
+            (min, max)
+        }
+    }
+
+    /// SVG path data for a line, arc, or circle, with Y flipped.
+    fn svg_path(&self, entity: &SketchEntity) -> Option<String> {
+        match entity {
+            SketchEntity::Point(_) => None,
+            SketchEntity::Line(l) => {
+                let (x1, y1) = self.get_point(l.start)?;
+                let (x2, y2) = self.get_point(l.end)?;
+                Some(format!("M {x1} {} L {x2} {}", -y1, -y2))
+            }
+            SketchEntity::Arc(a) => {
+                let (sx, sy) = self.get_point(a.start)?;
+                let (ex, ey) = self.get_point(a.end)?;
+                let (cx, cy) = self.get_point(a.center)?;
+                let r = (sx - cx).hypot(sy - cy);
+                if r < 1e-12 {
+                    return None;
+                }
+                // Flipping Y turns counter-clockwise into SVG's negative sweep
+                let sweep_flag = u8::from(!a.ccw);
+                if (ex - sx).hypot(ey - sy) < 1e-9 {
+                    // Coincident endpoints make a full circle. SVG draws
+                    // nothing for such an arc, so go via the opposite point.
+                    let (ox, oy) = (2.0 * cx - sx, 2.0 * cy - sy);
+                    return Some(format!(
+                        "M {sx} {} A {r} {r} 0 0 {sweep_flag} {ox} {} \
+                         A {r} {r} 0 0 {sweep_flag} {sx} {}",
+                        -sy, -oy, -sy
+                    ));
+                }
+                let a0 = (sy - cy).atan2(sx - cx);
+                let a1 = (ey - cy).atan2(ex - cx);
+                let sweep = if a.ccw { a1 - a0 } else { a0 - a1 }.rem_euclid(TAU);
+                let large = u8::from(sweep > TAU / 2.0);
+                Some(format!(
+                    "M {sx} {} A {r} {r} 0 {large} {sweep_flag} {ex} {}",
+                    -sy, -ey
+                ))
+            }
+            SketchEntity::Circle(c) => {
+                let (cx, cy) = self.get_point(c.center)?;
+                let r = self.parameters[c.param_radius];
+                Some(format!(
+                    "M {} {cy} A {r} {r} 0 1 0 {} {cy} A {r} {r} 0 1 0 {} {cy} Z",
+                    cx + r,
+                    cx - r,
+                    cx + r,
+                    cy = -cy
+                ))
+            }
+        }
+    }
+}
+
+/// Glyphs to draw for a constraint, each next to a line it constrains.
+fn constraint_glyphs(constraint: &Constraint) -> Vec<(&'static str, EntityId)> {
+    match *constraint {
+        Constraint::Horizontal { line } => vec![("H", line)],
+        Constraint::Vertical { line } => vec![("V", line)],
+        Constraint::Perpendicular { line_a, line_b } => vec![("⊥", line_a), ("⊥", line_b)],
+        Constraint::Parallel { line_a, line_b } => vec![("∥", line_a), ("∥", line_b)],
+        Constraint::EqualLength { line_a, line_b } => vec![("=", line_a), ("=", line_b)],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EntityRef, Sketch2D};
+
+    #[test]
+    fn test_rectangle_svg() {
+        let mut sketch = Sketch2D::new();
+        let p0 = sketch.add_point(0.0, 0.0);
+        let p1 = sketch.add_point(12.0, 1.0);
+        let p2 = sketch.add_point(11.0, 6.0);
+        let p3 = sketch.add_point(1.0, 5.0);
+        let l0 = sketch.add_line(p0, p1);
+        let l1 = sketch.add_line(p1, p2);
+        let l2 = sketch.add_line(p2, p3);
+        let l3 = sketch.add_line(p3, p0);
+        sketch.constrain_fixed(EntityRef::Point(p0), 0.0, 0.0);
+        sketch.constrain_horizontal(l0);
+        sketch.constrain_horizontal(l2);
+        sketch.constrain_vertical(l1);
+        sketch.constrain_vertical(l3);
+        sketch.constrain_length(l0, 10.0);
+        sketch.constrain_length(l1, 5.0);
+        assert!(sketch.solve_default().converged);
+
+        let svg = sketch.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches(r#"<path class="line""#).count(), 4);
+        assert_eq!(svg.matches(r#"class="point""#).count(), 4);
+        assert_eq!(svg.matches(">H</text>").count(), 2);
+        assert_eq!(svg.matches(">V</text>").count(), 2);
+        assert!(svg.contains("DOF: 0"));
+    }
+
+    #[test]
+    fn test_full_circle_arc_svg() {
+        let mut sketch = Sketch2D::new();
+        let p = sketch.add_point(5.0, 0.0);
+        let c = sketch.add_point(0.0, 0.0);
+        sketch.add_arc(p, p, c, true);
+        let svg = sketch.to_svg();
+        assert!(svg.contains(r#"d="M 5 -0 A 5 5 0 0 0 -5 -0 A 5 5 0 0 0 5 -0""#));
+    }
+}
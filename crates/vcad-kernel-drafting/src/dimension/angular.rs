@@ -11,7 +11,7 @@ use super::style::{ArrowType, DimensionStyle, ToleranceMode};
 use crate::types::{Point2D, ProjectedView};
 
 /// Definition of the angle to measure.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AngleDefinition {
     /// Angle between two edges (uses edge directions).
     TwoEdges {
@@ -39,7 +39,7 @@ pub enum AngleDefinition {
 }
 
 /// An angular dimension measuring an angle.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AngularDimension {
     /// Definition of the angle to measure.
     pub definition: AngleDefinition,
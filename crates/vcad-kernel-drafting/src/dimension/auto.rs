@@ -0,0 +1,289 @@
+//! Automatic dimensioning of projected views.
+//!
+//! Adds overall extents and circle dimensions to an [`AnnotationLayer`]
+//! without placing each annotation by hand.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use vcad_kernel_math::Quantize;
+
+use super::geometry_ref::GeometryRef;
+use super::layer::AnnotationLayer;
+use crate::section::DEFAULT_TOLERANCE;
+use crate::types::{Point2D, ProjectedView, Visibility};
+
+/// Which dimensions [`AnnotationLayer::auto_dimension`] should create.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoDimOptions {
+    /// Add overall horizontal and vertical extent dimensions.
+    pub overall: bool,
+    /// Add radius dimensions for detected circles.
+    pub radial: bool,
+    /// Call out the largest circle with a diameter leader instead of a radius.
+    pub hole_callout: bool,
+    /// Include hidden edges when detecting geometry.
+    pub include_hidden: bool,
+    /// Distance between the geometry and the overall dimension lines.
+    pub offset: f64,
+    /// Minimum number of segments for a closed loop to count as a circle.
+    pub min_circle_segments: usize,
+}
+
+impl Default for AutoDimOptions {
+    fn default() -> Self {
+        Self {
+            overall: true,
+            radial: true,
+            hole_callout: true,
+            include_hidden: false,
+            offset: 10.0,
+            min_circle_segments: 8,
+        }
+    }
+}
+
+/// A circle recovered from tessellated edges.
+#[derive(Debug, Clone, Copy)]
+struct DetectedCircle {
+    center: Point2D,
+    radius: f64,
+}
+
+/// Relative tolerance on radius variation for circle detection.
+const CIRCLE_TOLERANCE: f64 = 0.01;
+
+/// Leader angle for circle dimensions (up and to the right).
+const LEADER_ANGLE: f64 = std::f64::consts::FRAC_PI_4;
+
+impl AnnotationLayer {
+    /// Add dimensions derived from the geometry of `view`.
+    ///
+    /// Overall width is dimensioned below the view and overall height to its
+    /// left, attached to the extreme edge endpoints. Closed loops of edges
+    /// whose vertices are equidistant from their centroid are treated as
+    /// circles; the largest gets a diameter callout and the others a radius
+    /// dimension, depending on `opts`.
+    pub fn auto_dimension(&mut self, view: &ProjectedView, opts: AutoDimOptions) -> &mut Self {
+        let edges: Vec<usize> = view
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| opts.include_hidden || e.visibility == Visibility::Visible)
+            .filter(|(_, e)| !e.is_degenerate(1e-9))
+            .map(|(i, _)| i)
+            .collect();
+
+        if opts.overall && !edges.is_empty() {
+            let endpoints: Vec<(GeometryRef, Point2D)> = edges
+                .iter()
+                .flat_map(|&i| {
+                    let e = &view.edges[i];
+                    [
+                        (GeometryRef::edge_start(i), e.start),
+                        (GeometryRef::edge_end(i), e.end),
+                    ]
+                })
+                .collect();
+            let extreme = |key: fn(&Point2D) -> f64| {
+                let min = endpoints
+                    .iter()
+                    .min_by(|a, b| key(&a.1).total_cmp(&key(&b.1)))
+                    .cloned();
+                let max = endpoints
+                    .iter()
+                    .max_by(|a, b| key(&a.1).total_cmp(&key(&b.1)))
+                    .cloned();
+                min.zip(max)
+            };
+
+            if let Some(((left, lp), (right, rp))) = extreme(|p| p.x) {
+                if (rp.x - lp.x).abs() > 1e-9 {
+                    let below = view.bounds.min_y - lp.y.min(rp.y) - opts.offset;
+                    self.add_horizontal_dimension(left, right, below);
+                }
+            }
+            if let Some(((bottom, bp), (top, tp))) = extreme(|p| p.y) {
+                if (tp.y - bp.y).abs() > 1e-9 {
+                    let left = view.bounds.min_x - bp.x.min(tp.x) - opts.offset;
+                    self.add_vertical_dimension(bottom, top, left);
+                }
+            }
+        }
+
+        let mut circles = detect_circles(view, &edges, opts.min_circle_segments);
+        circles.sort_by(|a, b| b.radius.total_cmp(&a.radius));
+        for (i, circle) in circles.iter().enumerate() {
+            let circle_ref = GeometryRef::circle(circle.center, circle.radius);
+            if i == 0 && opts.hole_callout {
+                self.add_diameter_dimension(circle_ref, LEADER_ANGLE);
+            } else if opts.radial {
+                self.add_radius_dimension(circle_ref, LEADER_ANGLE);
+            }
+        }
+
+        self
+    }
+}
+
+/// Find closed loops of edges that approximate circles.
+fn detect_circles(
+    view: &ProjectedView,
+    edges: &[usize],
+    min_segments: usize,
+) -> Vec<DetectedCircle> {
+    let key = |p: &Point2D| p.quantize(1.0 / DEFAULT_TOLERANCE);
+
+    // Vertex → incident edges.
+    let mut incident: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    for &i in edges {
+        let e = &view.edges[i];
+        incident.entry(key(&e.start)).or_default().push(i);
+        incident.entry(key(&e.end)).or_default().push(i);
+    }
+
+    let mut visited = vec![false; view.edges.len()];
+    let mut circles = Vec::new();
+
+    for &first in edges {
+        if visited[first] {
+            continue;
+        }
+
+        // Walk the chain through degree-2 vertices until it closes or branches.
+        let start_key = key(&view.edges[first].start);
+        let mut points = vec![view.edges[first].start];
+        let mut current = first;
+        let mut at = key(&view.edges[first].end);
+        let mut closed = false;
+        visited[first] = true;
+        loop {
+            let e = &view.edges[current];
+            points.push(if key(&e.end) == at { e.end } else { e.start });
+            if at == start_key {
+                closed = true;
+                break;
+            }
+            let next = match incident.get(&at).map(Vec::as_slice) {
+                Some([a, b]) => {
+                    if *a == current {
+                        *b
+                    } else {
+                        *a
+                    }
+                }
+                _ => break,
+            };
+            if visited[next] {
+                break;
+            }
+            visited[next] = true;
+            let n = &view.edges[next];
+            at = if key(&n.start) == at {
+                key(&n.end)
+            } else {
+                key(&n.start)
+            };
+            current = next;
+        }
+
+        // The last point repeats the first.
+        points.pop();
+        if !closed || points.len() < min_segments {
+            continue;
+        }
+
+        let n = points.len() as f64;
+        let center = Point2D::new(
+            points.iter().map(|p| p.x).sum::<f64>() / n,
+            points.iter().map(|p| p.y).sum::<f64>() / n,
+        );
+        let radii: Vec<f64> = points.iter().map(|p| p.distance(&center)).collect();
+        let radius = radii.iter().sum::<f64>() / n;
+        if radius > 1e-9
+            && radii
+                .iter()
+                .all(|r| (r - radius).abs() <= CIRCLE_TOLERANCE * radius)
+        {
+            circles.push(DetectedCircle { center, radius });
+        }
+    }
+
+    circles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EdgeType, ProjectedEdge, ViewDirection};
+
+    fn line(view: &mut ProjectedView, a: (f64, f64), b: (f64, f64)) {
+        view.add_edge(ProjectedEdge::new(
+            Point2D::new(a.0, a.1),
+            Point2D::new(b.0, b.1),
+            Visibility::Visible,
+            EdgeType::Sharp,
+            0.0,
+        ));
+    }
+
+    /// A 100×50 plate with a ⌀20 hole at (30, 25).
+    fn plate_view() -> ProjectedView {
+        let mut view = ProjectedView::new(ViewDirection::Top);
+        line(&mut view, (0.0, 0.0), (100.0, 0.0));
+        line(&mut view, (100.0, 0.0), (100.0, 50.0));
+        line(&mut view, (100.0, 50.0), (0.0, 50.0));
+        line(&mut view, (0.0, 50.0), (0.0, 0.0));
+
+        let n = 32;
+        let point = |i: usize| {
+            let a = std::f64::consts::TAU * i as f64 / n as f64;
+            (30.0 + 10.0 * a.cos(), 25.0 + 10.0 * a.sin())
+        };
+        for i in 0..n {
+            line(&mut view, point(i), point((i + 1) % n));
+        }
+        view
+    }
+
+    #[test]
+    fn test_auto_dimension_plate_with_hole() {
+        let view = plate_view();
+        let mut layer = AnnotationLayer::new();
+        layer.auto_dimension(&view, AutoDimOptions::default());
+
+        assert_eq!(layer.annotation_count(), 3);
+        assert_eq!(layer.linear_dimensions.len(), 2);
+        assert_eq!(layer.radial_dimensions.len(), 1);
+        assert!(layer.radial_dimensions[0].is_diameter);
+
+        match layer.radial_dimensions[0].circle_ref {
+            GeometryRef::Circle { center, radius } => {
+                assert!(center.distance(&Point2D::new(30.0, 25.0)) < 1e-9);
+                assert!((radius - 10.0).abs() < 0.1);
+            }
+            ref other => panic!("expected circle ref, got {other:?}"),
+        }
+
+        let rendered = layer.render_all(Some(&view));
+        assert_eq!(rendered.len(), 3);
+        assert_eq!(rendered[0].texts[0].text, "100.00");
+        assert_eq!(rendered[1].texts[0].text, "50.00");
+    }
+
+    #[test]
+    fn test_auto_dimension_options() {
+        let view = plate_view();
+        let mut layer = AnnotationLayer::new();
+        layer.auto_dimension(
+            &view,
+            AutoDimOptions {
+                overall: false,
+                hole_callout: false,
+                ..Default::default()
+            },
+        );
+        assert_eq!(layer.annotation_count(), 1);
+        assert!(!layer.radial_dimensions[0].is_diameter);
+    }
+}
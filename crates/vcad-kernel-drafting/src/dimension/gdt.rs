@@ -143,7 +143,7 @@ impl MaterialCondition {
 }
 
 /// Reference to a datum feature.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DatumRef {
     /// Datum letter (A, B, C, etc.).
     pub letter: char,
@@ -179,7 +179,7 @@ impl fmt::Display for DatumRef {
 }
 
 /// A datum feature symbol (triangle with letter).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DatumFeatureSymbol {
     /// Datum letter (A, B, C, etc.).
     pub letter: char,
@@ -253,7 +253,7 @@ impl DatumFeatureSymbol {
 }
 
 /// A feature control frame (the rectangular GD&T callout).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FeatureControlFrame {
     /// The GD&T symbol (position, flatness, etc.).
     pub symbol: GdtSymbol,
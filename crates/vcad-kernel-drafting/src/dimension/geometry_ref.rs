@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 /// - Edge indices from a projected view
 /// - Circle/arc center and radius
 /// - Computed positions (intersections, midpoints, endpoints)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GeometryRef {
     /// Direct 2D point coordinate.
     Point(Point2D),
@@ -8,18 +8,29 @@ use serde::{Deserialize, Serialize};
 use super::angular::AngularDimension;
 use super::gdt::{DatumFeatureSymbol, FeatureControlFrame, GdtSymbol};
 use super::geometry_ref::GeometryRef;
-use super::linear::LinearDimension;
+use super::linear::{LinearDimension, LinearDimensionType};
 use super::ordinate::OrdinateDimension;
 use super::radial::RadialDimension;
 use super::render::RenderedDimension;
 use super::style::DimensionStyle;
-use crate::types::{Point2D, ProjectedView};
+use crate::projection::ViewMatrix;
+use crate::types::{Point2D, ProjectedView, ViewDirection};
+
+/// The projected view an [`AnnotationLayer`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AnnotatedView {
+    /// Direction the view looks along.
+    pub direction: ViewDirection,
+    /// World-to-drawing transform of the view, so annotations can be placed
+    /// back in 3D without recomputing it.
+    pub transform: ViewMatrix,
+}
 
 /// Container for all dimension annotations in a drawing.
 ///
 /// Collects linear, angular, radial, ordinate dimensions, and GD&T
 /// annotations, then renders them to graphical primitives.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct AnnotationLayer {
     /// Linear dimensions (horizontal, vertical, aligned, rotated).
     pub linear_dimensions: Vec<LinearDimension>,
@@ -41,6 +52,12 @@ pub struct AnnotationLayer {
 
     /// Default style for all dimensions.
     pub default_style: DimensionStyle,
+
+    /// The projected view the annotations belong to.
+    ///
+    /// Edge references in the annotations index into that view's edges.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view: Option<AnnotatedView>,
 }
 
 impl AnnotationLayer {
@@ -57,6 +74,30 @@ impl AnnotationLayer {
         }
     }
 
+    /// Attach the layer to the projected view looking along `direction`.
+    pub fn for_view(mut self, direction: ViewDirection) -> Self {
+        self.view = Some(AnnotatedView {
+            direction,
+            transform: ViewMatrix::from_view_direction(direction),
+        });
+        self
+    }
+
+    /// Serialize the whole layer, styles included, to compact JSON.
+    ///
+    /// Geometry references are stored as given: points and circles by
+    /// coordinates, edges by their index in the view. Since a serialized
+    /// [`ProjectedView`] keeps its edges in order, edge references resolve
+    /// the same way against a reloaded view.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Load a layer written by [`AnnotationLayer::to_json`].
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
     // ========================================================================
     // Linear dimension builders
     // ========================================================================
@@ -116,6 +157,65 @@ impl AnnotationLayer {
         self
     }
 
+    /// Add baseline dimensions from `base` to each of `points`.
+    ///
+    /// Every dimension starts at `base`. The nearest point is dimensioned at
+    /// `offset` and each following one a further `offset` out, so the
+    /// dimension lines stack without overlapping. The whole group is
+    /// horizontal, or vertical if the points spread further in Y than in X
+    /// (references that need a view to resolve are ignored for this).
+    pub fn add_baseline_chain(
+        &mut self,
+        base: impl Into<GeometryRef>,
+        points: &[GeometryRef],
+        offset: f64,
+    ) -> &mut Self {
+        let base = base.into();
+        let direction = chain_direction(std::iter::once(&base).chain(points));
+        let along = |r: &GeometryRef| {
+            r.resolve_standalone().map(|p| match direction {
+                LinearDimensionType::Vertical => p.y,
+                _ => p.x,
+            })
+        };
+
+        let mut ordered: Vec<&GeometryRef> = points.iter().collect();
+        if let Some(origin) = along(&base) {
+            // Unresolved references sort last, in their given order
+            ordered.sort_by(|a, b| {
+                let distance = |r| along(r).map_or(f64::INFINITY, |v| (v - origin).abs());
+                distance(a).total_cmp(&distance(b))
+            });
+        }
+
+        for (i, point) in ordered.into_iter().enumerate() {
+            self.linear_dimensions.push(chain_dimension(
+                base.clone(),
+                point.clone(),
+                direction,
+                offset * (i + 1) as f64,
+            ));
+        }
+        self
+    }
+
+    /// Add continuous (chain) dimensions between consecutive `points`.
+    ///
+    /// All dimensions share the same `offset` so they line up end to end.
+    /// The direction is chosen as in [`add_baseline_chain`](Self::add_baseline_chain).
+    pub fn add_continuous_chain(&mut self, points: &[GeometryRef], offset: f64) -> &mut Self {
+        let direction = chain_direction(points);
+        for pair in points.windows(2) {
+            self.linear_dimensions.push(chain_dimension(
+                pair[0].clone(),
+                pair[1].clone(),
+                direction,
+                offset,
+            ));
+        }
+        self
+    }
+
     // ========================================================================
     // Angular dimension builders
     // ========================================================================
@@ -352,9 +452,40 @@ impl AnnotationLayer {
     }
 }
 
+/// Direction shared by a group of chained dimensions.
+///
+/// Horizontal when the points that resolve without a view spread at least
+/// as far in X as in Y, vertical otherwise. Groups that can't be resolved
+/// default to horizontal.
+fn chain_direction<'a>(refs: impl IntoIterator<Item = &'a GeometryRef>) -> LinearDimensionType {
+    let mut bounds: Option<(Point2D, Point2D)> = None;
+    for p in refs.into_iter().filter_map(GeometryRef::resolve_standalone) {
+        let (min, max) = bounds.get_or_insert((p, p));
+        *min = Point2D::new(min.x.min(p.x), min.y.min(p.y));
+        *max = Point2D::new(max.x.max(p.x), max.y.max(p.y));
+    }
+    match bounds {
+        Some((min, max)) if max.y - min.y > max.x - min.x => LinearDimensionType::Vertical,
+        _ => LinearDimensionType::Horizontal,
+    }
+}
+
+fn chain_dimension(
+    p1: GeometryRef,
+    p2: GeometryRef,
+    direction: LinearDimensionType,
+    offset: f64,
+) -> LinearDimension {
+    match direction {
+        LinearDimensionType::Vertical => LinearDimension::vertical(p1, p2, offset),
+        _ => LinearDimension::horizontal(p1, p2, offset),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{EdgeType, ProjectedEdge, Visibility};
 
     #[test]
     fn test_empty_layer() {
@@ -393,6 +524,57 @@ mod tests {
         assert_eq!(rendered.len(), 2);
     }
 
+    #[test]
+    fn test_baseline_chain() {
+        let base = Point2D::new(0.0, 0.0);
+        let mut layer = AnnotationLayer::new();
+        layer.add_baseline_chain(
+            base,
+            &[GeometryRef::point(80.0, 5.0), GeometryRef::point(30.0, 0.0)],
+            10.0,
+        );
+
+        let dims = &layer.linear_dimensions;
+        assert_eq!(dims.len(), 2);
+        for dim in dims {
+            assert!(matches!(dim.point1, GeometryRef::Point(p) if p == base));
+            assert_eq!(dim.direction, LinearDimensionType::Horizontal);
+        }
+        // Nearest point innermost
+        assert!(matches!(dims[0].point2, GeometryRef::Point(p) if p.x == 30.0));
+        assert_eq!(dims[0].offset, 10.0);
+        assert_eq!(dims[1].offset, 20.0);
+
+        let texts: Vec<_> = layer
+            .render_all(None)
+            .into_iter()
+            .map(|r| r.texts[0].text.clone())
+            .collect();
+        assert_eq!(texts, ["30.00", "80.00"]);
+    }
+
+    #[test]
+    fn test_continuous_chain() {
+        let points = [
+            GeometryRef::point(0.0, 0.0),
+            GeometryRef::point(2.0, 15.0),
+            GeometryRef::point(0.0, 40.0),
+            GeometryRef::point(1.0, 50.0),
+        ];
+        let mut layer = AnnotationLayer::new();
+        layer.add_continuous_chain(&points, -8.0);
+
+        let dims = &layer.linear_dimensions;
+        assert_eq!(dims.len(), 3);
+        for (dim, pair) in dims.iter().zip(points.windows(2)) {
+            assert_eq!(dim.direction, LinearDimensionType::Vertical);
+            assert_eq!(dim.offset, -8.0);
+            assert!(
+                matches!((&dim.point1, &pair[0]), (GeometryRef::Point(a), GeometryRef::Point(b)) if a == b)
+            );
+        }
+    }
+
     #[test]
     fn test_gdt_annotations() {
         let mut layer = AnnotationLayer::new();
@@ -431,4 +613,46 @@ mod tests {
         let rendered = layer.render_all(None);
         assert_eq!(rendered[0].texts[0].text, "100.000");
     }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let mut view = ProjectedView::new(ViewDirection::Front);
+        view.add_edge(ProjectedEdge::new(
+            Point2D::new(0.0, 0.0),
+            Point2D::new(80.0, 0.0),
+            Visibility::Visible,
+            EdgeType::Sharp,
+            0.0,
+        ));
+
+        let mut layer = AnnotationLayer::with_style(DimensionStyle::new().with_precision(1))
+            .for_view(ViewDirection::Front);
+        layer
+            .add_linear_dimension(
+                LinearDimension::horizontal(
+                    GeometryRef::edge_start(0),
+                    GeometryRef::edge_end(0),
+                    12.5,
+                )
+                .with_style(DimensionStyle::new().with_precision(3)),
+            )
+            .add_position_tolerance(0.05, Point2D::new(100.0, 50.0), 'A')
+            .add_datum_symbol('A', Point2D::new(50.0, -10.0));
+
+        let restored = AnnotationLayer::from_json(&layer.to_json().unwrap()).unwrap();
+        assert_eq!(restored, layer);
+        let restored_view = restored.view.unwrap();
+        assert_eq!(restored_view.direction, ViewDirection::Front);
+        assert_eq!(
+            restored_view.transform,
+            ViewMatrix::from_view_direction(ViewDirection::Front)
+        );
+        assert!(restored.linear_dimensions[0].style.is_some());
+
+        // Edge references resolve against a reloaded view
+        let view: ProjectedView =
+            serde_json::from_str(&serde_json::to_string(&view).unwrap()).unwrap();
+        let rendered = restored.render_all(Some(&view));
+        assert_eq!(rendered[0].texts[0].text, "80.000");
+    }
 }
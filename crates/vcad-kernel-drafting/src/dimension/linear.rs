@@ -28,7 +28,7 @@ pub enum LinearDimensionType {
 }
 
 /// A linear dimension measuring distance between two points.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LinearDimension {
     /// First measurement point reference.
     pub point1: GeometryRef,
@@ -34,6 +34,7 @@
 //! ```
 
 mod angular;
+mod auto;
 mod gdt;
 mod geometry_ref;
 mod layer;
@@ -44,11 +45,14 @@ mod render;
 mod style;
 
 pub use angular::{AngleDefinition, AngularDimension};
+pub use auto::AutoDimOptions;
 pub use gdt::{DatumFeatureSymbol, DatumRef, FeatureControlFrame, GdtSymbol, MaterialCondition};
 pub use geometry_ref::GeometryRef;
-pub use layer::AnnotationLayer;
+pub use layer::{AnnotatedView, AnnotationLayer};
 pub use linear::{LinearDimension, LinearDimensionType};
 pub use ordinate::OrdinateDimension;
 pub use radial::RadialDimension;
-pub use render::{RenderedArc, RenderedArrow, RenderedDimension, RenderedText, TextAlignment};
+pub use render::{
+    ArrowShape, RenderedArc, RenderedArrow, RenderedDimension, RenderedText, TextAlignment,
+};
 pub use style::{ArrowType, DimensionStyle, TextPlacement, ToleranceMode};
@@ -15,7 +15,7 @@ use crate::types::{Point2D, ProjectedView};
 /// Ordinate dimensions are used in precision manufacturing drawings
 /// to show X or Y coordinates from a common datum point, reducing
 /// tolerance stack-up compared to chain dimensions.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OrdinateDimension {
     /// The point being dimensioned.
     pub point: GeometryRef,
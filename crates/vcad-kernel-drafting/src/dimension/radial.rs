@@ -4,13 +4,15 @@
 
 use serde::{Deserialize, Serialize};
 
+use std::f64::consts::TAU;
+
 use super::geometry_ref::GeometryRef;
 use super::render::{RenderedArrow, RenderedDimension, RenderedText, TextAlignment};
 use super::style::{ArrowType, DimensionStyle, ToleranceMode};
 use crate::types::{Point2D, ProjectedView};
 
 /// A radial dimension measuring radius or diameter of a circle/arc.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RadialDimension {
     /// Reference to the circle or arc being dimensioned.
     pub circle_ref: GeometryRef,
@@ -26,6 +28,13 @@ pub struct RadialDimension {
 
     /// Optional custom style (uses default if None).
     pub style: Option<DimensionStyle>,
+
+    /// Apparent center for a jogged (foreshortened) radius leader.
+    ///
+    /// When set, the leader starts here instead of at the true center and
+    /// zig-zags onto the radial line before reaching the arc, so large radii
+    /// fit on the sheet. Ignored for diameter dimensions.
+    pub jog: Option<Point2D>,
 }
 
 impl RadialDimension {
@@ -37,6 +46,7 @@ impl RadialDimension {
             leader_angle,
             text_override: None,
             style: None,
+            jog: None,
         }
     }
 
@@ -48,9 +58,40 @@ impl RadialDimension {
             leader_angle,
             text_override: None,
             style: None,
+            jog: None,
         }
     }
 
+    /// Dimension a full circle: a diameter through the center.
+    pub fn from_circle(center: Point2D, radius: f64, leader_angle: f64) -> Self {
+        Self::diameter(GeometryRef::circle(center, radius), leader_angle)
+    }
+
+    /// Dimension an arc running counter-clockwise from `start_angle` to
+    /// `end_angle`.
+    ///
+    /// Partial arcs get a radius leader from the center through the middle
+    /// of the arc; an arc spanning a full turn is treated as a circle and
+    /// gets a diameter.
+    pub fn from_arc(center: Point2D, radius: f64, start_angle: f64, end_angle: f64) -> Self {
+        let sweep = end_angle - start_angle;
+        if sweep.abs() >= TAU - FULL_TURN_TOLERANCE {
+            return Self::from_circle(center, radius, start_angle);
+        }
+        let mid_angle = start_angle + sweep.rem_euclid(TAU) * 0.5;
+        Self::radius(
+            GeometryRef::arc(center, radius, start_angle, end_angle),
+            mid_angle,
+        )
+    }
+
+    /// Jog the radius leader so that it starts at `origin` instead of the
+    /// true center.
+    pub fn with_jog(mut self, origin: Point2D) -> Self {
+        self.jog = Some(origin);
+        self
+    }
+
     /// Set a custom style.
     pub fn with_style(mut self, style: DimensionStyle) -> Self {
         self.style = Some(style);
@@ -146,7 +187,16 @@ impl RadialDimension {
                 center.y + (radius + text_extension) * sin_a,
             );
 
-            result.add_line(center, leader_end);
+            match self.jog {
+                Some(origin) => {
+                    for (start, end) in
+                        jogged_leader(center, radius, cos_a, sin_a, origin, leader_end)
+                    {
+                        result.add_line(start, end);
+                    }
+                }
+                None => result.add_line(center, leader_end),
+            }
 
             // Arrow at the edge of the circle, pointing outward
             if style.arrow_type != ArrowType::None {
@@ -187,6 +237,49 @@ impl RadialDimension {
     }
 }
 
+/// Angular slack, in radians, for treating an arc as a full circle.
+const FULL_TURN_TOLERANCE: f64 = 1e-9;
+
+/// Segments of a jogged radius leader from `origin` to `leader_end`.
+///
+/// The leader runs from `origin` parallel to the radial direction, crosses
+/// over to the true radial line with a 45° jog halfway to the arc, and then
+/// follows the radial line out past the arrow.
+fn jogged_leader(
+    center: Point2D,
+    radius: f64,
+    cos_a: f64,
+    sin_a: f64,
+    origin: Point2D,
+    leader_end: Point2D,
+) -> Vec<(Point2D, Point2D)> {
+    let along_line = |t: f64| Point2D::new(center.x + t * cos_a, center.y + t * sin_a);
+
+    // Position of the origin along the radial line and its offset across it
+    let (dx, dy) = (origin.x - center.x, origin.y - center.y);
+    let t_origin = dx * cos_a + dy * sin_a;
+    let offset = (dx * sin_a - dy * cos_a).abs();
+
+    if offset < 1e-12 || t_origin + offset >= radius {
+        // Already on the radial line, or too close to the arc to fit a jog
+        return vec![(origin, leader_end)];
+    }
+
+    // Split the remaining run evenly around the jog
+    let t_jog = t_origin + (radius - t_origin - offset) * 0.5;
+    let jog_start = Point2D::new(
+        origin.x + (t_jog - t_origin) * cos_a,
+        origin.y + (t_jog - t_origin) * sin_a,
+    );
+    let jog_end = along_line(t_jog + offset);
+
+    vec![
+        (origin, jog_start),
+        (jog_start, jog_end),
+        (jog_end, leader_end),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,4 +365,60 @@ mod tests {
 
         assert!(rendered.texts[0].text.contains("R30"));
     }
+
+    #[test]
+    fn test_from_circle_is_diameter_through_center() {
+        let center = Point2D::new(10.0, 20.0);
+        let dim = RadialDimension::from_circle(center, 5.0, std::f64::consts::FRAC_PI_6);
+        assert!(dim.is_diameter);
+
+        let style = DimensionStyle::default();
+        let rendered = dim.render(None, &style).unwrap();
+        assert!(rendered.texts[0].text.starts_with('\u{2300}'));
+        assert!(rendered.texts[0].text.contains("10"));
+
+        // The dimension line passes through the center
+        let (p1, p2) = rendered.lines[0];
+        let mid = Point2D::new((p1.x + p2.x) * 0.5, (p1.y + p2.y) * 0.5);
+        assert!((mid.x - center.x).abs() < 1e-9 && (mid.y - center.y).abs() < 1e-9);
+        assert!((p1.distance(&p2) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_from_arc_picks_radius() {
+        let dim = RadialDimension::from_arc(Point2D::new(0.0, 0.0), 8.0, 0.0, std::f64::consts::PI);
+        assert!(!dim.is_diameter);
+        assert!((dim.leader_angle - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+
+        // A full-turn arc is a circle
+        let dim = RadialDimension::from_arc(Point2D::new(0.0, 0.0), 8.0, 0.0, TAU);
+        assert!(dim.is_diameter);
+    }
+
+    #[test]
+    fn test_jogged_radius() {
+        let center = Point2D::new(0.0, 0.0);
+        let dim = RadialDimension::from_arc(center, 500.0, 0.0, std::f64::consts::FRAC_PI_2)
+            .with_jog(Point2D::new(300.0, 250.0));
+
+        let style = DimensionStyle::default();
+        let rendered = dim.render(None, &style).unwrap();
+
+        // Leader, jog, and radial run instead of a single line
+        assert_eq!(rendered.lines.len(), 3);
+        assert_eq!(rendered.lines[0].0, Point2D::new(300.0, 250.0));
+        for pair in rendered.lines.windows(2) {
+            assert!(pair[0].1.distance(&pair[1].0) < 1e-9);
+        }
+
+        // The last segment lies on the true radial line through the arrow
+        let (start, end) = rendered.lines[2];
+        let a = std::f64::consts::FRAC_PI_4;
+        for p in [start, end] {
+            assert!((p.x * a.sin() - p.y * a.cos()).abs() < 1e-9);
+        }
+        let tip = rendered.arrows[0].tip;
+        assert!((tip.distance(&center) - 500.0).abs() < 1e-9);
+        assert!(rendered.texts[0].text.contains("R500"));
+    }
 }
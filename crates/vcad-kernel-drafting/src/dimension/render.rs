@@ -112,6 +112,167 @@ impl RenderedDimension {
             None
         }
     }
+
+    /// Rasterize the lines, arcs, and arrows into an anti-aliased grayscale
+    /// image for previews.
+    ///
+    /// Returns `width * height` bytes in row-major order, top row first,
+    /// holding ink coverage (0 = blank, 255 = fully covered). Drawing
+    /// coordinates map to pixels as `(x * scale, height - y * scale)`, so the
+    /// drawing origin is the bottom-left corner of the image. Strokes are
+    /// one pixel wide. Text is not drawn.
+    pub fn rasterize(&self, width: usize, height: usize, scale: f64) -> Vec<u8> {
+        let mut canvas = Canvas {
+            coverage: vec![0.0; width * height],
+            width,
+            height,
+            scale,
+        };
+        for (start, end) in &self.lines {
+            canvas.stroke(&[*start, *end]);
+        }
+        for arc in &self.arcs {
+            canvas.stroke(&arc.to_polyline(ARC_TOLERANCE_PX / scale));
+        }
+        for arrow in &self.arrows {
+            let shape = arrow.shape();
+            if shape.filled {
+                canvas.fill(&shape.points);
+            }
+            let mut outline = shape.points;
+            if shape.closed && !outline.is_empty() {
+                outline.push(outline[0]);
+            }
+            canvas.stroke(&outline);
+        }
+        canvas
+            .coverage
+            .iter()
+            .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+            .collect()
+    }
+}
+
+/// Maximum distance, in pixels, between a rasterized arc and its polyline.
+const ARC_TOLERANCE_PX: f64 = 0.25;
+
+/// Stroke width, in pixels, used by [`RenderedDimension::rasterize`].
+const STROKE_WIDTH_PX: f64 = 1.0;
+
+/// Subsamples per pixel side when filling shapes.
+const FILL_SUBSAMPLES: usize = 4;
+
+/// Ink coverage buffer for [`RenderedDimension::rasterize`].
+struct Canvas {
+    coverage: Vec<f64>,
+    width: usize,
+    height: usize,
+    scale: f64,
+}
+
+impl Canvas {
+    fn to_pixel(&self, p: Point2D) -> (f64, f64) {
+        (p.x * self.scale, self.height as f64 - p.y * self.scale)
+    }
+
+    /// Pixel ranges overlapping `points` (already in pixel space), grown
+    /// by `margin`.
+    fn pixel_span(&self, points: &[(f64, f64)], margin: f64) -> (usize, usize, usize, usize) {
+        let (mut x0, mut y0) = (f64::INFINITY, f64::INFINITY);
+        let (mut x1, mut y1) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for &(x, y) in points {
+            x0 = x0.min(x);
+            y0 = y0.min(y);
+            x1 = x1.max(x);
+            y1 = y1.max(y);
+        }
+        let clamp = |v: f64, max: usize| v.clamp(0.0, max as f64) as usize;
+        (
+            clamp((x0 - margin).floor(), self.width),
+            clamp((x1 + margin).ceil(), self.width),
+            clamp((y0 - margin).floor(), self.height),
+            clamp((y1 + margin).ceil(), self.height),
+        )
+    }
+
+    fn cover(&mut self, x: usize, y: usize, amount: f64) {
+        let c = &mut self.coverage[y * self.width + x];
+        *c = c.max(amount);
+    }
+
+    /// Stroke a polyline, shading each pixel by its distance from the line.
+    fn stroke(&mut self, points: &[Point2D]) {
+        let half = STROKE_WIDTH_PX / 2.0;
+        for pair in points.windows(2) {
+            let a = self.to_pixel(pair[0]);
+            let b = self.to_pixel(pair[1]);
+            let (x0, x1, y0, y1) = self.pixel_span(&[a, b], half + 1.0);
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let d = segment_distance((x as f64 + 0.5, y as f64 + 0.5), a, b);
+                    let amount = half + 0.5 - d;
+                    if amount > 0.0 {
+                        self.cover(x, y, amount.min(1.0));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fill a closed polygon, shading edge pixels by subsample coverage.
+    fn fill(&mut self, points: &[Point2D]) {
+        if points.len() < 3 {
+            return;
+        }
+        let polygon: Vec<(f64, f64)> = points.iter().map(|&p| self.to_pixel(p)).collect();
+        let (x0, x1, y0, y1) = self.pixel_span(&polygon, 0.0);
+        let n = FILL_SUBSAMPLES;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let mut inside = 0;
+                for sy in 0..n {
+                    for sx in 0..n {
+                        let sample = (
+                            x as f64 + (sx as f64 + 0.5) / n as f64,
+                            y as f64 + (sy as f64 + 0.5) / n as f64,
+                        );
+                        if point_in_polygon(sample, &polygon) {
+                            inside += 1;
+                        }
+                    }
+                }
+                if inside > 0 {
+                    self.cover(x, y, inside as f64 / (n * n) as f64);
+                }
+            }
+        }
+    }
+}
+
+/// Distance from `p` to the segment `a`-`b`.
+fn segment_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dy * dy;
+    let t = if len_sq > 0.0 {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (p.0 - (a.0 + t * dx)).hypot(p.1 - (a.1 + t * dy))
+}
+
+/// Even-odd point-in-polygon test.
+fn point_in_polygon(p: (f64, f64), polygon: &[(f64, f64)]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.1 > p.1) != (b.1 > p.1) && p.0 < a.0 + (p.1 - a.1) * (b.0 - a.0) / (b.1 - a.1) {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
 }
 
 /// A rendered arc segment.
@@ -161,6 +322,28 @@ impl RenderedArc {
     pub fn span(&self) -> f64 {
         (self.end_angle - self.start_angle).abs()
     }
+
+    /// Approximate the arc by a polyline from start to end whose chords
+    /// stay within `tolerance` of the true arc.
+    pub fn to_polyline(&self, tolerance: f64) -> Vec<Point2D> {
+        let ratio = (1.0 - tolerance / self.radius).clamp(-1.0, 1.0);
+        let max_step = (2.0 * ratio.acos()).min(std::f64::consts::FRAC_PI_2);
+        let count = if max_step > 0.0 {
+            ((self.span() / max_step).ceil() as usize).max(1)
+        } else {
+            1
+        };
+        (0..=count)
+            .map(|i| {
+                let t = i as f64 / count as f64;
+                let angle = self.start_angle + t * (self.end_angle - self.start_angle);
+                Point2D::new(
+                    self.center.x + self.radius * angle.cos(),
+                    self.center.y + self.radius * angle.sin(),
+                )
+            })
+            .collect()
+    }
 }
 
 /// A rendered arrow terminator.
@@ -221,6 +404,65 @@ impl RenderedArrow {
         let (tip, p1, p2) = self.arrowhead_points();
         ((tip, p1), (tip, p2))
     }
+
+    /// Get the outline of the terminator for its [`ArrowType`].
+    ///
+    /// - `ClosedFilled`/`ClosedBlank`: the closed triangle from
+    ///   [`Self::arrowhead_points`], filled or not.
+    /// - `Open`: the open V `barb, tip, barb`.
+    /// - `Tick`: a slash of length `size` through the tip at 45° to the
+    ///   arrow direction.
+    /// - `Dot`: a filled circle of diameter `size / 2` around the tip.
+    /// - `None`: no points.
+    pub fn shape(&self) -> ArrowShape {
+        let (tip, p1, p2) = self.arrowhead_points();
+        let (points, closed, filled) = match self.arrow_type {
+            ArrowType::ClosedFilled => (vec![tip, p1, p2], true, true),
+            ArrowType::ClosedBlank => (vec![tip, p1, p2], true, false),
+            ArrowType::Open => (vec![p1, tip, p2], false, false),
+            ArrowType::Tick => {
+                let angle = self.direction + std::f64::consts::FRAC_PI_4;
+                let (dx, dy) = (self.size / 2.0 * angle.cos(), self.size / 2.0 * angle.sin());
+                let points = vec![
+                    Point2D::new(tip.x - dx, tip.y - dy),
+                    Point2D::new(tip.x + dx, tip.y + dy),
+                ];
+                (points, false, false)
+            }
+            ArrowType::Dot => {
+                let radius = self.size / 4.0;
+                let points = (0..DOT_SEGMENTS)
+                    .map(|i| {
+                        let angle = std::f64::consts::TAU * i as f64 / DOT_SEGMENTS as f64;
+                        Point2D::new(tip.x + radius * angle.cos(), tip.y + radius * angle.sin())
+                    })
+                    .collect();
+                (points, true, true)
+            }
+            ArrowType::None => (Vec::new(), false, false),
+        };
+        ArrowShape {
+            points,
+            closed,
+            filled,
+        }
+    }
+}
+
+/// Number of sides of the polygon approximating an [`ArrowType::Dot`].
+const DOT_SEGMENTS: usize = 16;
+
+/// Outline of an arrow terminator, see [`RenderedArrow::shape`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrowShape {
+    /// Outline vertices.
+    pub points: Vec<Point2D>,
+
+    /// Whether the last point connects back to the first.
+    pub closed: bool,
+
+    /// Whether the interior is filled.
+    pub filled: bool,
 }
 
 /// A rendered text label.
@@ -378,6 +620,81 @@ mod tests {
         assert!((end.y - 10.0).abs() < 1e-10);
     }
 
+    fn arrow(arrow_type: ArrowType) -> RenderedArrow {
+        RenderedArrow::new(Point2D::new(5.0, 5.0), 0.0, arrow_type, 2.0)
+    }
+
+    #[test]
+    fn test_arrow_shapes() {
+        let filled = arrow(ArrowType::ClosedFilled).shape();
+        assert_eq!(filled.points.len(), 3);
+        assert!(filled.closed && filled.filled);
+
+        let blank = arrow(ArrowType::ClosedBlank).shape();
+        assert_eq!(blank.points, filled.points);
+        assert!(blank.closed && !blank.filled);
+
+        let open = arrow(ArrowType::Open).shape();
+        assert_eq!(open.points.len(), 3);
+        assert_eq!(open.points[1], Point2D::new(5.0, 5.0));
+        assert!(!open.closed && !open.filled);
+
+        let tick = arrow(ArrowType::Tick).shape();
+        assert_eq!(tick.points.len(), 2);
+        assert!((tick.points[0].distance(&tick.points[1]) - 2.0).abs() < 1e-10);
+        // Slanted 45° and centered on the tip
+        assert!(
+            ((tick.points[1].y - tick.points[0].y) - (tick.points[1].x - tick.points[0].x)).abs()
+                < 1e-10
+        );
+        assert!((tick.points[0].x + tick.points[1].x - 10.0).abs() < 1e-10);
+
+        let dot = arrow(ArrowType::Dot).shape();
+        assert_eq!(dot.points.len(), DOT_SEGMENTS);
+        assert!(dot.closed && dot.filled);
+        for p in &dot.points {
+            assert!((p.distance(&Point2D::new(5.0, 5.0)) - 0.5).abs() < 1e-10);
+        }
+
+        assert!(arrow(ArrowType::None).shape().points.is_empty());
+    }
+
+    #[test]
+    fn test_arc_polyline_tolerance() {
+        let arc = RenderedArc::new(Point2D::new(0.0, 0.0), 10.0, 0.0, std::f64::consts::PI);
+        let points = arc.to_polyline(0.01);
+        assert!(points.len() > 10);
+        assert!((points[0].x - 10.0).abs() < 1e-10);
+        assert!((points.last().unwrap().x + 10.0).abs() < 1e-10);
+        for pair in points.windows(2) {
+            let mid = Point2D::new((pair[0].x + pair[1].x) / 2.0, (pair[0].y + pair[1].y) / 2.0);
+            assert!(10.0 - mid.distance(&Point2D::ORIGIN) <= 0.01 + 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let mut rd = RenderedDimension::new();
+        rd.add_line(Point2D::new(1.0, 5.0), Point2D::new(19.0, 5.0));
+        rd.add_arrow(RenderedArrow::new(
+            Point2D::new(19.0, 5.0),
+            0.0,
+            ArrowType::ClosedFilled,
+            4.0,
+        ));
+        let image = rd.rasterize(20, 10, 1.0);
+        assert_eq!(image.len(), 200);
+
+        // The line runs along the boundary between rows 4 and 5, so both
+        // are half covered; rows far away stay blank.
+        assert_eq!(image[4 * 20 + 5], 128);
+        assert_eq!(image[5 * 20 + 5], 128);
+        assert_eq!(image[20 + 5], 0);
+        // Inside the arrowhead, and partial coverage along its slanted edge
+        assert_eq!(image[4 * 20 + 16], 255);
+        assert!(image.iter().any(|&c| c > 0 && c < 255 && c != 128));
+    }
+
     #[test]
     fn test_text_builder() {
         let text = RenderedText::new(Point2D::new(50.0, 10.0), "100.00", 2.5)
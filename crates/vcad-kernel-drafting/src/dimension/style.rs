@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Controls the visual appearance of dimension elements including text,
 /// arrows, extension lines, and tolerance display.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DimensionStyle {
     /// Height of dimension text in drawing units (default: 2.5mm).
     pub text_height: f64,
@@ -1,16 +1,22 @@
 //! Hidden line removal via occlusion testing.
 //!
 //! Determines which edges are visible and which are hidden by checking
-//! if sample points along each edge are occluded by mesh triangles.
+//! if sample points along each edge are occluded by mesh triangles, or,
+//! for B-rep solids, by the solid's faces.
 
+use vcad_kernel_geom::{GeometryStore, Plane};
+use vcad_kernel_math::intersect::{segment_intersect_2d, SegIntersection};
 use vcad_kernel_math::{Point2, Point3, Vec3};
 use vcad_kernel_tessellate::TriangleMesh;
+use vcad_kernel_topo::{Face, Orientation, Topology};
 
 use crate::edge_extract::{
     build_triangles, extract_drawing_edges, get_vertex, DEFAULT_SHARP_ANGLE,
 };
 use crate::projection::ViewMatrix;
-use crate::types::{MeshEdge, ProjectedEdge, ProjectedView, Triangle3D, ViewDirection, Visibility};
+use crate::types::{
+    EdgeType, MeshEdge, ProjectedEdge, ProjectedView, Triangle3D, ViewDirection, Visibility,
+};
 
 /// Number of sample points along each edge for occlusion testing.
 const EDGE_SAMPLES: usize = 5;
@@ -64,6 +70,197 @@ pub fn project_mesh_with_options(
     result
 }
 
+impl ProjectedView {
+    /// Project a B-rep solid along `direction` with hidden line removal.
+    ///
+    /// `direction` points from the viewer toward the model (see
+    /// [`ViewDirection::from_vector`]). Every edge is drawn as the straight
+    /// segment between its vertices and split where its visibility changes;
+    /// planar faces facing the viewer are the occluders, so a segment is
+    /// hidden when such a face lies in front of it. Mesh-only solids go
+    /// through [`project_mesh`] instead.
+    pub fn from_brep(topo: &Topology, geom: &GeometryStore, direction: Vec3) -> ProjectedView {
+        project_brep(topo, geom, ViewDirection::from_vector(direction))
+    }
+}
+
+/// A planar face facing the viewer, projected to the view.
+struct OccludingFace {
+    /// Outer loop followed by the holes, in view coordinates.
+    loops: Vec<Vec<Point2>>,
+    /// A point on the face plane.
+    origin: Point3,
+    /// Outward face normal.
+    normal: Vec3,
+}
+
+/// Project the edges of a B-rep solid, splitting each into visible and
+/// hidden pieces.
+fn project_brep(topo: &Topology, geom: &GeometryStore, view_dir: ViewDirection) -> ProjectedView {
+    let view_matrix = ViewMatrix::from_view_direction(view_dir);
+    let view_vec = view_dir.view_vector();
+
+    let occluders: Vec<OccludingFace> = topo
+        .faces
+        .values()
+        .filter_map(|face| {
+            let (origin, normal) = face_plane(geom, face)?;
+            // Back-facing and edge-on faces can't hide anything
+            if normal.dot(&view_vec) > -EPSILON {
+                return None;
+            }
+            let loops = std::iter::once(face.outer_loop)
+                .chain(face.inner_loops.iter().copied())
+                .map(|loop_id| {
+                    topo.loop_vertices(loop_id)
+                        .into_iter()
+                        .map(|v| view_matrix.project_point(topo.vertices[v].point))
+                        .collect()
+                })
+                .collect();
+            Some(OccludingFace {
+                loops,
+                origin,
+                normal,
+            })
+        })
+        .collect();
+
+    let edges: Vec<_> = topo
+        .edges
+        .iter()
+        .map(|(edge_id, edge)| {
+            let v0 = topo.vertices[topo.half_edges[edge.half_edge].origin].point;
+            let v1 = topo.vertices[topo.half_edge_dest(edge.half_edge)].point;
+            let edge_type = match topo.edge_faces(edge_id) {
+                (Some(a), Some(b)) => {
+                    let facing =
+                        |f| face_plane(geom, &topo.faces[f]).map(|(_, n)| n.dot(&view_vec) < 0.0);
+                    match (facing(a), facing(b)) {
+                        (Some(fa), Some(fb)) if fa != fb => EdgeType::Silhouette,
+                        _ => EdgeType::Sharp,
+                    }
+                }
+                _ => EdgeType::Boundary,
+            };
+            (v0, v1, edge_type)
+        })
+        .collect();
+    let projected: Vec<(Point2, Point2)> = edges
+        .iter()
+        .map(|&(v0, v1, _)| (view_matrix.project_point(v0), view_matrix.project_point(v1)))
+        .collect();
+
+    let mut result = ProjectedView::new(view_dir);
+
+    for (&(v0, v1, edge_type), &(p0, p1)) in edges.iter().zip(&projected) {
+        // Edges seen end-on project to a point
+        let d = p1 - p0;
+        if d.norm() < EPSILON {
+            continue;
+        }
+
+        // Visibility can only change where the edge crosses another edge
+        let param = |p: Point2| ((p - p0).dot(&d) / d.norm_squared()).clamp(0.0, 1.0);
+        let mut breaks = vec![0.0, 1.0];
+        for (q0, q1) in &projected {
+            match segment_intersect_2d(&p0, &p1, q0, q1) {
+                SegIntersection::None => {}
+                SegIntersection::Point { t, .. } => breaks.push(t),
+                SegIntersection::Overlap(a, b) => breaks.extend([param(a), param(b)]),
+            }
+        }
+        breaks.sort_by(f64::total_cmp);
+        breaks.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+
+        // Classify each piece at its midpoint and merge runs of equal visibility
+        let at = |t: f64| v0 + (v1 - v0) * t;
+        let mut pieces: Vec<(f64, f64, Visibility)> = Vec::new();
+        for w in breaks.windows(2) {
+            let visibility = point_visibility(at((w[0] + w[1]) / 2.0), &occluders, &view_matrix);
+            match pieces.last_mut() {
+                Some(last) if last.2 == visibility => last.1 = w[1],
+                _ => pieces.push((w[0], w[1], visibility)),
+            }
+        }
+
+        for (t0, t1, visibility) in pieces {
+            let (a, depth0) = view_matrix.project(at(t0));
+            let (b, depth1) = view_matrix.project(at(t1));
+            let projected = ProjectedEdge::new(
+                a.into(),
+                b.into(),
+                visibility,
+                edge_type,
+                (depth0 + depth1) / 2.0,
+            );
+            if !projected.is_degenerate(1e-6) {
+                result.add_edge(projected);
+            }
+        }
+    }
+
+    result
+}
+
+/// A point on the plane of a planar face and its outward normal.
+fn face_plane(geom: &GeometryStore, face: &Face) -> Option<(Point3, Vec3)> {
+    let plane = geom.surfaces[face.surface_index]
+        .as_any()
+        .downcast_ref::<Plane>()?;
+    let normal = match face.orientation {
+        Orientation::Forward => *plane.normal_dir.as_ref(),
+        Orientation::Reversed => -*plane.normal_dir.as_ref(),
+    };
+    Some((plane.origin, normal))
+}
+
+/// Whether any occluding face lies in front of `point`.
+///
+/// Points on the outline of a nearer face count as covered, so an edge
+/// directly behind another edge is hidden.
+fn point_visibility(
+    point: Point3,
+    occluders: &[OccludingFace],
+    view_matrix: &ViewMatrix,
+) -> Visibility {
+    let (p, depth) = view_matrix.project(point);
+    let hidden = occluders.iter().any(|face| {
+        // Depth of the face plane along the line of sight through `p`
+        let along = face.normal.dot(&view_matrix.forward);
+        let face_depth = (face.normal.dot(&face.origin.coords)
+            - p.x * face.normal.dot(&view_matrix.right)
+            - p.y * face.normal.dot(&view_matrix.up))
+            / along;
+        face_depth < depth - EPSILON && point_in_loops(p, &face.loops)
+    });
+    if hidden {
+        Visibility::Hidden
+    } else {
+        Visibility::Visible
+    }
+}
+
+/// Even-odd point-in-polygon test over a face's loops, counting points on
+/// a loop as inside.
+fn point_in_loops(p: Point2, loops: &[Vec<Point2>]) -> bool {
+    let mut inside = false;
+    for ring in loops {
+        for (i, &a) in ring.iter().enumerate() {
+            let b = ring[(i + 1) % ring.len()];
+            let ab = b - a;
+            let t = ((p - a).dot(&ab) / ab.norm_squared().max(1e-24)).clamp(0.0, 1.0);
+            if (a + ab * t - p).norm() < EPSILON {
+                return true;
+            }
+            if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) * ab.x / ab.y {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
 /// Check if an edge is visible or hidden.
 ///
 /// Samples multiple points along the edge and checks each for occlusion.
@@ -235,6 +432,13 @@ pub fn classify_edge_visibility(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use vcad_kernel::Solid;
+
+    /// Project the B-rep of a kernel solid.
+    fn brep_view(solid: &Solid, direction: Vec3) -> ProjectedView {
+        let brep = solid.brep().expect("B-rep solid");
+        ProjectedView::from_brep(&brep.topology, &brep.geometry, direction)
+    }
 
     /// Create a simple cube mesh for testing.
     fn make_cube_mesh() -> TriangleMesh {
@@ -328,6 +532,84 @@ mod tests {
         assert_eq!(view.edges.len(), 12);
     }
 
+    #[test]
+    fn test_from_brep_cube_top_view() {
+        let cube = Solid::cube(10.0, 20.0, 5.0);
+        let view = brep_view(&cube, Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(view.view_direction, ViewDirection::Top);
+
+        // The top face's outline is visible and the bottom face's outline
+        // right behind it is hidden; the vertical edges are seen end-on
+        assert_eq!(view.num_visible(), 4);
+        assert_eq!(view.num_hidden(), 4);
+        assert!((view.bounds.width() - 10.0).abs() < 1e-9);
+        assert!((view.bounds.height() - 20.0).abs() < 1e-9);
+        for edge in view.visible_edges() {
+            assert!((edge.depth + 5.0).abs() < 1e-9, "visible edge at z = 5");
+        }
+        for edge in view.hidden_edges() {
+            assert!(edge.depth.abs() < 1e-9, "hidden edge at z = 0");
+        }
+    }
+
+    #[test]
+    fn test_from_brep_cube_isometric() {
+        let cube = Solid::cube(10.0, 10.0, 10.0);
+        let view = brep_view(&cube, Vec3::new(1.0, 1.0, -1.0));
+
+        // Three faces are seen: nine edges visible, the three edges meeting
+        // at the far corner hidden
+        assert_eq!(view.num_visible(), 9);
+        assert_eq!(view.num_hidden(), 3);
+        let silhouettes = view
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Silhouette)
+            .count();
+        assert_eq!(silhouettes, 6);
+    }
+
+    #[test]
+    fn test_from_brep_partially_hidden_edge() {
+        use vcad_kernel::vcad_kernel_sketch::{SketchProfile, SketchSegment};
+
+        // An inverted T: a 30 x 10 plate, 2 thick, with a fin at 12..18
+        // rising to z = 20. Seen from the front-left, the fin hides part of
+        // the plate's back edge to its right, up to x = 28.
+        let corners = [
+            (0.0, 0.0),
+            (30.0, 0.0),
+            (30.0, 2.0),
+            (18.0, 2.0),
+            (18.0, 20.0),
+            (12.0, 20.0),
+            (12.0, 2.0),
+            (0.0, 2.0),
+        ];
+        let segments = (0..corners.len())
+            .map(|i| {
+                let (x0, z0) = corners[i];
+                let (x1, z1) = corners[(i + 1) % corners.len()];
+                SketchSegment::Line {
+                    start: Point2::new(x0, z0),
+                    end: Point2::new(x1, z1),
+                }
+            })
+            .collect();
+        let profile = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::z(), segments).unwrap();
+        let prism = Solid::extrude(profile, Vec3::new(0.0, 10.0, 0.0)).unwrap();
+        let view = brep_view(&prism, Vec3::new(1.0, 1.0, -1.0));
+
+        let split = ViewMatrix::from_view_direction(view.view_direction)
+            .project_point(Point3::new(28.0, 10.0, 2.0));
+        let at_split = |e: &&ProjectedEdge| {
+            let close = |p: crate::types::Point2D| (Point2::from(p) - split).norm() < 1e-6;
+            close(e.start) || close(e.end)
+        };
+        assert_eq!(view.visible_edges().filter(at_split).count(), 1);
+        assert_eq!(view.hidden_edges().filter(at_split).count(), 1);
+    }
+
     #[test]
     fn test_bounding_box_computed() {
         let mesh = make_cube_mesh();
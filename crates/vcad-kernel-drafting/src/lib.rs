@@ -46,10 +46,11 @@ pub mod types;
 // Re-export main types and functions for convenience
 pub use detail::create_detail_view;
 pub use dimension::{
-    AngleDefinition, AngularDimension, AnnotationLayer, ArrowType, DatumFeatureSymbol, DatumRef,
-    DimensionStyle, FeatureControlFrame, GdtSymbol, GeometryRef, LinearDimension,
-    LinearDimensionType, MaterialCondition, OrdinateDimension, RadialDimension, RenderedArc,
-    RenderedArrow, RenderedDimension, RenderedText, TextAlignment, TextPlacement, ToleranceMode,
+    AngleDefinition, AngularDimension, AnnotatedView, AnnotationLayer, ArrowShape, ArrowType,
+    AutoDimOptions, DatumFeatureSymbol, DatumRef, DimensionStyle, FeatureControlFrame, GdtSymbol,
+    GeometryRef, LinearDimension, LinearDimensionType, MaterialCondition, OrdinateDimension,
+    RadialDimension, RenderedArc, RenderedArrow, RenderedDimension, RenderedText, TextAlignment,
+    TextPlacement, ToleranceMode,
 };
 pub use edge_extract::{
     extract_drawing_edges, extract_edges, extract_sharp_edges, extract_silhouette_edges,
@@ -3,6 +3,7 @@
 //! Provides view matrix generation and point projection for creating
 //! 2D technical drawings from 3D geometry.
 
+use serde::{Deserialize, Serialize};
 use vcad_kernel_math::{Point2, Point3, Vec3};
 
 use crate::types::ViewDirection;
@@ -13,7 +14,10 @@ use crate::types::ViewDirection;
 /// - X is the horizontal axis of the drawing
 /// - Y is the vertical axis of the drawing
 /// - Z is depth (used for hidden line removal)
-#[derive(Debug, Clone, Copy)]
+///
+/// Serializes as its three rows.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "[[f64; 3]; 3]", into = "[[f64; 3]; 3]")]
 pub struct ViewMatrix {
     /// Row 0: right vector (X axis in view space)
     pub right: Vec3,
@@ -78,6 +82,23 @@ impl ViewMatrix {
     }
 }
 
+impl From<[[f64; 3]; 3]> for ViewMatrix {
+    fn from([right, up, forward]: [[f64; 3]; 3]) -> Self {
+        let row = |[x, y, z]: [f64; 3]| Vec3::new(x, y, z);
+        Self {
+            right: row(right),
+            up: row(up),
+            forward: row(forward),
+        }
+    }
+}
+
+impl From<ViewMatrix> for [[f64; 3]; 3] {
+    fn from(m: ViewMatrix) -> Self {
+        [m.right, m.up, m.forward].map(|v| [v.x, v.y, v.z])
+    }
+}
+
 /// Project a single 3D point to 2D using the given view direction.
 pub fn project_point(p: Point3, view: ViewDirection) -> Point2 {
     ViewMatrix::from_view_direction(view).project_point(p)
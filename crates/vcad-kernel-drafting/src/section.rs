@@ -7,13 +7,13 @@
 //! - Cross-hatch generation for solid regions
 
 use std::collections::HashMap;
-use vcad_kernel_math::{Point3, Vec3};
+use vcad_kernel_math::{Point3, Quantize, Vec3};
 use vcad_kernel_tessellate::TriangleMesh;
 
 use crate::types::{BoundingBox2D, HatchPattern, Point2D, SectionCurve, SectionPlane, SectionView};
 
 /// Default tolerance for geometric comparisons (in mm).
-const DEFAULT_TOLERANCE: f64 = 1e-6;
+pub(crate) const DEFAULT_TOLERANCE: f64 = 1e-6;
 
 // ============================================================================
 // Plane-Triangle Intersection
@@ -150,13 +150,8 @@ pub fn intersect_mesh_with_plane(
 // ============================================================================
 
 /// Key for endpoint lookup with tolerance-based hashing.
-fn point_key(p: &Point3, tolerance: f64) -> (i64, i64, i64) {
-    let scale = 1.0 / tolerance;
-    (
-        (p.x * scale).round() as i64,
-        (p.y * scale).round() as i64,
-        (p.z * scale).round() as i64,
-    )
+fn point_key(p: &Point3, tolerance: f64) -> [i64; 3] {
+    p.quantize(1.0 / tolerance)
 }
 
 /// Chain individual segments into continuous polylines.
@@ -169,7 +164,7 @@ pub fn chain_segments(segments: Vec<(Point3, Point3)>, tolerance: f64) -> Vec<(V
     }
 
     // Build adjacency map: point_key -> list of (segment_index, is_end_point)
-    let mut adjacency: HashMap<(i64, i64, i64), Vec<(usize, bool)>> = HashMap::new();
+    let mut adjacency: HashMap<[i64; 3], Vec<(usize, bool)>> = HashMap::new();
     for (i, (p0, p1)) in segments.iter().enumerate() {
         let k0 = point_key(p0, tolerance);
         let k1 = point_key(p1, tolerance);
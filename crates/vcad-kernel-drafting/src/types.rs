@@ -1,7 +1,7 @@
 //! Core types for 2D drafting and technical drawing generation.
 
 use serde::{Deserialize, Serialize};
-use vcad_kernel_math::{Point3, Vec3};
+use vcad_kernel_math::{Point3, Quantize, Vec3};
 
 /// A 2D point for serializable drafting output.
 ///
@@ -36,6 +36,13 @@ impl Default for Point2D {
     }
 }
 
+impl Quantize for Point2D {
+    /// Quantizes as the point `(x, y, 0)`.
+    fn quantize(&self, scale: f64) -> [i64; 3] {
+        Vec3::new(self.x, self.y, 0.0).quantize(scale)
+    }
+}
+
 impl From<vcad_kernel_math::Point2> for Point2D {
     fn from(p: vcad_kernel_math::Point2) -> Self {
         Self { x: p.x, y: p.y }
@@ -87,6 +94,37 @@ impl ViewDirection {
         elevation: 0.46365,
     };
 
+    /// The view looking along `v` (from the viewer toward the model).
+    ///
+    /// Axis-aligned vectors give the matching standard view; any other
+    /// vector gives the [`ViewDirection::Isometric`] view with that view
+    /// vector. A zero vector gives the default (front) view.
+    pub fn from_vector(v: Vec3) -> Self {
+        let len = v.norm();
+        if len < 1e-12 {
+            return Self::default();
+        }
+        let v = v / len;
+        let standard = [
+            ViewDirection::Front,
+            ViewDirection::Back,
+            ViewDirection::Top,
+            ViewDirection::Bottom,
+            ViewDirection::Right,
+            ViewDirection::Left,
+        ];
+        if let Some(view) = standard
+            .into_iter()
+            .find(|view| view.view_vector().dot(&v) > 1.0 - 1e-12)
+        {
+            return view;
+        }
+        ViewDirection::Isometric {
+            azimuth: v.x.atan2(v.y),
+            elevation: (-v.z).clamp(-1.0, 1.0).asin(),
+        }
+    }
+
     /// Get the view direction as a unit vector pointing from the viewer toward the model.
     pub fn view_vector(&self) -> Vec3 {
         match self {
@@ -701,6 +739,23 @@ mod tests {
         assert!(v.norm() > 0.99 && v.norm() < 1.01);
     }
 
+    #[test]
+    fn test_view_direction_from_vector() {
+        assert_eq!(
+            ViewDirection::from_vector(Vec3::new(0.0, 0.0, -2.0)),
+            ViewDirection::Top
+        );
+        assert_eq!(
+            ViewDirection::from_vector(Vec3::new(-1.0, 0.0, 0.0)),
+            ViewDirection::Left
+        );
+
+        let v = Vec3::new(1.0, 2.0, -3.0);
+        let view = ViewDirection::from_vector(v);
+        assert!(matches!(view, ViewDirection::Isometric { .. }));
+        assert!((view.view_vector() - v.normalize()).norm() < 1e-12);
+    }
+
     #[test]
     fn test_bounding_box() {
         let mut bb = BoundingBox2D::empty();
@@ -6,12 +6,17 @@
 //! - **Chamfer**: replaces an edge with a planar bevel face
 //! - **Fillet**: replaces an edge with a cylindrical blend surface
 //!
+//! Either every edge is blended ([`chamfer_all_edges`], [`fillet_all_edges`])
+//! or only a selection ([`chamfer_edges`], [`fillet_edges`]), such as the
+//! boundary of a face ([`fillet_loop`]) or a tangent-continuous chain of
+//! edges ([`fillet_chain`]).
+//!
 //! Currently supports edges between planar faces (the most common case
 //! for prismatic CAD geometry).
 
-use std::collections::HashMap;
-use vcad_kernel_geom::{CylinderSurface, GeometryStore, Plane};
-use vcad_kernel_math::{Dir3, Point3, Vec3};
+use std::collections::{HashMap, HashSet};
+use vcad_kernel_geom::{ConeSurface, CylinderSurface, GeometryStore, Plane, SphereSurface};
+use vcad_kernel_math::{Dir3, Point3, Quantize, Vec3};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_topo::{EdgeId, FaceId, HalfEdgeId, Orientation, ShellType, Topology, VertexId};
 
@@ -34,7 +39,6 @@ struct FaceInfo {
 /// Information about an edge.
 #[derive(Debug, Clone)]
 struct EdgeInfo {
-    #[allow(dead_code)]
     edge_id: EdgeId,
     /// Start vertex (origin of the primary half-edge).
     v_start: VertexId,
@@ -138,11 +142,15 @@ type TrimKey = (VertexId, FaceId);
 ///
 /// For each vertex V on face F:
 /// - The entering edge E_enter and leaving edge E_leave define two trim lines
-///   (parallel to each edge, offset inward by `distance`)
+///   (parallel to each edge, offset inward by `offset(start, end)` of the
+///   edge)
 /// - The trim vertex is at the intersection of these two trim lines
 ///
 /// This gives one vertex per (original_vertex, face) pair.
-fn compute_trim_vertices(faces: &[FaceInfo], distance: f64) -> HashMap<TrimKey, Point3> {
+fn compute_trim_vertices(
+    faces: &[FaceInfo],
+    offset: impl Fn(VertexId, VertexId) -> f64,
+) -> HashMap<TrimKey, Point3> {
     let mut trims = HashMap::new();
 
     // Build a map: (vertex, face) → (entering_edge_dir, leaving_edge_dir)
@@ -188,25 +196,27 @@ fn compute_trim_vertices(faces: &[FaceInfo], distance: f64) -> HashMap<TrimKey,
 
             let perp_enter = perp_enter / pe_len;
             let perp_leave = perp_leave / pl_len;
+            let a = offset(face.vertex_ids[prev_idx], v_id);
+            let b = offset(v_id, face.vertex_ids[next_idx]);
 
             // Trim line 1: point on entering edge's trim line, direction d_enter
-            // P1 = V + distance * perp_enter
+            // P1 = V + a * perp_enter
             // Trim line 2: point on leaving edge's trim line, direction d_leave
-            // P2 = V + distance * perp_leave
+            // P2 = V + b * perp_leave
             //
             // Solve: P1 + t1 * d_enter = P2 + t2 * d_leave
-            // => distance * (perp_enter - perp_leave) = t2 * d_leave - t1 * d_enter
+            // => a * perp_enter - b * perp_leave = t2 * d_leave - t1 * d_enter
             //
             // Cross with d_leave: delta × d_leave = -t1 * (d_enter × d_leave)
             // t1 = -(delta × d_leave) · normal / (d_enter × d_leave) · normal
 
-            let delta = distance * (perp_enter - perp_leave);
+            let delta = a * perp_enter - b * perp_leave;
             let cross_dirs = d_enter.cross(&d_leave);
             let denom = cross_dirs.dot(&normal);
 
             if denom.abs() < 1e-15 {
                 // Parallel edges — use midpoint of perpendicular offsets
-                let p = v_pos + distance * 0.5 * (perp_enter + perp_leave);
+                let p = v_pos + 0.5 * (a * perp_enter + b * perp_leave);
                 trims.insert((v_id, face.face_id), p);
                 continue;
             }
@@ -214,7 +224,7 @@ fn compute_trim_vertices(faces: &[FaceInfo], distance: f64) -> HashMap<TrimKey,
             let cross_delta = delta.cross(&d_leave);
             let t1 = -cross_delta.dot(&normal) / denom;
 
-            let p1 = v_pos + distance * perp_enter;
+            let p1 = v_pos + a * perp_enter;
             let trim_point = Point3::from(p1.coords + t1 * d_enter);
             trims.insert((v_id, face.face_id), trim_point);
         }
@@ -249,7 +259,7 @@ pub fn chamfer_all_edges(brep: &BRepSolid, distance: f64) -> BRepSolid {
         return brep.clone();
     }
 
-    let trims = compute_trim_vertices(&faces, distance);
+    let trims = compute_trim_vertices(&faces, |_, _| distance);
 
     // Build vertex→edges map (which edges meet at each vertex)
     let mut vertex_edges: HashMap<VertexId, Vec<&EdgeInfo>> = HashMap::new();
@@ -313,21 +323,7 @@ pub fn chamfer_all_edges(brep: &BRepSolid, distance: f64) -> BRepSolid {
         let pb_e = trims.get(&(edge_info.v_end, edge_info.face_b));
 
         if let (Some(&pa_s), Some(&pa_e), Some(&pb_s), Some(&pb_e)) = (pa_s, pa_e, pb_s, pb_e) {
-            // Orient the quad for outward normal
-            let chamfer_center =
-                Point3::from((pa_s.coords + pa_e.coords + pb_e.coords + pb_s.coords) * 0.25);
-            let solid_center = compute_centroid(&faces);
-            let outward_dir = chamfer_center - solid_center;
-
-            let e1 = pa_e - pa_s;
-            let e2 = pb_s - pa_s;
-            let n = e1.cross(&e2);
-
-            let positions = if n.dot(&outward_dir) > 0.0 {
-                vec![pa_s, pa_e, pb_e, pb_s]
-            } else {
-                vec![pa_s, pb_s, pb_e, pa_e]
-            };
+            let positions = orient_quad([pa_s, pa_e, pb_e, pb_s], compute_centroid(&faces));
 
             let verts: Vec<VertexId> = positions
                 .iter()
@@ -364,11 +360,7 @@ pub fn chamfer_all_edges(brep: &BRepSolid, distance: f64) -> BRepSolid {
     let shell = new_topo.add_shell(all_faces, ShellType::Outer);
     let solid_id = new_topo.add_solid(shell);
 
-    BRepSolid {
-        topology: new_topo,
-        geometry: new_geom,
-        solid_id,
-    }
+    BRepSolid::new(new_topo, new_geom, solid_id)
 }
 
 /// Compute the centroid of all faces' vertex positions.
@@ -388,6 +380,63 @@ fn compute_centroid(faces: &[FaceInfo]) -> Point3 {
     }
 }
 
+/// Order the corners of a blend quad so its normal points away from the
+/// solid's center.
+fn orient_quad(quad: [Point3; 4], solid_center: Point3) -> Vec<Point3> {
+    let [pa_s, pa_e, pb_e, pb_s] = quad;
+    let quad_center = Point3::from((pa_s.coords + pa_e.coords + pb_e.coords + pb_s.coords) * 0.25);
+    let outward = quad_center - solid_center;
+
+    let e1 = pa_e - pa_s;
+    let e2 = pb_s - pa_s;
+    let n = e1.cross(&e2);
+
+    if n.dot(&outward) > 0.0 {
+        vec![pa_s, pa_e, pb_e, pb_s]
+    } else {
+        vec![pa_s, pb_s, pb_e, pa_e]
+    }
+}
+
+/// The cylinder of a fillet along an edge between faces `fa` and `fb`,
+/// tangent to `fa` at `pa_s`. `None` for a degenerate edge.
+fn fillet_cylinder(
+    brep: &BRepSolid,
+    edge_info: &EdgeInfo,
+    fa: &FaceInfo,
+    fb: &FaceInfo,
+    pa_s: Point3,
+    radius: f64,
+) -> Option<CylinderSurface> {
+    // Cylinder axis along the edge direction
+    let v_start_pos = brep.topology.vertices[edge_info.v_start].point;
+    let v_end_pos = brep.topology.vertices[edge_info.v_end].point;
+    let edge_dir = v_end_pos - v_start_pos;
+    let edge_len = edge_dir.norm();
+    if edge_len < 1e-12 {
+        return None;
+    }
+    let edge_unit = edge_dir / edge_len;
+
+    // Cylinder center: offset from the edge by r along both face normals
+    let center_offset = radius * (fa.normal + fb.normal);
+    let center_start = v_start_pos + center_offset;
+
+    // Ref dir: from cylinder center toward the tangent on face_a
+    let to_tangent_a = pa_s - center_start;
+    let ref_dir = to_tangent_a - to_tangent_a.dot(&edge_unit) * edge_unit;
+    if ref_dir.norm() < 1e-12 {
+        return None;
+    }
+
+    Some(CylinderSurface {
+        center: center_start,
+        axis: Dir3::new_normalize(edge_unit),
+        ref_dir: Dir3::new_normalize(ref_dir),
+        radius,
+    })
+}
+
 /// Pair twin half-edges by matching (origin, destination) vertex pairs.
 fn pair_twin_half_edges(topo: &mut Topology) {
     let mut he_map: HashMap<([i64; 3], [i64; 3]), HalfEdgeId> = HashMap::new();
@@ -416,15 +465,13 @@ fn pair_twin_half_edges(topo: &mut Topology) {
 }
 
 fn quantize(p: Point3) -> [i64; 3] {
-    [
-        (p.x * 1e9).round() as i64,
-        (p.y * 1e9).round() as i64,
-        (p.z * 1e9).round() as i64,
-    ]
+    p.quantize(1e9)
 }
 
-/// Build vertex faces for all vertices where ≥3 edges meet.
-/// Each vertex face is a polygon connecting the trim vertices from all adjacent faces.
+/// Build vertex faces for all vertices where the blended edges leave ≥3
+/// distinct trim vertices.
+/// Each vertex face is a polygon connecting the trim vertices of the faces
+/// along those edges.
 #[allow(clippy::too_many_arguments)]
 fn build_vertex_faces(
     faces: &[FaceInfo],
@@ -443,18 +490,21 @@ fn build_vertex_faces(
         };
 
     for (&v_id, v_edges) in vertex_edges {
-        if v_edges.len() < 3 {
+        if v_edges.len() < 2 {
             continue;
         }
 
         let v_pos = brep.topology.vertices[v_id].point;
 
-        // Collect trim vertices from all faces at this vertex
+        // Collect the distinct trim vertices of the faces along the edges
+        let mut seen = HashSet::new();
         let mut vertex_face_points: Vec<Point3> = Vec::new();
-        for face in faces {
-            if face.vertex_ids.contains(&v_id) {
-                if let Some(&p) = trims.get(&(v_id, face.face_id)) {
-                    vertex_face_points.push(p);
+        for edge in v_edges {
+            for face_id in [edge.face_a, edge.face_b] {
+                if let Some(&p) = trims.get(&(v_id, face_id)) {
+                    if seen.insert(quantize(p)) {
+                        vertex_face_points.push(p);
+                    }
                 }
             }
         }
@@ -557,7 +607,7 @@ pub fn fillet_all_edges(brep: &BRepSolid, radius: f64) -> BRepSolid {
     }
 
     // Tangent points are at the same positions as chamfer trim vertices
-    let trims = compute_trim_vertices(&faces, radius);
+    let trims = compute_trim_vertices(&faces, |_, _| radius);
     let face_map: HashMap<FaceId, &FaceInfo> = faces.iter().map(|f| (f.face_id, f)).collect();
 
     let mut vertex_edges: HashMap<VertexId, Vec<&EdgeInfo>> = HashMap::new();
@@ -621,51 +671,12 @@ pub fn fillet_all_edges(brep: &BRepSolid, radius: f64) -> BRepSolid {
         let pb_e = trims.get(&(edge_info.v_end, edge_info.face_b));
 
         if let (Some(&pa_s), Some(&pa_e), Some(&pb_s), Some(&pb_e)) = (pa_s, pa_e, pb_s, pb_e) {
-            // Cylinder axis along the edge direction
-            let v_start_pos = brep.topology.vertices[edge_info.v_start].point;
-            let v_end_pos = brep.topology.vertices[edge_info.v_end].point;
-            let edge_dir = v_end_pos - v_start_pos;
-            let edge_len = edge_dir.norm();
-            if edge_len < 1e-12 {
+            let Some(cyl_surface) = fillet_cylinder(brep, edge_info, fa, fb, pa_s, radius) else {
                 continue;
-            }
-            let edge_unit = edge_dir / edge_len;
-
-            // Cylinder center: offset from the edge by r along both face normals
-            let center_offset = radius * (fa.normal + fb.normal);
-            let center_start = v_start_pos + center_offset;
-
-            // Ref dir: from cylinder center toward the tangent on face_a
-            let to_tangent_a = pa_s - center_start;
-            let ref_dir = to_tangent_a - to_tangent_a.dot(&edge_unit) * edge_unit;
-            let ref_len = ref_dir.norm();
-            if ref_len < 1e-12 {
-                continue;
-            }
-
-            let cyl_surface = CylinderSurface {
-                center: center_start,
-                axis: Dir3::new_normalize(edge_unit),
-                ref_dir: Dir3::new_normalize(ref_dir),
-                radius,
             };
             let surf_idx = new_geom.add_surface(Box::new(cyl_surface));
 
-            // Orient the quad for outward normal
-            let solid_center = compute_centroid(&faces);
-            let chamfer_center =
-                Point3::from((pa_s.coords + pa_e.coords + pb_e.coords + pb_s.coords) * 0.25);
-            let outward = chamfer_center - solid_center;
-
-            let e1 = pa_e - pa_s;
-            let e2 = pb_s - pa_s;
-            let n = e1.cross(&e2);
-
-            let positions = if n.dot(&outward) > 0.0 {
-                vec![pa_s, pa_e, pb_e, pb_s]
-            } else {
-                vec![pa_s, pb_s, pb_e, pa_e]
-            };
+            let positions = orient_quad([pa_s, pa_e, pb_e, pb_s], compute_centroid(&faces));
 
             let verts: Vec<VertexId> = positions
                 .iter()
@@ -698,11 +709,280 @@ pub fn fillet_all_edges(brep: &BRepSolid, radius: f64) -> BRepSolid {
     let shell = new_topo.add_shell(all_faces, ShellType::Outer);
     let solid_id = new_topo.add_solid(shell);
 
-    BRepSolid {
-        topology: new_topo,
-        geometry: new_geom,
-        solid_id,
+    BRepSolid::new(new_topo, new_geom, solid_id)
+}
+
+// =============================================================================
+// Selected edges
+// =============================================================================
+
+/// How the selected edges are blended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Blend {
+    Chamfer,
+    Fillet,
+}
+
+/// Chamfer only the given edges of a B-rep solid by `distance`.
+///
+/// Like [`chamfer_all_edges`], but faces are only trimmed back from the
+/// selected edges. Where a chamfer ends at an unselected edge, the face
+/// across that edge is cut off to meet it. Edges that aren't manifold
+/// edges of `brep` are ignored; with none left the solid is returned
+/// unchanged.
+pub fn chamfer_edges(brep: &BRepSolid, edges: &[EdgeId], distance: f64) -> BRepSolid {
+    blend_edges(brep, edges, distance, Blend::Chamfer)
+}
+
+/// Fillet only the given edges of a B-rep solid with a constant radius.
+///
+/// Like [`fillet_all_edges`], with the same requirements, but faces are only
+/// trimmed back from the selected edges. Neighboring selected edges meeting
+/// at an unselected one share a blend corner where their cylinders meet;
+/// where three or more meet, a vertex face closes the gap.
+pub fn fillet_edges(brep: &BRepSolid, edges: &[EdgeId], radius: f64) -> BRepSolid {
+    blend_edges(brep, edges, radius, Blend::Fillet)
+}
+
+/// Fillet every edge around the outer boundary of `face`.
+///
+/// The edges come from [`Topology::edge_loop_of_face`]; on a box this
+/// rounds the four edges of one face, with the fillets meeting at the
+/// corners.
+pub fn fillet_loop(brep: &BRepSolid, face: FaceId, radius: f64) -> BRepSolid {
+    fillet_edges(brep, &brep.topology.edge_loop_of_face(face), radius)
+}
+
+/// Fillet the tangent-continuous chain of edges through `start`.
+///
+/// The chain comes from [`Topology::tangent_continuous_chain`], with each
+/// edge's tangent at a vertex taken where the surfaces of its two faces
+/// meet, so edges that continue one another smoothly are rounded together.
+pub fn fillet_chain(brep: &BRepSolid, start: EdgeId, radius: f64) -> BRepSolid {
+    let chain = brep
+        .topology
+        .tangent_continuous_chain(start, |edge, vertex| edge_tangent(brep, edge, vertex));
+    fillet_edges(brep, &chain, radius)
+}
+
+/// Tangent of `edge` at its end `vertex`, in either sense.
+///
+/// The edge lies on both of its faces' surfaces, so its tangent is the cross
+/// product of their normals there. `None` for a boundary edge, a surface
+/// without a known normal, or faces meeting tangentially.
+fn edge_tangent(brep: &BRepSolid, edge: EdgeId, vertex: VertexId) -> Option<Vec3> {
+    let (Some(a), Some(b)) = brep.topology.edge_faces(edge) else {
+        return None;
+    };
+    let point = brep.topology.vertices[vertex].point;
+    let normal = |face: FaceId| {
+        surface_normal_at(
+            &brep.geometry,
+            brep.topology.faces[face].surface_index,
+            point,
+        )
+    };
+    let tangent = normal(a)?.cross(&normal(b)?);
+    (tangent.norm() > 1e-9).then_some(tangent)
+}
+
+/// Unit normal, in the surface's own sense, of an analytic surface at a
+/// point lying on it.
+fn surface_normal_at(geom: &GeometryStore, surface: usize, point: Point3) -> Option<Vec3> {
+    let surface = geom.surfaces[surface].as_any();
+    if let Some(plane) = surface.downcast_ref::<Plane>() {
+        return Some(*plane.normal_dir.as_ref());
+    }
+    if let Some(sphere) = surface.downcast_ref::<SphereSurface>() {
+        return (point - sphere.center).try_normalize(1e-12);
+    }
+    // Cylinders and cones: the direction away from the axis, tilted back
+    // along it by a cone's half-angle
+    let (apex, axis, half_angle) = if let Some(cyl) = surface.downcast_ref::<CylinderSurface>() {
+        (cyl.center, *cyl.axis.as_ref(), 0.0)
+    } else if let Some(cone) = surface.downcast_ref::<ConeSurface>() {
+        (cone.apex, *cone.axis.as_ref(), cone.half_angle)
+    } else {
+        return None;
+    };
+    let v = point - apex;
+    let radial = (v - v.dot(&axis) * axis).try_normalize(1e-12)?;
+    let (sin, cos) = f64::sin_cos(half_angle);
+    Some(radial * cos - axis * sin)
+}
+
+fn blend_edges(brep: &BRepSolid, selection: &[EdgeId], distance: f64, blend: Blend) -> BRepSolid {
+    let faces = extract_faces(brep);
+    let edges: Vec<EdgeInfo> = extract_edges(brep)
+        .into_iter()
+        .filter(|e| selection.contains(&e.edge_id))
+        .collect();
+
+    if edges.is_empty() {
+        return brep.clone();
+    }
+
+    // Selected edges in both directions, as face loops see them
+    let selected: HashSet<(VertexId, VertexId)> = edges
+        .iter()
+        .flat_map(|e| [(e.v_start, e.v_end), (e.v_end, e.v_start)])
+        .collect();
+    let trims = compute_trim_vertices(&faces, |a, b| {
+        if selected.contains(&(a, b)) {
+            distance
+        } else {
+            0.0
+        }
+    });
+    let face_map: HashMap<FaceId, &FaceInfo> = faces.iter().map(|f| (f.face_id, f)).collect();
+
+    // The face on the left of each directed loop edge
+    let mut edge_face: HashMap<(VertexId, VertexId), FaceId> = HashMap::new();
+    for face in &faces {
+        let n = face.vertex_ids.len();
+        for i in 0..n {
+            let key = (face.vertex_ids[i], face.vertex_ids[(i + 1) % n]);
+            edge_face.insert(key, face.face_id);
+        }
+    }
+
+    let mut vertex_edges: HashMap<VertexId, Vec<&EdgeInfo>> = HashMap::new();
+    for edge in &edges {
+        vertex_edges.entry(edge.v_start).or_default().push(edge);
+        vertex_edges.entry(edge.v_end).or_default().push(edge);
+    }
+
+    let mut new_topo = Topology::new();
+    let mut new_geom = GeometryStore::new();
+    let mut vertex_cache: HashMap<[i64; 3], VertexId> = HashMap::new();
+
+    let get_or_create_vertex =
+        |cache: &mut HashMap<[i64; 3], VertexId>, topo: &mut Topology, pos: Point3| -> VertexId {
+            let key = quantize(pos);
+            *cache.entry(key).or_insert_with(|| topo.add_vertex(pos))
+        };
+
+    let mut all_faces = Vec::new();
+
+    // 1. Build modified original faces. Along an unselected edge, a face
+    //    runs on to wherever the face across it was trimmed back to, so a
+    //    blend ending at that edge cuts the corner off this face.
+    for face in &faces {
+        let n = face.vertex_ids.len();
+        let mut new_positions: Vec<Point3> = Vec::new();
+        for i in 0..n {
+            let v_id = face.vertex_ids[i];
+            let Some(&own) = trims.get(&(v_id, face.face_id)) else {
+                continue;
+            };
+            let v_pos = face.positions[i];
+            let along = |a: VertexId, b: VertexId| {
+                if selected.contains(&(a, b)) {
+                    return own;
+                }
+                match edge_face.get(&(b, a)).and_then(|&f| trims.get(&(v_id, f))) {
+                    Some(&p) if (p - v_pos).norm() > (own - v_pos).norm() => p,
+                    _ => own,
+                }
+            };
+            let prev = face.vertex_ids[(i + n - 1) % n];
+            let next = face.vertex_ids[(i + 1) % n];
+            for p in [along(prev, v_id), along(v_id, next)] {
+                if new_positions.last().map(|&q| quantize(q)) != Some(quantize(p)) {
+                    new_positions.push(p);
+                }
+            }
+        }
+        if new_positions.len() > 1
+            && quantize(new_positions[0]) == quantize(new_positions[new_positions.len() - 1])
+        {
+            new_positions.pop();
+        }
+
+        if new_positions.len() < 3 {
+            continue;
+        }
+
+        let verts: Vec<VertexId> = new_positions
+            .iter()
+            .map(|p| get_or_create_vertex(&mut vertex_cache, &mut new_topo, *p))
+            .collect();
+
+        let p0 = new_positions[0];
+        let x_dir = new_positions[1] - p0;
+        let y_dir = new_positions[new_positions.len() - 1] - p0;
+        let surf_idx = if x_dir.norm() > 1e-12 && y_dir.norm() > 1e-12 {
+            new_geom.add_surface(Box::new(Plane::new(p0, x_dir, y_dir)))
+        } else {
+            new_geom.add_surface(Box::new(Plane::from_normal(p0, face.normal)))
+        };
+
+        let hes: Vec<HalfEdgeId> = verts.iter().map(|&v| new_topo.add_half_edge(v)).collect();
+        let loop_id = new_topo.add_loop(&hes);
+        let face_id = new_topo.add_face(loop_id, surf_idx, Orientation::Forward);
+        all_faces.push(face_id);
     }
+
+    // 2. Build blend faces (one per selected edge)
+    let solid_center = compute_centroid(&faces);
+    for edge_info in &edges {
+        let pa_s = trims.get(&(edge_info.v_start, edge_info.face_a));
+        let pa_e = trims.get(&(edge_info.v_end, edge_info.face_a));
+        let pb_s = trims.get(&(edge_info.v_start, edge_info.face_b));
+        let pb_e = trims.get(&(edge_info.v_end, edge_info.face_b));
+
+        let (Some(&pa_s), Some(&pa_e), Some(&pb_s), Some(&pb_e)) = (pa_s, pa_e, pb_s, pb_e) else {
+            continue;
+        };
+        let positions = orient_quad([pa_s, pa_e, pb_e, pb_s], solid_center);
+        let surf_idx = match blend {
+            Blend::Chamfer => {
+                let x_dir = positions[1] - positions[0];
+                let y_dir = positions[3] - positions[0];
+                new_geom.add_surface(Box::new(Plane::new(positions[0], x_dir, y_dir)))
+            }
+            Blend::Fillet => {
+                let fa = face_map[&edge_info.face_a];
+                let fb = face_map[&edge_info.face_b];
+                let Some(cyl_surface) = fillet_cylinder(brep, edge_info, fa, fb, pa_s, distance)
+                else {
+                    continue;
+                };
+                new_geom.add_surface(Box::new(cyl_surface))
+            }
+        };
+
+        let verts: Vec<VertexId> = positions
+            .iter()
+            .map(|p| get_or_create_vertex(&mut vertex_cache, &mut new_topo, *p))
+            .collect();
+
+        let hes: Vec<HalfEdgeId> = verts.iter().map(|&v| new_topo.add_half_edge(v)).collect();
+        let loop_id = new_topo.add_loop(&hes);
+        let face_id = new_topo.add_face(loop_id, surf_idx, Orientation::Forward);
+        all_faces.push(face_id);
+    }
+
+    // 3. Build vertex faces
+    build_vertex_faces(
+        &faces,
+        &vertex_edges,
+        &trims,
+        brep,
+        &mut vertex_cache,
+        &mut new_topo,
+        &mut new_geom,
+        &mut all_faces,
+    );
+
+    // 4. Pair twin half-edges
+    pair_twin_half_edges(&mut new_topo);
+
+    // 5. Build shell and solid
+    let shell = new_topo.add_shell(all_faces, ShellType::Outer);
+    let solid_id = new_topo.add_solid(shell);
+
+    BRepSolid::new(new_topo, new_geom, solid_id)
 }
 
 // =============================================================================
@@ -832,6 +1112,155 @@ mod tests {
         );
     }
 
+    /// The face of `brep` whose vertices all have `z` equal to `z`.
+    fn face_at_z(brep: &BRepSolid, z: f64) -> FaceId {
+        extract_faces(brep)
+            .into_iter()
+            .find(|f| f.positions.iter().all(|p| (p.z - z).abs() < 1e-9))
+            .unwrap()
+            .face_id
+    }
+
+    fn assert_closed(brep: &BRepSolid) {
+        let unpaired = brep
+            .topology
+            .half_edges
+            .values()
+            .filter(|he| he.twin.is_none())
+            .count();
+        assert_eq!(unpaired, 0, "found {unpaired} unpaired half-edges");
+    }
+
+    #[test]
+    fn test_chamfer_single_edge() {
+        let cube = make_cube(10.0, 10.0, 10.0);
+        let top = face_at_z(&cube, 10.0);
+        let edge = cube.topology.edge_loop_of_face(top)[0];
+        let chamfered = chamfer_edges(&cube, &[edge], 1.0);
+
+        // The end faces get their corners cut off instead of vertex faces
+        assert_eq!(chamfered.topology.faces.len(), 7);
+        assert_closed(&chamfered);
+
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&chamfered, 32);
+        let vol = compute_mesh_volume(&mesh);
+        assert!((vol - 995.0).abs() < 1e-6, "expected volume 995, got {vol}");
+    }
+
+    #[test]
+    fn test_fillet_loop_cube_top() {
+        let cube = make_cube(10.0, 10.0, 10.0);
+        let top = face_at_z(&cube, 10.0);
+        assert_eq!(cube.topology.edge_loop_of_face(top).len(), 4);
+
+        let filleted = fillet_loop(&cube, top, 1.0);
+        // 6 trimmed faces + 4 fillets meeting at mitered corners
+        assert_eq!(filleted.topology.faces.len(), 10);
+        assert_closed(&filleted);
+        let n_cyl = filleted
+            .geometry
+            .surfaces
+            .iter()
+            .filter(|s| s.surface_type() == vcad_kernel_geom::SurfaceKind::Cylinder)
+            .count();
+        assert_eq!(n_cyl, 4);
+
+        // The bottom is untouched and the top is inset by the radius
+        let bottom = face_at_z(&filleted, 0.0);
+        let top = face_at_z(&filleted, 10.0);
+        let extent = |face: FaceId| {
+            let verts = filleted
+                .topology
+                .loop_vertices(filleted.topology.faces[face].outer_loop);
+            let xs = verts.iter().map(|&v| filleted.topology.vertices[v].point.x);
+            xs.clone().fold(f64::MIN, f64::max) - xs.fold(f64::MAX, f64::min)
+        };
+        assert!((extent(bottom) - 10.0).abs() < 1e-9);
+        assert!((extent(top) - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fillet_chain_follows_collinear_edges() {
+        use vcad_kernel_math::Point2;
+        use vcad_kernel_sketch::{extrude, SketchProfile, SketchSegment};
+
+        // A 10 x 10 x 10 block whose front side is split in two at x = 5
+        let corners = [
+            (0.0, 0.0),
+            (5.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+        ];
+        let segments = (0..corners.len())
+            .map(|i| {
+                let (x0, y0) = corners[i];
+                let (x1, y1) = corners[(i + 1) % corners.len()];
+                SketchSegment::Line {
+                    start: Point2::new(x0, y0),
+                    end: Point2::new(x1, y1),
+                }
+            })
+            .collect();
+        let profile = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments).unwrap();
+        let block = extrude(&profile, Vec3::new(0.0, 0.0, 10.0)).unwrap();
+
+        // The top edges of the two front faces continue one another; the
+        // edge between those coplanar faces has no tangent of its own
+        let topo = &block.topology;
+        let top = face_at_z(&block, 10.0);
+        let front: Vec<EdgeId> = topo
+            .edge_loop_of_face(top)
+            .into_iter()
+            .filter(|&e| {
+                let he = &topo.half_edges[topo.edges[e].half_edge];
+                let twin = &topo.half_edges[he.twin.unwrap()];
+                topo.vertices[he.origin].point.y.abs() < 1e-9
+                    && topo.vertices[twin.origin].point.y.abs() < 1e-9
+            })
+            .collect();
+        assert_eq!(front.len(), 2);
+        let mut chain = topo.tangent_continuous_chain(front[0], |e, v| edge_tangent(&block, e, v));
+        chain.sort();
+        let mut expected = front.clone();
+        expected.sort();
+        assert_eq!(chain, expected);
+
+        // Both halves of the front edge are rounded, so the top face is
+        // trimmed back along its whole length
+        let filleted = fillet_chain(&block, front[0], 1.0);
+        assert_closed(&filleted);
+        let n_cyl = filleted
+            .geometry
+            .surfaces
+            .iter()
+            .filter(|s| s.surface_type() == vcad_kernel_geom::SurfaceKind::Cylinder)
+            .count();
+        assert_eq!(n_cyl, 2);
+        let top = face_at_z(&filleted, 10.0);
+        let top_verts = filleted
+            .topology
+            .loop_vertices(filleted.topology.faces[top].outer_loop);
+        let min_y = top_verts
+            .iter()
+            .map(|&v| filleted.topology.vertices[v].point.y)
+            .fold(f64::MAX, f64::min);
+        assert!((min_y - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_blend_all_edges_matches_all() {
+        let cube = make_cube(10.0, 10.0, 10.0);
+        let all: Vec<EdgeId> = cube.topology.edges.keys().collect();
+        let filleted = fillet_edges(&cube, &all, 1.0);
+        assert_eq!(filleted.topology.faces.len(), 26);
+        assert_closed(&filleted);
+
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&chamfer_edges(&cube, &all, 1.0), 32);
+        let reference = vcad_kernel_tessellate::tessellate_brep(&chamfer_all_edges(&cube, 1.0), 32);
+        assert!((compute_mesh_volume(&mesh) - compute_mesh_volume(&reference)).abs() < 1e-6);
+    }
+
     fn compute_mesh_volume(mesh: &vcad_kernel_tessellate::TriangleMesh) -> f64 {
         let verts = &mesh.vertices;
         let indices = &mesh.indices;
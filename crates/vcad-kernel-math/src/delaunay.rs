@@ -0,0 +1,174 @@
+//! Delaunay tetrahedralization of 3D point sets.
+//!
+//! Uses the Bowyer–Watson incremental algorithm: each point is inserted by
+//! removing every tetrahedron whose circumsphere contains it and re-coning
+//! the resulting cavity to the new point. All in-sphere and orientation
+//! decisions go through the exact [`insphere`] and [`orient3d`] predicates,
+//! so the result does not depend on an epsilon.
+
+use std::collections::HashMap;
+
+use crate::predicates::{insphere, orient3d, Sign};
+use crate::Point3;
+
+/// Distance of the enclosing tetrahedron's vertices from the input's center,
+/// in multiples of the input's extent. Larger values bring the result closer
+/// to a full triangulation of the convex hull.
+const SUPER_SCALE: f64 = 1e6;
+
+/// Compute the Delaunay tetrahedralization of `points`.
+///
+/// Returns tetrahedra as indices into `points`, each positively oriented:
+/// `orient3d(a, b, c, d)` is [`Sign::Positive`], which is the orientation
+/// [`insphere`] expects. Duplicate points are skipped. Fewer than four
+/// points, or an entirely coplanar input, yield no tetrahedra.
+///
+/// Cospherical inputs (such as the corners of a box) have more than one
+/// Delaunay tetrahedralization; which one is returned depends on the input
+/// order. Each insertion scans all current tetrahedra, so this is quadratic
+/// in the number of points.
+pub fn tetrahedralize(points: &[Point3]) -> Vec<[usize; 4]> {
+    if points.len() < 4 {
+        return Vec::new();
+    }
+
+    let (mut min, mut max) = (points[0], points[0]);
+    for p in points {
+        min = min.inf(p);
+        max = max.sup(p);
+    }
+    let center = nalgebra::center(&min, &max);
+    let extent = (max - min).amax().max(1.0) * SUPER_SCALE;
+
+    // A regular tetrahedron around the input. Its vertices are appended after
+    // the real points and removed with every tetrahedron touching them.
+    let n = points.len();
+    let mut vertices = points.to_vec();
+    vertices.extend(
+        [
+            [1.0, 1.0, 1.0],
+            [1.0, -1.0, -1.0],
+            [-1.0, 1.0, -1.0],
+            [-1.0, -1.0, 1.0],
+        ]
+        .map(|[x, y, z]| center + crate::Vec3::new(x, y, z) * extent),
+    );
+    let mut tets = vec![oriented(&vertices, [n, n + 1, n + 2, n + 3])];
+
+    for i in 0..n {
+        let p = &vertices[i];
+        let (cavity, kept): (Vec<_>, Vec<_>) = tets.into_iter().partition(|t| {
+            let [a, b, c, d] = t.map(|v| &vertices[v]);
+            insphere(a, b, c, d, p) == Sign::Positive
+        });
+        tets = kept;
+
+        // Cavity faces that aren't shared by two cavity tetrahedra form its
+        // boundary. Replacing the opposite vertex with `i` keeps orientation.
+        let mut faces: HashMap<[usize; 3], Option<[usize; 4]>> = HashMap::new();
+        for tet in &cavity {
+            for k in 0..4 {
+                let mut key = [0; 3];
+                let mut m = 0;
+                for (j, &v) in tet.iter().enumerate() {
+                    if j != k {
+                        key[m] = v;
+                        m += 1;
+                    }
+                }
+                key.sort_unstable();
+                let mut cone = *tet;
+                cone[k] = i;
+                faces
+                    .entry(key)
+                    .and_modify(|shared| *shared = None)
+                    .or_insert(Some(cone));
+            }
+        }
+        tets.extend(faces.into_values().flatten().filter(|&[a, b, c, d]| {
+            // Points coplanar with a boundary face would give a flat tetrahedron
+            orient3d(&vertices[a], &vertices[b], &vertices[c], &vertices[d]) == Sign::Positive
+        }));
+    }
+
+    tets.retain(|t| t.iter().all(|&v| v < n));
+    tets.sort_unstable();
+    tets
+}
+
+/// Reorder a tetrahedron's vertices so that it is positively oriented.
+fn oriented(vertices: &[Point3], [a, b, c, d]: [usize; 4]) -> [usize; 4] {
+    if orient3d(&vertices[a], &vertices[b], &vertices[c], &vertices[d]) == Sign::Negative {
+        [b, a, c, d]
+    } else {
+        [a, b, c, d]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volume(points: &[Point3], [a, b, c, d]: [usize; 4]) -> f64 {
+        let (a, b, c, d) = (points[a], points[b], points[c], points[d]);
+        (a - d).dot(&(b - d).cross(&(c - d))).abs() / 6.0
+    }
+
+    fn assert_valid(points: &[Point3], tets: &[[usize; 4]], hull_volume: f64) {
+        for &[a, b, c, d] in tets {
+            let [pa, pb, pc, pd] = [a, b, c, d].map(|v| &points[v]);
+            assert_eq!(orient3d(pa, pb, pc, pd), Sign::Positive);
+            for p in points {
+                assert_ne!(insphere(pa, pb, pc, pd, p), Sign::Positive);
+            }
+        }
+        let total: f64 = tets.iter().map(|&t| volume(points, t)).sum();
+        assert!(
+            (total - hull_volume).abs() < 1e-9 * hull_volume,
+            "volume {total} != {hull_volume}"
+        );
+    }
+
+    #[test]
+    fn test_cube_corners() {
+        let points: Vec<Point3> = (0..8)
+            .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+            .collect();
+        let tets = tetrahedralize(&points);
+        // A cube splits into 5 or 6 tetrahedra; the corners are cospherical,
+        // so either is Delaunay.
+        assert!(tets.len() == 5 || tets.len() == 6, "{} tets", tets.len());
+        assert_valid(&points, &tets, 1.0);
+    }
+
+    #[test]
+    fn test_interior_point() {
+        let mut points: Vec<Point3> = (0..8)
+            .map(|i| {
+                Point3::new(
+                    (i & 1) as f64 * 2.0,
+                    ((i >> 1) & 1) as f64 * 3.0,
+                    (i >> 2) as f64 * 4.0,
+                )
+            })
+            .collect();
+        points.push(Point3::new(0.7, 1.1, 2.9));
+        points.push(Point3::new(1.3, 2.2, 0.6));
+        points.push(Point3::new(0.7, 1.1, 2.9));
+        let tets = tetrahedralize(&points);
+        assert_valid(&points, &tets, 24.0);
+        for v in [8, 9] {
+            assert!(tets.iter().any(|t| t.contains(&v)));
+        }
+        assert!(!tets.iter().any(|t| t.contains(&10)));
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert!(tetrahedralize(&[Point3::origin(); 3]).is_empty());
+        let flat: Vec<Point3> = (0..5)
+            .map(|i| Point3::new(i as f64, (i * i) as f64, 0.0))
+            .collect();
+        assert!(tetrahedralize(&flat).is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+//! Convex hulls of 2D point sets.
+//!
+//! Every turn decision goes through the exact [`orient2d`] predicate, so
+//! nearly collinear points are classified correctly and collinear points
+//! never end up as hull vertices.
+
+use crate::predicates::{orient2d, Sign};
+use crate::Point2;
+
+/// Compute the convex hull of `points` with Andrew's monotone chain.
+///
+/// Returns the hull vertices counter-clockwise, starting from the
+/// lowest-leftmost point. Points lying on a hull edge are left out, as are
+/// duplicates. Degenerate inputs give fewer than three vertices: an empty
+/// input gives none, coincident points give one, and collinear points give
+/// the two extremes.
+///
+/// # Example
+///
+/// ```
+/// use vcad_kernel_math::{hull::convex_hull_2d, Point2};
+///
+/// let points = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(1.0, 1.0),
+///     Point2::new(2.0, 0.0),
+///     Point2::new(1.0, 2.0),
+/// ];
+/// let hull = convex_hull_2d(&points);
+/// assert_eq!(hull.len(), 3); // (1, 1) is inside
+/// ```
+pub fn convex_hull_2d(points: &[Point2]) -> Vec<Point2> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // Lower chain left to right, then upper chain right to left
+    let mut hull: Vec<Point2> = Vec::with_capacity(sorted.len() + 1);
+    for &p in &sorted {
+        push_left_turn(&mut hull, 2, p);
+    }
+    let lower_len = hull.len();
+    for &p in sorted.iter().rev().skip(1) {
+        push_left_turn(&mut hull, lower_len + 1, p);
+    }
+
+    // The upper chain ends back at the first point
+    hull.pop();
+    hull
+}
+
+/// Push `p` onto a hull chain, first popping points that would no longer
+/// make a strict left turn for as long as the chain holds `min_len` points.
+fn push_left_turn(chain: &mut Vec<Point2>, min_len: usize, p: Point2) {
+    while chain.len() >= min_len
+        && orient2d(&chain[chain.len() - 2], &chain[chain.len() - 1], &p) != Sign::Positive
+    {
+        chain.pop();
+    }
+    chain.push(p);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_with_interior_point() {
+        let points = [
+            Point2::new(1.0, 1.0),
+            Point2::new(0.0, 0.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(0.0, 2.0),
+            Point2::new(1.0, 0.5),
+            Point2::new(2.0, 0.0),
+            Point2::new(1.0, 2.0), // on the top edge
+        ];
+        let hull = convex_hull_2d(&points);
+        assert_eq!(
+            hull,
+            vec![
+                Point2::new(0.0, 0.0),
+                Point2::new(2.0, 0.0),
+                Point2::new(2.0, 2.0),
+                Point2::new(0.0, 2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collinear_points() {
+        let points = [
+            Point2::new(1.0, 1.0),
+            Point2::new(3.0, 3.0),
+            Point2::new(0.0, 0.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(3.0, 3.0),
+        ];
+        assert_eq!(
+            convex_hull_2d(&points),
+            vec![Point2::new(0.0, 0.0), Point2::new(3.0, 3.0)]
+        );
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert!(convex_hull_2d(&[]).is_empty());
+        let p = Point2::new(1.0, -1.0);
+        assert_eq!(convex_hull_2d(&[p, p, p]), vec![p]);
+
+        // Nearly collinear: the middle point is a hair above the line
+        let points = [
+            Point2::new(0.0, 0.0),
+            Point2::new(0.5, 0.5 + 1e-15),
+            Point2::new(1.0, 1.0),
+        ];
+        assert_eq!(convex_hull_2d(&points).len(), 3);
+    }
+}
@@ -0,0 +1,317 @@
+//! Segment intersections decided by exact predicates.
+//!
+//! Whether two primitives meet — and whether they touch at an endpoint or
+//! overlap — is decided with the exact [`orient2d`] and [`orient3d`]
+//! predicates, so the configuration is never misjudged near degeneracies.
+//! Only the location of a proper crossing is computed in floating point;
+//! touching endpoints are returned exactly.
+
+use crate::predicates::{orient2d, orient3d, point_on_segment_2d, Sign};
+use crate::{Point2, Point3, Vec2};
+
+/// The intersection of two 2D segments, from [`segment_intersect_2d`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SegIntersection {
+    /// The segments don't meet.
+    None,
+    /// The segments meet in a single point.
+    Point {
+        /// The shared point.
+        point: Point2,
+        /// Parameter of the point along the first segment, in `[0, 1]`.
+        t: f64,
+        /// Parameter of the point along the second segment, in `[0, 1]`.
+        u: f64,
+    },
+    /// The segments are collinear and share a stretch of positive length,
+    /// given in the direction of the first segment.
+    Overlap(Point2, Point2),
+}
+
+/// Intersect the closed segments `a0a1` and `b0b1`.
+///
+/// A segment endpoint lying on the other segment (a T-junction, or two
+/// segments sharing an endpoint) is returned as that endpoint exactly.
+/// Collinear segments give an [`SegIntersection::Overlap`] if they share
+/// more than a point, and a [`SegIntersection::Point`] if they only touch
+/// end to end. A zero-length segment is treated as a point.
+///
+/// # Example
+///
+/// ```
+/// use vcad_kernel_math::Point2;
+/// use vcad_kernel_math::intersect::{segment_intersect_2d, SegIntersection};
+///
+/// let hit = segment_intersect_2d(
+///     &Point2::new(0.0, 0.0),
+///     &Point2::new(2.0, 2.0),
+///     &Point2::new(0.0, 2.0),
+///     &Point2::new(2.0, 0.0),
+/// );
+/// assert!(matches!(hit, SegIntersection::Point { t, .. } if t == 0.5));
+/// ```
+pub fn segment_intersect_2d(a0: &Point2, a1: &Point2, b0: &Point2, b1: &Point2) -> SegIntersection {
+    if a0 == a1 || b0 == b1 {
+        return degenerate_intersect_2d(a0, a1, b0, b1);
+    }
+
+    let o1 = orient2d(a0, a1, b0);
+    let o2 = orient2d(a0, a1, b1);
+    if o1.is_zero() && o2.is_zero() {
+        return collinear_intersect_2d(a0, a1, b0, b1);
+    }
+    let o3 = orient2d(b0, b1, a0);
+    let o4 = orient2d(b0, b1, a1);
+    if o1 == o2 || o3 == o4 {
+        return SegIntersection::None;
+    }
+
+    // The segments meet. An endpoint on the other segment is the exact answer.
+    let a = a1 - a0;
+    let b = b1 - b0;
+    let param = |p: &Point2, from: &Point2, dir: &Vec2| {
+        ((p - from).dot(dir) / dir.norm_squared()).clamp(0.0, 1.0)
+    };
+    let touch = [
+        (o1, *b0, None, Some(0.0)),
+        (o2, *b1, None, Some(1.0)),
+        (o3, *a0, Some(0.0), None),
+        (o4, *a1, Some(1.0), None),
+    ];
+    for (sign, point, t, u) in touch {
+        if sign.is_zero() {
+            return SegIntersection::Point {
+                point,
+                t: t.unwrap_or_else(|| param(&point, a0, &a)),
+                u: u.unwrap_or_else(|| param(&point, b0, &b)),
+            };
+        }
+    }
+
+    // Proper crossing: solve a0 + t a = b0 + u b
+    let denom = a.perp(&b);
+    let d = b0 - a0;
+    let t = (d.perp(&b) / denom).clamp(0.0, 1.0);
+    let u = (d.perp(&a) / denom).clamp(0.0, 1.0);
+    SegIntersection::Point {
+        point: a0 + t * a,
+        t,
+        u,
+    }
+}
+
+/// Intersect collinear, non-degenerate segments.
+fn collinear_intersect_2d(a0: &Point2, a1: &Point2, b0: &Point2, b1: &Point2) -> SegIntersection {
+    let a = a1 - a0;
+    let len2 = a.norm_squared();
+    let along_a = |p: &Point2| (p - a0).dot(&a) / len2;
+
+    // Ends of the shared stretch, with their parameters along `a`
+    let (mut lo, mut hi) = ((0.0, *a0), (1.0, *a1));
+    let (s0, s1) = (along_a(b0), along_a(b1));
+    let (b_lo, b_hi) = if s0 <= s1 {
+        ((s0, *b0), (s1, *b1))
+    } else {
+        ((s1, *b1), (s0, *b0))
+    };
+    if b_lo.0 > lo.0 {
+        lo = b_lo;
+    }
+    if b_hi.0 < hi.0 {
+        hi = b_hi;
+    }
+
+    if lo.0 > hi.0 {
+        SegIntersection::None
+    } else if lo.1 == hi.1 || lo.0 == hi.0 {
+        let b = b1 - b0;
+        SegIntersection::Point {
+            point: lo.1,
+            t: lo.0,
+            u: ((lo.1 - b0).dot(&b) / b.norm_squared()).clamp(0.0, 1.0),
+        }
+    } else {
+        SegIntersection::Overlap(lo.1, hi.1)
+    }
+}
+
+/// Intersect segments where at least one has zero length.
+fn degenerate_intersect_2d(a0: &Point2, a1: &Point2, b0: &Point2, b1: &Point2) -> SegIntersection {
+    let param = |p: &Point2, from: &Point2, to: &Point2| {
+        let d = to - from;
+        if d.norm_squared() == 0.0 {
+            0.0
+        } else {
+            ((p - from).dot(&d) / d.norm_squared()).clamp(0.0, 1.0)
+        }
+    };
+    let point = if a0 == a1 {
+        point_on_segment_2d(a0, b0, b1).then_some(*a0)
+    } else {
+        point_on_segment_2d(b0, a0, a1).then_some(*b0)
+    };
+    match point {
+        Some(point) => SegIntersection::Point {
+            point,
+            t: param(&point, a0, a1),
+            u: param(&point, b0, b1),
+        },
+        None => SegIntersection::None,
+    }
+}
+
+/// Intersect the closed segment `p0p1` with the plane through the three
+/// points of `plane`.
+///
+/// Whether the segment crosses is decided from the [`orient3d`] signs of
+/// its endpoints, so an endpoint exactly on the plane is returned as is.
+/// Returns `None` if the segment misses the plane or lies in it, and for a
+/// degenerate plane (collinear points).
+///
+/// # Example
+///
+/// ```
+/// use vcad_kernel_math::Point3;
+/// use vcad_kernel_math::intersect::segment_plane_intersect_3d;
+///
+/// let xy = [
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// ];
+/// let hit = segment_plane_intersect_3d(
+///     &Point3::new(1.0, 1.0, -1.0),
+///     &Point3::new(1.0, 1.0, 3.0),
+///     &xy,
+/// );
+/// assert_eq!(hit, Some(Point3::new(1.0, 1.0, 0.0)));
+/// ```
+pub fn segment_plane_intersect_3d(p0: &Point3, p1: &Point3, plane: &[Point3; 3]) -> Option<Point3> {
+    let [a, b, c] = plane;
+    let s0 = orient3d(a, b, c, p0);
+    let s1 = orient3d(a, b, c, p1);
+    match (s0, s1) {
+        (Sign::Zero, Sign::Zero) => None,
+        (Sign::Zero, _) => Some(*p0),
+        (_, Sign::Zero) => Some(*p1),
+        _ if s0 == s1 => None,
+        _ => {
+            let normal = (b - a).cross(&(c - a));
+            let d0 = normal.dot(&(p0 - a));
+            let d1 = normal.dot(&(p1 - a));
+            let t = (d0 / (d0 - d1)).clamp(0.0, 1.0);
+            Some(p0 + t * (p1 - p0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: f64, y: f64) -> Point2 {
+        Point2::new(x, y)
+    }
+
+    #[test]
+    fn test_crossing() {
+        let hit = segment_intersect_2d(&p(0.0, 0.0), &p(4.0, 0.0), &p(1.0, -1.0), &p(1.0, 3.0));
+        assert_eq!(
+            hit,
+            SegIntersection::Point {
+                point: p(1.0, 0.0),
+                t: 0.25,
+                u: 0.25
+            }
+        );
+
+        // Separated along the line of the other segment
+        let miss = segment_intersect_2d(&p(0.0, 0.0), &p(4.0, 0.0), &p(5.0, -1.0), &p(5.0, 3.0));
+        assert_eq!(miss, SegIntersection::None);
+    }
+
+    #[test]
+    fn test_parallel() {
+        let hit = segment_intersect_2d(&p(0.0, 0.0), &p(4.0, 0.0), &p(0.0, 1.0), &p(4.0, 1.0));
+        assert_eq!(hit, SegIntersection::None);
+        // Parallel but offset by far less than any tolerance
+        let hit =
+            segment_intersect_2d(&p(0.0, 0.0), &p(4.0, 0.0), &p(0.0, 1e-300), &p(4.0, 1e-300));
+        assert_eq!(hit, SegIntersection::None);
+    }
+
+    #[test]
+    fn test_collinear() {
+        let a = (p(0.0, 0.0), p(4.0, 4.0));
+        let hit = segment_intersect_2d(&a.0, &a.1, &p(5.0, 5.0), &p(2.0, 2.0));
+        assert_eq!(hit, SegIntersection::Overlap(p(2.0, 2.0), a.1));
+        let hit = segment_intersect_2d(&a.0, &a.1, &p(3.0, 3.0), &p(1.0, 1.0));
+        assert_eq!(hit, SegIntersection::Overlap(p(1.0, 1.0), p(3.0, 3.0)));
+
+        // End to end, and apart
+        let hit = segment_intersect_2d(&a.0, &a.1, &p(4.0, 4.0), &p(6.0, 6.0));
+        assert_eq!(
+            hit,
+            SegIntersection::Point {
+                point: a.1,
+                t: 1.0,
+                u: 0.0
+            }
+        );
+        let hit = segment_intersect_2d(&a.0, &a.1, &p(5.0, 5.0), &p(6.0, 6.0));
+        assert_eq!(hit, SegIntersection::None);
+    }
+
+    #[test]
+    fn test_t_junction() {
+        // b starts on the interior of a
+        let hit = segment_intersect_2d(&p(0.0, 0.0), &p(3.0, 0.0), &p(1.0, 0.0), &p(1.0, 2.0));
+        assert_eq!(
+            hit,
+            SegIntersection::Point {
+                point: p(1.0, 0.0),
+                t: 1.0 / 3.0,
+                u: 0.0
+            }
+        );
+        // a ends on the interior of b
+        let hit = segment_intersect_2d(&p(1.0, 2.0), &p(1.0, 0.0), &p(0.0, 0.0), &p(3.0, 0.0));
+        assert!(
+            matches!(hit, SegIntersection::Point { point, t: 1.0, .. } if point == p(1.0, 0.0))
+        );
+        // Shared endpoint at an angle
+        let hit = segment_intersect_2d(&p(0.0, 0.0), &p(1.0, 0.0), &p(1.0, 0.0), &p(2.0, 1.0));
+        assert!(matches!(hit, SegIntersection::Point { point, .. } if point == p(1.0, 0.0)));
+        // Zero-length segment on the other
+        let hit = segment_intersect_2d(&p(2.0, 0.0), &p(2.0, 0.0), &p(0.0, 0.0), &p(4.0, 0.0));
+        assert!(matches!(hit, SegIntersection::Point { u: 0.5, .. }));
+    }
+
+    #[test]
+    fn test_segment_plane() {
+        let plane = [
+            Point3::new(0.0, 0.0, 1.0),
+            Point3::new(1.0, 0.0, 1.0),
+            Point3::new(0.0, 1.0, 1.0),
+        ];
+        let hit = segment_plane_intersect_3d(
+            &Point3::new(0.0, 0.0, 0.0),
+            &Point3::new(2.0, 4.0, 4.0),
+            &plane,
+        );
+        assert_eq!(hit, Some(Point3::new(0.5, 1.0, 1.0)));
+
+        // Touching at an endpoint, missing, and lying in the plane
+        let on = Point3::new(5.0, 5.0, 1.0);
+        let hit = segment_plane_intersect_3d(&Point3::new(0.0, 0.0, 3.0), &on, &plane);
+        assert_eq!(hit, Some(on));
+        let miss = segment_plane_intersect_3d(
+            &Point3::new(0.0, 0.0, 2.0),
+            &Point3::new(1.0, 0.0, 3.0),
+            &plane,
+        );
+        assert_eq!(miss, None);
+        let inside = segment_plane_intersect_3d(&plane[0], &on, &plane);
+        assert_eq!(inside, None);
+    }
+}
@@ -8,9 +8,16 @@
 //!
 //! This crate also provides exact geometric predicates via the
 //! [`predicates`] module, which use adaptive-precision arithmetic
-//! for robust geometric computations.
-
+//! for robust geometric computations, and Delaunay tetrahedralization,
+//! 2D convex hulls, segment intersections and polygon triangulation built
+//! on them in the [`delaunay`], [`hull`], [`intersect`] and [`triangulate`]
+//! modules.
+
+pub mod delaunay;
+pub mod hull;
+pub mod intersect;
 pub mod predicates;
+pub mod triangulate;
 
 use nalgebra::{Matrix4, Unit, Vector2, Vector3, Vector4};
 
@@ -44,6 +51,13 @@ impl Transform {
         }
     }
 
+    /// Build a transform from a row-major 4x4 matrix.
+    pub fn from_rows(rows: &[[f64; 4]; 4]) -> Self {
+        Self {
+            matrix: Matrix4::from_fn(|i, j| rows[i][j]),
+        }
+    }
+
     /// Translation by `(dx, dy, dz)`.
     pub fn translation(dx: f64, dy: f64, dz: f64) -> Self {
         let mut m = Matrix4::identity();
@@ -195,11 +209,48 @@ impl Default for Tolerance {
     }
 }
 
+/// Snapping of 3D coordinates onto an integer grid.
+///
+/// Used to key hash maps by position so that points differing only by
+/// floating-point noise land in the same bucket.
+pub trait Quantize {
+    /// Round each coordinate onto a grid with `scale` steps per unit.
+    fn quantize(&self, scale: f64) -> [i64; 3];
+}
+
+impl Quantize for Point3 {
+    fn quantize(&self, scale: f64) -> [i64; 3] {
+        self.coords.quantize(scale)
+    }
+}
+
+impl Quantize for Vec3 {
+    fn quantize(&self, scale: f64) -> [i64; 3] {
+        [
+            (self.x * scale).round() as i64,
+            (self.y * scale).round() as i64,
+            (self.z * scale).round() as i64,
+        ]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::f64::consts::PI;
 
+    #[test]
+    fn test_quantize_merges_nearby_points() {
+        let a = Point3::new(1.0, -2.5, 3.25);
+        let b = Point3::new(1.0 + 1e-9, -2.5 - 1e-9, 3.25);
+        assert_eq!(a.quantize(1e6), b.quantize(1e6));
+        assert_eq!(a.quantize(1e6), a.coords.quantize(1e6));
+        assert_ne!(
+            a.quantize(1e6),
+            Point3::new(1.0 + 1e-5, -2.5, 3.25).quantize(1e6)
+        );
+    }
+
     #[test]
     fn test_identity_transform() {
         let t = Transform::identity();
@@ -0,0 +1,274 @@
+//! Triangulation of planar polygons with holes.
+//!
+//! Polygons are triangulated by ear clipping, with each ear validated by the
+//! exact [`orient2d`] predicate, and the result is refined towards a
+//! constrained Delaunay triangulation by flipping interior edges that fail
+//! the [`incircle`] test. Holes are joined to the outer boundary with bridge
+//! edges before clipping.
+
+use std::collections::HashMap;
+
+use crate::intersect::{segment_intersect_2d, SegIntersection};
+use crate::predicates::{incircle, orient2d, Sign};
+use crate::Point2;
+
+/// Triangulate polygon loops: the first is the outer boundary, the rest are
+/// holes.
+///
+/// Loops may have either winding. Indices refer to the concatenation of all
+/// loops, and triangles are counter-clockwise. Loops with fewer than three
+/// vertices are ignored.
+pub fn triangulate_loops(loops: &[Vec<Point2>]) -> Vec<[usize; 3]> {
+    let points: Vec<Point2> = loops.iter().flatten().copied().collect();
+
+    let mut rings = Vec::with_capacity(loops.len());
+    let mut base = 0;
+    for (k, l) in loops.iter().enumerate() {
+        let mut ring: Vec<usize> = (base..base + l.len()).collect();
+        base += l.len();
+        if ring.len() < 3 {
+            if k == 0 {
+                return Vec::new();
+            }
+            continue;
+        }
+        // Outer boundary counter-clockwise, holes clockwise.
+        if (k == 0) != (ring_area(&points, &ring) > 0.0) {
+            ring.reverse();
+        }
+        rings.push(ring);
+    }
+    if rings.is_empty() {
+        return Vec::new();
+    }
+
+    let mut polygon = rings.remove(0);
+    // Rightmost holes first, so each bridge only has to avoid holes that are
+    // already part of the polygon or lie to its left.
+    rings.sort_by(|a, b| max_x(&points, b).total_cmp(&max_x(&points, a)));
+    for k in 0..rings.len() {
+        let (hole, pending) = rings[k..].split_first().unwrap();
+        bridge_hole(&points, &mut polygon, hole, pending);
+    }
+
+    let mut triangles = ear_clip(&points, polygon);
+    delaunay_flip(&points, &mut triangles);
+    triangles
+}
+
+/// Twice the signed area of a ring (positive for counter-clockwise).
+fn ring_area(points: &[Point2], ring: &[usize]) -> f64 {
+    let n = ring.len();
+    (0..n)
+        .map(|i| {
+            let (p, q) = (points[ring[i]], points[ring[(i + 1) % n]]);
+            p.x * q.y - q.x * p.y
+        })
+        .sum()
+}
+
+fn max_x(points: &[Point2], ring: &[usize]) -> f64 {
+    ring.iter()
+        .map(|&i| points[i].x)
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Splice a clockwise `hole` into the counter-clockwise `polygon` through a
+/// bridge from the hole's rightmost vertex to the nearest visible polygon
+/// vertex.
+fn bridge_hole(
+    points: &[Point2],
+    polygon: &mut Vec<usize>,
+    hole: &[usize],
+    pending: &[Vec<usize>],
+) {
+    let m_pos = (0..hole.len())
+        .max_by(|&a, &b| points[hole[a]].x.total_cmp(&points[hole[b]].x))
+        .unwrap();
+    let m = points[hole[m_pos]];
+
+    let edges = ring_edges(polygon)
+        .chain(ring_edges(hole))
+        .chain(pending.iter().flat_map(|r| ring_edges(r)))
+        .collect::<Vec<_>>();
+
+    let n = polygon.len();
+    let mut best: Option<(usize, f64)> = None;
+    for i in 0..n {
+        let p = points[polygon[i]];
+        let dist = (p - m).norm_squared();
+        if best.is_some_and(|(_, d)| dist >= d) {
+            continue;
+        }
+        let a = points[polygon[(i + n - 1) % n]];
+        let c = points[polygon[(i + 1) % n]];
+        if !in_cone(&a, &p, &c, &m) {
+            continue;
+        }
+        let blocked = edges.iter().any(|&(u, v)| {
+            let (u, v) = (points[u], points[v]);
+            u != p
+                && v != p
+                && u != m
+                && v != m
+                && segment_intersect_2d(&p, &m, &u, &v) != SegIntersection::None
+        });
+        if !blocked {
+            best = Some((i, dist));
+        }
+    }
+    // A visible vertex always exists for a simple polygon; fall back to the
+    // nearest vertex rather than dropping the hole.
+    let i = best.map(|(i, _)| i).unwrap_or_else(|| {
+        (0..n)
+            .min_by(|&a, &b| {
+                let da = (points[polygon[a]] - m).norm_squared();
+                let db = (points[polygon[b]] - m).norm_squared();
+                da.total_cmp(&db)
+            })
+            .unwrap()
+    });
+
+    let mut spliced = Vec::with_capacity(n + hole.len() + 2);
+    spliced.extend_from_slice(&polygon[..=i]);
+    spliced.extend_from_slice(&hole[m_pos..]);
+    spliced.extend_from_slice(&hole[..=m_pos]);
+    spliced.extend_from_slice(&polygon[i..]);
+    *polygon = spliced;
+}
+
+fn ring_edges(ring: &[usize]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()]))
+}
+
+/// Whether `m` lies strictly inside the interior angle at `p` of a
+/// counter-clockwise polygon with neighbours `a` and `c`.
+fn in_cone(a: &Point2, p: &Point2, c: &Point2, m: &Point2) -> bool {
+    let left_of_in = orient2d(a, p, m) == Sign::Positive;
+    let left_of_out = orient2d(p, c, m) == Sign::Positive;
+    if orient2d(a, p, c) != Sign::Negative {
+        left_of_in && left_of_out
+    } else {
+        left_of_in || left_of_out
+    }
+}
+
+/// Ear-clip a counter-clockwise polygon (possibly with bridge edges).
+fn ear_clip(points: &[Point2], mut ring: Vec<usize>) -> Vec<[usize; 3]> {
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2));
+
+    while ring.len() > 3 {
+        let n = ring.len();
+        let ear = (0..n).find(|&i| {
+            let (ia, ib, ic) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+            let (a, b, c) = (points[ia], points[ib], points[ic]);
+            if orient2d(&a, &b, &c) != Sign::Positive {
+                return false;
+            }
+            // Bridge duplicates share coordinates with the ear's corners.
+            !ring.iter().any(|&j| {
+                let p = points[j];
+                p != a
+                    && p != b
+                    && p != c
+                    && orient2d(&a, &b, &p) != Sign::Negative
+                    && orient2d(&b, &c, &p) != Sign::Negative
+                    && orient2d(&c, &a, &p) != Sign::Negative
+            })
+        });
+
+        match ear {
+            Some(i) => {
+                triangles.push([ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]]);
+                ring.remove(i);
+            }
+            None => {
+                // Only degenerate (collinear) corners remain clippable;
+                // drop one without emitting a zero-area triangle.
+                let flat = (0..n).find(|&i| {
+                    let a = points[ring[(i + n - 1) % n]];
+                    let c = points[ring[(i + 1) % n]];
+                    orient2d(&a, &points[ring[i]], &c) == Sign::Zero
+                });
+                match flat {
+                    Some(i) => {
+                        ring.remove(i);
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    if ring.len() == 3
+        && orient2d(&points[ring[0]], &points[ring[1]], &points[ring[2]]) == Sign::Positive
+    {
+        triangles.push([ring[0], ring[1], ring[2]]);
+    }
+    triangles
+}
+
+/// Flip interior edges until every one passes the empty-circumcircle test.
+///
+/// Boundary edges belong to a single triangle and are never flipped, so the
+/// result is a constrained Delaunay triangulation of the input loops.
+fn delaunay_flip(points: &[Point2], triangles: &mut [[usize; 3]]) {
+    // Lawson flipping needs O(n²) flips at worst.
+    let max_passes = triangles.len().max(1) * triangles.len().max(1);
+    for _ in 0..max_passes {
+        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (t, tri) in triangles.iter().enumerate() {
+            for k in 0..3 {
+                let (u, v) = (tri[k], tri[(k + 1) % 3]);
+                edges.entry((u.min(v), u.max(v))).or_default().push(t);
+            }
+        }
+
+        let mut touched = vec![false; triangles.len()];
+        let mut flipped = false;
+        for tris in edges.values() {
+            let &[t1, t2] = tris.as_slice() else {
+                continue;
+            };
+            if touched[t1] || touched[t2] {
+                continue;
+            }
+            if let Some((new1, new2)) = try_flip(points, &triangles[t1], &triangles[t2]) {
+                triangles[t1] = new1;
+                triangles[t2] = new2;
+                touched[t1] = true;
+                touched[t2] = true;
+                flipped = true;
+            }
+        }
+        if !flipped {
+            break;
+        }
+    }
+}
+
+/// Flip the shared edge of two adjacent triangles if it is not locally
+/// Delaunay and the surrounding quad is convex.
+fn try_flip(
+    points: &[Point2],
+    t1: &[usize; 3],
+    t2: &[usize; 3],
+) -> Option<([usize; 3], [usize; 3])> {
+    // Rotate t1 so its shared edge is a → b; t2 must then contain b → a.
+    let (a, b, c) = (0..3).find_map(|k| {
+        let (a, b, c) = (t1[k], t1[(k + 1) % 3], t1[(k + 2) % 3]);
+        (0..3)
+            .any(|j| t2[j] == b && t2[(j + 1) % 3] == a)
+            .then_some((a, b, c))
+    })?;
+    let d = *t2.iter().find(|&&v| v != a && v != b)?;
+
+    let (pa, pb, pc, pd) = (points[a], points[b], points[c], points[d]);
+    if incircle(&pa, &pb, &pc, &pd) != Sign::Positive {
+        return None;
+    }
+    if orient2d(&pc, &pa, &pd) != Sign::Positive || orient2d(&pc, &pd, &pb) != Sign::Positive {
+        return None;
+    }
+    Some(([c, a, d], [c, d, b]))
+}
@@ -1,6 +1,7 @@
 //! Collision shape generation from vcad geometry.
 
-use nalgebra::{Point3, Vector3};
+use nalgebra::{Matrix3, Point3, Vector3};
+use parry3d::mass_properties::MassProperties;
 use parry3d::shape::{ConvexPolyhedron, SharedShape, TriMesh};
 use vcad_kernel_tessellate::TriangleMesh;
 
@@ -200,6 +201,15 @@ pub fn estimate_mass(mesh: &TriangleMesh, density: f32) -> f32 {
     (volume.abs() * density).max(0.001) // Minimum mass of 1 gram
 }
 
+/// Convert explicit part mass properties (kg, mm, kg·mm²) to body mass
+/// properties in meters.
+pub fn part_mass_properties(props: &vcad_ir::MassProperties) -> MassProperties {
+    let c = &props.center_of_mass;
+    let com = Point3::new(c.x / 1000.0, c.y / 1000.0, c.z / 1000.0).cast::<f32>();
+    let inertia = Matrix3::from_fn(|i, j| (props.inertia[i][j] * 1e-6) as f32);
+    MassProperties::with_inertia_matrix(com, props.mass as f32, inertia)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,7 +257,9 @@ mod tests {
                 name: Some("base".to_string()),
                 op: vcad_ir::CsgOp::Cube {
                     size: Vec3::new(100.0, 100.0, 50.0),
+                    origin: vcad_ir::BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
         doc.nodes.insert(
@@ -267,7 +269,9 @@ mod tests {
                 name: Some("link1".to_string()),
                 op: vcad_ir::CsgOp::Cube {
                     size: Vec3::new(20.0, 20.0, 100.0),
+                    origin: vcad_ir::BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
         doc.nodes.insert(
@@ -277,7 +281,9 @@ mod tests {
                 name: Some("link2".to_string()),
                 op: vcad_ir::CsgOp::Cube {
                     size: Vec3::new(20.0, 20.0, 100.0),
+                    origin: vcad_ir::BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
 
@@ -287,18 +293,24 @@ mod tests {
             id: "base".to_string(),
             name: Some("Base".to_string()),
             root: 1,
+            collision_root: None,
+            mass_properties: None,
             default_material: None,
         });
         part_defs.insert("link1".to_string(), PartDef {
             id: "link1".to_string(),
             name: Some("Link 1".to_string()),
             root: 2,
+            collision_root: None,
+            mass_properties: None,
             default_material: None,
         });
         part_defs.insert("link2".to_string(), PartDef {
             id: "link2".to_string(),
             name: Some("Link 2".to_string()),
             root: 3,
+            collision_root: None,
+            mass_properties: None,
             default_material: None,
         });
         doc.part_defs = Some(part_defs);
@@ -12,7 +12,7 @@ use rapier3d::geometry::{BroadPhaseMultiSap, ColliderBuilder, ColliderSet, Narro
 use rapier3d::pipeline::{PhysicsPipeline, QueryPipeline};
 use vcad_ir::{Document, JointKind};
 
-use crate::colliders::{estimate_mass, mesh_to_collider, ColliderStrategy};
+use crate::colliders::{estimate_mass, mesh_to_collider, part_mass_properties, ColliderStrategy};
 use crate::error::PhysicsError;
 use crate::joints::{convert_state_from_physics, convert_state_to_physics, get_joint_axis, vcad_joint_to_rapier};
 
@@ -105,18 +105,20 @@ impl PhysicsWorld {
                 })
                 .unwrap_or(Isometry3::identity());
 
-            // Estimate mass from mesh
-            let density = doc
-                .materials
-                .get(instance.material.as_deref().unwrap_or("default"))
-                .and_then(|m| m.density)
-                .unwrap_or(1000.0) as f32; // Default to plastic-like
-            let mass = estimate_mass(&mesh, density);
-
-            let rigid_body = RigidBodyBuilder::new(body_type)
-                .position(position)
-                .additional_mass(mass)
-                .build();
+            // Explicit mass properties win over an estimate from the mesh
+            let rigid_body = RigidBodyBuilder::new(body_type).position(position);
+            let rigid_body = match &part_def.mass_properties {
+                Some(props) => rigid_body.additional_mass_properties(part_mass_properties(props)),
+                None => {
+                    let density = doc
+                        .materials
+                        .get(instance.material.as_deref().unwrap_or("default"))
+                        .and_then(|m| m.density)
+                        .unwrap_or(1000.0) as f32; // Default to plastic-like
+                    rigid_body.additional_mass(estimate_mass(&mesh, density))
+                }
+            }
+            .build();
 
             let body_handle = world.bodies.insert(rigid_body);
             world
@@ -414,8 +416,8 @@ impl PhysicsWorld {
 
         // Create a simple mesh based on the primitive type
         let solid = match &node.op {
-            vcad_ir::CsgOp::Cube { size } => {
-                vcad_kernel::Solid::cube(size.x, size.y, size.z)
+            vcad_ir::CsgOp::Cube { size, origin } => {
+                vcad_kernel::Solid::cube_at(size.x, size.y, size.z, (*origin).into())
             }
             vcad_ir::CsgOp::Cylinder { radius, height, segments } => {
                 vcad_kernel::Solid::cylinder(*radius, *height, if *segments == 0 { 32 } else { *segments })
@@ -452,7 +454,9 @@ mod tests {
                 name: Some("base_geom".to_string()),
                 op: vcad_ir::CsgOp::Cube {
                     size: Vec3::new(100.0, 100.0, 50.0),
+                    origin: vcad_ir::BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
         doc.nodes.insert(
@@ -462,7 +466,9 @@ mod tests {
                 name: Some("arm_geom".to_string()),
                 op: vcad_ir::CsgOp::Cube {
                     size: Vec3::new(20.0, 20.0, 100.0),
+                    origin: vcad_ir::BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
 
@@ -474,6 +480,8 @@ mod tests {
                 id: "base".to_string(),
                 name: Some("Base".to_string()),
                 root: 1,
+                collision_root: None,
+                mass_properties: None,
                 default_material: None,
             },
         );
@@ -483,6 +491,8 @@ mod tests {
                 id: "arm".to_string(),
                 name: Some("Arm".to_string()),
                 root: 2,
+                collision_root: None,
+                mass_properties: None,
                 default_material: None,
             },
         );
@@ -5,10 +5,15 @@
 //! Constructs valid B-rep topology + geometry for standard CAD primitives:
 //! cube (box), cylinder, sphere, and cone.
 
+use std::sync::OnceLock;
+
 use vcad_kernel_geom::{Circle3d, CylinderSurface, GeometryStore, Line3d, Plane, SphereSurface};
 use vcad_kernel_math::{Point3, Vec3};
 use vcad_kernel_topo::{HalfEdgeId, Orientation, ShellType, SolidId, Topology};
 
+mod query;
+mod validate;
+
 /// Result of constructing a B-rep primitive: topology + geometry.
 #[derive(Debug, Clone)]
 pub struct BRepSolid {
@@ -18,6 +23,72 @@ pub struct BRepSolid {
     pub geometry: GeometryStore,
     /// The solid entity.
     pub solid_id: SolidId,
+    /// Lazily computed bounds; see [`BRepSolid::bounds_or_insert_with`].
+    bounds: BoundsCache,
+}
+
+impl BRepSolid {
+    /// Wrap topology and geometry into a solid with an empty bounds cache.
+    pub fn new(topology: Topology, geometry: GeometryStore, solid_id: SolidId) -> Self {
+        Self {
+            topology,
+            geometry,
+            solid_id,
+            bounds: BoundsCache::default(),
+        }
+    }
+
+    /// The cached `(min, max)` bounds, or `None` if not yet computed.
+    pub fn cached_bounds(&self) -> Option<(Point3, Point3)> {
+        self.bounds.0.get().copied()
+    }
+
+    /// The cached `(min, max)` bounds, computing them with `f` on first use.
+    ///
+    /// The cache is not tracked against `topology` or `geometry`: code that
+    /// edits a solid in place after its bounds were read must call
+    /// [`BRepSolid::invalidate_bounds`]. Clones start with an empty cache.
+    pub fn bounds_or_insert_with(
+        &self,
+        f: impl FnOnce(&BRepSolid) -> (Point3, Point3),
+    ) -> (Point3, Point3) {
+        *self.bounds.0.get_or_init(|| f(self))
+    }
+
+    /// Drop the cached bounds after an in-place edit.
+    pub fn invalidate_bounds(&mut self) {
+        self.bounds = BoundsCache::default();
+    }
+}
+
+/// Bounds cache that is deliberately emptied on clone, so a copy that is
+/// then modified never reports the bounds of the original.
+#[derive(Debug, Default)]
+struct BoundsCache(OnceLock<(Point3, Point3)>);
+
+impl Clone for BoundsCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Where a box is placed relative to the origin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BoxOrigin {
+    /// Min corner at the origin; the box extends along +X, +Y and +Z.
+    #[default]
+    Corner,
+    /// Center at the origin.
+    Center,
+}
+
+impl From<vcad_ir::BoxOrigin> for BoxOrigin {
+    fn from(origin: vcad_ir::BoxOrigin) -> Self {
+        match origin {
+            vcad_ir::BoxOrigin::Corner => BoxOrigin::Corner,
+            vcad_ir::BoxOrigin::Center => BoxOrigin::Center,
+        }
+    }
 }
 
 /// Build a B-rep box (cuboid) with corner at origin and dimensions `(sx, sy, sz)`.
@@ -33,18 +104,31 @@ pub struct BRepSolid {
 ///   v3----v2     +---x
 /// ```
 pub fn make_cube(sx: f64, sy: f64, sz: f64) -> BRepSolid {
+    make_cube_at(sx, sy, sz, BoxOrigin::Corner)
+}
+
+/// Build a B-rep box with dimensions `(sx, sy, sz)` placed by `origin`.
+///
+/// Same topology as [`make_cube`], which is the [`BoxOrigin::Corner`] case.
+pub fn make_cube_at(sx: f64, sy: f64, sz: f64, origin: BoxOrigin) -> BRepSolid {
+    let (x0, y0, z0) = match origin {
+        BoxOrigin::Corner => (0.0, 0.0, 0.0),
+        BoxOrigin::Center => (-sx / 2.0, -sy / 2.0, -sz / 2.0),
+    };
+    let (x1, y1, z1) = (x0 + sx, y0 + sy, z0 + sz);
+
     let mut topo = Topology::new();
     let mut geom = GeometryStore::new();
 
     // 8 vertices
-    let v0 = topo.add_vertex(Point3::new(0.0, 0.0, 0.0));
-    let v1 = topo.add_vertex(Point3::new(sx, 0.0, 0.0));
-    let v2 = topo.add_vertex(Point3::new(sx, sy, 0.0));
-    let v3 = topo.add_vertex(Point3::new(0.0, sy, 0.0));
-    let v4 = topo.add_vertex(Point3::new(0.0, 0.0, sz));
-    let v5 = topo.add_vertex(Point3::new(sx, 0.0, sz));
-    let v6 = topo.add_vertex(Point3::new(sx, sy, sz));
-    let v7 = topo.add_vertex(Point3::new(0.0, sy, sz));
+    let v0 = topo.add_vertex(Point3::new(x0, y0, z0));
+    let v1 = topo.add_vertex(Point3::new(x1, y0, z0));
+    let v2 = topo.add_vertex(Point3::new(x1, y1, z0));
+    let v3 = topo.add_vertex(Point3::new(x0, y1, z0));
+    let v4 = topo.add_vertex(Point3::new(x0, y0, z1));
+    let v5 = topo.add_vertex(Point3::new(x1, y0, z1));
+    let v6 = topo.add_vertex(Point3::new(x1, y1, z1));
+    let v7 = topo.add_vertex(Point3::new(x0, y1, z1));
 
     // 6 faces, each with 4 half-edges forming a loop.
     // Convention: outward normals, CCW vertex order when viewed from outside.
@@ -54,45 +138,45 @@ pub fn make_cube(sx: f64, sy: f64, sz: f64) -> BRepSolid {
     // Face helpers: for each face, define the 4 vertices in CCW order (viewed from outside)
     // Plane normal = x_dir × y_dir, so we choose x_dir/y_dir to produce outward normals
     let face_defs: [([vcad_kernel_topo::VertexId; 4], Point3, Vec3, Vec3); 6] = [
-        // Bottom face (z=0): normal -Z = (0,1,0) × (1,0,0)
+        // Bottom face (z=z0): normal -Z = (0,1,0) × (1,0,0)
         (
             [v0, v3, v2, v1],
-            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(x0, y0, z0),
             Vec3::new(0.0, 1.0, 0.0),
             Vec3::new(1.0, 0.0, 0.0),
         ),
-        // Top face (z=sz): normal +Z = (1,0,0) × (0,1,0)
+        // Top face (z=z1): normal +Z = (1,0,0) × (0,1,0)
         (
             [v4, v5, v6, v7],
-            Point3::new(0.0, 0.0, sz),
+            Point3::new(x0, y0, z1),
             Vec3::new(1.0, 0.0, 0.0),
             Vec3::new(0.0, 1.0, 0.0),
         ),
-        // Front face (y=0): normal -Y = (1,0,0) × (0,0,1)
+        // Front face (y=y0): normal -Y = (1,0,0) × (0,0,1)
         (
             [v0, v1, v5, v4],
-            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(x0, y0, z0),
             Vec3::new(1.0, 0.0, 0.0),
             Vec3::new(0.0, 0.0, 1.0),
         ),
-        // Back face (y=sy): normal +Y = (0,0,1) × (1,0,0)
+        // Back face (y=y1): normal +Y = (0,0,1) × (1,0,0)
         (
             [v2, v3, v7, v6],
-            Point3::new(0.0, sy, 0.0),
+            Point3::new(x0, y1, z0),
             Vec3::new(0.0, 0.0, 1.0),
             Vec3::new(1.0, 0.0, 0.0),
         ),
-        // Left face (x=0): normal -X = (0,0,1) × (0,1,0)
+        // Left face (x=x0): normal -X = (0,0,1) × (0,1,0)
         (
             [v0, v4, v7, v3],
-            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(x0, y0, z0),
             Vec3::new(0.0, 0.0, 1.0),
             Vec3::new(0.0, 1.0, 0.0),
         ),
-        // Right face (x=sx): normal +X = (0,1,0) × (0,0,1)
+        // Right face (x=x1): normal +X = (0,1,0) × (0,0,1)
         (
             [v1, v2, v6, v5],
-            Point3::new(sx, 0.0, 0.0),
+            Point3::new(x1, y0, z0),
             Vec3::new(0.0, 1.0, 0.0),
             Vec3::new(0.0, 0.0, 1.0),
         ),
@@ -147,11 +231,7 @@ pub fn make_cube(sx: f64, sy: f64, sz: f64) -> BRepSolid {
     let shell = topo.add_shell(all_faces, ShellType::Outer);
     let solid_id = topo.add_solid(shell);
 
-    BRepSolid {
-        topology: topo,
-        geometry: geom,
-        solid_id,
-    }
+    BRepSolid::new(topo, geom, solid_id)
 }
 
 /// Build a B-rep cylinder with the given radius and height, axis along Z.
@@ -163,44 +243,56 @@ pub fn make_cube(sx: f64, sy: f64, sz: f64) -> BRepSolid {
 /// - Seam edge connects the two circles at u=0
 ///
 /// `segments` controls tessellation quality but doesn't affect the B-rep structure.
-pub fn make_cylinder(radius: f64, height: f64, _segments: u32) -> BRepSolid {
+pub fn make_cylinder(radius: f64, height: f64, segments: u32) -> BRepSolid {
+    make_cylinder_at(radius, height, segments, BoxOrigin::Corner)
+}
+
+/// Build a B-rep cylinder with axis along Z, placed by `origin`.
+///
+/// [`BoxOrigin::Corner`] puts the bottom cap on z=0, as [`make_cylinder`]
+/// does; [`BoxOrigin::Center`] centers the cylinder along its axis. Either
+/// way the axis passes through the origin.
+pub fn make_cylinder_at(radius: f64, height: f64, _segments: u32, origin: BoxOrigin) -> BRepSolid {
+    let z0 = match origin {
+        BoxOrigin::Corner => 0.0,
+        BoxOrigin::Center => -height / 2.0,
+    };
+    let z1 = z0 + height;
+
     let mut topo = Topology::new();
     let mut geom = GeometryStore::new();
 
     // Vertices: 2 points on the seam (u=0) at bottom and top
-    let v_bot = topo.add_vertex(Point3::new(radius, 0.0, 0.0));
-    let v_top = topo.add_vertex(Point3::new(radius, 0.0, height));
+    let v_bot = topo.add_vertex(Point3::new(radius, 0.0, z0));
+    let v_top = topo.add_vertex(Point3::new(radius, 0.0, z1));
 
     // Surfaces:
     // 0: cylindrical lateral surface
-    let cyl_surf = CylinderSurface::new(radius);
+    let cyl_surf = CylinderSurface::with_axis(Point3::new(0.0, 0.0, z0), Vec3::z(), radius);
     let cyl_idx = geom.add_surface(Box::new(cyl_surf));
 
-    // 1: bottom cap (z=0, normal -Z)
+    // 1: bottom cap (z=z0, normal -Z)
     let bot_plane = Plane::new(
-        Point3::origin(),
+        Point3::new(0.0, 0.0, z0),
         Vec3::new(1.0, 0.0, 0.0),
         Vec3::new(0.0, -1.0, 0.0), // Reversed Y so normal points -Z
     );
     let bot_idx = geom.add_surface(Box::new(bot_plane));
 
-    // 2: top cap (z=height, normal +Z)
+    // 2: top cap (z=z1, normal +Z)
     let top_plane = Plane::new(
-        Point3::new(0.0, 0.0, height),
+        Point3::new(0.0, 0.0, z1),
         Vec3::new(1.0, 0.0, 0.0),
         Vec3::new(0.0, 1.0, 0.0),
     );
     let top_idx = geom.add_surface(Box::new(top_plane));
 
     // 3D curves: bottom circle, top circle, seam line
-    geom.add_curve_3d(Box::new(Circle3d::new(Point3::origin(), radius)));
-    geom.add_curve_3d(Box::new(Circle3d::new(
-        Point3::new(0.0, 0.0, height),
-        radius,
-    )));
+    geom.add_curve_3d(Box::new(Circle3d::new(Point3::new(0.0, 0.0, z0), radius)));
+    geom.add_curve_3d(Box::new(Circle3d::new(Point3::new(0.0, 0.0, z1), radius)));
     geom.add_curve_3d(Box::new(Line3d::from_points(
-        Point3::new(radius, 0.0, 0.0),
-        Point3::new(radius, 0.0, height),
+        Point3::new(radius, 0.0, z0),
+        Point3::new(radius, 0.0, z1),
     )));
 
     // Lateral face: single face with a single loop using a seam edge
@@ -245,11 +337,7 @@ pub fn make_cylinder(radius: f64, height: f64, _segments: u32) -> BRepSolid {
     let shell = topo.add_shell(vec![lat_face, bot_face, top_face], ShellType::Outer);
     let solid_id = topo.add_solid(shell);
 
-    BRepSolid {
-        topology: topo,
-        geometry: geom,
-        solid_id,
-    }
+    BRepSolid::new(topo, geom, solid_id)
 }
 
 /// Build a B-rep sphere with the given radius, centered at origin.
@@ -315,11 +403,7 @@ pub fn make_sphere(radius: f64, _segments: u32) -> BRepSolid {
     let shell = topo.add_shell(vec![sphere_face], ShellType::Outer);
     let solid_id = topo.add_solid(shell);
 
-    BRepSolid {
-        topology: topo,
-        geometry: geom,
-        solid_id,
-    }
+    BRepSolid::new(topo, geom, solid_id)
 }
 
 /// Build a B-rep cone (frustum) with bottom radius, top radius, and height along Z.
@@ -392,11 +476,7 @@ pub fn make_cone(radius_bottom: f64, radius_top: f64, height: f64, _segments: u3
         let shell = topo.add_shell(vec![lat_face, bot_face], ShellType::Outer);
         let solid_id = topo.add_solid(shell);
 
-        BRepSolid {
-            topology: topo,
-            geometry: geom,
-            solid_id,
-        }
+        BRepSolid::new(topo, geom, solid_id)
     } else {
         // Frustum: two circles + seam
         let v_bot = topo.add_vertex(Point3::new(radius_bottom, 0.0, 0.0));
@@ -444,11 +524,7 @@ pub fn make_cone(radius_bottom: f64, radius_top: f64, height: f64, _segments: u3
         let shell = topo.add_shell(vec![lat_face, bot_face, top_face], ShellType::Outer);
         let solid_id = topo.add_solid(shell);
 
-        BRepSolid {
-            topology: topo,
-            geometry: geom,
-            solid_id,
-        }
+        BRepSolid::new(topo, geom, solid_id)
     }
 }
 
@@ -470,6 +546,37 @@ mod tests {
         assert_eq!(topo.solids.len(), 1);
     }
 
+    #[test]
+    fn test_cube_origin() {
+        let bounds = |brep: &BRepSolid| {
+            let mut min = [f64::MAX; 3];
+            let mut max = [f64::MIN; 3];
+            for (_, v) in &brep.topology.vertices {
+                for (i, c) in [v.point.x, v.point.y, v.point.z].into_iter().enumerate() {
+                    min[i] = min[i].min(c);
+                    max[i] = max[i].max(c);
+                }
+            }
+            (min, max)
+        };
+        assert_eq!(
+            bounds(&make_cube(10.0, 20.0, 30.0)),
+            ([0.0; 3], [10.0, 20.0, 30.0])
+        );
+        let centered = make_cube_at(10.0, 20.0, 30.0, BoxOrigin::Center);
+        assert_eq!(bounds(&centered), ([-5.0, -10.0, -15.0], [5.0, 10.0, 15.0]));
+
+        // Every face's plane passes through its vertices
+        for (_, face) in &centered.topology.faces {
+            let surface = &centered.geometry.surfaces[face.surface_index];
+            let plane = surface.as_any().downcast_ref::<Plane>().unwrap();
+            for he in centered.topology.loop_half_edges(face.outer_loop) {
+                let p = centered.topology.vertices[centered.topology.half_edges[he].origin].point;
+                assert!((p - plane.origin).dot(plane.normal_dir.as_ref()).abs() < 1e-12);
+            }
+        }
+    }
+
     #[test]
     fn test_cube_geometry() {
         let brep = make_cube(10.0, 20.0, 30.0);
@@ -517,6 +624,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cylinder_origin() {
+        let z_range = |brep: &BRepSolid| {
+            let zs = brep.topology.vertices.values().map(|v| v.point.z);
+            (
+                zs.clone().fold(f64::MAX, f64::min),
+                zs.fold(f64::MIN, f64::max),
+            )
+        };
+        assert_eq!(z_range(&make_cylinder(5.0, 10.0, 32)), (0.0, 10.0));
+        let centered = make_cylinder_at(5.0, 10.0, 32, BoxOrigin::Center);
+        assert_eq!(z_range(&centered), (-5.0, 5.0));
+
+        // The lateral surface starts at the bottom cap
+        let lateral = centered.geometry.surfaces[0].as_any();
+        let cylinder = lateral.downcast_ref::<CylinderSurface>().unwrap();
+        assert_eq!(cylinder.center, Point3::new(0.0, 0.0, -5.0));
+        let bottom = centered.geometry.surfaces[1].as_any();
+        assert_eq!(bottom.downcast_ref::<Plane>().unwrap().origin.z, -5.0);
+    }
+
     #[test]
     fn test_sphere_topology() {
         let brep = make_sphere(10.0, 32);
@@ -0,0 +1,330 @@
+//! Proximity queries on B-rep solids.
+//!
+//! Maps a 3D point to the closest face or edge of a [`BRepSolid`], e.g. to
+//! resolve a pick in a viewer to a topological entity.
+
+use vcad_kernel_geom::{ConeSurface, CylinderSurface, Plane, Surface};
+use vcad_kernel_math::{Point2, Point3, Vec3};
+use vcad_kernel_topo::{EdgeId, FaceId, HalfEdgeId, LoopId};
+
+use crate::BRepSolid;
+
+/// Tolerance for matching a closed edge's vertex to a curve in the geometry store.
+const CURVE_MATCH_TOL: f64 = 1e-6;
+
+/// Grid resolution for the coarse closest-point search on general surfaces.
+const SURFACE_GRID: usize = 16;
+
+impl BRepSolid {
+    /// The face closest to `p`, or `None` if the solid has no faces.
+    ///
+    /// Planar and cylindrical/conical faces are measured against their
+    /// trimmed extent; other surfaces use their untrimmed parameter domain.
+    pub fn nearest_face(&self, p: Point3) -> Option<FaceId> {
+        self.topology
+            .faces
+            .keys()
+            .map(|face| (face, self.face_distance(face, p)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(face, _)| face)
+    }
+
+    /// The edge closest to `p`, or `None` if the solid has no edges.
+    pub fn nearest_edge(&self, p: Point3) -> Option<EdgeId> {
+        self.topology
+            .edges
+            .keys()
+            .map(|edge| (edge, self.edge_distance(edge, p)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(edge, _)| edge)
+    }
+
+    /// Distance from `p` to a face.
+    pub fn face_distance(&self, face: FaceId, p: Point3) -> f64 {
+        let segments = self.face_boundary(face);
+        let boundary = polyline_distance(&segments, p);
+        let surface = self.geometry.surfaces[self.topology.faces[face].surface_index].as_ref();
+        let any = surface.as_any();
+
+        if let Some(plane) = any.downcast_ref::<Plane>() {
+            let uv = plane.project(&p);
+            let inside = point_in_loops(
+                uv,
+                segments.iter().map(|s| s.iter().map(|q| plane.project(q))),
+            );
+            return if inside {
+                plane.signed_distance(&p).abs()
+            } else {
+                boundary
+            };
+        }
+        if let Some(cyl) = any.downcast_ref::<CylinderSurface>() {
+            return revolved_distance(cyl.center, *cyl.axis, &segments, p).min(boundary);
+        }
+        if let Some(cone) = any.downcast_ref::<ConeSurface>() {
+            return revolved_distance(cone.apex, *cone.axis, &segments, p).min(boundary);
+        }
+        surface_distance(surface, p).min(boundary)
+    }
+
+    /// Distance from `p` to an edge.
+    pub fn edge_distance(&self, edge: EdgeId, p: Point3) -> f64 {
+        polyline_distance(&[self.edge_polyline(edge)], p)
+    }
+
+    /// Centroid of a face's boundary, or `None` if the face does not exist.
+    ///
+    /// This is the average of points sampled along the outer loop, which is
+    /// the area centroid for convex planar polygons with evenly spaced
+    /// vertices and a reasonable "center" for picking labels otherwise.
+    pub fn face_centroid(&self, face: FaceId) -> Option<Point3> {
+        let outer = self.topology.faces.get(face)?.outer_loop;
+        let mut sum = Vec3::zeros();
+        let mut count = 0usize;
+        for he in self.topology.loop_half_edges(outer) {
+            let points = self.half_edge_polyline(he);
+            // The last point is the next half-edge's first point.
+            let n = points.len().saturating_sub(1).max(1);
+            for q in &points[..n] {
+                sum += q.coords;
+                count += 1;
+            }
+        }
+        (count > 0).then(|| Point3::from(sum / count as f64))
+    }
+
+    /// Point halfway along an edge by arc length, or `None` if the edge does not exist.
+    pub fn edge_midpoint(&self, edge: EdgeId) -> Option<Point3> {
+        self.topology.edges.get(edge)?;
+        let points = self.edge_polyline(edge);
+        let total: f64 = points.windows(2).map(|w| (w[1] - w[0]).norm()).sum();
+        let mut remaining = total / 2.0;
+        for w in points.windows(2) {
+            let len = (w[1] - w[0]).norm();
+            if remaining <= len && len > 0.0 {
+                return Some(w[0] + (w[1] - w[0]) * (remaining / len));
+            }
+            remaining -= len;
+        }
+        points.first().copied()
+    }
+
+    /// Sampled geometry of an edge, oriented along its primary half-edge.
+    fn edge_polyline(&self, edge: EdgeId) -> Vec<Point3> {
+        self.half_edge_polyline(self.topology.edges[edge].half_edge)
+    }
+
+    /// Sampled geometry of a half-edge from its origin to its destination.
+    ///
+    /// Open edges are straight segments between their vertices. A closed edge
+    /// (origin == destination) is sampled from the closed curve in the
+    /// geometry store that passes through its vertex.
+    fn half_edge_polyline(&self, he: HalfEdgeId) -> Vec<Point3> {
+        let topo = &self.topology;
+        let start = topo.vertices[topo.half_edges[he].origin].point;
+        let end = topo.vertices[topo.half_edge_dest(he)].point;
+        if (end - start).norm() > CURVE_MATCH_TOL {
+            return vec![start, end];
+        }
+
+        for curve in &self.geometry.curves_3d {
+            let (t0, t1) = curve.domain();
+            let closed = (curve.evaluate(t0) - curve.evaluate(t1)).norm() < CURVE_MATCH_TOL;
+            if !closed || (curve.evaluate(t0) - start).norm() > CURVE_MATCH_TOL {
+                continue;
+            }
+            let n = curve.suggested_segments().max(32);
+            return (0..=n)
+                .map(|i| curve.evaluate(t0 + (t1 - t0) * i as f64 / n as f64))
+                .collect();
+        }
+        vec![start]
+    }
+
+    /// Sampled geometry of every loop of a face, one polyline per loop.
+    fn face_boundary(&self, face: FaceId) -> Vec<Vec<Point3>> {
+        let f = &self.topology.faces[face];
+        std::iter::once(f.outer_loop)
+            .chain(f.inner_loops.iter().copied())
+            .map(|l| self.loop_polyline(l))
+            .collect()
+    }
+
+    fn loop_polyline(&self, loop_id: LoopId) -> Vec<Point3> {
+        let mut points: Vec<Point3> = Vec::new();
+        for he in self.topology.loop_half_edges(loop_id) {
+            let seg = self.half_edge_polyline(he);
+            let skip = usize::from(points.last().is_some_and(|&last| {
+                seg.first()
+                    .is_some_and(|&first| (first - last).norm() < CURVE_MATCH_TOL)
+            }));
+            points.extend(seg.into_iter().skip(skip));
+        }
+        if let Some(&first) = points.first() {
+            points.push(first);
+        }
+        points
+    }
+}
+
+/// Distance from `p` to the closest of several polylines.
+fn polyline_distance(polylines: &[Vec<Point3>], p: Point3) -> f64 {
+    polylines
+        .iter()
+        .flat_map(|pts| {
+            let single = (pts.len() == 1).then(|| (pts[0] - p).norm());
+            pts.windows(2)
+                .map(move |w| segment_distance(w[0], w[1], p))
+                .chain(single)
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+fn segment_distance(a: Point3, b: Point3, p: Point3) -> f64 {
+    let ab = b - a;
+    let len2 = ab.norm_squared();
+    let t = if len2 > 0.0 {
+        ((p - a).dot(&ab) / len2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (a + ab * t - p).norm()
+}
+
+/// Even-odd point-in-polygon test over all loops, so holes are excluded.
+fn point_in_loops<L, I>(p: Point2, loops: L) -> bool
+where
+    L: Iterator<Item = I>,
+    I: Iterator<Item = Point2>,
+{
+    let mut inside = false;
+    for loop_points in loops {
+        let pts: Vec<Point2> = loop_points.collect();
+        for w in pts.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            if (a.y > p.y) != (b.y > p.y) {
+                let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if p.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+    }
+    inside
+}
+
+/// Distance to a cylinder or cone face trimmed by its boundary.
+///
+/// Works in the half-plane through the axis and `p`: the face's generatrix is
+/// the segment between its lowest and highest boundary samples, expressed as
+/// (axial height, radial distance).
+fn revolved_distance(origin: Point3, axis: Vec3, boundary: &[Vec<Point3>], p: Point3) -> f64 {
+    let to_hr = |q: Point3| {
+        let d = q - origin;
+        let h = d.dot(&axis);
+        (h, (d - axis * h).norm())
+    };
+
+    let samples = boundary.iter().flatten().map(|&q| to_hr(q));
+    let (mut lo, mut hi) = ((f64::INFINITY, 0.0), (f64::NEG_INFINITY, 0.0));
+    for (h, r) in samples {
+        if h < lo.0 {
+            lo = (h, r);
+        }
+        if h > hi.0 {
+            hi = (h, r);
+        }
+    }
+    if !lo.0.is_finite() {
+        return f64::INFINITY;
+    }
+
+    let (ph, pr) = to_hr(p);
+    segment_distance(
+        Point3::new(lo.0, lo.1, 0.0),
+        Point3::new(hi.0, hi.1, 0.0),
+        Point3::new(ph, pr, 0.0),
+    )
+}
+
+/// Distance to an untrimmed surface: coarse grid search, then Newton refinement.
+fn surface_distance(surface: &dyn Surface, p: Point3) -> f64 {
+    let ((u0, u1), (v0, v1)) = surface.domain();
+    let (u0, u1) = (u0.max(-1e6), u1.min(1e6));
+    let (v0, v1) = (v0.max(-1e6), v1.min(1e6));
+    let at = |i: usize, lo: f64, hi: f64| lo + (hi - lo) * i as f64 / SURFACE_GRID as f64;
+
+    let mut best = (u0, v0, f64::INFINITY);
+    for i in 0..=SURFACE_GRID {
+        for j in 0..=SURFACE_GRID {
+            let (u, v) = (at(i, u0, u1), at(j, v0, v1));
+            let d = (surface.evaluate(Point2::new(u, v)) - p).norm_squared();
+            if d < best.2 {
+                best = (u, v, d);
+            }
+        }
+    }
+
+    let (mut u, mut v) = (best.0, best.1);
+    for _ in 0..10 {
+        let uv = Point2::new(u, v);
+        let r = surface.evaluate(uv) - p;
+        let su = surface.d_du(uv);
+        let sv = surface.d_dv(uv);
+        let (a, b, c) = (su.dot(&su), su.dot(&sv), sv.dot(&sv));
+        let det = a * c - b * b;
+        if det.abs() < 1e-14 {
+            break;
+        }
+        let (gu, gv) = (r.dot(&su), r.dot(&sv));
+        u = (u - (c * gu - b * gv) / det).clamp(u0, u1);
+        v = (v - (a * gv - b * gu) / det).clamp(v0, v1);
+    }
+
+    let refined = (surface.evaluate(Point2::new(u, v)) - p).norm();
+    refined.min(best.2.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{make_cube, make_cylinder, make_sphere};
+    use vcad_kernel_geom::SurfaceKind;
+    use vcad_kernel_math::Point3;
+
+    #[test]
+    fn test_nearest_face_on_cube() {
+        let cube = make_cube(10.0, 10.0, 10.0);
+        let face = cube.nearest_face(Point3::new(10.1, 5.0, 5.0)).unwrap();
+        let centroid = cube.face_centroid(face).unwrap();
+        assert!((centroid - Point3::new(10.0, 5.0, 5.0)).norm() < 1e-9);
+
+        // Inside the solid, closest to the bottom face.
+        let face = cube.nearest_face(Point3::new(4.0, 5.0, 1.0)).unwrap();
+        assert!((cube.face_centroid(face).unwrap().z).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_nearest_edge_on_cube() {
+        let cube = make_cube(10.0, 10.0, 10.0);
+        let edge = cube.nearest_edge(Point3::new(10.5, -0.5, 3.0)).unwrap();
+        let mid = cube.edge_midpoint(edge).unwrap();
+        assert!((mid - Point3::new(10.0, 0.0, 5.0)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_nearest_curved_faces() {
+        let cyl = make_cylinder(5.0, 10.0, 32);
+        let face = cyl.nearest_face(Point3::new(6.0, 0.0, 5.0)).unwrap();
+        let surface = &cyl.geometry.surfaces[cyl.topology.faces[face].surface_index];
+        assert_eq!(surface.surface_type(), SurfaceKind::Cylinder);
+        assert!((cyl.face_distance(face, Point3::new(6.0, 0.0, 5.0)) - 1.0).abs() < 1e-9);
+
+        // The circular edges sample their curves rather than collapsing to a vertex.
+        let edge = cyl.nearest_edge(Point3::new(-5.0, 0.0, 10.5)).unwrap();
+        assert!((cyl.edge_distance(edge, Point3::new(-5.0, 0.0, 10.5)) - 0.5).abs() < 1e-6);
+
+        let sphere = make_sphere(5.0, 32);
+        let face = sphere.nearest_face(Point3::new(0.0, 0.0, 7.0)).unwrap();
+        assert!((sphere.face_distance(face, Point3::new(0.0, 0.0, 7.0)) - 2.0).abs() < 1e-6);
+    }
+}
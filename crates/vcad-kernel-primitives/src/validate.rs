@@ -0,0 +1,96 @@
+//! Geometric consistency checks on B-rep solids.
+//!
+//! Catches topology whose vertices have drifted off the surfaces their
+//! faces claim to lie on, e.g. after converting a slightly warped mesh,
+//! before it shows up as bad tessellation or normals.
+
+use vcad_kernel_geom::Plane;
+use vcad_kernel_math::predicates::{orient3d, Sign};
+use vcad_kernel_topo::FaceId;
+
+use crate::BRepSolid;
+
+/// Distance from its plane beyond which a vertex makes a face non-planar
+/// in [`BRepSolid::validate_faces`].
+const PLANARITY_TOL: f64 = 1e-6;
+
+impl BRepSolid {
+    /// Whether every vertex of a face's loops lies within `tolerance` of
+    /// the face's plane.
+    ///
+    /// Vertices exactly on the plane are recognized with [`orient3d`]
+    /// against three points of it; the others are measured. Faces whose
+    /// surface is not a [`Plane`] are never planar.
+    pub fn face_is_planar(&self, face: FaceId, tolerance: f64) -> bool {
+        let Some(f) = self.topology.faces.get(face) else {
+            return false;
+        };
+        let surface = self.geometry.surfaces[f.surface_index].as_ref();
+        let Some(plane) = surface.as_any().downcast_ref::<Plane>() else {
+            return false;
+        };
+        let a = plane.origin;
+        let b = a + plane.x_dir.as_ref();
+        let c = a + plane.y_dir.as_ref();
+
+        std::iter::once(f.outer_loop)
+            .chain(f.inner_loops.iter().copied())
+            .flat_map(|l| self.topology.loop_vertices(l))
+            .all(|v| {
+                let p = self.topology.vertices[v].point;
+                orient3d(&a, &b, &c, &p) == Sign::Zero
+                    || plane.signed_distance(&p).abs() <= tolerance
+            })
+    }
+
+    /// Faces on a [`Plane`] surface whose vertices don't all lie on it.
+    ///
+    /// A valid solid returns an empty list. Faces on other surfaces are
+    /// not checked.
+    pub fn validate_faces(&self) -> Vec<FaceId> {
+        self.topology
+            .faces
+            .iter()
+            .filter(|(_, f)| {
+                let surface = self.geometry.surfaces[f.surface_index].as_ref();
+                surface.as_any().is::<Plane>()
+            })
+            .filter(|&(face, _)| !self.face_is_planar(face, PLANARITY_TOL))
+            .map(|(face, _)| face)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{make_cube, make_cylinder};
+    use vcad_kernel_math::Vec3;
+
+    #[test]
+    fn test_nudged_vertex_flags_its_faces() {
+        let mut cube = make_cube(10.0, 10.0, 10.0);
+        assert!(cube.validate_faces().is_empty());
+        assert!(make_cylinder(5.0, 10.0, 32).validate_faces().is_empty());
+
+        let (vertex, _) = cube
+            .topology
+            .vertices
+            .iter()
+            .find(|(_, v)| v.point.z > 5.0)
+            .unwrap();
+        cube.topology.vertices[vertex].point += Vec3::new(0.1, 0.1, 0.1);
+
+        // 0.1 off each of the three faces meeting at the vertex
+        let bad = cube.validate_faces();
+        assert_eq!(bad.len(), 3);
+        for &face in &bad {
+            assert!(!cube.face_is_planar(face, 0.05));
+            assert!(cube.face_is_planar(face, 0.2));
+            let touches = cube
+                .topology
+                .loop_vertices(cube.topology.faces[face].outer_loop)
+                .contains(&vertex);
+            assert!(touches);
+        }
+    }
+}
@@ -3,7 +3,7 @@
 //! Uses Surface Area Heuristic (SAH) for construction.
 
 use std::sync::Arc;
-use vcad_kernel_booleans::bbox::{face_aabb, Aabb3};
+use vcad_kernel_booleans::bbox::{face_aabb, Aabb};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_topo::FaceId;
 
@@ -13,7 +13,7 @@ use crate::{Ray, RayHit};
 
 /// A flattened BVH node tuple for GPU upload.
 /// Contains: (AABB, is_leaf, left_or_first, right_or_count)
-pub type FlatBvhNode = (Aabb3, bool, u32, u32);
+pub type FlatBvhNode = (Aabb, bool, u32, u32);
 
 /// A BVH node - either a leaf containing faces or an internal node with children.
 #[derive(Debug, Clone)]
@@ -21,14 +21,14 @@ pub enum BvhNode {
     /// Leaf node containing face indices.
     Leaf {
         /// Axis-aligned bounding box of this node.
-        aabb: Aabb3,
+        aabb: Aabb,
         /// Face IDs contained in this leaf.
         faces: Vec<FaceId>,
     },
     /// Internal node with two children.
     Internal {
         /// Axis-aligned bounding box of this node.
-        aabb: Aabb3,
+        aabb: Aabb,
         /// Left child node.
         left: Box<BvhNode>,
         /// Right child node.
@@ -49,7 +49,7 @@ impl Bvh {
         let brep = Arc::new(brep.clone());
 
         // Collect all faces with their AABBs
-        let mut face_data: Vec<(FaceId, Aabb3, vcad_kernel_math::Point3)> = brep
+        let mut face_data: Vec<(FaceId, Aabb, vcad_kernel_math::Point3)> = brep
             .topology
             .faces
             .iter()
@@ -244,7 +244,7 @@ impl Bvh {
 }
 
 /// Get the AABB of a node.
-fn get_aabb(node: &BvhNode) -> Aabb3 {
+fn get_aabb(node: &BvhNode) -> Aabb {
     match node {
         BvhNode::Leaf { aabb, .. } => *aabb,
         BvhNode::Internal { aabb, .. } => *aabb,
@@ -284,9 +284,9 @@ fn flatten_node(
 }
 
 /// Build a BVH node recursively using SAH.
-fn build_node(face_data: &mut [(FaceId, Aabb3, vcad_kernel_math::Point3)]) -> BvhNode {
+fn build_node(face_data: &mut [(FaceId, Aabb, vcad_kernel_math::Point3)]) -> BvhNode {
     // Compute bounds of all faces
-    let mut bounds = Aabb3::empty();
+    let mut bounds = Aabb::empty();
     for (_, aabb, _) in face_data.iter() {
         bounds.include_point(&aabb.min);
         bounds.include_point(&aabb.max);
@@ -329,8 +329,8 @@ fn build_node(face_data: &mut [(FaceId, Aabb3, vcad_kernel_math::Point3)]) -> Bv
 
 /// Find the best split axis and position using SAH.
 fn find_best_split(
-    face_data: &[(FaceId, Aabb3, vcad_kernel_math::Point3)],
-    bounds: &Aabb3,
+    face_data: &[(FaceId, Aabb, vcad_kernel_math::Point3)],
+    bounds: &Aabb,
 ) -> (usize, f64) {
     const NUM_BUCKETS: usize = 12;
 
@@ -364,7 +364,7 @@ fn find_best_split(
 
         // Initialize buckets
         let mut bucket_counts = [0usize; NUM_BUCKETS];
-        let mut bucket_bounds = [Aabb3::empty(); NUM_BUCKETS];
+        let mut bucket_bounds = [Aabb::empty(); NUM_BUCKETS];
 
         // Assign faces to buckets
         for (_, aabb, centroid) in face_data {
@@ -385,7 +385,7 @@ fn find_best_split(
         // Sweep to find best split
         for split in 1..NUM_BUCKETS {
             let mut left_count = 0;
-            let mut left_bounds = Aabb3::empty();
+            let mut left_bounds = Aabb::empty();
             for i in 0..split {
                 left_count += bucket_counts[i];
                 if bucket_counts[i] > 0 {
@@ -395,7 +395,7 @@ fn find_best_split(
             }
 
             let mut right_count = 0;
-            let mut right_bounds = Aabb3::empty();
+            let mut right_bounds = Aabb::empty();
             for i in split..NUM_BUCKETS {
                 right_count += bucket_counts[i];
                 if bucket_counts[i] > 0 {
@@ -430,7 +430,7 @@ fn find_best_split(
 
 /// Partition faces by centroid along an axis.
 fn partition_faces(
-    face_data: &mut [(FaceId, Aabb3, vcad_kernel_math::Point3)],
+    face_data: &mut [(FaceId, Aabb, vcad_kernel_math::Point3)],
     axis: usize,
     pos: f64,
 ) -> usize {
@@ -456,7 +456,7 @@ fn partition_faces(
 }
 
 /// Compute surface area of an AABB.
-fn surface_area(aabb: &Aabb3) -> f64 {
+fn surface_area(aabb: &Aabb) -> f64 {
     let d = vcad_kernel_math::Vec3::new(
         aabb.max.x - aabb.min.x,
         aabb.max.y - aabb.min.y,
@@ -1,6 +1,6 @@
 //! Ray representation and basic ray-geometry tests.
 
-use vcad_kernel_booleans::bbox::Aabb3;
+use vcad_kernel_booleans::bbox::Aabb;
 use vcad_kernel_math::{Dir3, Point2, Point3, Vec3};
 use vcad_kernel_topo::FaceId;
 
@@ -55,7 +55,7 @@ impl Ray {
     ///
     /// Handles infinite values correctly for axis-aligned rays.
     #[inline]
-    pub fn intersect_aabb(&self, aabb: &Aabb3) -> Option<(f64, f64)> {
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<(f64, f64)> {
         let bounds = [aabb.min, aabb.max];
 
         let tx1 = (bounds[self.sign[0]].x - self.origin.x) * self.inv_direction.x;
@@ -128,7 +128,7 @@ mod tests {
             Point3::new(-5.0, 0.5, 0.5),
             Vec3::new(1.0, 0.0, 0.0),
         );
-        let aabb = Aabb3::new(
+        let aabb = Aabb::new(
             Point3::new(0.0, 0.0, 0.0),
             Point3::new(1.0, 1.0, 1.0),
         );
@@ -145,7 +145,7 @@ mod tests {
             Point3::new(-5.0, 5.0, 5.0),
             Vec3::new(1.0, 0.0, 0.0),
         );
-        let aabb = Aabb3::new(
+        let aabb = Aabb::new(
             Point3::new(0.0, 0.0, 0.0),
             Point3::new(1.0, 1.0, 1.0),
         );
@@ -160,7 +160,7 @@ mod tests {
             Point3::new(0.5, 0.5, 0.5),
             Vec3::new(1.0, 0.0, 0.0),
         );
-        let aabb = Aabb3::new(
+        let aabb = Aabb::new(
             Point3::new(0.0, 0.0, 0.0),
             Point3::new(1.0, 1.0, 1.0),
         );
@@ -177,7 +177,7 @@ mod tests {
             Point3::new(-1.0, -1.0, -1.0),
             Vec3::new(1.0, 1.0, 1.0),
         );
-        let aabb = Aabb3::new(
+        let aabb = Aabb::new(
             Point3::new(0.0, 0.0, 0.0),
             Point3::new(1.0, 1.0, 1.0),
         );
@@ -192,7 +192,7 @@ mod tests {
             Point3::new(-5.0, 0.5, 0.5),
             Vec3::new(-1.0, 0.0, 0.0),
         );
-        let aabb = Aabb3::new(
+        let aabb = Aabb::new(
             Point3::new(0.0, 0.0, 0.0),
             Point3::new(1.0, 1.0, 1.0),
         );
@@ -17,10 +17,17 @@
 //! For B-rep solids with planar faces only:
 //! - Each face is offset by translating along its normal
 //! - The resulting inner shell is connected to the outer shell
+//!
+//! [`minkowski_sphere_mesh`] offsets outward instead, rounding every convex
+//! edge and vertex.
+
+mod minkowski;
+
+pub use minkowski::minkowski_sphere_mesh;
 
 use std::collections::HashMap;
 use vcad_kernel_geom::{GeometryStore, Plane};
-use vcad_kernel_math::{Point3, Vec3};
+use vcad_kernel_math::{Point3, Quantize};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_tessellate::TriangleMesh;
 use vcad_kernel_topo::{HalfEdgeId, Orientation, ShellType, Topology, VertexId};
@@ -78,7 +85,7 @@ pub fn shell_mesh(mesh: &TriangleMesh, thickness: f64) -> TriangleMesh {
     let num_verts = mesh.vertices.len() / 3;
 
     // Step 1: Compute vertex normals (average of adjacent face normals)
-    let vertex_normals = compute_vertex_normals(mesh);
+    let vertex_normals = mesh.vertex_normals();
 
     // Step 2: Create offset (inner) vertices
     let mut inner_vertices = Vec::with_capacity(num_verts * 3);
@@ -120,73 +127,15 @@ pub fn shell_mesh(mesh: &TriangleMesh, thickness: f64) -> TriangleMesh {
     }
 }
 
-/// Compute vertex normals as the average of adjacent face normals.
-fn compute_vertex_normals(mesh: &TriangleMesh) -> Vec<f64> {
-    let num_verts = mesh.vertices.len() / 3;
-    let mut normals = vec![0.0_f64; num_verts * 3];
-    let mut counts = vec![0_u32; num_verts];
-
-    // Accumulate face normals at each vertex
-    for tri in mesh.indices.chunks(3) {
-        let i0 = tri[0] as usize;
-        let i1 = tri[1] as usize;
-        let i2 = tri[2] as usize;
-
-        let v0 = Vec3::new(
-            mesh.vertices[i0 * 3] as f64,
-            mesh.vertices[i0 * 3 + 1] as f64,
-            mesh.vertices[i0 * 3 + 2] as f64,
-        );
-        let v1 = Vec3::new(
-            mesh.vertices[i1 * 3] as f64,
-            mesh.vertices[i1 * 3 + 1] as f64,
-            mesh.vertices[i1 * 3 + 2] as f64,
-        );
-        let v2 = Vec3::new(
-            mesh.vertices[i2 * 3] as f64,
-            mesh.vertices[i2 * 3 + 1] as f64,
-            mesh.vertices[i2 * 3 + 2] as f64,
-        );
-
-        let e1 = v1 - v0;
-        let e2 = v2 - v0;
-        let face_normal = e1.cross(&e2);
-
-        // Add to each vertex's normal
-        for &idx in &[i0, i1, i2] {
-            normals[idx * 3] += face_normal.x;
-            normals[idx * 3 + 1] += face_normal.y;
-            normals[idx * 3 + 2] += face_normal.z;
-            counts[idx] += 1;
-        }
-    }
-
-    // Normalize
-    for i in 0..num_verts {
-        let nx = normals[i * 3];
-        let ny = normals[i * 3 + 1];
-        let nz = normals[i * 3 + 2];
-        let len = (nx * nx + ny * ny + nz * nz).sqrt();
-        if len > 1e-12 {
-            normals[i * 3] = nx / len;
-            normals[i * 3 + 1] = ny / len;
-            normals[i * 3 + 2] = nz / len;
-        } else {
-            // Default to Z-up if degenerate
-            normals[i * 3] = 0.0;
-            normals[i * 3 + 1] = 0.0;
-            normals[i * 3 + 2] = 1.0;
-        }
-    }
-
-    normals
-}
+/// Grid steps per mm used to weld coincident mesh vertices.
+const WELD_SCALE: f64 = 1e6;
 
 /// Convert a triangle mesh to a B-rep solid.
 ///
 /// Creates a simple B-rep with one planar face per triangle.
-/// This is a minimal representation for mesh-based results.
-fn mesh_to_brep(mesh: &TriangleMesh) -> BRepSolid {
+/// This is a minimal representation for mesh-based results; an empty mesh
+/// yields a solid with a single empty shell.
+pub fn mesh_to_brep(mesh: &TriangleMesh) -> BRepSolid {
     let mut topo = Topology::new();
     let mut geom = GeometryStore::new();
 
@@ -195,12 +144,9 @@ fn mesh_to_brep(mesh: &TriangleMesh) -> BRepSolid {
 
     let get_or_create_vertex =
         |cache: &mut HashMap<[i64; 3], VertexId>, topo: &mut Topology, pos: Point3| -> VertexId {
-            let key = [
-                (pos.x * 1e6).round() as i64,
-                (pos.y * 1e6).round() as i64,
-                (pos.z * 1e6).round() as i64,
-            ];
-            *cache.entry(key).or_insert_with(|| topo.add_vertex(pos))
+            *cache
+                .entry(pos.quantize(WELD_SCALE))
+                .or_insert_with(|| topo.add_vertex(pos))
         };
 
     let mut all_faces = Vec::new();
@@ -255,11 +201,7 @@ fn mesh_to_brep(mesh: &TriangleMesh) -> BRepSolid {
     let shell = topo.add_shell(all_faces, ShellType::Outer);
     let solid_id = topo.add_solid(shell);
 
-    BRepSolid {
-        topology: topo,
-        geometry: geom,
-        solid_id,
-    }
+    BRepSolid::new(topo, geom, solid_id)
 }
 
 /// Pair twin half-edges by matching (origin, destination) vertex pairs.
@@ -276,16 +218,8 @@ fn pair_twin_half_edges(topo: &mut Topology) {
         };
         let dest = topo.vertices[topo.half_edges[next].origin].point;
 
-        let origin_key = [
-            (origin.x * 1e6).round() as i64,
-            (origin.y * 1e6).round() as i64,
-            (origin.z * 1e6).round() as i64,
-        ];
-        let dest_key = [
-            (dest.x * 1e6).round() as i64,
-            (dest.y * 1e6).round() as i64,
-            (dest.z * 1e6).round() as i64,
-        ];
+        let origin_key = origin.quantize(WELD_SCALE);
+        let dest_key = dest.quantize(WELD_SCALE);
 
         if let Some(&twin_id) = he_map.get(&(dest_key, origin_key)) {
             if topo.half_edges[*he_id].twin.is_none() && topo.half_edges[twin_id].twin.is_none() {
@@ -0,0 +1,364 @@
+//! Minkowski sum of a triangle mesh with a sphere.
+//!
+//! The boundary of the sum is the set of points at distance `radius` from
+//! the solid. For a closed mesh it is assembled from three kinds of patches:
+//! each triangle translated along its normal, a cylindrical strip along each
+//! edge sweeping between the two face normals, and a spherical patch at each
+//! vertex covering the normals of its incident faces. Patches share vertices
+//! by construction, so a closed, consistently wound input gives a closed
+//! output.
+
+use std::collections::HashMap;
+
+use vcad_kernel_math::{Point3, Quantize, Vec3};
+use vcad_kernel_tessellate::TriangleMesh;
+
+use crate::WELD_SCALE;
+
+/// Face normals closer than this (in radians) are treated as equal, so flat
+/// regions of the input don't produce zero-width strips.
+const FLAT_ANGLE: f64 = 1e-6;
+
+/// An edge strip between two adjacent triangles.
+struct Strip {
+    /// Output points along the arc at each end of the edge, from the
+    /// triangle holding the edge as `a -> b` to its twin.
+    arc_a: Vec<usize>,
+    arc_b: Vec<usize>,
+}
+
+/// Offset a closed triangle mesh by a sphere of `radius`.
+///
+/// `segments` is the number of steps in a full turn used to round edges and
+/// corners. Convex edges become cylindrical strips and convex vertices
+/// spherical patches, so a box turns into a rounded box grown by `radius` on
+/// every side. The result is exact (up to tessellation) for convex solids;
+/// at concave edges the offset faces overlap, so the output self-intersects
+/// there.
+///
+/// Open edges are left unrounded. Returns the mesh unchanged if `radius` is
+/// not positive.
+pub fn minkowski_sphere_mesh(mesh: &TriangleMesh, radius: f64, segments: u32) -> TriangleMesh {
+    if radius <= 0.0 || mesh.indices.is_empty() {
+        return mesh.clone();
+    }
+    let step = std::f64::consts::TAU / segments.max(3) as f64;
+
+    // Weld coincident corners so faces from different B-rep patches connect.
+    let mut lookup: HashMap<[i64; 3], usize> = HashMap::new();
+    let mut positions: Vec<Point3> = Vec::new();
+    let mut welded = Vec::with_capacity(mesh.vertices.len() / 3);
+    for v in mesh.vertices.chunks_exact(3) {
+        let p = Point3::new(v[0] as f64, v[1] as f64, v[2] as f64);
+        let index = *lookup.entry(p.quantize(WELD_SCALE)).or_insert_with(|| {
+            positions.push(p);
+            positions.len() - 1
+        });
+        welded.push(index);
+    }
+    let mut triangles: Vec<[usize; 3]> = Vec::new();
+    let mut normals: Vec<Vec3> = Vec::new();
+    for tri in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|k| welded[tri[k] as usize]);
+        let n = (positions[b] - positions[a]).cross(&(positions[c] - positions[a]));
+        if a == b || b == c || c == a || n.norm() < 1e-12 {
+            continue;
+        }
+        triangles.push([a, b, c]);
+        normals.push(n.normalize());
+    }
+
+    let mut directed: HashMap<(usize, usize), usize> = HashMap::new();
+    for (t, tri) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            directed.insert((tri[k], tri[(k + 1) % 3]), t);
+        }
+    }
+
+    // Output points with their sphere directions. The first three per
+    // triangle are its offset corners.
+    let mut points: Vec<(Point3, Vec3)> = Vec::with_capacity(triangles.len() * 3);
+    for (tri, n) in triangles.iter().zip(&normals) {
+        for &v in tri {
+            points.push((positions[v] + radius * n, *n));
+        }
+    }
+    let corner = |t: usize, v: usize| 3 * t + triangles[t].iter().position(|&x| x == v).unwrap();
+    let mut parent: Vec<usize> = (0..points.len()).collect();
+
+    let mut strips: HashMap<(usize, usize), Strip> = HashMap::new();
+    for (t, tri) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            let Some(&twin) = directed.get(&(b, a)) else {
+                continue;
+            };
+            if strips.contains_key(&(b, a)) {
+                continue;
+            }
+            let (n1, n2) = (normals[t], normals[twin]);
+            let angle = n1.dot(&n2).clamp(-1.0, 1.0).acos();
+            let steps = if angle < FLAT_ANGLE {
+                union(&mut parent, corner(t, a), corner(twin, a));
+                union(&mut parent, corner(t, b), corner(twin, b));
+                1
+            } else {
+                (angle / step).ceil() as usize
+            };
+            let mut arc = |v: usize| {
+                let mut arc = vec![corner(t, v)];
+                for j in 1..steps {
+                    let dir = slerp(&n1, &n2, j as f64 / steps as f64);
+                    arc.push(push_point(
+                        &mut points,
+                        &mut parent,
+                        positions[v],
+                        dir,
+                        radius,
+                    ));
+                }
+                arc.push(corner(twin, v));
+                arc
+            };
+            let strip = Strip {
+                arc_a: arc(a),
+                arc_b: arc(b),
+            };
+            strips.insert((a, b), strip);
+        }
+    }
+
+    let mut out: Vec<[usize; 3]> = Vec::new();
+    for (t, tri) in triangles.iter().enumerate() {
+        out.push(tri.map(|v| corner(t, v)));
+    }
+    for strip in strips.values() {
+        let (sa, sb) = (&strip.arc_a, &strip.arc_b);
+        for j in 0..sa.len() - 1 {
+            out.push([sb[j], sa[j], sa[j + 1]]);
+            out.push([sb[j], sa[j + 1], sb[j + 1]]);
+        }
+    }
+
+    // Corner patches. Walking the triangles around a vertex counterclockwise
+    // (seen from outside) and following each strip's arc between them gives
+    // the patch boundary in the same winding.
+    let mut start_of: Vec<Option<usize>> = vec![None; positions.len()];
+    for (t, tri) in triangles.iter().enumerate() {
+        for &v in tri {
+            start_of[v].get_or_insert(t);
+        }
+    }
+    for (v, start) in start_of.into_iter().enumerate() {
+        let Some(start) = start else { continue };
+        let Some(ring) = vertex_ring(v, start, &triangles, &directed, &strips) else {
+            continue;
+        };
+        let mut ring: Vec<usize> = ring.into_iter().map(|i| find(&mut parent, i)).collect();
+        ring.dedup();
+        while ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        if ring.len() < 3 {
+            continue;
+        }
+        corner_patch(
+            v,
+            &ring,
+            &positions,
+            radius,
+            step,
+            &mut points,
+            &mut parent,
+            &mut out,
+        );
+    }
+
+    // Compact the surviving points and drop triangles collapsed by welding.
+    let mut remap: Vec<Option<u32>> = vec![None; points.len()];
+    let mut result = TriangleMesh::new();
+    for tri in out {
+        let tri = tri.map(|i| find(&mut parent, i));
+        if tri[0] == tri[1] || tri[1] == tri[2] || tri[2] == tri[0] {
+            continue;
+        }
+        for i in tri {
+            let index = *remap[i].get_or_insert_with(|| {
+                let (p, n) = points[i];
+                result.vertices.extend([p.x as f32, p.y as f32, p.z as f32]);
+                result.normals.extend([n.x as f32, n.y as f32, n.z as f32]);
+                (result.vertices.len() / 3 - 1) as u32
+            });
+            result.indices.push(index);
+        }
+    }
+    result
+}
+
+/// Boundary of the corner patch at vertex `v`, or `None` if the triangles
+/// around it don't close into a fan (open or non-manifold mesh).
+fn vertex_ring(
+    v: usize,
+    start: usize,
+    triangles: &[[usize; 3]],
+    directed: &HashMap<(usize, usize), usize>,
+    strips: &HashMap<(usize, usize), Strip>,
+) -> Option<Vec<usize>> {
+    let mut ring = Vec::new();
+    let mut t = start;
+    for _ in 0..triangles.len() {
+        let tri = triangles[t];
+        let k = tri.iter().position(|&x| x == v)?;
+        let c = tri[(k + 2) % 3];
+        // The next triangle counterclockwise holds the twin of `c -> v`.
+        let next = *directed.get(&(v, c))?;
+        let arc = match strips.get(&(c, v)) {
+            // `t` holds `c -> v`: walk this end of the arc forwards
+            Some(strip) => strip.arc_b.clone(),
+            None => {
+                let strip = strips.get(&(v, c))?;
+                strip.arc_a.iter().rev().copied().collect()
+            }
+        };
+        ring.extend_from_slice(&arc[..arc.len() - 1]);
+        t = next;
+        if t == start {
+            return Some(ring);
+        }
+    }
+    None
+}
+
+/// Triangulate the spherical patch at vertex `v` bounded by `ring`, adding
+/// concentric rings of points toward its center so no triangle spans more
+/// than about one `step` of arc.
+#[allow(clippy::too_many_arguments)]
+fn corner_patch(
+    v: usize,
+    ring: &[usize],
+    positions: &[Point3],
+    radius: f64,
+    step: f64,
+    points: &mut Vec<(Point3, Vec3)>,
+    parent: &mut Vec<usize>,
+    out: &mut Vec<[usize; 3]>,
+) {
+    let dirs: Vec<Vec3> = ring.iter().map(|&i| points[i].1).collect();
+    let sum: Vec3 = dirs.iter().sum();
+    let center = if sum.norm() > 1e-9 {
+        sum.normalize()
+    } else {
+        dirs[0]
+    };
+    let widest = dirs
+        .iter()
+        .map(|d| center.dot(d).clamp(-1.0, 1.0).acos())
+        .fold(0.0, f64::max);
+    let levels = ((widest / step).ceil() as usize).max(1);
+
+    let apex = push_point(points, parent, positions[v], center, radius);
+    let mut inner: Option<Vec<usize>> = None;
+    for level in 1..=levels {
+        let current: Vec<usize> = if level == levels {
+            ring.to_vec()
+        } else {
+            let t = level as f64 / levels as f64;
+            dirs.iter()
+                .map(|d| push_point(points, parent, positions[v], slerp(&center, d, t), radius))
+                .collect()
+        };
+        let n = current.len();
+        for i in 0..n {
+            let j = (i + 1) % n;
+            match &inner {
+                None => out.push([apex, current[i], current[j]]),
+                Some(prev) => {
+                    out.push([prev[i], current[i], current[j]]);
+                    out.push([prev[i], current[j], prev[j]]);
+                }
+            }
+        }
+        inner = Some(current);
+    }
+}
+
+fn push_point(
+    points: &mut Vec<(Point3, Vec3)>,
+    parent: &mut Vec<usize>,
+    origin: Point3,
+    dir: Vec3,
+    radius: f64,
+) -> usize {
+    points.push((origin + radius * dir, dir));
+    parent.push(parent.len());
+    points.len() - 1
+}
+
+/// Spherical interpolation between unit vectors.
+fn slerp(a: &Vec3, b: &Vec3, t: f64) -> Vec3 {
+    let angle = a.dot(b).clamp(-1.0, 1.0).acos();
+    if angle < FLAT_ANGLE || std::f64::consts::PI - angle < FLAT_ANGLE {
+        return (a * (1.0 - t) + b * t).try_normalize(1e-12).unwrap_or(*a);
+    }
+    (a * ((1.0 - t) * angle).sin() + b * (t * angle).sin()) / angle.sin()
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parent, a), find(parent, b));
+    if ra != rb {
+        parent[rb.max(ra)] = rb.min(ra);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube_mesh(size: f64) -> TriangleMesh {
+        let cube = vcad_kernel_primitives::make_cube(size, size, size);
+        vcad_kernel_tessellate::tessellate_brep(&cube, 32)
+    }
+
+    #[test]
+    fn test_rounded_cube() {
+        let rounded = minkowski_sphere_mesh(&cube_mesh(10.0), 2.0, 32);
+        assert!(rounded.is_watertight());
+
+        let mut min = [f64::MAX; 3];
+        let mut max = [f64::MIN; 3];
+        for v in rounded.vertices.chunks_exact(3) {
+            let p = [v[0] as f64, v[1] as f64, v[2] as f64];
+            // Distance from the cube: every output point lies on the offset
+            // surface, including at the rounded corners.
+            let outside: f64 = p
+                .iter()
+                .map(|&c| (-c).max(c - 10.0).max(0.0).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            assert!((outside - 2.0).abs() < 1e-4, "point {p:?} at {outside}");
+            for k in 0..3 {
+                min[k] = min[k].min(p[k]);
+                max[k] = max[k].max(p[k]);
+            }
+        }
+        for k in 0..3 {
+            assert!((min[k] + 2.0).abs() < 1e-4);
+            assert!((max[k] - 12.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_non_positive_radius_is_identity() {
+        let mesh = cube_mesh(5.0);
+        let same = minkowski_sphere_mesh(&mesh, 0.0, 16);
+        assert_eq!(same.indices, mesh.indices);
+    }
+}
@@ -0,0 +1,404 @@
+//! 2D boolean operations on sketch profiles.
+//!
+//! Both operands are flattened to polygons (arcs become chords within a
+//! micrometer of the true curve), every edge is split where it meets the other
+//! polygon, and the resulting fragments are kept or dropped depending on
+//! which side of the other polygon they lie. The kept fragments are then
+//! linked back into loops. Results are line-only profiles whose holes are
+//! wound opposite to their outer loop.
+
+use std::collections::{HashMap, HashSet};
+
+use vcad_kernel_math::intersect::{segment_intersect_2d, SegIntersection};
+use vcad_kernel_math::{Point2, Tolerance, Vec2};
+
+use crate::profile::FLATTEN_TOLERANCE;
+use crate::{SketchProfile, SketchSegment};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Union,
+    Difference,
+    Intersection,
+}
+
+impl SketchProfile {
+    /// The region covered by either profile.
+    ///
+    /// `other` is mapped onto this profile's plane, which it is assumed to
+    /// share. Overlapping profiles merge into one; disjoint ones come back
+    /// as separate profiles.
+    pub fn union(&self, other: &SketchProfile) -> Vec<SketchProfile> {
+        self.boolean(other, Op::Union)
+    }
+
+    /// The region of this profile not covered by `other`.
+    ///
+    /// `other` lying strictly inside this profile becomes a hole.
+    pub fn difference(&self, other: &SketchProfile) -> Vec<SketchProfile> {
+        self.boolean(other, Op::Difference)
+    }
+
+    /// The region covered by both profiles.
+    pub fn intersection(&self, other: &SketchProfile) -> Vec<SketchProfile> {
+        self.boolean(other, Op::Intersection)
+    }
+
+    fn boolean(&self, other: &SketchProfile, op: Op) -> Vec<SketchProfile> {
+        let a = self.flattened_loops(|p| p);
+        let b = other.flattened_loops(|p| self.to_2d(other.to_3d(p)));
+
+        let mut pool = VertexPool::default();
+        let frags_a = split_edges(&a, &b, &mut pool);
+        let frags_b = split_edges(&b, &a, &mut pool);
+        let set_a: HashSet<(usize, usize)> = frags_a.iter().copied().collect();
+        let set_b: HashSet<(usize, usize)> = frags_b.iter().copied().collect();
+
+        let mut kept = Vec::new();
+        for &(s, e) in &frags_a {
+            let keep = if set_b.contains(&(s, e)) {
+                op != Op::Difference
+            } else if set_b.contains(&(e, s)) {
+                op == Op::Difference
+            } else {
+                let inside = contains(&b, midpoint(&pool, s, e));
+                inside == (op == Op::Intersection)
+            };
+            if keep {
+                kept.push((s, e));
+            }
+        }
+        for &(s, e) in &frags_b {
+            // Shared fragments were decided above
+            if set_a.contains(&(s, e)) || set_a.contains(&(e, s)) {
+                continue;
+            }
+            let inside = contains(&a, midpoint(&pool, s, e));
+            match op {
+                Op::Union if !inside => kept.push((s, e)),
+                Op::Intersection if inside => kept.push((s, e)),
+                Op::Difference if inside => kept.push((e, s)),
+                _ => {}
+            }
+        }
+
+        let loops: Vec<Vec<Point2>> = link_loops(&kept, &pool)
+            .into_iter()
+            .map(|l| simplify(l.iter().map(|&i| pool.points[i]).collect()))
+            .filter(|l| l.len() >= 3 && area(l).abs() > Tolerance::DEFAULT.linear.powi(2))
+            .collect();
+        let (outers, holes): (Vec<_>, Vec<_>) = loops.into_iter().partition(|l| area(l) > 0.0);
+
+        let mut profiles: Vec<SketchProfile> = outers
+            .iter()
+            .map(|l| SketchProfile {
+                origin: self.origin,
+                x_dir: self.x_dir,
+                y_dir: self.y_dir,
+                normal: self.normal,
+                segments: to_segments(l),
+                holes: Vec::new(),
+            })
+            .collect();
+        for hole in &holes {
+            // The smallest outer loop around the hole owns it
+            let probe = midpoint_of(hole[0], hole[1]);
+            let owner = (0..outers.len())
+                .filter(|&k| contains(&outers[k..=k], probe))
+                .min_by(|&i, &j| area(&outers[i]).total_cmp(&area(&outers[j])));
+            if let Some(k) = owner {
+                profiles[k].holes.push(to_segments(hole));
+            }
+        }
+        profiles
+    }
+
+    /// All loops as polygons, outer loop counter-clockwise and holes
+    /// clockwise, with `map` applied to every point.
+    fn flattened_loops(&self, map: impl Fn(Point2) -> Point2) -> Vec<Vec<Point2>> {
+        let mut loops: Vec<Vec<Point2>> = self
+            .loops()
+            .map(|segments| {
+                segments
+                    .iter()
+                    .flat_map(|s| s.flatten(FLATTEN_TOLERANCE))
+                    .map(&map)
+                    .collect()
+            })
+            .collect();
+        if loops.first().is_some_and(|outer| area(outer) < 0.0) {
+            for l in &mut loops {
+                l.reverse();
+            }
+        }
+        loops
+    }
+}
+
+/// Points shared between both operands, merged within tolerance so that
+/// fragments meeting at a point refer to the same index.
+#[derive(Default)]
+struct VertexPool {
+    points: Vec<Point2>,
+}
+
+impl VertexPool {
+    fn insert(&mut self, p: Point2) -> usize {
+        let tol = Tolerance::DEFAULT.linear;
+        if let Some(i) = self.points.iter().position(|q| (q - p).norm() < tol) {
+            return i;
+        }
+        self.points.push(p);
+        self.points.len() - 1
+    }
+}
+
+/// Split every edge of `loops` where it touches or crosses an edge of
+/// `others`, returning directed fragments as pairs of pool indices.
+fn split_edges(
+    loops: &[Vec<Point2>],
+    others: &[Vec<Point2>],
+    pool: &mut VertexPool,
+) -> Vec<(usize, usize)> {
+    let tol = Tolerance::DEFAULT.linear;
+    let other_edges: Vec<(Point2, Point2)> = others.iter().flat_map(|l| edges(l)).collect();
+
+    let mut fragments = Vec::new();
+    for l in loops {
+        for (p0, p1) in edges(l) {
+            let d = p1 - p0;
+            let len2 = d.norm_squared();
+            let param = |p: Point2| (p - p0).dot(&d) / len2;
+
+            let mut cuts = vec![(0.0, pool.insert(p0)), (1.0, pool.insert(p1))];
+            for &(q0, q1) in &other_edges {
+                // Vertices of the other polygon lying on this edge
+                for q in [q0, q1] {
+                    let t = param(q);
+                    if t > 0.0 && t < 1.0 && (p0 + t * d - q).norm() < tol {
+                        cuts.push((t, pool.insert(q)));
+                    }
+                }
+                // Proper crossings; touching ends are the vertices above
+                if let SegIntersection::Point { point, t, u } =
+                    segment_intersect_2d(&p0, &p1, &q0, &q1)
+                {
+                    if t > 0.0 && t < 1.0 && u > 0.0 && u < 1.0 {
+                        cuts.push((t, pool.insert(point)));
+                    }
+                }
+            }
+
+            cuts.sort_by(|x, y| x.0.total_cmp(&y.0));
+            cuts.dedup_by_key(|c| c.1);
+            fragments.extend(cuts.windows(2).map(|w| (w[0].1, w[1].1)));
+        }
+    }
+    fragments.retain(|(s, e)| s != e);
+    fragments
+}
+
+/// Chain directed fragments into closed loops of pool indices.
+///
+/// Where several fragments leave the same vertex, the walk takes the
+/// sharpest left turn, so regions touching at a point stay separate loops.
+fn link_loops(fragments: &[(usize, usize)], pool: &VertexPool) -> Vec<Vec<usize>> {
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (k, &(s, _)) in fragments.iter().enumerate() {
+        outgoing.entry(s).or_default().push(k);
+    }
+    let dir = |k: usize| {
+        let (s, e) = fragments[k];
+        pool.points[e] - pool.points[s]
+    };
+
+    let mut used = vec![false; fragments.len()];
+    let mut loops = Vec::new();
+    for first in 0..fragments.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let start = fragments[first].0;
+        let mut chain = vec![start];
+        let mut current = first;
+        loop {
+            let vertex = fragments[current].1;
+            if vertex == start {
+                loops.push(chain);
+                break;
+            }
+            chain.push(vertex);
+            let incoming = dir(current);
+            let next = outgoing.get(&vertex).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|&k| !used[k])
+                    .max_by(|&i, &j| turn(incoming, dir(i)).total_cmp(&turn(incoming, dir(j))))
+            });
+            // An open chain means inconsistent input; drop it
+            let Some(next) = next else { break };
+            used[next] = true;
+            current = next;
+        }
+    }
+    loops
+}
+
+/// Drop vertices where the loop continues straight on.
+fn simplify(mut points: Vec<Point2>) -> Vec<Point2> {
+    let tol = Tolerance::DEFAULT.linear;
+    let mut i = 0;
+    while points.len() >= 3 && i < points.len() {
+        let n = points.len();
+        let (prev, p, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+        let (d0, d1) = (p - prev, next - p);
+        if cross(d0, d1).abs() <= tol * d0.norm().max(d1.norm()) && d0.dot(&d1) > 0.0 {
+            points.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    points
+}
+
+fn to_segments(points: &[Point2]) -> Vec<SketchSegment> {
+    edges(points)
+        .map(|(start, end)| SketchSegment::Line { start, end })
+        .collect()
+}
+
+fn edges(points: &[Point2]) -> impl Iterator<Item = (Point2, Point2)> + '_ {
+    let n = points.len();
+    (0..n).map(move |i| (points[i], points[(i + 1) % n]))
+}
+
+/// Even-odd point containment over a set of loops.
+fn contains(loops: &[Vec<Point2>], p: Point2) -> bool {
+    let mut inside = false;
+    for (a, b) in loops.iter().flat_map(|l| edges(l)) {
+        if (a.y > p.y) != (b.y > p.y) && p.x < (b.x - a.x) * (p.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+fn area(points: &[Point2]) -> f64 {
+    edges(points)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<f64>()
+        / 2.0
+}
+
+fn midpoint(pool: &VertexPool, s: usize, e: usize) -> Point2 {
+    midpoint_of(pool.points[s], pool.points[e])
+}
+
+fn midpoint_of(a: Point2, b: Point2) -> Point2 {
+    Point2::from((a.coords + b.coords) / 2.0)
+}
+
+fn cross(a: Vec2, b: Vec2) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Signed angle from `from` to `to`, positive to the left.
+fn turn(from: Vec2, to: Vec2) -> f64 {
+    cross(from, to).atan2(from.dot(&to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+    use vcad_kernel_math::{Point3, Vec3};
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> SketchProfile {
+        SketchProfile::rectangle(
+            Point3::new(x0, y0, 0.0),
+            Vec3::x(),
+            Vec3::y(),
+            x1 - x0,
+            y1 - y0,
+        )
+    }
+
+    /// Area of a result, holes subtracted.
+    fn net_area(p: &SketchProfile) -> f64 {
+        p.loops()
+            .map(|l| area(&l.iter().map(|s| s.start()).collect::<Vec<_>>()))
+            .sum()
+    }
+
+    #[test]
+    fn test_union_plus() {
+        let result = rect(0.0, 10.0, 30.0, 20.0).union(&rect(10.0, 0.0, 20.0, 30.0));
+        assert_eq!(result.len(), 1);
+        let plus = &result[0];
+        assert_eq!(plus.segments.len(), 12);
+        assert!(plus.holes.is_empty());
+        assert!(plus.is_closed());
+        assert!((net_area(plus) - 500.0).abs() < 1e-9);
+        plus.validate().unwrap();
+    }
+
+    #[test]
+    fn test_difference_notch() {
+        // Points are in the first profile's plane: the second rectangle
+        // starts at (4, 8) in world space, which is also local (4, 8)
+        let result = rect(0.0, 0.0, 10.0, 10.0).difference(&rect(4.0, 8.0, 6.0, 12.0));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].segments.len(), 8);
+        assert!((net_area(&result[0]) - 96.0).abs() < 1e-9);
+        result[0].validate().unwrap();
+    }
+
+    #[test]
+    fn test_difference_inside_becomes_hole() {
+        let result = rect(0.0, 0.0, 10.0, 10.0).difference(&rect(3.0, 3.0, 7.0, 7.0));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].holes.len(), 1);
+        assert!((net_area(&result[0]) - 84.0).abs() < 1e-9);
+        result[0].validate().unwrap();
+
+        // Removing everything leaves nothing
+        assert!(rect(3.0, 3.0, 7.0, 7.0)
+            .difference(&rect(0.0, 0.0, 10.0, 10.0))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_intersection() {
+        let result = rect(0.0, 0.0, 10.0, 10.0).intersection(&rect(5.0, 5.0, 15.0, 15.0));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].segments.len(), 4);
+        assert!((net_area(&result[0]) - 25.0).abs() < 1e-9);
+
+        let apart = rect(0.0, 0.0, 1.0, 1.0).intersection(&rect(5.0, 5.0, 6.0, 6.0));
+        assert!(apart.is_empty());
+    }
+
+    #[test]
+    fn test_union_shared_edge_and_disjoint() {
+        let result = rect(0.0, 0.0, 10.0, 10.0).union(&rect(10.0, 0.0, 20.0, 10.0));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].segments.len(), 4);
+        assert!((net_area(&result[0]) - 200.0).abs() < 1e-9);
+
+        let result = rect(0.0, 0.0, 1.0, 1.0).union(&rect(5.0, 5.0, 6.0, 6.0));
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn test_arcs_are_flattened() {
+        let circle = SketchProfile::circle(Point3::new(5.0, 5.0, 0.0), Vec3::z(), 3.0, 4);
+        let square = rect(0.0, 0.0, 10.0, 10.0);
+        let result = square.difference(&circle);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].holes.len(), 1);
+        let expected = 100.0 - PI * 9.0;
+        assert!((net_area(&result[0]) - expected).abs() < 0.05);
+    }
+}
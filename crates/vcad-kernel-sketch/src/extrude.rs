@@ -1,10 +1,11 @@
 //! Extrude operation: create a solid by sweeping a profile along a direction.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::f64::consts::PI;
 
 use vcad_kernel_geom::{BilinearSurface, GeometryStore, Plane};
-use vcad_kernel_math::{Dir3, Point2, Point3, Vec3};
+use vcad_kernel_math::{Dir3, Point2, Point3, Quantize, Vec3};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_topo::{HalfEdgeId, Orientation, ShellType, Topology, VertexId};
 
@@ -68,22 +69,17 @@ pub fn extrude(profile: &SketchProfile, direction: Vec3) -> Result<BRepSolid, Sk
     if dir_len < 1e-12 {
         return Err(SketchError::ZeroExtrusion);
     }
+    profile.validate()?;
+    let oriented = ccw_oriented(profile);
+    let profile = &*oriented;
 
     let mut topo = Topology::new();
     let mut geom = GeometryStore::new();
 
-    let n_segments = profile.segments.len();
-
     // Vertex cache: quantized position -> VertexId
     let mut vertex_cache: HashMap<[i64; 3], VertexId> = HashMap::new();
 
-    let quantize_pt = |p: Point3| -> [i64; 3] {
-        [
-            (p.x * 1e9).round() as i64,
-            (p.y * 1e9).round() as i64,
-            (p.z * 1e9).round() as i64,
-        ]
-    };
+    let quantize_pt = |p: Point3| p.quantize(1e9);
 
     let get_or_create_vertex =
         |cache: &mut HashMap<[i64; 3], VertexId>, topo: &mut Topology, pos: Point3| -> VertexId {
@@ -91,20 +87,28 @@ pub fn extrude(profile: &SketchProfile, direction: Vec3) -> Result<BRepSolid, Sk
             *cache.entry(key).or_insert_with(|| topo.add_vertex(pos))
         };
 
-    // Create bottom and top vertices for each segment endpoint
-    let mut bottom_verts: Vec<VertexId> = Vec::with_capacity(n_segments);
-    let mut top_verts: Vec<VertexId> = Vec::with_capacity(n_segments);
+    // Create bottom and top vertices for each segment endpoint, per loop
+    let mut bottom_loops: Vec<Vec<VertexId>> = Vec::new();
+    let mut top_loops: Vec<Vec<VertexId>> = Vec::new();
 
-    for seg in &profile.segments {
-        let start_2d = seg.start();
-        let start_3d = profile.to_3d(start_2d);
-        let top_3d = start_3d + direction;
+    for segments in profile.loops() {
+        let mut bottom_verts: Vec<VertexId> = Vec::with_capacity(segments.len());
+        let mut top_verts: Vec<VertexId> = Vec::with_capacity(segments.len());
 
-        let bot_v = get_or_create_vertex(&mut vertex_cache, &mut topo, start_3d);
-        let top_v = get_or_create_vertex(&mut vertex_cache, &mut topo, top_3d);
+        for seg in segments {
+            let start_2d = seg.start();
+            let start_3d = profile.to_3d(start_2d);
+            let top_3d = start_3d + direction;
 
-        bottom_verts.push(bot_v);
-        top_verts.push(top_v);
+            let bot_v = get_or_create_vertex(&mut vertex_cache, &mut topo, start_3d);
+            let top_v = get_or_create_vertex(&mut vertex_cache, &mut topo, top_3d);
+
+            bottom_verts.push(bot_v);
+            top_verts.push(top_v);
+        }
+
+        bottom_loops.push(bottom_verts);
+        top_loops.push(top_verts);
     }
 
     let mut all_faces = Vec::new();
@@ -124,48 +128,54 @@ pub fn extrude(profile: &SketchProfile, direction: Vec3) -> Result<BRepSolid, Sk
     // Winding should be: bot[i] -> bot[i+1] -> top[i+1] -> top[i]
     // This creates a face whose normal points away from the solid interior.
 
-    for (i, seg) in profile.segments.iter().enumerate() {
-        let next_i = (i + 1) % n_segments;
-
-        let bot_i = bottom_verts[i];
-        let bot_next = bottom_verts[next_i];
-        let top_i = top_verts[i];
-        let top_next = top_verts[next_i];
-
-        let bot_i_pos = topo.vertices[bot_i].point;
-        let bot_next_pos = topo.vertices[bot_next].point;
-        let top_i_pos = topo.vertices[top_i].point;
-        let top_next_pos = topo.vertices[top_next].point;
-
-        // Create lateral face with winding: bot_i -> bot_next -> top_next -> top_i
-        let (face_id, face_hes) = match seg {
-            SketchSegment::Line { .. } => build_planar_lateral_face(
-                &mut topo,
-                &mut geom,
-                bot_i,
-                bot_next,
-                top_next,
-                top_i,
-                bot_i_pos,
-                bot_next_pos,
-                top_next_pos,
-                top_i_pos,
-            ),
-            SketchSegment::Arc { center, ccw, .. } => build_cylindrical_lateral_face(
-                &mut topo, &mut geom, profile, bot_i, bot_next, top_next, top_i, *center, *ccw,
-                &direction,
-            ),
-        };
+    // Holes wind opposite to the outer loop, so the same winding points
+    // their lateral faces into the hole.
+    for ((segments, bottom_verts), top_verts) in profile.loops().zip(&bottom_loops).zip(&top_loops)
+    {
+        let n_segments = segments.len();
+        for (i, seg) in segments.iter().enumerate() {
+            let next_i = (i + 1) % n_segments;
+
+            let bot_i = bottom_verts[i];
+            let bot_next = bottom_verts[next_i];
+            let top_i = top_verts[i];
+            let top_next = top_verts[next_i];
+
+            let bot_i_pos = topo.vertices[bot_i].point;
+            let bot_next_pos = topo.vertices[bot_next].point;
+            let top_i_pos = topo.vertices[top_i].point;
+            let top_next_pos = topo.vertices[top_next].point;
+
+            // Create lateral face with winding: bot_i -> bot_next -> top_next -> top_i
+            let (face_id, face_hes) = match seg {
+                SketchSegment::Line { .. } => build_planar_lateral_face(
+                    &mut topo,
+                    &mut geom,
+                    bot_i,
+                    bot_next,
+                    top_next,
+                    top_i,
+                    bot_i_pos,
+                    bot_next_pos,
+                    top_next_pos,
+                    top_i_pos,
+                ),
+                SketchSegment::Arc { center, ccw, .. } => build_cylindrical_lateral_face(
+                    &mut topo, &mut geom, profile, bot_i, bot_next, top_next, top_i, *center, *ccw,
+                    &direction,
+                ),
+            };
 
-        all_faces.push(face_id);
+            all_faces.push(face_id);
 
-        // Record half-edges for twin pairing
-        for he_id in face_hes {
-            let he = &topo.half_edges[he_id];
-            let origin = topo.vertices[he.origin].point;
-            let next = he.next.unwrap();
-            let dest = topo.vertices[topo.half_edges[next].origin].point;
-            he_map.insert((quantize_pt(origin), quantize_pt(dest)), he_id);
+            // Record half-edges for twin pairing
+            for he_id in face_hes {
+                let he = &topo.half_edges[he_id];
+                let origin = topo.vertices[he.origin].point;
+                let next = he.next.unwrap();
+                let dest = topo.vertices[topo.half_edges[next].origin].point;
+                he_map.insert((quantize_pt(origin), quantize_pt(dest)), he_id);
+            }
         }
     }
 
@@ -176,7 +186,8 @@ pub fn extrude(profile: &SketchProfile, direction: Vec3) -> Result<BRepSolid, Sk
     let bot_cap_face_id = build_cap_face(
         &mut topo,
         &mut geom,
-        &bottom_verts,
+        &bottom_loops[0],
+        &bottom_loops[1..],
         &-*profile.normal.as_ref(),
         true, // reversed winding for outward (-direction) normal
         &mut he_map,
@@ -190,7 +201,8 @@ pub fn extrude(profile: &SketchProfile, direction: Vec3) -> Result<BRepSolid, Sk
     let top_cap_face_id = build_cap_face(
         &mut topo,
         &mut geom,
-        &top_verts,
+        &top_loops[0],
+        &top_loops[1..],
         profile.normal.as_ref(),
         false, // forward winding for outward (+direction) normal
         &mut he_map,
@@ -205,11 +217,7 @@ pub fn extrude(profile: &SketchProfile, direction: Vec3) -> Result<BRepSolid, Sk
     let shell = topo.add_shell(all_faces, ShellType::Outer);
     let solid_id = topo.add_solid(shell);
 
-    Ok(BRepSolid {
-        topology: topo,
-        geometry: geom,
-        solid_id,
-    })
+    Ok(BRepSolid::new(topo, geom, solid_id))
 }
 
 /// Extrude a closed profile with twist and/or scale (taper).
@@ -266,9 +274,9 @@ pub fn extrude_with_options(
         return Err(SketchError::ZeroExtrusion);
     }
 
-    if profile.segments.is_empty() {
-        return Err(SketchError::EmptyProfile);
-    }
+    profile.validate()?;
+    let oriented = ccw_oriented(profile);
+    let profile = &*oriented;
 
     // Calculate number of segments based on twist angle
     // ~12 segments per 90 degrees of twist, minimum 8
@@ -284,8 +292,19 @@ pub fn extrude_with_options(
     // Tessellate arcs in the profile for smooth curves
     let arc_segments = options.arc_segments.max(1) as usize;
     let tessellated_profile = profile.tessellate(arc_segments);
-    let n_profile_verts = tessellated_profile.segments.len();
-    let profile_verts_2d = tessellated_profile.vertices_2d();
+
+    // Vertices of all loops back to back; `next_vert` wraps within each loop
+    let mut profile_verts_2d: Vec<Point2> = Vec::new();
+    let mut loop_ranges = Vec::new();
+    let mut next_vert = Vec::new();
+    for segments in tessellated_profile.loops() {
+        let start = profile_verts_2d.len();
+        profile_verts_2d.extend(segments.iter().map(|seg| seg.start()));
+        let end = profile_verts_2d.len();
+        next_vert.extend((start + 1..end).chain([start]));
+        loop_ranges.push(start..end);
+    }
+    let n_profile_verts = profile_verts_2d.len();
 
     // Build a simple linear frame system for the extrusion
     // Tangent is the direction, normal/binormal are profile X/Y axes
@@ -331,18 +350,12 @@ pub fn extrude_with_options(
     let mut all_faces = Vec::new();
     let mut he_map: HashMap<([i64; 3], [i64; 3]), HalfEdgeId> = HashMap::new();
 
-    let quantize_pt = |p: Point3| -> [i64; 3] {
-        [
-            (p.x * 1e9).round() as i64,
-            (p.y * 1e9).round() as i64,
-            (p.z * 1e9).round() as i64,
-        ]
-    };
+    let quantize_pt = |p: Point3| p.quantize(1e9);
 
     // Build lateral faces (one quad per profile edge × path segment)
     for path_idx in 0..n_path_segments {
         for profile_idx in 0..n_profile_verts {
-            let next_profile_idx = (profile_idx + 1) % n_profile_verts;
+            let next_profile_idx = next_vert[profile_idx];
 
             // Quad vertices (winding for outward normal):
             // v0 (this ring, this profile) -> v1 (this ring, next profile)
@@ -386,12 +399,20 @@ pub fn extrude_with_options(
         }
     }
 
+    // Split a ring into its outer loop and holes
+    let ring_loops = |ring: &[VertexId]| -> (Vec<VertexId>, Vec<Vec<VertexId>>) {
+        let mut loops = loop_ranges.iter().map(|r| ring[r.clone()].to_vec());
+        let outer = loops.next().unwrap_or_default();
+        (outer, loops.collect())
+    };
+
     // Build start cap (first ring, reversed winding for outward normal in -direction)
-    let start_ring = &vertex_grid[0];
+    let (start_ring, start_holes) = ring_loops(&vertex_grid[0]);
     let start_face_id = build_cap_face_twisted(
         &mut topo,
         &mut geom,
-        start_ring,
+        &start_ring,
+        &start_holes,
         true,
         &mut he_map,
         quantize_pt,
@@ -399,11 +420,12 @@ pub fn extrude_with_options(
     all_faces.push(start_face_id);
 
     // Build end cap (last ring, forward winding for outward normal in +direction)
-    let end_ring = &vertex_grid[n_path_samples - 1];
+    let (end_ring, end_holes) = ring_loops(&vertex_grid[n_path_samples - 1]);
     let end_face_id = build_cap_face_twisted(
         &mut topo,
         &mut geom,
-        end_ring,
+        &end_ring,
+        &end_holes,
         false,
         &mut he_map,
         quantize_pt,
@@ -417,17 +439,24 @@ pub fn extrude_with_options(
     let shell = topo.add_shell(all_faces, ShellType::Outer);
     let solid_id = topo.add_solid(shell);
 
-    Ok(BRepSolid {
-        topology: topo,
-        geometry: geom,
-        solid_id,
-    })
+    Ok(BRepSolid::new(topo, geom, solid_id))
+}
+
+/// The profile with its outer loop wound counter-clockwise, as the
+/// extrusions assume, reversing it only when needed.
+fn ccw_oriented(profile: &SketchProfile) -> Cow<'_, SketchProfile> {
+    if profile.is_ccw() {
+        Cow::Borrowed(profile)
+    } else {
+        Cow::Owned(profile.reversed())
+    }
 }
 
 fn build_cap_face_twisted<F>(
     topo: &mut Topology,
     geom: &mut GeometryStore,
     verts: &[VertexId],
+    holes: &[Vec<VertexId>],
     reversed: bool,
     he_map: &mut HashMap<([i64; 3], [i64; 3]), HalfEdgeId>,
     quantize_pt: F,
@@ -460,13 +489,15 @@ where
         verts.to_vec()
     };
 
-    let hes: Vec<HalfEdgeId> = ordered_verts
+    let mut hes: Vec<HalfEdgeId> = ordered_verts
         .iter()
         .map(|&v| topo.add_half_edge(v))
         .collect();
     let loop_id = topo.add_loop(&hes);
     let face_id = topo.add_face(loop_id, surf_idx, Orientation::Forward);
 
+    hes.extend(add_hole_loops(topo, face_id, holes, reversed));
+
     for &he_id in &hes {
         let he = &topo.half_edges[he_id];
         let origin = topo.vertices[he.origin].point;
@@ -571,10 +602,12 @@ fn build_cylindrical_lateral_face(
     (face_id, vec![he0, he1, he2, he3])
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_cap_face<F>(
     topo: &mut Topology,
     geom: &mut GeometryStore,
     verts: &[VertexId],
+    holes: &[Vec<VertexId>],
     normal: &Vec3,
     reversed: bool,
     he_map: &mut HashMap<([i64; 3], [i64; 3]), HalfEdgeId>,
@@ -594,6 +627,12 @@ where
         let x_dir = positions[1] - origin;
         let y_dir = positions[n - 1] - origin;
         if x_dir.norm() > 1e-12 && y_dir.norm() > 1e-12 && x_dir.cross(&y_dir).norm() > 1e-12 {
+            // Keep the plane normal on the requested side of the cap
+            let (x_dir, y_dir) = if x_dir.cross(&y_dir).dot(normal) < 0.0 {
+                (y_dir, x_dir)
+            } else {
+                (x_dir, y_dir)
+            };
             geom.add_surface(Box::new(Plane::new(origin, x_dir, y_dir)))
         } else {
             geom.add_surface(Box::new(Plane::from_normal(origin, *normal)))
@@ -609,13 +648,15 @@ where
         verts.to_vec()
     };
 
-    let hes: Vec<HalfEdgeId> = ordered_verts
+    let mut hes: Vec<HalfEdgeId> = ordered_verts
         .iter()
         .map(|&v| topo.add_half_edge(v))
         .collect();
     let loop_id = topo.add_loop(&hes);
     let face_id = topo.add_face(loop_id, surf_idx, Orientation::Forward);
 
+    hes.extend(add_hole_loops(topo, face_id, holes, reversed));
+
     // Record half-edges for twin pairing
     for &he_id in &hes {
         let he = &topo.half_edges[he_id];
@@ -628,6 +669,30 @@ where
     face_id
 }
 
+/// Add `holes` to a cap face as inner loops, returning their half-edges.
+///
+/// Holes are already wound opposite to the outer loop, and reversing both
+/// keeps it that way.
+fn add_hole_loops(
+    topo: &mut Topology,
+    face_id: vcad_kernel_topo::FaceId,
+    holes: &[Vec<VertexId>],
+    reversed: bool,
+) -> Vec<HalfEdgeId> {
+    let mut hes = Vec::new();
+    for hole in holes {
+        let hole_hes: Vec<HalfEdgeId> = if reversed {
+            hole.iter().rev().map(|&v| topo.add_half_edge(v)).collect()
+        } else {
+            hole.iter().map(|&v| topo.add_half_edge(v)).collect()
+        };
+        let loop_id = topo.add_loop(&hole_hes);
+        topo.add_inner_loop(face_id, loop_id);
+        hes.extend(hole_hes);
+    }
+    hes
+}
+
 fn pair_twin_half_edges(topo: &mut Topology, he_map: &HashMap<([i64; 3], [i64; 3]), HalfEdgeId>) {
     let mut paired = std::collections::HashSet::new();
 
@@ -688,6 +753,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extrude_clockwise_profile() {
+        let profile =
+            SketchProfile::rectangle(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 5.0).reversed();
+        assert!(!profile.is_ccw());
+
+        let solid = extrude(&profile, Vec3::new(0.0, 0.0, 20.0)).unwrap();
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&solid, 32);
+        let vol = compute_mesh_volume(&mesh);
+        assert!(
+            (vol - 1000.0).abs() < 1.0,
+            "expected volume ~1000, got {vol}"
+        );
+        assert!(solid
+            .topology
+            .half_edges
+            .values()
+            .all(|he| he.twin.is_some()));
+    }
+
     #[test]
     fn test_extrude_all_halfedges_paired() {
         let profile = SketchProfile::rectangle(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 5.0);
@@ -759,6 +844,66 @@ mod tests {
         assert_eq!(unpaired, 0);
     }
 
+    /// Areas of the mesh triangles lying in the plane `z = at`, signed by
+    /// which way they face along Z.
+    fn cap_triangle_areas(mesh: &vcad_kernel_tessellate::TriangleMesh, at: f64) -> Vec<f64> {
+        let point = |i: u32| {
+            let v = &mesh.vertices[i as usize * 3..i as usize * 3 + 3];
+            Point3::new(v[0] as f64, v[1] as f64, v[2] as f64)
+        };
+        mesh.indices
+            .chunks_exact(3)
+            .map(|t| [point(t[0]), point(t[1]), point(t[2])])
+            .filter(|t| t.iter().all(|p| (p.z - at).abs() < 1e-4))
+            .map(|[a, b, c]| (b - a).cross(&(c - a)).z / 2.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_extrude_concave_profile_caps() {
+        // Two slots cut into the top of a 15x10 block: no vertex sees every
+        // other one, so the caps can't be fanned
+        let pts = [
+            (0.0, 0.0),
+            (15.0, 0.0),
+            (15.0, 10.0),
+            (12.0, 10.0),
+            (12.0, 3.0),
+            (9.0, 3.0),
+            (9.0, 10.0),
+            (6.0, 10.0),
+            (6.0, 3.0),
+            (3.0, 3.0),
+            (3.0, 10.0),
+            (0.0, 10.0),
+        ];
+        let segments = (0..pts.len())
+            .map(|i| {
+                let (a, b) = (pts[i], pts[(i + 1) % pts.len()]);
+                SketchSegment::Line {
+                    start: Point2::new(a.0, a.1),
+                    end: Point2::new(b.0, b.1),
+                }
+            })
+            .collect();
+        let profile =
+            SketchProfile::new(Point3::new(0.0, 0.0, 2.0), Vec3::x(), Vec3::y(), segments).unwrap();
+
+        let solid = extrude(&profile, Vec3::new(0.0, 0.0, 4.0)).unwrap();
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&solid, 32);
+
+        // 150 - 2 × 3 × 7; every cap triangle faces out, none overlap
+        for (z, sign) in [(2.0, -1.0), (6.0, 1.0)] {
+            let areas = cap_triangle_areas(&mesh, z);
+            assert_eq!(areas.len(), pts.len() - 2);
+            assert!(areas.iter().all(|a| a * sign > 0.0), "{areas:?}");
+            let total: f64 = areas.iter().map(|a| a.abs()).sum();
+            assert!((total - 108.0).abs() < 1e-3, "cap area {total}");
+        }
+        let vol = compute_mesh_volume(&mesh);
+        assert!((vol - 432.0).abs() < 1e-3, "expected volume 432, got {vol}");
+    }
+
     #[test]
     fn test_extrude_circle() {
         let profile = SketchProfile::circle(Point3::origin(), Vec3::z(), 5.0, 8);
@@ -979,4 +1124,97 @@ mod tests {
             .count();
         assert_eq!(unpaired, 0, "expected no unpaired half-edges");
     }
+
+    #[test]
+    fn test_extrude_rejects_self_intersecting_profile() {
+        let pts = [
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 10.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(0.0, 10.0),
+        ];
+        let segments = (0..4)
+            .map(|i| SketchSegment::Line {
+                start: pts[i],
+                end: pts[(i + 1) % 4],
+            })
+            .collect();
+        let profile = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments).unwrap();
+
+        let result = extrude(&profile, Vec3::new(0.0, 0.0, 5.0));
+        assert!(matches!(result, Err(SketchError::SelfIntersecting(_, _))));
+        let options = ExtrudeOptions {
+            twist_angle: PI / 2.0,
+            ..Default::default()
+        };
+        let result = extrude_with_options(&profile, Vec3::new(0.0, 0.0, 5.0), options);
+        assert!(matches!(result, Err(SketchError::SelfIntersecting(_, _))));
+    }
+
+    /// Counter-clockwise square loop with corner `(x, y)` and side `size`.
+    fn square(x: f64, y: f64, size: f64) -> Vec<SketchSegment> {
+        let pts = [
+            Point2::new(x, y),
+            Point2::new(x + size, y),
+            Point2::new(x + size, y + size),
+            Point2::new(x, y + size),
+        ];
+        (0..4)
+            .map(|i| SketchSegment::Line {
+                start: pts[i],
+                end: pts[(i + 1) % 4],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_extrude_square_tube() {
+        // 10x10 square with a 4x4 hole, given in the same direction
+        let profile = SketchProfile::new(
+            Point3::origin(),
+            Vec3::x(),
+            Vec3::y(),
+            square(0.0, 0.0, 10.0),
+        )
+        .unwrap()
+        .with_holes(vec![square(3.0, 3.0, 4.0)])
+        .unwrap();
+
+        let solid = extrude(&profile, Vec3::new(0.0, 0.0, 5.0)).unwrap();
+
+        // 4 outer + 4 inner lateral faces + 2 caps, each with one hole
+        assert_eq!(solid.topology.faces.len(), 10);
+        let caps: Vec<_> = solid
+            .topology
+            .faces
+            .values()
+            .filter(|f| !f.inner_loops.is_empty())
+            .collect();
+        assert_eq!(caps.len(), 2);
+        assert!(solid
+            .topology
+            .half_edges
+            .values()
+            .all(|he| he.twin.is_some()));
+
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&solid, 32);
+        let vol = compute_mesh_volume(&mesh);
+        // (100 - 16) * 5
+        assert!((vol - 420.0).abs() < 1e-6, "expected volume 420, got {vol}");
+
+        // Tapered to half size: area scales by s², so volume by (1 + s + s²) / 3
+        let options = ExtrudeOptions {
+            scale_end: 0.5,
+            ..Default::default()
+        };
+        let solid = extrude_with_options(&profile, Vec3::new(0.0, 0.0, 5.0), options).unwrap();
+        assert!(solid
+            .topology
+            .half_edges
+            .values()
+            .all(|he| he.twin.is_some()));
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&solid, 32);
+        let vol = compute_mesh_volume(&mesh);
+        assert!((vol - 245.0).abs() < 1e-6, "expected volume 245, got {vol}");
+    }
 }
@@ -3,7 +3,9 @@
 //! Sketch-based operations for the vcad kernel.
 //!
 //! Provides extrude and revolve operations that create 3D B-rep solids
-//! from 2D sketch profiles.
+//! from 2D sketch profiles, and 2D booleans for combining profiles before
+//! they are extruded ([`SketchProfile::union`],
+//! [`SketchProfile::difference`], [`SketchProfile::intersection`]).
 //!
 //! # Example
 //!
@@ -25,13 +27,16 @@
 //! assert_eq!(solid.topology.faces.len(), 6);
 //! ```
 
+mod boolean;
 mod extrude;
 mod profile;
 mod revolve;
+mod triangulate;
 
 pub use extrude::{extrude, extrude_with_options, ExtrudeOptions};
 pub use profile::{SketchProfile, SketchSegment};
 pub use revolve::revolve;
+pub use triangulate::{triangulate_loops, triangulate_polygon, triangulate_polygon_with_holes};
 
 use thiserror::Error;
 
@@ -46,6 +51,10 @@ pub enum SketchError {
     #[error("degenerate segment at index {0}")]
     DegenerateSegment(usize),
 
+    /// An arc's start or end lies on its center.
+    #[error("arc at index {0} has zero radius")]
+    ZeroRadiusArc(usize),
+
     /// Extrusion direction has zero length.
     #[error("extrusion direction is zero")]
     ZeroExtrusion,
@@ -62,11 +71,32 @@ pub enum SketchError {
     #[error("arc segments not supported for revolve operation")]
     ArcNotSupported,
 
-    /// Profile intersects the revolution axis.
-    #[error("profile intersects the revolution axis")]
+    /// Profile crosses the revolution axis.
+    #[error("profile crosses the revolution axis")]
     AxisIntersection,
 
     /// Profile has no segments.
     #[error("profile has no segments")]
     EmptyProfile,
+
+    /// Two segments of the profile intersect, or neighbouring segments
+    /// meet other than at their shared vertex.
+    #[error("profile self-intersects: segments {0} and {1} cross")]
+    SelfIntersecting(usize, usize),
+
+    /// A hole crosses or lies outside the profile, or overlaps another hole.
+    #[error("hole {0} is not strictly inside the profile")]
+    InvalidHole(usize),
+
+    /// A polyline has fewer than two points.
+    #[error("polyline needs at least 2 points, got {0}")]
+    TooFewPoints(usize),
+
+    /// A regular polygon has fewer than three sides.
+    #[error("polygon needs at least 3 sides, got {0}")]
+    TooFewSides(u32),
+
+    /// A size given to a parametric profile is out of range.
+    #[error("invalid profile dimension: {0}")]
+    InvalidDimension(f64),
 }
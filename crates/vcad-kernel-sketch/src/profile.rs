@@ -1,10 +1,15 @@
 //! 2D sketch profile types.
 
 use std::f64::consts::PI;
-use vcad_kernel_math::{Dir3, Point2, Point3, Tolerance, Vec3};
+use vcad_kernel_math::intersect::{segment_intersect_2d, SegIntersection};
+use vcad_kernel_math::{Dir3, Point2, Point3, Tolerance, Vec2, Vec3};
 
 use crate::SketchError;
 
+/// Maximum distance between an arc and the chords replacing it when
+/// combining profiles, in mm.
+pub(crate) const FLATTEN_TOLERANCE: f64 = 1e-3;
+
 /// A segment of a 2D sketch profile.
 #[derive(Debug, Clone)]
 pub enum SketchSegment {
@@ -46,17 +51,35 @@ impl SketchSegment {
     }
 
     /// Check if this segment is degenerate (zero length).
+    ///
+    /// An arc is degenerate only if it has zero radius: one whose start and
+    /// end coincide is a full circle (see [`SketchSegment::is_full_circle`]).
     pub fn is_degenerate(&self) -> bool {
         let tol = Tolerance::DEFAULT;
         match self {
             SketchSegment::Line { start, end } => (end - start).norm() < tol.linear,
+            SketchSegment::Arc { .. } => self.has_zero_radius(),
+        }
+    }
+
+    /// Check if this is an arc whose start or end lies on its center.
+    pub fn has_zero_radius(&self) -> bool {
+        let tol = Tolerance::DEFAULT;
+        match self {
+            SketchSegment::Line { .. } => false,
             SketchSegment::Arc {
                 start, end, center, ..
-            } => {
-                // Degenerate if start == end or radius is zero
-                let r1 = (start - center).norm();
-                let r2 = (end - center).norm();
-                r1 < tol.linear || r2 < tol.linear || (end - start).norm() < tol.linear
+            } => (start - center).norm() < tol.linear || (end - center).norm() < tol.linear,
+        }
+    }
+
+    /// Check if this is an arc whose start and end coincide, which by
+    /// convention sweeps a full 360° circle in its direction.
+    pub fn is_full_circle(&self) -> bool {
+        match self {
+            SketchSegment::Line { .. } => false,
+            SketchSegment::Arc { start, end, .. } => {
+                (end - start).norm() < Tolerance::DEFAULT.linear && !self.has_zero_radius()
             }
         }
     }
@@ -78,7 +101,95 @@ impl SketchSegment {
         }
     }
 
+    /// The same segment traversed from end to start.
+    pub fn reversed(&self) -> Self {
+        match *self {
+            SketchSegment::Line { start, end } => SketchSegment::Line {
+                start: end,
+                end: start,
+            },
+            SketchSegment::Arc {
+                start,
+                end,
+                center,
+                ccw,
+            } => SketchSegment::Arc {
+                start: end,
+                end: start,
+                center,
+                ccw: !ccw,
+            },
+        }
+    }
+
+    /// The points of the segment from its start up to (not including) its
+    /// end, with arcs replaced by chords deviating at most `tolerance` from
+    /// them.
+    ///
+    /// A full-circle arc gives a closed polygon around the circle.
+    pub fn flatten(&self, tolerance: f64) -> Vec<Point2> {
+        match *self {
+            SketchSegment::Line { start, .. } => vec![start],
+            SketchSegment::Arc {
+                start,
+                end,
+                center,
+                ccw,
+            } => {
+                let radius = (start - center).norm();
+                if radius < Tolerance::DEFAULT.linear {
+                    return vec![start];
+                }
+                let sweep = self.arc_angle(start, end, center, ccw);
+                // Sagitta of a chord spanning `step`: r (1 - cos(step / 2))
+                let step = 2.0 * (1.0 - tolerance / radius).clamp(-1.0, 1.0).acos();
+                let n = ((sweep.abs() / step).ceil() as usize).max(1);
+                let a0 = (start.y - center.y).atan2(start.x - center.x);
+                (0..n)
+                    .map(|i| {
+                        let a = a0 + sweep * i as f64 / n as f64;
+                        Point2::new(center.x + radius * a.cos(), center.y + radius * a.sin())
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// The two half circles making up a full-circle arc, or `None` for any
+    /// other segment.
+    fn split_full_circle(&self) -> Option<[SketchSegment; 2]> {
+        let SketchSegment::Arc {
+            start, center, ccw, ..
+        } = *self
+        else {
+            return None;
+        };
+        if !self.is_full_circle() {
+            return None;
+        }
+        let opposite = center + (center - start);
+        Some([
+            SketchSegment::Arc {
+                start,
+                end: opposite,
+                center,
+                ccw,
+            },
+            SketchSegment::Arc {
+                start: opposite,
+                end: start,
+                center,
+                ccw,
+            },
+        ])
+    }
+
+    /// Signed sweep angle of an arc, positive counter-clockwise. Coincident
+    /// endpoints sweep a full turn.
     fn arc_angle(&self, start: Point2, end: Point2, center: Point2, ccw: bool) -> f64 {
+        if (end - start).norm() < Tolerance::DEFAULT.linear {
+            return if ccw { 2.0 * PI } else { -2.0 * PI };
+        }
         let d_start = start - center;
         let d_end = end - center;
         let start_angle = d_start.y.atan2(d_start.x);
@@ -111,6 +222,9 @@ pub struct SketchProfile {
     pub normal: Dir3,
     /// The segments forming the closed profile.
     pub segments: Vec<SketchSegment>,
+    /// Closed inner loops cut out of the profile, each wound opposite to
+    /// `segments`. Added with [`SketchProfile::with_holes`].
+    pub holes: Vec<Vec<SketchSegment>>,
 }
 
 impl SketchProfile {
@@ -127,8 +241,12 @@ impl SketchProfile {
     ///
     /// Returns an error if:
     /// - The profile has no segments
+    /// - Any arc has zero radius
     /// - Any segment is degenerate
     /// - The profile is not closed (start of first segment != end of last segment)
+    ///
+    /// An arc whose start and end coincide is a full circle. It is stored as
+    /// two half circles, so that every segment joins two distinct points.
     pub fn new(
         origin: Point3,
         x_dir: Vec3,
@@ -141,6 +259,9 @@ impl SketchProfile {
 
         // Validate segments
         for (i, seg) in segments.iter().enumerate() {
+            if seg.has_zero_radius() {
+                return Err(SketchError::ZeroRadiusArc(i));
+            }
             if seg.is_degenerate() {
                 return Err(SketchError::DegenerateSegment(i));
             }
@@ -165,6 +286,14 @@ impl SketchProfile {
             }
         }
 
+        let segments = segments
+            .into_iter()
+            .flat_map(|seg| match seg.split_full_circle() {
+                Some(halves) => halves.to_vec(),
+                None => vec![seg],
+            })
+            .collect();
+
         let x = Dir3::new_normalize(x_dir);
         let y = Dir3::new_normalize(y_dir);
         let n = Dir3::new_normalize(x_dir.cross(&y_dir));
@@ -175,9 +304,94 @@ impl SketchProfile {
             y_dir: y,
             normal: n,
             segments,
+            holes: Vec::new(),
         })
     }
 
+    /// Add inner loops (holes) to the profile.
+    ///
+    /// Each hole is checked like the outer profile and re-wound opposite to
+    /// it if needed, so holes may be given in either direction. Whether they
+    /// lie inside the profile is checked by [`SketchProfile::validate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a hole is empty, has a degenerate segment, or is
+    /// not closed.
+    pub fn with_holes(mut self, holes: Vec<Vec<SketchSegment>>) -> Result<Self, SketchError> {
+        let outer_ccw = self.signed_area() > 0.0;
+        for segments in holes {
+            let hole = Self::new(
+                self.origin,
+                *self.x_dir.as_ref(),
+                *self.y_dir.as_ref(),
+                segments,
+            )?;
+            if (hole.signed_area() > 0.0) == outer_ccw {
+                let reversed = hole.segments.iter().rev().map(|s| s.reversed());
+                self.holes.push(reversed.collect());
+            } else {
+                self.holes.push(hole.segments);
+            }
+        }
+        Ok(self)
+    }
+
+    /// The outer loop followed by each hole.
+    pub fn loops(&self) -> impl Iterator<Item = &[SketchSegment]> {
+        std::iter::once(self.segments.as_slice()).chain(self.holes.iter().map(Vec::as_slice))
+    }
+
+    /// A profile on the same plane with `segments` as its only loop.
+    fn with_loop(&self, segments: Vec<SketchSegment>) -> Self {
+        Self {
+            origin: self.origin,
+            x_dir: self.x_dir,
+            y_dir: self.y_dir,
+            normal: self.normal,
+            segments,
+            holes: Vec::new(),
+        }
+    }
+
+    /// Create a line-only profile through a list of points.
+    ///
+    /// With `closed`, a segment from the last point back to the first is
+    /// added, unless the list already ends where it starts. Without it, the
+    /// list itself must end at its first point.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two points, if two
+    /// consecutive points coincide, or if the points don't close up and
+    /// `closed` is false.
+    pub fn from_polyline(
+        origin: Point3,
+        x_dir: Vec3,
+        y_dir: Vec3,
+        points: &[Point2],
+        closed: bool,
+    ) -> Result<Self, SketchError> {
+        if points.len() < 2 {
+            return Err(SketchError::TooFewPoints(points.len()));
+        }
+        let mut segments: Vec<SketchSegment> = points
+            .windows(2)
+            .map(|w| SketchSegment::Line {
+                start: w[0],
+                end: w[1],
+            })
+            .collect();
+        let (first, last) = (points[0], points[points.len() - 1]);
+        if closed && (last - first).norm() > Tolerance::DEFAULT.linear {
+            segments.push(SketchSegment::Line {
+                start: last,
+                end: first,
+            });
+        }
+        Self::new(origin, x_dir, y_dir, segments)
+    }
+
     /// Create a rectangular profile.
     ///
     /// The rectangle has corners at:
@@ -236,6 +450,131 @@ impl SketchProfile {
         Self::new(origin, x_dir, y_dir, segments).unwrap()
     }
 
+    /// Create a slot profile: two half circles of diameter `width` joined
+    /// by straight sides.
+    ///
+    /// `length` is the distance between the arc centers, which lie on the
+    /// local X axis either side of the origin; the overall length is
+    /// `length + width`. The arcs are tangent to the sides, and a zero
+    /// `length` gives a circle. Segments go counter-clockwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `width` is not positive or `length` is negative.
+    pub fn slot(
+        origin: Point3,
+        x_dir: Vec3,
+        y_dir: Vec3,
+        length: f64,
+        width: f64,
+    ) -> Result<Self, SketchError> {
+        if width <= 0.0 {
+            return Err(SketchError::InvalidDimension(width));
+        }
+        if length < 0.0 {
+            return Err(SketchError::InvalidDimension(length));
+        }
+        let (half, r) = (length / 2.0, width / 2.0);
+        let mut segments = Vec::new();
+        push_line(&mut segments, Point2::new(-half, -r), Point2::new(half, -r));
+        segments.push(SketchSegment::Arc {
+            start: Point2::new(half, -r),
+            end: Point2::new(half, r),
+            center: Point2::new(half, 0.0),
+            ccw: true,
+        });
+        push_line(&mut segments, Point2::new(half, r), Point2::new(-half, r));
+        segments.push(SketchSegment::Arc {
+            start: Point2::new(-half, r),
+            end: Point2::new(-half, -r),
+            center: Point2::new(-half, 0.0),
+            ccw: true,
+        });
+        Self::new(origin, x_dir, y_dir, segments)
+    }
+
+    /// Create a rectangle with its corners rounded to quarter circles of
+    /// `radius`.
+    ///
+    /// Like [`SketchProfile::rectangle`], the rectangle spans `(0, 0)` to
+    /// `(width, height)` in local coordinates and goes counter-clockwise.
+    /// The arcs are tangent to the sides. `radius` is clamped to half the
+    /// shorter side, where the sides between the arcs vanish.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `width`, `height` or `radius` is not positive.
+    pub fn rounded_rect(
+        origin: Point3,
+        x_dir: Vec3,
+        y_dir: Vec3,
+        width: f64,
+        height: f64,
+        radius: f64,
+    ) -> Result<Self, SketchError> {
+        for size in [width, height, radius] {
+            if size <= 0.0 {
+                return Err(SketchError::InvalidDimension(size));
+            }
+        }
+        let r = radius.min(width.min(height) / 2.0);
+        // Corner arc centers, counter-clockwise from the origin corner, and
+        // the direction of the side leaving each corner's arc
+        let corners = [
+            (Point2::new(r, r), Vec2::new(1.0, 0.0)),
+            (Point2::new(width - r, r), Vec2::new(0.0, 1.0)),
+            (Point2::new(width - r, height - r), Vec2::new(-1.0, 0.0)),
+            (Point2::new(r, height - r), Vec2::new(0.0, -1.0)),
+        ];
+        let mut segments = Vec::new();
+        for (i, &(center, dir)) in corners.iter().enumerate() {
+            // The outward normal of a side is its direction turned clockwise
+            let outward = |d: Vec2| Vec2::new(d.y, -d.x) * r;
+            let (prev_dir, next_center) = (corners[(i + 3) % 4].1, corners[(i + 1) % 4].0);
+            segments.push(SketchSegment::Arc {
+                start: center + outward(prev_dir),
+                end: center + outward(dir),
+                center,
+                ccw: true,
+            });
+            push_line(
+                &mut segments,
+                center + outward(dir),
+                next_center + outward(dir),
+            );
+        }
+        Self::new(origin, x_dir, y_dir, segments)
+    }
+
+    /// Create a regular polygon with `n` sides inscribed in a circle of
+    /// `radius` around the origin, with its first corner on the local X
+    /// axis. Segments go counter-clockwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is less than 3 or `radius` is not positive.
+    pub fn regular_polygon(
+        origin: Point3,
+        x_dir: Vec3,
+        y_dir: Vec3,
+        n: u32,
+        radius: f64,
+    ) -> Result<Self, SketchError> {
+        if n < 3 {
+            return Err(SketchError::TooFewSides(n));
+        }
+        if radius <= 0.0 {
+            return Err(SketchError::InvalidDimension(radius));
+        }
+        let corners: Vec<Point2> = (0..n)
+            .map(|i| {
+                let theta = 2.0 * PI * i as f64 / n as f64;
+                Point2::new(radius * theta.cos(), radius * theta.sin())
+            })
+            .collect();
+        Self::from_polyline(origin, x_dir, y_dir, &corners, true)
+    }
+
     /// Map a 2D point in sketch coordinates to 3D.
     pub fn to_3d(&self, p: Point2) -> Point3 {
         self.origin + p.x * self.x_dir.as_ref() + p.y * self.y_dir.as_ref()
@@ -260,7 +599,7 @@ impl SketchProfile {
     /// Tessellate the profile, converting arcs to line segments.
     ///
     /// Each arc is subdivided into `segments_per_arc` line segments.
-    /// Lines are kept as-is.
+    /// Lines are kept as-is. Holes are tessellated the same way.
     pub fn tessellate(&self, segments_per_arc: usize) -> Self {
         let segments_per_arc = segments_per_arc.max(1);
         let mut new_segments = Vec::new();
@@ -281,22 +620,10 @@ impl SketchProfile {
                 } => {
                     // Compute start and end angles
                     let start_vec = *start - *center;
-                    let end_vec = *end - *center;
                     let radius = start_vec.norm();
 
                     let start_angle = start_vec.y.atan2(start_vec.x);
-                    let mut end_angle = end_vec.y.atan2(end_vec.x);
-
-                    // Adjust end_angle based on direction
-                    if *ccw {
-                        while end_angle <= start_angle {
-                            end_angle += 2.0 * PI;
-                        }
-                    } else {
-                        while end_angle >= start_angle {
-                            end_angle -= 2.0 * PI;
-                        }
-                    }
+                    let end_angle = start_angle + seg.arc_angle(*start, *end, *center, *ccw);
 
                     // Generate intermediate points
                     let mut prev = *start;
@@ -327,6 +654,15 @@ impl SketchProfile {
             y_dir: self.y_dir,
             normal: self.normal,
             segments: new_segments,
+            holes: self
+                .holes
+                .iter()
+                .map(|hole| {
+                    self.with_loop(hole.clone())
+                        .tessellate(segments_per_arc)
+                        .segments
+                })
+                .collect(),
         }
     }
 
@@ -344,6 +680,117 @@ impl SketchProfile {
             .all(|s| matches!(s, SketchSegment::Line { .. }))
     }
 
+    /// Check that consecutive segments connect end-to-start, including the
+    /// last segment back to the first.
+    pub fn is_closed(&self) -> bool {
+        self.closure_gap() <= Tolerance::DEFAULT.linear
+    }
+
+    /// Largest gap between consecutive segment endpoints (0 for an empty profile).
+    fn closure_gap(&self) -> f64 {
+        let n = self.segments.len();
+        (0..n)
+            .map(|i| (self.segments[(i + 1) % n].start() - self.segments[i].end()).norm())
+            .fold(0.0, f64::max)
+    }
+
+    /// Check that the profile is a closed, simple (non-self-intersecting) loop.
+    ///
+    /// Arcs are tessellated for the intersection test. Adjacent segments may
+    /// share their common endpoint; any other contact between two segments,
+    /// including touching at a vertex, is reported as a self-intersection.
+    /// Each hole must itself be a simple loop lying strictly inside the
+    /// profile, without touching or containing another hole.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SketchError::EmptyProfile`], [`SketchError::NotClosed`] or
+    /// [`SketchError::SelfIntersecting`] with the indices of the offending
+    /// segments, or [`SketchError::InvalidHole`] with the index of a
+    /// misplaced hole.
+    pub fn validate(&self) -> Result<(), SketchError> {
+        if self.segments.is_empty() {
+            return Err(SketchError::EmptyProfile);
+        }
+
+        let gap = self.closure_gap();
+        if gap > Tolerance::DEFAULT.linear {
+            return Err(SketchError::NotClosed(gap));
+        }
+
+        // Polyline pieces tagged with the index of the segment they came from.
+        let mut pieces: Vec<(usize, Point2, Point2)> = Vec::new();
+        for (i, seg) in self.segments.iter().enumerate() {
+            let single = self.with_loop(vec![seg.clone()]);
+            for piece in single.tessellate(8).segments {
+                pieces.push((i, piece.start(), piece.end()));
+            }
+        }
+
+        // Neighbouring segments may only meet at the vertex they share; a
+        // segment doubling back over its neighbour overlaps it.
+        let n = self.segments.len();
+        let shared_vertices = |i: usize, j: usize| {
+            let mut shared = Vec::new();
+            if j == (i + 1) % n {
+                shared.push(self.segments[i].end());
+            }
+            if i == (j + 1) % n {
+                shared.push(self.segments[j].end());
+            }
+            shared
+        };
+        let tol = Tolerance::DEFAULT.linear;
+        for (a, &(i, p0, p1)) in pieces.iter().enumerate() {
+            for &(j, q0, q1) in &pieces[a + 1..] {
+                if i == j {
+                    continue;
+                }
+                let crosses = match segment_intersect_2d(&p0, &p1, &q0, &q1) {
+                    SegIntersection::None => false,
+                    SegIntersection::Point { point, .. } => !shared_vertices(i, j)
+                        .iter()
+                        .any(|v| (point - v).norm() <= tol),
+                    SegIntersection::Overlap(..) => true,
+                };
+                if crosses {
+                    return Err(SketchError::SelfIntersecting(i, j));
+                }
+            }
+        }
+
+        if self.holes.is_empty() {
+            return Ok(());
+        }
+        let loops: Vec<Self> = self
+            .tessellate(8)
+            .loops()
+            .map(|segments| self.with_loop(segments.to_vec()))
+            .collect();
+        let touches = |a: &Self, b: &Self| {
+            a.segments.iter().any(|p| {
+                b.segments
+                    .iter()
+                    .any(|q| segments_intersect(&p.start(), &p.end(), &q.start(), &q.end()))
+            })
+        };
+        let start = |l: &Self| l.segments[0].start();
+        for (k, hole) in loops.iter().enumerate().skip(1) {
+            self.with_loop(self.holes[k - 1].clone()).validate()?;
+            let outside = !loops[0].contains_point_2d(start(hole)) || touches(hole, &loops[0]);
+            let overlaps = loops[1..k].iter().any(|other| {
+                touches(hole, other)
+                    || other.contains_point_2d(start(hole))
+                    || hole.contains_point_2d(start(other))
+            });
+            if outside || overlaps {
+                return Err(SketchError::InvalidHole(k - 1));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get the number of segments.
     pub fn len(&self) -> usize {
         self.segments.len()
@@ -354,28 +801,51 @@ impl SketchProfile {
         self.segments.is_empty()
     }
 
-    /// Calculate the signed area of the profile in 2D.
+    /// Calculate the signed area of the outer loop in 2D.
     ///
     /// Returns positive for counter-clockwise contours (outer shapes)
     /// and negative for clockwise contours (holes).
     ///
-    /// Uses the shoelace formula on tessellated vertices.
+    /// Exact for arcs: the shoelace area of the polygon through the segment
+    /// endpoints plus the circular segment each arc bulges out of its chord.
     pub fn signed_area(&self) -> f64 {
-        // Tessellate to handle arcs
-        let verts = self.tessellated_vertices_2d(8);
-        if verts.len() < 3 {
-            return 0.0;
+        area_moment(&self.segments).0
+    }
+
+    /// Whether the outer loop winds counter-clockwise in the sketch plane.
+    pub fn is_ccw(&self) -> bool {
+        self.signed_area() > 0.0
+    }
+
+    /// Area centroid of the outer loop in 2D, whichever way it winds.
+    ///
+    /// Holes are not subtracted. A loop enclosing no area gives the average
+    /// of its vertices.
+    pub fn centroid(&self) -> Point2 {
+        if self.segments.is_empty() {
+            return Point2::origin();
+        }
+        let (area, moment) = area_moment(&self.segments);
+        if area.abs() < 1e-12 {
+            let sum = self
+                .segments
+                .iter()
+                .fold(Vec2::zeros(), |acc, s| acc + s.start().coords);
+            return Point2::from(sum / self.segments.len() as f64);
         }
+        Point2::from(moment / (6.0 * area))
+    }
 
-        // Shoelace formula
-        let mut area = 0.0;
-        let n = verts.len();
-        for i in 0..n {
-            let j = (i + 1) % n;
-            area += verts[i].x * verts[j].y;
-            area -= verts[j].x * verts[i].y;
+    /// The same profile with every loop traversed the other way.
+    pub fn reversed(&self) -> Self {
+        let reverse = |segments: &[SketchSegment]| -> Vec<SketchSegment> {
+            segments.iter().rev().map(SketchSegment::reversed).collect()
+        };
+        Self {
+            segments: reverse(&self.segments),
+            holes: self.holes.iter().map(|h| reverse(h)).collect(),
+            ..self.clone()
         }
-        area / 2.0
     }
 
     /// Check if this profile represents a hole based on winding order.
@@ -475,10 +945,74 @@ impl SketchProfile {
             y_dir: Dir3::new_normalize(new_y_dir),
             normal: Dir3::new_normalize(new_x_dir.cross(&new_y_dir)),
             segments: self.segments.clone(),
+            holes: self.holes.clone(),
         }
     }
 }
 
+/// Append a line from `start` to `end`, unless the two coincide.
+fn push_line(segments: &mut Vec<SketchSegment>, start: Point2, end: Point2) {
+    if (end - start).norm() >= Tolerance::DEFAULT.linear {
+        segments.push(SketchSegment::Line { start, end });
+    }
+}
+
+/// Test whether closed segments `p0p1` and `q0q1` share at least one point.
+fn segments_intersect(p0: &Point2, p1: &Point2, q0: &Point2, q1: &Point2) -> bool {
+    segment_intersect_2d(p0, p1, q0, q1) != SegIntersection::None
+}
+
+/// Signed area of a closed polygon and its first moment `Σ (pᵢ + pᵢ₊₁)
+/// (pᵢ × pᵢ₊₁)`, which divided by six times the area gives the centroid.
+fn shoelace(verts: &[Point2]) -> (f64, Vec2) {
+    let mut area = 0.0;
+    let mut moment = Vec2::zeros();
+    for (i, p) in verts.iter().enumerate() {
+        let q = verts[(i + 1) % verts.len()];
+        let cross = p.x * q.y - q.x * p.y;
+        area += cross;
+        moment += (p.coords + q.coords) * cross;
+    }
+    (area / 2.0, moment)
+}
+
+/// Signed area of a closed loop and its first moment, scaled like
+/// [`shoelace`]'s.
+///
+/// Each arc adds to the polygon through the segment endpoints the circular
+/// segment between its chord and itself: `r²/2 (θ - sin θ)` for a signed
+/// sweep `θ`, with its centroid on the arc's bisector at
+/// `4 r sin³(θ/2) / 3 (θ - sin θ)` from the center.
+fn area_moment(segments: &[SketchSegment]) -> (f64, Vec2) {
+    let starts: Vec<Point2> = segments.iter().map(SketchSegment::start).collect();
+    let (mut area, mut moment) = shoelace(&starts);
+    for seg in segments {
+        let SketchSegment::Arc {
+            start,
+            end,
+            center,
+            ccw,
+        } = *seg
+        else {
+            continue;
+        };
+        let radius = (start - center).norm();
+        let sweep = seg.arc_angle(start, end, center, ccw);
+        let excess = sweep.abs() - sweep.abs().sin();
+        if radius < Tolerance::DEFAULT.linear || excess < 1e-12 {
+            continue;
+        }
+        let bulge = radius * radius / 2.0 * sweep.signum() * excess;
+        let distance = 4.0 * radius * (sweep.abs() / 2.0).sin().powi(3) / (3.0 * excess);
+        let (sin, cos) = (sweep / 2.0).sin_cos();
+        let d = (start - center) / radius;
+        let bisector = Vec2::new(d.x * cos - d.y * sin, d.x * sin + d.y * cos);
+        area += bulge;
+        moment += (center.coords + bisector * distance) * (6.0 * bulge);
+    }
+    (area, moment)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -540,12 +1074,284 @@ mod tests {
         assert!(matches!(result, Err(SketchError::DegenerateSegment(0))));
     }
 
+    #[test]
+    fn test_full_circle_arc() {
+        let circle = SketchSegment::Arc {
+            start: Point2::new(5.0, 0.0),
+            end: Point2::new(5.0, 0.0),
+            center: Point2::origin(),
+            ccw: false,
+        };
+        assert!(circle.is_full_circle());
+        assert!(!circle.is_degenerate());
+        assert!((circle.length() - 10.0 * PI).abs() < 1e-12);
+
+        // Flattened into a closed polygon whose chords stay within tolerance
+        let tolerance = 0.01;
+        let points = circle.flatten(tolerance);
+        assert!(points.len() > 8);
+        assert_eq!(points[0], circle.start());
+        for (i, p) in points.iter().enumerate() {
+            let next = points.get(i + 1).copied().unwrap_or(circle.end());
+            let mid = Point2::from((p.coords + next.coords) / 2.0);
+            assert!(5.0 - mid.coords.norm() <= tolerance + 1e-12);
+        }
+        // Clockwise: the second point is below the x axis
+        assert!(points[1].y < 0.0);
+
+        // A profile made of one full circle is split into two half circles
+        let profile =
+            SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), vec![circle]).unwrap();
+        assert_eq!(profile.segments.len(), 2);
+        assert!(profile.is_closed());
+        assert!(profile.validate().is_ok());
+        assert!(profile.signed_area() < 0.0);
+        let polygon = profile.tessellate(16).vertices_2d();
+        assert_eq!(polygon.len(), 32);
+        assert!(polygon.iter().all(|p| (p.coords.norm() - 5.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_zero_radius_arc_error() {
+        let segments = vec![SketchSegment::Arc {
+            start: Point2::new(0.0, 0.0),
+            end: Point2::new(0.0, 0.0),
+            center: Point2::new(0.0, 0.0),
+            ccw: true,
+        }];
+        let result = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments);
+        assert!(matches!(result, Err(SketchError::ZeroRadiusArc(0))));
+    }
+
     #[test]
     fn test_empty_profile_error() {
         let result = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), vec![]);
         assert!(matches!(result, Err(SketchError::EmptyProfile)));
     }
 
+    fn polyline(points: &[(f64, f64)]) -> Vec<SketchSegment> {
+        (0..points.len())
+            .map(|i| {
+                let (x0, y0) = points[i];
+                let (x1, y1) = points[(i + 1) % points.len()];
+                SketchSegment::Line {
+                    start: Point2::new(x0, y0),
+                    end: Point2::new(x1, y1),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_from_polyline() {
+        let square = [
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 10.0),
+            Point2::new(0.0, 10.0),
+        ];
+        let profile =
+            SketchProfile::from_polyline(Point3::origin(), Vec3::x(), Vec3::y(), &square, true)
+                .unwrap();
+        assert_eq!(profile.segments.len(), 4);
+        assert!(profile.is_closed());
+        assert!((profile.signed_area() - 100.0).abs() < 1e-12);
+
+        // Already closed: no second closing segment
+        let closed = [&square[..], &square[..1]].concat();
+        for auto_close in [true, false] {
+            let profile = SketchProfile::from_polyline(
+                Point3::origin(),
+                Vec3::x(),
+                Vec3::y(),
+                &closed,
+                auto_close,
+            )
+            .unwrap();
+            assert_eq!(profile.segments.len(), 4);
+        }
+
+        let open =
+            SketchProfile::from_polyline(Point3::origin(), Vec3::x(), Vec3::y(), &square, false);
+        assert!(matches!(open, Err(SketchError::NotClosed(_))));
+        let repeated = [square[0], square[1], square[1], square[2]];
+        let result =
+            SketchProfile::from_polyline(Point3::origin(), Vec3::x(), Vec3::y(), &repeated, true);
+        assert!(matches!(result, Err(SketchError::DegenerateSegment(1))));
+        let result = SketchProfile::from_polyline(
+            Point3::origin(),
+            Vec3::x(),
+            Vec3::y(),
+            &square[..1],
+            true,
+        );
+        assert!(matches!(result, Err(SketchError::TooFewPoints(1))));
+    }
+
+    #[test]
+    fn test_slot_profile() {
+        let slot = SketchProfile::slot(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 4.0).unwrap();
+        assert_eq!(slot.segments.len(), 4);
+        assert!(slot.is_closed());
+        assert!(slot.validate().is_ok());
+        // A 10 × 4 rectangle plus a circle of radius 2
+        let area = 40.0 + PI * 4.0;
+        assert!(
+            (slot.signed_area() - area).abs() < 1e-9,
+            "{}",
+            slot.signed_area()
+        );
+        let (min, max) = slot.bounding_box_2d();
+        assert!((min.x + 7.0).abs() < 1e-9 && (max.x - 7.0).abs() < 1e-9);
+
+        // No length leaves just the two half circles
+        let circle = SketchProfile::slot(Point3::origin(), Vec3::x(), Vec3::y(), 0.0, 4.0).unwrap();
+        assert_eq!(circle.segments.len(), 2);
+        let result = SketchProfile::slot(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 0.0);
+        assert!(matches!(result, Err(SketchError::InvalidDimension(_))));
+    }
+
+    #[test]
+    fn test_rounded_rect_corners() {
+        let radius = 1.5;
+        let rect =
+            SketchProfile::rounded_rect(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 6.0, radius)
+                .unwrap();
+        assert_eq!(rect.segments.len(), 8);
+        assert!(rect.is_closed());
+        assert!(rect.validate().is_ok());
+
+        let n = rect.segments.len();
+        for (i, seg) in rect.segments.iter().enumerate() {
+            let SketchSegment::Arc {
+                start,
+                end,
+                center,
+                ccw,
+            } = *seg
+            else {
+                continue;
+            };
+            // A counter-clockwise quarter circle of the given radius...
+            assert!(ccw);
+            assert!(((start - center).norm() - radius).abs() < 1e-12);
+            assert!(((end - center).norm() - radius).abs() < 1e-12);
+            assert!((start - center).dot(&(end - center)).abs() < 1e-12);
+            assert!((seg.length() - PI * radius / 2.0).abs() < 1e-12);
+            // ...tangent to the sides on either side of it
+            for line in [&rect.segments[(i + n - 1) % n], &rect.segments[(i + 1) % n]] {
+                let along = line.end() - line.start();
+                let joint = if line.end() == start { start } else { end };
+                assert!(along.dot(&(joint - center)).abs() < 1e-12);
+            }
+        }
+        // Four corners of area r² - πr²/4 cut off
+        let area = 60.0 - (4.0 - PI) * radius * radius;
+        assert!((rect.signed_area() - area).abs() < 1e-9);
+
+        // Too large a radius is clamped, leaving no straight ends
+        let pill =
+            SketchProfile::rounded_rect(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 6.0, 5.0)
+                .unwrap();
+        assert_eq!(pill.segments.len(), 6);
+        let sharp =
+            SketchProfile::rounded_rect(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 6.0, 0.0);
+        assert!(matches!(sharp, Err(SketchError::InvalidDimension(_))));
+    }
+
+    #[test]
+    fn test_regular_polygon() {
+        let hex =
+            SketchProfile::regular_polygon(Point3::origin(), Vec3::x(), Vec3::y(), 6, 2.0).unwrap();
+        assert_eq!(hex.segments.len(), 6);
+        assert!(hex.is_line_only());
+        assert!((hex.segments[0].start() - Point2::new(2.0, 0.0)).norm() < 1e-12);
+        assert!(hex
+            .segments
+            .iter()
+            .all(|s| (s.length() - 2.0).abs() < 1e-12));
+        assert!((hex.signed_area() - 6.0 * 3f64.sqrt()).abs() < 1e-9);
+
+        let result = SketchProfile::regular_polygon(Point3::origin(), Vec3::x(), Vec3::y(), 2, 2.0);
+        assert!(matches!(result, Err(SketchError::TooFewSides(2))));
+        let result = SketchProfile::regular_polygon(Point3::origin(), Vec3::x(), Vec3::y(), 6, 0.0);
+        assert!(matches!(result, Err(SketchError::InvalidDimension(_))));
+    }
+
+    #[test]
+    fn test_validate_open_profile() {
+        let mut profile =
+            SketchProfile::rectangle(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 5.0);
+        assert!(profile.is_closed());
+        assert!(profile.validate().is_ok());
+
+        // Drop the closing edge: the constructor would reject this, but the
+        // fields are public.
+        profile.segments.pop();
+        assert!(!profile.is_closed());
+        assert!(matches!(profile.validate(), Err(SketchError::NotClosed(_))));
+    }
+
+    #[test]
+    fn test_validate_figure_eight() {
+        // Bow-tie: the diagonals cross at (5, 5).
+        let segments = polyline(&[(0.0, 0.0), (10.0, 10.0), (10.0, 0.0), (0.0, 10.0)]);
+        let profile = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments).unwrap();
+        assert!(profile.is_closed());
+        assert!(matches!(
+            profile.validate(),
+            Err(SketchError::SelfIntersecting(0, 2))
+        ));
+
+        // Two loops touching at a single vertex are not simple either.
+        let segments = polyline(&[
+            (0.0, 0.0),
+            (5.0, 5.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (5.0, 5.0),
+            (0.0, 10.0),
+        ]);
+        let profile = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments).unwrap();
+        assert!(matches!(
+            profile.validate(),
+            Err(SketchError::SelfIntersecting(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_validate_doubling_back() {
+        // The second edge runs back along the first before turning away.
+        let segments = polyline(&[(0.0, 0.0), (10.0, 0.0), (5.0, 0.0), (5.0, 5.0)]);
+        let profile = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments).unwrap();
+        assert!(matches!(
+            profile.validate(),
+            Err(SketchError::SelfIntersecting(0, 1))
+        ));
+    }
+
+    #[test]
+    fn test_validate_circle_is_simple() {
+        let profile = SketchProfile::circle(Point3::origin(), Vec3::z(), 5.0, 4);
+        assert!(profile.validate().is_ok());
+
+        // Two segments share both of their endpoints.
+        let segments = vec![
+            SketchSegment::Line {
+                start: Point2::new(-5.0, 0.0),
+                end: Point2::new(5.0, 0.0),
+            },
+            SketchSegment::Arc {
+                start: Point2::new(5.0, 0.0),
+                end: Point2::new(-5.0, 0.0),
+                center: Point2::origin(),
+                ccw: true,
+            },
+        ];
+        let profile = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments).unwrap();
+        assert!(profile.validate().is_ok());
+    }
+
     #[test]
     fn test_vertices_3d() {
         let profile = SketchProfile::rectangle(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 5.0);
@@ -575,4 +1381,101 @@ mod tests {
         let expected_len = 5.0 * PI / 2.0; // quarter circle
         assert!((arc.length() - expected_len).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_holes() {
+        let outer = SketchProfile::rectangle(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 10.0);
+        let hole = |x: f64, y: f64, size: f64| {
+            SketchProfile::rectangle(Point3::origin(), Vec3::x(), Vec3::y(), size, size)
+                .segments
+                .iter()
+                .map(|s| SketchSegment::Line {
+                    start: Point2::new(s.start().x + x, s.start().y + y),
+                    end: Point2::new(s.end().x + x, s.end().y + y),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Holes are re-wound clockwise against the counter-clockwise outline
+        let profile = outer
+            .clone()
+            .with_holes(vec![hole(1.0, 1.0, 2.0), hole(5.0, 5.0, 3.0)])
+            .unwrap();
+        assert_eq!(profile.loops().count(), 3);
+        for segments in &profile.holes {
+            let hole = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments.clone())
+                .unwrap();
+            assert!(hole.signed_area() < 0.0);
+            assert!(hole.is_closed());
+        }
+        assert!(profile.validate().is_ok());
+        assert_eq!(profile.tessellate(4).holes.len(), 2);
+
+        for (bad, index) in [
+            (vec![hole(8.0, 8.0, 4.0)], 0),  // crosses the outline
+            (vec![hole(20.0, 0.0, 2.0)], 0), // outside
+            (vec![hole(1.0, 1.0, 6.0), hole(2.0, 2.0, 1.0)], 1), // nested
+            (vec![hole(1.0, 1.0, 2.0), hole(3.0, 1.0, 2.0)], 1), // touching
+        ] {
+            let profile = outer.clone().with_holes(bad).unwrap();
+            assert!(
+                matches!(profile.validate(), Err(SketchError::InvalidHole(i)) if i == index),
+                "{:?}",
+                profile.validate()
+            );
+        }
+    }
+
+    #[test]
+    fn test_area_centroid_and_winding() {
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let segments = (0..4)
+            .map(|i| {
+                let (a, b) = (corners[i], corners[(i + 1) % 4]);
+                SketchSegment::Line {
+                    start: Point2::new(a.0, a.1),
+                    end: Point2::new(b.0, b.1),
+                }
+            })
+            .collect();
+        let square = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments).unwrap();
+        assert!((square.signed_area() - 1.0).abs() < 1e-12);
+        assert!((square.centroid() - Point2::new(0.5, 0.5)).norm() < 1e-12);
+        assert!(square.is_ccw());
+
+        let reversed = square.reversed();
+        assert!((reversed.signed_area() + 1.0).abs() < 1e-12);
+        assert!((reversed.centroid() - Point2::new(0.5, 0.5)).norm() < 1e-12);
+        assert!(!reversed.is_ccw());
+        assert!(reversed.is_closed());
+
+        // Arcs count with their exact area
+        let circle = SketchProfile::circle(Point3::origin(), Vec3::z(), 5.0, 4);
+        assert!((circle.signed_area() - 25.0 * PI).abs() < 1e-9);
+        assert!(circle.centroid().coords.norm() < 1e-9);
+        assert!((circle.reversed().signed_area() + 25.0 * PI).abs() < 1e-9);
+
+        // A half disc above the X axis, its centroid 4r/3π up
+        let half_disc = SketchProfile::new(
+            Point3::origin(),
+            Vec3::x(),
+            Vec3::y(),
+            vec![
+                SketchSegment::Line {
+                    start: Point2::new(-2.0, 0.0),
+                    end: Point2::new(2.0, 0.0),
+                },
+                SketchSegment::Arc {
+                    start: Point2::new(2.0, 0.0),
+                    end: Point2::new(-2.0, 0.0),
+                    center: Point2::origin(),
+                    ccw: true,
+                },
+            ],
+        )
+        .unwrap();
+        assert!((half_disc.signed_area() - 2.0 * PI).abs() < 1e-9);
+        let expected = Point2::new(0.0, 8.0 / (3.0 * PI));
+        assert!((half_disc.centroid() - expected).norm() < 1e-9);
+    }
 }
@@ -4,9 +4,9 @@ use std::collections::HashMap;
 use std::f64::consts::PI;
 
 use vcad_kernel_geom::{CylinderSurface, GeometryStore, Plane};
-use vcad_kernel_math::{Dir3, Point3, Tolerance, Vec3};
+use vcad_kernel_math::{Dir3, Point3, Quantize, Tolerance, Vec3};
 use vcad_kernel_primitives::BRepSolid;
-use vcad_kernel_topo::{HalfEdgeId, Orientation, ShellType, Topology, VertexId};
+use vcad_kernel_topo::{FaceId, HalfEdgeId, Orientation, ShellType, Topology, VertexId};
 
 use crate::{SketchError, SketchProfile, SketchSegment};
 
@@ -28,7 +28,15 @@ use crate::{SketchError, SketchProfile, SketchSegment};
 /// - `ZeroAxis` if the axis direction is zero
 /// - `InvalidAngle` if angle is not in (0, 2π]
 /// - `ArcNotSupported` if the profile contains arc segments
-/// - `AxisIntersection` if any profile vertex lies on the axis
+/// - `AxisIntersection` if the profile crosses the axis
+///
+/// Profile vertices may lie on the axis. Their rings collapse to the axis
+/// point, and segments lying on the axis produce no face. A full revolution
+/// of such a profile is faceted into planar faces.
+///
+/// Holes in the profile sweep out inner surfaces, joined to the side caps
+/// of a partial revolution as inner loops. Full revolutions of profiles
+/// with holes are faceted as well.
 ///
 /// # Current Limitations
 ///
@@ -70,6 +78,8 @@ pub fn revolve(
         return Err(SketchError::InvalidAngle(angle));
     }
 
+    profile.validate()?;
+
     // Check for arc segments (not supported)
     if !profile.is_line_only() {
         return Err(SketchError::ArcNotSupported);
@@ -78,62 +88,100 @@ pub fn revolve(
     let tol = Tolerance::DEFAULT;
     let is_full = (angle - 2.0 * PI).abs() < 1e-9;
 
-    // Validate profile doesn't intersect axis
-    for seg in &profile.segments {
-        let p = profile.to_3d(seg.start());
-        let dist = point_to_line_distance(&p, &axis_origin, axis.as_ref());
-        if dist < tol.linear {
-            return Err(SketchError::AxisIntersection);
-        }
+    let positions: Vec<Point3> = profile
+        .segments
+        .iter()
+        .map(|seg| profile.to_3d(seg.start()))
+        .collect();
+    let holes: Vec<Vec<Point3>> = profile
+        .holes
+        .iter()
+        .map(|hole| hole.iter().map(|seg| profile.to_3d(seg.start())).collect())
+        .collect();
+
+    // Vertices may touch the axis, but the profile must stay on one side
+    if crosses_axis(&positions, &axis_origin, axis.as_ref(), tol.linear) {
+        return Err(SketchError::AxisIntersection);
     }
+    let on_axis: Vec<bool> = positions
+        .iter()
+        .map(|p| point_to_line_distance(p, &axis_origin, axis.as_ref()) < tol.linear)
+        .collect();
 
     let mut topo = Topology::new();
     let mut geom = GeometryStore::new();
 
-    // Vertex cache
-    let mut vertex_cache: HashMap<[i64; 3], VertexId> = HashMap::new();
-
-    let quantize_pt = |p: Point3| -> [i64; 3] {
-        [
-            (p.x * 1e9).round() as i64,
-            (p.y * 1e9).round() as i64,
-            (p.z * 1e9).round() as i64,
-        ]
+    let all_faces = if is_full && !on_axis.contains(&true) && holes.is_empty() {
+        revolve_seamed(
+            profile,
+            &positions,
+            &axis_origin,
+            axis.as_ref(),
+            &mut topo,
+            &mut geom,
+        )
+    } else {
+        // A full revolution touching the axis has no seam circle at the
+        // axis vertices, so it is faceted like a partial revolve.
+        let steps = if is_full { AXIS_REVOLVE_STEPS } else { 1 };
+        revolve_faceted(
+            &positions,
+            &on_axis,
+            &holes,
+            &axis_origin,
+            axis.as_ref(),
+            angle,
+            steps,
+            is_full,
+            &mut topo,
+            &mut geom,
+        )
     };
 
-    let get_or_create_vertex =
-        |cache: &mut HashMap<[i64; 3], VertexId>, topo: &mut Topology, pos: Point3| -> VertexId {
-            let key = quantize_pt(pos);
-            *cache.entry(key).or_insert_with(|| topo.add_vertex(pos))
-        };
+    // Build shell and solid
+    let shell = topo.add_shell(all_faces, ShellType::Outer);
+    let solid_id = topo.add_solid(shell);
 
-    let n_segments = profile.segments.len();
+    Ok(BRepSolid::new(topo, geom, solid_id))
+}
 
-    // For full revolution: each profile vertex maps to one vertex (seam)
-    // For partial revolution: each profile vertex spawns 2 vertices
-    let mut start_verts: Vec<VertexId> = Vec::with_capacity(n_segments);
-    let mut end_verts: Vec<VertexId> = Vec::with_capacity(n_segments);
-
-    for seg in &profile.segments {
-        let p = profile.to_3d(seg.start());
-
-        if is_full {
-            // Single vertex for full revolution
-            let v = get_or_create_vertex(&mut vertex_cache, &mut topo, p);
-            start_verts.push(v);
-            end_verts.push(v); // Same vertex for seam
-        } else {
-            // Two vertices for partial revolution
-            let p_rotated = rotate_point(&p, &axis_origin, axis.as_ref(), angle);
-            let v_start = get_or_create_vertex(&mut vertex_cache, &mut topo, p);
-            let v_end = get_or_create_vertex(&mut vertex_cache, &mut topo, p_rotated);
-            start_verts.push(v_start);
-            end_verts.push(v_end);
-        }
-    }
+/// Angular steps used to facet a full revolution of a profile touching the axis.
+const AXIS_REVOLVE_STEPS: usize = 32;
 
-    let mut all_faces = Vec::new();
-    let mut he_map: HashMap<([i64; 3], [i64; 3]), HalfEdgeId> = HashMap::new();
+type HalfEdgeMap = HashMap<([i64; 3], [i64; 3]), HalfEdgeId>;
+
+fn quantize_pt(p: Point3) -> [i64; 3] {
+    p.quantize(1e9)
+}
+
+fn get_or_create_vertex(
+    cache: &mut HashMap<[i64; 3], VertexId>,
+    topo: &mut Topology,
+    pos: Point3,
+) -> VertexId {
+    let key = quantize_pt(pos);
+    *cache.entry(key).or_insert_with(|| topo.add_vertex(pos))
+}
+
+/// Full revolution with one seam vertex per profile vertex and true
+/// surfaces of revolution where the tessellator supports them.
+fn revolve_seamed(
+    profile: &SketchProfile,
+    positions: &[Point3],
+    axis_origin: &Point3,
+    axis: &Vec3,
+    topo: &mut Topology,
+    geom: &mut GeometryStore,
+) -> Vec<FaceId> {
+    let mut vertex_cache: HashMap<[i64; 3], VertexId> = HashMap::new();
+    let verts: Vec<VertexId> = positions
+        .iter()
+        .map(|&p| get_or_create_vertex(&mut vertex_cache, topo, p))
+        .collect();
+
+    let n_segments = profile.segments.len();
+    let mut all_faces = Vec::with_capacity(n_segments);
+    let mut he_map = HalfEdgeMap::new();
 
     // Build revolution faces for each line segment
     for (i, seg) in profile.segments.iter().enumerate() {
@@ -147,94 +195,183 @@ pub fn revolve(
         let p_end = profile.to_3d(*end);
 
         // Classify the line segment relative to the axis
-        let surf_type = classify_line_segment(&p_start, &p_end, &axis_origin, axis.as_ref());
-
-        let face_id = if is_full {
-            // For full revolution, use true surface types
-            match surf_type {
-                RevolveSurfaceType::Cylinder { radius } => build_full_cylinder_face(
-                    &mut topo,
-                    &mut geom,
-                    &axis_origin,
-                    axis.as_ref(),
-                    radius,
-                    &start_verts[i],
-                    &start_verts[next_i],
+        let surf_type = classify_line_segment(&p_start, &p_end, axis_origin, axis);
+
+        let face_id = match surf_type {
+            RevolveSurfaceType::Cylinder { radius } => build_full_cylinder_face(
+                topo,
+                geom,
+                axis_origin,
+                axis,
+                radius,
+                &verts[i],
+                &verts[next_i],
+                &mut he_map,
+            ),
+            RevolveSurfaceType::Cone { .. } | RevolveSurfaceType::Plane { .. } => {
+                // For full cones and planes, use planar approximation
+                // (true cone tessellation has same issues as partial cylinder)
+                build_full_planar_approximation_face(
+                    topo,
+                    geom,
+                    &verts[i],
+                    &verts[next_i],
                     &mut he_map,
-                    quantize_pt,
-                ),
-                RevolveSurfaceType::Cone { .. } | RevolveSurfaceType::Plane { .. } => {
-                    // For full cones and planes, use planar approximation
-                    // (true cone tessellation has same issues as partial cylinder)
-                    build_full_planar_approximation_face(
-                        &mut topo,
-                        &mut geom,
-                        &start_verts[i],
-                        &start_verts[next_i],
-                        &mut he_map,
-                        quantize_pt,
-                    )
-                }
+                )
             }
-        } else {
-            // For partial revolution, always use planar approximation
-            // because tessellator can't handle partial curved surfaces
-            build_partial_planar_face(
-                &mut topo,
-                &mut geom,
-                &start_verts[i],
-                &start_verts[next_i],
-                &end_verts[next_i],
-                &end_verts[i],
-                &mut he_map,
-                quantize_pt,
-            )
         };
 
         all_faces.push(face_id);
     }
 
-    // For partial revolution, add closing side faces
-    if !is_full {
-        // Start side face (at angle=0)
-        // Revolution faces have edges start[i] -> start[i+1]
-        // For these to be properly paired, start side needs reversed winding
-        let start_face = build_side_face(
-            &mut topo,
-            &mut geom,
-            &start_verts,
-            &mut he_map,
-            quantize_pt,
-            true, // reversed winding so edges pair with revolution faces
-        );
-        all_faces.push(start_face);
+    pair_twin_half_edges(topo, &he_map);
+    all_faces
+}
 
-        // End side face (at angle=angle)
-        // Revolution faces have edges end[i+1] -> end[i] (going backward)
-        // For these to be properly paired, end side needs forward winding
-        let end_face = build_side_face(
-            &mut topo,
-            &mut geom,
-            &end_verts,
-            &mut he_map,
-            quantize_pt,
-            false, // forward winding
-        );
-        all_faces.push(end_face);
+/// Revolution built from `steps` rings of planar faces.
+///
+/// Vertices on the axis collapse to a single vertex shared by every ring, so
+/// faces next to the axis become triangles and segments lying on the axis
+/// produce no face. Partial revolutions are closed by planar side caps, with
+/// the `holes` (which never touch the axis) as inner loops.
+#[allow(clippy::too_many_arguments)]
+fn revolve_faceted(
+    positions: &[Point3],
+    on_axis: &[bool],
+    holes: &[Vec<Point3>],
+    axis_origin: &Point3,
+    axis: &Vec3,
+    angle: f64,
+    steps: usize,
+    closed: bool,
+    topo: &mut Topology,
+    geom: &mut GeometryStore,
+) -> Vec<FaceId> {
+    // Faces point outward when the profile runs clockwise in the
+    // (radius, height) half-plane, so reverse counter-clockwise profiles.
+    // Holes must run the other way.
+    let mut positions = positions.to_vec();
+    let mut on_axis = on_axis.to_vec();
+    if radial_signed_area(&positions, axis_origin, axis) > 0.0 {
+        positions.reverse();
+        on_axis.reverse();
+    }
+    let mut loops = vec![(positions, on_axis)];
+    for hole in holes {
+        let mut hole = hole.clone();
+        if radial_signed_area(&hole, axis_origin, axis) < 0.0 {
+            hole.reverse();
+        }
+        let on_axis = vec![false; hole.len()];
+        loops.push((hole, on_axis));
     }
 
-    // Pair twin half-edges
-    pair_twin_half_edges(&mut topo, &he_map);
+    let mut vertex_cache: HashMap<[i64; 3], VertexId> = HashMap::new();
+    let mut all_faces = Vec::new();
+    let mut he_map = HalfEdgeMap::new();
+    // Start and end rings of each loop, for the side caps
+    let mut cap_loops = Vec::new();
+
+    for (positions, on_axis) in &loops {
+        let first_ring: Vec<VertexId> = positions
+            .iter()
+            .map(|&p| get_or_create_vertex(&mut vertex_cache, topo, p))
+            .collect();
+
+        let mut rings = vec![first_ring.clone()];
+        for step in 1..=steps {
+            if closed && step == steps {
+                rings.push(first_ring.clone());
+                break;
+            }
+            let step_angle = angle * step as f64 / steps as f64;
+            let ring = positions
+                .iter()
+                .zip(on_axis)
+                .zip(&first_ring)
+                .map(|((p, &on_axis), &v)| {
+                    if on_axis {
+                        v
+                    } else {
+                        let rotated = rotate_point(p, axis_origin, axis, step_angle);
+                        get_or_create_vertex(&mut vertex_cache, topo, rotated)
+                    }
+                })
+                .collect();
+            rings.push(ring);
+        }
 
-    // Build shell and solid
-    let shell = topo.add_shell(all_faces, ShellType::Outer);
-    let solid_id = topo.add_solid(shell);
+        let n = positions.len();
+        for pair in rings.windows(2) {
+            let (ring, next_ring) = (&pair[0], &pair[1]);
+            for i in 0..n {
+                let j = (i + 1) % n;
+                let quad = [ring[i], ring[j], next_ring[j], next_ring[i]];
+                if let Some(face_id) = build_polygon_face(topo, geom, &quad, &mut he_map) {
+                    all_faces.push(face_id);
+                }
+            }
+        }
+
+        // Revolution faces run start[i] -> start[i+1] along the start ring
+        // and end[i+1] -> end[i] along the end ring, so the start cap is
+        // wound in reverse to pair with them.
+        let start_cap: Vec<VertexId> = rings[0].iter().rev().copied().collect();
+        let end_cap = rings.pop().unwrap_or_default();
+        cap_loops.push([start_cap, end_cap]);
+    }
+
+    if !closed {
+        for side in 0..2 {
+            let Some(face_id) = build_polygon_face(topo, geom, &cap_loops[0][side], &mut he_map)
+            else {
+                continue;
+            };
+            for hole in &cap_loops[1..] {
+                let hes: Vec<HalfEdgeId> =
+                    hole[side].iter().map(|&v| topo.add_half_edge(v)).collect();
+                let loop_id = topo.add_loop(&hes);
+                topo.add_inner_loop(face_id, loop_id);
+                record_half_edges(topo, &hes, &mut he_map);
+            }
+            all_faces.push(face_id);
+        }
+    }
+
+    pair_twin_half_edges(topo, &he_map);
+    all_faces
+}
+
+/// Signed area of the profile in (radius, height) coordinates about the axis.
+fn radial_signed_area(positions: &[Point3], axis_origin: &Point3, axis: &Vec3) -> f64 {
+    let coords: Vec<(f64, f64)> = positions
+        .iter()
+        .map(|p| {
+            let v = p - axis_origin;
+            let t = v.dot(axis);
+            ((v - t * axis).norm(), t)
+        })
+        .collect();
+    let mut area = 0.0;
+    for (i, &(r0, t0)) in coords.iter().enumerate() {
+        let (r1, t1) = coords[(i + 1) % coords.len()];
+        area += r0 * t1 - r1 * t0;
+    }
+    area / 2.0
+}
 
-    Ok(BRepSolid {
-        topology: topo,
-        geometry: geom,
-        solid_id,
-    })
+/// Whether the profile has vertices on both sides of the axis, or lies
+/// entirely on it.
+fn crosses_axis(positions: &[Point3], axis_origin: &Point3, axis: &Vec3, tol: f64) -> bool {
+    let radial = |p: &Point3| {
+        let v = p - axis_origin;
+        v - v.dot(axis) * axis
+    };
+    let Some(reference) = positions.iter().map(radial).find(|r| r.norm() >= tol) else {
+        return true;
+    };
+    let reference = reference.normalize();
+    positions.iter().any(|p| radial(p).dot(&reference) < -tol)
 }
 
 /// Classification of a line segment for revolve surface type.
@@ -290,7 +427,7 @@ fn classify_line_segment(
 }
 
 #[allow(clippy::too_many_arguments)]
-fn build_full_cylinder_face<F>(
+fn build_full_cylinder_face(
     topo: &mut Topology,
     geom: &mut GeometryStore,
     axis_origin: &Point3,
@@ -298,12 +435,8 @@ fn build_full_cylinder_face<F>(
     radius: f64,
     v_bot: &VertexId,
     v_top: &VertexId,
-    he_map: &mut HashMap<([i64; 3], [i64; 3]), HalfEdgeId>,
-    quantize_pt: F,
-) -> vcad_kernel_topo::FaceId
-where
-    F: Fn(Point3) -> [i64; 3],
-{
+    he_map: &mut HalfEdgeMap,
+) -> FaceId {
     let cyl_surface = CylinderSurface::with_axis(*axis_origin, *axis, radius);
     let surf_idx = geom.add_surface(Box::new(cyl_surface));
 
@@ -317,31 +450,18 @@ where
     let loop_id = topo.add_loop(&[he_bot, he_seam_up, he_top, he_seam_down]);
     let face_id = topo.add_face(loop_id, surf_idx, Orientation::Forward);
 
-    // Record in he_map
-    for &he_id in &[he_bot, he_seam_up, he_top, he_seam_down] {
-        let he = &topo.half_edges[he_id];
-        let origin = topo.vertices[he.origin].point;
-        if let Some(next) = he.next {
-            let dest = topo.vertices[topo.half_edges[next].origin].point;
-            he_map.insert((quantize_pt(origin), quantize_pt(dest)), he_id);
-        }
-    }
+    record_half_edges(topo, &[he_bot, he_seam_up, he_top, he_seam_down], he_map);
 
     face_id
 }
 
-#[allow(clippy::too_many_arguments)]
-fn build_full_planar_approximation_face<F>(
+fn build_full_planar_approximation_face(
     topo: &mut Topology,
     geom: &mut GeometryStore,
     v_bot: &VertexId,
     v_top: &VertexId,
-    he_map: &mut HashMap<([i64; 3], [i64; 3]), HalfEdgeId>,
-    quantize_pt: F,
-) -> vcad_kernel_topo::FaceId
-where
-    F: Fn(Point3) -> [i64; 3],
-{
+    he_map: &mut HalfEdgeMap,
+) -> FaceId {
     // For full revolution of cone/plane, approximate with a degenerate planar face
     let p_bot = topo.vertices[*v_bot].point;
     let p_top = topo.vertices[*v_top].point;
@@ -357,104 +477,55 @@ where
     let loop_id = topo.add_loop(&[he_bot, he_seam_up, he_top, he_seam_down]);
     let face_id = topo.add_face(loop_id, surf_idx, Orientation::Forward);
 
-    for &he_id in &[he_bot, he_seam_up, he_top, he_seam_down] {
-        let he = &topo.half_edges[he_id];
-        let origin = topo.vertices[he.origin].point;
-        if let Some(next) = he.next {
-            let dest = topo.vertices[topo.half_edges[next].origin].point;
-            he_map.insert((quantize_pt(origin), quantize_pt(dest)), he_id);
-        }
-    }
+    record_half_edges(topo, &[he_bot, he_seam_up, he_top, he_seam_down], he_map);
 
     face_id
 }
 
-#[allow(clippy::too_many_arguments)]
-fn build_partial_planar_face<F>(
+/// Build a planar face through `verts`, dropping consecutive repeats left
+/// by vertices collapsed onto the axis. Returns `None` if fewer than three
+/// distinct vertices remain.
+fn build_polygon_face(
     topo: &mut Topology,
     geom: &mut GeometryStore,
-    v_start_0: &VertexId,
-    v_start_1: &VertexId,
-    v_end_1: &VertexId,
-    v_end_0: &VertexId,
-    he_map: &mut HashMap<([i64; 3], [i64; 3]), HalfEdgeId>,
-    quantize_pt: F,
-) -> vcad_kernel_topo::FaceId
-where
-    F: Fn(Point3) -> [i64; 3],
-{
-    // Use planar approximation for partial revolution faces
-    let p0 = topo.vertices[*v_start_0].point;
-    let p1 = topo.vertices[*v_start_1].point;
-    let p2 = topo.vertices[*v_end_1].point;
-
-    let x_dir = p1 - p0;
-    let y_dir = p2 - p1;
-    let plane = Plane::new(p0, x_dir, y_dir);
-    let surf_idx = geom.add_surface(Box::new(plane));
-
-    // Winding: v_start_0 -> v_start_1 -> v_end_1 -> v_end_0
-    let he0 = topo.add_half_edge(*v_start_0);
-    let he1 = topo.add_half_edge(*v_start_1);
-    let he2 = topo.add_half_edge(*v_end_1);
-    let he3 = topo.add_half_edge(*v_end_0);
-
-    let loop_id = topo.add_loop(&[he0, he1, he2, he3]);
-    let face_id = topo.add_face(loop_id, surf_idx, Orientation::Forward);
-
-    for &he_id in &[he0, he1, he2, he3] {
-        let he = &topo.half_edges[he_id];
-        let origin = topo.vertices[he.origin].point;
-        if let Some(next) = he.next {
-            let dest = topo.vertices[topo.half_edges[next].origin].point;
-            he_map.insert((quantize_pt(origin), quantize_pt(dest)), he_id);
+    verts: &[VertexId],
+    he_map: &mut HalfEdgeMap,
+) -> Option<FaceId> {
+    let mut loop_verts: Vec<VertexId> = Vec::with_capacity(verts.len());
+    for &v in verts {
+        if loop_verts.last() != Some(&v) {
+            loop_verts.push(v);
         }
     }
+    while loop_verts.len() > 1 && loop_verts.first() == loop_verts.last() {
+        loop_verts.pop();
+    }
+    if loop_verts.len() < 3 {
+        return None;
+    }
 
-    face_id
-}
-
-fn build_side_face<F>(
-    topo: &mut Topology,
-    geom: &mut GeometryStore,
-    verts: &[VertexId],
-    he_map: &mut HashMap<([i64; 3], [i64; 3]), HalfEdgeId>,
-    quantize_pt: F,
-    reversed: bool,
-) -> vcad_kernel_topo::FaceId
-where
-    F: Fn(Point3) -> [i64; 3],
-{
-    let positions: Vec<Point3> = verts.iter().map(|&v| topo.vertices[v].point).collect();
-    let n = positions.len();
-
-    // Create plane from vertices
-    let plane = if n >= 3 {
-        Plane::new(
-            positions[0],
-            positions[1] - positions[0],
-            positions[n - 1] - positions[0],
-        )
-    } else {
-        Plane::from_normal(positions[0], Vec3::z())
-    };
+    // Newell normal, so the plane agrees with the loop winding
+    let positions: Vec<Point3> = loop_verts.iter().map(|&v| topo.vertices[v].point).collect();
+    let mut normal = Vec3::zeros();
+    for (i, p) in positions.iter().enumerate() {
+        let q = positions[(i + 1) % positions.len()];
+        normal.x += (p.y - q.y) * (p.z + q.z);
+        normal.y += (p.z - q.z) * (p.x + q.x);
+        normal.z += (p.x - q.x) * (p.y + q.y);
+    }
+    let plane = Plane::from_normal(positions[0], normal);
     let surf_idx = geom.add_surface(Box::new(plane));
 
-    // Create half-edges
-    let ordered_verts: Vec<VertexId> = if reversed {
-        verts.iter().rev().copied().collect()
-    } else {
-        verts.to_vec()
-    };
-
-    let hes: Vec<HalfEdgeId> = ordered_verts
-        .iter()
-        .map(|&v| topo.add_half_edge(v))
-        .collect();
+    let hes: Vec<HalfEdgeId> = loop_verts.iter().map(|&v| topo.add_half_edge(v)).collect();
     let loop_id = topo.add_loop(&hes);
     let face_id = topo.add_face(loop_id, surf_idx, Orientation::Forward);
+    record_half_edges(topo, &hes, he_map);
+
+    Some(face_id)
+}
 
-    for &he_id in &hes {
+fn record_half_edges(topo: &Topology, hes: &[HalfEdgeId], he_map: &mut HalfEdgeMap) {
+    for &he_id in hes {
         let he = &topo.half_edges[he_id];
         let origin = topo.vertices[he.origin].point;
         if let Some(next) = he.next {
@@ -462,11 +533,9 @@ where
             he_map.insert((quantize_pt(origin), quantize_pt(dest)), he_id);
         }
     }
-
-    face_id
 }
 
-fn pair_twin_half_edges(topo: &mut Topology, he_map: &HashMap<([i64; 3], [i64; 3]), HalfEdgeId>) {
+fn pair_twin_half_edges(topo: &mut Topology, he_map: &HalfEdgeMap) {
     let mut paired = std::collections::HashSet::new();
 
     for (&(origin_key, dest_key), &he_id) in he_map {
@@ -516,6 +585,7 @@ fn rotate_point(point: &Point3, axis_origin: &Point3, axis: &Vec3, angle: f64) -
 #[cfg(test)]
 mod tests {
     use super::*;
+    use vcad_kernel_math::Point2;
 
     #[test]
     fn test_revolve_rectangle_full() {
@@ -598,20 +668,69 @@ mod tests {
 
     #[test]
     fn test_revolve_axis_intersection_error() {
-        // Profile with a vertex on the Z-axis (x=0, y=0)
-        // Rectangle at origin in XZ plane, one corner at (0,0,0) which is on Z-axis
-        let profile = SketchProfile::rectangle(
-            Point3::origin(), // Origin at (0, 0, 0) which is on Z-axis
-            Vec3::x(),
-            Vec3::z(),
-            5.0,
-            5.0,
-        );
+        // Rectangle spanning x = -2..3 crosses the Z-axis
+        let profile =
+            SketchProfile::rectangle(Point3::new(-2.0, 0.0, 0.0), Vec3::x(), Vec3::z(), 5.0, 5.0);
 
         let result = revolve(&profile, Point3::origin(), Vec3::z(), PI);
         assert!(matches!(result, Err(SketchError::AxisIntersection)));
     }
 
+    #[test]
+    fn test_revolve_90_degrees_watertight() {
+        let profile =
+            SketchProfile::rectangle(Point3::new(5.0, 0.0, 0.0), Vec3::x(), Vec3::z(), 3.0, 10.0);
+
+        let solid = revolve(&profile, Point3::origin(), Vec3::z(), PI / 2.0).unwrap();
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&solid, 64);
+
+        assert!(mesh.is_watertight(), "{:?}", mesh.boundary_edges());
+        assert!(
+            signed_mesh_volume(&mesh) > 0.0,
+            "faces should point outward"
+        );
+    }
+
+    #[test]
+    fn test_revolve_touching_axis_partial() {
+        // Rectangle x = 0..5 has its left edge on the Z-axis
+        let profile = SketchProfile::rectangle(Point3::origin(), Vec3::x(), Vec3::z(), 5.0, 10.0);
+
+        let solid = revolve(&profile, Point3::origin(), Vec3::z(), PI).unwrap();
+
+        // Bottom and top collapse to triangles, the axis edge adds no face
+        assert_eq!(solid.topology.faces.len(), 5);
+        assert_eq!(solid.topology.vertices.len(), 6);
+
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&solid, 64);
+        assert!(mesh.is_watertight(), "{:?}", mesh.boundary_edges());
+    }
+
+    #[test]
+    fn test_revolve_touching_axis_full() {
+        let profile = SketchProfile::rectangle(Point3::origin(), Vec3::x(), Vec3::z(), 5.0, 10.0);
+
+        let solid = revolve(&profile, Point3::origin(), Vec3::z(), 2.0 * PI).unwrap();
+
+        let unpaired = solid
+            .topology
+            .half_edges
+            .values()
+            .filter(|he| he.twin.is_none())
+            .count();
+        assert_eq!(unpaired, 0, "all half-edges should be paired");
+
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&solid, 64);
+        assert!(mesh.is_watertight(), "{:?}", mesh.boundary_edges());
+
+        let vol = signed_mesh_volume(&mesh);
+        let expected = PI * 5.0 * 5.0 * 10.0;
+        assert!(
+            (vol - expected).abs() < expected * 0.02,
+            "expected volume ~{expected:.1}, got {vol:.1}"
+        );
+    }
+
     #[test]
     fn test_revolve_90_degrees_volume() {
         // Rectangle profile: inner radius 5, outer radius 8, height 10
@@ -692,6 +811,10 @@ mod tests {
     }
 
     fn compute_mesh_volume(mesh: &vcad_kernel_tessellate::TriangleMesh) -> f64 {
+        signed_mesh_volume(mesh).abs()
+    }
+
+    fn signed_mesh_volume(mesh: &vcad_kernel_tessellate::TriangleMesh) -> f64 {
         let verts = &mesh.vertices;
         let indices = &mesh.indices;
         let mut vol = 0.0;
@@ -708,6 +831,54 @@ mod tests {
                 - v1[0] * (v0[1] * v2[2] - v2[1] * v0[2])
                 + v2[0] * (v0[1] * v1[2] - v1[1] * v0[2]);
         }
-        (vol / 6.0).abs()
+        vol / 6.0
+    }
+
+    #[test]
+    fn test_revolve_profile_with_hole() {
+        // Rectangle from radius 5 to 8, height 10, with a 1x2 hole from
+        // radius 6 to 7 at height 4 (sketch X is radius, Y is height)
+        let outer =
+            SketchProfile::rectangle(Point3::new(5.0, 0.0, 0.0), Vec3::x(), Vec3::z(), 3.0, 10.0);
+        let pts = [
+            Point2::new(1.0, 4.0),
+            Point2::new(2.0, 4.0),
+            Point2::new(2.0, 6.0),
+            Point2::new(1.0, 6.0),
+        ];
+        let hole: Vec<SketchSegment> = (0..4)
+            .map(|i| SketchSegment::Line {
+                start: pts[i],
+                end: pts[(i + 1) % 4],
+            })
+            .collect();
+        let profile = outer.clone().with_holes(vec![hole.clone()]).unwrap();
+        let inner = SketchProfile::new(outer.origin, Vec3::x(), Vec3::z(), hole).unwrap();
+
+        let volume = |profile: &SketchProfile, angle: f64| {
+            let solid = revolve(profile, Point3::origin(), Vec3::z(), angle).unwrap();
+            assert!(solid
+                .topology
+                .half_edges
+                .values()
+                .all(|he| he.twin.is_some()));
+            let mesh = vcad_kernel_tessellate::tessellate_brep(&solid, 64);
+            compute_mesh_volume(&mesh)
+        };
+
+        // Partial revolves facet every loop the same way, so volumes subtract exactly
+        let partial = volume(&profile, PI / 2.0);
+        let expected = volume(&outer, PI / 2.0) - volume(&inner, PI / 2.0);
+        assert!(
+            (partial - expected).abs() < 1e-9 * expected,
+            "{partial} != {expected}"
+        );
+
+        let full = volume(&profile, 2.0 * PI);
+        let expected = PI * ((64.0 - 25.0) * 10.0 - (49.0 - 36.0) * 2.0);
+        assert!(
+            (full - expected).abs() < expected * 0.02,
+            "expected volume ~{expected:.1}, got {full:.1}"
+        );
     }
 }
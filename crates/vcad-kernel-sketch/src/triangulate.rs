@@ -0,0 +1,126 @@
+//! Triangulation of planar sketch profiles.
+//!
+//! Profiles are projected to 2D and handed to
+//! [`vcad_kernel_math::triangulate`], the constrained Delaunay triangulator
+//! tessellation also uses for planar faces.
+
+pub use vcad_kernel_math::triangulate::triangulate_loops;
+
+use crate::SketchProfile;
+
+/// Triangulate a profile's polygon.
+///
+/// Indices refer to [`SketchProfile::vertices_2d`]. Arcs contribute only their
+/// endpoints, so call [`SketchProfile::tessellate`] first for curved profiles.
+/// Triangles are counter-clockwise in the profile's 2D coordinates regardless
+/// of the profile's winding.
+pub fn triangulate_polygon(profile: &SketchProfile) -> Vec<[usize; 3]> {
+    triangulate_loops(&[profile.vertices_2d()])
+}
+
+/// Triangulate a profile with holes.
+///
+/// Hole vertices are projected onto `outer`'s plane. Indices refer to the
+/// concatenation of `outer.vertices_2d()` followed by each hole's vertices in
+/// order.
+pub fn triangulate_polygon_with_holes(
+    outer: &SketchProfile,
+    holes: &[SketchProfile],
+) -> Vec<[usize; 3]> {
+    let mut loops = vec![outer.vertices_2d()];
+    loops.extend(holes.iter().map(|hole| {
+        hole.vertices_3d()
+            .into_iter()
+            .map(|p| outer.to_2d(p))
+            .collect::<Vec<_>>()
+    }));
+    triangulate_loops(&loops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vcad_kernel_math::predicates::{orient2d, Sign};
+    use vcad_kernel_math::{Point2, Point3, Vec3};
+
+    fn polygon(points: &[(f64, f64)]) -> SketchProfile {
+        let segments = (0..points.len())
+            .map(|i| {
+                let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                crate::SketchSegment::Line {
+                    start: Point2::new(a.0, a.1),
+                    end: Point2::new(b.0, b.1),
+                }
+            })
+            .collect();
+        SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments).unwrap()
+    }
+
+    fn triangle_area(points: &[Point2], t: &[usize; 3]) -> f64 {
+        let (a, b, c) = (points[t[0]], points[t[1]], points[t[2]]);
+        ((b - a).x * (c - a).y - (b - a).y * (c - a).x) / 2.0
+    }
+
+    const L_SHAPE: [(f64, f64); 6] = [
+        (0.0, 0.0),
+        (10.0, 0.0),
+        (10.0, 5.0),
+        (5.0, 5.0),
+        (5.0, 10.0),
+        (0.0, 10.0),
+    ];
+
+    #[test]
+    fn test_triangulate_l_shape() {
+        let profile = polygon(&L_SHAPE);
+        let points = profile.vertices_2d();
+        let triangles = triangulate_polygon(&profile);
+
+        assert_eq!(triangles.len(), 4);
+        for t in &triangles {
+            assert_eq!(
+                orient2d(&points[t[0]], &points[t[1]], &points[t[2]]),
+                Sign::Positive
+            );
+        }
+        let area: f64 = triangles.iter().map(|t| triangle_area(&points, t)).sum();
+        assert!((area - profile.signed_area()).abs() < 1e-9);
+        assert!((area - 75.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_triangulate_clockwise_input() {
+        let mut reversed = L_SHAPE;
+        reversed.reverse();
+        let profile = polygon(&reversed);
+        let points = profile.vertices_2d();
+        let triangles = triangulate_polygon(&profile);
+
+        assert!(triangles.iter().all(|t| triangle_area(&points, t) > 0.0));
+        let area: f64 = triangles.iter().map(|t| triangle_area(&points, t)).sum();
+        assert!((area - 75.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_triangulate_with_hole() {
+        let outer = polygon(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]);
+        let hole = polygon(&[(3.0, 3.0), (7.0, 3.0), (7.0, 7.0), (3.0, 7.0)]);
+        let triangles = triangulate_polygon_with_holes(&outer, std::slice::from_ref(&hole));
+
+        let mut points = outer.vertices_2d();
+        points.extend(hole.vertices_2d());
+        // n + 2h - 2 triangles for n vertices and h holes.
+        assert_eq!(triangles.len(), 8);
+        assert!(triangles.iter().all(|t| triangle_area(&points, t) > 0.0));
+        let area: f64 = triangles.iter().map(|t| triangle_area(&points, t)).sum();
+        assert!((area - 84.0).abs() < 1e-9);
+
+        // No triangle may cover the hole's center.
+        let center = Point2::new(5.0, 5.0);
+        assert!(!triangles.iter().any(|t| {
+            (0..3).all(|k| {
+                orient2d(&points[t[k]], &points[t[(k + 1) % 3]], &center) == Sign::Positive
+            })
+        }));
+    }
+}
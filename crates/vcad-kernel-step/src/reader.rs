@@ -255,11 +255,7 @@ impl<'a> StepReader<'a> {
         let shell_id = topo.add_shell(vcad_face_ids, ShellType::Outer);
         let solid_id = topo.add_solid(shell_id);
 
-        Ok(BRepSolid {
-            topology: topo,
-            geometry: geom,
-            solid_id,
-        })
+        Ok(BRepSolid::new(topo, geom, solid_id))
     }
 }
 
@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::io::Write;
 use std::path::Path;
 
+use crate::entities::curves::write_circle;
 use crate::entities::{
     cylinder_to_placement, plane_to_placement, sphere_to_placement, torus_to_placement,
     write_advanced_face, write_axis2_placement_3d, write_cartesian_point, write_closed_shell,
@@ -14,13 +15,21 @@ use crate::entities::{
 };
 use crate::error::StepError;
 
-use vcad_kernel_geom::{ConeSurface, CylinderSurface, Plane, SphereSurface, SurfaceKind, TorusSurface};
-use vcad_kernel_math::{Dir3, Vec3};
+use vcad_kernel_geom::{
+    Circle3d, ConeSurface, CylinderSurface, Plane, SphereSurface, SurfaceKind, TorusSurface,
+};
+use vcad_kernel_math::{Dir3, Point3, Vec3};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_topo::{EdgeId, FaceId, HalfEdgeId, LoopId, Orientation, VertexId};
 
 /// Write a BRepSolid to a STEP file.
 ///
+/// The file follows AP203 (`CONFIG_CONTROL_DESIGN`): the solid is a
+/// `MANIFOLD_SOLID_BREP` in an `ADVANCED_BREP_SHAPE_REPRESENTATION` in
+/// millimetres, attached to a single `PRODUCT` so other CAD systems pick it
+/// up as a part. Closed edges on cylindrical faces are written as circles,
+/// all other edges as lines.
+///
 /// # Arguments
 ///
 /// * `solid` - The B-rep solid to write
@@ -104,7 +113,8 @@ impl<'a> StepWriter<'a> {
         self.write_loops()?;
         self.write_faces()?;
         let shell_id = self.write_shell()?;
-        let _solid_id = self.write_solid(shell_id)?;
+        let solid_id = self.write_solid(shell_id)?;
+        self.write_product(solid_id);
 
         // Assemble full file
         let mut buffer = Vec::new();
@@ -121,7 +131,7 @@ impl<'a> StepWriter<'a> {
             "FILE_NAME('model.step', '{}', ('vcad'), ('vcad'), 'vcad-kernel-step', 'vcad', '');",
             chrono_lite_date()
         )?;
-        writeln!(buffer, "FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));")?;
+        writeln!(buffer, "FILE_SCHEMA(('CONFIG_CONTROL_DESIGN'));")?;
         writeln!(buffer, "ENDSEC;")?;
         writeln!(buffer, "DATA;")?;
 
@@ -293,10 +303,25 @@ impl<'a> StepWriter<'a> {
             let start_vertex = self.vertex_map[&start_vid];
             let end_vertex = self.vertex_map[&end_vid];
 
-            // Write line geometry for the edge
             let start_point = topo.vertices[start_vid].point;
             let end_point = topo.vertices[end_vid].point;
 
+            // A closed edge on a cylinder is one of its circular rims
+            if start_vid == end_vid {
+                if let Some(cyl) = self.edge_cylinder(edge.half_edge) {
+                    let circle_id = self.write_rim_circle(cyl, &start_point)?;
+                    let same_sense = self.rim_same_sense(edge.half_edge, cyl);
+                    let step_edge_id = self.alloc_id();
+                    let entity =
+                        write_edge_curve("", start_vertex, end_vertex, circle_id, same_sense);
+                    self.emit(step_edge_id, &entity);
+                    self.edge_map.insert(edge_id, step_edge_id);
+                    continue;
+                }
+            }
+
+            // Write line geometry for the edge
+
             let dir_vec = end_point - start_point;
             let magnitude = dir_vec.norm();
             let dir = if magnitude > 1e-15 {
@@ -337,6 +362,106 @@ impl<'a> StepWriter<'a> {
         Ok(())
     }
 
+    /// The cylindrical surface of a face on either side of an edge, if any.
+    fn edge_cylinder(&self, half_edge: HalfEdgeId) -> Option<&'a CylinderSurface> {
+        let solid = self.solid;
+        let topo = &solid.topology;
+        let he = &topo.half_edges[half_edge];
+        [Some(half_edge), he.twin]
+            .into_iter()
+            .flatten()
+            .filter_map(|he| topo.loops[topo.half_edges[he].loop_id?].face)
+            .map(|face| &solid.geometry.surfaces[topo.faces[face].surface_index])
+            .find_map(|surface| surface.as_any().downcast_ref::<CylinderSurface>())
+    }
+
+    /// Whether a closed half-edge on a cylinder's rim runs counter-clockwise
+    /// about the cylinder's axis, the direction of the circle
+    /// [`write_rim_circle`](Self::write_rim_circle) writes for it.
+    ///
+    /// A closed half-edge has no direction of its own; it follows from the
+    /// face it bounds, whose loops run counter-clockwise about its outward
+    /// normal. A planar face (a cap) gives that normal directly. On the
+    /// cylindrical face itself the bottom rim runs counter-clockwise and the
+    /// top rim clockwise. The twin runs the other way.
+    fn rim_same_sense(&self, half_edge: HalfEdgeId, cyl: &CylinderSurface) -> bool {
+        let topo = &self.solid.topology;
+        let axis = cyl.axis.as_ref();
+        // Each side: the half-edge, its loop and face, and whether it is
+        // the twin
+        let sides: Vec<_> = [
+            (Some(half_edge), false),
+            (topo.half_edges[half_edge].twin, true),
+        ]
+        .into_iter()
+        .filter_map(|(he, twin)| {
+            let he = he?;
+            let loop_id = topo.half_edges[he].loop_id?;
+            let face = &topo.faces[topo.loops[loop_id].face?];
+            Some((he, loop_id, face, twin))
+        })
+        .collect();
+
+        for &(_, _, face, twin) in &sides {
+            let surface = self.solid.geometry.surfaces[face.surface_index].as_ref();
+            if let Some(plane) = surface.as_any().downcast_ref::<Plane>() {
+                let up = plane.normal_dir.as_ref().dot(axis) > 0.0;
+                let reversed = face.orientation == Orientation::Reversed;
+                return up ^ reversed ^ twin;
+            }
+        }
+
+        for &(he, loop_id, face, twin) in &sides {
+            let surface = self.solid.geometry.surfaces[face.surface_index].as_ref();
+            if !surface.as_any().is::<CylinderSurface>() {
+                continue;
+            }
+            // The rim is the bottom one unless the face reaches below it
+            let height = |v: VertexId| (topo.vertices[v].point - cyl.center).dot(axis);
+            let rim = height(topo.half_edges[he].origin);
+            let bottom = topo
+                .loop_vertices(loop_id)
+                .into_iter()
+                .all(|v| height(v) >= rim - 1e-9);
+            let reversed = face.orientation == Orientation::Reversed;
+            return bottom ^ reversed ^ twin;
+        }
+
+        true
+    }
+
+    /// Write the circle around a cylinder's axis through `point`, starting
+    /// at that point. Returns the CIRCLE's ID.
+    fn write_rim_circle(
+        &mut self,
+        cyl: &CylinderSurface,
+        point: &Point3,
+    ) -> Result<u64, StepError> {
+        let axis = cyl.axis.as_ref();
+        let center = cyl.center + axis * (point - cyl.center).dot(axis);
+        let radial = point - center;
+        let ref_direction = if radial.norm() > 1e-15 {
+            Dir3::new_normalize(radial)
+        } else {
+            cyl.ref_dir
+        };
+        let placement_id = self.write_axis_placement(&AxisPlacement {
+            location: center,
+            axis: Some(cyl.axis),
+            ref_direction: Some(ref_direction),
+        })?;
+        let circle = Circle3d {
+            center,
+            radius: cyl.radius,
+            x_dir: ref_direction,
+            y_dir: Dir3::new_normalize(axis.cross(ref_direction.as_ref())),
+            normal: cyl.axis,
+        };
+        let circle_id = self.alloc_id();
+        self.emit(circle_id, &write_circle(&circle, "", placement_id));
+        Ok(circle_id)
+    }
+
     fn write_loops(&mut self) -> Result<(), StepError> {
         let topo = &self.solid.topology;
 
@@ -431,6 +556,56 @@ impl<'a> StepWriter<'a> {
         self.emit(solid_id, &entity);
         Ok(solid_id)
     }
+
+    /// Emit an entity under a fresh ID and return the ID.
+    fn push(&mut self, entity: &str) -> u64 {
+        let id = self.alloc_id();
+        self.emit(id, entity);
+        id
+    }
+
+    /// Wrap the solid in the AP203 product structure: a single part whose
+    /// shape representation is in millimetres and radians.
+    fn write_product(&mut self, solid_id: u64) {
+        let app = self.push(
+            "APPLICATION_CONTEXT('configuration controlled 3d designs of mechanical parts and assemblies')",
+        );
+        self.push(&format!(
+            "APPLICATION_PROTOCOL_DEFINITION('international standard', 'config_control_design', 1994, #{app})"
+        ));
+        let product_context = self.push(&format!("MECHANICAL_CONTEXT('', #{app}, 'mechanical')"));
+        let product = self.push(&format!(
+            "PRODUCT('Solid', 'Solid', '', (#{product_context}))"
+        ));
+        let formation = self.push(&format!(
+            "PRODUCT_DEFINITION_FORMATION_WITH_SPECIFIED_SOURCE('', '', #{product}, .NOT_KNOWN.)"
+        ));
+        let design_context = self.push(&format!("DESIGN_CONTEXT('', #{app}, 'design')"));
+        let definition = self.push(&format!(
+            "PRODUCT_DEFINITION('design', '', #{formation}, #{design_context})"
+        ));
+        let shape = self.push(&format!("PRODUCT_DEFINITION_SHAPE('', '', #{definition})"));
+
+        // Complex instances list their types in alphabetical order
+        let length = self.push("(LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI., .METRE.))");
+        let angle = self.push("(NAMED_UNIT(*) PLANE_ANGLE_UNIT() SI_UNIT($, .RADIAN.))");
+        let solid_angle = self.push("(NAMED_UNIT(*) SI_UNIT($, .STERADIAN.) SOLID_ANGLE_UNIT())");
+        let uncertainty = self.push(&format!(
+            "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.0E-7), #{length}, 'distance_accuracy_value', 'confusion accuracy')"
+        ));
+        let context = self.push(&format!(
+            "(GEOMETRIC_REPRESENTATION_CONTEXT(3) \
+             GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{uncertainty})) \
+             GLOBAL_UNIT_ASSIGNED_CONTEXT((#{length}, #{angle}, #{solid_angle})) \
+             REPRESENTATION_CONTEXT('', '3D'))"
+        ));
+        let representation = self.push(&format!(
+            "ADVANCED_BREP_SHAPE_REPRESENTATION('', (#{solid_id}), #{context})"
+        ));
+        self.push(&format!(
+            "SHAPE_DEFINITION_REPRESENTATION(#{shape}, #{representation})"
+        ));
+    }
 }
 
 /// Simple date string without external chrono dependency.
@@ -443,7 +618,7 @@ fn chrono_lite_date() -> String {
 mod tests {
     use super::*;
     use crate::reader::read_step_from_buffer;
-    use vcad_kernel_primitives::make_cube;
+    use vcad_kernel_primitives::{make_cube, BoxOrigin};
 
     #[test]
     fn test_write_cube() {
@@ -488,4 +663,83 @@ mod tests {
             imported.geometry.surfaces.len()
         );
     }
+
+    #[test]
+    fn test_cube_entities_reparse() {
+        let buffer = write_step_to_buffer(&make_cube(10.0, 20.0, 30.0)).unwrap();
+        let file = stepperoni::Parser::parse(&buffer).unwrap();
+        let count = |type_name: &str| file.entities_of_type(type_name).len();
+
+        assert_eq!(file.header().schema, ["CONFIG_CONTROL_DESIGN"]);
+        assert_eq!(count("MANIFOLD_SOLID_BREP"), 1);
+        assert_eq!(count("ADVANCED_FACE"), 6);
+        assert_eq!(count("EDGE_CURVE"), 12);
+        assert_eq!(count("VERTEX_POINT"), 8);
+        assert_eq!(count("PRODUCT"), 1);
+        assert_eq!(count("ADVANCED_BREP_SHAPE_REPRESENTATION"), 1);
+        assert_eq!(count("SHAPE_DEFINITION_REPRESENTATION"), 1);
+    }
+
+    #[test]
+    fn test_cylinder_rims_are_circles() {
+        let cylinder = vcad_kernel_primitives::make_cylinder(5.0, 10.0, 32);
+        let buffer = write_step_to_buffer(&cylinder).unwrap();
+        let file = stepperoni::Parser::parse(&buffer).unwrap();
+
+        let circles = file.entities_of_type("CIRCLE");
+        assert_eq!(circles.len(), 2);
+        for circle in circles {
+            assert_eq!(circle.args[2].as_real(), Some(5.0));
+        }
+        assert_eq!(file.entities_of_type("LINE").len(), 1);
+        assert_eq!(file.entities_of_type("CYLINDRICAL_SURFACE").len(), 1);
+
+        let solids = read_step_from_buffer(&buffer).unwrap();
+        assert_eq!(solids[0].topology.faces.len(), 3);
+    }
+
+    #[test]
+    fn test_cylinder_cap_loops_are_counter_clockwise() {
+        use crate::entities::curves::parse_circle;
+        use crate::entities::surfaces::parse_plane;
+        use crate::entities::{
+            parse_advanced_face, parse_edge_curve, parse_edge_loop, parse_oriented_edge,
+        };
+
+        for origin in [BoxOrigin::Corner, BoxOrigin::Center] {
+            let cylinder = vcad_kernel_primitives::make_cylinder_at(5.0, 10.0, 32, origin);
+            let buffer = write_step_to_buffer(&cylinder).unwrap();
+            let file = stepperoni::Parser::parse(&buffer).unwrap();
+
+            let mut caps = 0;
+            for entity in file.entities_of_type("ADVANCED_FACE") {
+                let face = parse_advanced_face(&file, entity.id).unwrap();
+                let Ok(plane) = parse_plane(&file, face.surface_id) else {
+                    continue;
+                };
+                let sign = if face.same_sense { 1.0 } else { -1.0 };
+                let outward = plane.normal_dir.as_ref() * sign;
+
+                // Each cap is bounded by one circle, which must run
+                // counter-clockwise about the cap's outward normal
+                let bound = &face.bounds[0];
+                let edge_loop = parse_edge_loop(&file, bound.loop_id).unwrap();
+                let oriented = parse_oriented_edge(&file, edge_loop.edge_ids[0]).unwrap();
+                let edge = parse_edge_curve(&file, oriented.edge_id).unwrap();
+                let circle = parse_circle(&file, edge.curve_id).unwrap();
+                let forward = oriented.orientation == edge.same_sense;
+                let sign = if forward == bound.orientation {
+                    1.0
+                } else {
+                    -1.0
+                };
+                assert!(circle.normal.as_ref().dot(&outward) * sign > 0.0);
+                caps += 1;
+            }
+            assert_eq!(caps, 2);
+
+            let solids = read_step_from_buffer(&buffer).unwrap();
+            assert_eq!(solids[0].topology.faces.len(), 3);
+        }
+    }
 }
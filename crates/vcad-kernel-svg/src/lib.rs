@@ -0,0 +1,281 @@
+#![warn(missing_docs)]
+
+//! SVG path import for the vcad kernel.
+//!
+//! Converts SVG path data (the `d` attribute of a `<path>`) into closed
+//! sketch loops that can be extruded, e.g. to turn a logo into a solid.
+//!
+//! # Example
+//!
+//! ```
+//! use vcad_kernel_svg::parse_svg_path;
+//!
+//! let loops = parse_svg_path("M0 0 L10 0 L10 10 L0 10 Z", 1.0).unwrap();
+//! assert_eq!(loops.len(), 1);
+//! assert_eq!(loops[0].segments.len(), 4);
+//! ```
+
+mod path;
+
+use thiserror::Error;
+use vcad_ir::{SketchSegment2D, Vec2};
+
+/// Samples per segment used to measure winding and containment.
+const SAMPLES_PER_SEGMENT: usize = 8;
+
+/// Errors from SVG import.
+#[derive(Debug, Clone, Error)]
+pub enum SvgError {
+    /// The path data could not be parsed.
+    #[error("invalid SVG path at byte {offset}: {message}")]
+    Parse {
+        /// Byte offset into the path data.
+        offset: usize,
+        /// Description of the problem.
+        message: String,
+    },
+}
+
+/// One closed loop of an imported path.
+#[derive(Debug, Clone)]
+pub struct SvgLoop {
+    /// Connected segments; the last ends where the first starts.
+    pub segments: Vec<SketchSegment2D>,
+    /// Whether this loop is a hole in an enclosing loop.
+    ///
+    /// Outer loops wind counter-clockwise and holes clockwise.
+    pub is_hole: bool,
+}
+
+/// An outer loop of an imported path with the holes directly inside it,
+/// ready to become one sketch profile.
+#[derive(Debug, Clone)]
+pub struct SvgOutline {
+    /// The outer boundary, counter-clockwise.
+    pub segments: Vec<SketchSegment2D>,
+    /// Holes cut out of it, each clockwise.
+    pub holes: Vec<Vec<SketchSegment2D>>,
+}
+
+/// Parse SVG path data into closed sketch loops.
+///
+/// Each subpath becomes one loop. Coordinates are multiplied by `scale` and
+/// Y is flipped, since SVG's Y axis points down while sketches use Y up.
+/// Loops nested inside an odd number of other loops are holes (even-odd
+/// fill); loops are re-oriented so outer boundaries wind counter-clockwise
+/// and holes clockwise, whatever their direction in the file.
+///
+/// Béziers and elliptical arcs are flattened into lines; circular arcs are
+/// kept as exact arcs.
+pub fn parse_svg_path(path_data: &str, scale: f64) -> Result<Vec<SvgLoop>, SvgError> {
+    let loops: Vec<Vec<SketchSegment2D>> = path::parse_subpaths(path_data)?
+        .into_iter()
+        .map(|segments| segments.iter().map(|s| to_sketch(s, scale)).collect())
+        .collect();
+
+    let polygons: Vec<Vec<Vec2>> = loops.iter().map(|l| sample_loop(l)).collect();
+    Ok(loops
+        .into_iter()
+        .enumerate()
+        .map(|(i, segments)| {
+            let probe = polygons[i][0];
+            let depth = polygons
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| j != i && contains(other, probe))
+                .count();
+            let is_hole = depth % 2 == 1;
+            let ccw = signed_area(&polygons[i]) > 0.0;
+            let segments = if ccw == is_hole {
+                reverse_loop(&segments)
+            } else {
+                segments
+            };
+            SvgLoop { segments, is_hole }
+        })
+        .collect())
+}
+
+/// Parse SVG path data into outlines, one per outer loop.
+///
+/// Loops are found as by [`parse_svg_path`]; each hole is attached to the
+/// innermost outer loop around it. An island inside a hole is an outline
+/// of its own.
+pub fn parse_svg_outlines(path_data: &str, scale: f64) -> Result<Vec<SvgOutline>, SvgError> {
+    let loops = parse_svg_path(path_data, scale)?;
+    let polygons: Vec<Vec<Vec2>> = loops.iter().map(|l| sample_loop(&l.segments)).collect();
+
+    // Index of each outer loop's outline
+    let mut outline_of = vec![None; loops.len()];
+    let mut outlines = Vec::new();
+    for (i, l) in loops.iter().enumerate().filter(|(_, l)| !l.is_hole) {
+        outline_of[i] = Some(outlines.len());
+        outlines.push(SvgOutline {
+            segments: l.segments.clone(),
+            holes: Vec::new(),
+        });
+    }
+    for (i, l) in loops.into_iter().enumerate().filter(|(_, l)| l.is_hole) {
+        // Of the outer loops around the hole, the innermost is the smallest
+        let probe = polygons[i][0];
+        let host = (0..polygons.len())
+            .filter_map(|j| outline_of[j].map(|k| (j, k)))
+            .filter(|&(j, _)| contains(&polygons[j], probe))
+            .min_by(|&(a, _), &(b, _)| {
+                signed_area(&polygons[a]).total_cmp(&signed_area(&polygons[b]))
+            });
+        if let Some((_, k)) = host {
+            outlines[k].holes.push(l.segments);
+        }
+    }
+    Ok(outlines)
+}
+
+/// Map a segment from SVG coordinates into sketch coordinates.
+fn to_sketch(segment: &SketchSegment2D, scale: f64) -> SketchSegment2D {
+    let map = |p: &Vec2| Vec2::new(p.x * scale, -p.y * scale);
+    match segment {
+        SketchSegment2D::Line { start, end } => SketchSegment2D::Line {
+            start: map(start),
+            end: map(end),
+        },
+        // Flipping Y mirrors the arc, so its direction flips too.
+        SketchSegment2D::Arc {
+            start,
+            end,
+            center,
+            ccw,
+        } => SketchSegment2D::Arc {
+            start: map(start),
+            end: map(end),
+            center: map(center),
+            ccw: !ccw,
+        },
+    }
+}
+
+/// Traverse a loop in the opposite direction.
+fn reverse_loop(segments: &[SketchSegment2D]) -> Vec<SketchSegment2D> {
+    segments
+        .iter()
+        .rev()
+        .map(|s| match s {
+            SketchSegment2D::Line { start, end } => SketchSegment2D::Line {
+                start: *end,
+                end: *start,
+            },
+            SketchSegment2D::Arc {
+                start,
+                end,
+                center,
+                ccw,
+            } => SketchSegment2D::Arc {
+                start: *end,
+                end: *start,
+                center: *center,
+                ccw: !ccw,
+            },
+        })
+        .collect()
+}
+
+/// Approximate a loop by a polygon, following arcs.
+fn sample_loop(segments: &[SketchSegment2D]) -> Vec<Vec2> {
+    segments
+        .iter()
+        .flat_map(|s| {
+            let n = match s {
+                SketchSegment2D::Line { .. } => 1,
+                SketchSegment2D::Arc { .. } => SAMPLES_PER_SEGMENT,
+            };
+            (0..n).map(move |k| s.point_at(k as f64 / n as f64))
+        })
+        .collect()
+}
+
+fn signed_area(polygon: &[Vec2]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Even-odd point-in-polygon test.
+fn contains(polygon: &[Vec2], p: Vec2) -> bool {
+    let n = polygon.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rectangle_path() {
+        let loops = parse_svg_path("M0 0 L10 0 L10 10 L0 10 Z", 1.0).unwrap();
+        assert_eq!(loops.len(), 1);
+        let rect = &loops[0];
+        assert!(!rect.is_hole);
+        assert_eq!(rect.segments.len(), 4);
+        assert!(rect
+            .segments
+            .iter()
+            .all(|s| matches!(s, SketchSegment2D::Line { .. })));
+
+        // Closed and connected.
+        let n = rect.segments.len();
+        for i in 0..n {
+            assert_eq!(
+                rect.segments[i].point_at(1.0),
+                rect.segments[(i + 1) % n].point_at(0.0)
+            );
+        }
+        // Y is flipped, and the loop is re-oriented counter-clockwise.
+        assert!(signed_area(&sample_loop(&rect.segments)) > 0.0);
+        assert!(rect.segments.iter().all(|s| s.point_at(0.0).y <= 0.0));
+    }
+
+    #[test]
+    fn test_holes_by_nesting() {
+        // A square with a square hole, both drawn in the same direction,
+        // plus a separate island.
+        let d = "M0 0 H30 V30 H0 Z M10 10 H20 V20 H10 Z M40 0 h5 v5 h-5 z";
+        let loops = parse_svg_path(d, 2.0).unwrap();
+        assert_eq!(loops.len(), 3);
+        assert_eq!(
+            loops.iter().map(|l| l.is_hole).collect::<Vec<_>>(),
+            vec![false, true, false]
+        );
+        for l in &loops {
+            let area = signed_area(&sample_loop(&l.segments));
+            assert_eq!(area < 0.0, l.is_hole);
+        }
+        let outer_area = signed_area(&sample_loop(&loops[0].segments));
+        assert!((outer_area - 3600.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_outlines_group_holes() {
+        // A square with a hole holding an island, plus a separate square
+        let d = "M0 0 H30 V30 H0 Z M5 5 H25 V25 H5 Z M10 10 H20 V20 H10 Z M40 0 h5 v5 h-5 z";
+        let outlines = parse_svg_outlines(d, 1.0).unwrap();
+        assert_eq!(outlines.len(), 3);
+        assert_eq!(
+            outlines.iter().map(|o| o.holes.len()).collect::<Vec<_>>(),
+            vec![1, 0, 0]
+        );
+        let hole_area = signed_area(&sample_loop(&outlines[0].holes[0]));
+        assert!((hole_area + 400.0).abs() < 1e-9);
+    }
+}
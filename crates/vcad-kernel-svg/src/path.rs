@@ -0,0 +1,427 @@
+//! SVG path-data (`d` attribute) parser.
+//!
+//! Produces one closed loop of [`SketchSegment2D`]s per subpath, in SVG user
+//! coordinates (Y down). Circular arcs stay exact arcs; Béziers and elliptical
+//! arcs are flattened into line segments.
+
+use std::f64::consts::{PI, TAU};
+
+use vcad_ir::{SketchSegment2D, Vec2};
+
+use crate::SvgError;
+
+/// Line segments used to flatten one Bézier curve.
+const BEZIER_SEGMENTS: usize = 16;
+
+/// Largest angle (radians) covered by one line of a flattened elliptical arc.
+const ELLIPSE_STEP: f64 = PI / 16.0;
+
+/// Points closer than this are treated as coincident.
+const EPS: f64 = 1e-9;
+
+/// Parse path data into closed subpaths.
+///
+/// Supports `M L H V C S Q T A Z` in absolute and relative forms. Open
+/// subpaths are closed with a straight line, as a filled shape would be.
+pub(crate) fn parse_subpaths(d: &str) -> Result<Vec<Vec<SketchSegment2D>>, SvgError> {
+    let mut lexer = Lexer::new(d);
+    let mut builder = Builder::new();
+    let mut command: Option<char> = None;
+
+    loop {
+        lexer.skip_separators();
+        let Some(c) = lexer.peek() else {
+            break;
+        };
+        let cmd = if c.is_ascii_alphabetic() {
+            lexer.bump();
+            c
+        } else {
+            // Implicit repetition; extra pairs after a moveto are linetos.
+            match command {
+                Some('M') => 'L',
+                Some('m') => 'l',
+                Some(prev) if !matches!(prev, 'Z' | 'z') => prev,
+                _ => return Err(lexer.error("expected a command")),
+            }
+        };
+        command = Some(cmd);
+        let rel = cmd.is_ascii_lowercase();
+        let base = if rel {
+            builder.current
+        } else {
+            Vec2::new(0.0, 0.0)
+        };
+
+        match cmd.to_ascii_uppercase() {
+            'M' => {
+                let p = lexer.point(base)?;
+                builder.move_to(p);
+            }
+            'L' => {
+                let p = lexer.point(base)?;
+                builder.line_to(p);
+            }
+            'H' => {
+                let x = lexer.number()? + base.x;
+                builder.line_to(Vec2::new(x, builder.current.y));
+            }
+            'V' => {
+                let y = lexer.number()? + base.y;
+                builder.line_to(Vec2::new(builder.current.x, y));
+            }
+            'C' => {
+                let c1 = lexer.point(base)?;
+                let c2 = lexer.point(base)?;
+                let p = lexer.point(base)?;
+                builder.cubic_to(c1, c2, p);
+            }
+            'S' => {
+                let c1 = builder.reflected_control(true);
+                let c2 = lexer.point(base)?;
+                let p = lexer.point(base)?;
+                builder.cubic_to(c1, c2, p);
+            }
+            'Q' => {
+                let c = lexer.point(base)?;
+                let p = lexer.point(base)?;
+                builder.quad_to(c, p);
+            }
+            'T' => {
+                let c = builder.reflected_control(false);
+                let p = lexer.point(base)?;
+                builder.quad_to(c, p);
+            }
+            'A' => {
+                let rx = lexer.number()?;
+                let ry = lexer.number()?;
+                let rotation = lexer.number()?;
+                let large_arc = lexer.flag()?;
+                let sweep = lexer.flag()?;
+                let p = lexer.point(base)?;
+                builder.arc_to(rx, ry, rotation, large_arc, sweep, p);
+            }
+            'Z' => builder.close(),
+            _ => return Err(lexer.error(&format!("unsupported command `{}`", cmd))),
+        }
+    }
+
+    builder.close();
+    Ok(builder.subpaths)
+}
+
+/// Accumulates segments for the current subpath.
+struct Builder {
+    subpaths: Vec<Vec<SketchSegment2D>>,
+    segments: Vec<SketchSegment2D>,
+    start: Vec2,
+    current: Vec2,
+    /// Last control point of the previous curve and whether that curve was
+    /// cubic, for the smooth `S`/`T` commands.
+    last_control: Option<(Vec2, bool)>,
+}
+
+impl Builder {
+    fn new() -> Self {
+        let origin = Vec2::new(0.0, 0.0);
+        Self {
+            subpaths: Vec::new(),
+            segments: Vec::new(),
+            start: origin,
+            current: origin,
+            last_control: None,
+        }
+    }
+
+    fn move_to(&mut self, p: Vec2) {
+        self.close();
+        self.start = p;
+        self.current = p;
+    }
+
+    fn line_to(&mut self, p: Vec2) {
+        if distance(self.current, p) > EPS {
+            self.segments.push(SketchSegment2D::Line {
+                start: self.current,
+                end: p,
+            });
+        }
+        self.current = p;
+        self.last_control = None;
+    }
+
+    fn cubic_to(&mut self, c1: Vec2, c2: Vec2, p: Vec2) {
+        let p0 = self.current;
+        for i in 1..=BEZIER_SEGMENTS {
+            let t = i as f64 / BEZIER_SEGMENTS as f64;
+            let s = 1.0 - t;
+            let (a, b, c, d) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+            self.line_to(Vec2::new(
+                a * p0.x + b * c1.x + c * c2.x + d * p.x,
+                a * p0.y + b * c1.y + c * c2.y + d * p.y,
+            ));
+        }
+        self.current = p;
+        self.last_control = Some((c2, true));
+    }
+
+    fn quad_to(&mut self, c: Vec2, p: Vec2) {
+        let p0 = self.current;
+        for i in 1..=BEZIER_SEGMENTS {
+            let t = i as f64 / BEZIER_SEGMENTS as f64;
+            let s = 1.0 - t;
+            let (a, b, d) = (s * s, 2.0 * s * t, t * t);
+            self.line_to(Vec2::new(
+                a * p0.x + b * c.x + d * p.x,
+                a * p0.y + b * c.y + d * p.y,
+            ));
+        }
+        self.current = p;
+        self.last_control = Some((c, false));
+    }
+
+    /// Control point for a smooth curve: the previous control point of the
+    /// same kind reflected through the current point, or the current point.
+    fn reflected_control(&self, cubic: bool) -> Vec2 {
+        match self.last_control {
+            Some((c, was_cubic)) if was_cubic == cubic => {
+                Vec2::new(2.0 * self.current.x - c.x, 2.0 * self.current.y - c.y)
+            }
+            _ => self.current,
+        }
+    }
+
+    /// Elliptical arc per the SVG implementation notes (endpoint to center
+    /// parameterization). Circular arcs become exact arc segments.
+    fn arc_to(&mut self, rx: f64, ry: f64, rotation_deg: f64, large: bool, sweep: bool, p: Vec2) {
+        let p0 = self.current;
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        if distance(p0, p) <= EPS {
+            return;
+        }
+        if rx <= EPS || ry <= EPS {
+            self.line_to(p);
+            return;
+        }
+
+        let phi = rotation_deg.to_radians();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let hx = (p0.x - p.x) / 2.0;
+        let hy = (p0.y - p.y) / 2.0;
+        let x1 = cos_phi * hx + sin_phi * hy;
+        let y1 = -sin_phi * hx + cos_phi * hy;
+
+        // Scale up radii that are too small to span the endpoints.
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coef = (num / den).max(0.0).sqrt();
+        if large == sweep {
+            coef = -coef;
+        }
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+        let center = Vec2::new(
+            cos_phi * cx1 - sin_phi * cy1 + (p0.x + p.x) / 2.0,
+            sin_phi * cx1 + cos_phi * cy1 + (p0.y + p.y) / 2.0,
+        );
+
+        if (rx - ry).abs() <= EPS * rx.max(1.0) {
+            self.segments.push(SketchSegment2D::Arc {
+                start: p0,
+                end: p,
+                center,
+                ccw: sweep,
+            });
+            self.current = p;
+            self.last_control = None;
+            return;
+        }
+
+        let theta1 = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+        let theta2 = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx);
+        let mut delta = (theta2 - theta1).rem_euclid(TAU);
+        if !sweep && delta > 0.0 {
+            delta -= TAU;
+        }
+        let steps = (delta.abs() / ELLIPSE_STEP).ceil().max(1.0) as usize;
+        for i in 1..steps {
+            let theta = theta1 + delta * i as f64 / steps as f64;
+            let (ex, ey) = (rx * theta.cos(), ry * theta.sin());
+            self.line_to(Vec2::new(
+                center.x + cos_phi * ex - sin_phi * ey,
+                center.y + sin_phi * ex + cos_phi * ey,
+            ));
+        }
+        self.line_to(p);
+    }
+
+    /// Finish the current subpath, closing it back to its start.
+    fn close(&mut self) {
+        let start = self.start;
+        self.line_to(start);
+        if !self.segments.is_empty() {
+            self.subpaths.push(std::mem::take(&mut self.segments));
+        }
+    }
+}
+
+fn distance(a: Vec2, b: Vec2) -> f64 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
+
+/// Tokenizer for path data numbers, flags and command letters.
+struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace() || c == ',') {
+            self.bump();
+        }
+    }
+
+    fn error(&self, message: &str) -> SvgError {
+        SvgError::Parse {
+            offset: self.pos,
+            message: message.to_string(),
+        }
+    }
+
+    fn point(&mut self, base: Vec2) -> Result<Vec2, SvgError> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Ok(Vec2::new(base.x + x, base.y + y))
+    }
+
+    /// Read a number such as `-1.5e3`. Numbers may run together without
+    /// separators, e.g. `1-2` or `.5.5`.
+    fn number(&mut self) -> Result<f64, SvgError> {
+        self.skip_separators();
+        let start = self.pos;
+        let bytes = self.src.as_bytes();
+        let mut end = start;
+        if matches!(bytes.get(end), Some(b'+' | b'-')) {
+            end += 1;
+        }
+        let mut seen_dot = false;
+        while let Some(&b) = bytes.get(end) {
+            match b {
+                b'0'..=b'9' => end += 1,
+                b'.' if !seen_dot => {
+                    seen_dot = true;
+                    end += 1;
+                }
+                _ => break,
+            }
+        }
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let mut exp = end + 1;
+            if matches!(bytes.get(exp), Some(b'+' | b'-')) {
+                exp += 1;
+            }
+            if matches!(bytes.get(exp), Some(b'0'..=b'9')) {
+                while matches!(bytes.get(exp), Some(b'0'..=b'9')) {
+                    exp += 1;
+                }
+                end = exp;
+            }
+        }
+        let value = self.src[start..end]
+            .parse()
+            .map_err(|_| self.error("expected a number"))?;
+        self.pos = end;
+        Ok(value)
+    }
+
+    /// Read an arc flag, which is a single `0` or `1` and may be written
+    /// without a separator before the next value.
+    fn flag(&mut self) -> Result<bool, SvgError> {
+        self.skip_separators();
+        match self.peek() {
+            Some('0') => {
+                self.bump();
+                Ok(false)
+            }
+            Some('1') => {
+                self.bump();
+                Ok(true)
+            }
+            _ => Err(self.error("expected an arc flag")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_without_separators() {
+        let paths = parse_subpaths("M0-1.5L10,0l-.5e1 5 .5.5z").unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(
+            paths[0][0],
+            SketchSegment2D::Line {
+                start: Vec2::new(0.0, -1.5),
+                end: Vec2::new(10.0, 0.0)
+            }
+        );
+        assert_eq!(paths[0][1].point_at(1.0), Vec2::new(5.0, 5.0));
+        assert_eq!(paths[0][2].point_at(1.0), Vec2::new(5.5, 5.5));
+        assert_eq!(paths[0].len(), 4);
+    }
+
+    #[test]
+    fn test_circular_arc_is_exact() {
+        // Two half circles of radius 5 with compact flags.
+        let paths = parse_subpaths("M0 0A5 5 0 1010 0A5 5 0 100 0Z").unwrap();
+        assert_eq!(paths[0].len(), 2);
+        let SketchSegment2D::Arc { center, .. } = paths[0][0] else {
+            panic!("expected an arc");
+        };
+        assert!(distance(center, Vec2::new(5.0, 0.0)) < 1e-9);
+        let length: f64 = paths[0].iter().map(|s| s.length()).sum();
+        assert!((length - 10.0 * PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bezier_is_flattened() {
+        let paths = parse_subpaths("M0 0 C0 10 10 10 10 0 Z").unwrap();
+        assert_eq!(paths[0].len(), BEZIER_SEGMENTS + 1);
+        // The curve's midpoint is at 3/4 of the control height.
+        let mid = paths[0][BEZIER_SEGMENTS / 2 - 1].point_at(1.0);
+        assert!(distance(mid, Vec2::new(5.0, 7.5)) < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            parse_subpaths("M0 0 L10"),
+            Err(SvgError::Parse { .. })
+        ));
+        assert!(parse_subpaths("10 10").is_err());
+        assert!(parse_subpaths("M0 0 B1 1").is_err());
+    }
+}
@@ -1,7 +1,7 @@
 //! Frenet frame computation for orienting profiles along curves.
 
 use vcad_kernel_geom::Curve3d;
-use vcad_kernel_math::{Dir3, Point2, Point3, Vec3};
+use vcad_kernel_math::{Dir3, Point2, Point3, Transform, Vec3};
 
 /// A Frenet frame at a point on a curve.
 ///
@@ -25,7 +25,43 @@ pub struct FrenetFrame {
     pub binormal: Dir3,
 }
 
+/// How [`frames_along`] orients successive frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrameMode {
+    /// Propagate the first frame along the path with as little twist as
+    /// possible (default). Stable on straight runs and inflections.
+    #[default]
+    RotationMinimizing,
+    /// Compute each frame independently, with the normal toward the center
+    /// of curvature. Flips at inflections and is arbitrary on straight runs.
+    Frenet,
+}
+
 impl FrenetFrame {
+    /// Create a frame at `position` facing along `tangent`.
+    ///
+    /// The normal is the part of `up` perpendicular to the tangent. If `up`
+    /// is (nearly) parallel to the tangent, an arbitrary perpendicular is
+    /// used instead.
+    pub fn from_points(position: Point3, tangent: Vec3, up: Vec3) -> Self {
+        if tangent.norm() < 1e-12 {
+            return Self::default_at(position);
+        }
+        let tangent = Dir3::new_normalize(tangent);
+        let up_perp = up - up.dot(tangent.as_ref()) * tangent.as_ref();
+        if up_perp.norm() < 1e-9 * up.norm().max(1.0) {
+            return Self::with_arbitrary_normal(position, tangent);
+        }
+        let normal = Dir3::new_normalize(up_perp);
+        let binormal = Dir3::new_normalize(tangent.as_ref().cross(normal.as_ref()));
+        Self {
+            position,
+            tangent,
+            normal,
+            binormal,
+        }
+    }
+
     /// Compute a Frenet frame at parameter `t` on the given curve.
     ///
     /// Uses finite differences to approximate the tangent and second derivative.
@@ -170,6 +206,26 @@ impl FrenetFrame {
         }
     }
 
+    /// The placement matrix of this frame.
+    ///
+    /// Maps local X to the normal, Y to the binormal, Z to the tangent and
+    /// the origin to the frame position, so an object modeled around the
+    /// origin and pointing up +Z is placed on the curve facing along it.
+    pub fn to_transform(&self) -> Transform {
+        let (n, b, t, p) = (
+            self.normal.as_ref(),
+            self.binormal.as_ref(),
+            self.tangent.as_ref(),
+            self.position,
+        );
+        Transform::from_rows(&[
+            [n.x, b.x, t.x, p.x],
+            [n.y, b.y, t.y, p.y],
+            [n.z, b.z, t.z, p.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
     fn lerp_dir(a: &Dir3, b: &Dir3, t: f64) -> Dir3 {
         let v = (1.0 - t) * a.as_ref() + t * b.as_ref();
         if v.norm() < 1e-12 {
@@ -180,6 +236,29 @@ impl FrenetFrame {
     }
 }
 
+/// Sample `count` frames evenly in parameter along a path, endpoints
+/// included.
+///
+/// Useful for placing objects along a curve, e.g. via
+/// [`FrenetFrame::to_transform`]. Returns an empty list if `count < 2`.
+pub fn frames_along(path: &dyn Curve3d, count: usize, mode: FrameMode) -> Vec<FrenetFrame> {
+    match mode {
+        FrameMode::RotationMinimizing => rotation_minimizing_frames(path, count),
+        FrameMode::Frenet => {
+            if count < 2 {
+                return vec![];
+            }
+            let (t_min, t_max) = path.domain();
+            (0..count)
+                .map(|i| {
+                    let t = t_min + (t_max - t_min) * i as f64 / (count - 1) as f64;
+                    FrenetFrame::from_curve(path, t)
+                })
+                .collect()
+        }
+    }
+}
+
 /// Compute a sequence of rotation-minimizing frames along a curve.
 ///
 /// This produces smoother results than independent Frenet frames by
@@ -191,18 +270,28 @@ pub fn rotation_minimizing_frames(curve: &dyn Curve3d, n_samples: usize) -> Vec<
 
     let (t_min, t_max) = curve.domain();
     let dt = (t_max - t_min) / (n_samples - 1) as f64;
+    let params: Vec<f64> = (0..n_samples).map(|i| t_min + i as f64 * dt).collect();
+    rotation_minimizing_frames_at(curve, &params)
+}
 
-    let mut frames = Vec::with_capacity(n_samples);
+/// Rotation-minimizing frames at the given increasing curve parameters.
+pub(crate) fn rotation_minimizing_frames_at(
+    curve: &dyn Curve3d,
+    params: &[f64],
+) -> Vec<FrenetFrame> {
+    let Some((&t_first, rest)) = params.split_first() else {
+        return vec![];
+    };
+
+    let mut frames = Vec::with_capacity(params.len());
 
     // First frame: use standard computation
-    let first = FrenetFrame::from_curve(curve, t_min);
+    let first = FrenetFrame::from_curve(curve, t_first);
     frames.push(first);
 
     // Propagate frames using double reflection method (rotation minimizing)
-    for i in 1..n_samples {
-        let t = t_min + i as f64 * dt;
-
-        let prev = &frames[i - 1];
+    for (i, &t) in rest.iter().enumerate() {
+        let prev = &frames[i];
 
         let xi = curve.evaluate(t);
         let xi_prev = prev.position;
@@ -341,6 +430,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_points() {
+        let frame = FrenetFrame::from_points(
+            Point3::new(1.0, 2.0, 3.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(1.0, 0.0, 1.0),
+        );
+        assert!((frame.normal.as_ref() - Vec3::x()).norm() < 1e-12);
+        assert!((frame.binormal.as_ref() - Vec3::y()).norm() < 1e-12);
+
+        // Up parallel to the tangent still yields an orthonormal frame
+        let frame = FrenetFrame::from_points(Point3::origin(), Vec3::z(), Vec3::z());
+        assert!(frame.tangent.as_ref().dot(frame.normal.as_ref()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_frames_along_line_share_orientation() {
+        let line = Line3d::from_points(Point3::origin(), Point3::new(10.0, 0.0, 0.0));
+        for mode in [FrameMode::RotationMinimizing, FrameMode::Frenet] {
+            let frames = frames_along(&line, 6, mode);
+            assert_eq!(frames.len(), 6);
+            for (i, frame) in frames.iter().enumerate() {
+                assert!((frame.position.x - 2.0 * i as f64).abs() < 1e-9);
+                let m = frame.to_transform().matrix;
+                let m0 = frames[0].to_transform().matrix;
+                assert!((m.fixed_view::<3, 3>(0, 0) - m0.fixed_view::<3, 3>(0, 0)).norm() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_frames_along_quarter_circle() {
+        // Planar helix: a quarter circle of radius 10 from (10,0,0) to (0,10,0)
+        let arc = crate::Helix::new(10.0, 0.0, 0.0, 0.25);
+        let frames = frames_along(&arc, 5, FrameMode::RotationMinimizing);
+        let first = frames.first().unwrap();
+        let last = frames.last().unwrap();
+        assert!((first.tangent.as_ref() - Vec3::y()).norm() < 1e-6);
+        assert!((last.tangent.as_ref() + Vec3::x()).norm() < 1e-6);
+        assert!(first.tangent.as_ref().dot(last.tangent.as_ref()).abs() < 1e-6);
+
+        // The placement maps local +Z onto the tangent at the sample point
+        let xf = last.to_transform();
+        let p = xf.apply_point(&Point3::new(0.0, 0.0, 1.0));
+        assert!((p - Point3::new(-1.0, 10.0, 0.0)).norm() < 1e-6);
+    }
+
     #[test]
     fn test_lerp() {
         let frame1 = FrenetFrame {
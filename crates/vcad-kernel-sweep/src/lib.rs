@@ -3,8 +3,13 @@
 //! Sweep and loft operations for the vcad kernel.
 //!
 //! Provides operations to create 3D B-rep solids by:
-//! - **Sweep**: Moving a 2D profile along a 3D path curve
+//! - **Sweep**: Moving a 2D profile along a 3D path curve (a [`Helix`],
+//!   [`PolylinePath`], or any other [`vcad_kernel_geom::Curve3d`])
 //! - **Loft**: Interpolating between multiple 2D profiles
+//! - **Thread**: Sweeping an ISO metric tooth along a helix ([`thread`])
+//!
+//! [`frames_along`] exposes the frames sweeps use, for placing other
+//! geometry along a path.
 //!
 //! # Example
 //!
@@ -26,11 +31,18 @@
 
 mod frenet;
 mod loft;
+mod polyline;
 mod sweep;
+mod thread;
 
-pub use frenet::FrenetFrame;
-pub use loft::{loft, LoftMode, LoftOptions};
-pub use sweep::{sweep, Helix, SweepOptions};
+pub use frenet::{frames_along, FrameMode, FrenetFrame};
+pub use loft::{loft, loft_with_warnings, LoftMode, LoftOptions, LoftResult, LoftWarning};
+pub use polyline::PolylinePath;
+pub use sweep::{
+    sweep, sweep_with_warnings, CapMode, DegeneratePolicy, Helix, SweepOptions, SweepResult,
+    SweepWarning,
+};
+pub use thread::{thread, ThreadSpec};
 
 use thiserror::Error;
 
@@ -52,6 +64,10 @@ pub enum SweepError {
     /// The computed frame is degenerate (e.g., curvature is zero).
     #[error("degenerate frame at parameter t={0}")]
     DegenerateFrame(f64),
+
+    /// The thread parameters are invalid.
+    #[error("invalid thread: {0}")]
+    InvalidThread(String),
 }
 
 /// Errors from loft operations.
@@ -68,4 +84,8 @@ pub enum LoftError {
     /// A profile is invalid.
     #[error("invalid profile at index {0}: {1}")]
     InvalidProfile(usize, String),
+
+    /// The profile at this index coincides with the one before it.
+    #[error("profile at index {0} coincides with the previous profile")]
+    DegenerateProfile(usize),
 }
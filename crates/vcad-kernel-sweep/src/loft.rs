@@ -3,12 +3,12 @@
 use std::collections::HashMap;
 
 use vcad_kernel_geom::{GeometryStore, Plane};
-use vcad_kernel_math::{Point3, Vec3};
+use vcad_kernel_math::{Point3, Quantize, Vec3};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_sketch::SketchProfile;
 use vcad_kernel_topo::{HalfEdgeId, Orientation, ShellType, Topology, VertexId};
 
-use crate::LoftError;
+use crate::{CapMode, DegeneratePolicy, LoftError};
 
 /// The interpolation mode for lofting.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -27,6 +27,31 @@ pub struct LoftOptions {
     pub mode: LoftMode,
     /// If true, connect the last profile back to the first (creates a tube).
     pub closed: bool,
+    /// Which end profiles to cap. Ignored when `closed` is set.
+    pub caps: CapMode,
+    /// Handling of a profile that coincides with the one before it, which
+    /// would join them with zero-area faces. Default:
+    /// [`DegeneratePolicy::Error`].
+    pub on_degenerate: DegeneratePolicy,
+}
+
+/// A problem a loft recovered from, per [`LoftOptions::on_degenerate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoftWarning {
+    /// The profile at this index was dropped.
+    SkippedProfile(usize),
+    /// The profile at this index was replaced by one interpolated from its
+    /// neighbors.
+    InterpolatedProfile(usize),
+}
+
+/// A lofted solid together with the problems recovered from building it.
+#[derive(Debug, Clone)]
+pub struct LoftResult {
+    /// The lofted solid.
+    pub solid: BRepSolid,
+    /// Degenerate profiles that were skipped or interpolated, in order.
+    pub warnings: Vec<LoftWarning>,
 }
 
 /// Loft between multiple profiles to create a B-rep solid.
@@ -40,13 +65,17 @@ pub struct LoftOptions {
 ///
 /// A B-rep solid with:
 /// * Lateral faces connecting adjacent profiles
-/// * Cap faces at start and end (unless closed)
+/// * Cap faces at start and end, as selected by `options.caps` (none if
+///   closed). Without both caps the result is an open shell.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// * Less than 2 profiles are provided
 /// * Profiles have different segment counts
+/// * A profile is open or self-intersecting
+/// * A profile coincides with the one before it under
+///   [`DegeneratePolicy::Error`]
 ///
 /// # Example
 ///
@@ -70,6 +99,23 @@ pub struct LoftOptions {
 /// let solid = loft(&[profile1, profile2], LoftOptions::default()).unwrap();
 /// ```
 pub fn loft(profiles: &[SketchProfile], options: LoftOptions) -> Result<BRepSolid, LoftError> {
+    loft_with_warnings(profiles, options).map(|result| result.solid)
+}
+
+/// Loft between multiple profiles, reporting the degenerate profiles
+/// recovered from.
+///
+/// Behaves like [`loft`]; with [`LoftOptions::on_degenerate`] set to
+/// [`DegeneratePolicy::Skip`] or [`DegeneratePolicy::Interpolate`] the
+/// profiles handled that way are listed in [`LoftResult::warnings`].
+///
+/// # Errors
+///
+/// As for [`loft`].
+pub fn loft_with_warnings(
+    profiles: &[SketchProfile],
+    options: LoftOptions,
+) -> Result<LoftResult, LoftError> {
     // Validate inputs
     if profiles.len() < 2 {
         return Err(LoftError::TooFewProfiles(profiles.len()));
@@ -88,23 +134,81 @@ pub fn loft(profiles: &[SketchProfile], options: LoftOptions) -> Result<BRepSoli
 
     // Validate profiles
     for (i, profile) in profiles.iter().enumerate() {
-        if profile.segments.is_empty() {
-            return Err(LoftError::InvalidProfile(i, "empty profile".into()));
-        }
+        profile
+            .validate()
+            .map_err(|e| LoftError::InvalidProfile(i, e.to_string()))?;
     }
 
-    match options.mode {
-        LoftMode::Ruled => loft_ruled(profiles, options.closed),
+    let rings: Vec<Vec<Point3>> = profiles.iter().map(SketchProfile::vertices_3d).collect();
+    let mut warnings = Vec::new();
+    let rings = recover_degenerate_rings(rings, options.on_degenerate, &mut warnings)?;
+
+    let solid = match options.mode {
+        LoftMode::Ruled => loft_ruled(&rings, options.closed, options.caps),
         LoftMode::Smooth => {
             // Smooth mode not yet implemented - fall back to ruled
-            loft_ruled(profiles, options.closed)
+            loft_ruled(&rings, options.closed, options.caps)
+        }
+    };
+    Ok(LoftResult { solid, warnings })
+}
+
+/// Apply `policy` to every ring that coincides with the ring before it.
+///
+/// Skipped rings are dropped; interpolated ones are moved to their place
+/// between the nearest regular rings on either side, or dropped when no
+/// regular ring follows them.
+fn recover_degenerate_rings(
+    rings: Vec<Vec<Point3>>,
+    policy: DegeneratePolicy,
+    warnings: &mut Vec<LoftWarning>,
+) -> Result<Vec<Vec<Point3>>, LoftError> {
+    let coincident =
+        |a: &[Point3], b: &[Point3]| a.iter().zip(b).all(|(p, q)| (p - q).norm_squared() < 1e-18);
+    let degenerate: Vec<bool> = (0..rings.len())
+        .map(|i| i > 0 && coincident(&rings[i - 1], &rings[i]))
+        .collect();
+    if policy == DegeneratePolicy::Error {
+        if let Some(i) = degenerate.iter().position(|&d| d) {
+            return Err(LoftError::DegenerateProfile(i));
+        }
+    }
+
+    let regular: Vec<usize> = (0..rings.len()).filter(|&i| !degenerate[i]).collect();
+    let mut recovered = Vec::with_capacity(rings.len());
+    for (i, ring) in rings.iter().enumerate() {
+        if !degenerate[i] {
+            recovered.push(ring.clone());
+            continue;
+        }
+        // The first ring is never degenerate, so a regular one precedes
+        let k = regular.partition_point(|&r| r < i);
+        match (policy, regular.get(k)) {
+            (DegeneratePolicy::Interpolate, Some(&after)) => {
+                let before = regular[k - 1];
+                let f = (i - before) as f64 / (after - before) as f64;
+                recovered.push(
+                    rings[before]
+                        .iter()
+                        .zip(&rings[after])
+                        .map(|(p, q)| p + (q - p) * f)
+                        .collect(),
+                );
+                warnings.push(LoftWarning::InterpolatedProfile(i));
+            }
+            _ => warnings.push(LoftWarning::SkippedProfile(i)),
         }
     }
+
+    if recovered.len() < 2 {
+        return Err(LoftError::TooFewProfiles(recovered.len()));
+    }
+    Ok(recovered)
 }
 
-fn loft_ruled(profiles: &[SketchProfile], closed: bool) -> Result<BRepSolid, LoftError> {
-    let n_profiles = profiles.len();
-    let n_segments = profiles[0].segments.len();
+fn loft_ruled(rings: &[Vec<Point3>], closed: bool, caps: CapMode) -> BRepSolid {
+    let n_profiles = rings.len();
+    let n_segments = rings[0].len();
 
     let mut topo = Topology::new();
     let mut geom = GeometryStore::new();
@@ -112,22 +216,15 @@ fn loft_ruled(profiles: &[SketchProfile], closed: bool) -> Result<BRepSolid, Lof
     // Build vertex grid: [profile_index][vertex_index]
     let mut vertex_grid: Vec<Vec<VertexId>> = Vec::with_capacity(n_profiles);
 
-    for profile in profiles {
-        let verts_3d = profile.vertices_3d();
-        let ring: Vec<VertexId> = verts_3d.iter().map(|&p| topo.add_vertex(p)).collect();
+    for ring in rings {
+        let ring: Vec<VertexId> = ring.iter().map(|&p| topo.add_vertex(p)).collect();
         vertex_grid.push(ring);
     }
 
     let mut all_faces = Vec::new();
     let mut he_map: HashMap<([i64; 3], [i64; 3]), HalfEdgeId> = HashMap::new();
 
-    let quantize_pt = |p: Point3| -> [i64; 3] {
-        [
-            (p.x * 1e9).round() as i64,
-            (p.y * 1e9).round() as i64,
-            (p.z * 1e9).round() as i64,
-        ]
-    };
+    let quantize_pt = |p: Point3| p.quantize(1e9);
 
     // Number of profile transitions
     let n_transitions = if closed { n_profiles } else { n_profiles - 1 };
@@ -178,7 +275,7 @@ fn loft_ruled(profiles: &[SketchProfile], closed: bool) -> Result<BRepSolid, Lof
     }
 
     // Build cap faces if not closed
-    if !closed {
+    if !closed && caps.caps_start() {
         // Start cap (first profile, reversed winding)
         let start_ring = &vertex_grid[0];
         let start_face_id = build_cap_face(
@@ -190,7 +287,8 @@ fn loft_ruled(profiles: &[SketchProfile], closed: bool) -> Result<BRepSolid, Lof
             quantize_pt,
         );
         all_faces.push(start_face_id);
-
+    }
+    if !closed && caps.caps_end() {
         // End cap (last profile, forward winding)
         let end_ring = &vertex_grid[n_profiles - 1];
         let end_face_id = build_cap_face(
@@ -211,11 +309,7 @@ fn loft_ruled(profiles: &[SketchProfile], closed: bool) -> Result<BRepSolid, Lof
     let shell = topo.add_shell(all_faces, ShellType::Outer);
     let solid_id = topo.add_solid(shell);
 
-    Ok(BRepSolid {
-        topology: topo,
-        geometry: geom,
-        solid_id,
-    })
+    BRepSolid::new(topo, geom, solid_id)
 }
 
 fn build_cap_face<F>(
@@ -231,8 +325,19 @@ where
 {
     let n = verts.len();
 
+    // Order vertices by the cap's winding, so the plane normal built from
+    // them agrees with the loop and points out of the solid
+    let ordered_verts: Vec<VertexId> = if reversed {
+        verts.iter().rev().copied().collect()
+    } else {
+        verts.to_vec()
+    };
+
     // Get positions
-    let positions: Vec<Point3> = verts.iter().map(|&v| topo.vertices[v].point).collect();
+    let positions: Vec<Point3> = ordered_verts
+        .iter()
+        .map(|&v| topo.vertices[v].point)
+        .collect();
 
     // Create plane surface
     let origin = positions[0];
@@ -250,12 +355,6 @@ where
     };
 
     // Create half-edges in the correct order
-    let ordered_verts: Vec<VertexId> = if reversed {
-        verts.iter().rev().copied().collect()
-    } else {
-        verts.to_vec()
-    };
-
     let hes: Vec<HalfEdgeId> = ordered_verts
         .iter()
         .map(|&v| topo.add_half_edge(v))
@@ -385,6 +484,55 @@ mod tests {
         assert_eq!(unpaired, 0, "expected no unpaired half-edges");
     }
 
+    #[test]
+    fn test_loft_degenerate_profile() {
+        let bottom = create_rectangle_profile(Point3::origin(), 10.0, 10.0);
+        let top = create_rectangle_profile(Point3::new(0.0, 0.0, 20.0), 10.0, 10.0);
+        let profiles = [bottom.clone(), bottom, top];
+        let options = |on_degenerate| LoftOptions {
+            on_degenerate,
+            ..Default::default()
+        };
+
+        let result = loft(&profiles, options(DegeneratePolicy::Error));
+        assert!(matches!(result, Err(LoftError::DegenerateProfile(1))));
+
+        let skipped = loft_with_warnings(&profiles, options(DegeneratePolicy::Skip)).unwrap();
+        assert_eq!(skipped.warnings, vec![LoftWarning::SkippedProfile(1)]);
+        // 4 lateral faces + 2 caps
+        assert_eq!(skipped.solid.topology.faces.len(), 6);
+
+        let interpolated =
+            loft_with_warnings(&profiles, options(DegeneratePolicy::Interpolate)).unwrap();
+        assert_eq!(
+            interpolated.warnings,
+            vec![LoftWarning::InterpolatedProfile(1)]
+        );
+        assert_eq!(interpolated.solid.topology.faces.len(), 10);
+        assert!(interpolated
+            .solid
+            .topology
+            .vertices
+            .values()
+            .any(|v| (v.point.z - 10.0).abs() < 1e-9));
+
+        // With nothing after it, a trailing duplicate can only be dropped
+        let profiles = [
+            profiles[0].clone(),
+            profiles[2].clone(),
+            profiles[2].clone(),
+        ];
+        let trailing =
+            loft_with_warnings(&profiles, options(DegeneratePolicy::Interpolate)).unwrap();
+        assert_eq!(trailing.warnings, vec![LoftWarning::SkippedProfile(2)]);
+
+        let result = loft(
+            &[profiles[0].clone(), profiles[0].clone()],
+            options(DegeneratePolicy::Skip),
+        );
+        assert!(matches!(result, Err(LoftError::TooFewProfiles(1))));
+    }
+
     #[test]
     fn test_loft_closed() {
         // Create a ring of profiles (tube)
@@ -407,6 +555,24 @@ mod tests {
         assert_eq!(solid.topology.faces.len(), 16);
     }
 
+    #[test]
+    fn test_loft_cap_modes() {
+        let profile1 = create_rectangle_profile(Point3::origin(), 10.0, 10.0);
+        let profile2 = create_rectangle_profile(Point3::new(0.0, 0.0, 20.0), 10.0, 10.0);
+        let mesh_for = |caps| {
+            let options = LoftOptions {
+                caps,
+                ..Default::default()
+            };
+            let solid = loft(&[profile1.clone(), profile2.clone()], options).unwrap();
+            vcad_kernel_tessellate::tessellate_brep(&solid, 32)
+        };
+
+        assert!(mesh_for(CapMode::Both).is_watertight());
+        assert!(!mesh_for(CapMode::End).is_watertight());
+        assert!(!mesh_for(CapMode::None).boundary_edges().is_empty());
+    }
+
     #[test]
     fn test_loft_too_few_profiles_error() {
         let profile = create_rectangle_profile(Point3::origin(), 10.0, 10.0);
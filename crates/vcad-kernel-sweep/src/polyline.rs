@@ -0,0 +1,273 @@
+//! Polyline sweep paths with optional rounded corners.
+
+use vcad_kernel_geom::{Curve3d, CurveKind};
+use vcad_kernel_math::{Point3, Vec3};
+
+/// A piece of a polyline path: a straight run or a corner fillet arc.
+#[derive(Debug, Clone)]
+enum PathPiece {
+    Line {
+        start: Point3,
+        end: Point3,
+    },
+    /// Circular arc `center + radius * (cos(s) * u + sin(s) * v)` for `s ∈ [0, angle]`.
+    Arc {
+        center: Point3,
+        u: Vec3,
+        v: Vec3,
+        radius: f64,
+        angle: f64,
+    },
+}
+
+impl PathPiece {
+    fn length(&self) -> f64 {
+        match self {
+            PathPiece::Line { start, end } => (end - start).norm(),
+            PathPiece::Arc { radius, angle, .. } => radius * angle,
+        }
+    }
+
+    /// Point and unit tangent at fraction `f ∈ [0, 1]` of the piece.
+    fn evaluate(&self, f: f64) -> (Point3, Vec3) {
+        match self {
+            PathPiece::Line { start, end } => {
+                let d = end - start;
+                (start + d * f, d.normalize())
+            }
+            PathPiece::Arc {
+                center,
+                u,
+                v,
+                radius,
+                angle,
+            } => {
+                let (sin, cos) = (angle * f).sin_cos();
+                (center + (u * cos + v * sin) * *radius, v * cos - u * sin)
+            }
+        }
+    }
+}
+
+/// A sweep path through an ordered list of points.
+///
+/// The path is parameterized by normalized arc length over `[0, 1]`. Sharp
+/// corners can be replaced by circular fillets with
+/// [`PolylinePath::with_corner_radius`]; otherwise the path has tangent
+/// discontinuities at interior points and sweeps will pinch there.
+#[derive(Debug, Clone)]
+pub struct PolylinePath {
+    points: Vec<Point3>,
+    corner_radius: f64,
+    pieces: Vec<PathPiece>,
+    /// Cumulative length at the end of each piece.
+    lengths: Vec<f64>,
+}
+
+impl PolylinePath {
+    /// Create a path through `points` with sharp corners.
+    ///
+    /// Consecutive duplicate points are ignored.
+    pub fn new(points: Vec<Point3>) -> Self {
+        let mut path = Self {
+            points,
+            corner_radius: 0.0,
+            pieces: Vec::new(),
+            lengths: Vec::new(),
+        };
+        path.points.dedup_by(|a, b| (*a - *b).norm() < 1e-12);
+        path.rebuild();
+        path
+    }
+
+    /// Round interior corners with circular arcs of the given radius.
+    ///
+    /// The radius is reduced locally where the adjacent straight runs are too
+    /// short to fit the full fillet.
+    pub fn with_corner_radius(mut self, radius: f64) -> Self {
+        self.corner_radius = radius.max(0.0);
+        self.rebuild();
+        self
+    }
+
+    /// The control points of the path.
+    pub fn points(&self) -> &[Point3] {
+        &self.points
+    }
+
+    /// Total length of the path.
+    pub fn length(&self) -> f64 {
+        self.lengths.last().copied().unwrap_or(0.0)
+    }
+
+    fn rebuild(&mut self) {
+        self.pieces.clear();
+        self.lengths.clear();
+
+        let pts = &self.points;
+        if pts.len() < 2 {
+            return;
+        }
+
+        // Trim distance at each point (0 at the ends and at sharp corners).
+        let n = pts.len();
+        let mut trims = vec![0.0; n];
+        let mut arcs: Vec<Option<PathPiece>> = vec![None; n];
+        if self.corner_radius > 0.0 {
+            for i in 1..n - 1 {
+                let d1 = (pts[i] - pts[i - 1]).normalize();
+                let d2 = (pts[i + 1] - pts[i]).normalize();
+                let turn = d1.dot(&d2).clamp(-1.0, 1.0).acos();
+                if !(1e-9..=std::f64::consts::PI - 1e-9).contains(&turn) {
+                    continue;
+                }
+                let half_tan = (turn / 2.0).tan();
+                // Each run can give at most half its length to either corner.
+                let max_trim = 0.5
+                    * (pts[i] - pts[i - 1])
+                        .norm()
+                        .min((pts[i + 1] - pts[i]).norm());
+                let trim = (self.corner_radius * half_tan).min(max_trim);
+                let radius = trim / half_tan;
+
+                let start = pts[i] - d1 * trim;
+                let bisector = (d2 - d1).normalize();
+                let center = pts[i] + bisector * (radius / (turn / 2.0).cos());
+                let u = (start - center) / radius;
+                trims[i] = trim;
+                arcs[i] = Some(PathPiece::Arc {
+                    center,
+                    u,
+                    v: d1,
+                    radius,
+                    angle: turn,
+                });
+            }
+        }
+
+        for i in 0..n - 1 {
+            let d = (pts[i + 1] - pts[i]).normalize();
+            let start = pts[i] + d * trims[i];
+            let end = pts[i + 1] - d * trims[i + 1];
+            if (end - start).norm() > 1e-12 {
+                self.pieces.push(PathPiece::Line { start, end });
+            }
+            if let Some(arc) = arcs[i + 1].take() {
+                self.pieces.push(arc);
+            }
+        }
+
+        let mut total = 0.0;
+        for piece in &self.pieces {
+            total += piece.length();
+            self.lengths.push(total);
+        }
+    }
+
+    /// Point and unit tangent at normalized arc length `t`.
+    fn sample(&self, t: f64) -> (Point3, Vec3) {
+        if self.pieces.is_empty() {
+            let p = self.points.first().copied().unwrap_or_else(Point3::origin);
+            return (p, Vec3::z());
+        }
+        let s = t.clamp(0.0, 1.0) * self.length();
+        let i = self
+            .lengths
+            .iter()
+            .position(|&end| s <= end)
+            .unwrap_or(self.pieces.len() - 1);
+        let start = if i == 0 { 0.0 } else { self.lengths[i - 1] };
+        let len = self.pieces[i].length();
+        let f = if len > 1e-12 { (s - start) / len } else { 0.0 };
+        self.pieces[i].evaluate(f.clamp(0.0, 1.0))
+    }
+}
+
+impl Curve3d for PolylinePath {
+    fn evaluate(&self, t: f64) -> Point3 {
+        self.sample(t).0
+    }
+
+    fn tangent(&self, t: f64) -> Vec3 {
+        // Unit tangent scaled by length, i.e. the derivative with respect to t.
+        self.sample(t).1 * self.length()
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+
+    fn curve_type(&self) -> CurveKind {
+        CurveKind::Line // Closest approximation
+    }
+
+    fn clone_box(&self) -> Box<dyn Curve3d> {
+        Box::new(self.clone())
+    }
+
+    fn suggested_segments(&self) -> usize {
+        // 16 samples per piece so fillet arcs stay smooth, minimum 32
+        (self.pieces.len() * 16).max(32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sweep, SweepOptions};
+    use vcad_kernel_sketch::SketchProfile;
+
+    fn l_path() -> Vec<Point3> {
+        vec![
+            Point3::origin(),
+            Point3::new(0.0, 0.0, 20.0),
+            Point3::new(20.0, 0.0, 20.0),
+        ]
+    }
+
+    #[test]
+    fn test_sharp_polyline() {
+        let path = PolylinePath::new(l_path());
+        assert!((path.length() - 40.0).abs() < 1e-12);
+
+        let mid = path.evaluate(0.5);
+        assert!((mid - Point3::new(0.0, 0.0, 20.0)).norm() < 1e-9);
+        let end = path.evaluate(1.0);
+        assert!((end - Point3::new(20.0, 0.0, 20.0)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_rounded_corner() {
+        let radius = 5.0;
+        let path = PolylinePath::new(l_path()).with_corner_radius(radius);
+
+        // 15 + quarter circle + 15
+        let expected = 30.0 + radius * std::f64::consts::FRAC_PI_2;
+        assert!((path.length() - expected).abs() < 1e-9);
+
+        // Midpoint of the fillet is `radius` from its center at (5, 0, 15).
+        let mid = path.evaluate(0.5);
+        assert!(((mid - Point3::new(5.0, 0.0, 15.0)).norm() - radius).abs() < 1e-9);
+
+        // Tangent is continuous across the fillet.
+        let t_in = path.tangent(15.0 / expected).normalize();
+        assert!((t_in - Vec3::z()).norm() < 1e-6);
+        let t_out = path.tangent((15.0 + radius * std::f64::consts::FRAC_PI_2) / expected);
+        assert!((t_out.normalize() - Vec3::x()).norm() < 1e-6);
+    }
+
+    #[test]
+    fn test_sweep_rounded_polyline() {
+        let profile = SketchProfile::circle(Point3::origin(), Vec3::z(), 1.0, 8);
+        let path = PolylinePath::new(l_path()).with_corner_radius(5.0);
+
+        let solid = sweep(&profile, &path, SweepOptions::default()).unwrap();
+        let unpaired = solid
+            .topology
+            .half_edges
+            .values()
+            .filter(|he| he.twin.is_none())
+            .count();
+        assert_eq!(unpaired, 0, "expected no unpaired half-edges");
+    }
+}
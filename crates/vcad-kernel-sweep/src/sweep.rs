@@ -4,14 +4,78 @@ use std::collections::HashMap;
 use std::f64::consts::PI;
 
 use vcad_kernel_geom::{BilinearSurface, Curve3d, CurveKind, GeometryStore, Plane};
-use vcad_kernel_math::{Dir3, Point3, Vec3};
+use vcad_kernel_math::{Dir3, Point3, Quantize, Vec3};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_sketch::SketchProfile;
 use vcad_kernel_topo::{HalfEdgeId, Orientation, ShellType, Topology, VertexId};
 
-use crate::frenet::rotation_minimizing_frames;
+use crate::frenet::{rotation_minimizing_frames_at, FrenetFrame};
 use crate::SweepError;
 
+/// Which ends of a sweep or loft are closed with planar cap faces.
+///
+/// Leaving an end uncapped produces an open surface rather than a solid: the
+/// shell has boundary edges there, and volume and mass properties computed
+/// from it are meaningless.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CapMode {
+    /// Cap both ends (a closed solid).
+    #[default]
+    Both,
+    /// Cap only the start of the path.
+    Start,
+    /// Cap only the end of the path.
+    End,
+    /// Leave both ends open, e.g. for a pipe wall.
+    None,
+}
+
+impl CapMode {
+    pub(crate) fn caps_start(self) -> bool {
+        matches!(self, CapMode::Both | CapMode::Start)
+    }
+
+    pub(crate) fn caps_end(self) -> bool {
+        matches!(self, CapMode::Both | CapMode::End)
+    }
+}
+
+/// What a sweep does at a path station where the profile frame is
+/// undefined because the path's tangent vanishes there, e.g. at a cusp or a
+/// stationary point of the parameterization, and what a loft does with a
+/// profile that coincides with the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DegeneratePolicy {
+    /// Fail with [`SweepError::DegenerateFrame`] or
+    /// [`crate::LoftError::DegenerateProfile`].
+    #[default]
+    Error,
+    /// Drop the station, joining the profile rings on either side directly.
+    Skip,
+    /// Keep the station, orienting its ring by interpolating the frames of
+    /// the nearest regular stations on either side. A loft instead places
+    /// the profile between its regular neighbors.
+    Interpolate,
+}
+
+/// A problem a sweep recovered from, per [`SweepOptions::on_degenerate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SweepWarning {
+    /// The degenerate station at this path parameter was dropped.
+    SkippedStation(f64),
+    /// The frame at this path parameter was interpolated from its neighbors.
+    InterpolatedFrame(f64),
+}
+
+/// A swept solid together with the problems recovered from building it.
+#[derive(Debug, Clone)]
+pub struct SweepResult {
+    /// The swept solid.
+    pub solid: BRepSolid,
+    /// Degenerate stations that were skipped or interpolated, in path order.
+    pub warnings: Vec<SweepWarning>,
+}
+
 /// Options for the sweep operation.
 #[derive(Debug, Clone)]
 pub struct SweepOptions {
@@ -27,6 +91,11 @@ pub struct SweepOptions {
     pub arc_segments: u32,
     /// Initial profile rotation around the path tangent (radians). Default: 0.0
     pub orientation_angle: f64,
+    /// Which ends to cap. Default: [`CapMode::Both`].
+    pub caps: CapMode,
+    /// Handling of path stations with an undefined frame. Default:
+    /// [`DegeneratePolicy::Error`].
+    pub on_degenerate: DegeneratePolicy,
 }
 
 impl Default for SweepOptions {
@@ -38,6 +107,8 @@ impl Default for SweepOptions {
             scale_end: 1.0,
             arc_segments: 8,
             orientation_angle: 0.0,
+            caps: CapMode::Both,
+            on_degenerate: DegeneratePolicy::Error,
         }
     }
 }
@@ -54,25 +125,47 @@ impl Default for SweepOptions {
 ///
 /// A B-rep solid with:
 /// * N lateral faces (one per profile segment × path segment)
-/// * 2 cap faces (start and end)
+/// * Up to 2 cap faces (start and end), as selected by `options.caps`
+///
+/// With fewer than two caps the result is an open shell (a surface), not a
+/// closed solid.
 ///
 /// # Errors
 ///
-/// Returns an error if the path has zero length or the profile is invalid.
+/// Returns an error if the path has zero length, the profile is invalid, or
+/// a path station has an undefined frame under [`DegeneratePolicy::Error`].
 pub fn sweep(
     profile: &SketchProfile,
     path: &dyn Curve3d,
     options: SweepOptions,
 ) -> Result<BRepSolid, SweepError> {
+    sweep_with_warnings(profile, path, options).map(|result| result.solid)
+}
+
+/// Sweep a closed profile along a path curve, reporting the degenerate
+/// stations recovered from.
+///
+/// Behaves like [`sweep`]; with [`SweepOptions::on_degenerate`] set to
+/// [`DegeneratePolicy::Skip`] or [`DegeneratePolicy::Interpolate`] the
+/// stations handled that way are listed in [`SweepResult::warnings`].
+///
+/// # Errors
+///
+/// As for [`sweep`].
+pub fn sweep_with_warnings(
+    profile: &SketchProfile,
+    path: &dyn Curve3d,
+    options: SweepOptions,
+) -> Result<SweepResult, SweepError> {
     // Validate inputs
     let path_len = estimate_path_length(path);
     if path_len < 1e-12 {
         return Err(SweepError::ZeroLengthPath);
     }
 
-    if profile.segments.is_empty() {
-        return Err(SweepError::InvalidProfile("empty profile".into()));
-    }
+    profile
+        .validate()
+        .map_err(|e| SweepError::InvalidProfile(e.to_string()))?;
 
     let n_path_segments = if options.path_segments > 0 {
         options.path_segments as usize
@@ -88,13 +181,57 @@ pub fn sweep(
     let arc_segments = options.arc_segments.max(1) as usize;
     let tessellated_profile = profile.tessellate(arc_segments);
     let n_profile_verts = tessellated_profile.segments.len();
-    let n_path_samples = n_path_segments + 1; // number of profile copies
 
-    // Compute rotation-minimizing frames along the path
-    let mut frames = rotation_minimizing_frames(path, n_path_samples);
+    // Path stations, flagging those where the tangent (and so the frame)
+    // is undefined. A stall between two stations becomes a degenerate
+    // station of its own, so the policy applies to it as well.
+    let (t_min, t_max) = path.domain();
+    let mut params = Vec::with_capacity(n_path_segments + 1);
+    let mut degenerate = Vec::with_capacity(n_path_segments + 1);
+    for i in 0..=n_path_segments {
+        let t = t_min + (t_max - t_min) * i as f64 / n_path_segments as f64;
+        if let Some(&prev) = params.last() {
+            if let Some(stall) = stall_between(path, prev, t) {
+                params.push(stall);
+                degenerate.push(true);
+            }
+        }
+        params.push(t);
+        degenerate.push(path.tangent(t).norm() < 1e-12);
+    }
+    if options.on_degenerate == DegeneratePolicy::Error {
+        if let Some(i) = degenerate.iter().position(|&d| d) {
+            return Err(SweepError::DegenerateFrame(params[i]));
+        }
+    }
+
+    // Compute rotation-minimizing frames through the regular stations
+    let regular: Vec<f64> = params
+        .iter()
+        .zip(&degenerate)
+        .filter(|(_, &d)| !d)
+        .map(|(&t, _)| t)
+        .collect();
+    let regular_frames = rotation_minimizing_frames_at(path, &regular);
+    let mut warnings = Vec::new();
+    let mut frames = if options.on_degenerate == DegeneratePolicy::Interpolate {
+        interpolate_degenerate_frames(path, &params, &degenerate, regular_frames, &mut warnings)
+    } else {
+        warnings.extend(
+            params
+                .iter()
+                .zip(&degenerate)
+                .filter(|(_, &d)| d)
+                .map(|(&t, _)| SweepWarning::SkippedStation(t)),
+        );
+        params = regular;
+        regular_frames
+    };
     if frames.len() < 2 {
         return Err(SweepError::ZeroLengthPath);
     }
+    let n_path_samples = frames.len(); // number of profile copies
+    let n_path_segments = n_path_samples - 1;
 
     // Apply initial orientation to all frames (rotates profile around path tangent)
     if options.orientation_angle.abs() > 1e-12 {
@@ -112,8 +249,8 @@ pub fn sweep(
     // Build vertex grid: [path_sample][profile_vertex]
     let mut vertex_grid: Vec<Vec<VertexId>> = Vec::with_capacity(n_path_samples);
 
-    for (path_idx, frame) in frames.iter().enumerate() {
-        let t = path_idx as f64 / (n_path_samples - 1) as f64;
+    for (frame, &param) in frames.iter().zip(&params) {
+        let t = (param - t_min) / (t_max - t_min);
 
         // Compute twist and scale at this position
         let twist = options.twist_angle * t;
@@ -134,13 +271,7 @@ pub fn sweep(
     let mut all_faces = Vec::new();
     let mut he_map: HashMap<([i64; 3], [i64; 3]), HalfEdgeId> = HashMap::new();
 
-    let quantize_pt = |p: Point3| -> [i64; 3] {
-        [
-            (p.x * 1e9).round() as i64,
-            (p.y * 1e9).round() as i64,
-            (p.z * 1e9).round() as i64,
-        ]
-    };
+    let quantize_pt = |p: Point3| p.quantize(1e9);
 
     // Build lateral faces (one quad per profile edge × path segment)
     for path_idx in 0..n_path_segments {
@@ -206,28 +337,32 @@ pub fn sweep(
     }
 
     // Build start cap (first ring, reversed winding for outward normal)
-    let start_ring = &vertex_grid[0];
-    let start_face_id = build_cap_face(
-        &mut topo,
-        &mut geom,
-        start_ring,
-        true,
-        &mut he_map,
-        quantize_pt,
-    );
-    all_faces.push(start_face_id);
+    if options.caps.caps_start() {
+        let start_ring = &vertex_grid[0];
+        let start_face_id = build_cap_face(
+            &mut topo,
+            &mut geom,
+            start_ring,
+            true,
+            &mut he_map,
+            quantize_pt,
+        );
+        all_faces.push(start_face_id);
+    }
 
     // Build end cap (last ring, forward winding)
-    let end_ring = &vertex_grid[n_path_samples - 1];
-    let end_face_id = build_cap_face(
-        &mut topo,
-        &mut geom,
-        end_ring,
-        false,
-        &mut he_map,
-        quantize_pt,
-    );
-    all_faces.push(end_face_id);
+    if options.caps.caps_end() {
+        let end_ring = &vertex_grid[n_path_samples - 1];
+        let end_face_id = build_cap_face(
+            &mut topo,
+            &mut geom,
+            end_ring,
+            false,
+            &mut he_map,
+            quantize_pt,
+        );
+        all_faces.push(end_face_id);
+    }
 
     // Pair twin half-edges
     pair_twin_half_edges(&mut topo, &he_map);
@@ -236,13 +371,93 @@ pub fn sweep(
     let shell = topo.add_shell(all_faces, ShellType::Outer);
     let solid_id = topo.add_solid(shell);
 
-    Ok(BRepSolid {
-        topology: topo,
-        geometry: geom,
-        solid_id,
+    Ok(SweepResult {
+        solid: BRepSolid::new(topo, geom, solid_id),
+        warnings,
     })
 }
 
+/// Tangent samples per station interval when looking for a stall between
+/// stations.
+const STALL_SAMPLES: usize = 8;
+
+/// Where the path's tangent vanishes strictly between the stations `a` and
+/// `b`, if it does.
+///
+/// The tangent length is sampled across the interval and its smallest
+/// interior sample refined by ternary search; a minimum that is negligible
+/// next to the longest sampled tangent is a stall (or a cusp, where the
+/// tangent also reverses).
+fn stall_between(path: &dyn Curve3d, a: f64, b: f64) -> Option<f64> {
+    let speed = |t: f64| path.tangent(t).norm();
+    let step = (b - a) / STALL_SAMPLES as f64;
+    let samples: Vec<f64> = (0..=STALL_SAMPLES)
+        .map(|k| speed(a + step * k as f64))
+        .collect();
+    let scale = samples.iter().copied().fold(0.0, f64::max);
+    let k = (0..=STALL_SAMPLES).min_by(|&i, &j| samples[i].total_cmp(&samples[j]))?;
+    if k == 0 || k == STALL_SAMPLES {
+        // Slowest at a station, which is checked on its own
+        return None;
+    }
+
+    let (mut lo, mut hi) = (a + step * (k - 1) as f64, a + step * (k + 1) as f64);
+    for _ in 0..64 {
+        let m1 = lo + (hi - lo) / 3.0;
+        let m2 = hi - (hi - lo) / 3.0;
+        if speed(m1) < speed(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    let t = 0.5 * (lo + hi);
+    (speed(t) < 1e-9 * scale).then_some(t)
+}
+
+/// Frames at every station, filling in each degenerate one from the nearest
+/// regular stations on either side. `regular_frames` holds the frames at the
+/// regular stations, in order.
+fn interpolate_degenerate_frames(
+    path: &dyn Curve3d,
+    params: &[f64],
+    degenerate: &[bool],
+    regular_frames: Vec<FrenetFrame>,
+    warnings: &mut Vec<SweepWarning>,
+) -> Vec<FrenetFrame> {
+    let regular: Vec<usize> = (0..params.len()).filter(|&i| !degenerate[i]).collect();
+    if regular.is_empty() {
+        return vec![];
+    }
+
+    let mut frames = Vec::with_capacity(params.len());
+    for (i, &t) in params.iter().enumerate() {
+        let k = match regular.binary_search(&i) {
+            Ok(k) => {
+                frames.push(regular_frames[k].clone());
+                continue;
+            }
+            Err(k) => k,
+        };
+        warnings.push(SweepWarning::InterpolatedFrame(t));
+        // Stations past either end of the regular ones copy the nearest frame
+        let frame = match (k.checked_sub(1), regular.get(k)) {
+            (Some(before), Some(&after)) => {
+                let f = (t - params[regular[before]]) / (params[after] - params[regular[before]]);
+                regular_frames[before].lerp(&regular_frames[k], f)
+            }
+            (Some(before), None) => regular_frames[before].clone(),
+            (None, _) => regular_frames[0].clone(),
+        };
+        frames.push(FrenetFrame::from_points(
+            path.evaluate(t),
+            frame.tangent.into_inner(),
+            frame.normal.into_inner(),
+        ));
+    }
+    frames
+}
+
 fn build_cap_face<F>(
     topo: &mut Topology,
     geom: &mut GeometryStore,
@@ -256,8 +471,19 @@ where
 {
     let n = verts.len();
 
+    // Order vertices by the cap's winding, so the plane normal built from
+    // them agrees with the loop and points out of the solid
+    let ordered_verts: Vec<VertexId> = if reversed {
+        verts.iter().rev().copied().collect()
+    } else {
+        verts.to_vec()
+    };
+
     // Get positions
-    let positions: Vec<Point3> = verts.iter().map(|&v| topo.vertices[v].point).collect();
+    let positions: Vec<Point3> = ordered_verts
+        .iter()
+        .map(|&v| topo.vertices[v].point)
+        .collect();
 
     // Create plane surface from first 3 vertices
     let origin = positions[0];
@@ -275,12 +501,6 @@ where
     };
 
     // Create half-edges in the correct order
-    let ordered_verts: Vec<VertexId> = if reversed {
-        verts.iter().rev().copied().collect()
-    } else {
-        verts.to_vec()
-    };
-
     let hes: Vec<HalfEdgeId> = ordered_verts
         .iter()
         .map(|&v| topo.add_half_edge(v))
@@ -365,26 +585,35 @@ fn estimate_path_length(path: &dyn Curve3d) -> f64 {
 ///
 /// The helix is parameterized as:
 /// ```text
-/// x(t) = radius * cos(2π * turns * t)
-/// y(t) = radius * sin(2π * turns * t)
-/// z(t) = pitch * turns * t
+/// r(t)     = radius + (radius_end - radius) * t
+/// x(t)     = r(t) * cos(2π * turns(t))
+/// y(t)     = r(t) * sin(2π * turns(t))
+/// z(t)     = height * t
 /// ```
 ///
-/// Where `t ∈ [0, 1]`.
+/// Where `t ∈ [0, 1]`. For a constant-pitch helix `turns(t) = turns * t`;
+/// [`Helix::variable_pitch`] integrates the pitch function instead.
 #[derive(Debug, Clone)]
 pub struct Helix {
     /// Center of the helix at the base.
     pub center: Point3,
-    /// Radius of the helix.
+    /// Radius of the helix (at the base, for a conical helix).
     pub radius: f64,
-    /// Pitch (height per turn).
+    /// Radius at the top (equal to `radius` for a cylindrical helix).
+    pub radius_end: f64,
+    /// Pitch (height per turn; the average pitch for variable-pitch helices).
     pub pitch: f64,
     /// Total height of the helix.
     pub height: f64,
     /// Number of turns.
     pub turns: f64,
+    /// Cumulative turns sampled at uniform `t`, for variable-pitch helices.
+    turn_table: Option<Vec<f64>>,
 }
 
+/// Number of height samples used to integrate a variable pitch.
+const PITCH_SAMPLES: usize = 256;
+
 impl Helix {
     /// Create a new helix.
     ///
@@ -398,9 +627,51 @@ impl Helix {
         Self {
             center: Point3::origin(),
             radius,
+            radius_end: radius,
             pitch,
             height,
             turns,
+            turn_table: None,
+        }
+    }
+
+    /// Create a conical helix whose radius varies linearly from `r_start`
+    /// at the base to `r_end` at the top.
+    pub fn conical(r_start: f64, r_end: f64, pitch: f64, height: f64) -> Self {
+        let turns = if pitch.abs() > 1e-12 {
+            height / pitch
+        } else {
+            0.0
+        };
+        Self {
+            radius_end: r_end,
+            ..Self::new(r_start, pitch, height, turns)
+        }
+    }
+
+    /// Create a helix whose pitch varies with height.
+    ///
+    /// `pitch_fn` maps a height `z ∈ [0, height]` above the base to the local
+    /// pitch (height per turn) there; non-positive values are clamped to a
+    /// small positive pitch. Useful for springs with closed ends.
+    pub fn variable_pitch(radius: f64, height: f64, pitch_fn: impl Fn(f64) -> f64) -> Self {
+        let rate = |z: f64| 1.0 / pitch_fn(z).max(1e-6);
+
+        // Trapezoidal integration of d(turns)/dz = 1 / pitch(z).
+        let dz = height / PITCH_SAMPLES as f64;
+        let mut table = Vec::with_capacity(PITCH_SAMPLES + 1);
+        table.push(0.0);
+        let mut total = 0.0;
+        for i in 0..PITCH_SAMPLES {
+            let z0 = i as f64 * dz;
+            total += 0.5 * (rate(z0) + rate(z0 + dz)) * dz;
+            table.push(total);
+        }
+
+        let pitch = if total > 1e-12 { height / total } else { 0.0 };
+        Self {
+            turn_table: Some(table),
+            ..Self::new(radius, pitch, height, total)
         }
     }
 
@@ -409,26 +680,45 @@ impl Helix {
         self.center = center;
         self
     }
+
+    /// Cumulative turns at parameter `t` and its derivative with respect to `t`.
+    fn turns_at(&self, t: f64) -> (f64, f64) {
+        match &self.turn_table {
+            None => (self.turns * t, self.turns),
+            Some(table) => {
+                let n = table.len() - 1;
+                let x = t.clamp(0.0, 1.0) * n as f64;
+                let i = (x.floor() as usize).min(n - 1);
+                let slope = (table[i + 1] - table[i]) * n as f64;
+                (table[i] + (x - i as f64) * (table[i + 1] - table[i]), slope)
+            }
+        }
+    }
 }
 
 impl Curve3d for Helix {
     fn evaluate(&self, t: f64) -> Point3 {
-        let angle = 2.0 * PI * self.turns * t;
+        let angle = 2.0 * PI * self.turns_at(t).0;
+        let radius = self.radius + (self.radius_end - self.radius) * t;
         let z = self.height * t;
         Point3::new(
-            self.center.x + self.radius * angle.cos(),
-            self.center.y + self.radius * angle.sin(),
+            self.center.x + radius * angle.cos(),
+            self.center.y + radius * angle.sin(),
             self.center.z + z,
         )
     }
 
     fn tangent(&self, t: f64) -> Vec3 {
-        let angle = 2.0 * PI * self.turns * t;
-        let d_angle = 2.0 * PI * self.turns;
+        let (turns, d_turns) = self.turns_at(t);
+        let angle = 2.0 * PI * turns;
+        let d_angle = 2.0 * PI * d_turns;
+        let radius = self.radius + (self.radius_end - self.radius) * t;
+        let d_radius = self.radius_end - self.radius;
+        let (sin, cos) = angle.sin_cos();
 
         Vec3::new(
-            -self.radius * d_angle * angle.sin(),
-            self.radius * d_angle * angle.cos(),
+            d_radius * cos - radius * d_angle * sin,
+            d_radius * sin + radius * d_angle * cos,
             self.height,
         )
     }
@@ -561,6 +851,30 @@ mod tests {
         assert_eq!(unpaired, 0, "expected no unpaired half-edges");
     }
 
+    #[test]
+    fn test_sweep_cap_modes() {
+        let profile = create_circle_profile(2.0, 8);
+        let path = Line3d::from_points(Point3::origin(), Point3::new(0.0, 0.0, 10.0));
+        let mesh_for = |caps| {
+            let options = SweepOptions {
+                caps,
+                ..Default::default()
+            };
+            vcad_kernel_tessellate::tessellate_brep(&sweep(&profile, &path, options).unwrap(), 32)
+        };
+
+        assert!(mesh_for(CapMode::Both).is_watertight());
+        let open_one_end = mesh_for(CapMode::Start);
+        assert!(!open_one_end.is_watertight());
+        let open = mesh_for(CapMode::None);
+        assert!(!open.is_watertight());
+        // Both boundary rings are open, so twice as many as with one cap.
+        assert_eq!(
+            open.boundary_edges().len(),
+            2 * open_one_end.boundary_edges().len()
+        );
+    }
+
     #[test]
     fn test_sweep_zero_length_path_error() {
         let profile = create_rectangle_profile();
@@ -570,6 +884,144 @@ mod tests {
         assert!(matches!(result, Err(SweepError::ZeroLengthPath)));
     }
 
+    #[test]
+    fn test_sweep_self_intersecting_profile_error() {
+        use vcad_kernel_math::Point2;
+        use vcad_kernel_sketch::SketchSegment;
+
+        // Bow-tie: the diagonals cross at (1, 1).
+        let corners = [(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)];
+        let segments = (0..corners.len())
+            .map(|i| {
+                let (x0, y0) = corners[i];
+                let (x1, y1) = corners[(i + 1) % corners.len()];
+                SketchSegment::Line {
+                    start: Point2::new(x0, y0),
+                    end: Point2::new(x1, y1),
+                }
+            })
+            .collect();
+        let profile = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments).unwrap();
+        let path = Line3d::from_points(Point3::origin(), Point3::new(0.0, 0.0, 10.0));
+
+        let result = sweep(&profile, &path, SweepOptions::default());
+        assert!(
+            matches!(&result, Err(SweepError::InvalidProfile(msg)) if msg.contains("self-intersects")),
+            "{result:?}"
+        );
+    }
+
+    /// The cubic `y = x³` traced as `(t³, t⁹)`, which stops dead at its
+    /// inflection (`t = 0`), leaving the frame there undefined.
+    #[derive(Debug, Clone)]
+    struct StallingCubic;
+
+    impl Curve3d for StallingCubic {
+        fn evaluate(&self, t: f64) -> Point3 {
+            Point3::new(10.0 * t.powi(3), 10.0 * t.powi(9), 0.0)
+        }
+
+        fn tangent(&self, t: f64) -> Vec3 {
+            Vec3::new(30.0 * t.powi(2), 90.0 * t.powi(8), 0.0)
+        }
+
+        fn domain(&self) -> (f64, f64) {
+            (-1.0, 1.0)
+        }
+
+        fn curve_type(&self) -> CurveKind {
+            CurveKind::Line
+        }
+
+        fn clone_box(&self) -> Box<dyn Curve3d> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_sweep_degenerate_station() {
+        let profile = create_circle_profile(0.5, 8);
+        let options = |on_degenerate| SweepOptions {
+            path_segments: 16,
+            on_degenerate,
+            ..Default::default()
+        };
+
+        let result = sweep(&profile, &StallingCubic, options(DegeneratePolicy::Error));
+        assert!(
+            matches!(result, Err(SweepError::DegenerateFrame(t)) if t.abs() < 1e-12),
+            "{result:?}"
+        );
+
+        let interpolated = sweep_with_warnings(
+            &profile,
+            &StallingCubic,
+            options(DegeneratePolicy::Interpolate),
+        )
+        .unwrap();
+        assert_eq!(
+            interpolated.warnings,
+            vec![SweepWarning::InterpolatedFrame(0.0)]
+        );
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&interpolated.solid, 32);
+        assert!(mesh.is_watertight());
+        // The ring at the inflection sits on the path, across its tangent
+        let ring_at = |solid: &BRepSolid| {
+            solid
+                .topology
+                .vertices
+                .values()
+                .filter(|v| v.point.x.abs() < 1e-3 && (v.point.coords.norm() - 0.5).abs() < 1e-6)
+                .count()
+        };
+        let ring_len = ring_at(&interpolated.solid);
+        assert!(ring_len > 0);
+
+        let skipped =
+            sweep_with_warnings(&profile, &StallingCubic, options(DegeneratePolicy::Skip)).unwrap();
+        assert_eq!(skipped.warnings, vec![SweepWarning::SkippedStation(0.0)]);
+        assert_eq!(ring_at(&skipped.solid), 0);
+        assert_eq!(
+            skipped.solid.topology.faces.len() + ring_len,
+            interpolated.solid.topology.faces.len()
+        );
+    }
+
+    #[test]
+    fn test_sweep_stall_between_stations() {
+        let profile = create_circle_profile(0.5, 8);
+        // An odd segment count puts no station at the stall (t = 0)
+        let options = |on_degenerate| SweepOptions {
+            path_segments: 15,
+            on_degenerate,
+            ..Default::default()
+        };
+
+        let result = sweep(&profile, &StallingCubic, options(DegeneratePolicy::Error));
+        assert!(
+            matches!(result, Err(SweepError::DegenerateFrame(t)) if t.abs() < 1e-6),
+            "{result:?}"
+        );
+
+        let interpolated = sweep_with_warnings(
+            &profile,
+            &StallingCubic,
+            options(DegeneratePolicy::Interpolate),
+        )
+        .unwrap();
+        assert!(matches!(
+            interpolated.warnings[..],
+            [SweepWarning::InterpolatedFrame(t)] if t.abs() < 1e-6
+        ));
+        let mesh = vcad_kernel_tessellate::tessellate_brep(&interpolated.solid, 32);
+        assert!(mesh.is_watertight());
+
+        // A helix never stalls
+        let helix = Helix::new(10.0, 5.0, 10.0, 2.0);
+        let swept = sweep_with_warnings(&profile, &helix, options(DegeneratePolicy::Skip));
+        assert!(swept.unwrap().warnings.is_empty());
+    }
+
     #[test]
     fn test_helix_evaluate() {
         let helix = Helix::new(10.0, 5.0, 10.0, 2.0);
@@ -620,4 +1072,67 @@ mod tests {
         }
         (vol / 6.0).abs()
     }
+
+    /// Cap loops (the only faces with more than four vertices) as
+    /// `(centroid z, mean distance of the centroid from the z axis)`, sorted by z.
+    fn cap_rings(solid: &BRepSolid) -> Vec<(f64, f64)> {
+        let topo = &solid.topology;
+        let mut rings: Vec<(f64, f64)> = topo
+            .faces
+            .values()
+            .map(|f| topo.loop_vertices(f.outer_loop))
+            .filter(|verts| verts.len() > 4)
+            .map(|verts| {
+                let sum = verts
+                    .iter()
+                    .fold(Vec3::zeros(), |acc, &v| acc + topo.vertices[v].point.coords);
+                let c = sum / verts.len() as f64;
+                (c.z, (c.x * c.x + c.y * c.y).sqrt())
+            })
+            .collect();
+        rings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        rings
+    }
+
+    #[test]
+    fn test_sweep_conical_helix() {
+        let profile = create_circle_profile(0.5, 8);
+        let helix = Helix::conical(10.0, 4.0, 5.0, 20.0);
+        assert!((helix.turns - 4.0).abs() < 1e-12);
+
+        let p1 = helix.evaluate(1.0);
+        assert!((p1.x - 4.0).abs() < 1e-9);
+        assert!((p1.z - 20.0).abs() < 1e-9);
+
+        let solid = sweep(&profile, &helix, SweepOptions::default()).unwrap();
+        let unpaired = solid
+            .topology
+            .half_edges
+            .values()
+            .filter(|he| he.twin.is_none())
+            .count();
+        assert_eq!(unpaired, 0, "expected no unpaired half-edges");
+
+        let rings = cap_rings(&solid);
+        assert_eq!(rings.len(), 2);
+        let (bottom, top) = (rings[0].1, rings[1].1);
+        assert!((bottom - 10.0).abs() < 0.1, "bottom radius {bottom}");
+        assert!((top - 4.0).abs() < 0.1, "top radius {top}");
+    }
+
+    #[test]
+    fn test_helix_variable_pitch() {
+        // Tight 1mm pitch over the bottom 4mm, then 4mm pitch: 4 + 4 turns.
+        let helix = Helix::variable_pitch(5.0, 20.0, |z| if z < 4.0 { 1.0 } else { 4.0 });
+        assert!((helix.turns - 8.0).abs() < 0.1, "turns {}", helix.turns);
+
+        // Tangent agrees with a finite difference of the position.
+        let t = 0.6;
+        let h = 1e-6;
+        let fd = (helix.evaluate(t + h) - helix.evaluate(t - h)) / (2.0 * h);
+        assert!((fd - helix.tangent(t)).norm() < 1e-3 * fd.norm());
+
+        let profile = create_circle_profile(0.3, 8);
+        assert!(sweep(&profile, &helix, SweepOptions::default()).is_ok());
+    }
 }
@@ -0,0 +1,212 @@
+//! ISO metric screw threads built by sweeping a tooth profile along a helix.
+
+use std::f64::consts::PI;
+
+use vcad_kernel_math::{Point2, Point3, Vec3};
+use vcad_kernel_primitives::BRepSolid;
+use vcad_kernel_sketch::{SketchProfile, SketchSegment};
+
+use crate::frenet::rotation_minimizing_frames;
+use crate::sweep::{sweep, Helix, SweepOptions};
+use crate::SweepError;
+
+/// Path segments per helix turn.
+const SEGMENTS_PER_TURN: f64 = 32.0;
+
+/// Parameters of an ISO metric (60°) thread, e.g. M8×1.25.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThreadSpec {
+    /// Nominal (major) diameter in mm.
+    pub diameter: f64,
+    /// Axial distance between adjacent crests in mm.
+    pub pitch: f64,
+    /// Threaded length along +Z in mm.
+    pub length: f64,
+    /// `true` for a tapped hole, `false` for a bolt.
+    pub internal: bool,
+}
+
+impl ThreadSpec {
+    /// Height of the fundamental 60° triangle, `H = √3/2 · P`.
+    pub fn fundamental_height(&self) -> f64 {
+        3.0_f64.sqrt() / 2.0 * self.pitch
+    }
+
+    /// Minor (root) diameter of the basic profile, `D − 5H/4`.
+    pub fn minor_diameter(&self) -> f64 {
+        self.diameter - 1.25 * self.fundamental_height()
+    }
+}
+
+/// Build the helical thread tooth for `spec`.
+///
+/// The solid winds around the Z axis from z = 0 to `spec.length`, between
+/// the minor diameter and the major diameter. For an external thread, union
+/// it with a rod of [`ThreadSpec::minor_diameter`]; for an internal thread,
+/// subtract it from a part bored to the minor diameter. Internal teeth run
+/// out to the sharp apex of the fundamental triangle (H/8 beyond the major
+/// diameter) so the mating bolt's crest has clearance.
+///
+/// # Errors
+///
+/// Returns [`SweepError::InvalidThread`] if the diameter, pitch, or length
+/// is not positive, or the pitch is too coarse for the diameter.
+pub fn thread(spec: &ThreadSpec) -> Result<BRepSolid, SweepError> {
+    if !(spec.diameter > 0.0 && spec.pitch > 0.0 && spec.length > 0.0) {
+        return Err(SweepError::InvalidThread(
+            "diameter, pitch and length must be positive".into(),
+        ));
+    }
+    let minor_radius = spec.minor_diameter() / 2.0;
+    if minor_radius <= 0.0 {
+        return Err(SweepError::InvalidThread(format!(
+            "pitch {} is too coarse for diameter {}",
+            spec.pitch, spec.diameter
+        )));
+    }
+    let half_root = 3.0 * spec.pitch / 8.0;
+    if spec.length <= 2.0 * half_root {
+        return Err(SweepError::InvalidThread(format!(
+            "length {} is shorter than one tooth",
+            spec.length
+        )));
+    }
+
+    // Tooth cross-section: x is radial from the minor radius, y is axial.
+    let h = spec.fundamental_height();
+    let points = if spec.internal {
+        vec![
+            Point2::new(0.0, -half_root),
+            Point2::new(0.75 * h, 0.0),
+            Point2::new(0.0, half_root),
+        ]
+    } else {
+        let half_crest = spec.pitch / 16.0;
+        let depth = 0.625 * h;
+        vec![
+            Point2::new(0.0, -half_root),
+            Point2::new(depth, -half_crest),
+            Point2::new(depth, half_crest),
+            Point2::new(0.0, half_root),
+        ]
+    };
+    let segments = (0..points.len())
+        .map(|i| SketchSegment::Line {
+            start: points[i],
+            end: points[(i + 1) % points.len()],
+        })
+        .collect();
+    let profile = SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments)
+        .map_err(|e| SweepError::InvalidProfile(e.to_string()))?;
+
+    // Keep the whole tooth inside [0, length].
+    let height = spec.length - 2.0 * half_root;
+    let turns = height / spec.pitch;
+    let helix = Helix::new(minor_radius, spec.pitch, height, turns)
+        .with_center(Point3::new(0.0, 0.0, half_root));
+    let path_segments = ((turns * SEGMENTS_PER_TURN).ceil() as u32).max(8);
+
+    // Rotation-minimizing frames drift around a helix. Align the profile's
+    // x axis with the outward radial direction at the start and undo the
+    // drift (linear in arc length) with a matching twist.
+    let frames = rotation_minimizing_frames(&helix, path_segments as usize + 1);
+    let radial_angle = |i: usize| {
+        let f = &frames[i];
+        let radial = Vec3::new(f.position.x, f.position.y, 0.0);
+        radial
+            .dot(f.binormal.as_ref())
+            .atan2(radial.dot(f.normal.as_ref()))
+    };
+    let start = radial_angle(0);
+    let mut drift = 0.0;
+    let mut prev = start;
+    for i in 1..frames.len() {
+        let angle = radial_angle(i);
+        drift += (angle - prev + PI).rem_euclid(2.0 * PI) - PI;
+        prev = angle;
+    }
+
+    sweep(
+        &profile,
+        &helix,
+        SweepOptions {
+            path_segments,
+            orientation_angle: start,
+            twist_angle: drift,
+            ..Default::default()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extents(solid: &BRepSolid) -> (f64, f64, f64) {
+        let mut r_max: f64 = 0.0;
+        let (mut z_min, mut z_max) = (f64::INFINITY, f64::NEG_INFINITY);
+        for v in solid.topology.vertices.values() {
+            let p = v.point;
+            r_max = r_max.max((p.x * p.x + p.y * p.y).sqrt());
+            z_min = z_min.min(p.z);
+            z_max = z_max.max(p.z);
+        }
+        (r_max, z_min, z_max)
+    }
+
+    #[test]
+    fn test_m8_external_thread() {
+        let spec = ThreadSpec {
+            diameter: 8.0,
+            pitch: 1.25,
+            length: 10.0,
+            internal: false,
+        };
+        assert!((spec.minor_diameter() - 6.647).abs() < 1e-3);
+
+        let solid = thread(&spec).unwrap();
+        let (r_max, z_min, z_max) = extents(&solid);
+        assert!((r_max - 4.0).abs() < 1e-3, "major radius {r_max}");
+        // The profile plane leans with the ~3° lead angle.
+        assert!(z_min.abs() < 0.05, "z_min {z_min}");
+        assert!((z_max - 10.0).abs() < 0.05, "z_max {z_max}");
+
+        let unpaired = solid
+            .topology
+            .half_edges
+            .values()
+            .filter(|he| he.twin.is_none())
+            .count();
+        assert_eq!(unpaired, 0, "expected no unpaired half-edges");
+    }
+
+    #[test]
+    fn test_internal_thread_clears_major_diameter() {
+        let spec = ThreadSpec {
+            diameter: 8.0,
+            pitch: 1.25,
+            length: 10.0,
+            internal: true,
+        };
+        let (r_max, _, _) = extents(&thread(&spec).unwrap());
+        let apex = 4.0 + spec.fundamental_height() / 8.0;
+        assert!((r_max - apex).abs() < 1e-3, "apex radius {r_max}");
+    }
+
+    #[test]
+    fn test_invalid_thread_spec() {
+        let spec = ThreadSpec {
+            diameter: 8.0,
+            pitch: 0.0,
+            length: 10.0,
+            internal: false,
+        };
+        assert!(matches!(thread(&spec), Err(SweepError::InvalidThread(_))));
+
+        let coarse = ThreadSpec {
+            pitch: 10.0,
+            ..spec
+        };
+        assert!(matches!(thread(&coarse), Err(SweepError::InvalidThread(_))));
+    }
+}
@@ -0,0 +1,417 @@
+//! Mesh simplification by quadric error metric edge collapse.
+//!
+//! Implements Garland & Heckbert's algorithm: every vertex accumulates the
+//! squared-distance quadric of its incident triangle planes, and edges are
+//! collapsed cheapest-first to the point minimizing the combined quadric.
+//! Collapses that would fold a triangle over or pinch the surface into a
+//! non-manifold shape are skipped, and vertices on open boundaries never
+//! move, so holes and sheet edges keep their outline.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use vcad_kernel_math::Vec3;
+
+use crate::stl::weld_triangles;
+use crate::TriangleMesh;
+
+/// Symmetric 4x4 error quadric, stored as its upper triangle.
+#[derive(Debug, Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    /// Squared distance to the plane `n·x + d = 0`, scaled by `weight`.
+    fn plane(n: Vec3, d: f64, weight: f64) -> Self {
+        let (a, b, c) = (n.x, n.y, n.z);
+        Self(
+            [
+                a * a,
+                a * b,
+                a * c,
+                a * d,
+                b * b,
+                b * c,
+                b * d,
+                c * c,
+                c * d,
+                d * d,
+            ]
+            .map(|q| q * weight),
+        )
+    }
+
+    fn add(&self, other: &Quadric) -> Quadric {
+        let mut sum = self.0;
+        for (s, o) in sum.iter_mut().zip(other.0) {
+            *s += o;
+        }
+        Quadric(sum)
+    }
+
+    fn error(&self, p: Vec3) -> f64 {
+        let [a2, ab, ac, ad, b2, bc, bd, c2, cd, d2] = self.0;
+        let (x, y, z) = (p.x, p.y, p.z);
+        a2 * x * x
+            + 2.0 * ab * x * y
+            + 2.0 * ac * x * z
+            + 2.0 * ad * x
+            + b2 * y * y
+            + 2.0 * bc * y * z
+            + 2.0 * bd * y
+            + c2 * z * z
+            + 2.0 * cd * z
+            + d2
+    }
+
+    /// Point minimizing the error, if the quadric is well conditioned.
+    fn minimizer(&self) -> Option<Vec3> {
+        let [a2, ab, ac, ad, b2, bc, bd, c2, cd, _] = self.0;
+        let det = a2 * (b2 * c2 - bc * bc) - ab * (ab * c2 - bc * ac) + ac * (ab * bc - b2 * ac);
+        let scale = a2.abs().max(b2.abs()).max(c2.abs());
+        if det.abs() <= 1e-12 * scale * scale * scale {
+            return None;
+        }
+        // Cramer's rule on A·p = -b
+        let (rx, ry, rz) = (-ad, -bd, -cd);
+        let x = rx * (b2 * c2 - bc * bc) - ab * (ry * c2 - bc * rz) + ac * (ry * bc - b2 * rz);
+        let y = a2 * (ry * c2 - bc * rz) - rx * (ab * c2 - bc * ac) + ac * (ab * rz - ry * ac);
+        let z = a2 * (b2 * rz - ry * bc) - ab * (ab * rz - ry * ac) + rx * (ab * bc - b2 * ac);
+        Some(Vec3::new(x, y, z) / det)
+    }
+}
+
+/// A queued edge collapse: `(cost bits, u, v, version of u, version of v)`.
+/// Non-negative `f64` costs order the same as their bit patterns.
+type Candidate = (u64, usize, usize, u32, u32);
+
+/// Working state for edge collapses over a welded copy of the mesh.
+struct Decimator {
+    positions: Vec<Vec3>,
+    triangles: Vec<[usize; 3]>,
+    alive: Vec<bool>,
+    /// Triangles incident to each vertex (may include dead ones).
+    vertex_triangles: Vec<Vec<usize>>,
+    quadrics: Vec<Quadric>,
+    removed: Vec<bool>,
+    /// Vertices on an open boundary, which are never moved.
+    locked: Vec<bool>,
+    /// Bumped whenever a vertex changes, invalidating queued collapses.
+    versions: Vec<u32>,
+    queue: BinaryHeap<Reverse<Candidate>>,
+}
+
+impl TriangleMesh {
+    /// Simplify the mesh to about `target_ratio` of its triangle count.
+    ///
+    /// Coincident vertices are welded first, so independently tessellated
+    /// faces collapse across their shared edges. Open boundaries are kept
+    /// exactly. The result has shared vertices with area-weighted normals,
+    /// like an imported STL. Simplification stops early if no further
+    /// collapse is valid, and a ratio of 1 or more returns a copy.
+    pub fn decimate(&self, target_ratio: f64) -> TriangleMesh {
+        if target_ratio >= 1.0 || self.indices.is_empty() {
+            return self.clone();
+        }
+        let target = (self.num_triangles() as f64 * target_ratio.max(0.0)).ceil() as usize;
+
+        let mut decimator = Decimator::new(self);
+        decimator.run(target);
+        decimator.into_mesh()
+    }
+}
+
+impl Decimator {
+    fn new(mesh: &TriangleMesh) -> Self {
+        let mut lookup: HashMap<[u32; 3], usize> = HashMap::new();
+        let mut positions = Vec::new();
+        let welded: Vec<usize> = mesh
+            .vertices
+            .chunks_exact(3)
+            .map(|v| {
+                // `+ 0.0` folds -0.0 into 0.0 so both weld together.
+                let key = [v[0], v[1], v[2]].map(|c| (c + 0.0).to_bits());
+                *lookup.entry(key).or_insert_with(|| {
+                    positions.push(Vec3::new(v[0] as f64, v[1] as f64, v[2] as f64));
+                    positions.len() - 1
+                })
+            })
+            .collect();
+
+        let triangles: Vec<[usize; 3]> = mesh
+            .indices
+            .chunks_exact(3)
+            .map(|t| [0, 1, 2].map(|k| welded[t[k] as usize]))
+            .filter(|&[a, b, c]| a != b && b != c && c != a)
+            .collect();
+
+        let n = positions.len();
+        let mut vertex_triangles = vec![Vec::new(); n];
+        let mut quadrics = vec![Quadric::default(); n];
+        let mut edge_uses: HashMap<(usize, usize), usize> = HashMap::new();
+        for (t, &[a, b, c]) in triangles.iter().enumerate() {
+            let cross = (positions[b] - positions[a]).cross(&(positions[c] - positions[a]));
+            let area = cross.norm() / 2.0;
+            if area > 0.0 {
+                let normal = cross.normalize();
+                let q = Quadric::plane(normal, -normal.dot(&positions[a]), area);
+                for v in [a, b, c] {
+                    quadrics[v] = quadrics[v].add(&q);
+                }
+            }
+            for v in [a, b, c] {
+                vertex_triangles[v].push(t);
+            }
+            for (x, y) in [(a, b), (b, c), (c, a)] {
+                *edge_uses.entry((x.min(y), x.max(y))).or_insert(0) += 1;
+            }
+        }
+
+        let mut locked = vec![false; n];
+        for (&(x, y), &uses) in &edge_uses {
+            if uses != 2 {
+                locked[x] = true;
+                locked[y] = true;
+            }
+        }
+
+        let mut decimator = Self {
+            alive: vec![true; triangles.len()],
+            positions,
+            triangles,
+            vertex_triangles,
+            quadrics,
+            removed: vec![false; n],
+            locked,
+            versions: vec![0; n],
+            queue: BinaryHeap::new(),
+        };
+        for &(u, v) in edge_uses.keys() {
+            decimator.push_edge(u, v);
+        }
+        decimator
+    }
+
+    /// Position a collapse of edge `uv` would move the merged vertex to, or
+    /// `None` if the edge must not collapse.
+    fn collapse_target(&self, u: usize, v: usize) -> Option<Vec3> {
+        match (self.locked[u], self.locked[v]) {
+            (true, true) => None,
+            (true, false) => Some(self.positions[u]),
+            (false, true) => Some(self.positions[v]),
+            (false, false) => {
+                let q = self.quadrics[u].add(&self.quadrics[v]);
+                let (pu, pv) = (self.positions[u], self.positions[v]);
+                q.minimizer().or_else(|| {
+                    [pu, pv, (pu + pv) / 2.0]
+                        .into_iter()
+                        .min_by(|a, b| q.error(*a).total_cmp(&q.error(*b)))
+                })
+            }
+        }
+    }
+
+    fn push_edge(&mut self, u: usize, v: usize) {
+        let Some(target) = self.collapse_target(u, v) else {
+            return;
+        };
+        let cost = self.quadrics[u]
+            .add(&self.quadrics[v])
+            .error(target)
+            .max(0.0);
+        self.queue.push(Reverse((
+            cost.to_bits(),
+            u,
+            v,
+            self.versions[u],
+            self.versions[v],
+        )));
+    }
+
+    fn live_triangles(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.vertex_triangles[v]
+            .iter()
+            .copied()
+            .filter(|&t| self.alive[t])
+    }
+
+    fn neighbors(&self, v: usize) -> HashSet<usize> {
+        self.live_triangles(v)
+            .flat_map(|t| self.triangles[t])
+            .filter(|&w| w != v)
+            .collect()
+    }
+
+    fn run(&mut self, target: usize) {
+        let mut live = self.triangles.len();
+        while live > target {
+            let Some(Reverse((_, u, v, version_u, version_v))) = self.queue.pop() else {
+                break;
+            };
+            if self.removed[u]
+                || self.removed[v]
+                || self.versions[u] != version_u
+                || self.versions[v] != version_v
+            {
+                continue;
+            }
+            let Some(target) = self.collapse_target(u, v) else {
+                continue;
+            };
+            if !self.can_collapse(u, v, target) {
+                continue;
+            }
+            live -= self.collapse(u, v, target);
+        }
+    }
+
+    /// Whether collapsing `uv` to `target` keeps the surface manifold and no
+    /// remaining triangle flips over.
+    fn can_collapse(&self, u: usize, v: usize, target: Vec3) -> bool {
+        // Link condition: the only vertices adjacent to both ends are the
+        // apexes of the triangles being removed.
+        let shared = self
+            .live_triangles(u)
+            .filter(|&t| self.triangles[t].contains(&v));
+        let apexes = shared.count();
+        let common = self.neighbors(u).intersection(&self.neighbors(v)).count();
+        if apexes == 0 || common != apexes {
+            return false;
+        }
+
+        for w in [u, v] {
+            for t in self.live_triangles(w) {
+                let tri = self.triangles[t];
+                if tri.contains(&u) && tri.contains(&v) {
+                    continue;
+                }
+                let [a, b, c] = tri.map(|x| self.positions[x]);
+                let before = (b - a).cross(&(c - a));
+                let [a, b, c] = tri.map(|x| if x == w { target } else { self.positions[x] });
+                let after = (b - a).cross(&(c - a));
+                if before.dot(&after) <= 0.0 {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Merge `v` into `u` at `target`. Returns the number of triangles removed.
+    fn collapse(&mut self, u: usize, v: usize, target: Vec3) -> usize {
+        let mut removed = 0;
+        for t in std::mem::take(&mut self.vertex_triangles[v]) {
+            if !self.alive[t] {
+                continue;
+            }
+            if self.triangles[t].contains(&u) {
+                self.alive[t] = false;
+                removed += 1;
+            } else {
+                for x in &mut self.triangles[t] {
+                    if *x == v {
+                        *x = u;
+                    }
+                }
+                self.vertex_triangles[u].push(t);
+            }
+        }
+        self.vertex_triangles[u].retain(|&t| self.alive[t]);
+
+        self.positions[u] = target;
+        self.quadrics[u] = self.quadrics[u].add(&self.quadrics[v]);
+        self.locked[u] |= self.locked[v];
+        self.removed[v] = true;
+        self.versions[u] += 1;
+
+        for w in self.neighbors(u) {
+            self.push_edge(u, w);
+        }
+        removed
+    }
+
+    fn into_mesh(self) -> TriangleMesh {
+        let corners: Vec<[[f32; 3]; 3]> = self
+            .triangles
+            .iter()
+            .zip(&self.alive)
+            .filter(|(_, &alive)| alive)
+            .map(|(tri, _)| {
+                tri.map(|v| {
+                    let p = self.positions[v];
+                    [p.x as f32, p.y as f32, p.z as f32]
+                })
+            })
+            .collect();
+        weld_triangles(&corners)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tessellate_brep;
+    use crate::TriangleMesh;
+    use vcad_kernel_primitives::make_sphere;
+
+    fn bounds(mesh: &TriangleMesh) -> ([f32; 3], [f32; 3]) {
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for v in mesh.vertices.chunks_exact(3) {
+            for k in 0..3 {
+                min[k] = min[k].min(v[k]);
+                max[k] = max[k].max(v[k]);
+            }
+        }
+        (min, max)
+    }
+
+    #[test]
+    fn test_decimate_sphere() {
+        let mesh = tessellate_brep(&make_sphere(10.0, 64), 64);
+        let decimated = mesh.decimate(0.25);
+
+        let ratio = decimated.num_triangles() as f64 / mesh.num_triangles() as f64;
+        assert!((0.2..=0.3).contains(&ratio), "ratio {ratio}");
+        assert_eq!(decimated.normals.len(), decimated.vertices.len());
+
+        let (min, max) = bounds(&mesh);
+        let (dmin, dmax) = bounds(&decimated);
+        for k in 0..3 {
+            assert!((min[k] - dmin[k]).abs() < 0.2, "min {min:?} vs {dmin:?}");
+            assert!((max[k] - dmax[k]).abs() < 0.2, "max {max:?} vs {dmax:?}");
+        }
+    }
+
+    #[test]
+    fn test_decimate_keeps_boundary() {
+        // A flat 10x10 grid of quads: an open sheet whose rim must stay put.
+        let n = 10;
+        let mut mesh = TriangleMesh::new();
+        for j in 0..=n {
+            for i in 0..=n {
+                mesh.vertices.extend([i as f32, j as f32, 0.0]);
+                mesh.normals.extend([0.0, 0.0, 1.0]);
+            }
+        }
+        for j in 0..n {
+            for i in 0..n {
+                let a = (j * (n + 1) + i) as u32;
+                let (b, c, d) = (a + 1, a + n as u32 + 2, a + n as u32 + 1);
+                mesh.indices.extend([a, b, c, a, c, d]);
+            }
+        }
+
+        let decimated = mesh.decimate(0.1);
+        assert!(decimated.num_triangles() < mesh.num_triangles() / 2);
+        assert_eq!(bounds(&decimated), bounds(&mesh));
+        let rim = |v: &[f32]| v[0] == 0.0 || v[0] == n as f32 || v[1] == 0.0 || v[1] == n as f32;
+        assert_eq!(
+            decimated
+                .vertices
+                .chunks_exact(3)
+                .filter(|v| rim(v))
+                .count(),
+            4 * n
+        );
+        assert!(decimated.vertices.chunks_exact(3).all(|v| v[2] == 0.0));
+    }
+}
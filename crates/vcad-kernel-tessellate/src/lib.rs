@@ -5,15 +5,26 @@
 //! Converts B-rep faces into triangle meshes by:
 //! 1. Sampling face boundaries in parameter space
 //! 2. Generating interior sample points
-//! 3. Triangulating via ear-clipping
+//! 3. Triangulating via ear-clipping, refined to constrained Delaunay
+//!    for concave and holed planar faces
 //! 4. Mapping back to 3D via surface evaluation
 
 use std::f64::consts::PI;
 use vcad_kernel_geom::{BilinearSurface, GeometryStore, Surface, SurfaceKind};
-use vcad_kernel_math::{Point2, Point3, Vec3};
+use vcad_kernel_math::triangulate::triangulate_loops;
+use vcad_kernel_math::{Point2, Point3, Quantize, Vec3};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_topo::{FaceId, Orientation, Topology};
 
+mod decimate;
+mod normals;
+mod obj;
+mod stl;
+mod validate;
+
+pub use obj::{objects_to_obj, ObjError};
+pub use stl::StlError;
+
 /// Output triangle mesh for rendering and export.
 #[derive(Debug, Clone)]
 pub struct TriangleMesh {
@@ -113,7 +124,7 @@ pub fn tessellate_solid(brep: &BRepSolid, params: &TessellationParams) -> Triang
     let solid = &brep.topology.solids[brep.solid_id];
     let shell = &brep.topology.shells[solid.outer_shell];
 
-    for &face_id in &shell.faces {
+    for face_id in ordered_faces(&brep.topology, &shell.faces) {
         let face_mesh = tessellate_face(&brep.topology, &brep.geometry, face_id, params);
 
         // Validate face mesh before merge
@@ -153,6 +164,57 @@ pub fn tessellate_solid(brep: &BRepSolid, params: &TessellationParams) -> Triang
     mesh
 }
 
+/// Sort a shell's faces into a canonical emission order.
+///
+/// Face handles come out of slotmaps, and edits such as booleans can
+/// rebuild a shell with its faces in a different order, which would
+/// reorder the emitted vertices and triangles. Faces are ordered by the
+/// sorted positions of their loop vertices alone, so identical geometry
+/// always tessellates to identical buffers regardless of how its surfaces
+/// were numbered. Faces with the same vertex set are then ordered by each
+/// loop's vertex count and its vertices in loop order, starting from the
+/// smallest. Only faces whose loops match exactly, such as coincident
+/// duplicates, are left in shell order, which is not deterministic.
+fn ordered_faces(topo: &Topology, faces: &[FaceId]) -> Vec<FaceId> {
+    let loop_points = |loop_id| -> Vec<[i64; 3]> {
+        topo.loop_half_edges(loop_id)
+            .map(|he| {
+                topo.vertices[topo.half_edges[he].origin]
+                    .point
+                    .quantize(1e6)
+            })
+            .collect()
+    };
+    let mut keyed: Vec<_> = faces
+        .iter()
+        .map(|&face_id| {
+            let face = &topo.faces[face_id];
+            let mut loops: Vec<Vec<[i64; 3]>> = std::iter::once(face.outer_loop)
+                .chain(face.inner_loops.iter().copied())
+                .map(loop_points)
+                .collect();
+            let mut signature: Vec<[i64; 3]> = loops.iter().flatten().copied().collect();
+            signature.sort_unstable();
+
+            // Each loop read from its smallest vertex, so the starting
+            // half-edge doesn't matter; holes in a canonical order.
+            for points in &mut loops {
+                if let Some(start) = (0..points.len()).min_by_key(|&k| points[k]) {
+                    points.rotate_left(start);
+                }
+            }
+            loops[1..].sort_unstable();
+            let tiebreak: Vec<(usize, Vec<[i64; 3]>)> = loops
+                .into_iter()
+                .map(|points| (points.len(), points))
+                .collect();
+            ((signature, tiebreak), face_id)
+        })
+        .collect();
+    keyed.sort_by(|a, b| a.0.cmp(&b.0));
+    keyed.into_iter().map(|(_, face_id)| face_id).collect()
+}
+
 /// Tessellate a single B-rep face.
 fn tessellate_face(
     topo: &Topology,
@@ -280,62 +342,75 @@ fn tessellate_planar_face_core(outer_verts: &[Point3], reversed: bool) -> Triang
             mesh
         }
         None => {
-            // Polygon is too concave for fan triangulation - use ear clipping
-            tessellate_concave_polygon(outer_verts, reversed)
+            // Polygon is too concave for fan triangulation
+            tessellate_planar_loops(outer_verts, &[], reversed)
         }
     }
 }
 
-/// Tessellate a concave polygon using ear clipping algorithm.
-/// This is the fallback when fan triangulation cannot produce valid triangles.
-fn tessellate_concave_polygon(verts: &[Point3], reversed: bool) -> TriangleMesh {
-    let n = verts.len();
+/// Triangulate a planar polygon with holes using the shared constrained
+/// Delaunay triangulator.
+///
+/// This is the fallback for polygons too concave for a fan, and the only
+/// path for faces with holes. Triangles wind like the outer loop, or the
+/// other way if `reversed`. Vertices are the outer loop's followed by each
+/// hole's, in order.
+fn tessellate_planar_loops(outer: &[Point3], holes: &[Vec<Point3>], reversed: bool) -> TriangleMesh {
+    let n = outer.len();
     if n < 3 {
         return TriangleMesh::new();
     }
 
-    // Build 2D projection for ear clipping.
-    // Compute the face plane from first 3 non-collinear vertices.
-    let e1 = verts[1] - verts[0];
-    let e2 = verts[2] - verts[0];
-    let mut face_normal = e1.cross(&e2);
-    for i in 3..n {
-        if face_normal.norm() > 1e-12 {
-            break;
-        }
-        let ei = verts[i] - verts[0];
-        face_normal = e1.cross(&ei);
+    // Winding normal of the outer loop, by Newell's method
+    let mut normal = Vec3::zeros();
+    for i in 0..n {
+        let (curr, next) = (outer[i], outer[(i + 1) % n]);
+        normal.x += (curr.y - next.y) * (curr.z + next.z);
+        normal.y += (curr.z - next.z) * (curr.x + next.x);
+        normal.z += (curr.x - next.x) * (curr.y + next.y);
     }
-
-    if face_normal.norm() < 1e-12 {
+    if normal.norm() < 1e-12 {
         // Degenerate polygon - all points collinear
         return TriangleMesh::new();
     }
+    let normal = normal.normalize();
 
-    let u_axis = e1.normalize();
-    let v_axis = face_normal.cross(&e1).normalize();
-    let origin = verts[0];
+    // In-plane axes with u × v along the normal, so that counter-clockwise
+    // triangles in 2D wind like the outer loop in 3D
+    let longest = (0..n)
+        .map(|i| outer[(i + 1) % n] - outer[i])
+        .max_by(|a, b| a.norm_squared().total_cmp(&b.norm_squared()))
+        .unwrap();
+    let u_axis = (longest - normal * longest.dot(&normal)).normalize();
+    let v_axis = normal.cross(&u_axis);
+    let origin = outer[0];
 
-    // Project 3D points to 2D
-    let verts_2d: Vec<(f64, f64)> = verts
+    let loops: Vec<&[Point3]> = std::iter::once(outer)
+        .chain(holes.iter().map(Vec::as_slice))
+        .collect();
+    let loops_2d: Vec<Vec<Point2>> = loops
         .iter()
-        .map(|p| {
-            let d = *p - origin;
-            (d.dot(&u_axis), d.dot(&v_axis))
+        .map(|verts| {
+            verts
+                .iter()
+                .map(|p| {
+                    let d = *p - origin;
+                    Point2::new(d.dot(&u_axis), d.dot(&v_axis))
+                })
+                .collect()
         })
         .collect();
 
-    // Build mesh with all 3D vertices
     let mut mesh = TriangleMesh::new();
-    for v in verts {
+    for v in loops.iter().flat_map(|verts| verts.iter()) {
         mesh.vertices.push(v.x as f32);
         mesh.vertices.push(v.y as f32);
         mesh.vertices.push(v.z as f32);
     }
-
-    // Run ear clipping on the 2D projection
-    let indices: Vec<usize> = (0..n).collect();
-    ear_clip_triangulate(&verts_2d, &indices, &mut mesh.indices, reversed);
+    for [a, b, c] in triangulate_loops(&loops_2d) {
+        let (b, c) = if reversed { (c, b) } else { (b, c) };
+        mesh.indices.extend([a as u32, b as u32, c as u32]);
+    }
 
     mesh
 }
@@ -500,651 +575,25 @@ fn tessellate_bilinear_face(
 }
 
 /// Tessellate a planar face with inner loops (holes).
-/// Uses a ring-based approach for better triangle quality: adds intermediate
-/// Steiner points around each hole to prevent long thin triangles.
 fn tessellate_planar_face_with_holes(
     topo: &Topology,
     face_id: FaceId,
     reversed: bool,
 ) -> TriangleMesh {
     let face = &topo.faces[face_id];
-
-    // Get outer loop vertices
-    let outer_verts: Vec<Point3> = topo
-        .loop_half_edges(face.outer_loop)
-        .map(|he| topo.vertices[topo.half_edges[he].origin].point)
-        .collect();
-
-    if outer_verts.len() < 3 {
-        return TriangleMesh::new();
-    }
-
-    // Get all inner loop vertices
-    let mut inner_loops: Vec<Vec<Point3>> = Vec::new();
-    for &inner_loop in &face.inner_loops {
-        let inner_verts: Vec<Point3> = topo
-            .loop_half_edges(inner_loop)
+    let loop_points = |loop_id| -> Vec<Point3> {
+        topo.loop_half_edges(loop_id)
             .map(|he| topo.vertices[topo.half_edges[he].origin].point)
-            .collect();
-        if inner_verts.len() >= 3 {
-            inner_loops.push(inner_verts);
-        }
-    }
-
-    if inner_loops.is_empty() {
-        // No valid inner loops, fall back to simple triangulation
-        return tessellate_simple_polygon(&outer_verts, reversed);
-    }
-
-    // Build a 2D projection for triangulation
-    // Compute the face plane from first 3 vertices
-    let e1 = outer_verts[1] - outer_verts[0];
-    let e2 = outer_verts[2] - outer_verts[0];
-    let face_normal = e1.cross(&e2);
-    if face_normal.norm() < 1e-12 {
-        return TriangleMesh::new();
-    }
-
-    let u_axis = e1.normalize();
-    let v_axis = face_normal.cross(&e1).normalize();
-    let origin = outer_verts[0];
-
-    // Project 3D points to 2D
-    let project = |p: &Point3| -> (f64, f64) {
-        let d = *p - origin;
-        (d.dot(&u_axis), d.dot(&v_axis))
+            .collect()
     };
 
-    // Unproject 2D points back to 3D
-    let unproject = |uv: (f64, f64)| -> Point3 { origin + uv.0 * u_axis + uv.1 * v_axis };
-
-    // Project outer loop
-    let outer_2d: Vec<(f64, f64)> = outer_verts.iter().map(&project).collect();
-
-    // Project inner loops
-    let inner_2d: Vec<Vec<(f64, f64)>> = inner_loops
+    let outer_verts = loop_points(face.outer_loop);
+    let inner_loops: Vec<Vec<Point3>> = face
+        .inner_loops
         .iter()
-        .map(|loop_verts| loop_verts.iter().map(&project).collect())
+        .map(|&inner_loop| loop_points(inner_loop))
         .collect();
-
-    // Check if we need the ring-based approach (large face with small hole)
-    let outer_area = polygon_area_2d(&outer_2d);
-    let total_hole_area: f64 = inner_2d.iter().map(|h| polygon_area_2d(h).abs()).sum();
-
-    // Use ring-based approach if holes are small relative to the face
-    if total_hole_area < outer_area.abs() * 0.3 {
-        return triangulate_with_rings(
-            &outer_2d,
-            &inner_2d,
-            &outer_verts,
-            &inner_loops,
-            unproject,
-            reversed,
-        );
-    }
-
-    // Use ear-clipping with hole bridging for larger holes
-    triangulate_polygon_with_holes(&outer_2d, &inner_2d, &outer_verts, &inner_loops, reversed)
-}
-
-/// Compute signed area of a 2D polygon.
-fn polygon_area_2d(pts: &[(f64, f64)]) -> f64 {
-    let mut area = 0.0;
-    let n = pts.len();
-    for i in 0..n {
-        let j = (i + 1) % n;
-        area += pts[i].0 * pts[j].1 - pts[j].0 * pts[i].1;
-    }
-    area / 2.0
-}
-
-/// Triangulate a face with holes using rings around each hole.
-/// This creates better quality triangles by adding intermediate Steiner points.
-fn triangulate_with_rings<F>(
-    outer_2d: &[(f64, f64)],
-    inner_2d: &[Vec<(f64, f64)>],
-    outer_3d: &[Point3],
-    inner_3d: &[Vec<Point3>],
-    unproject: F,
-    reversed: bool,
-) -> TriangleMesh
-where
-    F: Fn((f64, f64)) -> Point3,
-{
-    let mut mesh = TriangleMesh::new();
-
-    // For each hole, create a ring of points and triangulate hole-to-ring
-    let mut ring_loops_2d: Vec<Vec<(f64, f64)>> = Vec::new();
-    let mut ring_loops_3d: Vec<Vec<Point3>> = Vec::new();
-
-    for (hole_2d, hole_3d) in inner_2d.iter().zip(inner_3d.iter()) {
-        // Compute hole centroid
-        let centroid: (f64, f64) = hole_2d
-            .iter()
-            .fold((0.0, 0.0), |acc, p| (acc.0 + p.0, acc.1 + p.1));
-        let n = hole_2d.len() as f64;
-        let centroid = (centroid.0 / n, centroid.1 / n);
-
-        // Compute approximate hole radius
-        let hole_radius: f64 = hole_2d
-            .iter()
-            .map(|p| ((p.0 - centroid.0).powi(2) + (p.1 - centroid.1).powi(2)).sqrt())
-            .sum::<f64>()
-            / n;
-
-        // Compute the maximum safe ring radius (must stay inside outer polygon)
-        // Find the minimum distance from hole centroid to any outer edge
-        let max_ring_radius = {
-            let mut min_dist = f64::INFINITY;
-            let n_outer = outer_2d.len();
-            for i in 0..n_outer {
-                let j = (i + 1) % n_outer;
-                let a = outer_2d[i];
-                let b = outer_2d[j];
-                // Distance from centroid to edge a-b
-                let ab = (b.0 - a.0, b.1 - a.1);
-                let len2 = ab.0 * ab.0 + ab.1 * ab.1;
-                let dist = if len2 < 1e-12 {
-                    // Degenerate edge
-                    ((centroid.0 - a.0).powi(2) + (centroid.1 - a.1).powi(2)).sqrt()
-                } else {
-                    let ap = (centroid.0 - a.0, centroid.1 - a.1);
-                    let t = (ap.0 * ab.0 + ap.1 * ab.1) / len2;
-                    let t = t.clamp(0.0, 1.0);
-                    let proj = (a.0 + t * ab.0, a.1 + t * ab.1);
-                    ((centroid.0 - proj.0).powi(2) + (centroid.1 - proj.1).powi(2)).sqrt()
-                };
-                min_dist = min_dist.min(dist);
-            }
-            // Use 80% of the distance to the nearest edge as max ring radius
-            min_dist * 0.8
-        };
-
-        // Create ring at 2x the hole radius, but capped at the max safe radius
-        let desired_ring_radius = hole_radius * 2.5;
-        let ring_radius = desired_ring_radius
-            .min(max_ring_radius)
-            .max(hole_radius * 1.2);
-
-        // Create ring vertices aligned with hole vertices (same angle, larger radius)
-        // This ensures proper 1-to-1 correspondence for triangle creation
-        let ring_2d: Vec<(f64, f64)> = hole_2d
-            .iter()
-            .map(|h| {
-                // Compute angle from centroid to this hole vertex
-                let dx = h.0 - centroid.0;
-                let dy = h.1 - centroid.1;
-                let dist = (dx * dx + dy * dy).sqrt();
-                if dist < 1e-12 {
-                    // Degenerate case: hole vertex at centroid
-                    (centroid.0 + ring_radius, centroid.1)
-                } else {
-                    // Scale to ring radius while preserving angle
-                    let scale = ring_radius / dist;
-                    (centroid.0 + dx * scale, centroid.1 + dy * scale)
-                }
-            })
-            .collect();
-
-        let ring_3d: Vec<Point3> = ring_2d.iter().map(|&uv| unproject(uv)).collect();
-
-        // Triangulate from hole to ring
-        let hole_start = mesh.num_vertices();
-        for v in hole_3d {
-            mesh.vertices.push(v.x as f32);
-            mesh.vertices.push(v.y as f32);
-            mesh.vertices.push(v.z as f32);
-        }
-        let ring_start = mesh.num_vertices();
-        for v in &ring_3d {
-            mesh.vertices.push(v.x as f32);
-            mesh.vertices.push(v.y as f32);
-            mesh.vertices.push(v.z as f32);
-        }
-
-        // Create triangles between hole and ring (quad strips)
-        let count = hole_2d.len();
-        for i in 0..count {
-            let h0 = (hole_start + i) as u32;
-            let h1 = (hole_start + (i + 1) % count) as u32;
-            let r0 = (ring_start + i) as u32;
-            let r1 = (ring_start + (i + 1) % count) as u32;
-
-            // Two triangles per quad
-            if reversed {
-                mesh.indices.extend_from_slice(&[h0, r0, h1]);
-                mesh.indices.extend_from_slice(&[h1, r0, r1]);
-            } else {
-                mesh.indices.extend_from_slice(&[h0, h1, r0]);
-                mesh.indices.extend_from_slice(&[h1, r1, r0]);
-            }
-        }
-
-        ring_loops_2d.push(ring_2d);
-        ring_loops_3d.push(ring_3d);
-    }
-
-    // Now triangulate from rings to outer boundary
-    // Treat rings as new inner loops and use ear-clipping
-    let ring_outer_mesh = triangulate_polygon_with_holes(
-        outer_2d,
-        &ring_loops_2d,
-        outer_3d,
-        &ring_loops_3d,
-        reversed,
-    );
-
-    // Merge ring-to-outer mesh into our mesh (with vertex offset)
-    let offset = mesh.num_vertices() as u32;
-    mesh.vertices.extend_from_slice(&ring_outer_mesh.vertices);
-    mesh.indices
-        .extend(ring_outer_mesh.indices.iter().map(|&i| i + offset));
-
-    mesh
-}
-
-/// Add Steiner points to the outer polygon to improve triangulation quality.
-///
-/// This function:
-/// 1. Subdivides long outer edges into smaller segments (max ~20 units)
-/// 2. Adds additional points near each hole centroid
-///
-/// This prevents very long bridges that cause thin, degenerate triangles.
-fn refine_outer_polygon_for_holes(
-    outer_2d: &[(f64, f64)],
-    outer_3d: &[Point3],
-    inner_2d: &[Vec<(f64, f64)>],
-) -> (Vec<(f64, f64)>, Vec<Point3>) {
-    if outer_2d.len() < 3 {
-        return (outer_2d.to_vec(), outer_3d.to_vec());
-    }
-
-    // Maximum edge length before subdivision.
-    // Using a small value to ensure good quality triangles near holes.
-    const MAX_EDGE_LENGTH: f64 = 8.0;
-
-    // First pass: subdivide long edges
-    let mut result_2d: Vec<(f64, f64)> = Vec::new();
-    let mut result_3d: Vec<Point3> = Vec::new();
-
-    for i in 0..outer_2d.len() {
-        let j = (i + 1) % outer_2d.len();
-        let a_2d = outer_2d[i];
-        let b_2d = outer_2d[j];
-        let a_3d = outer_3d[i];
-        let b_3d = outer_3d[j];
-
-        // Add the start vertex
-        result_2d.push(a_2d);
-        result_3d.push(a_3d);
-
-        // Calculate edge length
-        let edge_len = ((b_2d.0 - a_2d.0).powi(2) + (b_2d.1 - a_2d.1).powi(2)).sqrt();
-
-        // If edge is long, subdivide it
-        if edge_len > MAX_EDGE_LENGTH {
-            let num_segments = (edge_len / MAX_EDGE_LENGTH).ceil() as usize;
-            for k in 1..num_segments {
-                let t = k as f64 / num_segments as f64;
-                let new_2d = (
-                    a_2d.0 + t * (b_2d.0 - a_2d.0),
-                    a_2d.1 + t * (b_2d.1 - a_2d.1),
-                );
-                let new_3d = Point3::new(
-                    a_3d.x + t * (b_3d.x - a_3d.x),
-                    a_3d.y + t * (b_3d.y - a_3d.y),
-                    a_3d.z + t * (b_3d.z - a_3d.z),
-                );
-                result_2d.push(new_2d);
-                result_3d.push(new_3d);
-            }
-        }
-    }
-
-    // If no holes, we're done with just edge subdivision
-    if inner_2d.is_empty() {
-        return (result_2d, result_3d);
-    }
-
-    // Second pass: add points near each hole centroid
-    // Collect insertion points: (edge_index, t_param, 2d_point, 3d_point)
-    let mut insertions: Vec<(usize, f64, (f64, f64), Point3)> = Vec::new();
-
-    for hole in inner_2d {
-        if hole.is_empty() {
-            continue;
-        }
-
-        // Find centroid of hole
-        let centroid: (f64, f64) = hole
-            .iter()
-            .fold((0.0, 0.0), |acc, p| (acc.0 + p.0, acc.1 + p.1));
-        let n = hole.len() as f64;
-        let centroid = (centroid.0 / n, centroid.1 / n);
-
-        // Find closest point on outer polygon edges to the hole centroid
-        let mut best_edge = 0;
-        let mut best_t = 0.5;
-        let mut best_dist = f64::INFINITY;
-
-        for i in 0..result_2d.len() {
-            let j = (i + 1) % result_2d.len();
-            let a = result_2d[i];
-            let b = result_2d[j];
-
-            // Project centroid onto edge a-b
-            let ab = (b.0 - a.0, b.1 - a.1);
-            let len2 = ab.0 * ab.0 + ab.1 * ab.1;
-            if len2 < 1e-12 {
-                continue;
-            }
-
-            let ap = (centroid.0 - a.0, centroid.1 - a.1);
-            let t = (ap.0 * ab.0 + ap.1 * ab.1) / len2;
-
-            // Only consider points on the edge interior (not at endpoints)
-            if t <= 0.1 || t >= 0.9 {
-                continue;
-            }
-
-            let proj = (a.0 + t * ab.0, a.1 + t * ab.1);
-            let dist = ((centroid.0 - proj.0).powi(2) + (centroid.1 - proj.1).powi(2)).sqrt();
-
-            if dist < best_dist {
-                best_dist = dist;
-                best_edge = i;
-                best_t = t;
-            }
-        }
-
-        // Check if the best point is significantly closer than existing vertices
-        let mut min_vertex_dist = f64::INFINITY;
-        for &v in &result_2d {
-            let d = ((centroid.0 - v.0).powi(2) + (centroid.1 - v.1).powi(2)).sqrt();
-            min_vertex_dist = min_vertex_dist.min(d);
-        }
-
-        // Only add if the edge point is at least 30% closer than any existing vertex
-        if best_dist < min_vertex_dist * 0.7 && best_dist < f64::INFINITY {
-            let j = (best_edge + 1) % result_2d.len();
-            let a_2d = result_2d[best_edge];
-            let b_2d = result_2d[j];
-            let new_2d = (
-                a_2d.0 + best_t * (b_2d.0 - a_2d.0),
-                a_2d.1 + best_t * (b_2d.1 - a_2d.1),
-            );
-
-            let a_3d = result_3d[best_edge];
-            let b_3d = result_3d[j];
-            let new_3d = Point3::new(
-                a_3d.x + best_t * (b_3d.x - a_3d.x),
-                a_3d.y + best_t * (b_3d.y - a_3d.y),
-                a_3d.z + best_t * (b_3d.z - a_3d.z),
-            );
-
-            insertions.push((best_edge, best_t, new_2d, new_3d));
-        }
-    }
-
-    if insertions.is_empty() {
-        return (result_2d, result_3d);
-    }
-
-    // Sort insertions by edge index (descending) then by t (descending within same edge)
-    insertions.sort_by(|a, b| {
-        if a.0 != b.0 {
-            b.0.cmp(&a.0)
-        } else {
-            b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-        }
-    });
-
-    for (edge_idx, _, pt_2d, pt_3d) in insertions {
-        result_2d.insert(edge_idx + 1, pt_2d);
-        result_3d.insert(edge_idx + 1, pt_3d);
-    }
-
-    (result_2d, result_3d)
-}
-
-/// Triangulate a polygon with holes using ear-clipping with bridge construction.
-fn triangulate_polygon_with_holes(
-    outer_2d: &[(f64, f64)],
-    inner_2d: &[Vec<(f64, f64)>],
-    outer_3d: &[Point3],
-    inner_3d: &[Vec<Point3>],
-    reversed: bool,
-) -> TriangleMesh {
-    let mut mesh = TriangleMesh::new();
-
-    // First, refine the outer polygon by adding Steiner points near each hole.
-    // This prevents very long bridges that cause thin triangles.
-    let (refined_outer_2d, refined_outer_3d) =
-        refine_outer_polygon_for_holes(outer_2d, outer_3d, inner_2d);
-
-    // Collect all vertices
-    let mut all_verts_3d: Vec<Point3> = refined_outer_3d.clone();
-    let mut all_verts_2d: Vec<(f64, f64)> = refined_outer_2d.clone();
-
-    // Track where each inner loop starts
-    let mut inner_starts: Vec<usize> = Vec::new();
-    for (inner_loop_3d, inner_loop_2d) in inner_3d.iter().zip(inner_2d.iter()) {
-        inner_starts.push(all_verts_3d.len());
-        all_verts_3d.extend_from_slice(inner_loop_3d);
-        all_verts_2d.extend_from_slice(inner_loop_2d);
-    }
-
-    // Add all vertices to mesh
-    for v in &all_verts_3d {
-        mesh.vertices.push(v.x as f32);
-        mesh.vertices.push(v.y as f32);
-        mesh.vertices.push(v.z as f32);
-    }
-
-    // Build a merged polygon by bridging outer to each inner loop
-    let mut poly_indices: Vec<usize> = (0..refined_outer_2d.len()).collect();
-
-    // Track which vertices have been used as bridge endpoints
-    let mut used_bridge_vertices: std::collections::HashSet<usize> =
-        std::collections::HashSet::new();
-
-    for (hole_idx, inner_start) in inner_starts.iter().enumerate() {
-        let inner_len = inner_2d[hole_idx].len();
-
-        // Find the pair of (outer vertex, inner vertex) with minimum distance
-        // Avoid vertices already used as bridge endpoints
-        let mut candidates: Vec<(f64, usize, usize)> = Vec::new(); // (dist, inner_idx, outer_poly_idx)
-
-        for i in 0..inner_len {
-            let inner_pt = all_verts_2d[inner_start + i];
-            for (j, &outer_idx) in poly_indices.iter().enumerate() {
-                let outer_pt = all_verts_2d[outer_idx];
-                let dist = (outer_pt.0 - inner_pt.0).powi(2) + (outer_pt.1 - inner_pt.1).powi(2);
-                candidates.push((dist, i, j));
-            }
-        }
-
-        // Sort by distance
-        candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
-
-        // Find the best candidate that doesn't reuse a bridge vertex
-        let mut best_inner = 0;
-        let mut best_outer_idx = 0;
-
-        for (_, inner_idx, outer_poly_idx) in &candidates {
-            let outer_vertex_idx = poly_indices[*outer_poly_idx];
-            // For the first hole, allow any vertex. For subsequent holes,
-            // prefer vertices that haven't been used, but fall back if needed.
-            if !used_bridge_vertices.contains(&outer_vertex_idx) || hole_idx == 0 {
-                best_inner = *inner_idx;
-                best_outer_idx = *outer_poly_idx;
-                used_bridge_vertices.insert(outer_vertex_idx);
-                break;
-            }
-        }
-
-        // If all vertices are used (shouldn't happen with reasonable input), use closest anyway
-        if candidates.is_empty() {
-            continue;
-        }
-
-        let rightmost_inner = best_inner;
-
-        // Insert bridge: outer -> hole -> back to outer
-        let inner_global_start = *inner_start;
-        let hole_indices: Vec<usize> = (0..inner_len)
-            .map(|i| inner_global_start + ((rightmost_inner + i) % inner_len))
-            .collect();
-
-        // Insert after best_outer_idx:
-        // poly[0..=best_outer_idx] + hole_indices + [hole_indices[0], poly[best_outer_idx]] + poly[best_outer_idx+1..]
-        // Simplified: insert hole loop with bridge vertices
-        let bridge_outer = poly_indices[best_outer_idx];
-        let bridge_inner = hole_indices[0];
-
-        let mut new_poly = Vec::new();
-        new_poly.extend_from_slice(&poly_indices[..=best_outer_idx]);
-        new_poly.extend_from_slice(&hole_indices);
-        new_poly.push(bridge_inner);
-        new_poly.push(bridge_outer);
-        new_poly.extend_from_slice(&poly_indices[best_outer_idx + 1..]);
-
-        poly_indices = new_poly;
-    }
-
-    // Now triangulate the merged polygon using ear clipping
-    ear_clip_triangulate(&all_verts_2d, &poly_indices, &mut mesh.indices, reversed);
-
-    mesh
-}
-
-/// Simple ear-clipping triangulation for a polygon (defined by indices into a vertex array).
-fn ear_clip_triangulate(
-    verts_2d: &[(f64, f64)],
-    indices: &[usize],
-    out_indices: &mut Vec<u32>,
-    reversed: bool,
-) {
-    if indices.len() < 3 {
-        return;
-    }
-
-    let mut remaining: Vec<usize> = indices.to_vec();
-
-    while remaining.len() > 3 {
-        let n = remaining.len();
-        let mut found_ear = false;
-
-        for i in 0..n {
-            let prev = (i + n - 1) % n;
-            let next = (i + 1) % n;
-
-            let a = verts_2d[remaining[prev]];
-            let b = verts_2d[remaining[i]];
-            let c = verts_2d[remaining[next]];
-
-            // Check if this is a convex vertex (ear candidate)
-            let cross = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
-            let is_convex = if reversed { cross < 0.0 } else { cross > 0.0 };
-
-            if !is_convex {
-                continue;
-            }
-
-            // Check if any other vertex is inside this triangle
-            let mut is_ear = true;
-            for j in 0..n {
-                if j == prev || j == i || j == next {
-                    continue;
-                }
-                let p = verts_2d[remaining[j]];
-                if point_in_triangle_2d(p, a, b, c) {
-                    is_ear = false;
-                    break;
-                }
-            }
-
-            if is_ear {
-                // Add triangle
-                if reversed {
-                    out_indices.push(remaining[prev] as u32);
-                    out_indices.push(remaining[next] as u32);
-                    out_indices.push(remaining[i] as u32);
-                } else {
-                    out_indices.push(remaining[prev] as u32);
-                    out_indices.push(remaining[i] as u32);
-                    out_indices.push(remaining[next] as u32);
-                }
-                remaining.remove(i);
-                found_ear = true;
-                break;
-            }
-        }
-
-        if !found_ear {
-            // Degenerate case - just triangulate remaining as fan
-            break;
-        }
-    }
-
-    // Final triangle
-    if remaining.len() == 3 {
-        if reversed {
-            out_indices.push(remaining[0] as u32);
-            out_indices.push(remaining[2] as u32);
-            out_indices.push(remaining[1] as u32);
-        } else {
-            out_indices.push(remaining[0] as u32);
-            out_indices.push(remaining[1] as u32);
-            out_indices.push(remaining[2] as u32);
-        }
-    }
-}
-
-/// Check if a point is inside a triangle in 2D using barycentric coordinates.
-fn point_in_triangle_2d(p: (f64, f64), a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> bool {
-    let v0 = (c.0 - a.0, c.1 - a.1);
-    let v1 = (b.0 - a.0, b.1 - a.1);
-    let v2 = (p.0 - a.0, p.1 - a.1);
-
-    let dot00 = v0.0 * v0.0 + v0.1 * v0.1;
-    let dot01 = v0.0 * v1.0 + v0.1 * v1.1;
-    let dot02 = v0.0 * v2.0 + v0.1 * v2.1;
-    let dot11 = v1.0 * v1.0 + v1.1 * v1.1;
-    let dot12 = v1.0 * v2.0 + v1.1 * v2.1;
-
-    let inv_denom = 1.0 / (dot00 * dot11 - dot01 * dot01);
-    let u = (dot11 * dot02 - dot01 * dot12) * inv_denom;
-    let v = (dot00 * dot12 - dot01 * dot02) * inv_denom;
-
-    // Use small epsilon to avoid boundary issues
-    let eps = 1e-10;
-    u > eps && v > eps && (u + v) < 1.0 - eps
-}
-
-/// Simple fan triangulation for a convex polygon.
-fn tessellate_simple_polygon(verts: &[Point3], reversed: bool) -> TriangleMesh {
-    let mut mesh = TriangleMesh::new();
-
-    for v in verts {
-        mesh.vertices.push(v.x as f32);
-        mesh.vertices.push(v.y as f32);
-        mesh.vertices.push(v.z as f32);
-    }
-
-    for i in 1..(verts.len() - 1) {
-        if reversed {
-            mesh.indices.push(0);
-            mesh.indices.push((i + 1) as u32);
-            mesh.indices.push(i as u32);
-        } else {
-            mesh.indices.push(0);
-            mesh.indices.push(i as u32);
-            mesh.indices.push((i + 1) as u32);
-        }
-    }
-
-    mesh
+    tessellate_planar_loops(&outer_verts, &inner_loops, reversed)
 }
 
 /// Tessellate a cylindrical face (lateral surface of a cylinder).
@@ -2192,7 +1641,7 @@ pub fn tessellate(brep: &BRepSolid, segments: u32) -> TriangleMesh {
 
     let mut mesh = TriangleMesh::new();
 
-    for &face_id in &shell.faces {
+    for face_id in ordered_faces(&brep.topology, &shell.faces) {
         let face = &brep.topology.faces[face_id];
         let surface = &brep.geometry.surfaces[face.surface_index];
         let reversed = face.orientation == Orientation::Reversed;
@@ -2253,7 +1702,21 @@ pub fn tessellate(brep: &BRepSolid, segments: u32) -> TriangleMesh {
 /// have degenerate (single-vertex) loops.
 ///
 /// This is the primary tessellation function used by the facade crate.
+/// Output is deterministic: faces are emitted in a canonical order rather
+/// than shell order, so identical solids give identical buffers.
 pub fn tessellate_brep(brep: &BRepSolid, segments: u32) -> TriangleMesh {
+    let mut mesh = TriangleMesh::new();
+    for (_, face_mesh) in tessellate_brep_faces(brep, segments) {
+        mesh.merge(&face_mesh);
+    }
+    mesh
+}
+
+/// Tessellate each face of a B-rep solid into its own mesh.
+///
+/// Faces come in the canonical order [`tessellate_brep`] uses, and merging
+/// their meshes in order reproduces its output.
+pub fn tessellate_brep_faces(brep: &BRepSolid, segments: u32) -> Vec<(FaceId, TriangleMesh)> {
     let params = TessellationParams::from_segments(segments);
     let solid = &brep.topology.solids[brep.solid_id];
     let shell = &brep.topology.shells[solid.outer_shell];
@@ -2261,9 +1724,9 @@ pub fn tessellate_brep(brep: &BRepSolid, segments: u32) -> TriangleMesh {
     // DEBUG: print which shell we're tessellating
     eprintln!("TESSELLATE_BREP: shell has {} faces: {:?}", shell.faces.len(), shell.faces);
 
-    let mut mesh = TriangleMesh::new();
+    let mut faces = Vec::new();
 
-    for &face_id in &shell.faces {
+    for face_id in ordered_faces(&brep.topology, &shell.faces) {
         let face = &brep.topology.faces[face_id];
         let surface = &brep.geometry.surfaces[face.surface_index];
         let reversed = face.orientation == Orientation::Reversed;
@@ -2299,12 +1762,12 @@ pub fn tessellate_brep(brep: &BRepSolid, segments: u32) -> TriangleMesh {
                             params.circle_segments,
                             reversed,
                         );
-                        mesh.merge(&disk);
+                        faces.push((face_id, disk));
                     }
                 } else {
                     // Use winding-aware tessellation to handle faces with mismatched loop winding
                     let face_mesh = tessellate_planar_face_with_geom(&brep.topology, &brep.geometry, face_id, reversed);
-                    mesh.merge(&face_mesh);
+                    faces.push((face_id, face_mesh));
                 }
             }
             SurfaceKind::Cylinder => {
@@ -2315,7 +1778,7 @@ pub fn tessellate_brep(brep: &BRepSolid, segments: u32) -> TriangleMesh {
                     &params,
                     reversed,
                 );
-                mesh.merge(&face_mesh);
+                faces.push((face_id, face_mesh));
             }
             SurfaceKind::Sphere => {
                 let face_mesh = tessellate_spherical_face(
@@ -2325,7 +1788,7 @@ pub fn tessellate_brep(brep: &BRepSolid, segments: u32) -> TriangleMesh {
                     &params,
                     reversed,
                 );
-                mesh.merge(&face_mesh);
+                faces.push((face_id, face_mesh));
             }
             SurfaceKind::Cone => {
                 let face_mesh = tessellate_conical_face(
@@ -2335,17 +1798,17 @@ pub fn tessellate_brep(brep: &BRepSolid, segments: u32) -> TriangleMesh {
                     &params,
                     reversed,
                 );
-                mesh.merge(&face_mesh);
+                faces.push((face_id, face_mesh));
             }
             _ => {
                 // Fallback for tessellate_brep(): use winding-aware tessellation
                 let face_mesh = tessellate_planar_face_with_geom(&brep.topology, &brep.geometry, face_id, reversed);
-                mesh.merge(&face_mesh);
+                faces.push((face_id, face_mesh));
             }
         }
     }
 
-    mesh
+    faces
 }
 
 #[cfg(test)]
@@ -2400,6 +1863,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tessellation_is_deterministic() {
+        let brep = make_cylinder(5.0, 10.0, 32);
+        let first = tessellate_brep(&brep, 32);
+        let second = tessellate_brep(&brep, 32);
+        assert_eq!(first.vertices, second.vertices);
+        assert_eq!(first.indices, second.indices);
+
+        // Same geometry with the shell's faces listed in another order
+        let mut shuffled = brep.clone();
+        let shell = shuffled.topology.solids[shuffled.solid_id].outer_shell;
+        shuffled.topology.shells[shell].faces.reverse();
+        let reordered = tessellate_brep(&shuffled, 32);
+        assert_eq!(first.vertices, reordered.vertices);
+        assert_eq!(first.indices, reordered.indices);
+
+        let cube = make_cube(10.0, 20.0, 30.0);
+        let mut shuffled = cube.clone();
+        let shell = shuffled.topology.solids[shuffled.solid_id].outer_shell;
+        shuffled.topology.shells[shell].faces.rotate_left(2);
+        let params = TessellationParams::default();
+        let a = tessellate_solid(&cube, &params);
+        let b = tessellate_solid(&shuffled, &params);
+        assert_eq!(a.vertices, b.vertices);
+        assert_eq!(a.indices, b.indices);
+
+        // Same geometry with its surfaces numbered differently
+        let mut renumbered = cube.clone();
+        renumbered.geometry.surfaces.swap(0, 1);
+        for face in renumbered.topology.faces.values_mut() {
+            face.surface_index = match face.surface_index {
+                0 => 1,
+                1 => 0,
+                other => other,
+            };
+        }
+        let c = tessellate_solid(&renumbered, &params);
+        assert_eq!(a.vertices, c.vertices);
+        assert_eq!(a.indices, c.indices);
+    }
+
     #[test]
     fn test_cube_volume_from_mesh() {
         let brep = make_cube(10.0, 10.0, 10.0);
@@ -2495,7 +1999,6 @@ mod tests {
         use vcad_kernel_math::Point3;
 
         // Square: 10x10 in XY plane at Z=0 (CCW winding)
-        let outer_2d: Vec<(f64, f64)> = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
         let outer_3d: Vec<Point3> = vec![
             Point3::new(0.0, 0.0, 0.0),
             Point3::new(10.0, 0.0, 0.0),
@@ -2506,23 +2009,15 @@ mod tests {
         // Circular hole: radius 2, center at (5, 5), 8 segments
         // CW winding (opposite to outer) - this is how B-rep stores inner loops
         let n_seg = 8usize;
-        let hole_2d: Vec<(f64, f64)> = (0..n_seg)
+        let hole_3d: Vec<Point3> = (0..n_seg)
             .rev() // CW winding: reverse the order
             .map(|i| {
                 let theta = 2.0 * std::f64::consts::PI * (i as f64) / (n_seg as f64);
-                (5.0 + 2.0 * theta.cos(), 5.0 + 2.0 * theta.sin())
+                Point3::new(5.0 + 2.0 * theta.cos(), 5.0 + 2.0 * theta.sin(), 0.0)
             })
             .collect();
-        let hole_3d: Vec<Point3> = hole_2d
-            .iter()
-            .map(|&(x, y)| Point3::new(x, y, 0.0))
-            .collect();
-
-        let inner_2d = vec![hole_2d];
-        let inner_3d = vec![hole_3d];
 
-        let mesh =
-            triangulate_polygon_with_holes(&outer_2d, &inner_2d, &outer_3d, &inner_3d, false);
+        let mesh = tessellate_planar_loops(&outer_3d, &[hole_3d], false);
 
         println!(
             "Square with hole: {} triangles, {} vertices",
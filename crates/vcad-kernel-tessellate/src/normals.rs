@@ -0,0 +1,122 @@
+//! Per-vertex normals for shading.
+//!
+//! Tessellated meshes share vertices between adjacent triangles, so a single
+//! normal per vertex can either be averaged over its triangles (smooth
+//! shading) or, after splitting the vertices apart, be the normal of the one
+//! triangle using it (flat shading).
+
+use vcad_kernel_math::Vec3;
+
+use crate::TriangleMesh;
+
+impl TriangleMesh {
+    /// Unit normal at each vertex: the average of the normals of the
+    /// triangles using it, weighted by triangle area.
+    ///
+    /// Vertices not used by any triangle (or only by degenerate ones) get
+    /// `+Z`.
+    pub fn vertex_normals(&self) -> Vec<f64> {
+        let num_verts = self.num_vertices();
+        let mut normals = vec![0.0_f64; num_verts * 3];
+
+        for tri in self.indices.chunks_exact(3) {
+            let face_normal = self.face_normal(tri);
+            for &idx in tri {
+                let idx = idx as usize;
+                normals[idx * 3] += face_normal.x;
+                normals[idx * 3 + 1] += face_normal.y;
+                normals[idx * 3 + 2] += face_normal.z;
+            }
+        }
+
+        for n in normals.chunks_exact_mut(3) {
+            let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            if len > 1e-12 {
+                n.iter_mut().for_each(|c| *c /= len);
+            } else {
+                n.copy_from_slice(&[0.0, 0.0, 1.0]);
+            }
+        }
+        normals
+    }
+
+    /// A copy of the mesh with [`TriangleMesh::normals`] filled in.
+    ///
+    /// With `smooth`, vertices are kept shared and get averaged normals (see
+    /// [`TriangleMesh::vertex_normals`]). Otherwise every triangle gets its
+    /// own three vertices carrying the triangle's normal, for faceted
+    /// shading.
+    pub fn with_normals(&self, smooth: bool) -> TriangleMesh {
+        if smooth {
+            return TriangleMesh {
+                vertices: self.vertices.clone(),
+                indices: self.indices.clone(),
+                normals: self.vertex_normals().iter().map(|&c| c as f32).collect(),
+            };
+        }
+
+        let mut mesh = TriangleMesh::new();
+        for tri in self.indices.chunks_exact(3) {
+            let n = self.face_normal(tri);
+            let n = if n.norm() > 1e-12 {
+                n.normalize()
+            } else {
+                Vec3::z()
+            };
+            for &idx in tri {
+                let i = idx as usize * 3;
+                mesh.indices.push(mesh.num_vertices() as u32);
+                mesh.vertices.extend_from_slice(&self.vertices[i..i + 3]);
+                mesh.normals
+                    .extend_from_slice(&[n.x as f32, n.y as f32, n.z as f32]);
+            }
+        }
+        mesh
+    }
+
+    /// Unnormalized normal of a triangle, twice its area in length.
+    fn face_normal(&self, tri: &[u32]) -> Vec3 {
+        let v = |idx: u32| {
+            let i = idx as usize * 3;
+            Vec3::new(
+                self.vertices[i] as f64,
+                self.vertices[i + 1] as f64,
+                self.vertices[i + 2] as f64,
+            )
+        };
+        let (v0, v1, v2) = (v(tri[0]), v(tri[1]), v(tri[2]));
+        (v1 - v0).cross(&(v2 - v0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tessellate_brep;
+    use vcad_kernel_primitives::{make_cube, make_sphere};
+
+    #[test]
+    fn test_smooth_normals() {
+        let mesh = tessellate_brep(&make_sphere(5.0, 16), 16).with_normals(true);
+        assert_eq!(mesh.normals.len(), mesh.vertices.len());
+        // On a sphere around the origin, normals point along the position
+        for (p, n) in mesh.vertices.chunks(3).zip(mesh.normals.chunks(3)) {
+            let r = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+            let dot = (p[0] * n[0] + p[1] * n[1] + p[2] * n[2]) / r;
+            assert!(dot > 0.95, "normal deviates: {dot}");
+        }
+    }
+
+    #[test]
+    fn test_flat_normals() {
+        let cube = tessellate_brep(&make_cube(2.0, 2.0, 2.0), 8);
+        let mesh = cube.with_normals(false);
+        assert_eq!(mesh.num_triangles(), cube.num_triangles());
+        assert_eq!(mesh.num_vertices(), 3 * mesh.num_triangles());
+        assert_eq!(mesh.normals.len(), mesh.vertices.len());
+        // Each normal is an axis direction
+        for n in mesh.normals.chunks(3) {
+            let max = n.iter().fold(0.0_f32, |m, c| m.max(c.abs()));
+            assert!((max - 1.0).abs() < 1e-6);
+        }
+    }
+}
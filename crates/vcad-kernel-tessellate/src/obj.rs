@@ -0,0 +1,212 @@
+//! Wavefront OBJ export and import.
+//!
+//! Only geometry is handled: `v`, `vn`, and `f` records, with `o`/`g` names
+//! to tell parts apart on export. Texture coordinates, materials, and
+//! smoothing groups are skipped on import, and faces with more than three
+//! corners are fan-triangulated.
+
+use std::fmt::Write;
+
+use crate::TriangleMesh;
+
+/// Error returned when OBJ text cannot be read.
+#[derive(Debug)]
+pub struct ObjError {
+    /// 1-based line number of the offending record.
+    pub line: usize,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+impl std::fmt::Display for ObjError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "malformed OBJ on line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ObjError {}
+
+impl TriangleMesh {
+    /// Write the mesh as OBJ text.
+    ///
+    /// With `include_normals`, every vertex gets a `vn` record and faces
+    /// reference it as `f a//a b//b c//c`. The normals are
+    /// [`TriangleMesh::normals`] if filled in, and
+    /// [`TriangleMesh::vertex_normals`] otherwise.
+    pub fn to_obj(&self, include_normals: bool) -> String {
+        let mut out = String::new();
+        write_mesh(&mut out, self, include_normals, 0);
+        out
+    }
+
+    /// Parse OBJ text into a single mesh.
+    ///
+    /// All objects and groups in the file are merged. Vertex normals are
+    /// taken from the `vn` records the faces reference when every vertex
+    /// has one, and computed from the triangles otherwise.
+    pub fn from_obj_str(text: &str) -> Result<TriangleMesh, ObjError> {
+        let mut mesh = TriangleMesh::new();
+        let mut file_normals: Vec<[f32; 3]> = Vec::new();
+        let mut normal_of: Vec<Option<[f32; 3]>> = Vec::new();
+
+        for (line_no, line) in text.lines().enumerate() {
+            let error = |message: String| ObjError {
+                line: line_no + 1,
+                message,
+            };
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("v") => {
+                    let coord = parse_xyz(&mut words).ok_or_else(|| error("bad vertex".into()))?;
+                    mesh.vertices.extend_from_slice(&coord);
+                    normal_of.push(None);
+                }
+                Some("vn") => {
+                    let normal = parse_xyz(&mut words).ok_or_else(|| error("bad normal".into()))?;
+                    file_normals.push(normal);
+                }
+                Some("f") => {
+                    let mut corners = Vec::new();
+                    for word in words {
+                        let mut refs = word.split('/');
+                        let vertex = resolve(refs.next(), mesh.num_vertices())
+                            .ok_or_else(|| error(format!("bad vertex reference `{word}`")))?;
+                        if let Some(normal) = refs.nth(1).filter(|r| !r.is_empty()) {
+                            let normal = resolve(Some(normal), file_normals.len())
+                                .ok_or_else(|| error(format!("bad normal reference `{word}`")))?;
+                            normal_of[vertex] = Some(file_normals[normal]);
+                        }
+                        corners.push(vertex as u32);
+                    }
+                    if corners.len() < 3 {
+                        return Err(error(format!("face has {} corners", corners.len())));
+                    }
+                    for k in 1..corners.len() - 1 {
+                        mesh.indices
+                            .extend_from_slice(&[corners[0], corners[k], corners[k + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        mesh.normals = match normal_of.iter().copied().collect::<Option<Vec<_>>>() {
+            Some(normals) => normals.concat(),
+            None => mesh.vertex_normals().iter().map(|&c| c as f32).collect(),
+        };
+        Ok(mesh)
+    }
+}
+
+/// Write several meshes into one OBJ file, each as an object and group of
+/// its own (`o name` and `g name`).
+///
+/// Whitespace in names is replaced with `_`, since OBJ splits group names
+/// on it. See [`TriangleMesh::to_obj`] for `include_normals`.
+pub fn objects_to_obj(objects: &[(&str, &TriangleMesh)], include_normals: bool) -> String {
+    let mut out = String::new();
+    let mut written = 0;
+    for (name, mesh) in objects {
+        let name: String = name
+            .chars()
+            .map(|c| if c.is_whitespace() { '_' } else { c })
+            .collect();
+        let _ = writeln!(out, "o {name}\ng {name}");
+        write_mesh(&mut out, mesh, include_normals, written);
+        written += mesh.num_vertices();
+    }
+    out
+}
+
+/// Append a mesh's records, numbering its vertices after the `written`
+/// ones already in the file.
+fn write_mesh(out: &mut String, mesh: &TriangleMesh, include_normals: bool, written: usize) {
+    for v in mesh.vertices.chunks_exact(3) {
+        let _ = writeln!(out, "v {} {} {}", v[0], v[1], v[2]);
+    }
+    if include_normals {
+        let normals = if mesh.normals.len() == mesh.vertices.len() {
+            mesh.normals.clone()
+        } else {
+            mesh.vertex_normals().iter().map(|&c| c as f32).collect()
+        };
+        for n in normals.chunks_exact(3) {
+            let _ = writeln!(out, "vn {} {} {}", n[0], n[1], n[2]);
+        }
+    }
+    for tri in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|k| tri[k] as usize + written + 1);
+        let _ = if include_normals {
+            writeln!(out, "f {a}//{a} {b}//{b} {c}//{c}")
+        } else {
+            writeln!(out, "f {a} {b} {c}")
+        };
+    }
+}
+
+/// Parse the first three words as coordinates; extra words (a `w`
+/// component or vertex colors) are ignored.
+fn parse_xyz<'a>(words: &mut impl Iterator<Item = &'a str>) -> Option<[f32; 3]> {
+    let mut coord = [0.0f32; 3];
+    for c in &mut coord {
+        *c = words.next()?.parse().ok()?;
+    }
+    Some(coord)
+}
+
+/// Turn a 1-based (or negative, counting back from the last) OBJ index
+/// into a 0-based one, if it refers to one of the `count` records so far.
+fn resolve(index: Option<&str>, count: usize) -> Option<usize> {
+    let index: i64 = index?.parse().ok()?;
+    let resolved = if index < 0 {
+        count as i64 + index
+    } else {
+        index - 1
+    };
+    (0..count as i64)
+        .contains(&resolved)
+        .then_some(resolved as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tessellate_brep;
+    use vcad_kernel_primitives::make_cube;
+
+    #[test]
+    fn test_obj_roundtrip() {
+        let cube = tessellate_brep(&make_cube(10.0, 20.0, 30.0), 8).with_normals(false);
+        for include_normals in [false, true] {
+            let text = cube.to_obj(include_normals);
+            let mesh = TriangleMesh::from_obj_str(&text).unwrap();
+            assert_eq!(mesh.num_vertices(), cube.num_vertices());
+            assert_eq!(mesh.num_triangles(), cube.num_triangles());
+            assert_eq!(mesh.vertices, cube.vertices);
+            assert_eq!(mesh.indices, cube.indices);
+            if include_normals {
+                assert_eq!(mesh.normals, cube.normals);
+            }
+        }
+    }
+
+    #[test]
+    fn test_obj_groups_and_polygons() {
+        let quad = TriangleMesh::from_obj_str(
+            "# a unit square\nv 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvt 0 0\nf 1/1 2/1 3/1 -1/1\n",
+        )
+        .unwrap();
+        assert_eq!(quad.indices, vec![0, 1, 2, 0, 2, 3]);
+        assert_eq!(&quad.normals[..3], &[0.0, 0.0, 1.0]);
+
+        let text = objects_to_obj(&[("left part", &quad), ("right", &quad)], false);
+        assert!(text.contains("o left_part\ng left_part\n"));
+        assert!(text.contains("g right\n"));
+        let merged = TriangleMesh::from_obj_str(&text).unwrap();
+        assert_eq!(merged.num_vertices(), 8);
+        assert_eq!(&merged.indices[6..9], &[4, 5, 6]);
+
+        let err = TriangleMesh::from_obj_str("v 0 0 0\nf 1 2 3\n").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+}
@@ -0,0 +1,260 @@
+//! STL import.
+//!
+//! Reads both binary and ASCII STL into a [`TriangleMesh`]. STL stores every
+//! triangle with its own three corners, so coincident corners are welded into
+//! shared vertices and vertex normals are rebuilt from the triangle geometry
+//! (the per-facet normals in the file are ignored).
+
+use std::collections::HashMap;
+
+use crate::TriangleMesh;
+
+/// Size of the binary STL header plus the triangle count.
+const BINARY_HEADER_LEN: usize = 84;
+
+/// Size of one binary STL triangle record: normal, 3 corners, attribute bytes.
+const BINARY_TRIANGLE_LEN: usize = 50;
+
+/// Error returned when an STL file cannot be read.
+#[derive(Debug)]
+pub enum StlError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The data is neither a well-formed binary nor ASCII STL.
+    Malformed(String),
+}
+
+impl std::fmt::Display for StlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StlError::Io(e) => write!(f, "STL read error: {}", e),
+            StlError::Malformed(msg) => write!(f, "malformed STL: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for StlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StlError::Io(e) => Some(e),
+            StlError::Malformed(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for StlError {
+    fn from(e: std::io::Error) -> Self {
+        StlError::Io(e)
+    }
+}
+
+impl TriangleMesh {
+    /// Parse an STL file, auto-detecting binary vs ASCII.
+    ///
+    /// Data is treated as binary when its length matches the triangle count
+    /// in the 84-byte header, since binary headers may also begin with
+    /// `solid`. Otherwise it must be ASCII STL.
+    pub fn from_stl_bytes(bytes: &[u8]) -> Result<TriangleMesh, StlError> {
+        let triangles = if is_binary_stl(bytes) {
+            parse_binary(bytes)
+        } else if is_ascii_stl(bytes) {
+            parse_ascii(bytes)?
+        } else {
+            return Err(StlError::Malformed(
+                "not a binary STL and does not start with `solid`".to_string(),
+            ));
+        };
+        Ok(weld_triangles(&triangles))
+    }
+}
+
+fn is_binary_stl(bytes: &[u8]) -> bool {
+    if bytes.len() < BINARY_HEADER_LEN {
+        return false;
+    }
+    let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+    count
+        .checked_mul(BINARY_TRIANGLE_LEN)
+        .and_then(|n| n.checked_add(BINARY_HEADER_LEN))
+        == Some(bytes.len())
+}
+
+fn is_ascii_stl(bytes: &[u8]) -> bool {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    bytes[start..].starts_with(b"solid")
+}
+
+fn parse_binary(bytes: &[u8]) -> Vec<[[f32; 3]; 3]> {
+    bytes[BINARY_HEADER_LEN..]
+        .chunks_exact(BINARY_TRIANGLE_LEN)
+        .map(|record| {
+            let read_f32 = |at: usize| {
+                f32::from_le_bytes([record[at], record[at + 1], record[at + 2], record[at + 3]])
+            };
+            // Skip the 12-byte facet normal.
+            [0, 1, 2].map(|v| [0, 1, 2].map(|c| read_f32(12 + v * 12 + c * 4)))
+        })
+        .collect()
+}
+
+fn parse_ascii(bytes: &[u8]) -> Result<Vec<[[f32; 3]; 3]>, StlError> {
+    let text = std::str::from_utf8(bytes)
+        .map_err(|_| StlError::Malformed("ASCII STL is not valid UTF-8".to_string()))?;
+
+    let mut triangles = Vec::new();
+    let mut corners: Vec<[f32; 3]> = Vec::with_capacity(3);
+    for (line_no, line) in text.lines().enumerate() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("vertex") => {
+                let mut coord = [0.0f32; 3];
+                for c in &mut coord {
+                    *c = words.next().and_then(|w| w.parse().ok()).ok_or_else(|| {
+                        StlError::Malformed(format!("bad vertex on line {}", line_no + 1))
+                    })?;
+                }
+                corners.push(coord);
+            }
+            Some("endfacet") => {
+                if corners.len() != 3 {
+                    return Err(StlError::Malformed(format!(
+                        "facet ending on line {} has {} vertices",
+                        line_no + 1,
+                        corners.len()
+                    )));
+                }
+                triangles.push([corners[0], corners[1], corners[2]]);
+                corners.clear();
+            }
+            _ => {}
+        }
+    }
+    Ok(triangles)
+}
+
+/// Merge identical corners into shared vertices and compute area-weighted
+/// vertex normals.
+pub(crate) fn weld_triangles(triangles: &[[[f32; 3]; 3]]) -> TriangleMesh {
+    let mut mesh = TriangleMesh::new();
+    let mut lookup: HashMap<[u32; 3], u32> = HashMap::new();
+
+    for tri in triangles {
+        for corner in tri {
+            // `+ 0.0` folds -0.0 into 0.0 so both weld together.
+            let key = corner.map(|c| (c + 0.0).to_bits());
+            let index = *lookup.entry(key).or_insert_with(|| {
+                mesh.vertices.extend_from_slice(corner);
+                mesh.normals.extend_from_slice(&[0.0; 3]);
+                (mesh.vertices.len() / 3 - 1) as u32
+            });
+            mesh.indices.push(index);
+        }
+    }
+
+    for tri in mesh.indices.chunks_exact(3) {
+        let p = [0, 1, 2].map(|k| {
+            let i = tri[k] as usize * 3;
+            [mesh.vertices[i], mesh.vertices[i + 1], mesh.vertices[i + 2]]
+        });
+        let e1 = [0, 1, 2].map(|c| p[1][c] - p[0][c]);
+        let e2 = [0, 1, 2].map(|c| p[2][c] - p[0][c]);
+        // Unnormalized cross product weights each face by its area.
+        let n = [
+            e1[1] * e2[2] - e1[2] * e2[1],
+            e1[2] * e2[0] - e1[0] * e2[2],
+            e1[0] * e2[1] - e1[1] * e2[0],
+        ];
+        for &v in tri {
+            let i = v as usize * 3;
+            for (acc, nc) in mesh.normals[i..i + 3].iter_mut().zip(n) {
+                *acc += nc;
+            }
+        }
+    }
+    for n in mesh.normals.chunks_exact_mut(3) {
+        let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+        if len > 1e-12 {
+            n.iter_mut().for_each(|c| *c /= len);
+        }
+    }
+
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASCII_TETRA: &str = "solid tetra
+  facet normal 0 0 -1
+    outer loop
+      vertex 0 0 0
+      vertex 0 1 0
+      vertex 1 0 0
+    endloop
+  endfacet
+  facet normal 0 -1 0
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 0 0 1
+    endloop
+  endfacet
+  facet normal -1 0 0
+    outer loop
+      vertex 0 0 0
+      vertex 0 0 1
+      vertex 0 1 0
+    endloop
+  endfacet
+  facet normal 1 1 1
+    outer loop
+      vertex 1 0 0
+      vertex 0 1 0
+      vertex 0 0 1
+    endloop
+  endfacet
+endsolid tetra
+";
+
+    #[test]
+    fn test_ascii_stl_welds_vertices() {
+        let mesh = TriangleMesh::from_stl_bytes(ASCII_TETRA.as_bytes()).unwrap();
+        assert_eq!(mesh.num_triangles(), 4);
+        assert_eq!(mesh.num_vertices(), 4);
+        assert_eq!(mesh.normals.len(), mesh.vertices.len());
+    }
+
+    #[test]
+    fn test_binary_header_starting_with_solid() {
+        // Binary files may begin with "solid"; the length check must win.
+        let mut data = vec![b' '; 80];
+        data[..5].copy_from_slice(b"solid");
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[0u8; 12]);
+        for v in [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]] {
+            for c in v {
+                data.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        data.extend_from_slice(&0u16.to_le_bytes());
+
+        let mesh = TriangleMesh::from_stl_bytes(&data).unwrap();
+        assert_eq!(mesh.num_triangles(), 1);
+        assert_eq!(&mesh.vertices[3..6], &[1.0, 0.0, 0.0]);
+        assert_eq!(&mesh.normals[..3], &[0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_malformed_stl() {
+        assert!(TriangleMesh::from_stl_bytes(b"not an stl").is_err());
+        let truncated = "solid x\nfacet\nouter loop\nvertex 0 0 0\nendloop\nendfacet\n";
+        assert!(matches!(
+            TriangleMesh::from_stl_bytes(truncated.as_bytes()),
+            Err(StlError::Malformed(_))
+        ));
+    }
+}
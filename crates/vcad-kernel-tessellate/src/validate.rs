@@ -0,0 +1,333 @@
+//! Manifold checks and orientation repair for triangle meshes.
+//!
+//! Faces are tessellated independently, so the two sides of a B-rep edge
+//! carry duplicate vertices. Checks weld vertices with identical positions
+//! before comparing edges.
+
+use std::collections::{HashMap, VecDeque};
+
+use vcad_kernel_math::Vec3;
+
+use crate::TriangleMesh;
+
+impl TriangleMesh {
+    /// Whether the mesh is a closed, consistently wound surface.
+    ///
+    /// Every edge must be used by exactly two triangles, once in each
+    /// direction. Such a mesh encloses a volume and is safe to print.
+    pub fn is_watertight(&self) -> bool {
+        if self.indices.is_empty() {
+            return false;
+        }
+        let counts = self.directed_edge_counts();
+        counts
+            .iter()
+            .all(|(&(a, b), &n)| n == 1 && counts.get(&(b, a)) == Some(&1))
+    }
+
+    /// Edges not matched by an oppositely directed edge of another triangle.
+    ///
+    /// Each edge is returned as a pair of vertex indices in its triangle's
+    /// winding order. Coincident vertices are reported by their first index.
+    /// A watertight mesh has none.
+    pub fn boundary_edges(&self) -> Vec<(usize, usize)> {
+        let counts = self.directed_edge_counts();
+        let mut edges: Vec<(usize, usize)> = counts
+            .iter()
+            .filter(|&(&(a, b), &n)| counts.get(&(b, a)).copied().unwrap_or(0) != n)
+            .map(|(&edge, _)| edge)
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Wind every triangle consistently with its neighbours, with the
+    /// normals pointing out of the enclosed volume.
+    ///
+    /// Orientation is flood-filled across shared edges from one triangle of
+    /// each connected shell. Shells are then nested by containment: a shell
+    /// inside an even number of others (such as the outside of a body)
+    /// encloses positive volume, and one inside an odd number (the wall of
+    /// a cavity, or of an island in a cavity) encloses negative volume, so
+    /// that every normal points away from material. Vertex normals, if
+    /// present, are recomputed.
+    ///
+    /// Returns the number of triangles flipped.
+    pub fn orient_outward(&mut self) -> usize {
+        let welded = self.welded_indices();
+        let tris: Vec<[usize; 3]> = self
+            .indices
+            .chunks_exact(3)
+            .map(|tri| [0, 1, 2].map(|k| welded[tri[k] as usize]))
+            .collect();
+        let degenerate = |[a, b, c]: [usize; 3]| a == b || b == c || c == a;
+
+        // Triangles on each undirected edge, with whether they run low to high
+        let mut adjacency: HashMap<(usize, usize), Vec<(usize, bool)>> = HashMap::new();
+        for (t, &tri) in tris.iter().enumerate() {
+            if degenerate(tri) {
+                continue;
+            }
+            for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+                adjacency
+                    .entry((a.min(b), a.max(b)))
+                    .or_default()
+                    .push((t, a < b));
+            }
+        }
+
+        let mut flip: Vec<Option<bool>> = vec![None; tris.len()];
+        let mut shells: Vec<Vec<usize>> = Vec::new();
+        for seed in 0..tris.len() {
+            if flip[seed].is_some() || degenerate(tris[seed]) {
+                continue;
+            }
+
+            // Neighbours must run each shared edge the other way
+            flip[seed] = Some(false);
+            let mut shell = vec![seed];
+            let mut queue = VecDeque::from([seed]);
+            while let Some(t) = queue.pop_front() {
+                let tri = tris[t];
+                let flip_t = flip[t] == Some(true);
+                for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+                    let forward = (a < b) != flip_t;
+                    for &(n, n_forward) in &adjacency[&(a.min(b), a.max(b))] {
+                        if flip[n].is_none() {
+                            flip[n] = Some(n_forward == forward);
+                            shell.push(n);
+                            queue.push_back(n);
+                        }
+                    }
+                }
+            }
+
+            // Make the shell enclose positive volume for now
+            let volume: f64 = shell
+                .iter()
+                .map(|&t| {
+                    let v = self.signed_volume_of(&self.indices[3 * t..3 * t + 3]);
+                    if flip[t] == Some(true) {
+                        -v
+                    } else {
+                        v
+                    }
+                })
+                .sum();
+            if volume < 0.0 {
+                for &t in &shell {
+                    flip[t] = flip[t].map(|f| !f);
+                }
+            }
+            shells.push(shell);
+        }
+
+        // Shells nested inside an odd number of others bound cavities
+        let depths: Vec<usize> = shells
+            .iter()
+            .enumerate()
+            .map(|(i, shell)| {
+                let point = self.vertex(self.indices[3 * shell[0]]);
+                shells
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, other)| j != i && self.shell_contains(other, &point))
+                    .count()
+            })
+            .collect();
+
+        let mut flipped = 0;
+        for (shell, depth) in shells.iter().zip(depths) {
+            for &t in shell {
+                if (flip[t] == Some(true)) != (depth % 2 == 1) {
+                    self.indices.swap(3 * t + 1, 3 * t + 2);
+                    flipped += 1;
+                }
+            }
+        }
+
+        if flipped > 0 && !self.normals.is_empty() {
+            self.normals = self.vertex_normals().iter().map(|&c| c as f32).collect();
+        }
+        flipped
+    }
+
+    /// Volume enclosed by the mesh, positive when the triangles wind
+    /// counter-clockwise seen from outside.
+    pub fn signed_volume(&self) -> f64 {
+        self.indices
+            .chunks_exact(3)
+            .map(|tri| self.signed_volume_of(tri))
+            .sum()
+    }
+
+    /// Signed volume of the tetrahedron from the origin to a triangle.
+    fn signed_volume_of(&self, tri: &[u32]) -> f64 {
+        let v = |idx| self.vertex(idx);
+        v(tri[0]).dot(&v(tri[1]).cross(&v(tri[2]))) / 6.0
+    }
+
+    /// Position of a vertex.
+    fn vertex(&self, idx: u32) -> Vec3 {
+        let i = idx as usize * 3;
+        Vec3::new(
+            self.vertices[i] as f64,
+            self.vertices[i + 1] as f64,
+            self.vertices[i + 2] as f64,
+        )
+    }
+
+    /// Whether `point` lies inside the closed shell made of the given
+    /// triangles, by the parity of a ray's crossings with them.
+    fn shell_contains(&self, shell: &[usize], point: &Vec3) -> bool {
+        // Skewed so that it is unlikely to graze an edge of an axis-aligned
+        // mesh
+        let dir = Vec3::new(0.5773, 0.6172, 0.5349);
+        let crossings = shell
+            .iter()
+            .filter(|&&t| {
+                let [a, b, c] = [0, 1, 2].map(|k| self.vertex(self.indices[3 * t + k]));
+                ray_hits_triangle(point, &dir, &a, &b, &c)
+            })
+            .count();
+        crossings % 2 == 1
+    }
+
+    /// Count each directed edge over welded vertex indices. Degenerate
+    /// triangles (with repeated welded vertices) are ignored.
+    fn directed_edge_counts(&self) -> HashMap<(usize, usize), usize> {
+        let welded = self.welded_indices();
+        let mut counts = HashMap::new();
+        for tri in self.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| welded[tri[k] as usize]);
+            if a == b || b == c || c == a {
+                continue;
+            }
+            for edge in [(a, b), (b, c), (c, a)] {
+                *counts.entry(edge).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Map each vertex to the first vertex with the same position.
+    fn welded_indices(&self) -> Vec<usize> {
+        let mut first: HashMap<[u32; 3], usize> = HashMap::new();
+        self.vertices
+            .chunks_exact(3)
+            .enumerate()
+            .map(|(i, v)| {
+                // `+ 0.0` folds -0.0 into 0.0 so both weld together.
+                let key = [v[0], v[1], v[2]].map(|c| (c + 0.0).to_bits());
+                *first.entry(key).or_insert(i)
+            })
+            .collect()
+    }
+}
+
+/// Whether the ray from `origin` along `dir` crosses triangle `abc`
+/// (Möller–Trumbore).
+fn ray_hits_triangle(origin: &Vec3, dir: &Vec3, a: &Vec3, b: &Vec3, c: &Vec3) -> bool {
+    let (e1, e2) = (b - a, c - a);
+    let p = dir.cross(&e2);
+    let det = e1.dot(&p);
+    if det.abs() < 1e-12 {
+        return false;
+    }
+    let s = origin - a;
+    let u = s.dot(&p) / det;
+    if !(0.0..=1.0).contains(&u) {
+        return false;
+    }
+    let q = s.cross(&e1);
+    let v = dir.dot(&q) / det;
+    if v < 0.0 || u + v > 1.0 {
+        return false;
+    }
+    e2.dot(&q) / det > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tessellate_brep;
+    use vcad_kernel_primitives::make_cube;
+
+    #[test]
+    fn test_cube_watertight() {
+        let mut mesh = tessellate_brep(&make_cube(10.0, 10.0, 10.0), 32);
+        assert!(mesh.is_watertight());
+        assert!(mesh.boundary_edges().is_empty());
+
+        mesh.indices.truncate(mesh.indices.len() - 3);
+        assert!(!mesh.is_watertight());
+        assert!(mesh.boundary_edges().len() >= 2);
+    }
+
+    #[test]
+    fn test_orient_outward_mixed_winding() {
+        let mut mesh = tessellate_brep(&make_cube(10.0, 10.0, 10.0), 32);
+        let n = mesh.num_triangles();
+        for t in (0..n).step_by(3) {
+            mesh.indices.swap(3 * t, 3 * t + 1);
+        }
+        assert!(!mesh.is_watertight());
+
+        assert_eq!(mesh.orient_outward(), n.div_ceil(3));
+        assert!(mesh.is_watertight());
+        assert!((mesh.signed_volume() - 1000.0).abs() < 1e-6);
+        assert_eq!(mesh.orient_outward(), 0);
+    }
+
+    #[test]
+    fn test_orient_outward_inside_out() {
+        let mut mesh = tessellate_brep(&make_cube(10.0, 10.0, 10.0), 32);
+        for tri in mesh.indices.chunks_exact_mut(3) {
+            tri.swap(1, 2);
+        }
+        assert!(mesh.signed_volume() < 0.0);
+
+        assert_eq!(mesh.orient_outward(), mesh.num_triangles());
+        assert!((mesh.signed_volume() - 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_orient_outward_cavity() {
+        use vcad_kernel_primitives::{make_cube_at, BoxOrigin};
+
+        // A 10 mm box with a 6 mm void in the middle, both shells wound
+        // as separate bodies
+        let mut mesh = tessellate_brep(&make_cube_at(10.0, 10.0, 10.0, BoxOrigin::Center), 32);
+        let void = tessellate_brep(&make_cube_at(6.0, 6.0, 6.0, BoxOrigin::Center), 32);
+        let outer = mesh.num_triangles();
+        mesh.merge(&void);
+        assert!((mesh.signed_volume() - 1216.0).abs() < 1e-6);
+
+        assert_eq!(mesh.orient_outward(), void.num_triangles());
+        assert!((mesh.signed_volume() - 784.0).abs() < 1e-6);
+        assert!(mesh.is_watertight());
+        assert_eq!(mesh.orient_outward(), 0);
+
+        // Scrambled windings come out the same way
+        for t in (0..mesh.num_triangles()).step_by(2) {
+            mesh.indices.swap(3 * t, 3 * t + 1);
+        }
+        mesh.orient_outward();
+        assert!((mesh.signed_volume() - 784.0).abs() < 1e-6);
+
+        // An island inside the void is a body again
+        let island = tessellate_brep(&make_cube_at(2.0, 2.0, 2.0, BoxOrigin::Center), 32);
+        mesh.merge(&island);
+        for tri in mesh.indices.chunks_exact_mut(3).take(outer) {
+            tri.swap(1, 2);
+        }
+        mesh.orient_outward();
+        assert!((mesh.signed_volume() - 792.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_flipped_triangle_not_watertight() {
+        let mut mesh = tessellate_brep(&make_cube(10.0, 10.0, 10.0), 32);
+        mesh.indices.swap(0, 1);
+        assert!(!mesh.is_watertight());
+    }
+}
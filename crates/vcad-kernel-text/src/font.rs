@@ -1,11 +1,14 @@
 //! Font loading and management.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
 use std::sync::OnceLock;
 use thiserror::Error;
 use ttf_parser::{Face, GlyphId};
 
 use crate::builtin::OPEN_SANS_REGULAR;
+use crate::{TextAlignment, TextError};
+use vcad_kernel_sketch::SketchProfile;
 
 /// Errors from font operations.
 #[derive(Debug, Clone, Error)]
@@ -13,6 +16,14 @@ pub enum FontError {
     /// Failed to parse font data.
     #[error("failed to parse font: {0}")]
     ParseError(String),
+
+    /// The font has no glyph outlines (e.g. a bitmap-only font).
+    #[error("font has no glyph outlines")]
+    NoOutlines,
+
+    /// Failed to read a font file.
+    #[error("failed to read font file: {0}")]
+    Io(String),
 }
 
 /// A parsed font that can be used for text rendering.
@@ -53,6 +64,31 @@ impl Font {
         })
     }
 
+    /// Create a font from TrueType (.ttf) or OpenType (.otf) file contents.
+    ///
+    /// The font is named after its family name, if it has one. Both simple
+    /// and composite glyphs are supported; quadratic (TrueType) and cubic
+    /// (CFF) outlines are flattened to line segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FontError::ParseError`] if the data is not a valid font, or
+    /// [`FontError::NoOutlines`] if it has neither a `glyf` nor a `CFF` table.
+    pub fn from_ttf_bytes(bytes: &[u8]) -> Result<Self, FontError> {
+        let face = Face::parse(bytes, 0).map_err(|e| FontError::ParseError(format!("{:?}", e)))?;
+        let tables = face.tables();
+        if tables.glyf.is_none() && tables.cff.is_none() && tables.cff2.is_none() {
+            return Err(FontError::NoOutlines);
+        }
+        let name = face
+            .names()
+            .into_iter()
+            .filter(|n| n.name_id == ttf_parser::name_id::FAMILY)
+            .find_map(|n| n.to_string())
+            .unwrap_or_else(|| "unnamed".to_string());
+        Self::from_data(&name, bytes)
+    }
+
     /// Get the ttf-parser Face for this font.
     ///
     /// # Safety
@@ -67,6 +103,28 @@ impl Font {
         self.face().glyph_index(c)
     }
 
+    /// Whether the font has a glyph for `ch`.
+    pub fn covers(&self, ch: char) -> bool {
+        self.glyph_id(ch).is_some()
+    }
+
+    /// Every character the font has a glyph for, in code point order.
+    pub fn coverage(&self) -> impl Iterator<Item = char> {
+        let face = self.face();
+        let mut chars = BTreeSet::new();
+        if let Some(cmap) = face.tables().cmap {
+            for subtable in cmap.subtables.into_iter().filter(|s| s.is_unicode()) {
+                subtable.codepoints(|cp| {
+                    let mapped = subtable.glyph_index(cp).is_some_and(|g| g.0 != 0);
+                    if let Some(ch) = char::from_u32(cp).filter(|_| mapped) {
+                        chars.insert(ch);
+                    }
+                });
+            }
+        }
+        chars.into_iter()
+    }
+
     /// Get the horizontal advance width for a glyph.
     pub fn advance_width(&self, glyph_id: GlyphId) -> f64 {
         self.face()
@@ -93,9 +151,13 @@ impl std::fmt::Debug for Font {
 }
 
 /// Registry for managing loaded fonts.
+///
+/// Besides named fonts, a registry holds a fallback chain: fonts tried in
+/// order for characters the requested font has no glyph for.
 #[derive(Debug, Default)]
 pub struct FontRegistry {
     fonts: HashMap<String, Font>,
+    fallbacks: Vec<Font>,
 }
 
 impl FontRegistry {
@@ -104,6 +166,54 @@ impl FontRegistry {
         Self::default()
     }
 
+    /// Create a registry whose fallback chain is `fallbacks`, in order.
+    pub fn with_fallbacks(fallbacks: &[Font]) -> Self {
+        Self {
+            fonts: HashMap::new(),
+            fallbacks: fallbacks.to_vec(),
+        }
+    }
+
+    /// The fallback chain, in the order fonts are tried.
+    pub fn fallbacks(&self) -> &[Font] {
+        &self.fallbacks
+    }
+
+    /// Convert text to sketch profiles like [`crate::text_to_profiles`],
+    /// taking each character from `font` or, if it lacks the glyph, from
+    /// the first fallback that has it.
+    ///
+    /// Each glyph is scaled and advanced with the metrics of the font it
+    /// came from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TextError::GlyphNotFound`] for the first character that
+    /// neither `font` nor any fallback covers. Whitespace never fails.
+    pub fn text_to_profiles(
+        &self,
+        text: &str,
+        font: &Font,
+        height: f64,
+        letter_spacing: f64,
+        line_spacing: f64,
+        alignment: TextAlignment,
+    ) -> Result<Vec<SketchProfile>, TextError> {
+        let (profiles, missing) = crate::profile::layout_text(
+            text,
+            font,
+            &self.fallbacks,
+            height,
+            letter_spacing,
+            line_spacing,
+            alignment,
+        );
+        match missing.first() {
+            Some(&c) => Err(TextError::GlyphNotFound(c)),
+            None => Ok(profiles),
+        }
+    }
+
     /// Register a font from raw TTF/OTF data.
     pub fn register(&mut self, name: &str, data: &[u8]) -> Result<(), FontError> {
         let font = Font::from_data(name, data)?;
@@ -111,6 +221,20 @@ impl FontRegistry {
         Ok(())
     }
 
+    /// Load a TrueType/OpenType font file and register it under `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FontError::Io`] if the file cannot be read, or any error
+    /// from [`Font::from_ttf_bytes`].
+    pub fn load_ttf(&mut self, name: &str, path: impl AsRef<Path>) -> Result<(), FontError> {
+        let data = std::fs::read(path.as_ref()).map_err(|e| FontError::Io(e.to_string()))?;
+        let mut font = Font::from_ttf_bytes(&data)?;
+        font.name = name.to_string();
+        self.fonts.insert(name.to_string(), font);
+        Ok(())
+    }
+
     /// Get a font by name.
     pub fn get(&self, name: &str) -> Option<&Font> {
         self.fonts.get(name)
@@ -133,7 +257,7 @@ impl FontRegistry {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
     #[test]
@@ -150,6 +274,50 @@ mod tests {
         assert!(glyph.is_some());
     }
 
+    /// Minimal TrueType font with only `.notdef` and `A`.
+    pub(crate) const DEMO_TTF: &[u8] = include_bytes!("../testdata/demo.ttf");
+
+    #[test]
+    fn test_from_ttf_bytes() {
+        let font = Font::from_ttf_bytes(DEMO_TTF).unwrap();
+        assert_eq!(font.units_per_em, 1000.0);
+        let glyph = font.glyph_id('A').unwrap();
+        assert!(font.advance_width(glyph) > 0.0);
+        assert!(font.glyph_id('Z').is_none());
+
+        assert!(matches!(
+            Font::from_ttf_bytes(b"not a font"),
+            Err(FontError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_coverage() {
+        let font = Font::from_ttf_bytes(DEMO_TTF).unwrap();
+        assert!(font.covers('A'));
+        assert!(!font.covers('1'));
+        assert_eq!(font.coverage().collect::<Vec<_>>(), vec!['A']);
+
+        let builtin = FontRegistry::builtin_sans();
+        assert!(builtin.covers('1'));
+        let chars: Vec<char> = builtin.coverage().collect();
+        assert!(chars.contains(&'1') && chars.contains(&'z'));
+        assert!(chars.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_load_ttf() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/demo.ttf");
+        let mut registry = FontRegistry::new();
+        registry.load_ttf("demo", &path).unwrap();
+        assert_eq!(registry.get("demo").unwrap().name, "demo");
+
+        assert!(matches!(
+            registry.load_ttf("missing", path.with_file_name("missing.ttf")),
+            Err(FontError::Io(_))
+        ));
+    }
+
     #[test]
     fn test_advance_width() {
         let font = FontRegistry::builtin_sans();
@@ -21,16 +21,19 @@
 //!     TextAlignment::Left,
 //! );
 //!
-//! // Profiles can then be used with extrude() to create 3D geometry
+//! // Profiles can then be used with extrude() to create 3D geometry,
+//! // or wrapped onto a cylinder with project_profiles_onto_cylinder()
 //! ```
 
 mod builtin;
 mod font;
 mod glyph;
 mod profile;
+mod wrap;
 
 pub use font::{Font, FontError, FontRegistry};
-pub use profile::{text_bounds, text_to_profiles};
+pub use profile::{glyph_to_profiles, text_bounds, text_to_profiles};
+pub use wrap::{project_profiles_onto_cylinder, SketchProfile3D};
 
 use thiserror::Error;
 
@@ -3,9 +3,11 @@
 use vcad_kernel_math::{Point3, Vec3};
 use vcad_kernel_sketch::SketchProfile;
 
+use ttf_parser::{Face, GlyphId};
+
 use crate::font::Font;
 use crate::glyph::{contour_to_segments, extract_glyph_contours};
-use crate::TextAlignment;
+use crate::{TextAlignment, TextError};
 
 /// Convert text to a list of sketch profiles.
 ///
@@ -34,25 +36,55 @@ pub fn text_to_profiles(
     line_spacing: f64,
     alignment: TextAlignment,
 ) -> Vec<SketchProfile> {
+    // Characters the font lacks are skipped
+    layout_text(
+        text,
+        font,
+        &[],
+        height,
+        letter_spacing,
+        line_spacing,
+        alignment,
+    )
+    .0
+}
+
+/// Lay out text as in [`text_to_profiles`], taking each glyph from `font`
+/// or the first of `fallbacks` that has it.
+///
+/// Returns the profiles and the non-whitespace characters no font covers,
+/// which are left out.
+pub(crate) fn layout_text(
+    text: &str,
+    font: &Font,
+    fallbacks: &[Font],
+    height: f64,
+    letter_spacing: f64,
+    line_spacing: f64,
+    alignment: TextAlignment,
+) -> (Vec<SketchProfile>, Vec<char>) {
+    let mut profiles = Vec::new();
+    let mut missing = Vec::new();
     if text.is_empty() || height < 0.1 {
-        return Vec::new();
+        return (profiles, missing);
     }
 
-    let face = font.face();
+    let resolve = |c: char| {
+        std::iter::once(font)
+            .chain(fallbacks)
+            .find_map(|f| f.glyph_id(c).map(|glyph_id| (f, glyph_id)))
+    };
 
     // Calculate scale factor from font units to mm
-    let full_font_height = font.ascender - font.descender;
-    let scale = height / full_font_height;
+    let scale = font_scale(font, height);
 
     // Line height for multi-line text
     let line_height = height * line_spacing;
 
-    let mut profiles = Vec::new();
-
     // Process each line of text
     for (line_idx, line) in text.lines().enumerate() {
         // Calculate line width for alignment
-        let line_width = calculate_line_width(line, font, scale, letter_spacing);
+        let line_width = calculate_line_width(line, font, resolve, scale, letter_spacing);
 
         // Calculate starting X offset based on alignment
         let x_offset = match alignment {
@@ -70,8 +102,10 @@ pub fn text_to_profiles(
         for c in line.chars() {
             // Skip whitespace but advance cursor
             if c.is_whitespace() {
-                if let Some(glyph_id) = font.glyph_id(c) {
-                    let advance = font.advance_width(glyph_id) * scale * letter_spacing;
+                if let Some((glyph_font, glyph_id)) = resolve(c) {
+                    let advance = glyph_font.advance_width(glyph_id)
+                        * font_scale(glyph_font, height)
+                        * letter_spacing;
                     cursor_x += advance;
                 } else {
                     // Default space width
@@ -81,39 +115,69 @@ pub fn text_to_profiles(
             }
 
             // Get glyph for character
-            let Some(glyph_id) = font.glyph_id(c) else {
-                // Skip unknown characters
+            let Some((glyph_font, glyph_id)) = resolve(c) else {
+                missing.push(c);
                 continue;
             };
 
-            // Extract glyph contours
-            let contours = extract_glyph_contours(&face, glyph_id);
-
-            // Convert each contour to a profile
-            for contour in &contours {
-                let segments = contour_to_segments(contour, scale, cursor_x, y_offset);
-
-                // Need at least 3 segments for a closed profile
-                if segments.len() >= 3 {
-                    // Try to create the profile - skip if invalid
-                    if let Ok(profile) = SketchProfile::new(
-                        Point3::origin(),
-                        Vec3::x(),
-                        Vec3::y(),
-                        segments,
-                    ) {
-                        profiles.push(profile);
-                    }
-                }
-            }
+            let glyph_scale = font_scale(glyph_font, height);
+            profiles.extend(glyph_profiles(
+                &glyph_font.face(),
+                glyph_id,
+                glyph_scale,
+                cursor_x,
+                y_offset,
+            ));
 
             // Advance cursor by glyph width
-            let advance = font.advance_width(glyph_id) * scale * letter_spacing;
+            let advance = glyph_font.advance_width(glyph_id) * glyph_scale * letter_spacing;
             cursor_x += advance;
         }
     }
 
-    profiles
+    (profiles, missing)
+}
+
+/// Scale factor from font units to mm for text of the given height.
+fn font_scale(font: &Font, height: f64) -> f64 {
+    height / (font.ascender - font.descender)
+}
+
+/// Convert a single character to sketch profiles, with its origin at (0, 0).
+///
+/// `height` has the same meaning as in [`text_to_profiles`]. Characters
+/// without an outline (such as a space) yield no profiles.
+///
+/// # Errors
+///
+/// Returns [`TextError::GlyphNotFound`] if the font has no glyph for `c`.
+pub fn glyph_to_profiles(
+    c: char,
+    font: &Font,
+    height: f64,
+) -> Result<Vec<SketchProfile>, TextError> {
+    let glyph_id = font.glyph_id(c).ok_or(TextError::GlyphNotFound(c))?;
+    let scale = height / (font.ascender - font.descender);
+    Ok(glyph_profiles(&font.face(), glyph_id, scale, 0.0, 0.0))
+}
+
+/// Convert each contour of a glyph to a profile, scaled and offset.
+fn glyph_profiles(
+    face: &Face<'_>,
+    glyph_id: GlyphId,
+    scale: f64,
+    offset_x: f64,
+    offset_y: f64,
+) -> Vec<SketchProfile> {
+    extract_glyph_contours(face, glyph_id)
+        .iter()
+        .map(|contour| contour_to_segments(contour, scale, offset_x, offset_y))
+        // Need at least 3 segments for a closed profile; skip invalid ones
+        .filter(|segments| segments.len() >= 3)
+        .filter_map(|segments| {
+            SketchProfile::new(Point3::origin(), Vec3::x(), Vec3::y(), segments).ok()
+        })
+        .collect()
 }
 
 /// Get the bounding box of rendered text.
@@ -131,16 +195,16 @@ pub fn text_bounds(
     }
 
     // Calculate scale factor
-    let full_font_height = font.ascender - font.descender;
-    let scale = height / full_font_height;
+    let scale = font_scale(font, height);
 
     // Line height for multi-line text
     let line_height = height * line_spacing;
 
     // Find maximum line width
+    let resolve = |c: char| font.glyph_id(c).map(|glyph_id| (font, glyph_id));
     let max_width = text
         .lines()
-        .map(|line| calculate_line_width(line, font, scale, letter_spacing))
+        .map(|line| calculate_line_width(line, font, resolve, scale, letter_spacing))
         .fold(0.0_f64, |a, b| a.max(b));
 
     // Total height = number of lines * line height
@@ -151,12 +215,24 @@ pub fn text_bounds(
 }
 
 /// Calculate the width of a single line of text.
-fn calculate_line_width(line: &str, font: &Font, scale: f64, letter_spacing: f64) -> f64 {
+///
+/// `resolve` picks the font and glyph for each character; `font` and
+/// `scale` size the characters it finds nothing for.
+fn calculate_line_width<'a>(
+    line: &str,
+    font: &Font,
+    resolve: impl Fn(char) -> Option<(&'a Font, GlyphId)>,
+    scale: f64,
+    letter_spacing: f64,
+) -> f64 {
     let mut width = 0.0;
+    let height = scale * (font.ascender - font.descender);
 
     for c in line.chars() {
-        if let Some(glyph_id) = font.glyph_id(c) {
-            width += font.advance_width(glyph_id) * scale * letter_spacing;
+        if let Some((glyph_font, glyph_id)) = resolve(c) {
+            width += glyph_font.advance_width(glyph_id)
+                * font_scale(glyph_font, height)
+                * letter_spacing;
         } else {
             // Default character width for unknown glyphs
             width += scale * 0.5 * font.units_per_em;
@@ -188,6 +264,25 @@ mod tests {
         assert!(!profiles.is_empty());
     }
 
+    #[test]
+    fn test_glyph_from_loaded_ttf() {
+        let font = Font::from_ttf_bytes(crate::font::tests::DEMO_TTF).unwrap();
+
+        // 'A' has an outer contour and a triangular counter.
+        let profiles = glyph_to_profiles('A', &font, 10.0).unwrap();
+        assert_eq!(profiles.len(), 2);
+        let (min, max) = profiles[0].bounding_box_2d();
+        assert!(max.x - min.x > 0.0 && max.y - min.y > 0.0);
+
+        assert!(matches!(
+            glyph_to_profiles('Z', &font, 10.0),
+            Err(TextError::GlyphNotFound('Z'))
+        ));
+
+        let text = text_to_profiles("AA", &font, 10.0, 1.0, 1.2, TextAlignment::Left);
+        assert_eq!(text.len(), 4);
+    }
+
     #[test]
     fn test_text_bounds() {
         let font = FontRegistry::builtin_sans();
@@ -225,4 +320,38 @@ mod tests {
         // The x positions should differ based on alignment
         // (actual position testing would require looking at vertices)
     }
+
+    #[test]
+    fn test_fallback_font() {
+        let demo = Font::from_ttf_bytes(crate::font::tests::DEMO_TTF).unwrap();
+        let builtin = FontRegistry::builtin_sans();
+        let registry = FontRegistry::with_fallbacks(std::slice::from_ref(builtin));
+
+        // Without fallbacks the digit is dropped
+        let alone = text_to_profiles("A1", &demo, 10.0, 1.0, 1.2, TextAlignment::Left);
+        assert_eq!(alone.len(), 2);
+
+        let profiles = registry
+            .text_to_profiles("A1", &demo, 10.0, 1.0, 1.2, TextAlignment::Left)
+            .unwrap();
+        let digit = glyph_to_profiles('1', builtin, 10.0).unwrap();
+        assert_eq!(profiles.len(), 2 + digit.len());
+
+        // The digit starts one demo-font advance of 'A' along
+        let a = demo.glyph_id('A').unwrap();
+        let advance = demo.advance_width(a) * 10.0 / (demo.ascender - demo.descender);
+        let min_x = |profiles: &[SketchProfile]| {
+            profiles
+                .iter()
+                .map(|p| p.bounding_box_2d().0.x)
+                .fold(f64::INFINITY, f64::min)
+        };
+        assert!((min_x(&profiles[2..]) - (advance + min_x(&digit))).abs() < 1e-9);
+
+        // Nothing covers a CJK character
+        assert!(matches!(
+            registry.text_to_profiles("A\u{4e2d}", &demo, 10.0, 1.0, 1.2, TextAlignment::Left),
+            Err(TextError::GlyphNotFound('\u{4e2d}'))
+        ));
+    }
 }
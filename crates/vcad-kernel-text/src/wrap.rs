@@ -0,0 +1,141 @@
+//! Wrapping flat text profiles onto a cylinder for curved embossing.
+//!
+//! A profile's X coordinate becomes arc length around the cylinder and its Y
+//! coordinate becomes height along the axis, so glyphs keep their proportions
+//! on the surface. Offsetting the wrapped points along their normals gives
+//! raised (or, with a negative height, engraved) curved text.
+
+use vcad_kernel_math::{Point3, Vec3};
+use vcad_kernel_sketch::SketchProfile;
+
+/// Segments used to flatten each arc before wrapping.
+const SEGMENTS_PER_ARC: usize = 8;
+
+/// Largest angle (radians) one wrapped edge may span before it is subdivided,
+/// so long straight strokes follow the cylinder instead of cutting a chord.
+const MAX_EDGE_ANGLE: f64 = 2.0 * std::f64::consts::PI / 180.0;
+
+/// A closed profile lying on a curved surface.
+#[derive(Debug, Clone)]
+pub struct SketchProfile3D {
+    /// Loop points in order; the last point connects back to the first.
+    pub points: Vec<Point3>,
+    /// Unit outward surface normal at each point.
+    pub normals: Vec<Vec3>,
+}
+
+impl SketchProfile3D {
+    /// Points moved `distance` along their normals, e.g. the top of raised text.
+    pub fn offset(&self, distance: f64) -> Vec<Point3> {
+        self.points
+            .iter()
+            .zip(&self.normals)
+            .map(|(p, n)| p + n * distance)
+            .collect()
+    }
+}
+
+/// Wrap 2D profiles around a cylinder of `radius` centered on `axis`.
+///
+/// The cylinder axis passes through the origin. Profile X is measured as arc
+/// length around the circumference starting from a fixed reference direction
+/// perpendicular to `axis`, and profile Y runs along `axis`. Angles are not
+/// reduced modulo a full turn, so a glyph straddling the seam stays one
+/// continuous loop, and text longer than the circumference overlaps itself
+/// rather than being cut. Returns an empty list if `radius` or `axis` is
+/// degenerate.
+pub fn project_profiles_onto_cylinder(
+    profiles: &[SketchProfile],
+    radius: f64,
+    axis: Vec3,
+) -> Vec<SketchProfile3D> {
+    if radius <= 0.0 || axis.norm() < 1e-12 {
+        return Vec::new();
+    }
+    let axis = axis.normalize();
+    // Reference radial direction: the world axis least aligned with `axis`.
+    let helper = if axis.x.abs() < 0.9 {
+        Vec3::x()
+    } else {
+        Vec3::y()
+    };
+    let u = (helper - axis * helper.dot(&axis)).normalize();
+    let v = axis.cross(&u);
+
+    let wrap = |x: f64, y: f64| {
+        let theta = x / radius;
+        let normal = u * theta.cos() + v * theta.sin();
+        (Point3::origin() + normal * radius + axis * y, normal)
+    };
+
+    profiles
+        .iter()
+        .map(|profile| {
+            let flat = profile.tessellated_vertices_2d(SEGMENTS_PER_ARC);
+            let mut points = Vec::new();
+            let mut normals = Vec::new();
+            for (i, a) in flat.iter().enumerate() {
+                let b = flat[(i + 1) % flat.len()];
+                let span = ((b.x - a.x) / radius).abs();
+                let steps = (span / MAX_EDGE_ANGLE).ceil().max(1.0) as usize;
+                for k in 0..steps {
+                    let t = k as f64 / steps as f64;
+                    let (p, n) = wrap(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t);
+                    points.push(p);
+                    normals.push(n);
+                }
+            }
+            SketchProfile3D { points, normals }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{text_to_profiles, FontRegistry, TextAlignment};
+
+    #[test]
+    fn test_wrap_text_onto_cylinder() {
+        let font = FontRegistry::builtin_sans();
+        // Centered text straddles the seam at angle zero.
+        let profiles = text_to_profiles("HI", font, 8.0, 1.0, 1.2, TextAlignment::Center);
+        assert!(!profiles.is_empty());
+
+        let radius = 20.0;
+        let height = 1.5;
+        let wrapped = project_profiles_onto_cylinder(&profiles, radius, Vec3::z());
+        assert_eq!(wrapped.len(), profiles.len());
+
+        for profile in &wrapped {
+            assert_eq!(profile.points.len(), profile.normals.len());
+            let raised = profile.offset(height);
+            for p in profile.points.iter().chain(&raised) {
+                let r = (p.x * p.x + p.y * p.y).sqrt();
+                assert!(r >= radius - 1e-9 && r <= radius + height + 1e-9, "r = {r}");
+            }
+            // Consecutive points stay close: no jump across the seam.
+            let n = profile.points.len();
+            for i in 0..n {
+                let d = (profile.points[(i + 1) % n] - profile.points[i]).norm();
+                assert!(d < 8.0, "jump of {d}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrap_preserves_arc_length() {
+        let square = SketchProfile::rectangle(Point3::origin(), Vec3::x(), Vec3::y(), 10.0, 4.0);
+        let wrapped = project_profiles_onto_cylinder(&[square], 5.0, Vec3::z());
+        let perimeter: f64 = {
+            let pts = &wrapped[0].points;
+            (0..pts.len())
+                .map(|i| (pts[(i + 1) % pts.len()] - pts[i]).norm())
+                .sum()
+        };
+        // Chords slightly undercut the true arc length of 2 * (10 + 4).
+        assert!((perimeter - 28.0).abs() < 0.01, "perimeter = {perimeter}");
+
+        assert!(project_profiles_onto_cylinder(&[], 0.0, Vec3::z()).is_empty());
+    }
+}
@@ -6,8 +6,16 @@
 //! topology of B-rep solids: vertices, edges, loops, faces, shells,
 //! and solids.
 
-use slotmap::{new_key_type, SlotMap};
-use vcad_kernel_math::Point3;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+use slotmap::{new_key_type, Key, SlotMap};
+use vcad_kernel_math::{Point3, Vec3};
+
+/// Largest angle, in radians, between the directions of two edges meeting
+/// at a vertex for [`Topology::tangent_continuous_chain`] to treat them as
+/// tangent continuous (one degree).
+pub const TANGENT_ANGLE_TOLERANCE: f64 = std::f64::consts::PI / 180.0;
 
 new_key_type! {
     /// Handle for a vertex in the topology.
@@ -388,6 +396,306 @@ impl Topology {
     pub fn loop_len(&self, loop_id: LoopId) -> usize {
         self.loop_half_edges(loop_id).count()
     }
+
+    // =========================================================================
+    // Edge chains
+    // =========================================================================
+
+    /// Get the edges around the outer loop of a face, in loop order.
+    ///
+    /// Half-edges not yet joined into an edge are skipped.
+    pub fn edge_loop_of_face(&self, face: FaceId) -> Vec<EdgeId> {
+        self.loop_half_edges(self.faces[face].outer_loop)
+            .filter_map(|he| self.half_edges[he].edge)
+            .collect()
+    }
+
+    /// Grow a chain of edges from `start` through vertices where the next
+    /// edge continues tangentially.
+    ///
+    /// `tangent(edge, vertex)` gives the tangent of `edge` at its end
+    /// `vertex` in either sense, or `None` where it is unknown; the topology
+    /// has no curves, so this comes from the geometry. Each tangent is
+    /// oriented along the edge's chord. At each end of the chain, the edge
+    /// leaving the end vertex whose tangent deviates least from the chain's
+    /// is added, provided the deviation is within
+    /// [`TANGENT_ANGLE_TOLERANCE`]. The chain is returned in order along its
+    /// length, starting from whichever end it was walked back to; a closed
+    /// chain includes each edge once.
+    pub fn tangent_continuous_chain(
+        &self,
+        start: EdgeId,
+        tangent: impl Fn(EdgeId, VertexId) -> Option<Vec3>,
+    ) -> Vec<EdgeId> {
+        let he = self.edges[start].half_edge;
+        let Some(end) = self.edge_other_vertex(start, self.half_edges[he].origin) else {
+            return vec![start];
+        };
+        let mut forward = vec![start];
+        let mut visited = HashSet::from([start]);
+        self.extend_chain(start, end, &tangent, &mut forward, &mut visited);
+        let mut backward = Vec::new();
+        self.extend_chain(
+            start,
+            self.half_edges[he].origin,
+            &tangent,
+            &mut backward,
+            &mut visited,
+        );
+
+        backward.reverse();
+        backward.extend(forward);
+        backward
+    }
+
+    /// Walk from `edge` through `vertex`, pushing tangent continuations.
+    fn extend_chain(
+        &self,
+        mut edge: EdgeId,
+        mut vertex: VertexId,
+        tangent: &impl Fn(EdgeId, VertexId) -> Option<Vec3>,
+        chain: &mut Vec<EdgeId>,
+        visited: &mut HashSet<EdgeId>,
+    ) {
+        loop {
+            // The chain's direction arriving at `vertex`
+            let Some(dir) = self
+                .edge_other_vertex(edge, vertex)
+                .and_then(|from| self.edge_tangent(edge, vertex, from, vertex, tangent))
+            else {
+                return;
+            };
+            let best = self
+                .vertex_half_edges(vertex)
+                .filter_map(|he| self.half_edges[he].edge)
+                .filter(|e| !visited.contains(e))
+                .filter_map(|e| {
+                    // The next edge's direction leaving `vertex`
+                    let far = self.edge_other_vertex(e, vertex)?;
+                    let next_dir = self.edge_tangent(e, vertex, vertex, far, tangent)?;
+                    Some((e, far, dir.angle(&next_dir)))
+                })
+                .filter(|&(_, _, angle)| angle <= TANGENT_ANGLE_TOLERANCE)
+                .min_by(|a, b| a.2.total_cmp(&b.2));
+            let Some((next, far, _)) = best else {
+                return;
+            };
+            visited.insert(next);
+            chain.push(next);
+            edge = next;
+            vertex = far;
+        }
+    }
+
+    /// The end of `edge` that isn't `vertex`, if `edge` has both ends.
+    fn edge_other_vertex(&self, edge: EdgeId, vertex: VertexId) -> Option<VertexId> {
+        let he = self.edges[edge].half_edge;
+        let origin = self.half_edges[he].origin;
+        let dest = match self.half_edges[he].twin {
+            Some(twin) => self.half_edges[twin].origin,
+            None => self.half_edges[self.half_edges[he].next?].origin,
+        };
+        if origin == vertex {
+            Some(dest)
+        } else if dest == vertex {
+            Some(origin)
+        } else {
+            None
+        }
+    }
+
+    /// Unit tangent of `edge` at its end `at`, pointing the way the edge
+    /// runs from `from` to `to`, or `None` for a degenerate edge or an
+    /// unknown tangent.
+    fn edge_tangent(
+        &self,
+        edge: EdgeId,
+        at: VertexId,
+        from: VertexId,
+        to: VertexId,
+        tangent: &impl Fn(EdgeId, VertexId) -> Option<Vec3>,
+    ) -> Option<Vec3> {
+        let chord = self.vertices[to].point - self.vertices[from].point;
+        let t = tangent(edge, at)?;
+        let len = t.norm();
+        if chord.norm() < 1e-12 || len < 1e-12 {
+            return None;
+        }
+        Some(if t.dot(&chord) < 0.0 {
+            -t / len
+        } else {
+            t / len
+        })
+    }
+
+    // =========================================================================
+    // Shell traversal
+    // =========================================================================
+
+    /// Iterate the faces of a shell.
+    pub fn shell_faces(&self, shell: ShellId) -> impl Iterator<Item = FaceId> + '_ {
+        self.shells[shell].faces.iter().copied()
+    }
+
+    /// Get the faces sharing at least one edge with `face`.
+    ///
+    /// Both the outer and inner loops are walked. Each neighbor appears once,
+    /// in the order first reached.
+    pub fn face_neighbors(&self, face: FaceId) -> Vec<FaceId> {
+        let f = &self.faces[face];
+        let mut neighbors = Vec::new();
+        for loop_id in std::iter::once(f.outer_loop).chain(f.inner_loops.iter().copied()) {
+            for he in self.loop_half_edges(loop_id) {
+                let other = self.half_edges[he]
+                    .twin
+                    .and_then(|t| self.half_edges[t].loop_id)
+                    .and_then(|l| self.loops[l].face);
+                if let Some(other) = other {
+                    if other != face && !neighbors.contains(&other) {
+                        neighbors.push(other);
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+
+    /// Build the face-adjacency (dual) graph of a shell.
+    ///
+    /// Maps every face of the shell to its edge-sharing neighbors.
+    pub fn face_adjacency_graph(&self, shell: ShellId) -> HashMap<FaceId, Vec<FaceId>> {
+        self.shell_faces(shell)
+            .map(|f| (f, self.face_neighbors(f)))
+            .collect()
+    }
+
+    /// Group the faces of a shell into edge-connected patches.
+    ///
+    /// Only adjacency through faces of the same shell counts. Components are
+    /// ordered by their first face in the shell's face list.
+    pub fn connected_face_components(&self, shell: ShellId) -> Vec<Vec<FaceId>> {
+        let in_shell: HashSet<FaceId> = self.shell_faces(shell).collect();
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+        for start in self.shell_faces(shell) {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut component = Vec::new();
+            let mut queue = VecDeque::from([start]);
+            while let Some(face) = queue.pop_front() {
+                component.push(face);
+                for n in self.face_neighbors(face) {
+                    if in_shell.contains(&n) && visited.insert(n) {
+                        queue.push_back(n);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    // =========================================================================
+    // Debugging
+    // =========================================================================
+
+    /// Render the half-edge structure as a Graphviz DOT digraph.
+    ///
+    /// Vertices become nodes labeled with their id and position. Each
+    /// half-edge is a directed edge from its origin to its destination,
+    /// labeled with its own id and its twin's, grouped into a cluster per
+    /// loop inside a cluster per face. Half-edges without a loop (or without
+    /// a `next`, so no destination) are drawn at the top level, the latter
+    /// as edges to a `dangling` node. A loop whose `next` chain cycles
+    /// without returning to its first half-edge is cut at the first repeat.
+    /// Pipe the output to `dot -Tsvg`.
+    pub fn to_graphviz(&self) -> String {
+        let mut out = String::from("digraph topology {\n");
+        for (id, v) in &self.vertices {
+            let p = v.point;
+            let _ = writeln!(
+                out,
+                "  \"v{}\" [label=\"v{}\\n({}, {}, {})\"];",
+                key(id),
+                key(id),
+                p.x,
+                p.y,
+                p.z
+            );
+        }
+
+        let mut drawn = HashSet::new();
+        for (face_id, face) in &self.faces {
+            let _ = writeln!(out, "  subgraph \"cluster_f{}\" {{", key(face_id));
+            let _ = writeln!(out, "    label=\"f{}\";", key(face_id));
+            for loop_id in std::iter::once(face.outer_loop).chain(face.inner_loops.iter().copied())
+            {
+                let _ = writeln!(out, "    subgraph \"cluster_l{}\" {{", key(loop_id));
+                let _ = writeln!(out, "      label=\"l{}\";", key(loop_id));
+                let mut visited = HashSet::new();
+                for he in self.loop_half_edges(loop_id) {
+                    if !visited.insert(he) {
+                        break;
+                    }
+                    if drawn.insert(he) {
+                        self.write_half_edge(&mut out, he, "      ");
+                    }
+                }
+                out.push_str("    }\n");
+            }
+            out.push_str("  }\n");
+        }
+        for he in self.half_edges.keys() {
+            if !drawn.contains(&he) {
+                self.write_half_edge(&mut out, he, "  ");
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render only the face-adjacency dual as an undirected Graphviz graph.
+    ///
+    /// Faces become nodes and every pair of edge-sharing faces one edge,
+    /// which keeps the output readable for large solids.
+    pub fn to_graphviz_faces(&self) -> String {
+        let mut out = String::from("graph faces {\n");
+        for face_id in self.faces.keys() {
+            let _ = writeln!(out, "  \"f{}\";", key(face_id));
+        }
+        for face_id in self.faces.keys() {
+            for n in self.face_neighbors(face_id) {
+                if face_id.data().as_ffi() < n.data().as_ffi() {
+                    let _ = writeln!(out, "  \"f{}\" -- \"f{}\";", key(face_id), key(n));
+                }
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn write_half_edge(&self, out: &mut String, he: HalfEdgeId, indent: &str) {
+        let h = &self.half_edges[he];
+        let dest = match h.next {
+            Some(next) => format!("v{}", key(self.half_edges[next].origin)),
+            None => "dangling".to_string(),
+        };
+        let twin = h
+            .twin
+            .map_or_else(|| "none".to_string(), |t| format!("h{}", key(t)));
+        let _ = writeln!(
+            out,
+            "{indent}\"v{}\" -> \"{dest}\" [label=\"h{} / {twin}\"];",
+            key(h.origin),
+            key(he)
+        );
+    }
+}
+
+/// Short, stable text form of a slotmap key for DOT output, e.g. `3v1`.
+fn key(k: impl Key) -> String {
+    format!("{:?}", k.data())
 }
 
 impl Default for Topology {
@@ -540,4 +848,207 @@ mod tests {
         assert_eq!(topo.half_edge_dest(he1), v2);
         assert_eq!(topo.half_edge_dest(he2), v0);
     }
+
+    /// Build a closed unit cube shell with twin half-edges paired up.
+    fn cube_shell(topo: &mut Topology) -> ShellId {
+        let v: Vec<VertexId> = (0..8)
+            .map(|i| {
+                topo.add_vertex(Point3::new(
+                    (i & 1) as f64,
+                    ((i >> 1) & 1) as f64,
+                    ((i >> 2) & 1) as f64,
+                ))
+            })
+            .collect();
+        // Outward-facing quads, counterclockwise seen from outside.
+        let quads = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ];
+        let mut by_ends = HashMap::new();
+        let mut faces = Vec::new();
+        for (i, quad) in quads.iter().enumerate() {
+            let hes: Vec<HalfEdgeId> = quad.iter().map(|&k| topo.add_half_edge(v[k])).collect();
+            for k in 0..4 {
+                by_ends.insert((quad[k], quad[(k + 1) % 4]), hes[k]);
+            }
+            let loop_id = topo.add_loop(&hes);
+            faces.push(topo.add_face(loop_id, i, Orientation::Forward));
+        }
+        for (&(a, b), &he) in &by_ends {
+            if a < b {
+                topo.add_edge(he, by_ends[&(b, a)]);
+            }
+        }
+        topo.add_shell(faces, ShellType::Outer)
+    }
+
+    #[test]
+    fn test_cube_face_adjacency() {
+        let mut topo = Topology::new();
+        let shell = cube_shell(&mut topo);
+        assert_eq!(topo.edges.len(), 12);
+        assert_eq!(topo.shell_faces(shell).count(), 6);
+
+        let graph = topo.face_adjacency_graph(shell);
+        assert_eq!(graph.len(), 6);
+        for (face, neighbors) in &graph {
+            assert_eq!(neighbors.len(), 4);
+            assert!(!neighbors.contains(face));
+            // Adjacency is symmetric.
+            for n in neighbors {
+                assert!(graph[n].contains(face));
+            }
+        }
+
+        let components = topo.connected_face_components(shell);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), 6);
+    }
+
+    #[test]
+    fn test_cube_graphviz() {
+        let mut topo = Topology::new();
+        cube_shell(&mut topo);
+
+        let dot = topo.to_graphviz();
+        assert!(dot.starts_with("digraph topology {"));
+        assert_eq!(dot.matches(" -> ").count(), 24);
+        assert_eq!(dot.matches("subgraph \"cluster_f").count(), 6);
+        assert_eq!(dot.matches("subgraph \"cluster_l").count(), 6);
+        assert!(!dot.contains("/ none"));
+        assert!(!dot.contains("dangling"));
+
+        let dual = topo.to_graphviz_faces();
+        assert_eq!(dual.matches(" -- ").count(), 12);
+    }
+
+    #[test]
+    fn test_graphviz_stops_on_broken_loop() {
+        let mut topo = Topology::new();
+        let shell = cube_shell(&mut topo);
+        let top = topo.shells[shell].faces[1];
+        let loop_id = topo.faces[top].outer_loop;
+        let hes: Vec<HalfEdgeId> = topo.loop_half_edges(loop_id).collect();
+
+        // h1 -> h2 -> h1 never returns to h0
+        topo.half_edges[hes[2]].next = Some(hes[1]);
+        let dot = topo.to_graphviz();
+        assert_eq!(dot.matches(" -> ").count(), 24);
+    }
+
+    #[test]
+    fn test_disconnected_face_components() {
+        let mut topo = Topology::new();
+        let shell = cube_shell(&mut topo);
+        let faces: Vec<FaceId> = topo.shell_faces(shell).collect();
+
+        // Cut the shell down to the two opposite z faces, which share no edge.
+        topo.shells[shell].faces = vec![faces[0], faces[1]];
+        let components = topo.connected_face_components(shell);
+        assert_eq!(components, vec![vec![faces[0]], vec![faces[1]]]);
+    }
+
+    #[test]
+    fn test_cube_edge_loop_of_face() {
+        let mut topo = Topology::new();
+        let shell = cube_shell(&mut topo);
+        let top = topo.shells[shell].faces[1];
+
+        let edges = topo.edge_loop_of_face(top);
+        assert_eq!(edges.len(), 4);
+        for &edge in &edges {
+            let (a, b) = topo.edge_faces(edge);
+            assert!(a == Some(top) || b == Some(top));
+        }
+
+        // Cube edges all meet at right angles
+        let chord = |e, v| chord_tangent(&topo, e, v);
+        assert_eq!(
+            topo.tangent_continuous_chain(edges[0], chord),
+            vec![edges[0]]
+        );
+    }
+
+    #[test]
+    fn test_tangent_continuous_chain() {
+        // A two-sided pentagon whose bottom side is split into three
+        // collinear edges
+        let mut topo = Topology::new();
+        let v: Vec<VertexId> = [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0), (3.0, 1.0)]
+            .iter()
+            .map(|&(x, y)| topo.add_vertex(Point3::new(x, y, 0.0)))
+            .collect();
+        let front: Vec<HalfEdgeId> = v.iter().map(|&v| topo.add_half_edge(v)).collect();
+        let back: Vec<HalfEdgeId> = v.iter().rev().map(|&v| topo.add_half_edge(v)).collect();
+        topo.add_loop(&front);
+        topo.add_loop(&back);
+        // front[i] runs v[i] -> v[i + 1], back[3 - i] the other way
+        let edges: Vec<EdgeId> = (0..5)
+            .map(|i| topo.add_edge(front[i], back[(8 - i) % 5]))
+            .collect();
+
+        let chord = |e, v| chord_tangent(&topo, e, v);
+        let mut chain = topo.tangent_continuous_chain(edges[1], chord);
+        if chain[0] != edges[0] {
+            chain.reverse();
+        }
+        assert_eq!(chain, edges[..3]);
+        assert_eq!(
+            topo.tangent_continuous_chain(edges[3], chord),
+            vec![edges[3]]
+        );
+    }
+
+    #[test]
+    fn test_tangent_chain_follows_end_tangents() {
+        // Two quarter circles meeting tangentially at (1, 1), then a
+        // straight edge turning away from the second one at (2, 0)
+        let mut topo = Topology::new();
+        let v: Vec<VertexId> = [(0.0, 0.0), (1.0, 1.0), (2.0, 0.0), (3.0, 0.0)]
+            .iter()
+            .map(|&(x, y)| topo.add_vertex(Point3::new(x, y, 0.0)))
+            .collect();
+        let front: Vec<HalfEdgeId> = v.iter().map(|&v| topo.add_half_edge(v)).collect();
+        let back: Vec<HalfEdgeId> = v.iter().rev().map(|&v| topo.add_half_edge(v)).collect();
+        topo.add_loop(&front);
+        topo.add_loop(&back);
+        let edges: Vec<EdgeId> = (0..4)
+            .map(|i| topo.add_edge(front[i], back[(6 - i) % 4]))
+            .collect();
+
+        // Arcs of the circle of radius 1 about (1, 0), and straight lines
+        let tangent = |e: EdgeId, vertex: VertexId| {
+            let p = topo.vertices[vertex].point;
+            if e == edges[0] || e == edges[1] {
+                Some(Vec3::new(-p.y, p.x - 1.0, 0.0))
+            } else {
+                chord_tangent(&topo, e, vertex)
+            }
+        };
+        let mut chain = topo.tangent_continuous_chain(edges[0], tangent);
+        if chain[0] != edges[0] {
+            chain.reverse();
+        }
+        // The arcs' chords meet at 90°, yet the arcs are tangent
+        assert_eq!(chain, edges[..2]);
+        assert_eq!(
+            topo.tangent_continuous_chain(edges[0], |e, v| chord_tangent(&topo, e, v)),
+            vec![edges[0]]
+        );
+    }
+
+    /// The chord of a straight edge as its tangent.
+    fn chord_tangent(topo: &Topology, edge: EdgeId, _vertex: VertexId) -> Option<Vec3> {
+        let he = topo.edges[edge].half_edge;
+        let twin = topo.half_edges[he].twin?;
+        Some(
+            topo.vertices[topo.half_edges[twin].origin].point
+                - topo.vertices[topo.half_edges[he].origin].point,
+        )
+    }
 }
@@ -4,12 +4,12 @@ use std::collections::HashMap;
 use std::path::Path;
 
 use vcad_ir::{
-    CsgOp, Document, Instance, Joint as VcadJoint, JointKind, MaterialDef, Node, NodeId, PartDef,
-    SceneEntry, Vec3,
+    BoxOrigin, CsgOp, Document, Instance, Joint as VcadJoint, JointKind, MassProperties,
+    MaterialDef, Node, NodeId, PartDef, SceneEntry, Vec3,
 };
 
 use crate::error::UrdfError;
-use crate::types::{Geometry, Joint, Link, Robot};
+use crate::types::{Geometry, Inertial, Joint, Link, Origin, Robot};
 
 /// Read a URDF file from a path.
 ///
@@ -147,19 +147,19 @@ impl<'a> UrdfReader<'a> {
                 roughness: 0.5,
                 density: None,
                 friction: None,
+                opacity: None,
+                emissive: None,
+                base_color_texture: None,
             },
         );
 
         // Add materials from URDF
         for mat in &self.robot.materials {
-            let color = mat
-                .color
-                .as_ref()
-                .map(|c| {
-                    let rgba = c.rgba_vec();
-                    [rgba[0], rgba[1], rgba[2]]
-                })
+            let rgba = mat.color.as_ref().map(|c| c.rgba_vec());
+            let color = rgba
+                .map(|rgba| [rgba[0], rgba[1], rgba[2]])
                 .unwrap_or([0.5, 0.5, 0.5]);
+            let opacity = rgba.map(|rgba| rgba[3]).filter(|&a| a < 1.0);
 
             let mat_def = MaterialDef {
                 name: mat.name.clone(),
@@ -168,6 +168,9 @@ impl<'a> UrdfReader<'a> {
                 roughness: 0.5,
                 density: None,
                 friction: None,
+                opacity,
+                emissive: None,
+                base_color_texture: None,
             };
 
             doc.materials.insert(mat.name.clone(), mat_def);
@@ -184,35 +187,71 @@ impl<'a> UrdfReader<'a> {
     ) -> Result<(PartDef, Vec<(NodeId, Node)>), UrdfError> {
         let mut nodes = Vec::new();
 
-        // Get geometry from visual or collision
-        let (geom, origin) = if let Some(visual) = &link.visual {
-            (&visual.geometry, visual.origin.as_ref())
-        } else if let Some(collision) = &link.collision {
-            (&collision.geometry, collision.origin.as_ref())
-        } else {
-            // Link with no geometry - create empty cube placeholder
-            let node_id = self.alloc_node_id();
-            nodes.push((
-                node_id,
-                Node {
-                    id: node_id,
-                    name: Some(link.name.clone()),
-                    op: CsgOp::Cube {
-                        size: Vec3::new(0.01, 0.01, 0.01), // 1cm placeholder
+        let visual_root = match &link.visual {
+            Some(visual) => Some(self.geometry_root(
+                &link.name,
+                &visual.geometry,
+                visual.origin.as_ref(),
+                &mut nodes,
+            )?),
+            None => None,
+        };
+        let collision_root = match &link.collision {
+            Some(collision) => Some(self.geometry_root(
+                &format!("{}_collision", link.name),
+                &collision.geometry,
+                collision.origin.as_ref(),
+                &mut nodes,
+            )?),
+            None => None,
+        };
+
+        // The part's main root is the visual geometry; a separate collision
+        // root is only kept when the link has both.
+        let (root, collision_root) = match (visual_root, collision_root) {
+            (Some(visual), collision) => (visual, collision),
+            (None, Some(collision)) => (collision, None),
+            (None, None) => {
+                // Link with no geometry - create empty cube placeholder
+                let node_id = self.alloc_node_id();
+                nodes.push((
+                    node_id,
+                    Node {
+                        id: node_id,
+                        name: Some(link.name.clone()),
+                        op: CsgOp::Cube {
+                            size: Vec3::new(0.01, 0.01, 0.01), // 1cm placeholder
+                            origin: BoxOrigin::Center,
+                        },
+                        attributes: None,
                     },
-                },
-            ));
-            return Ok((
-                PartDef {
-                    id: format!("part_{}", link.name),
-                    name: Some(link.name.clone()),
-                    root: node_id,
-                    default_material: Some("default".to_string()),
-                },
-                nodes,
-            ));
+                ));
+                (node_id, None)
+            }
         };
 
+        Ok((
+            PartDef {
+                id: format!("part_{}", link.name),
+                name: Some(link.name.clone()),
+                root,
+                collision_root,
+                mass_properties: link.inertial.as_ref().map(inertial_to_mass_properties),
+                default_material: Some("default".to_string()),
+            },
+            nodes,
+        ))
+    }
+
+    /// Convert one visual or collision element to nodes, returning the root.
+    /// Node names are prefixed with `name`.
+    fn geometry_root(
+        &mut self,
+        name: &str,
+        geom: &Geometry,
+        origin: Option<&Origin>,
+        nodes: &mut Vec<(NodeId, Node)>,
+    ) -> Result<NodeId, UrdfError> {
         // Create geometry node
         let geom_node_id = self.alloc_node_id();
         let geom_op = self.geometry_to_csg(geom)?;
@@ -220,98 +259,75 @@ impl<'a> UrdfReader<'a> {
             geom_node_id,
             Node {
                 id: geom_node_id,
-                name: Some(format!("{}_geom", link.name)),
+                name: Some(format!("{}_geom", name)),
                 op: geom_op,
+                attributes: None,
             },
         ));
 
         // Apply origin transform if present
-        let root_id = if let Some(origin) = origin {
-            let xyz = origin.xyz_vec();
-            let rpy = origin.rpy_vec();
+        let Some(origin) = origin else {
+            return Ok(geom_node_id);
+        };
+        let xyz = origin.xyz_vec();
+        let rpy = origin.rpy_vec();
 
-            // URDF uses meters, vcad uses mm
-            let xyz_mm = [xyz[0] * 1000.0, xyz[1] * 1000.0, xyz[2] * 1000.0];
+        // URDF uses meters, vcad uses mm
+        let xyz_mm = [xyz[0] * 1000.0, xyz[1] * 1000.0, xyz[2] * 1000.0];
 
-            // URDF uses radians, vcad uses degrees
-            let rpy_deg = [
-                rpy[0].to_degrees(),
-                rpy[1].to_degrees(),
-                rpy[2].to_degrees(),
-            ];
+        // URDF uses radians, vcad uses degrees
+        let rpy_deg = [
+            rpy[0].to_degrees(),
+            rpy[1].to_degrees(),
+            rpy[2].to_degrees(),
+        ];
 
-            let has_translation = xyz_mm.iter().any(|v| v.abs() > 1e-6);
-            let has_rotation = rpy_deg.iter().any(|v| v.abs() > 1e-6);
+        let has_translation = xyz_mm.iter().any(|v| v.abs() > 1e-6);
+        let has_rotation = rpy_deg.iter().any(|v| v.abs() > 1e-6);
 
-            if has_rotation {
-                let rotate_id = self.alloc_node_id();
-                nodes.push((
-                    rotate_id,
-                    Node {
-                        id: rotate_id,
-                        name: Some(format!("{}_rotate", link.name)),
-                        op: CsgOp::Rotate {
-                            child: geom_node_id,
-                            angles: Vec3::new(rpy_deg[0], rpy_deg[1], rpy_deg[2]),
-                        },
+        let mut root = geom_node_id;
+        if has_rotation {
+            let rotate_id = self.alloc_node_id();
+            nodes.push((
+                rotate_id,
+                Node {
+                    id: rotate_id,
+                    name: Some(format!("{}_rotate", name)),
+                    op: CsgOp::Rotate {
+                        child: root,
+                        angles: Vec3::new(rpy_deg[0], rpy_deg[1], rpy_deg[2]),
                     },
-                ));
-
-                if has_translation {
-                    let translate_id = self.alloc_node_id();
-                    nodes.push((
-                        translate_id,
-                        Node {
-                            id: translate_id,
-                            name: Some(format!("{}_translate", link.name)),
-                            op: CsgOp::Translate {
-                                child: rotate_id,
-                                offset: Vec3::new(xyz_mm[0], xyz_mm[1], xyz_mm[2]),
-                            },
-                        },
-                    ));
-                    translate_id
-                } else {
-                    rotate_id
-                }
-            } else if has_translation {
-                let translate_id = self.alloc_node_id();
-                nodes.push((
-                    translate_id,
-                    Node {
-                        id: translate_id,
-                        name: Some(format!("{}_translate", link.name)),
-                        op: CsgOp::Translate {
-                            child: geom_node_id,
-                            offset: Vec3::new(xyz_mm[0], xyz_mm[1], xyz_mm[2]),
-                        },
+                    attributes: None,
+                },
+            ));
+            root = rotate_id;
+        }
+        if has_translation {
+            let translate_id = self.alloc_node_id();
+            nodes.push((
+                translate_id,
+                Node {
+                    id: translate_id,
+                    name: Some(format!("{}_translate", name)),
+                    op: CsgOp::Translate {
+                        child: root,
+                        offset: Vec3::new(xyz_mm[0], xyz_mm[1], xyz_mm[2]),
                     },
-                ));
-                translate_id
-            } else {
-                geom_node_id
-            }
-        } else {
-            geom_node_id
-        };
-
-        Ok((
-            PartDef {
-                id: format!("part_{}", link.name),
-                name: Some(link.name.clone()),
-                root: root_id,
-                default_material: Some("default".to_string()),
-            },
-            nodes,
-        ))
+                    attributes: None,
+                },
+            ));
+            root = translate_id;
+        }
+        Ok(root)
     }
 
     fn geometry_to_csg(&self, geom: &Geometry) -> Result<CsgOp, UrdfError> {
         if let Some(box_geom) = &geom.box_geom {
             let size = box_geom.size_vec();
-            // URDF uses meters, vcad uses mm
+            // URDF uses meters, vcad uses mm; URDF boxes are centered
             Ok(CsgOp::Cube {
                 size: Vec3::new(size[0] * 1000.0, size[1] * 1000.0, size[2] * 1000.0),
+                origin: BoxOrigin::Center,
             })
         } else if let Some(cyl) = &geom.cylinder {
             // URDF cylinder is along Z axis, centered
@@ -448,6 +464,55 @@ impl<'a> UrdfReader<'a> {
     }
 }
 
+/// Convert a link's `<inertial>` to mass properties in the link frame.
+///
+/// The inertia tensor is rotated out of the inertial frame, and converted
+/// from kg·m² to kg·mm² along with the center of mass from m to mm.
+fn inertial_to_mass_properties(inertial: &Inertial) -> MassProperties {
+    let (xyz, rpy) = inertial
+        .origin
+        .as_ref()
+        .map_or(([0.0; 3], [0.0; 3]), |o| (o.xyz_vec(), o.rpy_vec()));
+
+    let i = &inertial.inertia;
+    let local = [
+        [i.ixx, i.ixy, i.ixz],
+        [i.ixy, i.iyy, i.iyz],
+        [i.ixz, i.iyz, i.izz],
+    ];
+
+    // URDF frames rotate by roll about x, then pitch about y, then yaw
+    // about z: R = Rz(yaw) Ry(pitch) Rx(roll)
+    let (sr, cr) = rpy[0].sin_cos();
+    let (sp, cp) = rpy[1].sin_cos();
+    let (sy, cy) = rpy[2].sin_cos();
+    let r = [
+        [cy * cp, cy * sp * sr - sy * cr, cy * sp * cr + sy * sr],
+        [sy * cp, sy * sp * sr + cy * cr, sy * sp * cr - cy * sr],
+        [-sp, cp * sr, cp * cr],
+    ];
+
+    // R I Rᵀ
+    let mut inertia = [[0.0; 3]; 3];
+    for (a, row) in inertia.iter_mut().enumerate() {
+        for (b, value) in row.iter_mut().enumerate() {
+            let mut sum = 0.0;
+            for (j, local_row) in local.iter().enumerate() {
+                for (k, &l) in local_row.iter().enumerate() {
+                    sum += r[a][j] * l * r[b][k];
+                }
+            }
+            *value = sum * 1e6;
+        }
+    }
+
+    MassProperties {
+        mass: inertial.mass.value,
+        center_of_mass: Vec3::new(xyz[0] * 1000.0, xyz[1] * 1000.0, xyz[2] * 1000.0),
+        inertia,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -597,11 +662,81 @@ mod tests {
             matches!(n.op, CsgOp::Cube { .. })
         }).unwrap();
 
-        if let CsgOp::Cube { size } = &box_node.op {
+        if let CsgOp::Cube { size, origin } = &box_node.op {
+            assert_eq!(*origin, BoxOrigin::Center);
             // 0.1m = 100mm
             assert!((size.x - 100.0).abs() < 0.1);
             assert!((size.y - 200.0).abs() < 0.1);
             assert!((size.z - 300.0).abs() < 0.1);
         }
     }
+
+    #[test]
+    fn test_inertial_conversion() {
+        let urdf = r#"<?xml version="1.0"?>
+<robot name="weighted">
+    <link name="base">
+        <inertial>
+            <origin xyz="0 0 0.05" rpy="0 0 1.5707963267948966"/>
+            <mass value="2.5"/>
+            <inertia ixx="0.1" ixy="0" ixz="0" iyy="0.2" iyz="0" izz="0.3"/>
+        </inertial>
+    </link>
+    <link name="bare"/>
+</robot>"#;
+
+        let doc = read_urdf_from_str(urdf).unwrap();
+        let part_defs = doc.part_defs.unwrap();
+        assert!(part_defs["part_bare"].mass_properties.is_none());
+
+        let props = part_defs["part_base"].mass_properties.expect("inertial");
+        assert_eq!(props.mass, 2.5);
+        assert!((props.center_of_mass.z - 50.0).abs() < 1e-9);
+        // A quarter turn about z swaps the x and y moments; kg·m² to kg·mm²
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+        assert!(close(props.inertia[0][0], 0.2e6), "{:?}", props.inertia);
+        assert!(close(props.inertia[1][1], 0.1e6), "{:?}", props.inertia);
+        assert!(close(props.inertia[2][2], 0.3e6), "{:?}", props.inertia);
+        assert!(close(props.inertia[0][1], 0.0));
+    }
+
+    #[test]
+    fn test_pose_two_link_arm() {
+        let urdf = r#"<?xml version="1.0"?>
+<robot name="two_link_arm">
+    <link name="base"/>
+    <link name="upper_arm"/>
+    <link name="forearm"/>
+    <joint name="shoulder" type="revolute">
+        <parent link="base"/>
+        <child link="upper_arm"/>
+        <origin xyz="0 0 0.05"/>
+        <axis xyz="0 0 1"/>
+        <limit lower="-3.14" upper="3.14" effort="10" velocity="1"/>
+    </joint>
+    <joint name="elbow" type="revolute">
+        <parent link="upper_arm"/>
+        <child link="forearm"/>
+        <origin xyz="0.1 0 0"/>
+        <axis xyz="0 0 1"/>
+        <limit lower="-3.14" upper="3.14" effort="10" velocity="1"/>
+    </joint>
+</robot>"#;
+
+        let mut doc = read_urdf_from_str(urdf).unwrap();
+        for joint in doc.joints.as_mut().unwrap() {
+            if joint.id == "shoulder" {
+                joint.state = 90.0;
+            }
+        }
+
+        let poses = vcad_ir::pose_instances(&doc).unwrap();
+
+        // Shoulder at z=50mm; the 100mm upper arm swings from +X to +Y.
+        let t = poses["forearm_inst"].translation;
+        assert!(t.x.abs() < 1e-6);
+        assert!((t.y - 100.0).abs() < 1e-6);
+        assert!((t.z - 50.0).abs() < 1e-6);
+        assert!((poses["forearm_inst"].rotation.z - 90.0).abs() < 1e-6);
+    }
 }
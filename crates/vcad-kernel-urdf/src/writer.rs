@@ -3,12 +3,16 @@
 use std::io::Write;
 use std::path::Path;
 
-use vcad_ir::{CsgOp, Document, JointKind};
+use vcad_ir::{
+    BoxOrigin, CsgOp, Document, JointKind, MassProperties, NodeId, SplitKeep, VolumeProperties,
+};
+use vcad_kernel::Solid;
 
 use crate::error::UrdfError;
 use crate::types::{
-    Axis, BoxGeom, ChildLink, Color, CylinderGeom, Geometry, Joint, Limit, Link, Material,
-    MaterialRef, MeshGeom, Origin, ParentLink, Robot, SphereGeom, Visual,
+    Axis, BoxGeom, ChildLink, Collision, Color, CylinderGeom, Geometry, Inertia, Inertial, Joint,
+    Limit, Link, Mass, Material, MaterialRef, MeshGeom, Origin, ParentLink, Robot, SphereGeom,
+    Visual,
 };
 
 /// Write a vcad Document to a URDF file.
@@ -137,6 +141,34 @@ impl<'a> UrdfWriter<'a> {
             texture: None,
         });
 
+        // Explicit mass properties win over those from the material density
+        let inertial = match &part_def.mass_properties {
+            Some(props) => Some(mass_properties_to_inertial(props)),
+            None => self.link_inertial(
+                part_def.root,
+                part_def.default_material.as_deref(),
+                &geometry,
+                origin.as_ref(),
+            ),
+        };
+
+        // Collision falls back to the visual geometry
+        let collision = match part_def.collision_root {
+            Some(collision_root) => {
+                let (geometry, origin) = self.node_to_geometry(collision_root)?;
+                Collision {
+                    name: None,
+                    origin,
+                    geometry,
+                }
+            }
+            None => Collision {
+                name: None,
+                origin: origin.clone(),
+                geometry: geometry.clone(),
+            },
+        };
+
         Ok(Link {
             name,
             visual: Some(Visual {
@@ -145,8 +177,8 @@ impl<'a> UrdfWriter<'a> {
                 geometry,
                 material: material_ref,
             }),
-            collision: None,
-            inertial: None,
+            collision: Some(collision),
+            inertial,
         })
     }
 
@@ -172,6 +204,14 @@ impl<'a> UrdfWriter<'a> {
             texture: None,
         });
 
+        let inertial =
+            self.link_inertial(entry.root, Some(&entry.material), &geometry, origin.as_ref());
+        let collision = Collision {
+            name: None,
+            origin: origin.clone(),
+            geometry: geometry.clone(),
+        };
+
         Ok(Link {
             name,
             visual: Some(Visual {
@@ -180,11 +220,205 @@ impl<'a> UrdfWriter<'a> {
                 geometry,
                 material: material_ref,
             }),
-            collision: None,
-            inertial: None,
+            collision: Some(collision),
+            inertial,
         })
     }
 
+    /// Compute the `<inertial>` element for a link from its material density.
+    ///
+    /// Links whose geometry is a bare box, cylinder or sphere (possibly moved
+    /// or rotated) use the analytic tensor in the visual frame. Anything else
+    /// is evaluated and integrated from the tessellated mesh of each of its
+    /// bodies. Returns `None`
+    /// when the material has no density or the geometry cannot be evaluated.
+    fn link_inertial(
+        &self,
+        root: NodeId,
+        material: Option<&str>,
+        geometry: &Geometry,
+        origin: Option<&Origin>,
+    ) -> Option<Inertial> {
+        let density = self.doc.materials.get(material?)?.density?;
+
+        if self.is_placed_primitive(root) {
+            if let Some((mass, inertia)) = primitive_inertia(geometry, density) {
+                return Some(Inertial {
+                    origin: origin.cloned(),
+                    mass: Mass { value: mass },
+                    inertia,
+                });
+            }
+        }
+
+        let bodies = self.evaluate_bodies(root)?;
+        let props = MassProperties::combine(bodies.iter().map(|solid| {
+            let [x, y, z] = solid.center_of_mass();
+            let volume = VolumeProperties {
+                volume: solid.volume(),
+                centroid: vcad_ir::Vec3::new(x, y, z),
+                inertia: solid.inertia_tensor(),
+            };
+            MassProperties::from_volume(&volume, density)
+        }));
+        if props.mass <= 0.0 {
+            return None;
+        }
+        Some(mass_properties_to_inertial(&props))
+    }
+
+    /// Whether `node_id` is a box, cylinder or sphere under only placement
+    /// transforms and tags, so its exported geometry is exact.
+    fn is_placed_primitive(&self, node_id: NodeId) -> bool {
+        let mut current = node_id;
+        for _ in 0..=self.doc.nodes.len() {
+            let Some(node) = self.doc.nodes.get(&current) else {
+                return false;
+            };
+            match &node.op {
+                CsgOp::Cube { .. } | CsgOp::Cylinder { .. } | CsgOp::Sphere { .. } => {
+                    return true
+                }
+                CsgOp::Translate { child, .. }
+                | CsgOp::Rotate { child, .. }
+                | CsgOp::Tag { child, .. } => current = *child,
+                _ => return false,
+            }
+        }
+        false
+    }
+
+    /// Evaluate a node to the separate bodies it produces.
+    ///
+    /// A split keeping both halves gives them as two bodies, also through
+    /// transforms and tags above it; any other node gives the single solid
+    /// from [`Self::evaluate_solid`].
+    fn evaluate_bodies(&self, node_id: NodeId) -> Option<Vec<Solid>> {
+        use vcad_kernel::vcad_kernel_math::{Point3, Transform, Vec3};
+
+        let node = self.doc.nodes.get(&node_id)?;
+        match &node.op {
+            CsgOp::SplitByPlane {
+                child,
+                plane_origin,
+                plane_normal,
+                keep: SplitKeep::Both,
+            } => {
+                let (positive, negative) = self.evaluate_solid(*child)?.split_by_plane(
+                    Point3::new(plane_origin.x, plane_origin.y, plane_origin.z),
+                    Vec3::new(plane_normal.x, plane_normal.y, plane_normal.z),
+                );
+                Some(vec![positive, negative])
+            }
+            CsgOp::Translate { .. } | CsgOp::Rotate { .. } | CsgOp::Scale { .. } => {
+                let (base, transform) = self.doc.fold_transforms(node_id);
+                let transform = Transform::from_rows(&transform.matrix);
+                let bodies = self.evaluate_bodies(base)?;
+                Some(bodies.iter().map(|s| s.transform(&transform)).collect())
+            }
+            CsgOp::Tag { child, .. } => self.evaluate_bodies(*child),
+            _ => Some(vec![self.evaluate_solid(node_id)?]),
+        }
+    }
+
+    /// Evaluate a node to a kernel solid for mass properties.
+    ///
+    /// A split keeping both halves is two bodies rather than one solid, so
+    /// gives `None`; see [`Self::evaluate_bodies`].
+    fn evaluate_solid(&self, node_id: NodeId) -> Option<Solid> {
+        use vcad_kernel::vcad_kernel_math::{Point3, Transform, Vec3};
+
+        let node = self.doc.nodes.get(&node_id)?;
+        match &node.op {
+            CsgOp::Empty => Some(Solid::empty()),
+            CsgOp::Cube { size, origin } => {
+                Some(Solid::cube_at(size.x, size.y, size.z, (*origin).into()))
+            }
+            CsgOp::Cylinder {
+                radius,
+                height,
+                segments,
+            } => Some(Solid::cylinder(*radius, *height, *segments)),
+            CsgOp::Sphere { radius, segments } => Some(Solid::sphere(*radius, *segments)),
+            CsgOp::Cone {
+                radius_bottom,
+                radius_top,
+                height,
+                segments,
+            } => Some(Solid::cone(*radius_bottom, *radius_top, *height, *segments)),
+            CsgOp::Union { left, right } => {
+                Some(self.evaluate_solid(*left)?.union(&self.evaluate_solid(*right)?))
+            }
+            CsgOp::Difference { left, right } => {
+                Some(self.evaluate_solid(*left)?.difference(&self.evaluate_solid(*right)?))
+            }
+            CsgOp::Intersection { left, right } => {
+                Some(self.evaluate_solid(*left)?.intersection(&self.evaluate_solid(*right)?))
+            }
+            CsgOp::Translate { .. } | CsgOp::Rotate { .. } | CsgOp::Scale { .. } => {
+                let (base, transform) = self.doc.fold_transforms(node_id);
+                let solid = self.evaluate_solid(base)?;
+                Some(solid.transform(&Transform::from_rows(&transform.matrix)))
+            }
+            CsgOp::LinearPattern {
+                child,
+                direction,
+                count,
+                spacing,
+            } => Some(self.evaluate_solid(*child)?.linear_pattern(
+                Vec3::new(direction.x, direction.y, direction.z),
+                *count,
+                *spacing,
+            )),
+            CsgOp::CircularPattern {
+                child,
+                axis_origin,
+                axis_dir,
+                count,
+                angle_deg,
+            } => Some(self.evaluate_solid(*child)?.circular_pattern(
+                Point3::new(axis_origin.x, axis_origin.y, axis_origin.z),
+                Vec3::new(axis_dir.x, axis_dir.y, axis_dir.z),
+                *count,
+                *angle_deg,
+            )),
+            CsgOp::Shell { child, thickness } => {
+                Some(self.evaluate_solid(*child)?.shell(*thickness))
+            }
+            CsgOp::Fillet { child, radius } => Some(self.evaluate_solid(*child)?.fillet(*radius)),
+            CsgOp::Chamfer { child, distance } => {
+                Some(self.evaluate_solid(*child)?.chamfer(*distance))
+            }
+            CsgOp::SplitByPlane {
+                child,
+                plane_origin,
+                plane_normal,
+                keep,
+            } => {
+                let (positive, negative) = self.evaluate_solid(*child)?.split_by_plane(
+                    Point3::new(plane_origin.x, plane_origin.y, plane_origin.z),
+                    Vec3::new(plane_normal.x, plane_normal.y, plane_normal.z),
+                );
+                match keep {
+                    SplitKeep::Positive => Some(positive),
+                    SplitKeep::Negative => Some(negative),
+                    SplitKeep::Both => None,
+                }
+            }
+            CsgOp::Tag { child, .. } => self.evaluate_solid(*child),
+            CsgOp::StepImport { path } => Solid::from_step(path).ok(),
+            CsgOp::Sketch2D { .. }
+            | CsgOp::Text2D { .. }
+            | CsgOp::Text { .. }
+            | CsgOp::ProjectToSketch { .. }
+            | CsgOp::SvgProfile { .. }
+            | CsgOp::Extrude { .. }
+            | CsgOp::Revolve { .. }
+            | CsgOp::Loft { .. }
+            | CsgOp::Sweep { .. } => None,
+        }
+    }
+
     fn node_to_geometry(
         &self,
         node_id: vcad_ir::NodeId,
@@ -194,7 +428,7 @@ impl<'a> UrdfWriter<'a> {
         })?;
 
         match &node.op {
-            CsgOp::Cube { size } => {
+            CsgOp::Cube { size, origin } => {
                 // vcad uses mm, URDF uses meters
                 let geometry = Geometry {
                     box_geom: Some(BoxGeom {
@@ -209,7 +443,21 @@ impl<'a> UrdfWriter<'a> {
                     sphere: None,
                     mesh: None,
                 };
-                Ok((geometry, None))
+                // URDF boxes are centered, so a corner-aligned cube is
+                // shifted by half its size
+                let origin = match origin {
+                    BoxOrigin::Corner => Some(Origin {
+                        xyz: Some(format!(
+                            "{} {} {}",
+                            size.x / 2000.0,
+                            size.y / 2000.0,
+                            size.z / 2000.0
+                        )),
+                        rpy: None,
+                    }),
+                    BoxOrigin::Center => None,
+                };
+                Ok((geometry, origin))
             }
             CsgOp::Cylinder {
                 radius, height, ..
@@ -254,15 +502,17 @@ impl<'a> UrdfWriter<'a> {
                 Ok((geometry, None))
             }
             CsgOp::Translate { child, offset } => {
-                let (geometry, _) = self.node_to_geometry(*child)?;
+                let (geometry, existing_origin) = self.node_to_geometry(*child)?;
+                let existing = existing_origin.unwrap_or_default();
+                let [x, y, z] = existing.xyz_vec();
                 let origin = Some(Origin {
                     xyz: Some(format!(
                         "{} {} {}",
-                        offset.x / 1000.0,
-                        offset.y / 1000.0,
-                        offset.z / 1000.0
+                        x + offset.x / 1000.0,
+                        y + offset.y / 1000.0,
+                        z + offset.z / 1000.0
                     )),
-                    rpy: None,
+                    rpy: existing.rpy,
                 });
                 Ok((geometry, origin))
             }
@@ -323,14 +573,21 @@ impl<'a> UrdfWriter<'a> {
             | CsgOp::CircularPattern { child, .. }
             | CsgOp::Shell { child, .. }
             | CsgOp::Fillet { child, .. }
-            | CsgOp::Chamfer { child, .. } => {
-                // For patterns/shell/fillet/chamfer, export base geometry
+            | CsgOp::Chamfer { child, .. }
+            | CsgOp::SplitByPlane { child, .. } => {
+                // For patterns/shell/fillet/chamfer/split, export base geometry
                 self.node_to_geometry(*child)
             }
+            CsgOp::Tag { child, .. } => self.node_to_geometry(*child),
             CsgOp::Sketch2D { .. }
             | CsgOp::Text2D { .. }
+            | CsgOp::Text { .. }
+            | CsgOp::ProjectToSketch { .. }
+            | CsgOp::SvgProfile { .. }
             | CsgOp::Extrude { .. }
-            | CsgOp::Revolve { .. } => {
+            | CsgOp::Revolve { .. }
+            | CsgOp::Loft { .. }
+            | CsgOp::Sweep { .. } => {
                 // Sketch-based geometry - approximate as box
                 Err(UrdfError::Conversion(
                     "Sketch-based geometry cannot be exported to URDF directly".to_string(),
@@ -428,6 +685,75 @@ impl<'a> UrdfWriter<'a> {
     }
 }
 
+/// The `<inertial>` element for explicit mass properties, converted from
+/// mm and kg·mm² to m and kg·m².
+fn mass_properties_to_inertial(props: &MassProperties) -> Inertial {
+    let c = &props.center_of_mass;
+    let i = props.inertia.map(|row| row.map(|v| v * 1e-6));
+    Inertial {
+        origin: Some(Origin {
+            xyz: Some(format!(
+                "{} {} {}",
+                c.x / 1000.0,
+                c.y / 1000.0,
+                c.z / 1000.0
+            )),
+            rpy: None,
+        }),
+        mass: Mass { value: props.mass },
+        inertia: Inertia {
+            ixx: i[0][0],
+            ixy: i[0][1],
+            ixz: i[0][2],
+            iyy: i[1][1],
+            iyz: i[1][2],
+            izz: i[2][2],
+        },
+    }
+}
+
+/// Mass and analytic inertia of an exported box, cylinder or sphere.
+///
+/// Dimensions are read back from the URDF geometry, so they are already in
+/// meters. URDF primitives are centered on their frame with cylinders along Z.
+fn primitive_inertia(geometry: &Geometry, density: f64) -> Option<(f64, Inertia)> {
+    let diagonal = |mass: f64, ixx: f64, iyy: f64, izz: f64| {
+        (
+            mass,
+            Inertia {
+                ixx,
+                ixy: 0.0,
+                ixz: 0.0,
+                iyy,
+                iyz: 0.0,
+                izz,
+            },
+        )
+    };
+    if let Some(box_geom) = &geometry.box_geom {
+        let [x, y, z] = box_geom.size_vec();
+        let m = density * x * y * z;
+        Some(diagonal(
+            m,
+            m / 12.0 * (y * y + z * z),
+            m / 12.0 * (x * x + z * z),
+            m / 12.0 * (x * x + y * y),
+        ))
+    } else if let Some(cyl) = &geometry.cylinder {
+        let (r, h) = (cyl.radius, cyl.length);
+        let m = density * std::f64::consts::PI * r * r * h;
+        let radial = m / 12.0 * (3.0 * r * r + h * h);
+        Some(diagonal(m, radial, radial, m * r * r / 2.0))
+    } else if let Some(sphere) = &geometry.sphere {
+        let r = sphere.radius;
+        let m = density * 4.0 / 3.0 * std::f64::consts::PI * r * r * r;
+        let i = 0.4 * m * r * r;
+        Some(diagonal(m, i, i, i))
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -480,6 +806,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_roundtrip_visual_and_collision() {
+        let urdf = r#"<?xml version="1.0"?>
+<robot name="gripper">
+    <link name="finger">
+        <visual>
+            <geometry>
+                <mesh filename="meshes/finger_detailed.stl"/>
+            </geometry>
+        </visual>
+        <collision>
+            <origin xyz="0 0 0.02"/>
+            <geometry>
+                <box size="0.01 0.02 0.04"/>
+            </geometry>
+        </collision>
+    </link>
+</robot>"#;
+
+        let check = |doc: &Document| {
+            let part_defs = doc.part_defs.as_ref().unwrap();
+            let part = &part_defs["part_finger"];
+            assert!(matches!(
+                &doc.nodes[&part.root].op,
+                CsgOp::StepImport { path } if path == "meshes/finger_detailed.stl"
+            ));
+            let collision = part.collision_root.expect("collision root");
+            let CsgOp::Translate { child, offset } = &doc.nodes[&collision].op else {
+                panic!("expected translated collision box");
+            };
+            assert!((offset.z - 20.0).abs() < 1e-9);
+            assert!(matches!(doc.nodes[child].op, CsgOp::Cube { .. }));
+        };
+
+        let doc = read_urdf_from_str(urdf).unwrap();
+        check(&doc);
+
+        let output = write_urdf_to_string(&doc).unwrap();
+        assert!(output.contains("finger_detailed.stl"));
+        assert!(output.contains("<collision>"));
+
+        let doc2 = read_urdf_from_str(&output).unwrap();
+        check(&doc2);
+
+        // The compact form keeps the collision root too
+        let compact = vcad_ir::compact::to_compact(&doc).unwrap();
+        check(&vcad_ir::compact::from_compact(&compact).unwrap());
+    }
+
+    #[test]
+    fn test_roundtrip_inertial() {
+        let urdf = r#"<?xml version="1.0"?>
+<robot name="weighted">
+    <link name="base">
+        <visual>
+            <geometry><box size="0.1 0.1 0.1"/></geometry>
+        </visual>
+        <inertial>
+            <origin xyz="0.01 0 0.02"/>
+            <mass value="3.5"/>
+            <inertia ixx="0.01" ixy="0.001" ixz="0" iyy="0.02" iyz="0" izz="0.03"/>
+        </inertial>
+    </link>
+</robot>"#;
+
+        // The link's material has no density, so the inertial can only come
+        // from the imported mass properties
+        let doc = read_urdf_from_str(urdf).unwrap();
+        let robot = UrdfWriter::new(&doc).to_robot().unwrap();
+        let inertial = robot.links[0].inertial.as_ref().expect("inertial");
+
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+        assert_eq!(inertial.mass.value, 3.5);
+        let com = inertial.origin.as_ref().unwrap().xyz_vec();
+        assert!(close(com[0], 0.01) && close(com[1], 0.0) && close(com[2], 0.02));
+        assert!(close(inertial.inertia.ixx, 0.01));
+        assert!(close(inertial.inertia.ixy, 0.001));
+        assert!(close(inertial.inertia.iyy, 0.02));
+        assert!(close(inertial.inertia.izz, 0.03));
+    }
+
+    #[test]
+    fn test_collision_reuses_visual() {
+        let urdf = r#"<?xml version="1.0"?>
+<robot name="single">
+    <link name="base">
+        <visual>
+            <geometry><sphere radius="0.1"/></geometry>
+        </visual>
+    </link>
+</robot>"#;
+
+        let doc = read_urdf_from_str(urdf).unwrap();
+        assert!(doc.part_defs.as_ref().unwrap()["part_base"]
+            .collision_root
+            .is_none());
+
+        let robot = UrdfWriter::new(&doc).to_robot().unwrap();
+        let link = &robot.links[0];
+        let visual = link.visual.as_ref().unwrap();
+        let collision = link.collision.as_ref().unwrap();
+        assert_eq!(
+            visual.geometry.sphere.as_ref().map(|s| s.radius),
+            collision.geometry.sphere.as_ref().map(|s| s.radius)
+        );
+    }
+
     #[test]
     fn test_write_box_dimensions() {
         let mut doc = Document::new();
@@ -492,7 +925,9 @@ mod tests {
                 name: Some("test_box".to_string()),
                 op: CsgOp::Cube {
                     size: vcad_ir::Vec3::new(100.0, 200.0, 300.0),
+                    origin: BoxOrigin::Corner,
                 },
+                attributes: None,
             },
         );
 
@@ -507,4 +942,229 @@ mod tests {
         // Check that dimensions are in meters (0.1, 0.2, 0.3)
         assert!(urdf.contains("0.1 0.2 0.3") || urdf.contains("size=\"0.1 0.2 0.3\""));
     }
+
+    #[test]
+    fn test_corner_box_is_offset() {
+        let mut doc = Document::new();
+        let cube = |origin| CsgOp::Cube {
+            size: vcad_ir::Vec3::new(100.0, 200.0, 300.0),
+            origin,
+        };
+        let ops = [
+            (1, cube(BoxOrigin::Corner)),
+            (2, cube(BoxOrigin::Center)),
+            (
+                3,
+                CsgOp::Translate {
+                    child: 1,
+                    offset: vcad_ir::Vec3::new(1000.0, 0.0, 0.0),
+                },
+            ),
+        ];
+        for (id, op) in ops {
+            doc.nodes.insert(
+                id,
+                vcad_ir::Node {
+                    id,
+                    name: None,
+                    op,
+                    attributes: None,
+                },
+            );
+        }
+
+        let writer = UrdfWriter::new(&doc);
+        let xyz = |id| writer.node_to_geometry(id).unwrap().1.map(|o| o.xyz_vec());
+        // URDF boxes are centered on their origin
+        assert_eq!(xyz(1), Some([0.05, 0.1, 0.15]));
+        assert_eq!(xyz(2), None);
+        assert_eq!(xyz(3), Some([1.05, 0.1, 0.15]));
+    }
+
+    #[test]
+    fn test_box_inertial_from_density() {
+        let mut doc = Document::new();
+        doc.nodes.insert(
+            1,
+            vcad_ir::Node {
+                id: 1,
+                name: Some("steel_box".to_string()),
+                op: CsgOp::Cube {
+                    size: vcad_ir::Vec3::new(100.0, 200.0, 300.0),
+                    origin: BoxOrigin::Corner,
+                },
+                attributes: None,
+            },
+        );
+        doc.materials.insert(
+            "steel".to_string(),
+            vcad_ir::MaterialDef {
+                name: "steel".to_string(),
+                color: [0.6, 0.6, 0.6],
+                metallic: 1.0,
+                roughness: 0.3,
+                density: Some(7850.0),
+                friction: None,
+                opacity: None,
+                emissive: None,
+                base_color_texture: None,
+            },
+        );
+        doc.roots.push(vcad_ir::SceneEntry {
+            root: 1,
+            material: "steel".to_string(),
+            visible: None,
+        });
+
+        let urdf = write_urdf_to_string(&doc).unwrap();
+        let robot: Robot = quick_xml::de::from_str(&urdf).unwrap();
+        let inertial = robot.links[0].inertial.as_ref().expect("inertial");
+
+        let (x, y, z) = (0.1, 0.2, 0.3);
+        let mass = 7850.0 * x * y * z;
+        assert!((inertial.mass.value - mass).abs() < 1e-9 * mass);
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9 * b;
+        assert!(close(inertial.inertia.ixx, mass / 12.0 * (y * y + z * z)));
+        assert!(close(inertial.inertia.iyy, mass / 12.0 * (x * x + z * z)));
+        assert!(close(inertial.inertia.izz, mass / 12.0 * (x * x + y * y)));
+        assert_eq!(inertial.inertia.ixy, 0.0);
+
+        // Without a density the link carries no inertial.
+        doc.materials.get_mut("steel").unwrap().density = None;
+        let urdf = write_urdf_to_string(&doc).unwrap();
+        assert!(!urdf.contains("<inertial"));
+    }
+
+    #[test]
+    fn test_boolean_inertial_from_mesh() {
+        let mut doc = Document::new();
+        let cube = |id| vcad_ir::Node {
+            id,
+            name: None,
+            op: CsgOp::Cube {
+                size: vcad_ir::Vec3::new(100.0, 100.0, 100.0),
+                origin: BoxOrigin::Corner,
+            },
+            attributes: None,
+        };
+        doc.nodes.insert(1, cube(1));
+        doc.nodes.insert(2, cube(2));
+        doc.nodes.insert(
+            3,
+            vcad_ir::Node {
+                id: 3,
+                name: None,
+                op: CsgOp::Translate {
+                    child: 2,
+                    offset: vcad_ir::Vec3::new(200.0, 0.0, 0.0),
+                },
+                attributes: None,
+            },
+        );
+        doc.nodes.insert(
+            4,
+            vcad_ir::Node {
+                id: 4,
+                name: Some("pair".to_string()),
+                op: CsgOp::Union { left: 1, right: 3 },
+                attributes: None,
+            },
+        );
+        doc.materials.insert(
+            "water".to_string(),
+            vcad_ir::MaterialDef {
+                name: "water".to_string(),
+                color: [0.2, 0.4, 0.8],
+                metallic: 0.0,
+                roughness: 0.1,
+                density: Some(1000.0),
+                friction: None,
+                opacity: None,
+                emissive: None,
+                base_color_texture: None,
+            },
+        );
+        doc.roots.push(vcad_ir::SceneEntry {
+            root: 4,
+            material: "water".to_string(),
+            visible: None,
+        });
+
+        let urdf = write_urdf_to_string(&doc).unwrap();
+        let robot: Robot = quick_xml::de::from_str(&urdf).unwrap();
+        let inertial = robot.links[0].inertial.as_ref().expect("inertial");
+
+        // Two 1 kg cubes of water whose centers are 0.2 m apart along X.
+        assert!((inertial.mass.value - 2.0).abs() < 1e-6);
+        let com = inertial.origin.as_ref().unwrap().xyz_vec();
+        assert!((com[0] - 0.15).abs() < 1e-6, "com x: {}", com[0]);
+        // Parallel axis: each cube sits 0.1 m from the combined center.
+        let izz = 2.0 * (0.02 / 12.0 + 0.01);
+        assert!((inertial.inertia.izz - izz).abs() < 1e-6, "izz: {}", inertial.inertia.izz);
+    }
+
+    #[test]
+    fn test_split_both_inertial_from_halves() {
+        let mut doc = Document::new();
+        doc.nodes.insert(
+            1,
+            vcad_ir::Node {
+                id: 1,
+                name: None,
+                op: CsgOp::Cube {
+                    size: vcad_ir::Vec3::new(100.0, 100.0, 100.0),
+                    origin: BoxOrigin::Corner,
+                },
+                attributes: None,
+            },
+        );
+        doc.nodes.insert(
+            2,
+            vcad_ir::Node {
+                id: 2,
+                name: Some("halves".to_string()),
+                op: CsgOp::SplitByPlane {
+                    child: 1,
+                    plane_origin: vcad_ir::Vec3::new(0.0, 0.0, 30.0),
+                    plane_normal: vcad_ir::Vec3::new(0.0, 0.0, 1.0),
+                    keep: SplitKeep::Both,
+                },
+                attributes: None,
+            },
+        );
+        doc.materials.insert(
+            "water".to_string(),
+            vcad_ir::MaterialDef {
+                name: "water".to_string(),
+                color: [0.2, 0.4, 0.8],
+                metallic: 0.0,
+                roughness: 0.1,
+                density: Some(1000.0),
+                friction: None,
+                opacity: None,
+                emissive: None,
+                base_color_texture: None,
+            },
+        );
+        doc.roots.push(vcad_ir::SceneEntry {
+            root: 2,
+            material: "water".to_string(),
+            visible: None,
+        });
+
+        let urdf = write_urdf_to_string(&doc).unwrap();
+        let robot: Robot = quick_xml::de::from_str(&urdf).unwrap();
+        let inertial = robot.links[0].inertial.as_ref().expect("inertial");
+
+        // The halves together are the whole 1 kg cube of water.
+        assert!((inertial.mass.value - 1.0).abs() < 1e-6);
+        let com = inertial.origin.as_ref().unwrap().xyz_vec();
+        assert!((com[2] - 0.05).abs() < 1e-6, "com z: {}", com[2]);
+        let ixx = 1.0 * 0.02 / 12.0;
+        assert!(
+            (inertial.inertia.ixx - ixx).abs() < 1e-6,
+            "ixx: {}",
+            inertial.inertia.ixx
+        );
+    }
 }
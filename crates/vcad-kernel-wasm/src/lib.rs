@@ -35,6 +35,41 @@ pub struct WasmMesh {
     pub positions: Vec<f32>,
     /// Flat array of triangle indices: [i0, i1, i2, ...]
     pub indices: Vec<u32>,
+    /// Flat array of vertex normals, same length as `positions`. Empty
+    /// unless requested with `getMeshWithNormals`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub normals: Vec<f32>,
+    /// Ranges of `indices` sharing one material.
+    #[serde(
+        default,
+        rename = "materialGroups",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub material_groups: Vec<WasmMaterialGroup>,
+}
+
+/// A range of a [`WasmMesh`]'s indices drawn with one material.
+#[derive(Serialize, Deserialize)]
+pub struct WasmMaterialGroup {
+    /// First index of the range.
+    pub start: u32,
+    /// Number of indices in the range.
+    pub count: u32,
+    /// Index of the material used for the range.
+    #[serde(rename = "materialIndex")]
+    pub material_index: u32,
+}
+
+/// Options for `Solid.getMeshWithNormals`.
+#[derive(Serialize, Deserialize, Default)]
+pub struct WasmMeshOptions {
+    /// Number of segments for curved surfaces (default 32).
+    #[serde(default)]
+    pub segments: Option<u32>,
+    /// Average normals across triangles sharing a vertex instead of giving
+    /// each triangle its own flat normal.
+    #[serde(default)]
+    pub smooth: bool,
 }
 
 /// A 2D sketch segment (line or arc) for WASM input.
@@ -64,38 +99,45 @@ pub struct WasmSketchProfile {
     pub y_dir: [f64; 3],
     /// Segments forming the closed profile.
     pub segments: Vec<WasmSketchSegment>,
+    /// Closed inner loops (holes) cut out of the profile.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub holes: Vec<Vec<WasmSketchSegment>>,
 }
 
 impl WasmSketchProfile {
     fn to_kernel_profile(&self) -> Result<SketchProfile, String> {
-        let segments: Vec<SketchSegment> = self
-            .segments
-            .iter()
-            .map(|s| match s {
-                WasmSketchSegment::Line { start, end } => SketchSegment::Line {
-                    start: Point2::new(start[0], start[1]),
-                    end: Point2::new(end[0], end[1]),
-                },
-                WasmSketchSegment::Arc {
-                    start,
-                    end,
-                    center,
-                    ccw,
-                } => SketchSegment::Arc {
-                    start: Point2::new(start[0], start[1]),
-                    end: Point2::new(end[0], end[1]),
-                    center: Point2::new(center[0], center[1]),
-                    ccw: *ccw,
-                },
-            })
-            .collect();
+        let convert = |s: &WasmSketchSegment| match s {
+            WasmSketchSegment::Line { start, end } => SketchSegment::Line {
+                start: Point2::new(start[0], start[1]),
+                end: Point2::new(end[0], end[1]),
+            },
+            WasmSketchSegment::Arc {
+                start,
+                end,
+                center,
+                ccw,
+            } => SketchSegment::Arc {
+                start: Point2::new(start[0], start[1]),
+                end: Point2::new(end[0], end[1]),
+                center: Point2::new(center[0], center[1]),
+                ccw: *ccw,
+            },
+        };
 
         SketchProfile::new(
             Point3::new(self.origin[0], self.origin[1], self.origin[2]),
             Vec3::new(self.x_dir[0], self.x_dir[1], self.x_dir[2]),
             Vec3::new(self.y_dir[0], self.y_dir[1], self.y_dir[2]),
-            segments,
+            self.segments.iter().map(convert).collect(),
         )
+        .and_then(|profile| {
+            profile.with_holes(
+                self.holes
+                    .iter()
+                    .map(|hole| hole.iter().map(convert).collect())
+                    .collect(),
+            )
+        })
         .map_err(|e| e.to_string())
     }
 
@@ -327,8 +369,10 @@ impl Solid {
 
     /// Create a solid by sweeping a profile along a line path.
     ///
-    /// Takes a sketch profile and path endpoints.
+    /// Takes a sketch profile and path endpoints. `on_degenerate` is
+    /// `"error"` (default), `"skip"` or `"interpolate"`.
     #[wasm_bindgen(js_name = sweepLine)]
+    #[allow(clippy::too_many_arguments)]
     pub fn sweep_line(
         profile_js: JsValue,
         start: Vec<f64>,
@@ -337,6 +381,7 @@ impl Solid {
         scale_start: Option<f64>,
         scale_end: Option<f64>,
         orientation: Option<f64>,
+        on_degenerate: Option<String>,
     ) -> Result<Solid, JsError> {
         use vcad_kernel::vcad_kernel_geom::Line3d;
         use vcad_kernel::vcad_kernel_sweep::SweepOptions;
@@ -363,6 +408,7 @@ impl Solid {
             scale_start: scale_start.unwrap_or(1.0),
             scale_end: scale_end.unwrap_or(1.0),
             orientation_angle: orientation.unwrap_or(0.0),
+            on_degenerate: parse_degenerate_policy(on_degenerate.as_deref())?,
             ..Default::default()
         };
 
@@ -373,7 +419,8 @@ impl Solid {
 
     /// Create a solid by sweeping a profile along a helix path.
     ///
-    /// Takes a sketch profile and helix parameters.
+    /// Takes a sketch profile and helix parameters. `on_degenerate` is as
+    /// for [`Solid::sweep_line`].
     #[wasm_bindgen(js_name = sweepHelix)]
     #[allow(clippy::too_many_arguments)]
     pub fn sweep_helix(
@@ -388,6 +435,7 @@ impl Solid {
         path_segments: Option<u32>,
         arc_segments: Option<u32>,
         orientation: Option<f64>,
+        on_degenerate: Option<String>,
     ) -> Result<Solid, JsError> {
         use vcad_kernel::vcad_kernel_sweep::{Helix, SweepOptions};
 
@@ -408,6 +456,63 @@ impl Solid {
             path_segments: path_segments.unwrap_or(0),
             arc_segments: arc_segments.unwrap_or(8),
             orientation_angle: orientation.unwrap_or(0.0),
+            on_degenerate: parse_degenerate_policy(on_degenerate.as_deref())?,
+            ..Default::default()
+        };
+
+        vcad_kernel::Solid::sweep(kernel_profile, &path, options)
+            .map(|inner| Solid { inner })
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Create a solid by sweeping a profile along a polyline path.
+    ///
+    /// Takes a sketch profile and the path vertices as flat `[x, y, z, ...]`
+    /// coordinates (at least two points). `on_degenerate` is as for
+    /// [`Solid::sweep_line`].
+    #[wasm_bindgen(js_name = sweepPolyline)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn sweep_polyline(
+        profile_js: JsValue,
+        points: Vec<f64>,
+        twist_angle: Option<f64>,
+        scale_start: Option<f64>,
+        scale_end: Option<f64>,
+        path_segments: Option<u32>,
+        arc_segments: Option<u32>,
+        orientation: Option<f64>,
+        on_degenerate: Option<String>,
+    ) -> Result<Solid, JsError> {
+        use vcad_kernel::vcad_kernel_sweep::{PolylinePath, SweepOptions};
+
+        let profile: WasmSketchProfile = serde_wasm_bindgen::from_value(profile_js)
+            .map_err(|e| JsError::new(&format!("Invalid profile: {}", e)))?;
+
+        if points.len() < 6 || points.len() % 3 != 0 {
+            return Err(JsError::new("Polyline needs at least 2 points of 3 components"));
+        }
+
+        // Use centered profile so it wraps around the path properly
+        let kernel_profile = profile
+            .to_kernel_profile_centered()
+            .map_err(|e| JsError::new(&e))?;
+
+        let path = PolylinePath::new(
+            points
+                .chunks_exact(3)
+                .map(|p| Point3::new(p[0], p[1], p[2]))
+                .collect(),
+        );
+
+        let options = SweepOptions {
+            twist_angle: twist_angle.unwrap_or(0.0),
+            scale_start: scale_start.unwrap_or(1.0),
+            scale_end: scale_end.unwrap_or(1.0),
+            path_segments: path_segments.unwrap_or(0),
+            arc_segments: arc_segments.unwrap_or(8),
+            orientation_angle: orientation.unwrap_or(0.0),
+            on_degenerate: parse_degenerate_policy(on_degenerate.as_deref())?,
+            ..Default::default()
         };
 
         vcad_kernel::Solid::sweep(kernel_profile, &path, options)
@@ -417,9 +522,16 @@ impl Solid {
 
     /// Create a solid by lofting between multiple profiles.
     ///
-    /// Takes an array of sketch profiles (minimum 2).
+    /// Takes an array of sketch profiles (minimum 2) and an optional mode,
+    /// `"ruled"` (default) or `"smooth"`. `on_degenerate` handles a profile
+    /// that coincides with the one before it, as for [`Solid::sweep_line`].
     #[wasm_bindgen(js_name = loft)]
-    pub fn loft(profiles_js: JsValue, closed: Option<bool>) -> Result<Solid, JsError> {
+    pub fn loft(
+        profiles_js: JsValue,
+        closed: Option<bool>,
+        mode: Option<String>,
+        on_degenerate: Option<String>,
+    ) -> Result<Solid, JsError> {
         use vcad_kernel::vcad_kernel_sweep::{LoftMode, LoftOptions};
 
         let profiles: Vec<WasmSketchProfile> = serde_wasm_bindgen::from_value(profiles_js)
@@ -433,9 +545,17 @@ impl Solid {
             profiles.iter().map(|p| p.to_kernel_profile()).collect();
         let kernel_profiles = kernel_profiles.map_err(|e| JsError::new(&e))?;
 
+        let mode = match mode.as_deref() {
+            None | Some("ruled") => LoftMode::Ruled,
+            Some("smooth") => LoftMode::Smooth,
+            Some(other) => return Err(JsError::new(&format!("Unknown loft mode: {}", other))),
+        };
+
         let options = LoftOptions {
-            mode: LoftMode::Ruled,
+            mode,
             closed: closed.unwrap_or(false),
+            on_degenerate: parse_degenerate_policy(on_degenerate.as_deref())?,
+            ..Default::default()
         };
 
         vcad_kernel::Solid::loft(&kernel_profiles, options)
@@ -579,6 +699,14 @@ impl Solid {
         }
     }
 
+    /// Boolean symmetric difference (self ⊕ other).
+    #[wasm_bindgen(js_name = xor)]
+    pub fn xor(&self, other: &Solid) -> Solid {
+        Solid {
+            inner: self.inner.xor(&other.inner),
+        }
+    }
+
     // =========================================================================
     // Transforms
     // =========================================================================
@@ -708,9 +836,14 @@ impl Solid {
     /// Get the triangle mesh representation.
     ///
     /// Returns a JS object with `positions` (Float32Array) and `indices` (Uint32Array).
+    /// Pass a `decimate` ratio below 1 to simplify the mesh to about that
+    /// fraction of its triangles, e.g. for distant levels of detail.
     #[wasm_bindgen(js_name = getMesh)]
-    pub fn get_mesh(&self, segments: Option<u32>) -> JsValue {
-        let mesh = self.inner.to_mesh(segments.unwrap_or(32));
+    pub fn get_mesh(&self, segments: Option<u32>, decimate: Option<f64>) -> JsValue {
+        let mut mesh = self.inner.to_mesh(segments.unwrap_or(32));
+        if let Some(ratio) = decimate.filter(|&r| r < 1.0) {
+            mesh = mesh.decimate(ratio);
+        }
         let num_verts = mesh.vertices.len() / 3;
 
         // Validate indices - check for out-of-bounds references
@@ -740,10 +873,57 @@ impl Solid {
         let wasm_mesh = WasmMesh {
             positions: mesh.vertices,
             indices: mesh.indices,
+            normals: Vec::new(),
+            material_groups: Vec::new(),
         };
         serde_wasm_bindgen::to_value(&wasm_mesh).unwrap_or(JsValue::NULL)
     }
 
+    /// Get the triangle mesh with vertex normals.
+    ///
+    /// `options` is `{ segments?: number, smooth?: boolean }`. Smooth normals
+    /// are averaged over the triangles sharing a vertex; flat normals give
+    /// every triangle its own vertices. Returns `positions`, `indices`,
+    /// `normals` and `materialGroups` (a single group covering all indices
+    /// for now).
+    #[wasm_bindgen(js_name = getMeshWithNormals)]
+    pub fn get_mesh_with_normals(&self, options: JsValue) -> Result<JsValue, JsError> {
+        let options: WasmMeshOptions = if options.is_undefined() || options.is_null() {
+            WasmMeshOptions::default()
+        } else {
+            serde_wasm_bindgen::from_value(options).map_err(|e| JsError::new(&e.to_string()))?
+        };
+        let mesh = self
+            .inner
+            .to_mesh(options.segments.unwrap_or(32))
+            .with_normals(options.smooth);
+        let wasm_mesh = WasmMesh {
+            material_groups: vec![WasmMaterialGroup {
+                start: 0,
+                count: mesh.indices.len() as u32,
+                material_index: 0,
+            }],
+            positions: mesh.vertices,
+            indices: mesh.indices,
+            normals: mesh.normals,
+        };
+        serde_wasm_bindgen::to_value(&wasm_mesh).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Get the feature edges for a wireframe overlay, as a flat segment list
+    /// `[x0, y0, z0, x1, y1, z1, ...]` with six values per segment.
+    ///
+    /// `angleThreshold` (degrees, default 30) is the crease angle above which
+    /// an edge between triangles is drawn; B-rep face boundaries always are.
+    #[wasm_bindgen(js_name = featureEdges)]
+    pub fn feature_edges(&self, angle_threshold: Option<f64>) -> Vec<f64> {
+        self.inner
+            .feature_edges(angle_threshold.unwrap_or(30.0))
+            .into_iter()
+            .flat_map(|(a, b)| [a.x, a.y, a.z, b.x, b.y, b.z])
+            .collect()
+    }
+
     /// Compute the volume of the solid.
     #[wasm_bindgen(js_name = volume)]
     pub fn volume(&self) -> f64 {
@@ -756,6 +936,19 @@ impl Solid {
         self.inner.surface_area()
     }
 
+    /// Volume shared with another solid (0 if they don't overlap).
+    #[wasm_bindgen(js_name = interferenceVolume)]
+    pub fn interference_volume(&self, other: &Solid) -> f64 {
+        self.inner.interference_volume(&other.inner)
+    }
+
+    /// Smallest distance between this solid's surface and another's
+    /// (0 if they interfere or touch).
+    #[wasm_bindgen(js_name = minClearance)]
+    pub fn min_clearance(&self, other: &Solid) -> f64 {
+        self.inner.min_clearance(&other.inner)
+    }
+
     /// Get the bounding box as [minX, minY, minZ, maxX, maxY, maxZ].
     #[wasm_bindgen(js_name = boundingBox)]
     pub fn bounding_box(&self) -> Vec<f64> {
@@ -1161,6 +1354,7 @@ pub fn op_revolve(
 /// This is a standalone wrapper for lazy loading via wasmosis.
 #[module("sweep")]
 #[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
 pub fn op_sweep_line(
     profile_js: JsValue,
     start: Vec<f64>,
@@ -1169,8 +1363,18 @@ pub fn op_sweep_line(
     scale_start: Option<f64>,
     scale_end: Option<f64>,
     orientation: Option<f64>,
+    on_degenerate: Option<String>,
 ) -> Result<Solid, JsError> {
-    Solid::sweep_line(profile_js, start, end, twist_angle, scale_start, scale_end, orientation)
+    Solid::sweep_line(
+        profile_js,
+        start,
+        end,
+        twist_angle,
+        scale_start,
+        scale_end,
+        orientation,
+        on_degenerate,
+    )
 }
 
 /// Create a solid by sweeping a profile along a helix path.
@@ -1191,6 +1395,7 @@ pub fn op_sweep_helix(
     path_segments: Option<u32>,
     arc_segments: Option<u32>,
     orientation: Option<f64>,
+    on_degenerate: Option<String>,
 ) -> Result<Solid, JsError> {
     Solid::sweep_helix(
         profile_js,
@@ -1204,6 +1409,7 @@ pub fn op_sweep_helix(
         path_segments,
         arc_segments,
         orientation,
+        on_degenerate,
     )
 }
 
@@ -1212,8 +1418,13 @@ pub fn op_sweep_helix(
 /// This is a standalone wrapper for lazy loading via wasmosis.
 #[module("sweep")]
 #[wasm_bindgen]
-pub fn op_loft(profiles_js: JsValue, closed: Option<bool>) -> Result<Solid, JsError> {
-    Solid::loft(profiles_js, closed)
+pub fn op_loft(
+    profiles_js: JsValue,
+    closed: Option<bool>,
+    mode: Option<String>,
+    on_degenerate: Option<String>,
+) -> Result<Solid, JsError> {
+    Solid::loft(profiles_js, closed, mode, on_degenerate)
 }
 
 // =========================================================================
@@ -1786,6 +1997,8 @@ pub fn import_step_buffer(data: &[u8]) -> Result<JsValue, JsError> {
             WasmMesh {
                 positions: mesh.vertices,
                 indices: mesh.indices,
+                normals: Vec::new(),
+                material_groups: Vec::new(),
             }
         })
         .collect();
@@ -2469,6 +2682,58 @@ pub fn to_compact_ir(doc_json: &str) -> Result<String, JsError> {
         .map_err(|e| JsError::new(&format!("Conversion error: {}", e)))
 }
 
+/// Resolve a document's parameter expressions.
+///
+/// Returns the document JSON with every expression evaluated into its
+/// node's op, as the Rust evaluators see it. Documents without expressions
+/// come back unchanged.
+///
+/// # Arguments
+/// * `doc_json` - JSON string representing a vcad IR Document
+#[wasm_bindgen(js_name = resolveParameters)]
+pub fn resolve_parameters(doc_json: &str) -> Result<String, JsError> {
+    let doc = vcad_ir::Document::from_json(doc_json)
+        .map_err(|e| JsError::new(&format!("Invalid JSON: {}", e)))?;
+    let doc = doc
+        .resolved()
+        .map_err(|e| JsError::new(&format!("Parameter error: {}", e)))?;
+
+    doc.to_json()
+        .map_err(|e| JsError::new(&format!("JSON serialization failed: {}", e)))
+}
+
+/// Parse SVG path data into sketch profiles.
+///
+/// Returns one profile per outer loop of the path, with the holes inside
+/// it, in the form [`Solid::extrude`] takes. The plane is given by its
+/// origin and x/y directions; SVG's Y is flipped onto `y_dir`.
+///
+/// # Arguments
+/// * `path_data` - SVG path data, e.g. `"M0 0 L10 0 L10 10 Z"`
+/// * `scale` - Millimeters per SVG user unit
+#[wasm_bindgen(js_name = svgProfiles)]
+pub fn svg_profiles(
+    path_data: &str,
+    scale: f64,
+    origin: Vec<f64>,
+    x_dir: Vec<f64>,
+    y_dir: Vec<f64>,
+) -> Result<JsValue, JsError> {
+    let vec3 = |v: &[f64]| -> Result<[f64; 3], JsError> {
+        v.try_into()
+            .map_err(|_| JsError::new("Plane vectors must have 3 components"))
+    };
+    let profiles = svg_wasm_profiles(
+        path_data,
+        scale,
+        vec3(&origin)?,
+        vec3(&x_dir)?,
+        vec3(&y_dir)?,
+    )?;
+    serde_wasm_bindgen::to_value(&profiles)
+        .map_err(|e| JsError::new(&format!("Profile serialization failed: {}", e)))
+}
+
 /// Evaluate compact IR and return a Solid for rendering.
 ///
 /// This is a convenience function that parses compact IR and evaluates
@@ -2484,6 +2749,9 @@ pub fn to_compact_ir(doc_json: &str) -> Result<String, JsError> {
 pub fn evaluate_compact_ir(compact_ir: &str) -> Result<Solid, JsError> {
     let doc = vcad_ir::compact::from_compact(compact_ir)
         .map_err(|e| JsError::new(&format!("Parse error: {}", e)))?;
+    let doc = doc
+        .resolved()
+        .map_err(|e| JsError::new(&format!("Parameter error: {}", e)))?;
 
     // Find the root node
     let root_id = doc.roots.first()
@@ -2494,6 +2762,32 @@ pub fn evaluate_compact_ir(compact_ir: &str) -> Result<Solid, JsError> {
     evaluate_node(&doc, root_id)
 }
 
+/// Evaluate compact IR and return the separate bodies of its first root.
+///
+/// Like [`evaluate_compact_ir`], but a root that splits a solid keeping
+/// both halves gives each half as its own Solid instead of failing.
+///
+/// # Arguments
+/// * `compact_ir` - The compact IR text to evaluate
+///
+/// # Returns
+/// One Solid per body, without empty halves.
+#[module("ml")]
+#[wasm_bindgen(js_name = evaluateCompactIRBodies)]
+pub fn evaluate_compact_ir_bodies(compact_ir: &str) -> Result<Vec<Solid>, JsError> {
+    let doc = vcad_ir::compact::from_compact(compact_ir)
+        .map_err(|e| JsError::new(&format!("Parse error: {}", e)))?;
+    let doc = doc
+        .resolved()
+        .map_err(|e| JsError::new(&format!("Parameter error: {}", e)))?;
+
+    let root_id = doc.roots.first()
+        .ok_or_else(|| JsError::new("Document has no root nodes"))?
+        .root;
+
+    evaluate_bodies(&doc, root_id)
+}
+
 // =========================================================================
 // Physics Simulation (Rapier-based gym environment)
 // =========================================================================
@@ -2682,13 +2976,175 @@ pub fn is_physics_available() -> bool {
 // Internal evaluation helpers
 // =========================================================================
 
+/// Parse a degenerate-station policy name, defaulting to `"error"`.
+fn parse_degenerate_policy(
+    name: Option<&str>,
+) -> Result<vcad_kernel::vcad_kernel_sweep::DegeneratePolicy, JsError> {
+    use vcad_kernel::vcad_kernel_sweep::DegeneratePolicy;
+    match name {
+        None | Some("error") => Ok(DegeneratePolicy::Error),
+        Some("skip") => Ok(DegeneratePolicy::Skip),
+        Some("interpolate") => Ok(DegeneratePolicy::Interpolate),
+        Some(other) => Err(JsError::new(&format!("Unknown degenerate policy: {}", other))),
+    }
+}
+
+/// Convert an IR degenerate-station policy to the kernel's.
+fn ir_degenerate_policy(
+    policy: Option<vcad_ir::DegeneratePolicy>,
+) -> vcad_kernel::vcad_kernel_sweep::DegeneratePolicy {
+    use vcad_kernel::vcad_kernel_sweep::DegeneratePolicy;
+    match policy.unwrap_or_default() {
+        vcad_ir::DegeneratePolicy::Error => DegeneratePolicy::Error,
+        vcad_ir::DegeneratePolicy::Skip => DegeneratePolicy::Skip,
+        vcad_ir::DegeneratePolicy::Interpolate => DegeneratePolicy::Interpolate,
+    }
+}
+
+/// Convert a sketch node to a single kernel profile, see
+/// [`ir_sketch_profiles`].
+fn ir_sketch_profile(
+    doc: &vcad_ir::Document,
+    node_id: vcad_ir::NodeId,
+) -> Result<SketchProfile, JsError> {
+    let mut profiles = ir_sketch_profiles(doc, node_id)?;
+    if profiles.len() != 1 {
+        return Err(JsError::new(&format!(
+            "Sketch {} has {} separate outlines, expected one",
+            node_id,
+            profiles.len()
+        )));
+    }
+    Ok(profiles.remove(0))
+}
+
+/// Convert a Sketch2D, ProjectToSketch or SvgProfile node to kernel
+/// profiles, one per separate outline.
+fn ir_sketch_profiles(
+    doc: &vcad_ir::Document,
+    node_id: vcad_ir::NodeId,
+) -> Result<Vec<SketchProfile>, JsError> {
+    let node = doc.nodes.get(&node_id)
+        .ok_or_else(|| JsError::new(&format!("Sketch node {} not found", node_id)))?;
+    let profiles = match &node.op {
+        vcad_ir::CsgOp::Sketch2D { origin, x_dir, y_dir, segments, holes } => {
+            vec![WasmSketchProfile {
+                origin: [origin.x, origin.y, origin.z],
+                x_dir: [x_dir.x, x_dir.y, x_dir.z],
+                y_dir: [y_dir.x, y_dir.y, y_dir.z],
+                segments: ir_wasm_segments(segments),
+                holes: holes.iter().map(|hole| ir_wasm_segments(hole)).collect(),
+            }]
+        }
+        vcad_ir::CsgOp::ProjectToSketch { solid, plane_origin, plane_normal } => {
+            let profile = evaluate_node(doc, *solid)?
+                .inner
+                .project_to_sketch(
+                    Point3::new(plane_origin.x, plane_origin.y, plane_origin.z),
+                    Vec3::new(plane_normal.x, plane_normal.y, plane_normal.z),
+                )
+                .ok_or_else(|| JsError::new(&format!("Projection {} is not a single outline", node_id)))?;
+            return Ok(vec![profile]);
+        }
+        vcad_ir::CsgOp::SvgProfile { path_data, scale, plane_origin, x_dir, y_dir } => {
+            svg_wasm_profiles(
+                path_data,
+                *scale,
+                [plane_origin.x, plane_origin.y, plane_origin.z],
+                [x_dir.x, x_dir.y, x_dir.z],
+                [y_dir.x, y_dir.y, y_dir.z],
+            )?
+        }
+        _ => return Err(JsError::new(&format!("Node {} is not a sketch", node_id))),
+    };
+    profiles
+        .iter()
+        .map(|profile| profile.to_kernel_profile().map_err(|e| JsError::new(&e)))
+        .collect()
+}
+
+/// Parse SVG path data into JS-facing profiles, one per outer loop.
+fn svg_wasm_profiles(
+    path_data: &str,
+    scale: f64,
+    origin: [f64; 3],
+    x_dir: [f64; 3],
+    y_dir: [f64; 3],
+) -> Result<Vec<WasmSketchProfile>, JsError> {
+    let outlines = vcad_kernel_svg::parse_svg_outlines(path_data, scale)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(outlines
+        .iter()
+        .map(|outline| WasmSketchProfile {
+            origin,
+            x_dir,
+            y_dir,
+            segments: ir_wasm_segments(&outline.segments),
+            holes: outline.holes.iter().map(|hole| ir_wasm_segments(hole)).collect(),
+        })
+        .collect())
+}
+
+/// Convert IR sketch segments to their JS-facing form.
+fn ir_wasm_segments(segments: &[vcad_ir::SketchSegment2D]) -> Vec<WasmSketchSegment> {
+    segments.iter().map(|seg| match seg {
+        vcad_ir::SketchSegment2D::Line { start, end } => WasmSketchSegment::Line {
+            start: [start.x, start.y],
+            end: [end.x, end.y],
+        },
+        vcad_ir::SketchSegment2D::Arc { start, end, center, ccw } => WasmSketchSegment::Arc {
+            start: [start.x, start.y],
+            end: [end.x, end.y],
+            center: [center.x, center.y],
+            ccw: *ccw,
+        },
+    }).collect()
+}
+
+/// Evaluate a node to the separate bodies it produces.
+///
+/// A split keeping both halves gives its non-empty halves, also through
+/// transforms and tags above it; any other node gives the one solid from
+/// [`evaluate_node`].
+fn evaluate_bodies(doc: &vcad_ir::Document, node_id: vcad_ir::NodeId) -> Result<Vec<Solid>, JsError> {
+    let node = doc.nodes.get(&node_id)
+        .ok_or_else(|| JsError::new(&format!("Node {} not found", node_id)))?;
+
+    match &node.op {
+        vcad_ir::CsgOp::SplitByPlane { child, plane_origin, plane_normal, keep: vcad_ir::SplitKeep::Both } => {
+            let c = evaluate_node(doc, *child)?;
+            let (positive, negative) = c.inner.split_by_plane(
+                Point3::new(plane_origin.x, plane_origin.y, plane_origin.z),
+                Vec3::new(plane_normal.x, plane_normal.y, plane_normal.z),
+            );
+            Ok([positive, negative].into_iter()
+                .filter(|half| !half.is_empty())
+                .map(|inner| Solid { inner })
+                .collect())
+        }
+        vcad_ir::CsgOp::Translate { child, offset } => Ok(evaluate_bodies(doc, *child)?.iter()
+            .map(|s| s.translate(offset.x, offset.y, offset.z))
+            .collect()),
+        vcad_ir::CsgOp::Rotate { child, angles } => Ok(evaluate_bodies(doc, *child)?.iter()
+            .map(|s| s.rotate(angles.x, angles.y, angles.z))
+            .collect()),
+        vcad_ir::CsgOp::Scale { child, factor } => Ok(evaluate_bodies(doc, *child)?.iter()
+            .map(|s| s.scale(factor.x, factor.y, factor.z))
+            .collect()),
+        vcad_ir::CsgOp::Tag { child, .. } => evaluate_bodies(doc, *child),
+        _ => Ok(vec![evaluate_node(doc, node_id)?]),
+    }
+}
+
 /// Recursively evaluate a node in the IR DAG.
 fn evaluate_node(doc: &vcad_ir::Document, node_id: vcad_ir::NodeId) -> Result<Solid, JsError> {
     let node = doc.nodes.get(&node_id)
         .ok_or_else(|| JsError::new(&format!("Node {} not found", node_id)))?;
 
     match &node.op {
-        vcad_ir::CsgOp::Cube { size } => Ok(Solid::cube(size.x, size.y, size.z)),
+        vcad_ir::CsgOp::Cube { size, origin } => Ok(Solid {
+            inner: vcad_kernel::Solid::cube_at(size.x, size.y, size.z, (*origin).into()),
+        }),
 
         vcad_ir::CsgOp::Cylinder { radius, height, segments } => {
             let segs = if *segments == 0 { None } else { Some(*segments) };
@@ -2775,109 +3231,136 @@ fn evaluate_node(doc: &vcad_ir::Document, node_id: vcad_ir::NodeId) -> Result<So
         }
 
         vcad_ir::CsgOp::Extrude { sketch, direction, twist_angle, scale_end } => {
-            // Get the sketch node
-            let sketch_node = doc.nodes.get(sketch)
-                .ok_or_else(|| JsError::new(&format!("Sketch node {} not found", sketch)))?;
-
-            match &sketch_node.op {
-                vcad_ir::CsgOp::Sketch2D { origin, x_dir, y_dir, segments } => {
-                    let wasm_segments: Vec<WasmSketchSegment> = segments.iter().map(|seg| {
-                        match seg {
-                            vcad_ir::SketchSegment2D::Line { start, end } => {
-                                WasmSketchSegment::Line {
-                                    start: [start.x, start.y],
-                                    end: [end.x, end.y],
-                                }
-                            }
-                            vcad_ir::SketchSegment2D::Arc { start, end, center, ccw } => {
-                                WasmSketchSegment::Arc {
-                                    start: [start.x, start.y],
-                                    end: [end.x, end.y],
-                                    center: [center.x, center.y],
-                                    ccw: *ccw,
-                                }
-                            }
-                        }
-                    }).collect();
-
-                    let profile = WasmSketchProfile {
-                        origin: [origin.x, origin.y, origin.z],
-                        x_dir: [x_dir.x, x_dir.y, x_dir.z],
-                        y_dir: [y_dir.x, y_dir.y, y_dir.z],
-                        segments: wasm_segments,
-                    };
-
-                    let profile_js = serde_wasm_bindgen::to_value(&profile)
-                        .map_err(|e| JsError::new(&format!("Profile serialization failed: {}", e)))?;
-
-                    // Use extrudeWithOptions if twist or scale is specified
-                    let has_twist = twist_angle.is_some_and(|t| t.abs() > 1e-12);
-                    let has_scale = scale_end.is_some_and(|s| (s - 1.0).abs() > 1e-12);
-                    if has_twist || has_scale {
-                        Solid::extrude_with_options(
-                            profile_js,
-                            vec![direction.x, direction.y, direction.z],
-                            twist_angle.unwrap_or(0.0),
-                            scale_end.unwrap_or(1.0),
-                        )
-                    } else {
-                        Solid::extrude(profile_js, vec![direction.x, direction.y, direction.z])
-                    }
+            let dir = Vec3::new(direction.x, direction.y, direction.z);
+
+            // Use the twisting/tapering extrusion only if it's needed
+            let has_twist = twist_angle.is_some_and(|t| t.abs() > 1e-12);
+            let has_scale = scale_end.is_some_and(|s| (s - 1.0).abs() > 1e-12);
+            let mut result: Option<vcad_kernel::Solid> = None;
+            for kernel_profile in ir_sketch_profiles(doc, *sketch)? {
+                let part = if has_twist || has_scale {
+                    vcad_kernel::Solid::extrude_with_options(
+                        kernel_profile,
+                        dir,
+                        twist_angle.unwrap_or(0.0),
+                        scale_end.unwrap_or(1.0),
+                    )
+                } else {
+                    vcad_kernel::Solid::extrude(kernel_profile, dir)
                 }
-                _ => Err(JsError::new("Extrude requires a Sketch2D node"))
+                .map_err(|e| JsError::new(&e.to_string()))?;
+                result = Some(match result {
+                    Some(solid) => solid.merge_disjoint(&part),
+                    None => part,
+                });
             }
+            Ok(result.map_or_else(Solid::empty, |inner| Solid { inner }))
         }
 
         vcad_ir::CsgOp::Revolve { sketch, axis_origin, axis_dir, angle_deg } => {
-            let sketch_node = doc.nodes.get(sketch)
-                .ok_or_else(|| JsError::new(&format!("Sketch node {} not found", sketch)))?;
-
-            match &sketch_node.op {
-                vcad_ir::CsgOp::Sketch2D { origin, x_dir, y_dir, segments } => {
-                    let wasm_segments: Vec<WasmSketchSegment> = segments.iter().map(|seg| {
-                        match seg {
-                            vcad_ir::SketchSegment2D::Line { start, end } => {
-                                WasmSketchSegment::Line {
-                                    start: [start.x, start.y],
-                                    end: [end.x, end.y],
-                                }
-                            }
-                            vcad_ir::SketchSegment2D::Arc { start, end, center, ccw } => {
-                                WasmSketchSegment::Arc {
-                                    start: [start.x, start.y],
-                                    end: [end.x, end.y],
-                                    center: [center.x, center.y],
-                                    ccw: *ccw,
-                                }
-                            }
-                        }
-                    }).collect();
-
-                    let profile = WasmSketchProfile {
-                        origin: [origin.x, origin.y, origin.z],
-                        x_dir: [x_dir.x, x_dir.y, x_dir.z],
-                        y_dir: [y_dir.x, y_dir.y, y_dir.z],
-                        segments: wasm_segments,
-                    };
-
-                    let profile_js = serde_wasm_bindgen::to_value(&profile)
-                        .map_err(|e| JsError::new(&format!("Profile serialization failed: {}", e)))?;
-
-                    Solid::revolve(
-                        profile_js,
-                        vec![axis_origin.x, axis_origin.y, axis_origin.z],
-                        vec![axis_dir.x, axis_dir.y, axis_dir.z],
-                        *angle_deg,
-                    )
+            let kernel_profile = ir_sketch_profile(doc, *sketch)?;
+            vcad_kernel::Solid::revolve(
+                kernel_profile,
+                Point3::new(axis_origin.x, axis_origin.y, axis_origin.z),
+                Vec3::new(axis_dir.x, axis_dir.y, axis_dir.z),
+                *angle_deg,
+            )
+            .map(|inner| Solid { inner })
+            .map_err(|e| JsError::new(&e.to_string()))
+        }
+
+        vcad_ir::CsgOp::Loft { profiles, mode, closed } => {
+            use vcad_kernel::vcad_kernel_sweep::{LoftMode, LoftOptions};
+
+            let kernel_profiles = profiles
+                .iter()
+                .map(|id| ir_sketch_profile(doc, *id))
+                .collect::<Result<Vec<_>, _>>()?;
+            let options = LoftOptions {
+                mode: match mode {
+                    vcad_ir::LoftMode::Ruled => LoftMode::Ruled,
+                    vcad_ir::LoftMode::Smooth => LoftMode::Smooth,
+                },
+                closed: *closed,
+                ..Default::default()
+            };
+            vcad_kernel::Solid::loft(&kernel_profiles, options)
+                .map(|inner| Solid { inner })
+                .map_err(|e| JsError::new(&e.to_string()))
+        }
+
+        vcad_ir::CsgOp::Sweep { profile, path, options } => {
+            use vcad_kernel::vcad_kernel_geom::Line3d;
+            use vcad_kernel::vcad_kernel_sweep::{Helix, PolylinePath, SweepOptions};
+
+            let kernel_profile = ir_sketch_profile(doc, *profile)?;
+            let defaults = SweepOptions::default();
+            let options = SweepOptions {
+                twist_angle: options.twist_angle.unwrap_or(defaults.twist_angle),
+                scale_start: options.scale_start.unwrap_or(defaults.scale_start),
+                scale_end: options.scale_end.unwrap_or(defaults.scale_end),
+                path_segments: options.path_segments.unwrap_or(defaults.path_segments),
+                arc_segments: options.arc_segments.unwrap_or(defaults.arc_segments),
+                orientation_angle: options.orientation.unwrap_or(defaults.orientation_angle),
+                on_degenerate: ir_degenerate_policy(options.on_degenerate),
+                ..defaults
+            };
+            let point = |v: &vcad_ir::Vec3| Point3::new(v.x, v.y, v.z);
+            let result = match path {
+                vcad_ir::SweepPath::Line { start, end } => {
+                    let path = Line3d::from_points(point(start), point(end));
+                    vcad_kernel::Solid::sweep(kernel_profile, &path, options)
                 }
-                _ => Err(JsError::new("Revolve requires a Sketch2D node"))
-            }
+                vcad_ir::SweepPath::Helix { radius, pitch, height, turns } => {
+                    let path = Helix::new(*radius, *pitch, *height, *turns);
+                    vcad_kernel::Solid::sweep(kernel_profile, &path, options)
+                }
+                vcad_ir::SweepPath::Polyline { points } => {
+                    let path = PolylinePath::new(points.iter().map(point).collect());
+                    vcad_kernel::Solid::sweep(kernel_profile, &path, options)
+                }
+            };
+            result
+                .map(|inner| Solid { inner })
+                .map_err(|e| JsError::new(&e.to_string()))
+        }
+
+        vcad_ir::CsgOp::SplitByPlane { child, plane_origin, plane_normal, keep } => {
+            let c = evaluate_node(doc, *child)?;
+            let (positive, negative) = c.inner.split_by_plane(
+                Point3::new(plane_origin.x, plane_origin.y, plane_origin.z),
+                Vec3::new(plane_normal.x, plane_normal.y, plane_normal.z),
+            );
+            let inner = match keep {
+                vcad_ir::SplitKeep::Positive => positive,
+                vcad_ir::SplitKeep::Negative => negative,
+                vcad_ir::SplitKeep::Both => {
+                    return Err(JsError::new(&format!(
+                        "Node {} keeps both halves of a split, which are two bodies; \
+                         evaluate it with evaluateCompactIRBodies",
+                        node_id
+                    )))
+                }
+            };
+            Ok(Solid { inner })
         }
 
+        vcad_ir::CsgOp::Tag { child, .. } => evaluate_node(doc, *child),
+
         vcad_ir::CsgOp::StepImport { .. } => {
             Err(JsError::new("STEP import not supported in compact IR evaluation"))
         }
 
+        vcad_ir::CsgOp::ProjectToSketch { .. } => {
+            // Resolved to a profile by `ir_sketch_profiles` when extruded
+            Err(JsError::new("ProjectToSketch cannot be evaluated directly - use Extrude to convert to solid"))
+        }
+
+        vcad_ir::CsgOp::SvgProfile { .. } => {
+            // Resolved to profiles by `ir_sketch_profiles` when extruded
+            Err(JsError::new("SvgProfile cannot be evaluated directly - use Extrude to convert to solid"))
+        }
+
         vcad_ir::CsgOp::Text2D { .. } => {
             // Text2D doesn't produce geometry by itself - it needs to be extruded.
             // This case handles direct evaluation of Text2D nodes (should be rare).
@@ -2889,6 +3372,29 @@ fn evaluate_node(doc: &vcad_ir::Document, node_id: vcad_ir::NodeId) -> Result<So
             // The TypeScript evaluate.ts handles converting Text2D inside Extrude
             Err(JsError::new("Text2D cannot be evaluated directly - use Extrude to convert to solid"))
         }
+
+        vcad_ir::CsgOp::Text { content, font: _, height, depth, plane_origin, x_dir, y_dir, alignment } => {
+            use vcad_kernel::vcad_kernel_text::{FontRegistry, TextAlignment};
+
+            // Only the builtin sans-serif font is available; other fonts fall back to it
+            let align = match alignment {
+                vcad_ir::TextAlignment::Left => TextAlignment::Left,
+                vcad_ir::TextAlignment::Center => TextAlignment::Center,
+                vcad_ir::TextAlignment::Right => TextAlignment::Right,
+            };
+            let inner = vcad_kernel::Solid::text(
+                content,
+                FontRegistry::builtin_sans(),
+                *height,
+                *depth,
+                Point3::new(plane_origin.x, plane_origin.y, plane_origin.z),
+                Vec3::new(x_dir.x, x_dir.y, x_dir.z),
+                Vec3::new(y_dir.x, y_dir.y, y_dir.z),
+                align,
+            )
+            .map_err(|e| JsError::new(&format!("Text failed: {}", e)))?;
+            Ok(Solid { inner })
+        }
     }
 }
 
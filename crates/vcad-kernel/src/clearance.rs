@@ -0,0 +1,180 @@
+//! Minimum distance between two tessellated surfaces.
+//!
+//! Two disjoint triangles are closest either at a vertex of one and the
+//! face of the other, or between an edge of each, so checking those pairs
+//! gives the exact distance between the meshes.
+
+use vcad_kernel_math::{Point3, Vec3};
+use vcad_kernel_tessellate::TriangleMesh;
+
+/// A triangle with its axis-aligned bounds, for pruning.
+struct BoundedTriangle {
+    corners: [Point3; 3],
+    min: Point3,
+    max: Point3,
+}
+
+/// Smallest distance between any triangle of `a` and any triangle of `b`.
+///
+/// Triangle pairs whose bounding boxes are already further apart than the
+/// best distance so far are skipped, but the search is still quadratic in
+/// the worst case. Returns `f64::INFINITY` if either mesh has no triangles.
+pub(crate) fn mesh_distance(a: &TriangleMesh, b: &TriangleMesh) -> f64 {
+    let tris_a = bounded_triangles(a);
+    let tris_b = bounded_triangles(b);
+
+    let mut best = f64::INFINITY;
+    for ta in &tris_a {
+        for tb in &tris_b {
+            if box_distance(ta, tb) >= best {
+                continue;
+            }
+            best = best.min(triangle_distance(&ta.corners, &tb.corners));
+            if best == 0.0 {
+                return 0.0;
+            }
+        }
+    }
+    best
+}
+
+fn bounded_triangles(mesh: &TriangleMesh) -> Vec<BoundedTriangle> {
+    let vertex = |i: u32| {
+        let i = i as usize * 3;
+        Point3::new(
+            mesh.vertices[i] as f64,
+            mesh.vertices[i + 1] as f64,
+            mesh.vertices[i + 2] as f64,
+        )
+    };
+    mesh.indices
+        .chunks_exact(3)
+        .map(|tri| {
+            let corners = [vertex(tri[0]), vertex(tri[1]), vertex(tri[2])];
+            let min = corners[0].inf(&corners[1]).inf(&corners[2]);
+            let max = corners[0].sup(&corners[1]).sup(&corners[2]);
+            BoundedTriangle { corners, min, max }
+        })
+        .collect()
+}
+
+/// Distance between the bounding boxes of two triangles.
+fn box_distance(a: &BoundedTriangle, b: &BoundedTriangle) -> f64 {
+    let gap = |k: usize| (a.min[k] - b.max[k]).max(b.min[k] - a.max[k]).max(0.0);
+    Vec3::new(gap(0), gap(1), gap(2)).norm()
+}
+
+/// Distance between two triangles that do not cross each other.
+fn triangle_distance(a: &[Point3; 3], b: &[Point3; 3]) -> f64 {
+    let mut best = f64::INFINITY;
+    for p in a {
+        best = best.min((p - closest_point_on_triangle(p, b)).norm());
+    }
+    for p in b {
+        best = best.min((p - closest_point_on_triangle(p, a)).norm());
+    }
+    for i in 0..3 {
+        for j in 0..3 {
+            let d = segment_distance(a[i], a[(i + 1) % 3], b[j], b[(j + 1) % 3]);
+            best = best.min(d);
+        }
+    }
+    best
+}
+
+/// Closest point to `p` on triangle `tri`, by Voronoi region
+/// (Ericson, *Real-Time Collision Detection*, 5.1.5).
+fn closest_point_on_triangle(p: &Point3, tri: &[Point3; 3]) -> Point3 {
+    let [a, b, c] = *tri;
+    let ab = b - a;
+    let ac = c - a;
+
+    let ap = p - a;
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    // Inside the face
+    let denom = va + vb + vc;
+    if denom.abs() < 1e-300 {
+        // Degenerate triangle: fall back to its vertices
+        return [a, b, c]
+            .into_iter()
+            .min_by(|x, y| (p - x).norm().total_cmp(&(p - y).norm()))
+            .unwrap();
+    }
+    a + ab * (vb / denom) + ac * (vc / denom)
+}
+
+/// Distance between segments `p0 p1` and `q0 q1`
+/// (Ericson, *Real-Time Collision Detection*, 5.1.9).
+fn segment_distance(p0: Point3, p1: Point3, q0: Point3, q1: Point3) -> f64 {
+    let d1 = p1 - p0;
+    let d2 = q1 - q0;
+    let r = p0 - q0;
+    let a = d1.dot(&d1);
+    let e = d2.dot(&d2);
+    let f = d2.dot(&r);
+    const EPS: f64 = 1e-24;
+
+    let (s, t) = if a <= EPS && e <= EPS {
+        (0.0, 0.0)
+    } else if a <= EPS {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(&r);
+        if e <= EPS {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(&d2);
+            let denom = a * e - b * b;
+            let mut s = if denom > EPS {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let mut t = (b * s + f) / e;
+            if t < 0.0 {
+                t = 0.0;
+                s = (-c / a).clamp(0.0, 1.0);
+            } else if t > 1.0 {
+                t = 1.0;
+                s = ((b - c) / a).clamp(0.0, 1.0);
+            }
+            (s, t)
+        }
+    };
+
+    ((p0 + d1 * s) - (q0 + d2 * t)).norm()
+}
@@ -0,0 +1,128 @@
+//! Connected components of a tessellated surface.
+//!
+//! Triangles are connected when they share an edge. As in [`crate::edges`],
+//! vertices are welded by position first, so that seams between separately
+//! tessellated faces don't split a body. The wall of an internal void is
+//! not connected to the body around it, so it is joined to that body by
+//! containment.
+
+use std::collections::HashMap;
+
+use vcad_kernel_booleans::point_in_mesh_robust;
+use vcad_kernel_math::{Point3, Quantize, Vec3};
+use vcad_kernel_tessellate::TriangleMesh;
+
+use crate::edges::WELD_SCALE;
+
+/// Split a mesh into the groups of triangles connected through shared
+/// edges, in order of each group's first triangle.
+///
+/// A group inside an odd number of others bounds a void, and is merged
+/// into the innermost group around it; an island inside that void is a
+/// group of its own again. Each group keeps only the vertices its
+/// triangles use; normals are kept if the mesh has them.
+pub(crate) fn mesh_components(mesh: &TriangleMesh) -> Vec<TriangleMesh> {
+    let mut weld: HashMap<[i64; 3], usize> = HashMap::new();
+    let ids: Vec<usize> = mesh
+        .vertices
+        .chunks_exact(3)
+        .map(|v| {
+            let key = Vec3::new(v[0] as f64, v[1] as f64, v[2] as f64).quantize(WELD_SCALE);
+            let next = weld.len();
+            *weld.entry(key).or_insert(next)
+        })
+        .collect();
+
+    // Union-find over triangles, joined through the first triangle seen on
+    // each welded edge
+    let triangles = mesh.num_triangles();
+    let mut parent: Vec<usize> = (0..triangles).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    let mut edge_owner: HashMap<(usize, usize), usize> = HashMap::new();
+    for (t, tri) in mesh.indices.chunks_exact(3).enumerate() {
+        let [a, b, c] = [0, 1, 2].map(|k| ids[tri[k] as usize]);
+        for (u, v) in [(a, b), (b, c), (c, a)] {
+            if u == v {
+                continue;
+            }
+            let owner = *edge_owner.entry((u.min(v), u.max(v))).or_insert(t);
+            let (ro, rt) = (root(&mut parent, owner), root(&mut parent, t));
+            parent[rt] = ro;
+        }
+    }
+
+    let has_normals = mesh.normals.len() == mesh.vertices.len();
+    let mut component_of: HashMap<usize, usize> = HashMap::new();
+    // Per component: the mesh, and the new index of each old vertex in it
+    let mut components: Vec<(TriangleMesh, HashMap<u32, u32>)> = Vec::new();
+    for (t, tri) in mesh.indices.chunks_exact(3).enumerate() {
+        let r = root(&mut parent, t);
+        let next = components.len();
+        let k = *component_of.entry(r).or_insert(next);
+        if k == next {
+            components.push((TriangleMesh::new(), HashMap::new()));
+        }
+        let (part, remap) = &mut components[k];
+        for &i in tri {
+            let index = *remap.entry(i).or_insert_with(|| {
+                let i = i as usize * 3;
+                part.vertices.extend_from_slice(&mesh.vertices[i..i + 3]);
+                if has_normals {
+                    part.normals.extend_from_slice(&mesh.normals[i..i + 3]);
+                }
+                part.num_vertices() as u32 - 1
+            });
+            part.indices.push(index);
+        }
+    }
+    let parts: Vec<TriangleMesh> = components.into_iter().map(|(part, _)| part).collect();
+    nest_voids(parts)
+}
+
+/// Merge each part that bounds a void into the part around it.
+fn nest_voids(parts: Vec<TriangleMesh>) -> Vec<TriangleMesh> {
+    // The parts each part lies inside, judged by one of its vertices
+    let enclosing: Vec<Vec<usize>> = parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let v = &part.vertices;
+            let point = Point3::new(v[0] as f64, v[1] as f64, v[2] as f64);
+            (0..parts.len())
+                .filter(|&j| j != i && point_in_mesh_robust(&point, &parts[j]))
+                .collect()
+        })
+        .collect();
+
+    let mut host: Vec<usize> = (0..parts.len()).collect();
+    for (i, around) in enclosing.iter().enumerate() {
+        if around.len() % 2 == 1 {
+            // The innermost enclosing part is the one inside the most others
+            if let Some(&outer) = around.iter().max_by_key(|&&j| enclosing[j].len()) {
+                host[i] = outer;
+            }
+        }
+    }
+
+    let mut merged: Vec<Option<TriangleMesh>> = parts.into_iter().map(Some).collect();
+    for (i, &h) in host.iter().enumerate() {
+        if h == i {
+            continue;
+        }
+        let Some(void) = merged[i].take() else {
+            continue;
+        };
+        match merged[h].as_mut() {
+            Some(outer) => outer.merge(&void),
+            // Inconsistent nesting; keep the void as a part of its own
+            None => merged[i] = Some(void),
+        }
+    }
+    merged.into_iter().flatten().collect()
+}
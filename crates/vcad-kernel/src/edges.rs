@@ -0,0 +1,72 @@
+//! Feature edge extraction for wireframe overlays.
+//!
+//! Works on the tessellation: faces are tessellated separately, so vertices
+//! are welded by position before triangles are matched across edges.
+
+use std::collections::HashMap;
+
+use vcad_kernel_math::{Point3, Quantize, Vec3};
+use vcad_kernel_tessellate::TriangleMesh;
+
+/// Welding grid, in vertices per model unit. Coarse enough to absorb the
+/// `f32` rounding between two faces' copies of a shared boundary point.
+pub(crate) const WELD_SCALE: f64 = 1e4;
+
+/// Feature edges of a tessellated surface split into parts (one per B-rep
+/// face, or a single part for a mesh).
+///
+/// An edge is a feature edge if it is used by other than two triangles, if
+/// its two triangles belong to different parts, or if their normals differ
+/// by more than `angle_threshold_deg`.
+pub(crate) fn feature_edges(
+    parts: &[&TriangleMesh],
+    angle_threshold_deg: f64,
+) -> Vec<(Point3, Point3)> {
+    let cos_threshold = angle_threshold_deg.to_radians().cos();
+
+    let mut weld: HashMap<[i64; 3], usize> = HashMap::new();
+    let mut points: Vec<Point3> = Vec::new();
+    // Undirected welded edge -> (part, unit normal) of each triangle using it
+    let mut edges: HashMap<(usize, usize), Vec<(usize, Vec3)>> = HashMap::new();
+    let mut order: Vec<(usize, usize)> = Vec::new();
+
+    for (part, mesh) in parts.iter().enumerate() {
+        let ids: Vec<usize> = mesh
+            .vertices
+            .chunks_exact(3)
+            .map(|v| {
+                let p = Point3::new(v[0] as f64, v[1] as f64, v[2] as f64);
+                *weld.entry(p.quantize(WELD_SCALE)).or_insert_with(|| {
+                    points.push(p);
+                    points.len() - 1
+                })
+            })
+            .collect();
+
+        for tri in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| ids[tri[k] as usize]);
+            let normal = (points[b] - points[a]).cross(&(points[c] - points[a]));
+            if a == b || b == c || c == a || normal.norm() < 1e-12 {
+                continue;
+            }
+            let normal = normal.normalize();
+            for (u, v) in [(a, b), (b, c), (c, a)] {
+                let key = (u.min(v), u.max(v));
+                let uses = edges.entry(key).or_default();
+                if uses.is_empty() {
+                    order.push(key);
+                }
+                uses.push((part, normal));
+            }
+        }
+    }
+
+    order
+        .into_iter()
+        .filter(|key| match edges[key].as_slice() {
+            [(part_a, n_a), (part_b, n_b)] => part_a != part_b || n_a.dot(n_b) < cos_threshold,
+            _ => true,
+        })
+        .map(|(u, v)| (points[u], points[v]))
+        .collect()
+}
@@ -17,6 +17,12 @@
 
 use std::path::Path;
 
+mod clearance;
+mod components;
+mod edges;
+mod project;
+mod section;
+
 pub use vcad_kernel_booleans;
 pub use vcad_kernel_constraints;
 pub use vcad_kernel_fillet;
@@ -35,7 +41,7 @@ use vcad_kernel_booleans::{boolean_op, BooleanOp, BooleanResult};
 use vcad_kernel_math::{Point3, Transform, Vec3};
 use vcad_kernel_primitives::BRepSolid;
 use vcad_kernel_step::StepError;
-use vcad_kernel_tessellate::{tessellate_brep, TriangleMesh};
+use vcad_kernel_tessellate::{tessellate_brep, tessellate_brep_faces, StlError, TriangleMesh};
 
 /// Error returned when STEP export fails.
 #[derive(Debug)]
@@ -128,6 +134,17 @@ impl Solid {
         }
     }
 
+    /// Create a box with dimensions `(sx, sy, sz)`, placed at the origin by
+    /// its min corner or its center.
+    pub fn cube_at(sx: f64, sy: f64, sz: f64, origin: vcad_kernel_primitives::BoxOrigin) -> Self {
+        Self {
+            repr: SolidRepr::BRep(Box::new(vcad_kernel_primitives::make_cube_at(
+                sx, sy, sz, origin,
+            ))),
+            segments: 32,
+        }
+    }
+
     /// Create a cylinder along Z axis with the given radius and height.
     pub fn cylinder(radius: f64, height: f64, segments: u32) -> Self {
         Self {
@@ -138,6 +155,22 @@ impl Solid {
         }
     }
 
+    /// Create a cylinder along Z axis, with its bottom cap or its center at
+    /// the origin.
+    pub fn cylinder_at(
+        radius: f64,
+        height: f64,
+        segments: u32,
+        origin: vcad_kernel_primitives::BoxOrigin,
+    ) -> Self {
+        Self {
+            repr: SolidRepr::BRep(Box::new(vcad_kernel_primitives::make_cylinder_at(
+                radius, height, segments, origin,
+            ))),
+            segments,
+        }
+    }
+
     /// Create a sphere centered at origin with the given radius.
     pub fn sphere(radius: f64, segments: u32) -> Self {
         Self {
@@ -180,14 +213,20 @@ impl Solid {
         self.boolean(other, BooleanOp::Intersection)
     }
 
+    /// Boolean symmetric difference (self ⊕ other): the parts of each solid
+    /// outside the other.
+    pub fn xor(&self, other: &Solid) -> Solid {
+        self.boolean(other, BooleanOp::Xor)
+    }
+
     fn boolean(&self, other: &Solid, op: BooleanOp) -> Solid {
         match (&self.repr, &other.repr) {
             (SolidRepr::Empty, _) => match op {
-                BooleanOp::Union => other.clone(),
+                BooleanOp::Union | BooleanOp::Xor => other.clone(),
                 BooleanOp::Difference | BooleanOp::Intersection => Solid::empty(),
             },
             (_, SolidRepr::Empty) => match op {
-                BooleanOp::Union | BooleanOp::Difference => self.clone(),
+                BooleanOp::Union | BooleanOp::Difference | BooleanOp::Xor => self.clone(),
                 BooleanOp::Intersection => Solid::empty(),
             },
             (SolidRepr::BRep(a), SolidRepr::BRep(b)) => {
@@ -221,6 +260,99 @@ impl Solid {
         }
     }
 
+    // =========================================================================
+    // Splitting
+    // =========================================================================
+
+    /// Cut the solid by a plane into the parts on either side of it.
+    ///
+    /// Returns `(positive, negative)`: the part on the side `plane_normal`
+    /// points to, and the part behind the plane. Each is closed by a planar
+    /// cap where the plane cut through the solid. A side the solid doesn't
+    /// reach comes back empty.
+    ///
+    /// Computed as intersections with two boxes, each covering one
+    /// half-space over the extent of the solid.
+    pub fn split_by_plane(&self, plane_origin: Point3, plane_normal: Vec3) -> (Solid, Solid) {
+        if self.is_empty() || plane_normal.norm() < 1e-12 {
+            return (self.clone(), Solid::empty());
+        }
+        let positive = self.intersection(&self.half_space(plane_origin, plane_normal));
+        let negative = self.intersection(&self.half_space(plane_origin, -plane_normal));
+        (positive, negative)
+    }
+
+    /// Combine two solids that don't overlap, e.g. the halves from
+    /// [`Solid::split_by_plane`], without running a boolean.
+    ///
+    /// Both boundaries are kept as they are, so touching solids stay
+    /// separate bodies. The result is mesh-only.
+    pub fn merge_disjoint(&self, other: &Solid) -> Solid {
+        if other.is_empty() {
+            return self.clone();
+        }
+        if self.is_empty() {
+            return other.clone();
+        }
+        let segments = self.segments.max(other.segments);
+        let mut mesh = self.to_mesh(segments);
+        mesh.merge(&other.to_mesh(segments));
+        Solid {
+            repr: SolidRepr::Mesh(mesh),
+            segments,
+        }
+    }
+
+    /// Separate the solid into its connected pieces, e.g. the two halves
+    /// left after a difference cuts a bar in two.
+    ///
+    /// Triangles of the tessellation that share an edge belong to the same
+    /// piece, so pieces touching along an edge stay together. The wall of
+    /// an internal void belongs to the piece around it.
+    /// A solid that is already one piece is returned unchanged; otherwise
+    /// the pieces are mesh-only. An empty solid has no pieces.
+    pub fn split_components(&self) -> Vec<Solid> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let components = components::mesh_components(&self.to_mesh(self.segments));
+        if components.len() <= 1 {
+            return vec![self.clone()];
+        }
+        components
+            .into_iter()
+            .map(|mesh| Solid {
+                repr: SolidRepr::Mesh(mesh),
+                segments: self.segments,
+            })
+            .collect()
+    }
+
+    /// A box with one face on the plane, extending along `normal` far
+    /// enough to contain every part of this solid on that side.
+    fn half_space(&self, origin: Point3, normal: Vec3) -> Solid {
+        let (min, max) = self.bounding_box();
+        let (min, max) = (Point3::from(min), Point3::from(max));
+        let center = Point3::from((min.coords + max.coords) / 2.0);
+        let size = 2.0 * ((max - min).norm() + (center - origin).norm()) + 1.0;
+
+        let plane = vcad_kernel_geom::Plane::from_normal(origin, normal);
+        let (x, y, n) = (
+            plane.x_dir.as_ref(),
+            plane.y_dir.as_ref(),
+            plane.normal_dir.as_ref(),
+        );
+        let frame = Transform::from_rows(&[
+            [x.x, y.x, n.x, origin.x],
+            [x.y, y.y, n.y, origin.y],
+            [x.z, y.z, n.z, origin.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        Solid::cube(size, size, size)
+            .translate(-size / 2.0, -size / 2.0, 0.0)
+            .transform(&frame)
+    }
+
     // =========================================================================
     // Fillet & chamfer
     // =========================================================================
@@ -290,6 +422,28 @@ impl Solid {
         }
     }
 
+    /// Minkowski sum of the solid with a sphere of `radius`.
+    ///
+    /// Faces move outward by `radius`, convex edges become cylindrical
+    /// blends and convex corners spherical patches, each rounded with
+    /// `segments` steps per full turn. A cube grows by `2 * radius` along
+    /// each axis and has no sharp edges left. Concave regions are not
+    /// trimmed, so the result is only exact for convex solids. The result is
+    /// mesh-only.
+    pub fn minkowski_sphere(&self, radius: f64, segments: u32) -> Solid {
+        match &self.repr {
+            SolidRepr::Empty => Solid::empty(),
+            _ => Solid {
+                repr: SolidRepr::Mesh(vcad_kernel_shell::minkowski_sphere_mesh(
+                    &self.to_mesh(segments),
+                    radius,
+                    segments,
+                )),
+                segments: self.segments,
+            },
+        }
+    }
+
     // =========================================================================
     // Pattern operations
     // =========================================================================
@@ -367,7 +521,7 @@ impl Solid {
             let t_back = Transform::translation(axis_origin.x, axis_origin.y, axis_origin.z);
             // Compose: first translate to origin, then rotate, then translate back
             let composed = t_back.then(&rot).then(&t_to_origin);
-            let copy = self.apply_transform(&composed);
+            let copy = self.transform(&composed);
             result = result.union(&copy);
         }
         result
@@ -428,6 +582,62 @@ impl Solid {
         })
     }
 
+    /// Create a solid of extruded text.
+    ///
+    /// Each glyph outline is laid out on the plane at `origin` spanned by
+    /// `x_dir` and `y_dir`, with contours nested inside another contour
+    /// becoming holes (and contours inside those holes becoming islands
+    /// again). The glyphs are extruded by `depth` along the plane normal.
+    ///
+    /// # Returns
+    ///
+    /// A mesh-only solid (the glyphs are merged without a boolean), or an
+    /// empty solid if no character has an outline.
+    #[allow(clippy::too_many_arguments)]
+    pub fn text(
+        content: &str,
+        font: &vcad_kernel_text::Font,
+        height: f64,
+        depth: f64,
+        origin: Point3,
+        x_dir: Vec3,
+        y_dir: Vec3,
+        alignment: vcad_kernel_text::TextAlignment,
+    ) -> Result<Self, vcad_kernel_sketch::SketchError> {
+        let profiles =
+            vcad_kernel_text::text_to_profiles(content, font, height, 1.0, 1.0, alignment);
+        let direction = x_dir.cross(&y_dir).normalize() * depth;
+
+        // Nesting depth of each contour: even depths are solid regions, odd
+        // depths are holes in the innermost contour containing them
+        let containers: Vec<Vec<usize>> = (0..profiles.len())
+            .map(|i| {
+                (0..profiles.len())
+                    .filter(|&j| j != i && profiles[i].is_contained_in(&profiles[j]))
+                    .collect()
+            })
+            .collect();
+
+        let mut result = Solid::empty();
+        for (i, outer) in profiles.iter().enumerate() {
+            if containers[i].len() % 2 == 1 {
+                continue;
+            }
+            let holes = (0..profiles.len())
+                .filter(|&j| {
+                    containers[j].len() == containers[i].len() + 1 && containers[j].contains(&i)
+                })
+                .map(|j| profiles[j].segments.clone())
+                .collect();
+            let profile = outer
+                .clone()
+                .with_holes(holes)?
+                .transform(origin, x_dir, y_dir);
+            result = result.merge_disjoint(&Solid::extrude(profile, direction)?);
+        }
+        Ok(result)
+    }
+
     /// Create a solid by revolving a sketch profile around an axis.
     ///
     /// # Arguments
@@ -481,6 +691,21 @@ impl Solid {
         })
     }
 
+    /// Sweep like [`Solid::sweep`], also returning the degenerate path
+    /// stations skipped or interpolated per the options' `on_degenerate`.
+    pub fn sweep_with_warnings<P: vcad_kernel_geom::Curve3d>(
+        profile: vcad_kernel_sketch::SketchProfile,
+        path: &P,
+        options: vcad_kernel_sweep::SweepOptions,
+    ) -> Result<(Self, Vec<vcad_kernel_sweep::SweepWarning>), vcad_kernel_sweep::SweepError> {
+        let result = vcad_kernel_sweep::sweep_with_warnings(&profile, path, options)?;
+        let solid = Solid {
+            repr: SolidRepr::BRep(Box::new(result.solid)),
+            segments: 32,
+        };
+        Ok((solid, result.warnings))
+    }
+
     /// Create a solid by lofting between multiple profiles.
     ///
     /// # Arguments
@@ -502,6 +727,20 @@ impl Solid {
         })
     }
 
+    /// Loft like [`Solid::loft`], also returning the degenerate profiles
+    /// skipped or interpolated per the options' `on_degenerate`.
+    pub fn loft_with_warnings(
+        profiles: &[vcad_kernel_sketch::SketchProfile],
+        options: vcad_kernel_sweep::LoftOptions,
+    ) -> Result<(Self, Vec<vcad_kernel_sweep::LoftWarning>), vcad_kernel_sweep::LoftError> {
+        let result = vcad_kernel_sweep::loft_with_warnings(profiles, options)?;
+        let solid = Solid {
+            repr: SolidRepr::BRep(Box::new(result.solid)),
+            segments: 32,
+        };
+        Ok((solid, result.warnings))
+    }
+
     // =========================================================================
     // Transforms
     // =========================================================================
@@ -509,7 +748,7 @@ impl Solid {
     /// Translate the solid by `(x, y, z)`.
     pub fn translate(&self, x: f64, y: f64, z: f64) -> Solid {
         let t = Transform::translation(x, y, z);
-        self.apply_transform(&t)
+        self.transform(&t)
     }
 
     /// Rotate the solid by angles in degrees around X, Y, Z axes.
@@ -519,16 +758,19 @@ impl Solid {
         let rz = Transform::rotation_z(z_deg.to_radians());
         // Apply Z, then Y, then X (Euler XYZ intrinsic rotation)
         let t = rx.then(&ry).then(&rz);
-        self.apply_transform(&t)
+        self.transform(&t)
     }
 
     /// Scale the solid by `(x, y, z)`.
     pub fn scale(&self, x: f64, y: f64, z: f64) -> Solid {
         let t = Transform::scale(x, y, z);
-        self.apply_transform(&t)
+        self.transform(&t)
     }
 
-    fn apply_transform(&self, transform: &Transform) -> Solid {
+    /// Apply an arbitrary affine transform to the solid.
+    ///
+    /// Face orientations are flipped when the transform mirrors geometry.
+    pub fn transform(&self, transform: &Transform) -> Solid {
         match &self.repr {
             SolidRepr::Empty => Solid::empty(),
             SolidRepr::BRep(brep) => {
@@ -607,6 +849,25 @@ impl Solid {
         }
     }
 
+    /// Edges to draw over the shaded model, as line segments.
+    ///
+    /// Taken from the tessellation: an edge is kept where the triangles on
+    /// either side meet at more than `angle_threshold_deg` degrees, or where
+    /// the surface is open. For B-rep solids every boundary between two
+    /// faces is kept too, even a tangent one such as the edge of a fillet;
+    /// seams inside a single curved face are not.
+    pub fn feature_edges(&self, angle_threshold_deg: f64) -> Vec<(Point3, Point3)> {
+        match &self.repr {
+            SolidRepr::Empty => Vec::new(),
+            SolidRepr::BRep(brep) => {
+                let faces = tessellate_brep_faces(brep.as_ref(), self.segments);
+                let parts: Vec<&TriangleMesh> = faces.iter().map(|(_, mesh)| mesh).collect();
+                edges::feature_edges(&parts, angle_threshold_deg)
+            }
+            SolidRepr::Mesh(m) => edges::feature_edges(&[m], angle_threshold_deg),
+        }
+    }
+
     /// Compute the volume of the solid from its triangle mesh.
     pub fn volume(&self) -> f64 {
         let mesh = self.to_mesh(self.segments);
@@ -664,12 +925,201 @@ impl Solid {
         compute_center_of_mass(&mesh)
     }
 
+    /// Compute the inertia tensor about the center of mass for unit density.
+    ///
+    /// Integrates second moments over the tessellated volume. Multiply by a
+    /// material density to get physical moments of inertia.
+    pub fn inertia_tensor(&self) -> [[f64; 3]; 3] {
+        let mesh = self.to_mesh(self.segments);
+        compute_inertia_tensor(&mesh)
+    }
+
+    /// Whether the tessellated mesh is closed and consistently wound.
+    ///
+    /// Boolean results can leave gaps along seams; check this before export.
+    pub fn is_watertight(&self) -> bool {
+        self.to_mesh(self.segments).is_watertight()
+    }
+
     /// Number of triangles in the tessellated mesh.
     pub fn num_triangles(&self) -> usize {
         let mesh = self.to_mesh(self.segments);
         mesh.num_triangles()
     }
 
+    /// Volume shared by this solid and `other`, or 0 if they don't overlap.
+    ///
+    /// Solids that only touch have no interference.
+    pub fn interference_volume(&self, other: &Solid) -> f64 {
+        if self.is_empty() || other.is_empty() {
+            return 0.0;
+        }
+        let (min_a, max_a) = self.bounding_box();
+        let (min_b, max_b) = other.bounding_box();
+        if (0..3).any(|k| min_a[k] >= max_b[k] || min_b[k] >= max_a[k]) {
+            return 0.0;
+        }
+        self.intersection(other).volume().max(0.0)
+    }
+
+    /// Smallest distance between the surfaces of this solid and `other`.
+    ///
+    /// Returns 0 if the solids interfere or touch, and infinity if either
+    /// is empty. Measured on the tessellations, so curved surfaces are
+    /// only as accurate as their facets.
+    pub fn min_clearance(&self, other: &Solid) -> f64 {
+        if self.is_empty() || other.is_empty() {
+            return f64::INFINITY;
+        }
+        if self.interference_volume(other) > 0.0 {
+            return 0.0;
+        }
+        clearance::mesh_distance(&self.to_mesh(self.segments), &other.to_mesh(other.segments))
+    }
+
+    // =========================================================================
+    // Sections
+    // =========================================================================
+
+    /// Compute the planar cross-section of the solid.
+    ///
+    /// Each face is intersected with the plane through `plane_origin` with
+    /// normal `plane_normal`, and the resulting segments are chained into
+    /// closed loops. Loops are returned as line-only profiles in the plane's
+    /// coordinate frame (see [`vcad_kernel_geom::Plane::from_normal`]), with
+    /// outer boundaries counter-clockwise and holes clockwise. A tube, for
+    /// example, yields two loops.
+    ///
+    /// Faces lying in the cutting plane contribute their outline. For B-rep
+    /// solids with only planar faces the cut is computed exactly from the
+    /// face boundaries; otherwise the tessellated mesh is sectioned.
+    pub fn section(
+        &self,
+        plane_origin: Point3,
+        plane_normal: Vec3,
+    ) -> Vec<vcad_kernel_sketch::SketchProfile> {
+        let (polygons, tol) = self.section_polygons();
+        section::section_polygons(&polygons, plane_origin, plane_normal, tol)
+    }
+
+    /// Slice the solid into horizontal layers, e.g. for 3D-print previews.
+    ///
+    /// Returns one entry per height in `z_heights`, holding the closed
+    /// contours of the cut by the plane `z = height` with the same frame and
+    /// winding conventions as [`Solid::section`]. A layer may hold several
+    /// loops (disjoint islands and holes), and heights above or below the
+    /// solid yield an empty layer. The solid is tessellated at most once.
+    pub fn slice(&self, z_heights: &[f64]) -> Vec<Vec<vcad_kernel_sketch::SketchProfile>> {
+        let (polygons, tol) = self.section_polygons();
+        z_heights
+            .iter()
+            .map(|&z| {
+                section::section_polygons(&polygons, Point3::new(0.0, 0.0, z), Vec3::z(), tol)
+            })
+            .collect()
+    }
+
+    /// Planar polygons covering the solid's boundary, with the tolerance to
+    /// section them at: exact face boundaries when every face is planar,
+    /// otherwise the triangles of the tessellated mesh.
+    fn section_polygons(&self) -> (Vec<section::PlanarPolygon>, f64) {
+        use section::{PlanarPolygon, BREP_TOLERANCE, MESH_TOLERANCE};
+        use vcad_kernel_geom::{Plane, SurfaceKind};
+
+        if let SolidRepr::BRep(brep) = &self.repr {
+            let all_planar = brep
+                .geometry
+                .surfaces
+                .iter()
+                .all(|s| s.surface_type() == SurfaceKind::Plane);
+            if all_planar {
+                let topo = &brep.topology;
+                let polygons: Vec<PlanarPolygon> = topo
+                    .faces
+                    .values()
+                    .filter_map(|face| {
+                        let plane = brep.geometry.surfaces[face.surface_index]
+                            .as_any()
+                            .downcast_ref::<Plane>()?;
+                        let rings = std::iter::once(face.outer_loop)
+                            .chain(face.inner_loops.iter().copied())
+                            .map(|l| {
+                                topo.loop_vertices(l)
+                                    .into_iter()
+                                    .map(|v| topo.vertices[v].point)
+                                    .collect()
+                            })
+                            .collect();
+                        Some(PlanarPolygon {
+                            rings,
+                            normal: *plane.normal_dir.as_ref(),
+                        })
+                    })
+                    .collect();
+                return (polygons, BREP_TOLERANCE);
+            }
+        }
+
+        let mesh = self.to_mesh(self.segments);
+        let vertex = |i: u32| {
+            let i = i as usize * 3;
+            Point3::new(
+                mesh.vertices[i] as f64,
+                mesh.vertices[i + 1] as f64,
+                mesh.vertices[i + 2] as f64,
+            )
+        };
+        let polygons: Vec<PlanarPolygon> = mesh
+            .indices
+            .chunks(3)
+            .filter_map(|tri| {
+                let (a, b, c) = (vertex(tri[0]), vertex(tri[1]), vertex(tri[2]));
+                let normal = (b - a).cross(&(c - a));
+                if normal.norm() < 1e-12 {
+                    return None;
+                }
+                Some(PlanarPolygon {
+                    rings: vec![vec![a, b, c]],
+                    normal,
+                })
+            })
+            .collect();
+        (polygons, MESH_TOLERANCE)
+    }
+
+    /// Project the solid's silhouette onto a plane.
+    ///
+    /// Edges of the tessellated solid where a triangle facing along
+    /// `plane_normal` meets one facing away (or edge-on) are projected onto
+    /// the plane through `plane_origin` and chained into closed loops. Loops
+    /// use the same frame and winding conventions as [`Solid::section`]: a
+    /// cylinder projected along its axis yields one circular loop, a tube two.
+    ///
+    /// Silhouettes of faces at different depths are all returned, even where
+    /// they overlap in projection.
+    pub fn project_to_plane(
+        &self,
+        plane_origin: Point3,
+        plane_normal: Vec3,
+    ) -> Vec<vcad_kernel_sketch::SketchProfile> {
+        let mesh = self.to_mesh(self.segments);
+        project::silhouette_profiles(&mesh, plane_origin, plane_normal, section::MESH_TOLERANCE)
+    }
+
+    /// Project the solid's silhouette onto a plane as a single profile.
+    ///
+    /// Like [`Solid::project_to_plane`], but the loops must form one outline:
+    /// the outer loop becomes the profile and the loops inside it its holes,
+    /// ready to extrude. Returns `None` if the silhouette is empty or has
+    /// several separate outlines.
+    pub fn project_to_sketch(
+        &self,
+        plane_origin: Point3,
+        plane_normal: Vec3,
+    ) -> Option<vcad_kernel_sketch::SketchProfile> {
+        project::single_outline(self.project_to_plane(plane_origin, plane_normal))
+    }
+
     // =========================================================================
     // STEP import/export
     // =========================================================================
@@ -762,6 +1212,55 @@ impl Solid {
             .collect())
     }
 
+    /// Import a solid from an STL file (binary or ASCII).
+    ///
+    /// Coincident triangle corners are welded and the mesh is wrapped in a
+    /// B-rep with one planar face per triangle, as for shelled solids.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StlError` if the file cannot be read or is not valid STL.
+    pub fn from_stl(path: impl AsRef<Path>) -> Result<Self, StlError> {
+        let data = std::fs::read(path)?;
+        Self::from_stl_bytes(&data)
+    }
+
+    /// Import a solid from an in-memory STL file (binary or ASCII).
+    ///
+    /// See [`Solid::from_stl`].
+    pub fn from_stl_bytes(data: &[u8]) -> Result<Self, StlError> {
+        let mesh = TriangleMesh::from_stl_bytes(data)?;
+        Ok(Self {
+            repr: SolidRepr::BRep(Box::new(vcad_kernel_shell::mesh_to_brep(&mesh))),
+            segments: 32,
+        })
+    }
+
+    /// Wind the solid's surface consistently, with normals pointing out.
+    ///
+    /// Meant for imported meshes (see [`Solid::from_stl`]), whose triangles
+    /// may be wound either way; see [`TriangleMesh::orient_outward`]. The
+    /// outer surface faces out and the walls of internal voids face into
+    /// the void. A
+    /// B-rep that needs fixing is rebuilt from its corrected mesh, with one
+    /// planar face per triangle; a consistent one is left untouched.
+    ///
+    /// Returns the number of triangles flipped.
+    pub fn fix_orientation(&mut self) -> usize {
+        match &mut self.repr {
+            SolidRepr::Empty => 0,
+            SolidRepr::Mesh(mesh) => mesh.orient_outward(),
+            SolidRepr::BRep(brep) => {
+                let mut mesh = tessellate_brep(brep.as_ref(), self.segments);
+                let flipped = mesh.orient_outward();
+                if flipped > 0 {
+                    **brep = vcad_kernel_shell::mesh_to_brep(&mesh);
+                }
+                flipped
+            }
+        }
+    }
+
     /// Export this solid to a STEP file.
     ///
     /// # Arguments
@@ -980,6 +1479,52 @@ fn compute_center_of_mass(mesh: &TriangleMesh) -> [f64; 3] {
     [cx * s, cy * s, cz * s]
 }
 
+fn compute_inertia_tensor(mesh: &TriangleMesh) -> [[f64; 3]; 3] {
+    let verts = &mesh.vertices;
+    let vertex = |i: u32| {
+        let i = i as usize * 3;
+        [verts[i] as f64, verts[i + 1] as f64, verts[i + 2] as f64]
+    };
+    // Volume, first moments and second moments (products) about the origin,
+    // summed over tetrahedra spanned by the origin and each triangle.
+    let mut vol = 0.0;
+    let mut first = [0.0; 3];
+    let mut second = [[0.0; 3]; 3];
+    for tri in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [vertex(tri[0]), vertex(tri[1]), vertex(tri[2])];
+        let det = a[0] * (b[1] * c[2] - c[1] * b[2]) - b[0] * (a[1] * c[2] - c[1] * a[2])
+            + c[0] * (a[1] * b[2] - b[1] * a[2]);
+        let v = det / 6.0;
+        vol += v;
+        for i in 0..3 {
+            first[i] += v * (a[i] + b[i] + c[i]) / 4.0;
+            for j in 0..3 {
+                second[i][j] += v / 20.0
+                    * (2.0 * (a[i] * a[j] + b[i] * b[j] + c[i] * c[j])
+                        + a[i] * (b[j] + c[j])
+                        + b[i] * (a[j] + c[j])
+                        + c[i] * (a[j] + b[j]));
+            }
+        }
+    }
+    if vol.abs() < 1e-15 {
+        return [[0.0; 3]; 3];
+    }
+    // Inward-facing meshes integrate to negative volume; flip every moment.
+    let sign = vol.signum();
+    let vol = vol.abs();
+    let com = first.map(|m| sign * m / vol);
+
+    // Shift the second moments to the center of mass.
+    let c: [[f64; 3]; 3] = std::array::from_fn(|i| {
+        std::array::from_fn(|j| sign * second[i][j] - vol * com[i] * com[j])
+    });
+    let trace = c[0][0] + c[1][1] + c[2][2];
+    std::array::from_fn(|i| {
+        std::array::from_fn(|j| if i == j { trace - c[i][i] } else { -c[i][j] })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1058,6 +1603,94 @@ mod tests {
         assert!(!result.is_empty());
     }
 
+    #[test]
+    fn test_split_components_of_bisected_bar() {
+        let bar = Solid::cube(30.0, 10.0, 10.0);
+        let slot = Solid::cube(2.0, 20.0, 20.0).translate(14.0, -5.0, -5.0);
+        let pieces = bar.difference(&slot).split_components();
+        assert_eq!(pieces.len(), 2);
+        for piece in &pieces {
+            let (min, max) = piece.bounding_box();
+            assert!(max[0] <= 14.0 + 1e-6 || min[0] >= 16.0 - 1e-6);
+        }
+
+        assert_eq!(bar.split_components().len(), 1);
+        assert!(Solid::empty().split_components().is_empty());
+    }
+
+    #[test]
+    fn test_split_components_keeps_void_with_its_body() {
+        use vcad_kernel_primitives::BoxOrigin;
+
+        // A hollow box, a cube floating in its void, and a separate cube
+        let outer = Solid::cube_at(20.0, 20.0, 20.0, BoxOrigin::Center);
+        let void = Solid::cube_at(12.0, 12.0, 12.0, BoxOrigin::Center);
+        let island = Solid::cube_at(4.0, 4.0, 4.0, BoxOrigin::Center);
+        let apart = Solid::cube(5.0, 5.0, 5.0).translate(30.0, 0.0, 0.0);
+
+        let mut mesh = outer.to_mesh(32);
+        let mut inner = void.to_mesh(32);
+        for tri in inner.indices.chunks_exact_mut(3) {
+            tri.swap(1, 2);
+        }
+        mesh.merge(&inner);
+        mesh.merge(&island.to_mesh(32));
+        mesh.merge(&apart.to_mesh(32));
+
+        let pieces = components::mesh_components(&mesh);
+        let mut volumes: Vec<f64> = pieces.iter().map(|p| p.signed_volume()).collect();
+        volumes.sort_by(f64::total_cmp);
+        let expected = [64.0, 125.0, 8000.0 - 1728.0];
+        assert_eq!(volumes.len(), expected.len());
+        for (volume, expected) in volumes.iter().zip(expected) {
+            assert!((volume - expected).abs() < 1e-6, "{volume}");
+        }
+        assert!(pieces.iter().all(|p| p.is_watertight()));
+    }
+
+    #[test]
+    fn test_split_cube_through_center() {
+        let cube = Solid::cube(10.0, 10.0, 10.0);
+        let (pos, neg) = cube.split_by_plane(Point3::new(5.0, 5.0, 5.0), Vec3::x());
+        assert!((pos.volume() - 500.0).abs() < 1.0, "{}", pos.volume());
+        assert!((neg.volume() - 500.0).abs() < 1.0, "{}", neg.volume());
+        assert!(pos.bounding_box().0[0] > 5.0 - 1e-6);
+        assert!(neg.bounding_box().1[0] < 5.0 + 1e-6);
+
+        // Each half is closed by a 10x10 cap on x = 5, facing away from
+        // the half it belongs to
+        for (half, facing) in [(&pos, -1.0), (&neg, 1.0)] {
+            let mesh = half.to_mesh(32);
+            let v = |i: u32| {
+                let i = i as usize * 3;
+                Vec3::new(
+                    mesh.vertices[i] as f64,
+                    mesh.vertices[i + 1] as f64,
+                    mesh.vertices[i + 2] as f64,
+                )
+            };
+            let cap_area: f64 = mesh
+                .indices
+                .chunks(3)
+                .map(|t| (v(t[0]), v(t[1]), v(t[2])))
+                .filter(|(a, b, c)| [a, b, c].iter().all(|p| (p.x - 5.0).abs() < 1e-4))
+                .map(|(a, b, c)| (b - a).cross(&(c - a)).x * facing / 2.0)
+                .sum();
+            assert!((cap_area - 100.0).abs() < 1e-3, "cap area {cap_area}");
+        }
+
+        let both = pos.merge_disjoint(&neg);
+        assert!((both.volume() - 1000.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_split_misses_solid() {
+        let cube = Solid::cube(10.0, 10.0, 10.0);
+        let (pos, neg) = cube.split_by_plane(Point3::new(0.0, 0.0, 20.0), Vec3::z());
+        assert!(pos.is_empty() || pos.volume().abs() < 1e-6);
+        assert!((neg.volume() - 1000.0).abs() < 1.0);
+    }
+
     #[test]
     fn test_plate_with_hole_via_solid_api() {
         // This mirrors the exact code path used by the WASM/app
@@ -1120,6 +1753,48 @@ mod tests {
         assert!((max[2] - min[2] - 30.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_feature_edges() {
+        let cube = Solid::cube(10.0, 20.0, 30.0);
+        let edges = cube.feature_edges(30.0);
+        assert_eq!(edges.len(), 12);
+        let total: f64 = edges.iter().map(|(a, b)| (b - a).norm()).sum();
+        assert!((total - 4.0 * 60.0).abs() < 1e-6, "total length {total}");
+
+        // Same answer from the mesh alone, where only the angle counts
+        let mesh = Solid::from_mesh(cube.to_mesh(32));
+        assert_eq!(mesh.feature_edges(30.0).len(), 12);
+
+        assert!(Solid::sphere(10.0, 32).feature_edges(30.0).is_empty());
+
+        // A cylinder keeps its two rims but not its seam
+        let cyl = Solid::cylinder(5.0, 10.0, 32);
+        let edges = cyl.feature_edges(30.0);
+        assert_eq!(edges.len(), 64);
+        assert!(edges
+            .iter()
+            .all(|(a, b)| a.z.abs() < 1e-6 && b.z.abs() < 1e-6
+                || (a.z - 10.0).abs() < 1e-6 && (b.z - 10.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_cube_origin_matches_mesh() {
+        use vcad_kernel_primitives::BoxOrigin;
+
+        // Both conventions hold for the tessellated mesh, not just the B-rep
+        for (origin, min) in [
+            (BoxOrigin::Corner, [0.0; 3]),
+            (BoxOrigin::Center, [-5.0, -10.0, -15.0]),
+        ] {
+            let mesh = Solid::cube_at(10.0, 20.0, 30.0, origin).to_mesh(32);
+            let (lo, hi) = compute_bounding_box(&mesh);
+            for i in 0..3 {
+                assert!((lo[i] - min[i]).abs() < 1e-6);
+                assert!((hi[i] - min[i] - [10.0, 20.0, 30.0][i]).abs() < 1e-6);
+            }
+        }
+    }
+
     #[test]
     fn test_cube_center_of_mass() {
         let cube = Solid::cube(10.0, 10.0, 10.0);
@@ -1129,6 +1804,33 @@ mod tests {
         assert!((com[2] - 5.0).abs() < 0.1, "cz: {}", com[2]);
     }
 
+    #[test]
+    fn test_box_inertia_tensor() {
+        let (a, b, c) = (10.0, 20.0, 30.0);
+        let solid = Solid::cube(a, b, c);
+        let tensor = solid.inertia_tensor();
+        // Unit density: mass equals volume.
+        let m = a * b * c;
+        let expected = [
+            m / 12.0 * (b * b + c * c),
+            m / 12.0 * (a * a + c * c),
+            m / 12.0 * (a * a + b * b),
+        ];
+        for (i, row) in tensor.iter().enumerate() {
+            assert!(
+                (row[i] - expected[i]).abs() < 1e-6 * expected[i],
+                "I{i}{i}: {} vs {}",
+                row[i],
+                expected[i]
+            );
+            for (j, value) in row.iter().enumerate() {
+                if i != j {
+                    assert!(value.abs() < 1e-6 * m, "I{i}{j}: {value}");
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_rotate_cube_volume() {
         let cube = Solid::cube(10.0, 10.0, 10.0);
@@ -1253,6 +1955,38 @@ mod tests {
         assert!(vol > 100.0, "expected positive volume, got {vol}");
     }
 
+    #[test]
+    fn test_text() {
+        let font = vcad_kernel_text::FontRegistry::builtin_sans();
+        let text = |content: &str| {
+            Solid::text(
+                content,
+                font,
+                10.0,
+                2.0,
+                Point3::new(0.0, 0.0, 5.0),
+                Vec3::x(),
+                Vec3::y(),
+                vcad_kernel_text::TextAlignment::Left,
+            )
+            .unwrap()
+        };
+
+        let solid = text("AB");
+        assert!(solid.volume() > 0.0);
+        let (min, max) = solid.bounding_box();
+        assert!((min[2] - 5.0).abs() < 1e-9);
+        assert!((max[2] - 7.0).abs() < 1e-9);
+
+        // The counter of the "O" is a hole, not extra material
+        let o = text("O");
+        let (min, max) = o.bounding_box();
+        let filled = (max[0] - min[0]) * (max[1] - min[1]) * 2.0;
+        assert!(o.volume() < 0.6 * filled);
+
+        assert!(text(" ").is_empty());
+    }
+
     #[test]
     fn test_extrude_then_boolean() {
         use vcad_kernel_sketch::SketchProfile;
@@ -1351,6 +2085,146 @@ mod tests {
         assert!(shell.is_empty());
     }
 
+    #[test]
+    fn test_section_cube_through_center() {
+        let cube = Solid::cube(10.0, 10.0, 10.0);
+        let loops = cube.section(Point3::new(5.0, 5.0, 5.0), Vec3::z());
+        assert_eq!(loops.len(), 1, "expected a single loop");
+        let profile = &loops[0];
+        assert_eq!(profile.segments.len(), 4);
+        assert!((profile.signed_area() - 100.0).abs() < 1e-9);
+        let (min, max) = profile.bounding_box_2d();
+        assert!((max.x - min.x - 10.0).abs() < 1e-9);
+        assert!((max.y - min.y - 10.0).abs() < 1e-9);
+        for v in profile.vertices_3d() {
+            assert!((v.z - 5.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_section_coincident_face() {
+        let cube = Solid::cube(10.0, 10.0, 10.0);
+        for z in [0.0, 10.0] {
+            let loops = cube.section(Point3::new(0.0, 0.0, z), Vec3::z());
+            assert_eq!(loops.len(), 1, "z={z}");
+            assert!((loops[0].signed_area() - 100.0).abs() < 1e-9);
+        }
+        assert!(cube
+            .section(Point3::new(0.0, 0.0, 20.0), Vec3::z())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_section_tube_two_loops() {
+        let outer = Solid::cube(20.0, 20.0, 10.0);
+        let inner = Solid::cube(10.0, 10.0, 20.0).translate(5.0, 5.0, -5.0);
+        let tube = outer.difference(&inner);
+        let loops = tube.section(Point3::new(0.0, 0.0, 5.0), Vec3::z());
+        assert_eq!(loops.len(), 2);
+        let mut areas: Vec<f64> = loops.iter().map(|p| p.signed_area()).collect();
+        areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((areas[0] + 100.0).abs() < 0.5, "hole area: {}", areas[0]);
+        assert!((areas[1] - 400.0).abs() < 0.5, "outer area: {}", areas[1]);
+    }
+
+    #[test]
+    fn test_minkowski_sphere_rounds_cube() {
+        let rounded = Solid::cube(10.0, 10.0, 10.0).minkowski_sphere(2.0, 32);
+        let (min, max) = rounded.bounding_box();
+        for k in 0..3 {
+            assert!((max[k] - min[k] - 14.0).abs() < 1e-4);
+        }
+        assert!(rounded.is_watertight());
+
+        // Box plus slabs, quarter cylinders and corner octants
+        use std::f64::consts::PI;
+        let exact = 1000.0 + 2.0 * 600.0 + PI * 4.0 * 30.0 + 4.0 / 3.0 * PI * 8.0;
+        let volume = rounded.volume();
+        assert!(volume < exact && volume > 0.99 * exact, "volume {volume}");
+    }
+
+    #[test]
+    fn test_slice_cylinder_layers() {
+        let cyl = Solid::cylinder(5.0, 10.0, 32);
+        let layers = cyl.slice(&[-1.0, 2.5, 5.0, 7.5, 12.0]);
+        assert_eq!(layers.len(), 5);
+        assert!(layers[0].is_empty());
+        assert!(layers[4].is_empty());
+
+        for (layer, z) in layers[1..4].iter().zip([2.5, 5.0, 7.5]) {
+            assert_eq!(layer.len(), 1);
+            let profile = &layer[0];
+            assert!(profile.is_closed());
+            assert!(profile.segments.len() >= 16);
+            for v in profile.vertices_3d() {
+                assert!((v.z - z).abs() < 1e-6);
+                // Cuts through lateral triangles land on chords of the circle
+                let r = (v.x * v.x + v.y * v.y).sqrt();
+                assert!((r - 5.0).abs() < 0.03, "radius {r} at z={z}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_slice_tube_multiple_loops() {
+        let outer = Solid::cube(20.0, 20.0, 10.0);
+        let inner = Solid::cube(10.0, 10.0, 20.0).translate(5.0, 5.0, -5.0);
+        let layers = outer.difference(&inner).slice(&[2.0, 8.0]);
+        assert!(layers.iter().all(|layer| layer.len() == 2));
+    }
+
+    #[test]
+    fn test_project_cylinder_along_axis() {
+        let cyl = Solid::cylinder(5.0, 10.0, 32);
+        let loops = cyl.project_to_plane(Point3::origin(), Vec3::z());
+        assert_eq!(loops.len(), 1);
+
+        let profile = &loops[0];
+        assert!(profile.segments.len() >= 16);
+        assert!(profile.is_closed());
+        for v in profile.vertices_3d() {
+            assert!(v.z.abs() < 1e-9);
+            let r = (v.x * v.x + v.y * v.y).sqrt();
+            assert!((r - 5.0).abs() < 1e-3, "radius {r}");
+        }
+        let circle_area = std::f64::consts::PI * 25.0;
+        assert!((profile.signed_area() - circle_area).abs() < 0.05 * circle_area);
+    }
+
+    #[test]
+    fn test_project_ring_to_sketch() {
+        // A 3 × 3 grid of unit cubes with the middle one left out
+        let ring = (0..9)
+            .filter(|&i| i != 4)
+            .map(|i| Solid::cube(1.0, 1.0, 1.0).translate((i % 3) as f64, (i / 3) as f64, 0.0))
+            .reduce(|a, b| a.merge_disjoint(&b))
+            .unwrap();
+
+        let profile = ring.project_to_sketch(Point3::origin(), Vec3::z()).unwrap();
+        assert_eq!(profile.segments.len(), 4);
+        assert_eq!(profile.holes.len(), 1);
+        let area = Solid::extrude(profile, Vec3::new(0.0, 0.0, 1.0))
+            .unwrap()
+            .volume();
+        assert!((area - 8.0).abs() < 1e-6, "area {area}");
+
+        // Two cubes apart have two outlines
+        let pair = Solid::cube(1.0, 1.0, 1.0)
+            .merge_disjoint(&Solid::cube(1.0, 1.0, 1.0).translate(3.0, 0.0, 0.0));
+        assert!(pair
+            .project_to_sketch(Point3::origin(), Vec3::z())
+            .is_none());
+    }
+
+    #[test]
+    fn test_project_cube_from_side() {
+        let cube = Solid::cube(10.0, 20.0, 30.0);
+        let loops = cube.project_to_plane(Point3::origin(), Vec3::x());
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].segments.len(), 4);
+        assert!((loops[0].signed_area() - 600.0).abs() < 1e-3);
+    }
+
     #[test]
     fn test_step_roundtrip() {
         // Create a cube
@@ -1385,6 +2259,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_stl_file() {
+        let path = std::env::temp_dir().join("vcad_kernel_test_tetra.stl");
+        std::fs::write(
+            &path,
+            "solid t
+facet normal 0 0 -1
+outer loop
+vertex 0 0 0
+vertex 0 6 0
+vertex 6 0 0
+endloop
+endfacet
+facet normal 0 -1 0
+outer loop
+vertex 0 0 0
+vertex 6 0 0
+vertex 0 0 6
+endloop
+endfacet
+facet normal -1 0 0
+outer loop
+vertex 0 0 0
+vertex 0 0 6
+vertex 0 6 0
+endloop
+endfacet
+facet normal 1 1 1
+outer loop
+vertex 6 0 0
+vertex 0 6 0
+vertex 0 0 6
+endloop
+endfacet
+endsolid t
+",
+        )
+        .unwrap();
+        let solid = Solid::from_stl(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(solid.num_triangles(), 4);
+        assert!((solid.volume() - 36.0).abs() < 1e-6);
+        assert!(matches!(
+            Solid::from_stl("/nonexistent/model.stl"),
+            Err(StlError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_fix_orientation_of_imported_stl() {
+        // A cube exported with every other triangle wound backwards
+        let mesh = Solid::cube(10.0, 10.0, 10.0).to_mesh(32);
+        let mut stl = String::from("solid mixed\n");
+        for (t, tri) in mesh.indices.chunks_exact(3).enumerate() {
+            let order = if t % 2 == 0 { [0, 1, 2] } else { [0, 2, 1] };
+            stl.push_str("facet normal 0 0 0\nouter loop\n");
+            for k in order {
+                let i = tri[k] as usize * 3;
+                let v = &mesh.vertices[i..i + 3];
+                stl.push_str(&format!("vertex {} {} {}\n", v[0], v[1], v[2]));
+            }
+            stl.push_str("endloop\nendfacet\n");
+        }
+        stl.push_str("endsolid mixed\n");
+
+        let mut solid = Solid::from_stl_bytes(stl.as_bytes()).unwrap();
+        assert!(!solid.to_mesh(32).is_watertight());
+
+        assert!(solid.fix_orientation() > 0);
+        let fixed = solid.to_mesh(32);
+        assert!(fixed.is_watertight());
+        assert!((fixed.signed_volume() - 1000.0).abs() < 1e-6);
+        assert!((solid.volume() - 1000.0).abs() < 1e-6);
+        assert_eq!(solid.fix_orientation(), 0);
+    }
+
+    #[test]
+    fn test_fix_orientation_keeps_void() {
+        use vcad_kernel_primitives::BoxOrigin;
+
+        // A 10 mm box around a 6 mm void, both shells wound as bodies
+        let mut mesh = Solid::cube_at(10.0, 10.0, 10.0, BoxOrigin::Center).to_mesh(32);
+        mesh.merge(&Solid::cube_at(6.0, 6.0, 6.0, BoxOrigin::Center).to_mesh(32));
+        let mut stl = String::from("solid hollow\n");
+        for tri in mesh.indices.chunks_exact(3) {
+            stl.push_str("facet normal 0 0 0\nouter loop\n");
+            for &i in tri {
+                let v = &mesh.vertices[i as usize * 3..i as usize * 3 + 3];
+                stl.push_str(&format!("vertex {} {} {}\n", v[0], v[1], v[2]));
+            }
+            stl.push_str("endloop\nendfacet\n");
+        }
+        stl.push_str("endsolid hollow\n");
+
+        let mut solid = Solid::from_stl_bytes(stl.as_bytes()).unwrap();
+        assert!((solid.volume() - 1216.0).abs() < 1e-6);
+        assert!(solid.fix_orientation() > 0);
+        assert!((solid.volume() - 784.0).abs() < 1e-6);
+        assert!(solid.to_mesh(32).is_watertight());
+    }
+
     #[test]
     fn test_step_can_export() {
         let cube = Solid::cube(10.0, 10.0, 10.0);
@@ -1447,4 +2423,27 @@ mod tests {
         assert!(!diff.is_empty());
         assert!(!inter.is_empty());
     }
+
+    #[test]
+    fn test_interference_and_clearance() {
+        let a = Solid::cube(10.0, 10.0, 10.0);
+
+        // Overlap is the box [6, 10] x [3, 10] x [2, 10]
+        let b = Solid::cube(10.0, 10.0, 10.0).translate(6.0, 3.0, 2.0);
+        let overlap = 4.0 * 7.0 * 8.0;
+        assert!((a.interference_volume(&b) - overlap).abs() < 1e-6);
+        assert!((b.interference_volume(&a) - overlap).abs() < 1e-6);
+        assert_eq!(a.min_clearance(&b), 0.0);
+
+        // 3 mm gap along x, offset in y and z so only faces overlap
+        let c = Solid::cube(5.0, 5.0, 5.0).translate(13.0, 2.0, 7.0);
+        assert_eq!(a.interference_volume(&c), 0.0);
+        assert!((a.min_clearance(&c) - 3.0).abs() < 1e-6);
+
+        // Diagonal gap between corners (10, 10, 10) and (12, 13, 16)
+        let d = Solid::cube(1.0, 1.0, 1.0).translate(12.0, 13.0, 16.0);
+        assert!((a.min_clearance(&d) - 7.0).abs() < 1e-6);
+
+        assert_eq!(a.min_clearance(&Solid::empty()), f64::INFINITY);
+    }
 }
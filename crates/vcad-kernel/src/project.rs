@@ -0,0 +1,82 @@
+//! Silhouette projection of solids onto a plane.
+//!
+//! A mesh edge is on the silhouette when exactly one of its adjacent
+//! triangles faces the viewer (its normal points along the plane normal).
+//! Silhouette edges are chained into loops and flattened into the plane's
+//! 2D frame with the same helpers used for sections.
+
+use std::collections::BTreeMap;
+
+use vcad_kernel_geom::Plane;
+use vcad_kernel_math::{Point3, Vec3};
+use vcad_kernel_sketch::SketchProfile;
+use vcad_kernel_tessellate::TriangleMesh;
+
+use crate::section::{chain_loops, orient_and_build, simplify_loop, PointWeld};
+
+/// Project the silhouette of `mesh` onto the plane and return closed loops.
+pub(crate) fn silhouette_profiles(
+    mesh: &TriangleMesh,
+    plane_origin: Point3,
+    plane_normal: Vec3,
+    tol: f64,
+) -> Vec<SketchProfile> {
+    if plane_normal.norm() < 1e-12 {
+        return Vec::new();
+    }
+    let plane = Plane::from_normal(plane_origin, plane_normal);
+    let n = *plane.normal_dir.as_ref();
+
+    // Weld coincident vertices: faces are tessellated independently, so the
+    // two sides of an edge do not share indices.
+    let mut weld = PointWeld::new(tol);
+    let welded: Vec<usize> = mesh
+        .vertices
+        .chunks_exact(3)
+        .map(|v| weld.node(Point3::new(v[0] as f64, v[1] as f64, v[2] as f64)))
+        .collect();
+    let nodes = weld.into_nodes();
+
+    // Undirected edge → number of adjacent front-facing triangles.
+    let mut edges: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    for tri in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|k| welded[tri[k] as usize]);
+        if a == b || b == c || c == a {
+            continue;
+        }
+        let normal = (nodes[b] - nodes[a]).cross(&(nodes[c] - nodes[a]));
+        let front = normal.dot(&n) > 1e-6 * normal.norm();
+        for (u, v) in [(a, b), (b, c), (c, a)] {
+            let count = edges.entry((u.min(v), u.max(v))).or_insert(0);
+            if front {
+                *count += 1;
+            }
+        }
+    }
+
+    let segments: Vec<(Point3, Point3)> = edges
+        .into_iter()
+        .filter(|&(_, front)| front == 1)
+        .map(|((u, v), _)| (nodes[u], nodes[v]))
+        .collect();
+
+    let loops = chain_loops(&segments, tol)
+        .into_iter()
+        .map(|pts| simplify_loop(pts.iter().map(|p| plane.project(p)).collect(), tol))
+        .filter(|pts| pts.len() >= 3)
+        .collect();
+
+    orient_and_build(&plane, loops)
+}
+
+/// Combine silhouette loops into one profile: the single counter-clockwise
+/// loop, with the clockwise ones (inside it) as holes.
+pub(crate) fn single_outline(profiles: Vec<SketchProfile>) -> Option<SketchProfile> {
+    let (outer, holes): (Vec<_>, Vec<_>) = profiles
+        .into_iter()
+        .partition(|profile| profile.signed_area() > 0.0);
+    let [outer] = <[SketchProfile; 1]>::try_from(outer).ok()?;
+    outer
+        .with_holes(holes.into_iter().map(|hole| hole.segments).collect())
+        .ok()
+}
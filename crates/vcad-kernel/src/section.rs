@@ -0,0 +1,348 @@
+//! Planar cross-sections of solids.
+//!
+//! Intersects planar polygons (B-rep faces or mesh triangles) with a cutting
+//! plane, chains the resulting segments into closed loops, and maps the loops
+//! into the plane's 2D coordinate frame as [`SketchProfile`]s.
+
+use std::collections::HashMap;
+
+use vcad_kernel_geom::Plane;
+use vcad_kernel_math::predicates::{orient3d, Sign};
+use vcad_kernel_math::{Point2, Point3, Tolerance, Vec3};
+use vcad_kernel_sketch::{SketchProfile, SketchSegment};
+
+/// A planar polygon to be cut: one or more closed vertex rings plus a normal.
+pub(crate) struct PlanarPolygon {
+    /// Boundary rings (outer loop first, then holes). Vertices are not repeated.
+    pub rings: Vec<Vec<Point3>>,
+    /// Normal of the polygon's supporting plane (need not be unit length).
+    pub normal: Vec3,
+}
+
+/// Cut a set of planar polygons with a plane and return closed section loops.
+pub(crate) fn section_polygons(
+    polygons: &[PlanarPolygon],
+    plane_origin: Point3,
+    plane_normal: Vec3,
+    tol: f64,
+) -> Vec<SketchProfile> {
+    if plane_normal.norm() < 1e-12 {
+        return Vec::new();
+    }
+    let plane = Plane::from_normal(plane_origin, plane_normal);
+    let n = *plane.normal_dir.as_ref();
+    // Three points spanning the cutting plane, for exact side classification.
+    let pa = plane.origin;
+    let pb = plane.origin + *plane.x_dir.as_ref();
+    let pc = plane.origin + *plane.y_dir.as_ref();
+
+    let classify = |p: &Point3| -> Sign {
+        if (p - plane_origin).dot(&n).abs() <= tol {
+            Sign::Zero
+        } else {
+            // orient3d is negative for points above (on the +normal side)
+            match orient3d(&pa, &pb, &pc, p) {
+                Sign::Negative => Sign::Positive,
+                Sign::Positive => Sign::Negative,
+                Sign::Zero => Sign::Zero,
+            }
+        }
+    };
+
+    let mut segments: Vec<(Point3, Point3)> = Vec::new();
+    let mut coplanar_edges: Vec<(Point3, Point3)> = Vec::new();
+
+    for poly in polygons {
+        let signs: Vec<Vec<Sign>> = poly
+            .rings
+            .iter()
+            .map(|ring| ring.iter().map(&classify).collect())
+            .collect();
+
+        if signs.iter().flatten().all(|s| s.is_zero()) {
+            // Face lies in the cutting plane: its boundary is part of the section.
+            for ring in &poly.rings {
+                for i in 0..ring.len() {
+                    coplanar_edges.push((ring[i], ring[(i + 1) % ring.len()]));
+                }
+            }
+            continue;
+        }
+
+        // Direction of the line where the face plane meets the cutting plane.
+        let line_dir = n.cross(&poly.normal);
+        if line_dir.norm() < 1e-12 {
+            // Parallel to the cutting plane but not in it.
+            continue;
+        }
+
+        // Points on the cutting plane are treated as lying below it, so each
+        // ring crosses an even number of times and touching edges are emitted
+        // exactly once (from the face above).
+        let mut crossings: Vec<Point3> = Vec::new();
+        for (ring, ring_signs) in poly.rings.iter().zip(&signs) {
+            let len = ring.len();
+            for i in 0..len {
+                let j = (i + 1) % len;
+                let above_i = ring_signs[i].is_positive();
+                let above_j = ring_signs[j].is_positive();
+                if above_i == above_j {
+                    continue;
+                }
+                let di = (ring[i] - plane_origin).dot(&n);
+                let dj = (ring[j] - plane_origin).dot(&n);
+                let p = if ring_signs[i].is_zero() {
+                    ring[i]
+                } else if ring_signs[j].is_zero() {
+                    ring[j]
+                } else {
+                    let t = di / (di - dj);
+                    ring[i] + (ring[j] - ring[i]) * t
+                };
+                crossings.push(p);
+            }
+        }
+
+        crossings.sort_by(|a, b| {
+            a.coords
+                .dot(&line_dir)
+                .partial_cmp(&b.coords.dot(&line_dir))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        for pair in crossings.chunks_exact(2) {
+            if (pair[1] - pair[0]).norm() > tol {
+                segments.push((pair[0], pair[1]));
+            }
+        }
+    }
+
+    // Interior edges between coplanar faces appear in both directions and
+    // cancel; only the outline of the coplanar region remains.
+    for (i, &(a, b)) in coplanar_edges.iter().enumerate() {
+        let shared = coplanar_edges
+            .iter()
+            .enumerate()
+            .any(|(j, &(c, d))| i != j && (a - d).norm() <= tol && (b - c).norm() <= tol);
+        if !shared {
+            segments.push((a, b));
+        }
+    }
+
+    // Drop duplicate segments (e.g. an edge reported by both a coplanar face
+    // and its neighbour).
+    let mut unique: Vec<(Point3, Point3)> = Vec::with_capacity(segments.len());
+    for (a, b) in segments {
+        let dup = unique.iter().any(|&(c, d)| {
+            ((a - c).norm() <= tol && (b - d).norm() <= tol)
+                || ((a - d).norm() <= tol && (b - c).norm() <= tol)
+        });
+        if !dup {
+            unique.push((a, b));
+        }
+    }
+
+    let loops: Vec<Vec<Point2>> = chain_loops(&unique, tol)
+        .into_iter()
+        .map(|pts| simplify_loop(pts.iter().map(|p| plane.project(p)).collect(), tol))
+        .filter(|pts| pts.len() >= 3)
+        .collect();
+
+    orient_and_build(&plane, loops)
+}
+
+/// Chain undirected segments into closed loops; open chains are discarded.
+pub(crate) fn chain_loops(segments: &[(Point3, Point3)], tol: f64) -> Vec<Vec<Point3>> {
+    // Merge coincident endpoints into shared nodes.
+    let mut weld = PointWeld::new(tol);
+    let edges: Vec<(usize, usize)> = segments
+        .iter()
+        .map(|&(a, b)| (weld.node(a), weld.node(b)))
+        .filter(|(a, b)| a != b)
+        .collect();
+    let nodes = weld.into_nodes();
+
+    let mut incident: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for (ei, &(a, b)) in edges.iter().enumerate() {
+        incident[a].push(ei);
+        incident[b].push(ei);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut loops = Vec::new();
+
+    for start_edge in 0..edges.len() {
+        if used[start_edge] {
+            continue;
+        }
+        used[start_edge] = true;
+        let (start, mut current) = edges[start_edge];
+        let mut chain = vec![nodes[start]];
+        let mut closed = false;
+
+        loop {
+            if current == start {
+                closed = true;
+                break;
+            }
+            chain.push(nodes[current]);
+            let next_edge = incident[current].iter().copied().find(|&ei| !used[ei]);
+            match next_edge {
+                Some(ei) => {
+                    used[ei] = true;
+                    let (a, b) = edges[ei];
+                    current = if a == current { b } else { a };
+                }
+                None => break,
+            }
+        }
+
+        if closed && chain.len() >= 3 {
+            loops.push(chain);
+        }
+    }
+
+    loops
+}
+
+/// Merges points within a tolerance of each other into shared nodes.
+///
+/// Points are hashed into a grid of cells `tol` wide, so finding a point's
+/// node only checks the 27 cells around it rather than every node.
+pub(crate) struct PointWeld {
+    tol: f64,
+    nodes: Vec<Point3>,
+    cells: HashMap<[i64; 3], Vec<usize>>,
+}
+
+impl PointWeld {
+    pub(crate) fn new(tol: f64) -> Self {
+        Self {
+            tol: tol.max(f64::EPSILON),
+            nodes: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Index of the first node within `tol` of `p`, or of a new node at `p`.
+    pub(crate) fn node(&mut self, p: Point3) -> usize {
+        let [cx, cy, cz] = [p.x, p.y, p.z].map(|c| (c / self.tol).floor() as i64);
+        let mut found: Option<usize> = None;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(ids) = self.cells.get(&[cx + dx, cy + dy, cz + dz]) else {
+                        continue;
+                    };
+                    for &i in ids {
+                        if (self.nodes[i] - p).norm() <= self.tol && found.is_none_or(|f| i < f) {
+                            found = Some(i);
+                        }
+                    }
+                }
+            }
+        }
+        found.unwrap_or_else(|| {
+            self.nodes.push(p);
+            let i = self.nodes.len() - 1;
+            self.cells.entry([cx, cy, cz]).or_default().push(i);
+            i
+        })
+    }
+
+    /// The nodes, in the order they were created.
+    pub(crate) fn into_nodes(self) -> Vec<Point3> {
+        self.nodes
+    }
+}
+
+/// Remove repeated and collinear vertices from a closed 2D loop.
+pub(crate) fn simplify_loop(mut pts: Vec<Point2>, tol: f64) -> Vec<Point2> {
+    let mut changed = true;
+    while changed && pts.len() >= 3 {
+        changed = false;
+        let mut i = 0;
+        while i < pts.len() && pts.len() >= 3 {
+            let len = pts.len();
+            let prev = pts[(i + len - 1) % len];
+            let cur = pts[i];
+            let next = pts[(i + 1) % len];
+            let a = cur - prev;
+            let b = next - cur;
+            let span = (next - prev).norm();
+            let cross = a.x * b.y - a.y * b.x;
+            if a.norm() <= tol || (span > tol && cross.abs() / span <= tol && a.dot(&b) > 0.0) {
+                pts.remove(i);
+                changed = true;
+            } else {
+                i += 1;
+            }
+        }
+    }
+    pts
+}
+
+/// Orient loops (outer boundaries CCW, holes CW) and convert them to profiles.
+pub(crate) fn orient_and_build(plane: &Plane, loops: Vec<Vec<Point2>>) -> Vec<SketchProfile> {
+    let mut profiles = Vec::with_capacity(loops.len());
+    for (i, pts) in loops.iter().enumerate() {
+        let depth = loops
+            .iter()
+            .enumerate()
+            .filter(|(j, other)| *j != i && point_in_loop(&pts[0], other))
+            .count();
+        let is_hole = depth % 2 == 1;
+
+        let mut pts = pts.clone();
+        if (signed_area(&pts) < 0.0) != is_hole {
+            pts.reverse();
+        }
+
+        let segments: Vec<SketchSegment> = (0..pts.len())
+            .map(|k| SketchSegment::Line {
+                start: pts[k],
+                end: pts[(k + 1) % pts.len()],
+            })
+            .collect();
+        if let Ok(profile) = SketchProfile::new(
+            plane.origin,
+            *plane.x_dir.as_ref(),
+            *plane.y_dir.as_ref(),
+            segments,
+        ) {
+            profiles.push(profile);
+        }
+    }
+    profiles
+}
+
+fn signed_area(pts: &[Point2]) -> f64 {
+    let n = pts.len();
+    let mut area = 0.0;
+    for i in 0..n {
+        let j = (i + 1) % n;
+        area += pts[i].x * pts[j].y - pts[j].x * pts[i].y;
+    }
+    area / 2.0
+}
+
+fn point_in_loop(p: &Point2, pts: &[Point2]) -> bool {
+    let n = pts.len();
+    let mut inside = false;
+    let mut j = n - 1;
+    for i in 0..n {
+        let (vi, vj) = (pts[i], pts[j]);
+        if ((vi.y > p.y) != (vj.y > p.y))
+            && (p.x < (vj.x - vi.x) * (p.y - vi.y) / (vj.y - vi.y) + vi.x)
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Default linear tolerance used when sectioning B-rep (exact) geometry.
+pub(crate) const BREP_TOLERANCE: f64 = Tolerance::DEFAULT.linear;
+
+/// Tolerance for sectioning tessellated geometry (vertices are stored as f32).
+pub(crate) const MESH_TOLERANCE: f64 = 1e-4;
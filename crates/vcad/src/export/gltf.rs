@@ -128,6 +128,8 @@ fn build_gltf_json(
     min: &[f32; 3],
     max: &[f32; 3],
 ) -> String {
+    let (texture_indices, textures_json) = textures_json(std::slice::from_ref(material));
+
     // Build JSON manually for control over output
     format!(
         r#"{{
@@ -143,14 +145,7 @@ fn build_gltf_json(
       "material": 0
     }}]
   }}],
-  "materials": [{{
-    "name": "{mat_name}",
-    "pbrMetallicRoughness": {{
-      "baseColorFactor": [{r}, {g}, {b}, 1.0],
-      "metallicFactor": {metallic},
-      "roughnessFactor": {roughness}
-    }}
-  }}],
+  "materials": [{material_json}],{textures_json}
   "accessors": [
     {{
       "bufferView": 0,
@@ -184,12 +179,8 @@ fn build_gltf_json(
   "buffers": [{{ "byteLength": {buffer_length} }}]
 }}"#,
         name = name,
-        mat_name = material.name,
-        r = material.color[0],
-        g = material.color[1],
-        b = material.color[2],
-        metallic = material.metallic,
-        roughness = material.roughness,
+        material_json = material_json(material, texture_indices[0]),
+        textures_json = textures_json,
         index_count = index_count,
         vertex_count = vertex_count,
         min0 = min[0],
@@ -204,6 +195,78 @@ fn build_gltf_json(
     )
 }
 
+/// glTF material object for a [`Material`].
+///
+/// Opacity below 1.0 maps to `alphaMode: BLEND` with the alpha stored in
+/// `baseColorFactor`; a non-black emissive color maps to `emissiveFactor`.
+fn material_json(material: &Material, texture: Option<usize>) -> String {
+    let [r, g, b] = material.color;
+    let mut pbr = format!(
+        r#""baseColorFactor": [{}, {}, {}, {}], "metallicFactor": {}, "roughnessFactor": {}"#,
+        r, g, b, material.opacity, material.metallic, material.roughness
+    );
+    if let Some(index) = texture {
+        pbr.push_str(&format!(
+            r#", "baseColorTexture": {{ "index": {} }}"#,
+            index
+        ));
+    }
+
+    let mut json = format!(
+        r#"{{ "name": "{}", "pbrMetallicRoughness": {{ {} }}"#,
+        material.name, pbr
+    );
+    if material.opacity < 1.0 {
+        json.push_str(r#", "alphaMode": "BLEND""#);
+    }
+    if material.emissive != [0.0, 0.0, 0.0] {
+        let [er, eg, eb] = material.emissive;
+        json.push_str(&format!(r#", "emissiveFactor": [{}, {}, {}]"#, er, eg, eb));
+    }
+    json.push_str(" }");
+    json
+}
+
+/// Texture index per material plus the `images`/`textures` JSON fragment.
+///
+/// Textures are referenced by URI rather than embedded in the binary chunk.
+/// The fragment is empty when no material has a texture.
+fn textures_json(materials: &[Material]) -> (Vec<Option<usize>>, String) {
+    let mut uris: Vec<&str> = Vec::new();
+    let indices = materials
+        .iter()
+        .map(|m| {
+            m.base_color_texture.as_deref().map(|uri| {
+                uris.iter().position(|u| *u == uri).unwrap_or_else(|| {
+                    uris.push(uri);
+                    uris.len() - 1
+                })
+            })
+        })
+        .collect();
+
+    if uris.is_empty() {
+        return (indices, String::new());
+    }
+
+    let images: Vec<String> = uris
+        .iter()
+        .map(|uri| {
+            let escaped = uri.replace('\\', "\\\\").replace('"', "\\\"");
+            format!(r#"{{ "uri": "{}" }}"#, escaped)
+        })
+        .collect();
+    let textures: Vec<String> = (0..uris.len())
+        .map(|i| format!(r#"{{ "source": {} }}"#, i))
+        .collect();
+    let fragment = format!(
+        "\n  \"images\": [{}],\n  \"textures\": [{}],",
+        images.join(", "),
+        textures.join(", ")
+    );
+    (indices, fragment)
+}
+
 // =============================================================================
 // Multi-material Scene export
 // =============================================================================
@@ -361,12 +424,12 @@ pub fn scene_to_glb_bytes(scene: &Scene, materials_db: &Materials) -> Result<Vec
     }
 
     // Build materials JSON
-    let materials_json: Vec<String> = materials_list.iter().map(|m| {
-        format!(
-            r#"{{ "name": "{}", "pbrMetallicRoughness": {{ "baseColorFactor": [{}, {}, {}, 1.0], "metallicFactor": {}, "roughnessFactor": {} }} }}"#,
-            m.name, m.color[0], m.color[1], m.color[2], m.metallic, m.roughness
-        )
-    }).collect();
+    let (texture_indices, textures_json) = textures_json(&materials_list);
+    let materials_json: Vec<String> = materials_list
+        .iter()
+        .zip(&texture_indices)
+        .map(|(m, &texture)| material_json(m, texture))
+        .collect();
 
     // Build node indices for scene
     let node_indices: Vec<String> = (0..nodes.len()).map(|i| i.to_string()).collect();
@@ -379,7 +442,7 @@ pub fn scene_to_glb_bytes(scene: &Scene, materials_db: &Materials) -> Result<Vec
   "scenes": [{{ "name": "{}", "nodes": [{}] }}],
   "nodes": [{}],
   "meshes": [{}],
-  "materials": [{}],
+  "materials": [{}],{}
   "accessors": [{}],
   "bufferViews": [{}],
   "buffers": [{{ "byteLength": {} }}]
@@ -389,6 +452,7 @@ pub fn scene_to_glb_bytes(scene: &Scene, materials_db: &Materials) -> Result<Vec
         nodes.join(",\n    "),
         meshes.join(",\n    "),
         materials_json.join(",\n    "),
+        textures_json,
         accessors.join(",\n    "),
         buffer_views.join(",\n    "),
         bin_buffer.len()
@@ -474,4 +538,34 @@ mod tests {
         // Check GLB magic
         assert_eq!(&glb_data[0..4], b"glTF");
     }
+
+    #[test]
+    fn test_glb_transparent_emissive_material() {
+        let cube = Part::cube("cover", 10.0, 10.0, 10.0);
+        let def = vcad_ir::MaterialDef {
+            name: "smoked_acrylic".to_string(),
+            color: [0.2, 0.2, 0.2],
+            metallic: 0.0,
+            roughness: 0.1,
+            density: None,
+            friction: None,
+            opacity: Some(0.5),
+            emissive: Some([0.0, 1.0, 0.0]),
+            base_color_texture: Some("textures/acrylic.png".to_string()),
+        };
+        let glb_data = to_glb_bytes(&cube, &Material::from(&def)).unwrap();
+
+        let json_len =
+            u32::from_le_bytes([glb_data[12], glb_data[13], glb_data[14], glb_data[15]]) as usize;
+        let json = std::str::from_utf8(&glb_data[20..20 + json_len]).unwrap();
+        assert!(json.contains(r#""baseColorFactor": [0.2, 0.2, 0.2, 0.5]"#));
+        assert!(json.contains(r#""alphaMode": "BLEND""#));
+        assert!(json.contains(r#""emissiveFactor": [0, 1, 0]"#));
+        assert!(json.contains(r#""baseColorTexture": { "index": 0 }"#));
+        assert!(json.contains(r#""uri": "textures/acrylic.png""#));
+
+        // Opaque materials stay in the default alpha mode.
+        let opaque = to_glb_bytes(&cube, &Material::default()).unwrap();
+        assert!(!String::from_utf8_lossy(&opaque).contains("alphaMode"));
+    }
 }
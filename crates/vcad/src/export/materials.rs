@@ -35,6 +35,12 @@ pub struct Material {
     pub density: f32,
     /// Coefficient of friction (for physics)
     pub friction: f32,
+    /// Opacity 0.0-1.0 (1.0 = fully opaque)
+    pub opacity: f32,
+    /// Emissive color [R, G, B] normalized 0.0-1.0
+    pub emissive: [f32; 3],
+    /// Base color texture image path or URI
+    pub base_color_texture: Option<String>,
 }
 
 impl Default for Material {
@@ -47,6 +53,29 @@ impl Default for Material {
             roughness: 0.5,
             density: 1000.0,
             friction: 0.5,
+            opacity: 1.0,
+            emissive: [0.0, 0.0, 0.0],
+            base_color_texture: None,
+        }
+    }
+}
+
+impl From<&vcad_ir::MaterialDef> for Material {
+    fn from(def: &vcad_ir::MaterialDef) -> Self {
+        let defaults = Material::default();
+        Self {
+            name: def.name.clone(),
+            description: None,
+            color: def.color.map(|c| c as f32),
+            metallic: def.metallic as f32,
+            roughness: def.roughness as f32,
+            density: def.density.map_or(defaults.density, |d| d as f32),
+            friction: def.friction.map_or(defaults.friction, |f| f as f32),
+            opacity: def.opacity() as f32,
+            emissive: def
+                .emissive
+                .map_or(defaults.emissive, |e| e.map(|c| c as f32)),
+            base_color_texture: def.base_color_texture.clone(),
         }
     }
 }
@@ -133,6 +162,28 @@ impl Materials {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        let opacity = props
+            .get("opacity")
+            .and_then(|v| v.as_float())
+            .unwrap_or(1.0) as f32;
+
+        let emissive = props
+            .get("emissive")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                let mut c = [0.0f32; 3];
+                for (i, val) in arr.iter().take(3).enumerate() {
+                    c[i] = val.as_float().unwrap_or(0.0) as f32;
+                }
+                c
+            })
+            .unwrap_or([0.0, 0.0, 0.0]);
+
+        let base_color_texture = props
+            .get("base_color_texture")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
         Ok(Material {
             name: name.to_string(),
             description,
@@ -141,6 +192,9 @@ impl Materials {
             roughness,
             density,
             friction,
+            opacity,
+            emissive,
+            base_color_texture,
         })
     }
 
@@ -20,7 +20,10 @@ use std::collections::HashMap;
 use std::f64::consts::PI;
 use std::sync::atomic::{AtomicU64, Ordering};
 use thiserror::Error;
-use vcad_ir::{CsgOp, Document, Node, NodeId, SceneEntry, Vec3 as IrVec3};
+use vcad_ir::{
+    BoxOrigin, CsgOp, Document, Node, NodeId, SceneEntry, SketchSegment2D, SweepPath, Vec2,
+    Vec3 as IrVec3,
+};
 
 pub mod export;
 pub mod step;
@@ -36,6 +39,9 @@ pub enum CadError {
     /// The geometry is empty (no vertices or triangles).
     #[error("Empty geometry")]
     EmptyGeometry,
+    /// A constructor was given parameters that cannot describe valid geometry.
+    #[error("Invalid parameters: {0}")]
+    InvalidParameters(String),
 }
 
 /// Global atomic counter for unique IR node IDs.
@@ -123,6 +129,7 @@ impl Part {
                 id,
                 name: Some(name.to_string()),
                 op,
+                attributes: None,
             },
         );
         (id, nodes)
@@ -144,6 +151,7 @@ impl Part {
                 id,
                 name: Some(name.to_string()),
                 op: op_fn(left.ir_node_id, right.ir_node_id),
+                attributes: None,
             },
         );
         (id, nodes)
@@ -163,6 +171,7 @@ impl Part {
                 id,
                 name: Some(name.to_string()),
                 op: op_fn(child.ir_node_id),
+                attributes: None,
             },
         );
         (id, nodes)
@@ -186,6 +195,7 @@ impl Part {
             &name,
             CsgOp::Cube {
                 size: IrVec3::new(x, y, z),
+                origin: BoxOrigin::Corner,
             },
         );
         Self::with_ir(name, vcad_kernel::Solid::cube(x, y, z), id, nodes)
@@ -248,6 +258,95 @@ impl Part {
         )
     }
 
+    /// Create a helical compression spring: a round wire of `wire_radius`
+    /// swept along a helix of `coil_radius` around the Z axis, starting at
+    /// the origin and rising `pitch` per turn.
+    ///
+    /// The construction is recorded as a circle sketch swept along a helix,
+    /// so it persists through [`Part::to_document`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CadError::InvalidParameters`] if any dimension is not
+    /// positive, if the wire would touch the next coil
+    /// (`wire_radius >= pitch / 2`) or if it would cross the axis
+    /// (`wire_radius >= coil_radius`).
+    pub fn spring(
+        name: impl Into<String>,
+        wire_radius: f64,
+        coil_radius: f64,
+        pitch: f64,
+        turns: f64,
+    ) -> Result<Self, CadError> {
+        use vcad_kernel::vcad_kernel_math::{Point3, Vec3};
+        use vcad_kernel::vcad_kernel_sketch::SketchProfile;
+        use vcad_kernel::vcad_kernel_sweep::{Helix, SweepOptions};
+
+        if !(wire_radius > 0.0 && coil_radius > 0.0 && pitch > 0.0 && turns > 0.0) {
+            return Err(CadError::InvalidParameters(
+                "spring dimensions must be positive".into(),
+            ));
+        }
+        if wire_radius >= pitch / 2.0 {
+            return Err(CadError::InvalidParameters(format!(
+                "wire radius {wire_radius} must be less than half the pitch {pitch}"
+            )));
+        }
+        if wire_radius >= coil_radius {
+            return Err(CadError::InvalidParameters(format!(
+                "wire radius {wire_radius} must be less than the coil radius {coil_radius}"
+            )));
+        }
+
+        let name = name.into();
+        let height = pitch * turns;
+
+        // Four quarter arcs, matching `SketchProfile::circle` below
+        let r = wire_radius;
+        let points = [(r, 0.0), (0.0, r), (-r, 0.0), (0.0, -r)].map(|(x, y)| Vec2::new(x, y));
+        let quarter = |i: usize| SketchSegment2D::Arc {
+            start: points[i],
+            end: points[(i + 1) % 4],
+            center: Vec2::new(0.0, 0.0),
+            ccw: true,
+        };
+        let (profile_id, mut nodes) = Self::make_leaf(
+            &format!("{name}-wire"),
+            CsgOp::Sketch2D {
+                origin: IrVec3::new(0.0, 0.0, 0.0),
+                x_dir: IrVec3::new(1.0, 0.0, 0.0),
+                y_dir: IrVec3::new(0.0, 1.0, 0.0),
+                segments: (0..4).map(quarter).collect(),
+                holes: Vec::new(),
+            },
+        );
+        let id = alloc_node_id();
+        nodes.insert(
+            id,
+            Node {
+                id,
+                name: Some(name.clone()),
+                op: CsgOp::Sweep {
+                    profile: profile_id,
+                    path: SweepPath::Helix {
+                        radius: coil_radius,
+                        pitch,
+                        height,
+                        turns,
+                    },
+                    options: Default::default(),
+                },
+                attributes: None,
+            },
+        );
+
+        let profile = SketchProfile::circle(Point3::origin(), Vec3::z(), wire_radius, 4);
+        let helix = Helix::new(coil_radius, pitch, height, turns);
+        let solid = vcad_kernel::Solid::sweep(profile, &helix, SweepOptions::default())
+            .map_err(|e| CadError::InvalidParameters(e.to_string()))?;
+        Ok(Self::with_ir(name, solid, id, nodes))
+    }
+
     // =========================================================================
     // CSG operations
     // =========================================================================
@@ -522,6 +621,16 @@ pub fn counterbore_hole(
     through.union(&counterbore)
 }
 
+/// Create a document holding a single spring (see [`Part::spring`]).
+pub fn make_spring(
+    wire_radius: f64,
+    coil_radius: f64,
+    pitch: f64,
+    turns: f64,
+) -> Result<Document, CadError> {
+    Ok(Part::spring("spring", wire_radius, coil_radius, pitch, turns)?.to_document())
+}
+
 /// Create a bolt pattern (circle of holes)
 pub fn bolt_pattern(
     num_holes: usize,
@@ -981,7 +1090,7 @@ mod tests {
         let root = &doc.nodes[&doc.roots[0].root];
         assert_eq!(root.name, Some("box".to_string()));
         match &root.op {
-            CsgOp::Cube { size } => {
+            CsgOp::Cube { size, .. } => {
                 assert_eq!(size.x, 10.0);
                 assert_eq!(size.y, 20.0);
                 assert_eq!(size.z, 30.0);
@@ -1067,4 +1176,47 @@ mod tests {
         // 2 nodes total (one per primitive)
         assert_eq!(doc.nodes.len(), 2);
     }
+
+    #[test]
+    fn test_spring() {
+        let spring = Part::spring("spring", 0.5, 5.0, 2.0, 5.0).unwrap();
+        let (min, max) = spring.bounding_box();
+        // The wire center rises pitch × turns; the wire adds at most its
+        // diameter on top of that
+        let height = max[2] - min[2];
+        assert!(height > 10.0 && height < 11.0 + 1e-3, "height {height}");
+        assert!((max[0] - 5.5).abs() < 0.1);
+
+        // Watertight: every edge is shared by exactly two triangles
+        use vcad_kernel::vcad_kernel_math::{Quantize, Vec3};
+        let mesh = spring.to_mesh();
+        let verts = mesh.vertices();
+        let key = |i: u32| {
+            let i = i as usize * 3;
+            Vec3::new(verts[i] as f64, verts[i + 1] as f64, verts[i + 2] as f64).quantize(1e4)
+        };
+        let mut edges: HashMap<_, usize> = HashMap::new();
+        for tri in mesh.indices().chunks(3) {
+            for k in 0..3 {
+                let (a, b) = (key(tri[k]), key(tri[(k + 1) % 3]));
+                *edges
+                    .entry(if a < b { (a, b) } else { (b, a) })
+                    .or_default() += 1;
+            }
+        }
+        assert!(edges.values().all(|&n| n == 2));
+
+        let doc = make_spring(0.5, 5.0, 2.0, 5.0).unwrap();
+        let json = doc.to_json().unwrap();
+        assert_eq!(Document::from_json(&json).unwrap(), doc);
+        assert_eq!(doc.nodes.len(), 2);
+
+        // The wire would touch the next coil
+        assert!(matches!(
+            Part::spring("spring", 1.0, 5.0, 2.0, 5.0),
+            Err(CadError::InvalidParameters(_))
+        ));
+        assert!(make_spring(0.5, 0.4, 2.0, 5.0).is_err());
+        assert!(make_spring(0.5, 5.0, 2.0, 0.0).is_err());
+    }
 }
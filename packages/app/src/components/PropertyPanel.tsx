@@ -245,6 +245,7 @@ function SweepProperties({ part }: { part: SweepPartInfo }) {
   if (!node || node.op.type !== "Sweep") return null;
 
   const op = node.op;
+  const options = op.options ?? {};
   const helixPath = op.path.type === "Helix" ? op.path : null;
 
   return (
@@ -304,7 +305,7 @@ function SweepProperties({ part }: { part: SweepPartInfo }) {
         </SectionHeader>
         <ScrubInput
           label="Angle"
-          value={(op.orientation ?? 0) * (180 / Math.PI)}
+          value={(options.orientation ?? 0) * (180 / Math.PI)}
           step={5}
           onChange={(v) =>
             updateSweepOp(part.id, { orientation: v * (Math.PI / 180) })
@@ -318,7 +319,7 @@ function SweepProperties({ part }: { part: SweepPartInfo }) {
         <SectionHeader tooltip="Twist angle along the sweep path">Twist</SectionHeader>
         <ScrubInput
           label="Angle"
-          value={(op.twist_angle ?? 0) * (180 / Math.PI)}
+          value={(options.twist_angle ?? 0) * (180 / Math.PI)}
           step={5}
           onChange={(v) =>
             updateSweepOp(part.id, { twist_angle: v * (Math.PI / 180) })
@@ -333,14 +334,14 @@ function SweepProperties({ part }: { part: SweepPartInfo }) {
         <div className="space-y-0.5">
           <ScrubInput
             label="Start"
-            value={op.scale_start ?? 1}
+            value={options.scale_start ?? 1}
             min={0.1}
             step={0.1}
             onChange={(v) => updateSweepOp(part.id, { scale_start: v })}
           />
           <ScrubInput
             label="End"
-            value={op.scale_end ?? 1}
+            value={options.scale_end ?? 1}
             min={0.1}
             step={0.1}
             onChange={(v) => updateSweepOp(part.id, { scale_end: v })}
@@ -354,7 +355,7 @@ function SweepProperties({ part }: { part: SweepPartInfo }) {
         <div className="space-y-0.5">
           <ScrubInput
             label="Path Segments"
-            value={op.path_segments ?? 0}
+            value={options.path_segments ?? 0}
             min={0}
             max={500}
             step={10}
@@ -363,7 +364,7 @@ function SweepProperties({ part }: { part: SweepPartInfo }) {
           <div className="text-[10px] text-text-muted pl-1 pb-1">0 = auto</div>
           <ScrubInput
             label="Arc Segments"
-            value={op.arc_segments ?? 8}
+            value={options.arc_segments ?? 8}
             min={1}
             max={32}
             step={1}
@@ -131,6 +131,7 @@ export function InlineSweepProperties({ part }: InlineSweepPropertiesProps) {
   if (!node || node.op.type !== "Sweep") return null;
 
   const op = node.op;
+  const options = op.options ?? {};
   const helixPath = op.path.type === "Helix" ? op.path : null;
 
   return (
@@ -202,7 +203,7 @@ export function InlineSweepProperties({ part }: InlineSweepPropertiesProps) {
           <ScrubInput
             label="O"
             tooltip="Orientation"
-            value={(op.orientation ?? 0) * (180 / Math.PI)}
+            value={(options.orientation ?? 0) * (180 / Math.PI)}
             step={5}
             onChange={(v) =>
               updateSweepOp(part.id, { orientation: v * (Math.PI / 180) })
@@ -213,7 +214,7 @@ export function InlineSweepProperties({ part }: InlineSweepPropertiesProps) {
           <ScrubInput
             label="T"
             tooltip="Twist"
-            value={(op.twist_angle ?? 0) * (180 / Math.PI)}
+            value={(options.twist_angle ?? 0) * (180 / Math.PI)}
             step={5}
             onChange={(v) =>
               updateSweepOp(part.id, { twist_angle: v * (Math.PI / 180) })
@@ -224,7 +225,7 @@ export function InlineSweepProperties({ part }: InlineSweepPropertiesProps) {
           <ScrubInput
             label="S"
             tooltip="Scale start"
-            value={op.scale_start ?? 1}
+            value={options.scale_start ?? 1}
             min={0.1}
             step={0.1}
             onChange={(v) => updateSweepOp(part.id, { scale_start: v })}
@@ -233,7 +234,7 @@ export function InlineSweepProperties({ part }: InlineSweepPropertiesProps) {
           <ScrubInput
             label="E"
             tooltip="Scale end"
-            value={op.scale_end ?? 1}
+            value={options.scale_end ?? 1}
             min={0.1}
             step={0.1}
             onChange={(v) => updateSweepOp(part.id, { scale_end: v })}
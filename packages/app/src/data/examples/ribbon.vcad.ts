@@ -41,15 +41,17 @@ const document: Document = {
       name: "Twisted Line Sweep",
       op: {
         type: "Sweep",
-        sketch: 1,
+        profile: 1,
         path: {
           type: "Line",
           start: { x: 0, y: 0, z: 0 },
           end: { x: 0, y: 80, z: 0 },  // 80mm vertical path
         },
-        twist_angle: 4 * Math.PI,  // 720° = 2 full rotations
-        scale_start: 1.0,
-        scale_end: 1.0,
+        options: {
+          twist_angle: 4 * Math.PI,  // 720° = 2 full rotations
+          scale_start: 1.0,
+          scale_end: 1.0,
+        },
       },
     },
 
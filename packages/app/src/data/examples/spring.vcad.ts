@@ -59,7 +59,7 @@ const document: Document = {
       name: "Helix Sweep",
       op: {
         type: "Sweep",
-        sketch: 1,
+        profile: 1,
         path: {
           type: "Helix",
           radius: 10,   // 10mm helix radius (coil outer diameter = 20mm + wire)
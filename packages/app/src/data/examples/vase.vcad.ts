@@ -94,7 +94,7 @@ const document: Document = {
       name: "Loft (4 profiles)",
       op: {
         type: "Loft",
-        sketches: [1, 2, 3, 4],  // Bottom to top
+        profiles: [1, 2, 3, 4],  // Bottom to top
         closed: false,          // Open solid (not a tube)
       },
     },
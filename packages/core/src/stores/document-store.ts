@@ -9,6 +9,7 @@ import type {
   PathCurve,
   Transform3D,
   SweepOp,
+  SweepOptions,
   JointKind,
   Instance,
   Joint,
@@ -111,7 +112,7 @@ export interface DocumentState {
   updatePrimitiveOp: (partId: string, op: CsgOp, skipUndo?: boolean) => void;
   updateSweepOp: (
     partId: string,
-    updates: Partial<SweepOp>,
+    updates: Partial<Pick<SweepOp, "path">> & SweepOptions,
     skipUndo?: boolean,
   ) => void;
   renamePart: (partId: string, name: string) => void;
@@ -581,8 +582,13 @@ export const useDocumentStore = create<DocumentState>((set, get) => ({
     const newDoc = structuredClone(state.document);
     const node = newDoc.nodes[String(part.sweepNodeId)];
     if (node && node.op.type === "Sweep") {
-      // Merge updates into the existing op
-      node.op = { ...node.op, ...updates };
+      // Merge updates into the existing op and its options
+      const { path, ...options } = updates;
+      node.op = {
+        ...node.op,
+        path: path ?? node.op.path,
+        options: { ...node.op.options, ...options },
+      };
     }
 
     set({ document: newDoc, isDirty: true, ...undoState });
@@ -761,8 +767,11 @@ export const useDocumentStore = create<DocumentState>((set, get) => ({
         if ("sketch" in clonedOp && typeof clonedOp.sketch === "number") {
           clonedOp.sketch = idMap.get(clonedOp.sketch) ?? clonedOp.sketch;
         }
-        if ("sketches" in clonedOp && Array.isArray(clonedOp.sketches)) {
-          clonedOp.sketches = clonedOp.sketches.map(
+        if ("profile" in clonedOp && typeof clonedOp.profile === "number") {
+          clonedOp.profile = idMap.get(clonedOp.profile) ?? clonedOp.profile;
+        }
+        if ("profiles" in clonedOp && Array.isArray(clonedOp.profiles)) {
+          clonedOp.profiles = clonedOp.profiles.map(
             (id: number) => idMap.get(id) ?? id,
           );
         }
@@ -929,8 +938,11 @@ export const useDocumentStore = create<DocumentState>((set, get) => ({
       if ("sketch" in clonedOp && typeof clonedOp.sketch === "number") {
         clonedOp.sketch = idMap.get(clonedOp.sketch) ?? clonedOp.sketch;
       }
-      if ("sketches" in clonedOp && Array.isArray(clonedOp.sketches)) {
-        clonedOp.sketches = clonedOp.sketches.map(
+      if ("profile" in clonedOp && typeof clonedOp.profile === "number") {
+        clonedOp.profile = idMap.get(clonedOp.profile) ?? clonedOp.profile;
+      }
+      if ("profiles" in clonedOp && Array.isArray(clonedOp.profiles)) {
+        clonedOp.profiles = clonedOp.profiles.map(
           (id: number) => idMap.get(id) ?? id
         );
       }
@@ -1243,11 +1255,13 @@ export const useDocumentStore = create<DocumentState>((set, get) => ({
 
     const sweepOp: CsgOp = {
       type: "Sweep",
-      sketch: sketchId,
+      profile: sketchId,
       path,
-      twist_angle: options.twist_angle,
-      scale_start: options.scale_start,
-      scale_end: options.scale_end,
+      options: {
+        twist_angle: options.twist_angle,
+        scale_start: options.scale_start,
+        scale_end: options.scale_end,
+      },
     };
 
     const scaleOp: CsgOp = {
@@ -1349,7 +1363,7 @@ export const useDocumentStore = create<DocumentState>((set, get) => ({
 
     const loftOp: CsgOp = {
       type: "Loft",
-      sketches: sketchIds,
+      profiles: sketchIds,
       closed: options.closed,
     };
 
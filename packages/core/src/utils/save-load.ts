@@ -1,5 +1,5 @@
 import type { Document, NodeId, CsgOp, Node } from "@vcad/ir";
-import { toCompact, fromCompact, createDocument } from "@vcad/ir";
+import { toCompact, fromCompact, createDocument, migrateDocument } from "@vcad/ir";
 import type { PartInfo, PrimitiveKind } from "../types.js";
 
 export interface VcadFile {
@@ -52,6 +52,7 @@ function parseJsonVcadFile(json: string): VcadFile {
     throw new Error("Invalid .vcad file: missing nextNodeId or nextPartNum");
   }
 
+  migrateDocument(data.document);
   return data;
 }
 
@@ -192,7 +193,7 @@ function derivePartFromRoot(
         id: partId,
         name,
         kind: "sweep",
-        sketchNodeId: coreOp.sketch,
+        sketchNodeId: coreOp.profile,
         sweepNodeId: coreNodeId,
         scaleNodeId,
         rotateNodeId,
@@ -204,7 +205,7 @@ function derivePartFromRoot(
         id: partId,
         name,
         kind: "loft",
-        sketchNodeIds: coreOp.sketches,
+        sketchNodeIds: coreOp.profiles,
         loftNodeId: coreNodeId,
         scaleNodeId,
         rotateNodeId,
@@ -371,10 +372,11 @@ function getChildNodes(op: CsgOp): NodeId[] {
       return [op.left, op.right];
     case "Extrude":
     case "Revolve":
-    case "Sweep":
       return [op.sketch];
+    case "Sweep":
+      return [op.profile];
     case "Loft":
-      return op.sketches;
+      return op.profiles;
     default:
       return [];
   }
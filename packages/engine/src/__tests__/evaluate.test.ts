@@ -625,7 +625,7 @@ describe("Sketch operations", () => {
           name: "swept",
           op: {
             type: "Sweep",
-            sketch: 1,
+            profile: 1,
             path: {
               type: "Line",
               start: { x: 0, y: 0, z: 0 },
@@ -666,7 +666,7 @@ describe("Sketch operations", () => {
           name: "spring",
           op: {
             type: "Sweep",
-            sketch: 1,
+            profile: 1,
             path: {
               type: "Helix",
               radius: 10,
@@ -725,7 +725,7 @@ describe("Sketch operations", () => {
           name: "lofted",
           op: {
             type: "Loft",
-            sketches: [1, 2],
+            profiles: [1, 2],
           },
         },
       ],
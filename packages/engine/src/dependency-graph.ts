@@ -39,10 +39,10 @@ function getChildNodeIds(op: CsgOp): NodeId[] {
       return [op.sketch];
 
     case "Sweep":
-      return [op.sketch];
+      return [op.profile];
 
     case "Loft":
-      return [...op.sketches];
+      return [...op.profiles];
 
     default:
       return [];
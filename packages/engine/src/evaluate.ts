@@ -49,12 +49,21 @@ function convertSketchToProfile(op: Sketch2DOp) {
     x_dir: [op.x_dir.x, op.x_dir.y, op.x_dir.z],
     y_dir: [op.y_dir.x, op.y_dir.y, op.y_dir.z],
     segments: op.segments.map(convertSegment),
+    holes: (op.holes ?? []).map((hole) => hole.map(convertSegment)),
   };
 }
 
 /** Type for the kernel module */
 interface KernelModule {
   Solid: typeof Solid;
+  resolveParameters: (docJson: string) => string;
+  svgProfiles: (
+    pathData: string,
+    scale: number,
+    origin: Float64Array,
+    xDir: Float64Array,
+    yDir: Float64Array,
+  ) => unknown[];
 }
 
 /** Extract a TriangleMesh from a Solid. */
@@ -237,6 +246,12 @@ export function evaluateDocument(
   const { Solid } = kernel;
   const cache = new Map<NodeId, Solid>();
 
+  // Resolve parameter expressions with the kernel, so that the geometry
+  // matches what the Rust evaluators produce
+  if (doc.expressions && Object.keys(doc.expressions).length > 0) {
+    doc = JSON.parse(kernel.resolveParameters(JSON.stringify(doc))) as Document;
+  }
+
   if (DEBUG_EVAL) {
     console.group("[ENGINE] evaluateDocument");
     console.log("Number of roots:", doc.roots.length);
@@ -273,7 +288,7 @@ export function evaluateDocument(
     }
 
     // Normal solid-based evaluation
-    const solid = evaluateNode(entry.root, doc.nodes, Solid, cache, 0);
+    const solid = evaluateNode(entry.root, doc.nodes, kernel, cache, 0);
     const mesh = solidToMesh(solid);
     if (DEBUG_EVAL) {
       console.log("Result mesh - triangles:", mesh.indices.length / 3, "vertices:", mesh.positions.length / 3);
@@ -307,7 +322,7 @@ export function evaluateDocument(
 
     evaluatedPartDefs = [];
     for (const [id, partDef] of Object.entries(doc.partDefs)) {
-      const solid = evaluateNode(partDef.root, doc.nodes, Solid, cache, 0);
+      const solid = evaluateNode(partDef.root, doc.nodes, kernel, cache, 0);
       partDefSolids.set(id, solid);
       const mesh = solidToMesh(solid);
       partDefMeshes.set(id, mesh);
@@ -385,7 +400,7 @@ export function evaluateDocument(
 function evaluateNode(
   nodeId: NodeId,
   nodes: Record<string, Node>,
-  Solid: typeof import("@vcad/kernel-wasm").Solid,
+  kernel: KernelModule,
   cache: Map<NodeId, import("@vcad/kernel-wasm").Solid>,
   depth = 0,
 ): import("@vcad/kernel-wasm").Solid {
@@ -407,7 +422,7 @@ function evaluateNode(
     const indent = "  ".repeat(depth);
     console.log(`${indent}[NODE] ${nodeId} type=${node.op.type} name=${node.name || "(unnamed)"}`);
   }
-  const result = evaluateOp(node.op, nodes, Solid, cache, depth);
+  const result = evaluateOp(node.op, nodes, kernel, cache, depth);
   cache.set(nodeId, result);
   return result;
 }
@@ -415,16 +430,24 @@ function evaluateNode(
 function evaluateOp(
   op: CsgOp,
   nodes: Record<string, Node>,
-  Solid: typeof import("@vcad/kernel-wasm").Solid,
+  kernel: KernelModule,
   cache: Map<NodeId, import("@vcad/kernel-wasm").Solid>,
   depth = 0,
 ): import("@vcad/kernel-wasm").Solid {
+  const { Solid } = kernel;
   switch (op.type) {
     case "Cube":
       if (DEBUG_EVAL) {
         const indent = "  ".repeat(depth);
         console.log(`${indent}  -> Cube(${op.size.x}, ${op.size.y}, ${op.size.z})`);
       }
+      if (op.origin === "center") {
+        return Solid.cube(op.size.x, op.size.y, op.size.z).translate(
+          -op.size.x / 2,
+          -op.size.y / 2,
+          -op.size.z / 2,
+        );
+      }
       return Solid.cube(op.size.x, op.size.y, op.size.z);
 
     case "Cylinder":
@@ -453,8 +476,8 @@ function evaluateOp(
         const indent = "  ".repeat(depth);
         console.log(`${indent}  -> Union(left=${op.left}, right=${op.right})`);
       }
-      const left = evaluateNode(op.left, nodes, Solid, cache, depth + 1);
-      const right = evaluateNode(op.right, nodes, Solid, cache, depth + 1);
+      const left = evaluateNode(op.left, nodes, kernel, cache, depth + 1);
+      const right = evaluateNode(op.right, nodes, kernel, cache, depth + 1);
       return left.union(right);
     }
 
@@ -463,8 +486,8 @@ function evaluateOp(
         const indent = "  ".repeat(depth);
         console.log(`${indent}  -> Difference(left=${op.left}, right=${op.right})`);
       }
-      const left = evaluateNode(op.left, nodes, Solid, cache, depth + 1);
-      const right = evaluateNode(op.right, nodes, Solid, cache, depth + 1);
+      const left = evaluateNode(op.left, nodes, kernel, cache, depth + 1);
+      const right = evaluateNode(op.right, nodes, kernel, cache, depth + 1);
       if (DEBUG_EVAL) {
         const indent = "  ".repeat(depth);
         const leftTris = left.getMesh().indices.length / 3;
@@ -480,8 +503,8 @@ function evaluateOp(
     }
 
     case "Intersection": {
-      const left = evaluateNode(op.left, nodes, Solid, cache, depth + 1);
-      const right = evaluateNode(op.right, nodes, Solid, cache, depth + 1);
+      const left = evaluateNode(op.left, nodes, kernel, cache, depth + 1);
+      const right = evaluateNode(op.right, nodes, kernel, cache, depth + 1);
       return left.intersection(right);
     }
 
@@ -490,7 +513,7 @@ function evaluateOp(
         const indent = "  ".repeat(depth);
         console.log(`${indent}  -> Translate(${op.offset.x}, ${op.offset.y}, ${op.offset.z}) child=${op.child}`);
       }
-      const child = evaluateNode(op.child, nodes, Solid, cache, depth + 1);
+      const child = evaluateNode(op.child, nodes, kernel, cache, depth + 1);
       return child.translate(op.offset.x, op.offset.y, op.offset.z);
     }
 
@@ -499,7 +522,7 @@ function evaluateOp(
         const indent = "  ".repeat(depth);
         console.log(`${indent}  -> Rotate(${op.angles.x}, ${op.angles.y}, ${op.angles.z}) child=${op.child}`);
       }
-      const child = evaluateNode(op.child, nodes, Solid, cache, depth + 1);
+      const child = evaluateNode(op.child, nodes, kernel, cache, depth + 1);
       return child.rotate(op.angles.x, op.angles.y, op.angles.z);
     }
 
@@ -508,7 +531,7 @@ function evaluateOp(
         const indent = "  ".repeat(depth);
         console.log(`${indent}  -> Scale(${op.factor.x}, ${op.factor.y}, ${op.factor.z}) child=${op.child}`);
       }
-      const child = evaluateNode(op.child, nodes, Solid, cache, depth + 1);
+      const child = evaluateNode(op.child, nodes, kernel, cache, depth + 1);
       return child.scale(op.factor.x, op.factor.y, op.factor.z);
     }
 
@@ -559,15 +582,26 @@ function evaluateOp(
         return result;
       }
 
-      // Handle Sketch2D nodes (normal sketch extrusion)
-      if (sketchNode.op.type !== "Sketch2D") {
+      // Sketch2D has one profile; SVG paths have one per separate outline
+      let profiles: unknown[];
+      if (sketchNode.op.type === "Sketch2D") {
+        profiles = [convertSketchToProfile(sketchNode.op)];
+      } else if (sketchNode.op.type === "SvgProfile") {
+        const svgOp = sketchNode.op;
+        profiles = kernel.svgProfiles(
+          svgOp.path_data,
+          svgOp.scale,
+          new Float64Array([svgOp.plane_origin.x, svgOp.plane_origin.y, svgOp.plane_origin.z]),
+          new Float64Array([svgOp.x_dir.x, svgOp.x_dir.y, svgOp.x_dir.z]),
+          new Float64Array([svgOp.y_dir.x, svgOp.y_dir.y, svgOp.y_dir.z]),
+        );
+      } else {
         throw new Error(`Extrude references invalid sketch node: ${op.sketch} (type=${sketchNode.op.type})`);
       }
-      const profile = convertSketchToProfile(sketchNode.op);
       // Use extrudeWithOptions if twist or scale is specified
       const hasTwist = op.twist_angle !== undefined && Math.abs(op.twist_angle) > 1e-12;
       const hasScale = op.scale_end !== undefined && Math.abs(op.scale_end - 1.0) > 1e-12;
-      const result = (hasTwist || hasScale)
+      const extrudeProfile = (profile: unknown) => (hasTwist || hasScale)
         ? Solid.extrudeWithOptions(
             profile,
             direction,
@@ -575,6 +609,9 @@ function evaluateOp(
             op.scale_end ?? 1.0
           )
         : Solid.extrude(profile, direction);
+      const result = profiles
+        .map(extrudeProfile)
+        .reduce((acc, part) => acc.union(part), Solid.empty());
       if (DEBUG_EVAL) {
         const indent = "  ".repeat(depth);
         console.log(`${indent}  -> Extrude result: ${result.getMesh().indices.length / 3} tris`);
@@ -602,7 +639,7 @@ function evaluateOp(
     }
 
     case "LinearPattern": {
-      const child = evaluateNode(op.child, nodes, Solid, cache, depth + 1);
+      const child = evaluateNode(op.child, nodes, kernel, cache, depth + 1);
       return child.linearPattern(
         op.direction.x,
         op.direction.y,
@@ -613,7 +650,7 @@ function evaluateOp(
     }
 
     case "CircularPattern": {
-      const child = evaluateNode(op.child, nodes, Solid, cache, depth + 1);
+      const child = evaluateNode(op.child, nodes, kernel, cache, depth + 1);
       return child.circularPattern(
         op.axis_origin.x,
         op.axis_origin.y,
@@ -627,74 +664,118 @@ function evaluateOp(
     }
 
     case "Shell": {
-      const child = evaluateNode(op.child, nodes, Solid, cache, depth + 1);
+      const child = evaluateNode(op.child, nodes, kernel, cache, depth + 1);
       return child.shell(op.thickness);
     }
 
     case "Fillet": {
-      const child = evaluateNode(op.child, nodes, Solid, cache, depth + 1);
+      const child = evaluateNode(op.child, nodes, kernel, cache, depth + 1);
       return child.fillet(op.radius);
     }
 
     case "Chamfer": {
-      const child = evaluateNode(op.child, nodes, Solid, cache, depth + 1);
+      const child = evaluateNode(op.child, nodes, kernel, cache, depth + 1);
       return child.chamfer(op.distance);
     }
 
     case "Sweep": {
-      const sketchNode = nodes[String(op.sketch)];
+      const sketchNode = nodes[String(op.profile)];
       if (!sketchNode || sketchNode.op.type !== "Sketch2D") {
-        throw new Error(`Sweep references invalid sketch node: ${op.sketch}`);
+        throw new Error(`Sweep references invalid sketch node: ${op.profile}`);
       }
       const profile = convertSketchToProfile(sketchNode.op);
-
-      if (op.path.type === "Line") {
-        const start = new Float64Array([
-          op.path.start.x,
-          op.path.start.y,
-          op.path.start.z,
-        ]);
-        const end = new Float64Array([
-          op.path.end.x,
-          op.path.end.y,
-          op.path.end.z,
-        ]);
-        return Solid.sweepLine(
-          profile,
-          start,
-          end,
-          op.twist_angle,
-          op.scale_start,
-          op.scale_end,
-          op.orientation,
-        );
-      } else {
-        // Helix path
-        return Solid.sweepHelix(
-          profile,
-          op.path.radius,
-          op.path.pitch,
-          op.path.height,
-          op.path.turns,
-          op.twist_angle,
-          op.scale_start,
-          op.scale_end,
-          op.path_segments,
-          op.arc_segments,
-          op.orientation,
-        );
+      const options = op.options ?? {};
+
+      switch (op.path.type) {
+        case "Line": {
+          const start = new Float64Array([
+            op.path.start.x,
+            op.path.start.y,
+            op.path.start.z,
+          ]);
+          const end = new Float64Array([
+            op.path.end.x,
+            op.path.end.y,
+            op.path.end.z,
+          ]);
+          return Solid.sweepLine(
+            profile,
+            start,
+            end,
+            options.twist_angle,
+            options.scale_start,
+            options.scale_end,
+            options.orientation,
+            options.on_degenerate,
+          );
+        }
+        case "Helix":
+          return Solid.sweepHelix(
+            profile,
+            op.path.radius,
+            op.path.pitch,
+            op.path.height,
+            op.path.turns,
+            options.twist_angle,
+            options.scale_start,
+            options.scale_end,
+            options.path_segments,
+            options.arc_segments,
+            options.orientation,
+            options.on_degenerate,
+          );
+        case "Polyline":
+          return Solid.sweepPolyline(
+            profile,
+            new Float64Array(op.path.points.flatMap((p) => [p.x, p.y, p.z])),
+            options.twist_angle,
+            options.scale_start,
+            options.scale_end,
+            options.path_segments,
+            options.arc_segments,
+            options.orientation,
+            options.on_degenerate,
+          );
       }
     }
 
     case "Loft": {
-      const profiles = op.sketches.map((sketchId) => {
+      const profiles = op.profiles.map((sketchId) => {
         const sketchNode = nodes[String(sketchId)];
         if (!sketchNode || sketchNode.op.type !== "Sketch2D") {
           throw new Error(`Loft references invalid sketch node: ${sketchId}`);
         }
         return convertSketchToProfile(sketchNode.op);
       });
-      return Solid.loft(profiles, op.closed);
+      return Solid.loft(profiles, op.closed, op.mode);
+    }
+
+    case "Text": {
+      // Extrude along the text plane normal; unknown fonts fall back to the
+      // built-in one, as in the Rust evaluator
+      const n = {
+        x: op.x_dir.y * op.y_dir.z - op.x_dir.z * op.y_dir.y,
+        y: op.x_dir.z * op.y_dir.x - op.x_dir.x * op.y_dir.z,
+        z: op.x_dir.x * op.y_dir.y - op.x_dir.y * op.y_dir.x,
+      };
+      const len = Math.hypot(n.x, n.y, n.z);
+      if (len === 0) {
+        throw new Error(`Text node has parallel x_dir and y_dir`);
+      }
+      const scale = op.depth / len;
+      if (op.font !== "sans-serif") {
+        console.warn(`Unknown font '${op.font}', falling back to 'sans-serif'`);
+      }
+      return Solid.textExtrude(
+        op.content,
+        new Float64Array([op.plane_origin.x, op.plane_origin.y, op.plane_origin.z]),
+        new Float64Array([op.x_dir.x, op.x_dir.y, op.x_dir.z]),
+        new Float64Array([op.y_dir.x, op.y_dir.y, op.y_dir.z]),
+        new Float64Array([n.x * scale, n.y * scale, n.z * scale]),
+        op.height,
+        undefined,
+        op.alignment || undefined,
+      );
     }
 
     case "ImportedMesh":
@@ -94,6 +94,14 @@ export interface DetailView {
 /** Type for the initialized kernel module */
 export interface KernelModule {
   Solid: typeof Solid;
+  resolveParameters: (docJson: string) => string;
+  svgProfiles: (
+    pathData: string,
+    scale: number,
+    origin: Float64Array,
+    xDir: Float64Array,
+    yDir: Float64Array,
+  ) => unknown[];
   WasmAnnotationLayer: typeof WasmAnnotationLayer;
   projectMesh: (mesh: { positions: Float32Array; indices: Uint32Array }, viewDirection: string) => ProjectedView | null;
   importStepBuffer: (data: Uint8Array) => Array<{ positions: Float32Array; indices: Uint32Array }>;
@@ -198,6 +206,8 @@ export class Engine {
 
     return new Engine({
       Solid: wasmModule.Solid,
+      resolveParameters: wasmModule.resolveParameters,
+      svgProfiles: wasmModule.svgProfiles,
       WasmAnnotationLayer: wasmModule.WasmAnnotationLayer,
       projectMesh: wasmModule.projectMesh,
       importStepBuffer: wasmModule.importStepBuffer,
@@ -228,6 +228,85 @@ describe("Compact IR", () => {
       expect(doc2.roots[0].root).toBe(3);
     });
 
+    it("roundtrips cube origin", () => {
+      const doc = fromCompact("C 10 20 30 center\nC 10 20 30");
+      const [centered, corner] = [doc.nodes["0"].op, doc.nodes["1"].op];
+      expect(centered).toEqual({ type: "Cube", size: { x: 10, y: 20, z: 30 }, origin: "center" });
+      expect(corner).toEqual({ type: "Cube", size: { x: 10, y: 20, z: 30 } });
+
+      const compact = toCompact(doc);
+      expect(compact).toContain("C 10 20 30 center");
+      const doc2 = fromCompact(compact);
+      expect(doc2.nodes["0"].op).toEqual(centered);
+      expect(doc2.nodes["1"].op).toEqual(corner);
+    });
+
+    it("roundtrips an extruded SVG profile", () => {
+      const doc = fromCompact('SVG "M0 0 H10 V10 H0 Z" 0.5 0 0 1 1 0 0 0 1 0 "logo"\nE 0 0 0 3');
+      const svg = doc.nodes["0"];
+      expect(svg.name).toBe("logo");
+      expect(svg.op).toEqual({
+        type: "SvgProfile",
+        path_data: "M0 0 H10 V10 H0 Z",
+        scale: 0.5,
+        plane_origin: { x: 0, y: 0, z: 1 },
+        x_dir: { x: 1, y: 0, z: 0 },
+        y_dir: { x: 0, y: 1, z: 0 },
+      });
+
+      const doc2 = fromCompact(toCompact(doc));
+      expect(doc2.nodes["0"].op).toEqual(svg.op);
+      expect(doc2.nodes["1"].op).toEqual(doc.nodes["1"].op);
+    });
+
+    it("roundtrips 3D text", () => {
+      const doc = fromCompact('TX "AB \\"x\\"" "sans-serif" 10 1.5 0 0 5 1 0 0 0 1 0 center "Label"');
+      const node = doc.nodes["0"];
+      expect(node.name).toBe("Label");
+      expect(node.op).toEqual({
+        type: "Text",
+        content: 'AB "x"',
+        font: "sans-serif",
+        height: 10,
+        depth: 1.5,
+        plane_origin: { x: 0, y: 0, z: 5 },
+        x_dir: { x: 1, y: 0, z: 0 },
+        y_dir: { x: 0, y: 1, z: 0 },
+        alignment: "center",
+      });
+
+      const doc2 = fromCompact(toCompact(doc));
+      expect(doc2.nodes["0"]).toEqual(node);
+      expect(() => fromCompact('TX "A" "sans-serif" 10 1 0 0 0 1 0 0 0 1 0 top')).toThrow(CompactParseError);
+    });
+
+    it("roundtrips part mass properties", () => {
+      const doc = fromCompact('C 50 30 5\nPDEF base "Base" 0\nPMASS base 1.5 25 15 2.5 400 -1 0 900 0 1200');
+      const props = doc.partDefs?.["base"].massProperties;
+      expect(props).toEqual({
+        mass: 1.5,
+        center_of_mass: { x: 25, y: 15, z: 2.5 },
+        inertia: [[400, -1, 0], [-1, 900, 0], [0, 0, 1200]],
+      });
+
+      const doc2 = fromCompact(toCompact(doc));
+      expect(doc2.partDefs?.["base"].massProperties).toEqual(props);
+      expect(() => fromCompact("C 1 1 1\nPMASS base 1 0 0 0 1 0 0 1 0 1")).toThrow(CompactParseError);
+    });
+
+    it("roundtrips part def collision roots", () => {
+      const doc = fromCompact('C 50 30 5\nC 10 10 10\nPDEF base "Base" 0 _ 1\nPDEF arm "Arm" 1 steel');
+      expect(doc.partDefs?.["base"]).toEqual({ id: "base", name: "Base", root: 0, defaultMaterial: undefined, collisionRoot: 1 });
+      expect(doc.partDefs?.["arm"].collisionRoot).toBeUndefined();
+
+      const doc2 = fromCompact(toCompact(doc));
+      expect(doc2.partDefs).toEqual(doc.partDefs);
+    });
+
+    it("rejects an unknown cube origin", () => {
+      expect(() => fromCompact("C 10 20 30 middle")).toThrow(CompactParseError);
+    });
+
     it("serializes all primitives", () => {
       const doc = createDocument();
       doc.nodes["0"] = { id: 0, name: null, op: { type: "Cube", size: { x: 10, y: 20, z: 30 } } };
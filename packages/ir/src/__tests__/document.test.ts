@@ -3,6 +3,9 @@ import {
   createDocument,
   toJson,
   fromJson,
+  migrateDocument,
+  DOCUMENT_VERSION,
+  type Document,
   type Node,
   type MaterialDef,
   type CsgOp,
@@ -76,10 +79,52 @@ describe("Document", () => {
 
   it("creates empty document with defaults", () => {
     const doc = createDocument();
-    expect(doc.version).toBe("0.1");
+    expect(doc.version).toBe(DOCUMENT_VERSION);
     expect(Object.keys(doc.nodes)).toHaveLength(0);
     expect(Object.keys(doc.materials)).toHaveLength(0);
     expect(Object.keys(doc.part_materials)).toHaveLength(0);
     expect(doc.roots).toHaveLength(0);
   });
+
+  it("migrates legacy 0.1 sweeps and lofts", () => {
+    const legacy = {
+      version: "0.1",
+      nodes: {
+        "1": {
+          id: 1,
+          name: null,
+          op: {
+            type: "Sweep",
+            sketch: 0,
+            path: { type: "Line", start: { x: 0, y: 0, z: 0 }, end: { x: 0, y: 0, z: 10 } },
+            twist_angle: 0.5,
+            arc_segments: 12,
+          },
+        },
+        "2": { id: 2, name: null, op: { type: "Loft", sketches: [0, 3], closed: true } },
+        "4": {
+          id: 4,
+          name: null,
+          op: { type: "Extrude", sketch: 0, direction: { x: 0, y: 0, z: 5 }, twist_angle: 0, scale_end: 1 },
+        },
+      },
+      roots: [],
+    } as unknown as Document;
+
+    const doc = migrateDocument(legacy);
+    expect(doc.version).toBe(DOCUMENT_VERSION);
+    expect(doc.materials).toEqual({});
+    expect(doc.nodes["1"].op).toEqual({
+      type: "Sweep",
+      profile: 0,
+      path: { type: "Line", start: { x: 0, y: 0, z: 0 }, end: { x: 0, y: 0, z: 10 } },
+      options: { twist_angle: 0.5, arc_segments: 12 },
+    });
+    expect(doc.nodes["2"].op).toEqual({ type: "Loft", profiles: [0, 3], closed: true });
+    expect(doc.nodes["4"].op).toEqual({ type: "Extrude", sketch: 0, direction: { x: 0, y: 0, z: 5 } });
+
+    // Current documents pass through unchanged
+    expect(migrateDocument(createDocument())).toEqual(createDocument());
+    expect(() => migrateDocument({ ...createDocument(), version: "9.0" })).toThrow();
+  });
 });
@@ -160,9 +160,13 @@ export type SketchConstraint =
 
 // --- CsgOp discriminated union ---
 
+/** Where a cube sits relative to the origin. Defaults to "corner". */
+export type BoxOrigin = "corner" | "center";
+
 export interface CubeOp {
   type: "Cube";
   size: Vec3;
+  origin?: BoxOrigin;
 }
 
 export interface CylinderOp {
@@ -232,6 +236,28 @@ export interface Sketch2DOp {
   x_dir: Vec3;
   y_dir: Vec3;
   segments: SketchSegment2D[];
+  /** Closed inner loops cut out of the profile. */
+  holes?: SketchSegment2D[][];
+}
+
+/**
+ * Sketch profiles imported from SVG path data.
+ *
+ * Each separate outline of the path becomes its own profile, with the
+ * holes inside it; SVG's downward Y axis is flipped onto `y_dir`.
+ */
+export interface SvgProfileOp {
+  type: "SvgProfile";
+  /** SVG path data, e.g. `"M0 0 L10 0 L10 10 Z"`. */
+  path_data: string;
+  /** Millimeters per SVG user unit. */
+  scale: number;
+  /** Origin of the sketch plane in 3D. */
+  plane_origin: Vec3;
+  /** Unit vector along the local X axis. */
+  x_dir: Vec3;
+  /** Unit vector along the local Y axis. */
+  y_dir: Vec3;
 }
 
 export interface ExtrudeOp {
@@ -318,6 +344,33 @@ export interface Text2DOp {
   alignment?: TextAlignment;
 }
 
+/**
+ * 3D text: glyph outlines extruded into a solid, e.g. for engraved or
+ * embossed labels.
+ *
+ * Laid out as for {@link Text2DOp}, with the baseline starting at
+ * `plane_origin`, and extruded by `depth` along `x_dir × y_dir`.
+ */
+export interface TextOp {
+  type: "Text";
+  /** The text string to render. */
+  content: string;
+  /** Font name. Unknown fonts fall back to the built-in sans-serif. */
+  font: string;
+  /** Text height in mm. */
+  height: number;
+  /** Extrusion depth in mm. */
+  depth: number;
+  /** Origin of the text plane in 3D. */
+  plane_origin: Vec3;
+  /** X direction of the text plane (text flows along this axis). */
+  x_dir: Vec3;
+  /** Y direction of the text plane (text height along this axis). */
+  y_dir: Vec3;
+  /** Text alignment (default "left"). */
+  alignment?: TextAlignment;
+}
+
 /**
  * An imported mesh (e.g., from STEP file).
  * Stores pre-tessellated geometry that can be transformed but not used in booleans.
@@ -352,26 +405,46 @@ export interface HelixPath {
   turns: number;
 }
 
+/** Connected straight segments through the given points. */
+export interface PolylinePath {
+  type: "Polyline";
+  /** Path vertices, at least two. */
+  points: Vec3[];
+}
+
 /** Path curve types for sweep operations. */
-export type PathCurve = LinePath | HelixPath;
+export type PathCurve = LinePath | HelixPath | PolylinePath;
 
-/** Sweep operation — extrude a profile along a path curve. */
-export interface SweepOp {
-  type: "Sweep";
-  sketch: NodeId;              // Reference to Sketch2D node
-  path: PathCurve;             // The path to sweep along
+/** Options for a sweep. Unset fields use the kernel defaults. */
+/** What a sweep does at a path station where the profile frame is undefined. */
+export type DegeneratePolicy = "error" | "skip" | "interpolate";
+
+export interface SweepOptions {
   twist_angle?: number;        // Total twist in radians (default 0)
   scale_start?: number;        // Scale at start (default 1.0)
   scale_end?: number;          // Scale at end (default 1.0)
-  orientation?: number;        // Initial profile rotation around path tangent (radians, default 0)
   path_segments?: number;      // Segments along path (0 = auto)
   arc_segments?: number;       // Segments per arc in profile (default 8)
+  orientation?: number;        // Initial profile rotation around path tangent (radians, default 0)
+  on_degenerate?: DegeneratePolicy; // Handling of degenerate stations (default "error")
 }
 
+/** Sweep operation — extrude a profile along a path curve. */
+export interface SweepOp {
+  type: "Sweep";
+  profile: NodeId;             // Reference to Sketch2D node
+  path: PathCurve;             // The path to sweep along
+  options?: SweepOptions;
+}
+
+/** How a loft interpolates between adjacent profiles. */
+export type LoftMode = "ruled" | "smooth";
+
 /** Loft operation — interpolate between multiple profiles. */
 export interface LoftOp {
   type: "Loft";
-  sketches: NodeId[];          // Array of Sketch2D node references (≥2)
+  profiles: NodeId[];          // Array of Sketch2D node references (≥2)
+  mode?: LoftMode;             // Interpolation (default "ruled")
   closed?: boolean;            // Connect last to first (creates tube)
 }
 
@@ -389,6 +462,7 @@ export type CsgOp =
   | RotateOp
   | ScaleOp
   | Sketch2DOp
+  | SvgProfileOp
   | ExtrudeOp
   | RevolveOp
   | LinearPatternOp
@@ -397,6 +471,7 @@ export type CsgOp =
   | FilletOp
   | ChamferOp
   | Text2DOp
+  | TextOp
   | SweepOp
   | LoftOp
   | ImportedMeshOp;
@@ -406,6 +481,8 @@ export interface Node {
   id: NodeId;
   name: string | null;
   op: CsgOp;
+  /** Free-form tool metadata; ignored by evaluation and compact IR. */
+  attributes?: Record<string, string>;
 }
 
 /** PBR material definition. */
@@ -416,6 +493,12 @@ export interface MaterialDef {
   roughness: number;
   density?: number;
   friction?: number;
+  /** Opacity in 0..1 (default: 1). */
+  opacity?: number;
+  /** Emissive color as [r, g, b] in 0..1. */
+  emissive?: [number, number, number];
+  /** Path or URI of a base color texture image. */
+  baseColorTexture?: string;
 }
 
 /** An entry in the scene — a root node with an assigned material. */
@@ -461,11 +544,21 @@ export interface Instance {
 /** Alias for Instance (used in some components). */
 export type PartInstance = Instance;
 
+/** Mass, center of mass (mm) and inertia tensor about it (kg·mm²). */
+export interface MassProperties {
+  mass: number;
+  center_of_mass: Vec3;
+  inertia: [[number, number, number], [number, number, number], [number, number, number]];
+}
+
 /** A reusable part definition in an assembly. */
 export interface PartDef {
   id: string;
   name?: string;
   root: NodeId;
+  collisionRoot?: NodeId;
+  /** Explicit mass properties, e.g. from a URDF `<inertial>`. */
+  massProperties?: MassProperties;
   defaultMaterial?: string;
 }
 
@@ -651,12 +744,19 @@ export interface Document {
   joints?: Joint[];
   /** The instance that is fixed in world space (ground). */
   groundInstanceId?: string;
+  /** Named values that expressions can refer to. */
+  parameters?: Record<string, number>;
+  /** Expressions driving numeric node fields: node ID → field path → expression. */
+  expressions?: Record<string, Record<string, string>>;
 }
 
+/** Document schema version written by this package (`CURRENT_VERSION` in Rust). */
+export const DOCUMENT_VERSION = "0.2";
+
 /** Create a new empty document. */
 export function createDocument(): Document {
   return {
-    version: "0.1",
+    version: DOCUMENT_VERSION,
     nodes: {},
     materials: {},
     part_materials: {},
@@ -669,9 +769,79 @@ export function toJson(doc: Document): string {
   return JSON.stringify(doc, null, 2);
 }
 
-/** Deserialize a document from a JSON string. */
+/** Deserialize a document from a JSON string, upgrading older versions. */
 export function fromJson(json: string): Document {
-  return JSON.parse(json) as Document;
+  return migrateDocument(JSON.parse(json) as Document);
+}
+
+/** Sweep options that 0.1 writers stored directly on the op. */
+const LEGACY_SWEEP_OPTIONS = [
+  "twist_angle",
+  "scale_start",
+  "scale_end",
+  "path_segments",
+  "arc_segments",
+  "orientation",
+] as const;
+
+/** Move `op[from]` to `op[to]`, unless `to` is already set. */
+function renameField(op: Record<string, unknown>, from: string, to: string): void {
+  if (to in op || !(from in op)) return;
+  op[to] = op[from];
+  delete op[from];
+}
+
+/**
+ * Upgrade a parsed document to {@link DOCUMENT_VERSION} in place, like
+ * `Document::from_json_migrated` in the Rust IR.
+ *
+ * 0.1 → 0.2 renames a sweep's `sketch` to `profile` and moves its flat
+ * options into `options`, renames a loft's `sketches` to `profiles`, and
+ * drops identity `twist_angle: 0` / `scale_end: 1` from extrusions. Missing
+ * top-level collections and version are filled in as 0.1 files allowed.
+ *
+ * @throws If the document's version is not one this package can upgrade.
+ */
+export function migrateDocument(doc: Document): Document {
+  doc.version ??= "0.1";
+  if (doc.version === DOCUMENT_VERSION) return doc;
+  if (doc.version !== "0.1") {
+    throw new Error(
+      `Cannot migrate document version '${doc.version}' to '${DOCUMENT_VERSION}'`,
+    );
+  }
+
+  doc.nodes ??= {};
+  doc.materials ??= {};
+  doc.part_materials ??= {};
+  doc.roots ??= [];
+  for (const node of Object.values(doc.nodes)) {
+    const op = node.op as unknown as Record<string, unknown>;
+    switch (op.type) {
+      case "Extrude":
+        if (op.twist_angle === 0) delete op.twist_angle;
+        if (op.scale_end === 1) delete op.scale_end;
+        break;
+      case "Sweep": {
+        renameField(op, "sketch", "profile");
+        const options: Record<string, unknown> = {
+          ...(op.options as Record<string, unknown> | undefined),
+        };
+        for (const key of LEGACY_SWEEP_OPTIONS) {
+          if (!(key in op)) continue;
+          if (op[key] != null && !(key in options)) options[key] = op[key];
+          delete op[key];
+        }
+        if (Object.keys(options).length > 0) op.options = options;
+        break;
+      }
+      case "Loft":
+        renameField(op, "sketches", "profiles");
+        break;
+    }
+  }
+  doc.version = DOCUMENT_VERSION;
+  return doc;
 }
 
 // ============================================================================
@@ -730,6 +900,10 @@ export function fromCompact(compact: string): Document {
         parsePartDef(doc, parts, i);
         break;
 
+      case 'PMASS':
+        parsePartMass(doc, parts, i);
+        break;
+
       case 'INST':
         parseInstance(doc, parts, i);
         break;
@@ -949,8 +1123,21 @@ export function toCompact(doc: Document): string {
       let line = `PDEF ${escapeId(pdef.id)} ${formatQuotedString(pdef.name ?? pdef.id)} ${mappedRoot}`;
       if (pdef.defaultMaterial) {
         line += ` ${escapeId(pdef.defaultMaterial)}`;
+      } else if (pdef.collisionRoot !== undefined) {
+        line += ' _';
+      }
+      if (pdef.collisionRoot !== undefined) {
+        const mappedCollision = idMap.get(pdef.collisionRoot);
+        if (mappedCollision === undefined) {
+          throw new Error(`Unknown part def collision root ${pdef.collisionRoot}`);
+        }
+        line += ` ${mappedCollision}`;
       }
       lines.push(line);
+      if (pdef.massProperties) {
+        const { mass, center_of_mass: c, inertia: t } = pdef.massProperties;
+        lines.push(`PMASS ${escapeId(pdef.id)} ${mass} ${c.x} ${c.y} ${c.z} ${t[0][0]} ${t[0][1]} ${t[0][2]} ${t[1][1]} ${t[1][2]} ${t[2][2]}`);
+      }
     }
     lines.push('');
   }
@@ -1083,10 +1270,11 @@ function getChildren(op: CsgOp): number[] {
       return [op.child];
     case 'Extrude':
     case 'Revolve':
-    case 'Sweep':
       return [op.sketch];
+    case 'Sweep':
+      return [op.profile];
     case 'Loft':
-      return op.sketches;
+      return op.profiles;
     default:
       return [];
   }
@@ -1137,8 +1325,10 @@ function formatOp(op: CsgOp, idMap: Map<number, number>, name?: string): string
   const nameSuffix = name ? ` ${formatQuotedString(name)}` : '';
 
   switch (op.type) {
-    case 'Cube':
-      return `C ${op.size.x} ${op.size.y} ${op.size.z}${nameSuffix}`;
+    case 'Cube': {
+      const origin = op.origin === 'center' ? ' center' : '';
+      return `C ${op.size.x} ${op.size.y} ${op.size.z}${origin}${nameSuffix}`;
+    }
     case 'Cylinder':
       return `Y ${op.radius} ${op.height}${nameSuffix}`;
     case 'Sphere':
@@ -1182,10 +1372,19 @@ function formatOp(op: CsgOp, idMap: Map<number, number>, name?: string): string
       skLines.push('END');
       return skLines.join('\n');
     }
+    case 'SvgProfile':
+      return `SVG ${formatQuotedString(op.path_data)} ${op.scale} ${op.plane_origin.x} ${op.plane_origin.y} ${op.plane_origin.z} ${op.x_dir.x} ${op.x_dir.y} ${op.x_dir.z} ${op.y_dir.x} ${op.y_dir.y} ${op.y_dir.z}${nameSuffix}`;
     case 'Extrude':
       return `E ${idMap.get(op.sketch)} ${op.direction.x} ${op.direction.y} ${op.direction.z}${nameSuffix}`;
     case 'Revolve':
       return `V ${idMap.get(op.sketch)} ${op.axis_origin.x} ${op.axis_origin.y} ${op.axis_origin.z} ${op.axis_dir.x} ${op.axis_dir.y} ${op.axis_dir.z} ${op.angle_deg}${nameSuffix}`;
+    case 'Text': {
+      if (/[\n\r]/.test(op.content)) {
+        throw new Error('multi-line text not supported in compact format');
+      }
+      const align = op.alignment && op.alignment !== 'left' ? ` ${op.alignment}` : '';
+      return `TX ${formatQuotedString(op.content)} ${formatQuotedString(op.font)} ${op.height} ${op.depth} ${op.plane_origin.x} ${op.plane_origin.y} ${op.plane_origin.z} ${op.x_dir.x} ${op.x_dir.y} ${op.x_dir.z} ${op.y_dir.x} ${op.y_dir.y} ${op.y_dir.z}${align}${nameSuffix}`;
+    }
     default:
       throw new Error(`Unsupported op type for compact IR: ${(op as CsgOp).type}`);
   }
@@ -1357,10 +1556,30 @@ function parsePartDef(doc: Document, parts: string[], line: number): void {
   const id = parseStringArg(parts[1]);
   const name = parseStringArg(parts[2]);
   const root = parseInt(parts[3]);
-  const defaultMaterial = parts[4] ? parseStringArg(parts[4]) : undefined;
+  const defaultMaterial = parts[4] && parts[4] !== '_' ? parseStringArg(parts[4]) : undefined;
+  const collisionRoot = parts[5] ? parseInt(parts[5]) : undefined;
 
   if (!doc.partDefs) doc.partDefs = {};
   doc.partDefs[id] = { id, name, root, defaultMaterial };
+  if (collisionRoot !== undefined) doc.partDefs[id].collisionRoot = collisionRoot;
+}
+
+function parsePartMass(doc: Document, parts: string[], line: number): void {
+  if (parts.length !== 12) {
+    throw new CompactParseError(line, `PMASS requires 11 args, got ${parts.length - 1}`);
+  }
+
+  const id = parseStringArg(parts[1]);
+  const pdef = doc.partDefs?.[id];
+  if (!pdef) {
+    throw new CompactParseError(line, `PMASS for unknown part def ${id}`);
+  }
+  const [mass, cx, cy, cz, ixx, ixy, ixz, iyy, iyz, izz] = parts.slice(2).map(parseFloat);
+  pdef.massProperties = {
+    mass,
+    center_of_mass: { x: cx, y: cy, z: cz },
+    inertia: [[ixx, ixy, ixz], [ixy, iyy, iyz], [ixz, iyz, izz]],
+  };
 }
 
 function parseInstance(doc: Document, parts: string[], line: number): void {
@@ -1740,9 +1959,13 @@ function findSketchEndLine(startLine: number, lines: string[]): number {
 /** Parse a geometry opcode. */
 function parseGeometryOpcode(opcode: string, parts: string[], lineNum: number, lines: string[]): CsgOp {
   switch (opcode) {
-    case 'C':
-      if (parts.length !== 4) throw new CompactParseError(lineNum, `C requires 3 args, got ${parts.length - 1}`);
-      return { type: 'Cube', size: { x: parseFloat(parts[1]), y: parseFloat(parts[2]), z: parseFloat(parts[3]) } };
+    case 'C': {
+      if (parts.length !== 4 && parts.length !== 5) throw new CompactParseError(lineNum, `C requires 3 args, got ${parts.length - 1}`);
+      const size = { x: parseFloat(parts[1]), y: parseFloat(parts[2]), z: parseFloat(parts[3]) };
+      if (parts.length === 4) return { type: 'Cube', size };
+      if (parts[4] !== 'center') throw new CompactParseError(lineNum, `unknown cube origin: ${parts[4]}`);
+      return { type: 'Cube', size, origin: 'center' };
+    }
 
     case 'Y':
       if (parts.length !== 3) throw new CompactParseError(lineNum, `Y requires 2 args, got ${parts.length - 1}`);
@@ -1837,6 +2060,17 @@ function parseGeometryOpcode(opcode: string, parts: string[], lineNum: number, l
       return { type: 'Sketch2D', origin, x_dir, y_dir, segments };
     }
 
+    case 'SVG':
+      if (parts.length !== 12) throw new CompactParseError(lineNum, `SVG requires 11 args, got ${parts.length - 1}`);
+      return {
+        type: 'SvgProfile',
+        path_data: parseStringArg(parts[1]),
+        scale: parseFloat(parts[2]),
+        plane_origin: { x: parseFloat(parts[3]), y: parseFloat(parts[4]), z: parseFloat(parts[5]) },
+        x_dir: { x: parseFloat(parts[6]), y: parseFloat(parts[7]), z: parseFloat(parts[8]) },
+        y_dir: { x: parseFloat(parts[9]), y: parseFloat(parts[10]), z: parseFloat(parts[11]) },
+      };
+
     case 'E':
       if (parts.length !== 5) throw new CompactParseError(lineNum, `E requires 4 args, got ${parts.length - 1}`);
       return { type: 'Extrude', sketch: parseInt(parts[1]), direction: { x: parseFloat(parts[2]), y: parseFloat(parts[3]), z: parseFloat(parts[4]) } };
@@ -1845,6 +2079,26 @@ function parseGeometryOpcode(opcode: string, parts: string[], lineNum: number, l
       if (parts.length !== 9) throw new CompactParseError(lineNum, `V requires 8 args, got ${parts.length - 1}`);
       return { type: 'Revolve', sketch: parseInt(parts[1]), axis_origin: { x: parseFloat(parts[2]), y: parseFloat(parts[3]), z: parseFloat(parts[4]) }, axis_dir: { x: parseFloat(parts[5]), y: parseFloat(parts[6]), z: parseFloat(parts[7]) }, angle_deg: parseFloat(parts[8]) };
 
+    case 'TX': {
+      if (parts.length !== 14 && parts.length !== 15) throw new CompactParseError(lineNum, `TX requires 13 args, got ${parts.length - 1}`);
+      const align = parts[14] ?? 'left';
+      if (align !== 'left' && align !== 'center' && align !== 'right') {
+        throw new CompactParseError(lineNum, `unknown text alignment: ${align}`);
+      }
+      const op: TextOp = {
+        type: 'Text',
+        content: parseStringArg(parts[1]),
+        font: parseStringArg(parts[2]),
+        height: parseFloat(parts[3]),
+        depth: parseFloat(parts[4]),
+        plane_origin: { x: parseFloat(parts[5]), y: parseFloat(parts[6]), z: parseFloat(parts[7]) },
+        x_dir: { x: parseFloat(parts[8]), y: parseFloat(parts[9]), z: parseFloat(parts[10]) },
+        y_dir: { x: parseFloat(parts[11]), y: parseFloat(parts[12]), z: parseFloat(parts[13]) },
+      };
+      if (align !== 'left') op.alignment = align;
+      return op;
+    }
+
     default:
       throw new CompactParseError(lineNum, `Unknown opcode: ${opcode}`);
   }
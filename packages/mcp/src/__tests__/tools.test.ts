@@ -28,7 +28,7 @@ describe("create_cad_document", () => {
     expect(result.content[0].type).toBe("text");
 
     const doc = JSON.parse(result.content[0].text);
-    expect(doc.version).toBe("0.1");
+    expect(doc.version).toBe("0.2");
     expect(doc.roots).toHaveLength(1);
     expect(Object.keys(doc.nodes)).toHaveLength(1);
   });
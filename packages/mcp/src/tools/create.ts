@@ -1082,15 +1082,17 @@ function createPartBaseNode(
         name: null,
         op: {
           type: "Sweep",
-          sketch: sketchId,
+          profile: sketchId,
           path: {
             type: "Line",
             start: path.start,
             end: path.end,
           },
-          twist_angle: twist_deg ? (twist_deg * Math.PI) / 180 : undefined,
-          scale_start,
-          scale_end,
+          options: {
+            twist_angle: twist_deg ? (twist_deg * Math.PI) / 180 : undefined,
+            scale_start,
+            scale_end,
+          },
         },
       };
     } else {
@@ -1101,7 +1103,7 @@ function createPartBaseNode(
         name: null,
         op: {
           type: "Sweep",
-          sketch: sketchId,
+          profile: sketchId,
           path: {
             type: "Helix",
             radius: path.radius,
@@ -1109,9 +1111,11 @@ function createPartBaseNode(
             height: path.height,
             turns,
           },
-          twist_angle: twist_deg ? (twist_deg * Math.PI) / 180 : undefined,
-          scale_start,
-          scale_end,
+          options: {
+            twist_angle: twist_deg ? (twist_deg * Math.PI) / 180 : undefined,
+            scale_start,
+            scale_end,
+          },
         },
       };
     }
@@ -1129,7 +1133,7 @@ function createPartBaseNode(
       name: null,
       op: {
         type: "Loft",
-        sketches: sketchIds,
+        profiles: sketchIds,
         closed,
       },
     };
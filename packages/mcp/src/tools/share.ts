@@ -3,7 +3,7 @@
  */
 
 import type { Document } from "@vcad/ir";
-import { fromCompact, toCompact } from "@vcad/ir";
+import { fromCompact, migrateDocument, toCompact } from "@vcad/ir";
 import { gzipSync } from "node:zlib";
 
 interface ShareInput {
@@ -43,7 +43,7 @@ function base64urlEncode(data: Buffer): string {
 function parseDocument(input: string): Document {
   const trimmed = input.trim();
   if (trimmed.startsWith("{")) {
-    return JSON.parse(trimmed) as Document;
+    return migrateDocument(JSON.parse(trimmed) as Document);
   }
   if (trimmed.startsWith("#")) {
     return fromCompact(trimmed);